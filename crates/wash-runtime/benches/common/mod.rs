@@ -103,6 +103,7 @@ impl BenchHost {
             .host
             .workload_stop(WorkloadStopRequest {
                 workload_id: self.workload_id,
+                ..Default::default()
             })
             .await;
         let _ = self.host.stop().await;
@@ -161,6 +162,7 @@ pub async fn start_backend_host(flavor: Flavor) -> anyhow::Result<BenchHost> {
             pool_size: 0,
             max_invocations: 0,
             max_concurrency: 1,
+            ..Default::default()
         }],
         host_interfaces: http_host_interfaces(host),
         volumes: vec![],
@@ -183,6 +185,7 @@ pub async fn start_service_host() -> anyhow::Result<BenchHost> {
                 ..LocalResources::default()
             },
             max_restarts: 0,
+            ..Default::default()
         }),
         components: vec![],
         host_interfaces: http_host_interfaces(host),
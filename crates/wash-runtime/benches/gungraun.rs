@@ -84,6 +84,7 @@ fn setup_warm(flavor: Flavor) -> Warm {
                     pool_size: 0,
                     max_invocations: 0,
                     max_concurrency: 1,
+                    ..Default::default()
                 }],
                 host_interfaces: http_host_interfaces(flavor.host_header()),
                 volumes: vec![],
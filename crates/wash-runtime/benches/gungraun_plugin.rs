@@ -230,6 +230,7 @@ impl Call {
                     pool_size: 0,
                     max_invocations: 0,
                     max_concurrency: 0,
+                    ..Default::default()
                 }],
                 host_interfaces: self.host_interfaces(),
                 volumes: vec![],
@@ -358,7 +359,10 @@ fn drop_warm(warm: Warm) {
     rt.block_on(async {
         for workload_id in workload_ids {
             let _ = host
-                .workload_stop(WorkloadStopRequest { workload_id })
+                .workload_stop(WorkloadStopRequest {
+                    workload_id,
+                    ..Default::default()
+                })
                 .await;
         }
         let _ = host.stop().await;
@@ -84,6 +84,7 @@ async fn start_warm_host(flavor: Flavor) -> anyhow::Result<WarmHost> {
                 pool_size: 0,
                 max_invocations: 0,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: http_host_interfaces(flavor.host_header()),
             volumes: vec![],
@@ -292,6 +293,7 @@ async fn start_sleeper_host(
                 pool_size,
                 max_invocations: 0,
                 max_concurrency,
+                ..Default::default()
             }],
             host_interfaces: http_host_interfaces(host_header),
             volumes: vec![],
@@ -0,0 +1,347 @@
+//! Opt-in on-disk cache of compiled components, so a restarted host loads what
+//! it compiled last time instead of running Cranelift over every image again.
+//!
+//! Entries live at `<dir>/<engine>/<component>.cwasm`:
+//!
+//! - `<engine>` hashes the engine's
+//!   [`precompile_compatibility_hash`](wasmtime::Engine::precompile_compatibility_hash):
+//!   the wasmtime version, the compilation target and every codegen setting,
+//!   including the enabled [`WasmProposal`](super::WasmProposal)s. An engine
+//!   that compiles differently looks in a different directory, so it never
+//!   meets an artifact it can't run. Opening the cache removes the directories
+//!   of other engines that no host has used for [`STALE_ENGINE_AGE`], so an
+//!   upgrade does not leave the last version's artifacts behind for good.
+//! - `<component>` hashes the component's digest, the same key the in-memory
+//!   cache uses.
+//!
+//! Each file is a magic, the SHA-256 of the serialized component and then the
+//! component itself. A file that fails that check, or that wasmtime still
+//! refuses to deserialize, is deleted and the component compiled afresh. The
+//! check catches truncation and corruption, not tampering: loading an entry
+//! runs the native code it contains, so the directory must be writable by the
+//! host alone.
+//!
+//! The cache is best effort. Failing to read or write an entry is logged and
+//! the component compiled in memory as if there were no cache. Every operation
+//! is blocking file I/O, so callers run it off the async executor, alongside
+//! the compilation it saves or records.
+
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Context as _;
+use sha2::{Digest as _, Sha256};
+use tracing::{debug, warn};
+use wasmtime::component::Component;
+
+/// Leads every entry; bumped if the file layout ever changes.
+const MAGIC: &[u8; 8] = b"WCCWASM1";
+
+/// Length of the SHA-256 checksum following [`MAGIC`].
+const CHECKSUM_LEN: usize = 32;
+
+/// How long another engine's directory may go unused before opening the cache
+/// removes it. An engine's directory counts as used whenever a host opens it or
+/// loads or stores an entry in it.
+const STALE_ENGINE_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// One engine's directory of compiled components.
+#[derive(Debug)]
+pub(crate) struct DiskCache {
+    dir: PathBuf,
+}
+
+impl DiskCache {
+    /// Open the directory under `root` that holds components compiled by
+    /// `engine`, creating it if needed.
+    pub(crate) fn open(root: &Path, engine: &wasmtime::Engine) -> anyhow::Result<Self> {
+        let mut hasher = Sha256Hasher::default();
+        engine.precompile_compatibility_hash().hash(&mut hasher);
+        let dir = root.join(format!("{:x}", hasher.0.finalize()));
+        std::fs::create_dir_all(&dir).with_context(|| {
+            format!(
+                "failed to create compilation cache directory {}",
+                dir.display()
+            )
+        })?;
+        let cache = Self { dir };
+        cache.touch();
+        prune_stale_engines(root, &cache.dir, STALE_ENGINE_AGE);
+        Ok(cache)
+    }
+
+    /// Mark this engine's directory used, so other hosts opening the cache
+    /// leave it alone. Storing an entry does the same through the directory's
+    /// own modification time.
+    fn touch(&self) {
+        if let Err(e) =
+            std::fs::File::open(&self.dir).and_then(|dir| dir.set_modified(SystemTime::now()))
+        {
+            debug!(path = %self.dir.display(), error = %e, "failed to mark compilation cache used");
+        }
+    }
+
+    /// The component compiled from the image `digest` names, if one was
+    /// stored and is still intact.
+    pub(crate) fn load(&self, engine: &wasmtime::Engine, digest: &str) -> Option<Component> {
+        let path = self.path(digest);
+        let file = match std::fs::read(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "failed to read compiled component");
+                return None;
+            }
+        };
+        let Some(serialized) = verified(&file) else {
+            warn!(path = %path.display(), "discarding corrupt compiled component");
+            discard(&path);
+            return None;
+        };
+        // SAFETY: `deserialize` trusts the bytes to be a component serialized
+        // by a compatible engine. The checksum rules out a torn or corrupted
+        // write, the directory is keyed by the engine's compatibility hash,
+        // and wasmtime re-checks the version and settings recorded in the
+        // artifact itself. Keeping the directory writable only by the host is
+        // the operator's part, as documented on the flag that enables it.
+        #[allow(unsafe_code)]
+        match unsafe { Component::deserialize(engine, serialized) } {
+            Ok(component) => {
+                debug!(path = %path.display(), "loaded compiled component from disk");
+                self.touch();
+                Some(component)
+            }
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "discarding incompatible compiled component");
+                discard(&path);
+                None
+            }
+        }
+    }
+
+    /// Store `component`, compiled from the image `digest` names, for the
+    /// next engine like this one to load.
+    pub(crate) fn store(&self, digest: &str, component: &Component) {
+        if let Err(e) = self.try_store(digest, component) {
+            warn!(
+                error = format!("{e:#}"),
+                "failed to write compiled component to disk"
+            );
+        }
+    }
+
+    fn try_store(&self, digest: &str, component: &Component) -> anyhow::Result<()> {
+        let serialized = component
+            .serialize()
+            .map_err(anyhow::Error::from)
+            .context("failed to serialize component")?;
+        let mut file = Vec::with_capacity(MAGIC.len() + CHECKSUM_LEN + serialized.len());
+        file.extend_from_slice(MAGIC);
+        file.extend_from_slice(&Sha256::digest(&serialized));
+        file.extend_from_slice(&serialized);
+
+        // Written aside and renamed into place, so a concurrent reader (another
+        // host sharing the directory) never sees a partial entry.
+        let path = self.path(digest);
+        let staging = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4().simple()));
+        std::fs::write(&staging, &file)
+            .with_context(|| format!("failed to write {}", staging.display()))?;
+        std::fs::rename(&staging, &path).with_context(|| {
+            discard(&staging);
+            format!("failed to move compiled component to {}", path.display())
+        })?;
+        debug!(path = %path.display(), "stored compiled component on disk");
+        Ok(())
+    }
+
+    fn path(&self, digest: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:x}.cwasm", Sha256::digest(digest.as_bytes())))
+    }
+}
+
+/// Remove the directories under `root` of engines other than the one at `own`
+/// that have gone unused for longer than `max_age`. Only directories named
+/// like an engine's are considered; a failure is logged and the rest pruned.
+fn prune_stale_engines(root: &Path, own: &Path, max_age: Duration) {
+    let entries = match std::fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!(path = %root.display(), error = %e, "failed to list compilation cache");
+            return;
+        }
+    };
+    let now = SystemTime::now();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_engine_dir = entry.file_name().to_str().is_some_and(|name| {
+            name.len() == 64 && name.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        });
+        if path == own || !is_engine_dir {
+            continue;
+        }
+        let stale = entry.metadata().is_ok_and(|metadata| {
+            metadata.is_dir()
+                && metadata.modified().is_ok_and(|modified| {
+                    now.duration_since(modified).unwrap_or_default() > max_age
+                })
+        });
+        if !stale {
+            continue;
+        }
+        match std::fs::remove_dir_all(&path) {
+            Ok(()) => debug!(path = %path.display(), "removed stale compilation cache"),
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "failed to remove stale compilation cache")
+            }
+        }
+    }
+}
+
+/// The serialized component in an entry, if its header and checksum hold.
+fn verified(file: &[u8]) -> Option<&[u8]> {
+    let (checksum, serialized) = file
+        .strip_prefix(MAGIC.as_slice())?
+        .split_at_checked(CHECKSUM_LEN)?;
+    (Sha256::digest(serialized).as_slice() == checksum).then_some(serialized)
+}
+
+/// Remove an entry that can't be used, so the next load doesn't trip on it.
+fn discard(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path)
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        warn!(path = %path.display(), error = %e, "failed to remove compiled component");
+    }
+}
+
+/// Feeds [`Hash`] output into SHA-256, for a key that stays the same across
+/// processes and toolchains where `DefaultHasher` makes no such promise.
+#[derive(Default)]
+struct Sha256Hasher(Sha256);
+
+impl Hasher for Sha256Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+        digest
+            .first_chunk::<8>()
+            .map_or(0, |bytes| u64::from_le_bytes(*bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: &str = "sha256:0123456789abcdef";
+
+    fn compiled(engine: &wasmtime::Engine) -> Component {
+        Component::new(engine, "(component)").unwrap()
+    }
+
+    fn entries(dir: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect()
+    }
+
+    #[test]
+    fn a_stored_component_loads_back() {
+        let root = tempfile::tempdir().unwrap();
+        let engine = wasmtime::Engine::default();
+        let cache = DiskCache::open(root.path(), &engine).unwrap();
+
+        assert!(cache.load(&engine, DIGEST).is_none());
+        cache.store(DIGEST, &compiled(&engine));
+        assert!(cache.load(&engine, DIGEST).is_some());
+        assert!(cache.load(&engine, "sha256:other").is_none());
+
+        // Only the entry itself remains: no staging files left behind.
+        assert_eq!(entries(&cache.dir), vec![cache.path(DIGEST)]);
+    }
+
+    #[test]
+    fn a_corrupt_entry_is_discarded() {
+        let root = tempfile::tempdir().unwrap();
+        let engine = wasmtime::Engine::default();
+        let cache = DiskCache::open(root.path(), &engine).unwrap();
+        cache.store(DIGEST, &compiled(&engine));
+
+        let path = cache.path(DIGEST);
+        let mut file = std::fs::read(&path).unwrap();
+        let last = file.last_mut().unwrap();
+        *last = last.wrapping_add(1);
+        std::fs::write(&path, &file).unwrap();
+
+        assert!(cache.load(&engine, DIGEST).is_none());
+        assert!(!path.exists(), "the corrupt entry is removed");
+
+        // A truncated one too.
+        cache.store(DIGEST, &compiled(&engine));
+        std::fs::write(&path, MAGIC).unwrap();
+        assert!(cache.load(&engine, DIGEST).is_none());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn engines_that_compile_differently_do_not_share_entries() {
+        let root = tempfile::tempdir().unwrap();
+        let engine = wasmtime::Engine::default();
+        let mut config = wasmtime::Config::new();
+        config.consume_fuel(true);
+        let fueled = wasmtime::Engine::new(&config).unwrap();
+
+        let cache = DiskCache::open(root.path(), &engine).unwrap();
+        let fueled_cache = DiskCache::open(root.path(), &fueled).unwrap();
+        assert_ne!(cache.dir, fueled_cache.dir);
+        assert_eq!(
+            DiskCache::open(root.path(), &wasmtime::Engine::default())
+                .unwrap()
+                .dir,
+            cache.dir,
+            "the same settings find the same directory"
+        );
+
+        cache.store(DIGEST, &compiled(&engine));
+        assert!(fueled_cache.load(&fueled, DIGEST).is_none());
+    }
+
+    #[test]
+    fn opening_prunes_only_stale_engine_directories() {
+        let root = tempfile::tempdir().unwrap();
+        let age = |path: &Path, age: Duration| {
+            std::fs::File::open(path)
+                .unwrap()
+                .set_modified(SystemTime::now() - age)
+                .unwrap();
+        };
+        let stale = root.path().join("a".repeat(64));
+        let recent = root.path().join("b".repeat(64));
+        let unrelated = root.path().join("backups");
+        for dir in [&stale, &recent, &unrelated] {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("entry.cwasm"), b"compiled").unwrap();
+        }
+        age(&stale, STALE_ENGINE_AGE * 2);
+        age(&unrelated, STALE_ENGINE_AGE * 2);
+        age(&recent, STALE_ENGINE_AGE / 2);
+
+        let engine = wasmtime::Engine::default();
+        let cache = DiskCache::open(root.path(), &engine).unwrap();
+        assert!(!stale.exists(), "an engine unused for too long is removed");
+        assert!(recent.exists(), "an engine in recent use is kept");
+        assert!(unrelated.exists(), "what isn't an engine directory is kept");
+
+        // Reopening does not prune the engine's own directory, however old.
+        cache.store(DIGEST, &compiled(&engine));
+        age(&cache.dir, STALE_ENGINE_AGE * 2);
+        let reopened = DiskCache::open(root.path(), &engine).unwrap();
+        assert!(reopened.load(&engine, DIGEST).is_some());
+    }
+}
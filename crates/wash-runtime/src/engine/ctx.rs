@@ -77,6 +77,22 @@ pub struct SharedCtx {
     /// a caller store leaves it `None` and holds opaque proxies instead. See
     /// [`crate::engine::store::resource_bridge`].
     pub resource_registry: Option<crate::engine::store::resource_bridge::ResourceRegistry>,
+    /// Enforces the active component's memory limit once installed on the
+    /// store by [`crate::engine::limits::install`].
+    pub(crate) limiter: crate::engine::limits::StoreLimiter,
+    /// Times the calls running in this store against the active component's
+    /// `max_execution_time`, when it has one. See [`crate::engine::epoch`].
+    pub(crate) execution_clock: Option<Arc<crate::engine::epoch::ExecutionClock>>,
+    /// The call a store built for a single call was built for, timed from the
+    /// store's creation. A store that outlives its first call drops this and
+    /// times each call it serves instead.
+    pub(crate) store_call_timer: Option<crate::engine::epoch::CallTimer>,
+    /// Counts the calls running in this store against the active component.
+    /// See [`crate::engine::stats`].
+    pub(crate) call_stats: Option<Arc<crate::engine::stats::ComponentStats>>,
+    /// The call a store built for a single call was built for, counted for as
+    /// long as the store lives; like `store_call_timer`.
+    pub(crate) store_call: Option<crate::engine::stats::InFlightCall>,
 }
 
 /// The identity of whoever is invoking a host component plugin, used to
@@ -101,6 +117,11 @@ impl SharedCtx {
             contexts: Default::default(),
             exporter_instances: Default::default(),
             resource_registry: None,
+            limiter: crate::engine::limits::StoreLimiter::unlimited(),
+            execution_clock: None,
+            store_call_timer: None,
+            call_stats: None,
+            store_call: None,
         }
     }
 
@@ -0,0 +1,247 @@
+//! Wall-clock deadlines for guest calls, through wasmtime's epochs.
+//!
+//! Every [`crate::engine::Engine`] compiles with epoch interruption and owns an
+//! [`EpochTicker`] thread that advances the epoch once per tick. A store for a
+//! component or service with a `max_execution_time` gets an epoch callback that
+//! runs on every tick while guest code is executing and traps the store with
+//! [`ExecutionTimeExceeded`] once a call in it has run for longer than that.
+//! That is what interrupts a synchronous guest stuck in a loop, which no
+//! host-side timeout can reach: the guest never yields back to the host for
+//! one to fire.
+//!
+//! The deadline is per call, not per store. A store built for one call starts
+//! its clock when it is built. A warm instance and a service's shared instance
+//! outlive their calls, so each call they admit takes a [`CallTimer`] of its
+//! own for as long as it runs; a service's own `wasi:cli/run` is not a call
+//! and never takes one, so only the HTTP requests and messages its trigger
+//! service serves are timed. The trap still takes the whole store with it,
+//! exactly as any other guest trap does, so every call in flight on a shared
+//! instance fails alongside the overrunning one.
+//!
+//! The same callback time-slices a store whose component sets a `cpu_limit`
+//! (see [`crate::engine::limits`]): after that many ticks of guest execution
+//! the store yields back to the async executor, then carries on.
+//!
+//! A store with neither gets a deadline far enough out that it never fires.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use wasmtime::UpdateDeadline;
+
+/// How often the engine's epoch advances unless configured otherwise. Bounds
+/// how far past its `max_execution_time` a call can run before it is trapped.
+pub const DEFAULT_EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// The epoch deadline of a store with no `max_execution_time`: at one tick per
+/// millisecond this is still hundreds of millions of years out, and it leaves
+/// headroom for the engine's current epoch to be added to it.
+const NO_DEADLINE: u64 = u64::MAX / 2;
+
+/// Advances an engine's epoch once per tick on a thread of its own, for as
+/// long as the ticker lives.
+pub(crate) struct EpochTicker {
+    /// Dropping this ends the thread: its `recv_timeout` sees the disconnect.
+    _stop: std::sync::mpsc::Sender<()>,
+}
+
+impl EpochTicker {
+    pub(crate) fn spawn(engine: &wasmtime::Engine, tick: Duration) -> anyhow::Result<Self> {
+        let (stop, stopped) = std::sync::mpsc::channel::<()>();
+        // Weak, so a ticker that outlives its engine does not keep it alive.
+        let engine = engine.weak();
+        std::thread::Builder::new()
+            .name("wasmtime-epoch".to_string())
+            .spawn(move || {
+                while let Err(std::sync::mpsc::RecvTimeoutError::Timeout) =
+                    stopped.recv_timeout(tick)
+                {
+                    let Some(engine) = engine.upgrade() else {
+                        break;
+                    };
+                    engine.increment_epoch();
+                }
+            })?;
+        Ok(Self { _stop: stop })
+    }
+}
+
+/// A guest call ran past its component's `max_execution_time` and was
+/// interrupted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionTimeExceeded {
+    pub component_id: Arc<str>,
+    pub max_execution_time: Duration,
+}
+
+impl std::fmt::Display for ExecutionTimeExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "execution time exceeded: component {} ran for longer than its {:?} limit",
+            self.component_id, self.max_execution_time
+        )
+    }
+}
+
+impl std::error::Error for ExecutionTimeExceeded {}
+
+/// The start times of the calls running in one store, against the component's
+/// `max_execution_time`.
+pub(crate) struct ExecutionClock {
+    component_id: Arc<str>,
+    max_execution_time: Duration,
+    calls: Mutex<HashMap<u64, Instant>>,
+    next_call: AtomicU64,
+}
+
+impl ExecutionClock {
+    pub(crate) fn new(component_id: Arc<str>, max_execution_time: Duration) -> Self {
+        Self {
+            component_id,
+            max_execution_time,
+            calls: Mutex::default(),
+            next_call: AtomicU64::new(0),
+        }
+    }
+
+    /// Start timing a call. It is timed until the returned timer is dropped.
+    pub(crate) fn begin(self: &Arc<Self>) -> CallTimer {
+        let id = self.next_call.fetch_add(1, Ordering::Relaxed);
+        self.calls
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(id, Instant::now());
+        CallTimer {
+            clock: Arc::clone(self),
+            id,
+        }
+    }
+
+    /// Whether any call in the store has run past the deadline.
+    fn overrun(&self) -> bool {
+        self.calls
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .values()
+            .any(|started| started.elapsed() > self.max_execution_time)
+    }
+
+    fn exceeded(&self) -> ExecutionTimeExceeded {
+        ExecutionTimeExceeded {
+            component_id: self.component_id.clone(),
+            max_execution_time: self.max_execution_time,
+        }
+    }
+}
+
+/// One call's entry on an [`ExecutionClock`], removed when dropped.
+pub(crate) struct CallTimer {
+    clock: Arc<ExecutionClock>,
+    id: u64,
+}
+
+impl Drop for CallTimer {
+    fn drop(&mut self) {
+        self.clock
+            .calls
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(&self.id);
+    }
+}
+
+/// Sets `store`'s epoch deadline: checked on every tick against `clock` when
+/// there is one, yielding every `time_slice` ticks when that is set, and never
+/// reached otherwise. Must run before the store executes any guest code, which
+/// would otherwise trap at once.
+pub(crate) fn install<T: 'static>(
+    store: &mut wasmtime::Store<T>,
+    clock: Option<Arc<ExecutionClock>>,
+    time_slice: Option<u64>,
+) {
+    match (clock, time_slice) {
+        (None, None) => store.set_epoch_deadline(NO_DEADLINE),
+        (None, Some(slice)) => {
+            store.set_epoch_deadline(slice);
+            store.epoch_deadline_async_yield_and_update(slice);
+        }
+        (Some(clock), time_slice) => {
+            let mut ticks = 0;
+            store.set_epoch_deadline(1);
+            store.epoch_deadline_callback(move |_store| {
+                if clock.overrun() {
+                    let exceeded = clock.exceeded();
+                    tracing::warn!(component_id = %exceeded.component_id, "{exceeded}");
+                    return Err(wasmtime::Error::new(exceeded));
+                }
+                ticks += 1;
+                match time_slice {
+                    Some(slice) if ticks >= slice => {
+                        ticks = 0;
+                        Ok(UpdateDeadline::Yield(1))
+                    }
+                    _ => Ok(UpdateDeadline::Continue(1)),
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn only_calls_in_flight_are_timed() {
+        let clock = Arc::new(ExecutionClock::new(
+            Arc::from("c-1"),
+            Duration::from_millis(20),
+        ));
+        assert!(!clock.overrun());
+
+        let finished = clock.begin();
+        let running = clock.begin();
+        std::thread::sleep(Duration::from_millis(30));
+        drop(finished);
+        assert!(clock.overrun());
+
+        // A call that started after the deadline would have passed for the
+        // earlier ones is measured from its own start.
+        drop(running);
+        let _fresh = clock.begin();
+        assert!(!clock.overrun());
+    }
+
+    #[test]
+    fn the_ticker_advances_the_epoch_until_dropped() {
+        let mut config = wasmtime::Config::new();
+        config.epoch_interruption(true);
+        let engine = wasmtime::Engine::new(&config).unwrap();
+        let ticker = EpochTicker::spawn(&engine, Duration::from_millis(1)).unwrap();
+
+        let mut store = wasmtime::Store::new(&engine, ());
+        let clock = Arc::new(ExecutionClock::new(
+            Arc::from("c-1"),
+            Duration::from_millis(10),
+        ));
+        let _call = clock.begin();
+        install(&mut store, Some(clock), None);
+
+        // An infinite loop is interrupted rather than left to spin.
+        let module =
+            wasmtime::Module::new(&engine, r#"(module (func (export "spin") (loop (br 0))))"#)
+                .unwrap();
+        let instance = wasmtime::Instance::new(&mut store, &module, &[]).unwrap();
+        let spin = instance
+            .get_typed_func::<(), ()>(&mut store, "spin")
+            .unwrap();
+        let err = spin.call(&mut store, ()).unwrap_err();
+        let exceeded = err.downcast_ref::<ExecutionTimeExceeded>().unwrap();
+        assert_eq!(exceeded.max_execution_time, Duration::from_millis(10));
+        drop(ticker);
+    }
+}
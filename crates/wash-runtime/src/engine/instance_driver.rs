@@ -30,13 +30,20 @@
 //!    many, after which it drains and its store drops. A call that times out
 //!    or fails in the host mid-call retires the instance the same way: the
 //!    guest work cannot be cancelled from the host, so draining and dropping
-//!    the store is what ends it.
+//!    the store is what ends it. A guest that is busy rather than waiting
+//!    never lets that happen; the component's `max_execution_time` is what
+//!    interrupts it, trapping the store (see [`crate::engine::epoch`]).
 //!
 //! A retired instance ends its own run loop as soon as its last call finishes,
 //! rather than waiting for the pool to notice. That matters for the timed-out
 //! call: dropping the store is what ends the guest work it left running, so it
 //! must not wait on traffic that may never come.
 //!
+//! When the pool lets go of an instance — its workload is stopping — the
+//! instance stops taking calls but finishes the ones it has before its store
+//! drops, so a stop drains rather than failing them. Halting the pool ends it
+//! at once, which is what a stop falls back to once its drain deadline passes.
+//!
 //! The cost of sharing an instance is that a guest trap takes the whole store
 //! with it, so every call in flight on that instance fails rather than just
 //! one. That is bounded by `max_concurrency`, and by `1/pool_size` of the pool.
@@ -44,12 +51,15 @@
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
+use tokio_util::sync::CancellationToken;
 use wasmtime::component::{Accessor, AccessorTask, ComponentExportIndex, Instance, Val};
 use wasmtime::error::Context as _;
 use wasmtime_wasi_http::p3::bindings::Service;
 
 use crate::engine::ctx::SharedCtx;
+use crate::engine::epoch::{CallTimer, ExecutionClock};
 use crate::engine::instance_pool::ComponentInstance;
+use crate::engine::stats::{ComponentStats, InFlightCall};
 use crate::host::http::ServiceHttpJob;
 use crate::host::trigger_service::HttpTask;
 
@@ -110,6 +120,11 @@ pub(crate) struct PoolSlot {
     state: Arc<DriverState>,
     /// Frees this call's slot when the slot is dropped, however the task ends.
     _in_flight: InFlightGuard,
+    /// Times this call against the component's `max_execution_time` for as
+    /// long as it runs.
+    _call_timer: Option<CallTimer>,
+    /// Counts this call as in flight in the component's stats.
+    _call: Option<InFlightCall>,
 }
 
 impl PoolSlot {
@@ -246,11 +261,18 @@ impl InstanceDriver {
         instance: ComponentInstance,
         max_concurrency: usize,
         max_invocations: Option<usize>,
+        halt: CancellationToken,
     ) -> Self {
         let ComponentInstance {
             mut store,
             instance,
         } = instance;
+        // The store was timed as a single call while it was instantiated.
+        // From here on it serves many, and each is timed on its own.
+        store.data_mut().store_call_timer = None;
+        store.data_mut().store_call = None;
+        let execution_clock = store.data().execution_clock.clone();
+        let call_stats = store.data().call_stats.clone();
         let (tx, mut rx) =
             tokio::sync::mpsc::channel::<(InstanceJob, InFlightGuard)>(max_concurrency.max(1));
         let state = Arc::new(DriverState {
@@ -269,8 +291,9 @@ impl InstanceDriver {
         tokio::spawn(async move {
             // One `run_concurrent` for the life of the instance. Each call is
             // spawned onto it, so calls overlap instead of taking the store in
-            // turn. It returns when the channel closes (the pool dropped the
-            // handle), when a retired instance has drained, or when the guest
+            // turn. It returns once the channel closes (the pool dropped the
+            // handle) and the calls in flight have finished, when a retired
+            // instance has drained, when the pool is halted, or when the guest
             // traps.
             let outcome = store
                 .run_concurrent(async |accessor| {
@@ -278,8 +301,19 @@ impl InstanceDriver {
                         let (job, guard) = tokio::select! {
                             received = rx.recv() => match received {
                                 Some(received) => received,
-                                None => break,
+                                // Let go of by the pool: take nothing more,
+                                // but drain what was taken before the store
+                                // drops with it.
+                                None => {
+                                    task_state.retire();
+                                    tokio::select! {
+                                        _ = task_state.drained.notified() => {}
+                                        _ = halt.cancelled() => {}
+                                    }
+                                    break;
+                                }
                             },
+                            _ = halt.cancelled() => break,
                             // Retired and drained: stopping here drops the
                             // store, which is what ends guest work a
                             // timed-out call left running.
@@ -302,9 +336,15 @@ impl InstanceDriver {
                                     service,
                                     req,
                                     resp_tx,
+                                    call: None,
+                                    call_timer: None,
                                     pool_slot: Some(PoolSlot {
                                         state: Arc::clone(&task_state),
                                         _in_flight: guard,
+                                        _call_timer: execution_clock
+                                            .as_ref()
+                                            .map(ExecutionClock::begin),
+                                        _call: call_stats.as_ref().map(ComponentStats::begin),
                                     }),
                                 })
                             }
@@ -314,6 +354,10 @@ impl InstanceDriver {
                                 slot: PoolSlot {
                                     state: Arc::clone(&task_state),
                                     _in_flight: guard,
+                                    _call_timer: execution_clock
+                                        .as_ref()
+                                        .map(ExecutionClock::begin),
+                                    _call: call_stats.as_ref().map(ComponentStats::begin),
                                 },
                             }),
                         };
@@ -328,6 +372,9 @@ impl InstanceDriver {
                 // died with it. The pool reaps the handle and the next call
                 // starts a fresh instance.
                 tracing::error!(err = ?e, "pooled instance faulted; its in-flight calls failed");
+                if let Some(stats) = &call_stats {
+                    stats.record_restart();
+                }
             }
         });
 
@@ -29,7 +29,9 @@
 //!    flight on that instance; the pool reaps it and the next call starts a
 //!    fresh one. A call that times out or fails in the host mid-call retires
 //!    the instance the same way — the guest work cannot be cancelled from the
-//!    host, so draining and dropping the store is what ends it.
+//!    host, so draining and dropping the store is what ends it. A call that
+//!    runs past the component's `max_execution_time` is interrupted and traps,
+//!    so a guest stuck in a loop ends as a trap rather than never finishing.
 //!
 //! Two further consequences of an instance outliving a call, both of which a
 //! component opts into along with the pooling:
@@ -47,6 +49,7 @@ use std::collections::{BTreeMap, HashSet};
 use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
 
+use tokio_util::sync::CancellationToken;
 use wasmtime::component::Instance;
 
 use crate::engine::ctx::SharedCtx;
@@ -210,6 +213,9 @@ pub(crate) struct InstancePool {
     /// they arrive over HTTP or from another component in the workload.
     drivers: Mutex<Vec<Arc<InstanceDriver>>>,
     policy: InstancePolicy,
+    /// Cancelled by [`InstancePool::halt`]; ends every instance's run loop,
+    /// including one installed after the pool was cleared.
+    halt: CancellationToken,
 }
 
 impl InstancePool {
@@ -217,6 +223,7 @@ impl InstancePool {
         Self {
             drivers: Mutex::new(Vec::new()),
             policy,
+            halt: CancellationToken::new(),
         }
     }
 
@@ -253,6 +260,9 @@ impl InstancePool {
         let Some((pool_size, _, _)) = self.limits() else {
             return Dispatch::Saturated(job);
         };
+        if self.halt.is_cancelled() {
+            return Dispatch::Saturated(job);
+        }
         let Ok(mut drivers) = self.drivers.lock() else {
             return Dispatch::Saturated(job);
         };
@@ -293,6 +303,9 @@ impl InstancePool {
         let Some((pool_size, max_invocations, max_concurrency)) = self.limits() else {
             return Err(job);
         };
+        if self.halt.is_cancelled() {
+            return Err(job);
+        }
         let Ok(mut drivers) = self.drivers.lock() else {
             return Err(job);
         };
@@ -307,26 +320,53 @@ impl InstancePool {
             instance,
             max_concurrency,
             max_invocations,
+            self.halt.clone(),
         ));
         drivers.push(Arc::clone(&driver));
         drop(drivers);
         driver.try_send(job)
     }
 
+    /// The most instances this component keeps warm; zero when it keeps none.
+    pub(crate) fn pool_size(&self) -> usize {
+        self.limits().map_or(0, |(pool_size, _, _)| pool_size)
+    }
+
+    /// Warm instances that are live and admitting calls right now. Instances
+    /// that faulted or are draining toward retirement are not counted, even
+    /// before the next offer reaps them.
+    pub(crate) fn warm_instances(&self) -> usize {
+        self.drivers.lock().map_or(0, |drivers| {
+            drivers
+                .iter()
+                .filter(|d| !(d.is_gone() || d.is_retired()))
+                .count()
+        })
+    }
+
     /// Whether this component keeps instances warm at all.
     pub(crate) fn warms_instances(&self) -> bool {
         self.policy.keeps_instances_warm()
     }
 
     /// Drop every warm instance, e.g. when the component is being shut down.
-    /// Dropping a driver's handle closes its channel, which ends its store's
-    /// run loop. This does not wait for a drain: calls still in flight on
-    /// those instances end with the store they were running on.
+    /// Dropping a driver's handle closes its channel: the instance takes no
+    /// more calls, finishes the ones in flight, and then ends its store's run
+    /// loop. This does not wait for that drain.
     pub(crate) fn clear(&self) {
         if let Ok(mut drivers) = self.drivers.lock() {
             drop(std::mem::take(&mut *drivers));
         }
     }
+
+    /// Drop every warm instance and end its run loop now, without draining:
+    /// calls still in flight on those instances end with their store. Warm
+    /// instances are refused from then on, so a late call is served from a
+    /// store of its own.
+    pub(crate) fn halt(&self) {
+        self.halt.cancel();
+        self.clear();
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,286 @@
+//! Runtime enforcement of a component's [`LocalResources`] limits.
+//!
+//! Every store built for a component call carries a [`StoreLimiter`] sized
+//! from the active component's `memory_limit_mb`. It caps the linear memory
+//! and table space of every instance in the store (the component and the
+//! components linked into it), and the number of instances, memories, and
+//! tables it may create. Growing past the memory limit traps the guest with a
+//! [`ResourceLimitExceeded`] and reports the workload failed through the
+//! host's [`WorkloadFailureSink`], so the breach shows up as a failure reason
+//! of its own instead of a generic trap.
+//!
+//! `cpu_limit` is the length of a guest's time slice, in ticks of the engine's
+//! epoch: a guest that has run that many ticks without returning to the host
+//! yields back to the async executor, so a busy component cannot monopolize an
+//! executor thread and the tasks sharing it keep being served. It is not a
+//! cap. A larger value gives the guest longer slices, and so a larger share of
+//! a contended thread, while a guest with the thread to itself still runs flat
+//! out. Slicing rides on the same epoch callback as `max_execution_time` (see
+//! [`crate::engine::epoch`]), so it works whether or not the engine consumes
+//! fuel.
+//!
+//! A limit of zero or below (the proto default, and `-1` in
+//! [`LocalResources::default`]) means unlimited.
+
+use std::sync::Arc;
+
+use crate::engine::ctx::SharedCtx;
+use crate::plugin::WorkloadFailureSink;
+use crate::types::LocalResources;
+
+/// Instances a single store may create. Each component instantiates a
+/// handful of core instances (its own plus adapters), and a store holds the
+/// active component and every component linked into it.
+const MAX_INSTANCES: usize = 1_000;
+/// Tables a single store may create.
+const MAX_TABLES: usize = 1_000;
+/// Linear memories a single store may create.
+const MAX_MEMORIES: usize = 1_000;
+
+/// A guest grew its memory or tables past its component's `memory_limit_mb`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResourceLimitExceeded {
+    pub component_id: Arc<str>,
+    pub limit_mb: u64,
+    /// The store-wide size, in bytes, the failed growth asked for.
+    pub requested_bytes: u64,
+}
+
+impl std::fmt::Display for ResourceLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "resource limit exceeded: component {} requested {} bytes of memory, over its {} MiB limit",
+            self.component_id, self.requested_bytes, self.limit_mb
+        )
+    }
+}
+
+impl std::error::Error for ResourceLimitExceeded {}
+
+/// The [`wasmtime::ResourceLimiter`] installed on every component store.
+///
+/// Memory and table growth is tracked store-wide, since a linked component's
+/// instances live in the caller's store and count against the caller's limit.
+/// Table elements are charged at a pointer's width each, which is what they
+/// cost wasmtime.
+pub struct StoreLimiter {
+    workload_id: Arc<str>,
+    component_id: Arc<str>,
+    /// `None` when the component has no memory limit.
+    memory_limit: Option<usize>,
+    /// Bytes of linear memory and table space currently granted.
+    used: usize,
+    /// Bytes granted by the last `*_growing` call, handed back if the growth
+    /// then fails.
+    pending: usize,
+    failure_sink: Option<WorkloadFailureSink>,
+    /// Set once the breach has been reported, so a guest that keeps trying is
+    /// reported once per store.
+    reported: bool,
+}
+
+impl StoreLimiter {
+    /// A limiter that enforces nothing beyond the instance, table, and memory
+    /// counts.
+    pub(crate) fn unlimited() -> Self {
+        Self {
+            workload_id: Arc::from(""),
+            component_id: Arc::from(""),
+            memory_limit: None,
+            used: 0,
+            pending: 0,
+            failure_sink: None,
+            reported: false,
+        }
+    }
+
+    pub(crate) fn new(
+        workload_id: Arc<str>,
+        component_id: Arc<str>,
+        local_resources: &LocalResources,
+        failure_sink: Option<WorkloadFailureSink>,
+    ) -> Self {
+        Self {
+            workload_id,
+            component_id,
+            memory_limit: memory_limit_bytes(local_resources.memory_limit_mb),
+            failure_sink,
+            ..Self::unlimited()
+        }
+    }
+
+    /// Grants a growth of one memory or table from `current` to `desired`
+    /// bytes if the store stays within its limit, and traps otherwise.
+    fn grow(&mut self, current: usize, desired: usize) -> wasmtime::Result<bool> {
+        let delta = desired.saturating_sub(current);
+        let requested = self.used.saturating_add(delta);
+        if let Some(limit) = self.memory_limit
+            && requested > limit
+        {
+            let breach = ResourceLimitExceeded {
+                component_id: self.component_id.clone(),
+                limit_mb: (limit >> 20) as u64,
+                requested_bytes: requested as u64,
+            };
+            if !self.reported {
+                self.reported = true;
+                tracing::warn!(
+                    workload_id = %self.workload_id,
+                    component_id = %self.component_id,
+                    "{breach}"
+                );
+                if let Some(sink) = &self.failure_sink {
+                    sink.report(self.workload_id.as_ref(), breach.to_string());
+                }
+            }
+            return Err(wasmtime::Error::new(breach));
+        }
+        self.used = requested;
+        self.pending = delta;
+        Ok(true)
+    }
+
+    fn grow_failed(&mut self) {
+        self.used = self.used.saturating_sub(self.pending);
+        self.pending = 0;
+    }
+}
+
+impl wasmtime::ResourceLimiter for StoreLimiter {
+    fn memory_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        self.grow(current, desired)
+    }
+
+    fn memory_grow_failed(&mut self, _error: wasmtime::Error) -> wasmtime::Result<()> {
+        self.grow_failed();
+        Ok(())
+    }
+
+    fn table_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        let width = std::mem::size_of::<usize>();
+        self.grow(current.saturating_mul(width), desired.saturating_mul(width))
+    }
+
+    fn table_grow_failed(&mut self, _error: wasmtime::Error) -> wasmtime::Result<()> {
+        self.grow_failed();
+        Ok(())
+    }
+
+    fn instances(&self) -> usize {
+        MAX_INSTANCES
+    }
+
+    fn tables(&self) -> usize {
+        MAX_TABLES
+    }
+
+    fn memories(&self) -> usize {
+        MAX_MEMORIES
+    }
+}
+
+/// Installs the store's [`StoreLimiter`]. Must run before anything is
+/// instantiated into the store.
+pub(crate) fn install(store: &mut wasmtime::Store<SharedCtx>) {
+    store.limiter(|ctx| &mut ctx.limiter);
+}
+
+/// The epoch ticks a guest with this `cpu_limit` runs before yielding, if it
+/// is sliced at all.
+pub(crate) fn time_slice(cpu_limit: i32) -> Option<u64> {
+    u64::try_from(cpu_limit).ok().filter(|ticks| *ticks > 0)
+}
+
+fn memory_limit_bytes(memory_limit_mb: i32) -> Option<usize> {
+    usize::try_from(memory_limit_mb)
+        .ok()
+        .filter(|mb| *mb > 0)
+        .map(|mb| mb.saturating_mul(1 << 20))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use wasmtime::ResourceLimiter;
+
+    const MIB: usize = 1 << 20;
+
+    fn limiter(
+        memory_limit_mb: i32,
+    ) -> (
+        StoreLimiter,
+        tokio::sync::mpsc::UnboundedReceiver<crate::plugin::WorkloadFailure>,
+    ) {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let local_resources = LocalResources {
+            memory_limit_mb,
+            ..Default::default()
+        };
+        let limiter = StoreLimiter::new(
+            Arc::from("wl-1"),
+            Arc::from("c-1"),
+            &local_resources,
+            Some(WorkloadFailureSink::new(tx)),
+        );
+        (limiter, rx)
+    }
+
+    #[test]
+    fn non_positive_limits_are_unlimited() {
+        assert_eq!(memory_limit_bytes(-1), None);
+        assert_eq!(memory_limit_bytes(0), None);
+        assert_eq!(memory_limit_bytes(2), Some(2 * MIB));
+        assert_eq!(time_slice(-1), None);
+        assert_eq!(time_slice(0), None);
+        assert_eq!(time_slice(3), Some(3));
+
+        let (mut unlimited, _rx) = limiter(-1);
+        assert!(unlimited.memory_growing(0, 1 << 40, None).unwrap());
+    }
+
+    #[test]
+    fn memory_is_limited_across_the_store() {
+        let (mut limiter, mut rx) = limiter(2);
+        assert!(limiter.memory_growing(0, MIB, None).unwrap());
+        // A second memory in the same store shares the budget.
+        assert!(limiter.memory_growing(0, MIB, None).unwrap());
+        let err = limiter.memory_growing(MIB, MIB + 65536, None).unwrap_err();
+
+        let breach = err.downcast_ref::<ResourceLimitExceeded>().unwrap();
+        assert_eq!(breach.limit_mb, 2);
+        assert_eq!(breach.requested_bytes, (2 * MIB + 65536) as u64);
+        let failure = rx.try_recv().unwrap();
+        assert_eq!(failure.workload_id, "wl-1");
+        assert!(failure.reason.starts_with("resource limit exceeded"));
+
+        // Trying again traps again, but is reported once.
+        assert!(limiter.memory_growing(MIB, 2 * MIB, None).is_err());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn failed_growth_is_returned_to_the_budget() {
+        let (mut limiter, _rx) = limiter(1);
+        assert!(limiter.memory_growing(0, MIB, None).unwrap());
+        limiter
+            .memory_grow_failed(wasmtime::format_err!("out of memory"))
+            .unwrap();
+        assert!(
+            limiter
+                .table_growing(0, MIB / std::mem::size_of::<usize>(), None)
+                .unwrap()
+        );
+    }
+}
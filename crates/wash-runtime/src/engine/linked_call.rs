@@ -34,15 +34,18 @@ use wasmtime_wasi::WasiCtxBuilder;
 #[cfg(feature = "wasi-tls")]
 use crate::engine::ctx::SharedTlsProvider;
 use crate::engine::ctx::{AccessorActiveCtxGuard, Ctx, SharedCtx, StoreActiveCtxGuard};
+use crate::engine::epoch::{self, ExecutionClock};
 use crate::engine::instance_driver::{InstanceJob, LinkedJob};
 use crate::engine::instance_pool::{self, ComponentInstance, Dispatch, InstancePool};
+use crate::engine::limits::{self, StoreLimiter};
+use crate::engine::stats::ComponentStats;
 use crate::engine::store::relocate::{self, Relocated, bridgeable_element_type};
 use crate::engine::store::stream_pump::Done;
 use crate::engine::value::{carries_cross_store_handle, lift_results, lower_params};
 use crate::engine::volumes::{ResolvedVolumeMount, resolve_component_volume_mounts_in_map};
 use crate::engine::workload::{WorkloadComponent, WorkloadMetadata};
-use crate::plugin::HostPlugin;
-use crate::sockets::{self, SocketAddrUse, loopback};
+use crate::plugin::{HostPlugin, WorkloadFailureSink};
+use crate::sockets::{self, loopback};
 
 /// A cheap, cloneable recipe for building a component's [`Ctx`].
 ///
@@ -70,6 +73,9 @@ pub(crate) struct ComponentCtxTemplate {
     volume_mounts: Vec<ResolvedVolumeMount>,
     plugins: Option<HashMap<&'static str, Arc<dyn HostPlugin + Send + Sync>>>,
     loopback: Arc<std::sync::Mutex<loopback::Network>>,
+    failure_sink: Option<WorkloadFailureSink>,
+    max_execution_time: Option<std::time::Duration>,
+    stats: Arc<ComponentStats>,
     #[cfg(feature = "wasi-tls")]
     tls_provider: Option<SharedTlsProvider>,
 }
@@ -83,6 +89,9 @@ impl ComponentCtxTemplate {
             volume_mounts: metadata.resolved_volume_mounts.clone(),
             plugins: metadata.plugins.clone(),
             loopback: metadata.loopback.clone(),
+            failure_sink: metadata.failure_sink.clone(),
+            max_execution_time: metadata.max_execution_time,
+            stats: metadata.stats.clone(),
             #[cfg(feature = "wasi-tls")]
             tls_provider: None,
         }
@@ -237,18 +246,14 @@ async fn build_ctx_from_template(
         .inherit_stdout()
         .inherit_stderr();
 
+    // Every TCP and UDP bind, connect and send runs through this check (see
+    // `crate::host::allowed_socket_addr` for the policy it applies); only a
+    // service may listen on TCP.
+    let socket_policy = Arc::clone(&template.local_resources.allowed_socket_addrs);
     let sockets_ctx = sockets::WasiSocketsCtx {
         socket_addr_check: sockets::SocketAddrCheck::new(move |addr, reason| {
-            Box::pin(async move {
-                match reason {
-                    SocketAddrUse::TcpBind if is_service => addr.ip().is_loopback(),
-                    SocketAddrUse::TcpBind => false,
-                    SocketAddrUse::UdpBind => addr.ip().is_loopback() || addr.ip().is_unspecified(),
-                    SocketAddrUse::TcpConnect
-                    | SocketAddrUse::UdpConnect
-                    | SocketAddrUse::UdpOutgoingDatagram => true,
-                }
-            })
+            let permitted = socket_policy.permits(addr, reason, is_service);
+            Box::pin(async move { permitted })
         }),
         loopback: Arc::clone(&template.loopback),
         allowed_ip_name_lookups: Arc::clone(&template.local_resources.allowed_ip_name_lookups),
@@ -306,6 +311,33 @@ pub(crate) async fn new_store_from_templates(
     )
     .await?;
     let mut shared_ctx = SharedCtx::new(active_ctx);
+    shared_ctx.limiter = StoreLimiter::new(
+        active.workload_id.clone(),
+        active.component_id.clone(),
+        &active.local_resources,
+        active.failure_sink.clone(),
+    );
+    // A component's store is built for the call it is about to serve, so it
+    // is timed as one; one kept warm stops its store-wide timer and times each
+    // call instead (see `InstanceDriver::spawn`). A service's store runs
+    // `wasi:cli/run` for as long as the service does, so only the calls the
+    // trigger service serves on it are timed (see `trigger_service`).
+    if let Some(max_execution_time) = active.max_execution_time {
+        let clock = Arc::new(ExecutionClock::new(
+            active.component_id.clone(),
+            max_execution_time,
+        ));
+        if !is_service {
+            shared_ctx.store_call_timer = Some(clock.begin());
+        }
+        shared_ctx.execution_clock = Some(clock);
+    }
+    // Counted the same way: the store's one call, or each call of a warm one
+    // or of a trigger service.
+    if !is_service {
+        shared_ctx.store_call = Some(active.stats.begin());
+    }
+    shared_ctx.call_stats = Some(active.stats.clone());
 
     for linked in linked {
         let linked_ctx = build_ctx_from_template(
@@ -322,6 +354,10 @@ pub(crate) async fn new_store_from_templates(
     }
 
     let mut store = wasmtime::Store::new(engine, shared_ctx);
+    limits::install(&mut store);
+    let execution_clock = store.data().execution_clock.clone();
+    let time_slice = limits::time_slice(active.local_resources.cpu_limit);
+    epoch::install(&mut store, execution_clock, time_slice);
 
     let active_id = active.component_id.clone();
     for (linked_id, linked_pre) in linked_instances {
@@ -44,6 +44,7 @@ use std::time::Duration;
 
 use crate::sockets::loopback;
 use anyhow::{Context, bail};
+use arc_swap::ArcSwapOption;
 use moka::sync::Cache;
 use tracing::{instrument, warn};
 use wasmtime::PoolingAllocationConfig;
@@ -53,6 +54,8 @@ use crate::engine::ctx::SharedCtx;
 #[cfg(feature = "wasi-tls")]
 use crate::engine::ctx::SharedTlsProvider;
 use crate::engine::workload::{UnresolvedWorkload, WorkloadComponent, WorkloadService};
+use crate::host::events::HostEventSink;
+use crate::plugin::WorkloadFailureSink;
 use crate::types::{EmptyDirVolume, HostPathVolume, VolumeType, Workload};
 use std::env;
 use std::str::FromStr;
@@ -217,11 +220,15 @@ pub fn targets_wasip3_http(component: &Component) -> bool {
             .any(|(name, _)| name.starts_with("wasi:http") && name.contains("@0.3"))
 }
 
+mod compile_cache;
 pub mod ctx;
+pub mod epoch;
 pub(crate) mod instance_driver;
 pub(crate) mod instance_pool;
+pub mod limits;
 pub use instance_pool::InstancePolicy;
 mod linked_call;
+pub(crate) mod stats;
 pub(crate) mod store;
 mod value;
 mod volumes;
@@ -237,9 +244,22 @@ pub struct Engine {
     // wasmtime engine
     pub(crate) inner: wasmtime::Engine,
     pub(crate) cache: Cache<CacheKey, CacheValue>,
+    /// Where compiled components outlive the process, when enabled. Behind
+    /// [`Self::cache`]: only a component missing from memory is looked up
+    /// here. Shared by every clone of the engine.
+    disk_cache: Arc<ArcSwapOption<compile_cache::DiskCache>>,
     /// TLS provider override for `wasi:tls` client connections.
     #[cfg(feature = "wasi-tls")]
     pub(crate) tls_provider: Option<SharedTlsProvider>,
+    /// Where workloads initialized from here report resource-limit breaches.
+    /// Set by the host at start; shared by every clone of the engine.
+    failure_sink: Arc<ArcSwapOption<WorkloadFailureSink>>,
+    /// Where workloads initialized from here report a service restart. Set by
+    /// the host at start; shared by every clone of the engine.
+    event_sink: Arc<ArcSwapOption<HostEventSink>>,
+    /// Advances the epoch that `max_execution_time` deadlines are measured
+    /// in. Stops once the last clone of the engine is dropped.
+    _epoch_ticker: Arc<epoch::EpochTicker>,
 }
 
 impl std::fmt::Debug for Engine {
@@ -305,6 +325,34 @@ impl Engine {
         &self.inner
     }
 
+    /// Set the sink that workloads initialized after this call report a
+    /// component breaching its [`crate::types::LocalResources`] limits on.
+    pub(crate) fn set_workload_failure_sink(&self, sink: WorkloadFailureSink) {
+        self.failure_sink.store(Some(Arc::new(sink)));
+    }
+
+    /// Keep compiled components under `dir` as well as in memory, so the
+    /// next engine built the same way loads them instead of compiling again.
+    /// Replaces any directory set through
+    /// [`EngineBuilder::with_compilation_disk_cache`].
+    ///
+    /// # Errors
+    /// Returns an error if the directory cannot be created.
+    pub fn set_compilation_disk_cache(
+        &self,
+        dir: impl AsRef<std::path::Path>,
+    ) -> anyhow::Result<()> {
+        let cache = compile_cache::DiskCache::open(dir.as_ref(), &self.inner)?;
+        self.disk_cache.store(Some(Arc::new(cache)));
+        Ok(())
+    }
+
+    /// Set the sink that workloads initialized after this call report their
+    /// service being restarted on.
+    pub(crate) fn set_host_event_sink(&self, sink: HostEventSink) {
+        self.event_sink.store(Some(Arc::new(sink)));
+    }
+
     /// Initializes a workload by validating and preparing all its components.
     ///
     /// This function takes a workload definition and prepares it for execution by:
@@ -421,7 +469,9 @@ impl Engine {
             service,
             workload_components,
             host_interfaces,
-        );
+        )
+        .with_failure_sink(self.failure_sink.load_full().map(|sink| (*sink).clone()))
+        .with_event_sink(self.event_sink.load_full().map(|sink| (*sink).clone()));
 
         #[cfg(feature = "wasi-tls")]
         let workload = workload.maybe_with_tls_provider(self.tls_provider.clone());
@@ -441,7 +491,7 @@ impl Engine {
     ) -> anyhow::Result<WorkloadService> {
         // Create a wasmtime component from the bytes
         let wasmtime_component = self
-            .load_component_bytes(service.bytes, service.digest)
+            .load_component_bytes(service.bytes, service.digest.clone())
             .context("failed to create component from bytes")?;
 
         // Create a linker for this component
@@ -482,7 +532,9 @@ impl Engine {
             service.local_resources,
             service.max_restarts,
             loopback,
-        );
+        )
+        .with_max_execution_time(service.max_execution_time)
+        .with_digest(service.digest);
 
         let world = service.world();
 
@@ -498,6 +550,9 @@ impl Engine {
     }
 
     /// Load a WebAssembly component from raw bytes or yields a previously compiled one.
+    ///
+    /// Blocks on compilation and on the compilation disk cache's file I/O, so
+    /// async callers run it through `spawn_blocking`.
     #[instrument(name = "load_component_bytes", skip_all, fields(digest = %digest.as_ref().map(|d| d.as_ref()).unwrap_or("none")))]
     fn load_component_bytes(
         &self,
@@ -516,13 +571,24 @@ impl Engine {
                 let key = CacheKey(digest.as_ref().to_string());
                 let inner = &self.inner;
                 let bytes_ref = bytes.as_ref();
+                let disk_cache = self.disk_cache.load_full();
 
                 self.cache
                     .try_get_with(key, || {
-                        Component::new(inner, bytes_ref)
+                        let digest = digest.as_ref();
+                        if let Some(component) = disk_cache
+                            .as_ref()
+                            .and_then(|disk_cache| disk_cache.load(inner, digest))
+                        {
+                            return Ok(CacheValue(component));
+                        }
+                        let component = Component::new(inner, bytes_ref)
                             .map_err(anyhow::Error::from)
-                            .context("failed to compile component from bytes")
-                            .map(CacheValue)
+                            .context("failed to compile component from bytes")?;
+                        if let Some(disk_cache) = &disk_cache {
+                            disk_cache.store(digest, &component);
+                        }
+                        Ok(CacheValue(component))
                     })
                     .map_err(|e: Arc<anyhow::Error>| {
                         anyhow::Error::new(SharedError(e)).context("compilation cache error")
@@ -546,6 +612,7 @@ impl Engine {
     ) -> anyhow::Result<WorkloadComponent> {
         // Read before the component's fields are moved out below.
         let instances = InstancePolicy::from_component(&component);
+        let digest = component.digest.clone();
 
         // Create a wasmtime component from the bytes
         let wasmtime_component = self
@@ -592,7 +659,9 @@ impl Engine {
             component.local_resources,
             loopback,
             instances,
-        ))
+        )
+        .with_max_execution_time(component.max_execution_time)
+        .with_digest(digest))
     }
 
     /// Compile a host component plugin and build a linker with WASI (and
@@ -764,7 +833,9 @@ pub struct EngineBuilder {
     proposals: std::collections::BTreeSet<WasmProposal>,
     compilation_cache_size: Option<u64>,
     compilation_cache_ttl: Option<Duration>,
+    compilation_disk_cache: Option<PathBuf>,
     fuel_consumption: Option<bool>,
+    epoch_tick: Option<Duration>,
     /// Optional TLS provider override for wasi:tls client connections.
     #[cfg(feature = "wasi-tls")]
     tls_provider: Option<SharedTlsProvider>,
@@ -830,6 +901,17 @@ impl EngineBuilder {
         self
     }
 
+    /// Sets how often the engine's epoch advances, which is how often a
+    /// running guest is checked against its component's `max_execution_time`.
+    /// Defaults to [`epoch::DEFAULT_EPOCH_TICK`].
+    ///
+    /// Epoch interruption is always enabled, on top of a custom base config
+    /// too, since every store the engine builds relies on it.
+    pub fn with_epoch_tick(mut self, tick: Duration) -> Self {
+        self.epoch_tick = Some(tick);
+        self
+    }
+
     /// Sets a custom wasmtime configuration to use as the *base* for the engine.
     ///
     /// This config is used as the starting point, and any other builder
@@ -864,6 +946,18 @@ impl EngineBuilder {
         self
     }
 
+    /// Keeps compiled components under `dir` as well as in memory, so a
+    /// restarted host loads them instead of compiling every image again.
+    ///
+    /// Only components that carry a digest are stored. Entries are checked
+    /// for corruption on load and are only ever loaded by an engine with the
+    /// same wasmtime version and compilation settings. Loading one runs the
+    /// native code it holds, so `dir` must be writable by the host alone.
+    pub fn with_compilation_disk_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.compilation_disk_cache = Some(dir.into());
+        self
+    }
+
     /// Override the TLS provider used for `wasi:tls` client connections.
     ///
     /// Use this to plug in an alternative TLS backend, install a custom root
@@ -934,6 +1028,9 @@ impl EngineBuilder {
             config.consume_fuel(fuel);
         }
 
+        // Per-call `max_execution_time` deadlines are enforced through epochs.
+        config.epoch_interruption(true);
+
         // WASIP3's async ABI requires the component-model async proposal.
         self.proposals.insert(WasmProposal::ComponentModelAsync);
 
@@ -952,6 +1049,9 @@ impl EngineBuilder {
         }
 
         let inner = wasmtime::Engine::new(&config)?;
+        let epoch_ticker =
+            epoch::EpochTicker::spawn(&inner, self.epoch_tick.unwrap_or(epoch::DEFAULT_EPOCH_TICK))
+                .context("failed to start the epoch ticker")?;
         let cache = Cache::builder()
             .max_capacity(self.compilation_cache_size.unwrap_or(100))
             .time_to_idle(
@@ -959,11 +1059,19 @@ impl EngineBuilder {
                     .unwrap_or(Duration::from_secs(600)),
             )
             .build();
+        let disk_cache = self
+            .compilation_disk_cache
+            .map(|dir| compile_cache::DiskCache::open(&dir, &inner).map(Arc::new))
+            .transpose()?;
         Ok(Engine {
             inner,
             cache,
+            disk_cache: Arc::new(ArcSwapOption::new(disk_cache)),
             #[cfg(feature = "wasi-tls")]
             tls_provider: self.tls_provider,
+            failure_sink: Arc::default(),
+            event_sink: Arc::default(),
+            _epoch_ticker: Arc::new(epoch_ticker),
         })
     }
 }
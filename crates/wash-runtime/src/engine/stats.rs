@@ -0,0 +1,108 @@
+//! Live counters for a component, reported by
+//! [`HostApi::workload_list`](crate::host::HostApi::workload_list).
+//!
+//! A component's [`ComponentStats`] is shared by every clone of its
+//! [`WorkloadComponent`](crate::engine::workload::WorkloadComponent) and handed
+//! to each store built for it, so calls are counted on every path that runs
+//! one: a store built for a single call counts as one call for as long as it
+//! lives, and a warm instance counts each call it serves instead (see
+//! [`crate::engine::instance_driver`]). A service counts each call its
+//! trigger service serves. Stopping a workload waits on these counts to drain
+//! (see [`ComponentStats::idle`]).
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use tokio::sync::Notify;
+
+/// Counters for one component.
+#[derive(Debug, Default)]
+pub(crate) struct ComponentStats {
+    in_flight: AtomicUsize,
+    restarts: AtomicU64,
+    /// Woken each time the last call in flight finishes.
+    idle: Notify,
+}
+
+impl ComponentStats {
+    /// Count a call as in flight until the returned guard is dropped.
+    pub(crate) fn begin(self: &Arc<Self>) -> InFlightCall {
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        InFlightCall(Arc::clone(self))
+    }
+
+    /// Calls running in this component right now.
+    pub(crate) fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Wait until no call is in flight. Returns at once if none is.
+    pub(crate) async fn idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+            tokio::pin!(notified);
+            // Registered before the count is read, so a call finishing in
+            // between still wakes this waiter.
+            notified.as_mut().enable();
+            if self.in_flight() == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Record that a warm instance of this component faulted and will be
+    /// replaced by a fresh one.
+    pub(crate) fn record_restart(&self) {
+        self.restarts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Warm instances of this component replaced after a fault so far.
+    pub(crate) fn restarts(&self) -> u64 {
+        self.restarts.load(Ordering::Relaxed)
+    }
+}
+
+/// One call counted by [`ComponentStats::begin`].
+pub(crate) struct InFlightCall(Arc<ComponentStats>);
+
+impl Drop for InFlightCall {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_call_is_in_flight_until_its_guard_drops() {
+        let stats = Arc::new(ComponentStats::default());
+        let first = stats.begin();
+        let second = stats.begin();
+        assert_eq!(stats.in_flight(), 2);
+        drop(first);
+        assert_eq!(stats.in_flight(), 1);
+        drop(second);
+        assert_eq!(stats.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn idle_waits_for_the_last_call() {
+        let stats = Arc::new(ComponentStats::default());
+        stats.idle().await;
+
+        let call = stats.begin();
+        let waiter = tokio::spawn({
+            let stats = Arc::clone(&stats);
+            async move { stats.idle().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished(), "a call is still in flight");
+        drop(call);
+        waiter.await.expect("idle returns once the call ends");
+    }
+}
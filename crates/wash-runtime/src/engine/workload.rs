@@ -5,6 +5,7 @@ use std::{
     ops::{Deref, DerefMut},
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
 use crate::{plugin::WitInterfaces, sockets::loopback};
@@ -28,8 +29,10 @@ use crate::{
             func_is_bridge_safe, func_is_ephemeral_safe, invoke_linked_async_export,
             invoke_linked_sync_export, new_store_from_templates,
         },
+        stats::ComponentStats,
         volumes::{ResolvedVolumeMount, resolve_component_volume_mounts_in_map},
     },
+    host::events::{HostEvent, HostEventSink},
     plugin::HostPlugin,
     types::{LocalResources, VolumeMount},
     wit::{WitInterface, WitWorld},
@@ -68,6 +71,14 @@ pub struct WorkloadMetadata {
     pub(crate) plugins: Option<HashMap<&'static str, Arc<dyn HostPlugin + Send + Sync>>>,
     /// Workload loopback
     pub(crate) loopback: Arc<std::sync::Mutex<loopback::Network>>,
+    /// Where a store reports a component breaching its resource limits; see
+    /// [`crate::engine::limits`]. `None` outside a running host.
+    pub(crate) failure_sink: Option<crate::plugin::WorkloadFailureSink>,
+    /// How long one call into this component may run before it is
+    /// interrupted; see [`crate::engine::epoch`]. `None` is unbounded.
+    pub(crate) max_execution_time: Option<Duration>,
+    /// Calls in flight and restarts, reported by the host's workload list.
+    pub(crate) stats: Arc<ComponentStats>,
     /// Linked component ids
     linked_components: HashSet<Arc<str>>,
 }
@@ -261,6 +272,11 @@ pub struct WorkloadService {
     max_restarts: u64,
     /// The [`JoinHandle`] for the running service
     handle: Option<Arc<JoinHandle<()>>>,
+    /// The digest the service's bytes were pulled by, if known.
+    digest: Option<Arc<str>>,
+    /// Where the service's supervisor reports a restart. `None` outside a
+    /// running host.
+    event_sink: Option<HostEventSink>,
 }
 
 impl WorkloadService {
@@ -291,10 +307,61 @@ impl WorkloadService {
                 local_resources,
                 plugins: None,
                 loopback,
+                failure_sink: None,
+                max_execution_time: None,
+                stats: Arc::default(),
                 linked_components: Default::default(),
             },
             handle: None,
+            digest: None,
             max_restarts,
+            event_sink: None,
+        }
+    }
+
+    /// Record the digest the service's bytes were pulled by, for reporting.
+    pub fn with_digest(mut self, digest: Option<String>) -> Self {
+        self.digest = digest.map(Arc::from);
+        self
+    }
+
+    /// Bound how long one host-invoked call into this service may run. Its
+    /// `wasi:cli/run` is not a call and keeps running unbounded.
+    pub fn with_max_execution_time(mut self, max_execution_time: Option<Duration>) -> Self {
+        self.metadata.max_execution_time = max_execution_time;
+        self
+    }
+
+    /// A reporter for this service being restarted after `reason` ended its
+    /// previous incarnation, for the supervisor task to call.
+    fn restart_reporter(&self) -> impl Fn(String) + Send + 'static {
+        let sink = self.event_sink.clone();
+        let stats = self.metadata.stats.clone();
+        let workload_id = self.metadata.workload_id.to_string();
+        let component_id = self.metadata.id.to_string();
+        move |reason| {
+            stats.record_restart();
+            if let Some(sink) = &sink {
+                sink.emit(HostEvent::ComponentRestarted {
+                    workload_id: workload_id.clone(),
+                    component_id: component_id.clone(),
+                    reason,
+                });
+            }
+        }
+    }
+
+    /// This service's current status, as reported by the host's workload list
+    /// under the name `service`. A service is a single instance rather than a
+    /// pool, so it reports no warm instances.
+    pub fn status(&self) -> crate::types::ComponentStatus {
+        crate::types::ComponentStatus {
+            name: "service".to_string(),
+            digest: self.digest.as_deref().map(str::to_string),
+            pool_size: 0,
+            warm_instances: 0,
+            in_flight: self.metadata.stats.in_flight(),
+            restarts: self.metadata.stats.restarts(),
         }
     }
 
@@ -344,6 +411,8 @@ impl WorkloadService {
 pub struct WorkloadComponent {
     /// Component name. Primarily for debugging purposes.
     name: Arc<str>,
+    /// The digest the component's bytes were pulled by, if known.
+    digest: Option<Arc<str>>,
     /// The [`WorkloadMetadata`] for this component
     pub(crate) metadata: WorkloadMetadata,
     /// Instances kept warm between ephemeral linked calls. Shared by every
@@ -385,9 +454,13 @@ impl WorkloadComponent {
                 local_resources,
                 plugins: None,
                 loopback,
+                failure_sink: None,
+                max_execution_time: None,
+                stats: Arc::default(),
                 linked_components: Default::default(),
             },
             name: component_name.into(),
+            digest: None,
             instances: Arc::new(InstancePool::new(instances)),
         }
     }
@@ -408,6 +481,32 @@ impl WorkloadComponent {
             .instantiate_pre(&self.metadata.component)
     }
 
+    /// Bound how long one call into this component may run. A call still
+    /// running past it is interrupted and traps.
+    pub fn with_max_execution_time(mut self, max_execution_time: Option<Duration>) -> Self {
+        self.metadata.max_execution_time = max_execution_time;
+        self
+    }
+
+    /// Record the digest the component's bytes were pulled by, for reporting.
+    pub fn with_digest(mut self, digest: Option<String>) -> Self {
+        self.digest = digest.map(Arc::from);
+        self
+    }
+
+    /// This component's current status, as reported by the host's workload
+    /// list.
+    pub fn status(&self) -> crate::types::ComponentStatus {
+        crate::types::ComponentStatus {
+            name: self.name.to_string(),
+            digest: self.digest.as_deref().map(str::to_string),
+            pool_size: self.instances.pool_size(),
+            warm_instances: self.instances.warm_instances(),
+            in_flight: self.metadata.stats.in_flight(),
+            restarts: self.metadata.stats.restarts(),
+        }
+    }
+
     pub fn metadata(&self) -> &WorkloadMetadata {
         &self.metadata
     }
@@ -642,6 +741,7 @@ impl ResolvedWorkload {
         };
         let pre = service.pre_instantiate()?;
         let mut max_restarts = service.max_restarts;
+        let report_restart = service.restart_reporter();
         self.resolve_service_volume_mounts().await?;
         // Re-borrow immutably after the mutable borrow for pre_instantiate() is done
         let Some(service) = self.service.as_ref() else {
@@ -659,6 +759,7 @@ impl ResolvedWorkload {
                         warn!("max restarts reached, service will not be restarted");
                         break;
                     }
+                    report_restart(format!("{e:#}"));
                 } else {
                     info!("service exited successfully");
                     break;
@@ -679,9 +780,9 @@ impl ResolvedWorkload {
         let service = self
             .service
             .as_mut()
-            .map(|s| (s.pre_instantiate_p3(), s.max_restarts));
+            .map(|s| (s.pre_instantiate_p3(), s.max_restarts, s.restart_reporter()));
 
-        if let Some((Ok(pre), mut max_restarts)) = service {
+        if let Some((Ok(pre), mut max_restarts, report_restart)) = service {
             self.resolve_service_volume_mounts().await?;
             // Capture the store recipe so each restarted incarnation gets a
             // FRESH store: a trapped store cannot re-enter any component
@@ -720,6 +821,7 @@ impl ResolvedWorkload {
                                 warn!("max restarts reached, P3 service will not be restarted");
                                 break;
                             }
+                            report_restart("service exited with an error".to_string());
                         }
                         Ok(Err(e)) | Err(e) => {
                             error!(err = %e, retries = max_restarts, "P3 service execution failed");
@@ -727,6 +829,7 @@ impl ResolvedWorkload {
                                 warn!("max restarts reached, P3 service will not be restarted");
                                 break;
                             }
+                            report_restart(format!("{e:#}"));
                         }
                     }
                     max_restarts = max_restarts.saturating_sub(1);
@@ -766,6 +869,7 @@ impl ResolvedWorkload {
             crate::engine::exports_messaging_handler(&service.metadata.component),
             service.max_restarts,
         );
+        let report_restart = service.restart_reporter();
         self.resolve_service_volume_mounts().await?;
 
         // Capture the store recipe so the supervisor below can rebuild a FRESH
@@ -852,6 +956,7 @@ impl ResolvedWorkload {
                     }
                     Err(e) => {
                         warn!(err = %e, retries = restarts, "trigger service faulted; restarting");
+                        report_restart(format!("{e:#}"));
                         restarts = restarts.saturating_sub(1);
                         // The faulted store cannot re-enter any component
                         // instance; the next incarnation needs a fresh one.
@@ -891,6 +996,137 @@ impl ResolvedWorkload {
         self.components.clone()
     }
 
+    /// Take the workload out of service: unbind it so nothing new is routed to
+    /// it, give the calls already in flight up to `drain_timeout` to finish,
+    /// and then stop its service. Calls still running at the deadline are
+    /// cancelled through the plugins serving them and the warm instances they
+    /// run on are halted.
+    pub(crate) async fn shut_down(&self, drain_timeout: Duration) {
+        if let Err(e) = self.unbind_all_plugins().await {
+            warn!(
+                workload_id = self.id.as_ref(),
+                error = ?e,
+                "error unbinding plugins during workload stop, continuing"
+            );
+        }
+
+        let drained = tokio::time::timeout(drain_timeout, self.drain())
+            .await
+            .is_ok();
+        if !drained {
+            let cancelled = self.cancel_in_flight().await;
+            warn!(
+                workload_id = self.id.as_ref(),
+                drain_timeout = ?drain_timeout,
+                cancelled,
+                "workload did not drain in time; cancelling its calls still in flight"
+            );
+        }
+        self.stop_service();
+    }
+
+    /// Take a revision replaced by [`crate::host::HostApi::workload_update`]
+    /// out of service. Its plugins are already unbound and its successor has
+    /// taken over its routes under the same workload ID, so unlike
+    /// [`Self::shut_down`] this leaves both alone, and doesn't ask plugins to
+    /// cancel the workload's calls: those would now include the successor's.
+    /// Calls in flight get up to `drain_timeout` to finish; then the
+    /// revision's warm instances are released, halting any still busy, and
+    /// its service is stopped.
+    pub(crate) async fn retire(&self, drain_timeout: Duration) {
+        let drained = tokio::time::timeout(drain_timeout, self.drain())
+            .await
+            .is_ok();
+        for component in self.components.read().await.values() {
+            if !drained {
+                component.instances.halt();
+            }
+            component.instances.clear();
+        }
+        if !drained {
+            warn!(
+                workload_id = self.id.as_ref(),
+                drain_timeout = ?drain_timeout,
+                "replaced revision did not drain in time; halting its instances"
+            );
+        }
+        self.stop_service();
+    }
+
+    /// Whether a successor resolved under this workload's ID overwrites every
+    /// registration this workload holds with the HTTP handler: it must route
+    /// HTTP if this one does, and run a service if this one does.
+    pub(crate) fn is_replaced_in_place_by(&self, successor: &UnresolvedWorkload) -> bool {
+        let routes_http = |interfaces: &[WitInterface]| {
+            interfaces
+                .iter()
+                .any(|iface| iface.is_incoming_http_handler())
+        };
+        (!routes_http(&self.host_interfaces) || routes_http(&successor.host_interfaces))
+            && (self.service.is_none() || successor.service.is_some())
+    }
+
+    /// Wait until no call is in flight on any of the workload's components or
+    /// its service.
+    async fn drain(&self) {
+        let stats = self
+            .components
+            .read()
+            .await
+            .values()
+            .map(|c| Arc::clone(&c.metadata.stats))
+            .chain(self.service.iter().map(|s| Arc::clone(&s.metadata.stats)))
+            .collect::<Vec<_>>();
+        for stats in stats {
+            stats.idle().await;
+        }
+    }
+
+    /// Past the drain deadline: ask each bound plugin to cancel the calls it is
+    /// serving for this workload, and halt every warm instance so its store
+    /// drops with whatever is left on it. Returns how many plugin calls were
+    /// asked to cancel.
+    async fn cancel_in_flight(&self) -> usize {
+        let mut plugins: HashMap<&'static str, Arc<dyn HostPlugin + Send + Sync>> = HashMap::new();
+        for component in self.components.read().await.values() {
+            component.instances.halt();
+            plugins.extend(
+                component
+                    .plugins()
+                    .iter()
+                    .flatten()
+                    .map(|(id, p)| (*id, p.clone())),
+            );
+        }
+        if let Some(service) = &self.service {
+            plugins.extend(
+                service
+                    .plugins()
+                    .iter()
+                    .flatten()
+                    .map(|(id, p)| (*id, p.clone())),
+            );
+        }
+        plugins
+            .values()
+            .map(|plugin| plugin.cancel_workload_calls(self.id()))
+            .sum()
+    }
+
+    /// The status of each of this workload's components, in component ID
+    /// order, followed by the service's if it has one.
+    pub async fn component_statuses(&self) -> Vec<crate::types::ComponentStatus> {
+        let mut statuses: Vec<_> = self
+            .components
+            .read()
+            .await
+            .values()
+            .map(WorkloadComponent::status)
+            .collect();
+        statuses.extend(self.service.as_ref().map(WorkloadService::status));
+        statuses
+    }
+
     pub fn host_interfaces(&self) -> &Vec<WitInterface> {
         &self.host_interfaces
     }
@@ -1591,13 +1827,47 @@ impl ResolvedWorkload {
             "unbinding all plugins from workload"
         );
 
+        self.unbind_host_plugins().await;
+
         for component in self.components.read().await.values() {
+            if component.exports_wasi_http() {
+                anyhow::Context::context(
+                    self.http_handler.on_workload_unbind(self.id()).await,
+                    "failed to notify HTTP handler of workload",
+                )?;
+            }
+
             // Warm instances hold guest resources (sockets, open files) for as
             // long as they stay parked, so release them with the rest of the
-            // workload's teardown. Calls still in flight own their own stores
-            // and are unaffected.
+            // workload's teardown, once nothing routes to them any more. Each
+            // finishes the calls it has in flight before its store drops.
             component.instances.clear();
+        }
 
+        // A trigger service registered its HTTP/messaging handlers at start
+        // (`execute_trigger_service`); drop those registrations on stop so it no
+        // longer receives host-invoked deliveries on a torn-down instance.
+        if self.service.is_some() {
+            if let Err(e) = self.http_handler.on_service_http_unbind(self.id()).await {
+                tracing::error!(workload.id = %self.id(), err = %e, "failed to unbind service HTTP handler, continuing");
+            }
+            if let Err(e) = self
+                .http_handler
+                .on_trigger_service_messaging_unbind(self.id())
+                .await
+            {
+                tracing::error!(workload.id = %self.id(), err = %e, "failed to unbind trigger service messaging handler, continuing");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unbind the host plugins from this workload's components and service,
+    /// leaving its HTTP and trigger service registrations with the HTTP
+    /// handler in place. Errors from individual plugins are logged.
+    pub(crate) async fn unbind_host_plugins(&self) {
+        for component in self.components.read().await.values() {
             if let Some(plugins) = component.plugins() {
                 for (plugin_id, plugin) in plugins.iter() {
                     trace!(
@@ -1633,13 +1903,6 @@ impl ResolvedWorkload {
                     }
                 }
             }
-
-            if component.exports_wasi_http() {
-                anyhow::Context::context(
-                    self.http_handler.on_workload_unbind(self.id()).await,
-                    "failed to notify HTTP handler of workload",
-                )?;
-            }
         }
 
         // The service item records plugin bindings just like a component;
@@ -1672,24 +1935,6 @@ impl ResolvedWorkload {
                 }
             }
         }
-
-        // A trigger service registered its HTTP/messaging handlers at start
-        // (`execute_trigger_service`); drop those registrations on stop so it no
-        // longer receives host-invoked deliveries on a torn-down instance.
-        if self.service.is_some() {
-            if let Err(e) = self.http_handler.on_service_http_unbind(self.id()).await {
-                tracing::error!(workload.id = %self.id(), err = %e, "failed to unbind service HTTP handler, continuing");
-            }
-            if let Err(e) = self
-                .http_handler
-                .on_trigger_service_messaging_unbind(self.id())
-                .await
-            {
-                tracing::error!(workload.id = %self.id(), err = %e, "failed to unbind trigger service messaging handler, continuing");
-            }
-        }
-
-        Ok(())
     }
 }
 
@@ -1828,6 +2073,33 @@ impl UnresolvedWorkload {
         }
     }
 
+    /// Set where this workload's stores report a component breaching its
+    /// resource limits. No-op when `None`.
+    pub(crate) fn with_failure_sink(
+        mut self,
+        sink: Option<crate::plugin::WorkloadFailureSink>,
+    ) -> Self {
+        if sink.is_none() {
+            return self;
+        }
+        if let Some(service) = &mut self.service {
+            service.metadata.failure_sink = sink.clone();
+        }
+        for component in self.components.values_mut() {
+            component.metadata.failure_sink = sink.clone();
+        }
+        self
+    }
+
+    /// Set where this workload's service reports being restarted. No-op when
+    /// `None`.
+    pub(crate) fn with_event_sink(mut self, sink: Option<HostEventSink>) -> Self {
+        if let Some(service) = &mut self.service {
+            service.event_sink = sink;
+        }
+        self
+    }
+
     /// Removes and returns the component `id`, if present.
     ///
     /// Used by the host-component-plugin loader, which represents a loading
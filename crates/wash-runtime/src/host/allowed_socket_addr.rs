@@ -0,0 +1,455 @@
+//! Address policy for raw `wasi:sockets` TCP and UDP.
+//!
+//! A workload declares which socket addresses its components may connect to
+//! and which they may bind. Each entry parses from a plain string on the wire
+//! (proto, wash YAML, CRD) into an [`AllowedSocketAddr`]: a CIDR block and a
+//! port range. An [`AllowedSocketAddrs`] holds the connect and bind lists and
+//! whether the workload-local loopback network is available, and
+//! [`AllowedSocketAddrs::permits`] evaluates every address the socket layer
+//! is about to use against them.
+//!
+//! # Accepted forms
+//!
+//! | Form                          | Matches                                   |
+//! | ----------------------------- | ----------------------------------------- |
+//! | `*`                           | every address, every port                 |
+//! | `*:443`                       | every address, port 443                   |
+//! | `10.0.0.0/8`                  | that block, every port                    |
+//! | `10.1.2.3:5432`               | that address, port 5432                   |
+//! | `192.168.0.0/16:8000-8999`    | that block, ports 8000 through 8999       |
+//! | `2001:db8::/32`               | an IPv6 block, every port                 |
+//! | `[2001:db8::/32]:443`         | an IPv6 block with a port (bracketed)     |
+//!
+//! A block must not have host bits set: `10.0.0.1/8` is rejected rather than
+//! silently widened to `10.0.0.0/8`.
+//!
+//! # Empty lists deny
+//!
+//! An empty `connect` list denies every non-loopback connect and datagram; an
+//! empty `bind` list denies every non-loopback bind. Nothing substitutes an
+//! allow-all policy for a workload that declared none. To connect anywhere,
+//! declare `connect: ["*"]`.
+//!
+//! # Loopback
+//!
+//! Loopback addresses (`127.0.0.0/8`, `::1`) never reach the host's network
+//! stack: they are served by a network private to the workload, which its
+//! components use to talk to each other and to its service. That network is
+//! governed by [`AllowedSocketAddrs::loopback`] alone, not by the lists, and
+//! is available unless a workload turns it off.
+//!
+//! # Binding
+//!
+//! Binding the unspecified address (`0.0.0.0`, `::`) to port 0 is how a UDP
+//! client gets a socket to send from; no port is claimed, and whatever the
+//! socket then sends is checked against `connect`. It is always permitted.
+//! A TCP listener is further limited to the workload's service: a component
+//! serves one call at a time and has nothing to accept connections with.
+//!
+//! # Examples
+//!
+//! ```
+//! use wash_runtime::host::allowed_socket_addr::AllowedSocketAddr;
+//!
+//! let policy: AllowedSocketAddr = "10.0.0.0/8:5432".parse().unwrap();
+//! assert!(policy.matches("10.20.30.40:5432".parse().unwrap()));
+//! assert!(!policy.matches("10.20.30.40:22".parse().unwrap()));
+//! assert!(!policy.matches("192.168.1.1:5432".parse().unwrap()));
+//! ```
+
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow, bail};
+use serde::{Deserialize, Serialize, de, ser};
+
+use crate::sockets::SocketAddrUse;
+
+/// A parsed entry from a socket address allowlist.
+///
+/// See the [module-level docs](self) for accepted string forms. Parsed via
+/// [`FromStr`]; rendered back to its wire representation via
+/// [`Display`](fmt::Display); the [`Serialize`] / [`Deserialize`] impls
+/// round-trip through that same string form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowedSocketAddr {
+    /// The block an address must fall in; `None` for `*`, which matches
+    /// both families.
+    network: Option<(IpAddr, u8)>,
+    ports: RangeInclusive<u16>,
+}
+
+impl AllowedSocketAddr {
+    /// Returns `true` if `addr` falls in this entry's block and port range.
+    ///
+    /// An IPv4-mapped IPv6 address matches as the IPv4 address it carries.
+    #[must_use]
+    pub fn matches(&self, addr: SocketAddr) -> bool {
+        if !self.ports.contains(&addr.port()) {
+            return false;
+        }
+        let Some((network, prefix_len)) = self.network else {
+            return true;
+        };
+        match (network, addr.ip().to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                mask(u32::from(ip).into(), 32, prefix_len) == u32::from(network).into()
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                mask(u128::from(ip), 128, prefix_len) == u128::from(network)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Clear every bit of `bits` (an address `width` bits wide) past `prefix_len`.
+fn mask(bits: u128, width: u8, prefix_len: u8) -> u128 {
+    let host_bits = u32::from(width.saturating_sub(prefix_len));
+    bits.checked_shr(host_bits)
+        .and_then(|network| network.checked_shl(host_bits))
+        .unwrap_or(0)
+}
+
+/// Returns `true` if `addr` satisfies any entry in `policy`.
+///
+/// An empty `policy` denies every address.
+#[must_use]
+pub fn check_allowed_socket_addr(policy: &[AllowedSocketAddr], addr: SocketAddr) -> bool {
+    policy.iter().any(|entry| entry.matches(addr))
+}
+
+/// A workload's raw socket policy: where its components may connect, what
+/// they may bind, and whether its loopback network is available.
+///
+/// See the [module-level docs](self). The default denies every non-loopback
+/// connect and bind and leaves loopback available.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AllowedSocketAddrs {
+    /// Where components may connect TCP sockets, connect UDP sockets and
+    /// send datagrams.
+    pub connect: Vec<AllowedSocketAddr>,
+    /// Non-loopback addresses components may bind.
+    pub bind: Vec<AllowedSocketAddr>,
+    /// Whether the workload-local loopback network is available.
+    pub loopback: bool,
+}
+
+impl Default for AllowedSocketAddrs {
+    fn default() -> Self {
+        Self {
+            connect: Vec::new(),
+            bind: Vec::new(),
+            loopback: true,
+        }
+    }
+}
+
+impl AllowedSocketAddrs {
+    /// Whether a socket may use `addr` for `reason`. `may_listen` is whether
+    /// the socket belongs to a workload service, the only caller allowed a
+    /// TCP listener.
+    pub(crate) fn permits(
+        &self,
+        addr: SocketAddr,
+        reason: SocketAddrUse,
+        may_listen: bool,
+    ) -> bool {
+        let ip = addr.ip().to_canonical();
+        if ip.is_loopback() {
+            return self.loopback && (may_listen || !matches!(reason, SocketAddrUse::TcpBind));
+        }
+        match reason {
+            SocketAddrUse::TcpBind => may_listen && check_allowed_socket_addr(&self.bind, addr),
+            SocketAddrUse::UdpBind => {
+                (ip.is_unspecified() && addr.port() == 0)
+                    || check_allowed_socket_addr(&self.bind, addr)
+            }
+            SocketAddrUse::TcpConnect
+            | SocketAddrUse::UdpConnect
+            | SocketAddrUse::UdpOutgoingDatagram => check_allowed_socket_addr(&self.connect, addr),
+        }
+    }
+}
+
+impl FromStr for AllowedSocketAddr {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            return Err(anyhow!("allowed-socket-address entry is empty"));
+        }
+
+        // An IPv6 block carrying a port is bracketed; a bare one holds more
+        // than one colon and has no port. Anything else splits at its colon.
+        let (network, ports) = if let Some(rest) = trimmed.strip_prefix('[') {
+            let (network, rest) = rest
+                .split_once(']')
+                .ok_or_else(|| anyhow!("'[' without a closing ']'"))?;
+            let ports = match rest {
+                "" => None,
+                rest => Some(
+                    rest.strip_prefix(':')
+                        .ok_or_else(|| anyhow!("expected ':port' after ']'"))?,
+                ),
+            };
+            (network, ports)
+        } else if trimmed.matches(':').count() > 1 {
+            (trimmed, None)
+        } else {
+            match trimmed.split_once(':') {
+                Some((network, ports)) => (network, Some(ports)),
+                None => (trimmed, None),
+            }
+        };
+
+        Ok(Self {
+            network: parse_network(network)?,
+            ports: ports.map_or(Ok(0..=u16::MAX), parse_ports)?,
+        })
+    }
+}
+
+fn parse_network(network: &str) -> anyhow::Result<Option<(IpAddr, u8)>> {
+    if network == "*" {
+        return Ok(None);
+    }
+    let (addr, prefix_len) = match network.split_once('/') {
+        Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+        None => (network, None),
+    };
+    let addr: IpAddr = addr
+        .parse()
+        .with_context(|| format!("'{addr}' is not an IP address"))?;
+    let width = addr_width(addr);
+    let prefix_len = match prefix_len {
+        Some(prefix_len) => prefix_len
+            .parse::<u8>()
+            .ok()
+            .filter(|len| *len <= width)
+            .ok_or_else(|| anyhow!("'/{prefix_len}' is not a prefix length from 0 to {width}"))?,
+        None => width,
+    };
+    let bits = match addr {
+        IpAddr::V4(v4) => u32::from(v4).into(),
+        IpAddr::V6(v6) => u128::from(v6),
+    };
+    if mask(bits, width, prefix_len) != bits {
+        bail!("'{network}' has host bits set past its prefix length");
+    }
+    Ok(Some((addr, prefix_len)))
+}
+
+fn parse_ports(ports: &str) -> anyhow::Result<RangeInclusive<u16>> {
+    let port = |port: &str| {
+        port.parse::<u16>()
+            .with_context(|| format!("'{port}' is not a port"))
+    };
+    let (low, high) = match ports.split_once('-') {
+        Some((low, high)) => (port(low)?, port(high)?),
+        None => {
+            let port = port(ports)?;
+            (port, port)
+        }
+    };
+    if low > high {
+        bail!("port range '{ports}' runs backwards");
+    }
+    Ok(low..=high)
+}
+
+impl fmt::Display for AllowedSocketAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ports = match (*self.ports.start(), *self.ports.end()) {
+            (0, u16::MAX) => None,
+            (low, high) if low == high => Some(format!("{low}")),
+            (low, high) => Some(format!("{low}-{high}")),
+        };
+        let network = match self.network {
+            None => "*".to_string(),
+            Some((addr, len)) if len == addr_width(addr) => addr.to_string(),
+            Some((addr, len)) => format!("{addr}/{len}"),
+        };
+        match (ports, self.network) {
+            (None, _) => f.write_str(&network),
+            (Some(ports), Some((IpAddr::V6(_), _))) => write!(f, "[{network}]:{ports}"),
+            (Some(ports), _) => write!(f, "{network}:{ports}"),
+        }
+    }
+}
+
+/// How many bits wide `addr` is.
+fn addr_width(addr: IpAddr) -> u8 {
+    if addr.is_ipv4() { 32 } else { 128 }
+}
+
+impl Serialize for AllowedSocketAddr {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AllowedSocketAddr {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(s: &str) -> SocketAddr {
+        s.parse().expect("test gave an invalid socket address")
+    }
+
+    fn parse(s: &str) -> AllowedSocketAddr {
+        s.parse()
+            .expect("test gave an invalid allowed-socket-address entry")
+    }
+
+    fn policy(connect: &[&str], bind: &[&str]) -> AllowedSocketAddrs {
+        AllowedSocketAddrs {
+            connect: connect.iter().map(|s| parse(s)).collect(),
+            bind: bind.iter().map(|s| parse(s)).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn star_matches_every_address_and_port() {
+        let any = parse("*");
+        assert!(any.matches(addr("203.0.113.9:1")));
+        assert!(any.matches(addr("[2001:db8::1]:65535")));
+
+        let https = parse("*:443");
+        assert!(https.matches(addr("203.0.113.9:443")));
+        assert!(https.matches(addr("[2001:db8::1]:443")));
+        assert!(!https.matches(addr("203.0.113.9:80")));
+    }
+
+    #[test]
+    fn blocks_match_their_addresses_only() {
+        let block = parse("10.0.0.0/8");
+        assert!(block.matches(addr("10.0.0.0:1")));
+        assert!(block.matches(addr("10.255.255.255:1")));
+        assert!(!block.matches(addr("11.0.0.0:1")));
+        // A v4 block never matches a v6 address, except the v4 it maps.
+        assert!(!block.matches(addr("[2001:db8::1]:1")));
+        assert!(block.matches(addr("[::ffff:10.1.2.3]:1")));
+
+        let host = parse("192.0.2.7");
+        assert!(host.matches(addr("192.0.2.7:9")));
+        assert!(!host.matches(addr("192.0.2.8:9")));
+
+        let v6 = parse("2001:db8::/32");
+        assert!(v6.matches(addr("[2001:db8:ffff::1]:1")));
+        assert!(!v6.matches(addr("[2001:db9::1]:1")));
+    }
+
+    #[test]
+    fn ports_and_ranges_bound_the_match() {
+        let range = parse("192.168.0.0/16:8000-8999");
+        assert!(range.matches(addr("192.168.1.1:8000")));
+        assert!(range.matches(addr("192.168.1.1:8999")));
+        assert!(!range.matches(addr("192.168.1.1:9000")));
+
+        let bracketed = parse("[2001:db8::/32]:443");
+        assert!(bracketed.matches(addr("[2001:db8::1]:443")));
+        assert!(!bracketed.matches(addr("[2001:db8::1]:80")));
+    }
+
+    #[test]
+    fn empty_policy_denies_everything_but_loopback() {
+        let policy = AllowedSocketAddrs::default();
+        assert!(!policy.permits(addr("203.0.113.9:80"), SocketAddrUse::TcpConnect, false));
+        assert!(!policy.permits(
+            addr("203.0.113.9:53"),
+            SocketAddrUse::UdpOutgoingDatagram,
+            false
+        ));
+        assert!(!policy.permits(addr("0.0.0.0:5353"), SocketAddrUse::UdpBind, false));
+        assert!(policy.permits(addr("127.0.0.1:8080"), SocketAddrUse::TcpConnect, false));
+        assert!(policy.permits(addr("[::1]:8080"), SocketAddrUse::UdpBind, false));
+        // A UDP client socket claims no port, so it is always allowed.
+        assert!(policy.permits(addr("0.0.0.0:0"), SocketAddrUse::UdpBind, false));
+    }
+
+    #[test]
+    fn connect_and_bind_rules_are_separate() {
+        let policy = policy(&["10.0.0.0/8:5432"], &["192.0.2.1:9000"]);
+        assert!(policy.permits(addr("10.0.0.5:5432"), SocketAddrUse::TcpConnect, false));
+        assert!(policy.permits(addr("10.0.0.5:5432"), SocketAddrUse::UdpConnect, false));
+        assert!(!policy.permits(addr("10.0.0.5:5432"), SocketAddrUse::UdpBind, false));
+        assert!(policy.permits(addr("192.0.2.1:9000"), SocketAddrUse::UdpBind, false));
+        assert!(!policy.permits(addr("192.0.2.1:9000"), SocketAddrUse::TcpConnect, false));
+    }
+
+    #[test]
+    fn only_a_service_may_listen_on_tcp() {
+        let policy = policy(&[], &["192.0.2.1:9000"]);
+        assert!(!policy.permits(addr("192.0.2.1:9000"), SocketAddrUse::TcpBind, false));
+        assert!(policy.permits(addr("192.0.2.1:9000"), SocketAddrUse::TcpBind, true));
+        assert!(!policy.permits(addr("127.0.0.1:9000"), SocketAddrUse::TcpBind, false));
+        assert!(policy.permits(addr("127.0.0.1:9000"), SocketAddrUse::TcpBind, true));
+    }
+
+    #[test]
+    fn loopback_is_governed_by_its_own_switch() {
+        let policy = AllowedSocketAddrs {
+            connect: vec![parse("*")],
+            bind: vec![parse("*")],
+            loopback: false,
+        };
+        assert!(!policy.permits(addr("127.0.0.1:8080"), SocketAddrUse::TcpConnect, true));
+        assert!(!policy.permits(addr("[::1]:8080"), SocketAddrUse::TcpBind, true));
+        assert!(policy.permits(addr("203.0.113.9:80"), SocketAddrUse::TcpConnect, true));
+    }
+
+    #[test]
+    fn malformed_entries_are_rejected() {
+        for entry in [
+            "",
+            "   ",
+            "example.com",
+            "10.0.0.0/33",
+            "10.0.0.1/8",
+            "10.0.0.0/8:http",
+            "10.0.0.0/8:9000-8000",
+            "10.0.0.0/8:70000",
+            "[2001:db8::/32",
+            "[2001:db8::/32]443",
+        ] {
+            assert!(
+                entry.parse::<AllowedSocketAddr>().is_err(),
+                "{entry:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn entries_round_trip_through_their_string_form() {
+        for entry in [
+            "*",
+            "*:443",
+            "10.0.0.0/8",
+            "10.1.2.3:5432",
+            "192.168.0.0/16:8000-8999",
+            "2001:db8::/32",
+            "[2001:db8::/32]:443",
+            "::1",
+        ] {
+            let parsed = parse(entry);
+            assert_eq!(parsed.to_string(), entry);
+            assert_eq!(
+                parsed.to_string().parse::<AllowedSocketAddr>().unwrap(),
+                parsed
+            );
+        }
+    }
+}
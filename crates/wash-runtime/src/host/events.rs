@@ -0,0 +1,74 @@
+//! Lifecycle events a host emits as its workloads and plugins change state.
+//!
+//! The host broadcasts every [`HostEvent`] to the receivers handed out by
+//! [`Host::subscribe_events`](crate::host::Host::subscribe_events); the washlet
+//! is one such subscriber, publishing each event to the operator over NATS.
+//!
+//! Workload transitions the host drives itself (start, stop, a reported
+//! failure) are broadcast directly. Changes that happen out of band — a
+//! service restarted by its supervisor, a host component plugin giving up on
+//! its driver — are reported on a [`HostEventSink`], which the host injects
+//! into the engine and into each plugin at start.
+
+/// A state change on a host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostEvent {
+    /// A workload was accepted and is being started.
+    WorkloadStarting {
+        /// The workload being started.
+        workload_id: String,
+    },
+    /// A workload started, or an update swapped in its new revision.
+    WorkloadRunning {
+        /// The running workload.
+        workload_id: String,
+    },
+    /// A workload failed to start, or failed while it was running.
+    WorkloadFailed {
+        /// The failed workload.
+        workload_id: String,
+        /// Human-readable cause, the same as the workload's status message.
+        reason: String,
+    },
+    /// A workload was stopped and removed from the host.
+    WorkloadStopped {
+        /// The stopped workload.
+        workload_id: String,
+    },
+    /// A workload's service faulted and was restarted by its supervisor.
+    ComponentRestarted {
+        /// The workload the service belongs to.
+        workload_id: String,
+        /// The restarted service component.
+        component_id: String,
+        /// Why the previous incarnation ended.
+        reason: String,
+    },
+    /// A host plugin's driver faulted past its restart budget and was not
+    /// restarted again.
+    PluginCrashLooped {
+        /// The plugin that gave up.
+        plugin_id: String,
+        /// How many restarts it went through before giving up.
+        restarts: u32,
+    },
+}
+
+/// A cheap, cloneable handle the engine and plugins use to report a
+/// [`HostEvent`] that happened out of band. The host drains it on a background
+/// task and broadcasts each event to its subscribers.
+#[derive(Clone)]
+pub struct HostEventSink(tokio::sync::mpsc::UnboundedSender<HostEvent>);
+
+impl HostEventSink {
+    /// Wrap the sender end of the host's event channel.
+    pub fn new(tx: tokio::sync::mpsc::UnboundedSender<HostEvent>) -> Self {
+        Self(tx)
+    }
+
+    /// Report `event`. Non-blocking and infallible from the caller's view: if
+    /// the host has torn down the receiver, the event is dropped.
+    pub fn emit(&self, event: HostEvent) {
+        let _ = self.0.send(event);
+    }
+}
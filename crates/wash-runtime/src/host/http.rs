@@ -4,7 +4,8 @@
 //! WebAssembly components to handle HTTP requests. It provides a complete HTTP
 //! server implementation with support for:
 //!
-//! - Virtual hosting based on Host headers
+//! - Virtual hosting based on Host headers, with path-prefix, header and
+//!   method route rules (see [`crate::host::http_route`])
 //! - TLS/HTTPS connections
 //! - Component isolation per request
 //! - Graceful shutdown capabilities
@@ -19,9 +20,8 @@
 //! ```
 
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::HashMap,
     net::SocketAddr,
-    path::Path,
     sync::{Arc, OnceLock},
     time::Duration,
 };
@@ -29,6 +29,12 @@ use std::{
 use arc_swap::ArcSwap;
 
 use crate::host::allowed_hosts::AllowedHost;
+use crate::host::http_admission::{Admission, AdmissionPermit, AdmissionPolicy, Rejection};
+use crate::host::http_limits::{
+    ActiveRequest, ConnActivity, IngressBody, IngressLimits, LimitExceeded, WorkloadLimits,
+};
+use crate::host::http_route::RouteRule;
+use crate::host::http_tls::{self, CertSource, DEFAULT_RELOAD_INTERVAL, SniResolver};
 use crate::host::trigger_service::{BrokerMessage, MessagingJob};
 use crate::{engine::ctx::SharedCtx, observability::Meters};
 use crate::{engine::workload::ResolvedWorkload, observability::FuelConsumptionMeter};
@@ -61,9 +67,10 @@ use wasmtime_wasi_http::{
 };
 
 use rustls::ServerConfig;
-use rustls::pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
+use rustls::pki_types::{CertificateDer, pem::PemObject};
 use tokio::sync::{RwLock, mpsc};
 use tokio_rustls::TlsAcceptor;
+use tokio_util::either::Either;
 
 /// Validates a hostname according to RFC 1123.
 fn is_valid_hostname(host: &str) -> bool {
@@ -125,6 +132,14 @@ pub enum RouteError {
     /// `DynamicRouter` passes the offending host header; `DevRouter` is
     /// host-agnostic and passes an empty string. Maps to 404.
     NoWorkloadForHost(String),
+    /// Workloads are bound to the host, but none of their route rules
+    /// matches the request. Maps to 404.
+    NoRouteForRequest {
+        /// The request's host.
+        host: String,
+        /// The request's path.
+        path: String,
+    },
     /// Router is momentarily unable to read its routing table (lock
     /// contention under heavy load). Retrying should succeed. Maps to 503.
     Unavailable,
@@ -135,7 +150,7 @@ impl RouteError {
     pub fn status(&self) -> u16 {
         match self {
             Self::MissingHost => 400,
-            Self::NoWorkloadForHost(_) => 404,
+            Self::NoWorkloadForHost(_) | Self::NoRouteForRequest { .. } => 404,
             Self::Unavailable => 503,
         }
     }
@@ -152,6 +167,9 @@ impl std::fmt::Display for RouteError {
                 write!(f, "no workload registered")
             }
             Self::NoWorkloadForHost(host) => write!(f, "no workload bound to host {host:?}"),
+            Self::NoRouteForRequest { host, path } => {
+                write!(f, "no route on host {host:?} matches {path:?}")
+            }
             Self::Unavailable => write!(f, "router is temporarily unavailable"),
         }
     }
@@ -218,7 +236,8 @@ pub trait Router: Send + Sync + 'static {
     ) -> Result<String, RouteError>;
 }
 
-/// Router that routes requests by 'Host' header, configured via WitInterface config
+/// Router that routes requests by 'Host' header, then by each bound workload's
+/// [`RouteRule`], configured via WitInterface config
 #[derive(Default)]
 pub struct DynamicRouter {
     /// Routing tables behind a single [`ArcSwap`] so the per-request read in
@@ -233,62 +252,110 @@ pub struct DynamicRouter {
 /// reader never sees the forward and reverse maps disagree.
 #[derive(Default, Clone)]
 struct Routes {
-    /// Maps a hostname to every workload replica bound to it. A `BTreeSet` keeps
-    /// membership ordered and deterministic; a request picks one replica at
-    /// random (see [`DynamicRouter::select_workload`]).
-    host_to_workload: HashMap<String, BTreeSet<String>>,
+    /// Maps a hostname to every workload replica bound to it, with the rule
+    /// each serves under. Kept sorted by workload id so membership is ordered
+    /// and deterministic; a request picks one matching replica at random (see
+    /// [`DynamicRouter::select_workload`]).
+    host_to_workload: HashMap<String, Vec<BoundRoute>>,
     /// Maps workload_id -> all hostnames (primary + aliases) registered for it,
     /// so `on_workload_unbind` can remove all entries cleanly.
     workload_to_host: HashMap<String, Vec<String>>,
 }
 
+/// One replica bound to a hostname, and the rule it serves under there.
+#[derive(Clone)]
+struct BoundRoute {
+    workload_id: String,
+    rule: Arc<RouteRule>,
+}
+
 impl DynamicRouter {
-    /// Register `workload_id` under every hostname in `hosts`, updating both the
-    /// forward (host -> replicas) and reverse (workload -> hosts) maps so
-    /// [`Router::on_workload_unbind`] can later remove every entry cleanly.
-    /// Idempotent: re-registering the same workload (e.g. a service restart)
-    /// leaves the tables unchanged.
-    fn register_hostnames(&self, workload_id: &str, hosts: &[String]) {
+    /// Register `workload_id` under every hostname in `hosts` with `rule`,
+    /// updating both the forward (host -> replicas) and reverse (workload ->
+    /// hosts) maps so [`Router::on_workload_unbind`] can later remove every
+    /// entry cleanly. Idempotent: re-registering the same workload (e.g. a
+    /// service restart) replaces its entries rather than adding to them.
+    fn register_hostnames(&self, workload_id: &str, hosts: &[String], rule: RouteRule) {
+        let rule = Arc::new(rule);
         self.routes.rcu(|cur| {
             let mut routes = (**cur).clone();
-            routes
+            // Re-registering a workload (a restart, or an update swapping in
+            // its new revision) replaces the hostnames it had before.
+            let previous = routes
                 .workload_to_host
-                .insert(workload_id.to_string(), hosts.to_vec());
+                .insert(workload_id.to_string(), hosts.to_vec())
+                .unwrap_or_default();
+            for host in previous.iter().filter(|host| !hosts.contains(host)) {
+                if let Some(bound) = routes.host_to_workload.get_mut(host) {
+                    bound.retain(|route| route.workload_id != workload_id);
+                    if bound.is_empty() {
+                        routes.host_to_workload.remove(host);
+                    }
+                }
+            }
             for host in hosts {
-                routes
-                    .host_to_workload
-                    .entry(host.clone())
-                    .or_default()
-                    .insert(workload_id.to_string());
+                let bound = routes.host_to_workload.entry(host.clone()).or_default();
+                bound.retain(|route| route.workload_id != workload_id);
+                let at = bound.partition_point(|route| route.workload_id.as_str() < workload_id);
+                bound.insert(
+                    at,
+                    BoundRoute {
+                        workload_id: workload_id.to_string(),
+                        rule: Arc::clone(&rule),
+                    },
+                );
             }
             routes
         });
     }
 
-    /// Pick one replica bound to `host` at random so requests fan out across
-    /// every replica instead of pinning to one. A per-thread PRNG
-    /// ([`fastrand`]) avoids the cross-core cache-line contention a shared
-    /// atomic cursor would incur under concurrent load, and spreads load just as
-    /// evenly in aggregate. Split out from [`Router::route_incoming_request`] so
-    /// the selection logic is unit-testable without constructing a
-    /// [`hyper::body::Incoming`].
-    fn select_workload(&self, host: &str) -> Result<String, RouteError> {
+    /// Pick the replica bound to `host` that serves `req`: among the replicas
+    /// whose rules match it, those with the most specific rule (see
+    /// [`crate::host::http_route`]), one at random in proportion to its
+    /// weight, so requests fan out across every replica instead of pinning to
+    /// one. A per-thread PRNG ([`fastrand`]) avoids the cross-core cache-line
+    /// contention a shared atomic cursor would incur under concurrent load,
+    /// and spreads load just as evenly in aggregate. Split out from
+    /// [`Router::route_incoming_request`] so the selection logic is
+    /// unit-testable without constructing a [`hyper::body::Incoming`].
+    fn select_workload<B>(
+        &self,
+        host: &str,
+        req: &hyper::Request<B>,
+    ) -> Result<String, RouteError> {
         // Lock-free read of a routing-table snapshot.
         let routes = self.routes.load();
-        let Some(workload_set) = routes.host_to_workload.get(host) else {
-            return Err(RouteError::NoWorkloadForHost(host.to_string()));
-        };
         // An entry can exist but be empty; treat that as "no workload bound"
-        // (same 404) and, importantly, keep the range below non-empty.
-        if workload_set.is_empty() {
-            return Err(RouteError::NoWorkloadForHost(host.to_string()));
+        // (same 404).
+        let bound = match routes.host_to_workload.get(host) {
+            Some(bound) if !bound.is_empty() => bound,
+            _ => return Err(RouteError::NoWorkloadForHost(host.to_string())),
+        };
+        let no_route = || RouteError::NoRouteForRequest {
+            host: host.to_string(),
+            path: req.uri().path().to_string(),
+        };
+
+        // Three passes over the (short) replica list rather than collecting
+        // the candidates, so routing allocates nothing.
+        let matching = || bound.iter().filter(|route| route.rule.matches(req));
+        let best = matching()
+            .map(|route| route.rule.specificity())
+            .max()
+            .ok_or_else(no_route)?;
+        let candidates = || matching().filter(move |route| route.rule.specificity() == best);
+        let total: u64 = candidates()
+            .map(|route| u64::from(route.rule.weight()))
+            .sum();
+        let mut pick = fastrand::u64(..total);
+        for route in candidates() {
+            let weight = u64::from(route.rule.weight());
+            if pick < weight {
+                return Ok(route.workload_id.clone());
+            }
+            pick -= weight;
         }
-        let idx = fastrand::usize(..workload_set.len());
-        let workload_id = workload_set
-            .iter()
-            .nth(idx)
-            .ok_or_else(|| RouteError::NoWorkloadForHost(host.to_string()))?;
-        Ok(workload_id.clone())
+        Err(no_route())
     }
 }
 
@@ -322,6 +389,8 @@ impl Router for DynamicRouter {
             "primary host {primary_host:?} is not a valid RFC 1123 hostname"
         );
 
+        let rule = RouteRule::from_config(&http_iface.config).context("invalid route rule")?;
+
         // Collect primary hostname plus any DNS aliases injected by the operator.
         // Aliases are a comma-separated list of Service DNS names (e.g.
         // "my-svc,my-svc.default,my-svc.default.svc,my-svc.default.svc.cluster.local")
@@ -336,7 +405,7 @@ impl Router for DynamicRouter {
             );
         }
 
-        self.register_hostnames(resolved_handle.id(), &all_hosts);
+        self.register_hostnames(resolved_handle.id(), &all_hosts, rule);
 
         Ok(())
     }
@@ -348,7 +417,8 @@ impl Router for DynamicRouter {
     ) -> anyhow::Result<()> {
         // A service-only workload (a p3 trigger service serving HTTP) reaches
         // routing here rather than through `on_workload_resolved`. Register its
-        // hostnames exactly like a component workload so requests resolve to it.
+        // hostnames like a component workload so requests resolve to it. The
+        // service serves every path on them.
         if hostnames.is_empty() {
             // debug, not warn: a service restart re-resolves, so a misconfigured one would spam.
             debug!(
@@ -357,7 +427,7 @@ impl Router for DynamicRouter {
             );
             return Ok(());
         }
-        self.register_hostnames(workload_id, hostnames);
+        self.register_hostnames(workload_id, hostnames, RouteRule::default());
         Ok(())
     }
 
@@ -366,9 +436,9 @@ impl Router for DynamicRouter {
             let mut routes = (**cur).clone();
             if let Some(hostnames) = routes.workload_to_host.remove(workload_id) {
                 for hostname in &hostnames {
-                    if let Some(workload_set) = routes.host_to_workload.get_mut(hostname) {
-                        workload_set.remove(workload_id);
-                        if workload_set.is_empty() {
+                    if let Some(bound) = routes.host_to_workload.get_mut(hostname) {
+                        bound.retain(|route| route.workload_id != workload_id);
+                        if bound.is_empty() {
                             routes.host_to_workload.remove(hostname);
                         }
                     }
@@ -390,7 +460,8 @@ impl Router for DynamicRouter {
     }
 
     /// Pick a workload ID based on the incoming request, spreading load at
-    /// random across every replica bound to the request's `Host`.
+    /// random across the replicas bound to the request's `Host` whose route
+    /// rules match it most specifically.
     fn route_incoming_request(
         &self,
         req: &hyper::Request<hyper::body::Incoming>,
@@ -404,7 +475,7 @@ impl Router for DynamicRouter {
         // `select_workload` does a lock-free `ArcSwap` load and an in-memory
         // lookup, so it runs inline on the async worker — no `block_in_place`
         // needed (and routing works on any runtime flavor).
-        self.select_workload(workload_host)
+        self.select_workload(workload_host, req)
     }
 }
 
@@ -894,10 +965,15 @@ impl HostHandler for NullServer {
 pub type WorkloadHandles =
     Arc<RwLock<HashMap<String, (ResolvedWorkload, InstancePre<SharedCtx>, String)>>>;
 
+/// A map from workload id to its admission state, for the workloads whose
+/// incoming-handler config limits their traffic (see
+/// [`crate::host::http_admission`]).
+pub(crate) type WorkloadAdmission = Arc<RwLock<HashMap<String, Arc<Admission>>>>;
+
 /// An inbound HTTP request routed to a long-lived service instance, paired with
 /// a oneshot for its response.
 pub type ServiceHttpJob = (
-    hyper::Request<hyper::body::Incoming>,
+    hyper::Request<IngressBody>,
     tokio::sync::oneshot::Sender<anyhow::Result<hyper::Response<HyperOutgoingBody>>>,
 );
 
@@ -909,6 +985,17 @@ pub type ServiceHandlers = Arc<RwLock<HashMap<String, tokio::sync::mpsc::Sender<
 /// instance. Empty unless a workload's service exports a messaging handler.
 pub type MessagingHandlers = Arc<RwLock<HashMap<String, tokio::sync::mpsc::Sender<MessagingJob>>>>;
 
+/// What every request on an ingress's connections consults to find, admit and
+/// serve its workload, shared by the accept loop and each request.
+#[derive(Clone)]
+struct IngressState {
+    workload_handles: WorkloadHandles,
+    service_handlers: ServiceHandlers,
+    admission: WorkloadAdmission,
+    limits: Arc<WorkloadLimits>,
+    meters: Meters,
+}
+
 /// The host's HTTP ingress: it owns the listening socket and routes each
 /// inbound request to a workload by virtual host — either to a per-request
 /// `wasi:http/incoming-handler` instance or, when the workload runs a
@@ -938,10 +1025,14 @@ pub struct Ingress<T: Router, O: OutgoingHandler = DefaultOutgoingHandler> {
     workload_handles: WorkloadHandles,
     /// Workloads whose long-lived service serves HTTP ingress directly.
     service_handlers: ServiceHandlers,
+    admission: WorkloadAdmission,
+    limits: Arc<WorkloadLimits>,
     /// Workloads whose long-lived trigger service serves messaging ingress directly.
     messaging_handlers: MessagingHandlers,
     shutdown_tx: Arc<RwLock<Option<mpsc::Sender<()>>>>,
     tls_acceptor: Option<TlsAcceptor>,
+    /// The acceptor's certificates, which workloads can add to.
+    tls_certs: Option<Arc<SniResolver>>,
     listener: Arc<tokio::sync::Mutex<Option<TcpListener>>>,
     meters: RwLock<Meters>,
     /// h2 (ALPN) variant of the outgoing handler's client TLS configuration,
@@ -956,25 +1047,54 @@ impl<T: Router, O: OutgoingHandler> std::fmt::Debug for Ingress<T, O> {
 }
 
 /// TLS configuration for [`IngressBuilder::tls`] / [`Ingress::new_with_tls`].
+///
+/// Certificates are chosen per connection by SNI and reloaded when their files
+/// change; see [`crate::host::http_tls`].
 #[derive(Debug, Clone)]
 pub struct TlsConfig {
-    cert_path: std::path::PathBuf,
-    key_path: std::path::PathBuf,
+    default_cert: Option<CertSource>,
+    cert_dir: Option<std::path::PathBuf>,
     ca_path: Option<std::path::PathBuf>,
+    reload_interval: Duration,
 }
 
 impl TlsConfig {
+    /// Serve the certificate at `cert_path` by default.
     pub fn new(
         cert_path: impl Into<std::path::PathBuf>,
         key_path: impl Into<std::path::PathBuf>,
     ) -> Self {
         Self {
-            cert_path: cert_path.into(),
-            key_path: key_path.into(),
+            default_cert: Some(CertSource::new(cert_path, key_path)),
+            cert_dir: None,
+            ca_path: None,
+            reload_interval: DEFAULT_RELOAD_INTERVAL,
+        }
+    }
+
+    /// Serve the certificates in `dir` with no default certificate, so a
+    /// client whose SNI none of them covers fails its handshake.
+    pub fn from_cert_dir(dir: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            default_cert: None,
+            cert_dir: Some(dir.into()),
             ca_path: None,
+            reload_interval: DEFAULT_RELOAD_INTERVAL,
         }
     }
 
+    /// Also serve the certificates in `dir`, by the names they cover.
+    pub fn with_cert_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.cert_dir = Some(dir.into());
+        self
+    }
+
+    /// How often to check the certificate files for changes.
+    pub fn with_reload_interval(mut self, interval: Duration) -> Self {
+        self.reload_interval = interval;
+        self
+    }
+
     pub fn with_ca(mut self, ca_path: impl Into<std::path::PathBuf>) -> Self {
         self.ca_path = Some(ca_path.into());
         self
@@ -989,6 +1109,8 @@ impl TlsConfig {
 /// # Optional
 /// - [`outgoing_handler`](Self::outgoing_handler) — defaults to [`DefaultOutgoingHandler`].
 /// - [`tls`](Self::tls) — enables HTTPS.
+/// - [`limits`](Self::limits) — request size limits and timeouts; defaults to
+///   [`IngressLimits::default`].
 ///
 /// # Example
 /// ```rust,ignore
@@ -1009,6 +1131,7 @@ pub struct IngressBuilder<T: Router, O: OutgoingHandler = DefaultOutgoingHandler
     outgoing_handler: O,
     addr: SocketAddr,
     tls: Option<TlsConfig>,
+    limits: IngressLimits,
 }
 
 impl<T: Router> IngressBuilder<T, DefaultOutgoingHandler> {
@@ -1018,6 +1141,7 @@ impl<T: Router> IngressBuilder<T, DefaultOutgoingHandler> {
             outgoing_handler: DefaultOutgoingHandler::default(),
             addr,
             tls: None,
+            limits: IngressLimits::default(),
         }
     }
 }
@@ -1031,6 +1155,7 @@ impl<T: Router, O: OutgoingHandler> IngressBuilder<T, O> {
             outgoing_handler: handler,
             addr: self.addr,
             tls: self.tls,
+            limits: self.limits,
         }
     }
 
@@ -1040,16 +1165,24 @@ impl<T: Router, O: OutgoingHandler> IngressBuilder<T, O> {
         self
     }
 
+    /// Set the host-wide request limits, which workloads can override in their
+    /// incoming-handler config (see [`crate::host::http_limits`]).
+    pub fn limits(mut self, limits: IngressLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Bind to the address and build the [`Ingress`].
     pub async fn build(self) -> anyhow::Result<Ingress<T, O>> {
         crate::init_crypto();
-        let tls_acceptor = match &self.tls {
+        self.limits.validate().context("invalid ingress limits")?;
+        let (tls_acceptor, tls_certs) = match &self.tls {
             Some(tls) => {
-                let config =
-                    load_tls_config(&tls.cert_path, &tls.key_path, tls.ca_path.as_deref()).await?;
-                Some(TlsAcceptor::from(Arc::new(config)))
+                let (config, certs) = load_tls_config(tls).await?;
+                tokio::spawn(http_tls::watch(Arc::downgrade(&certs), tls.reload_interval));
+                (Some(TlsAcceptor::from(Arc::new(config))), Some(certs))
             }
-            None => None,
+            None => (None, None),
         };
 
         let listener = TcpListener::bind(self.addr).await?;
@@ -1061,9 +1194,12 @@ impl<T: Router, O: OutgoingHandler> IngressBuilder<T, O> {
             addr,
             workload_handles: Arc::default(),
             service_handlers: Arc::default(),
+            admission: Arc::default(),
+            limits: Arc::new(WorkloadLimits::new(self.limits)),
             messaging_handlers: Arc::default(),
             shutdown_tx: Arc::new(RwLock::new(None)),
             tls_acceptor,
+            tls_certs,
             listener: Arc::new(tokio::sync::Mutex::new(Some(listener))),
             meters: Default::default(),
             grpc_tls: OnceLock::new(),
@@ -1129,8 +1265,6 @@ impl<T: Router, O: OutgoingHandler> HostHandler for Ingress<T, O> {
         let addr = self.addr;
         let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
         let shutdown_tx_clone = self.shutdown_tx.clone();
-        let workload_handles = self.workload_handles.clone();
-        let service_handlers = self.service_handlers.clone();
         let tls_acceptor = self.tls_acceptor.clone();
 
         // Store the shutdown sender
@@ -1151,18 +1285,16 @@ impl<T: Router, O: OutgoingHandler> HostHandler for Ingress<T, O> {
         // Start the HTTP server, any incoming requests call Host::handle and then it's routed
         // to the workload based on host header.
         let handler = self.router.clone();
-        let fuel_meter = self.meters.read().await.fuel_consumption.clone();
+        let state = IngressState {
+            workload_handles: self.workload_handles.clone(),
+            service_handlers: self.service_handlers.clone(),
+            admission: self.admission.clone(),
+            limits: self.limits.clone(),
+            meters: self.meters.read().await.clone(),
+        };
         tokio::spawn(async move {
-            if let Err(e) = run_http_server(
-                listener,
-                handler,
-                workload_handles,
-                service_handlers,
-                &mut shutdown_rx,
-                tls_acceptor,
-                fuel_meter,
-            )
-            .await
+            if let Err(e) =
+                run_http_server(listener, handler, state, &mut shutdown_rx, tls_acceptor).await
             {
                 error!(err = ?e, addr = ?addr, "HTTP server error");
             }
@@ -1188,11 +1320,65 @@ impl<T: Router, O: OutgoingHandler> HostHandler for Ingress<T, O> {
         resolved_handle: &ResolvedWorkload,
         component_id: &str,
     ) -> anyhow::Result<()> {
+        let http_config = resolved_handle
+            .host_interfaces()
+            .iter()
+            .find(|iface| iface.is_incoming_http_handler())
+            .map(|iface| &iface.config);
+        let policy = http_config
+            .map(AdmissionPolicy::from_config)
+            .transpose()
+            .context("invalid admission policy")?
+            .unwrap_or_default();
+        let limits = http_config
+            .map(|config| self.limits.host().for_workload(config))
+            .transpose()
+            .context("invalid request limits")?;
+        let tls_cert = http_config
+            .map(CertSource::from_config)
+            .transpose()
+            .context("invalid TLS certificate")?
+            .flatten();
         self.router
             .on_workload_resolved(resolved_handle, component_id)
             .await?;
         let instance_pre = resolved_handle.instantiate_pre(component_id).await?;
 
+        // Load the workload's certificate last of all that can fail, so a
+        // failed resolve doesn't leave it served.
+        match (&self.tls_certs, tls_cert) {
+            (Some(certs), Some(source)) => {
+                let certs = certs.clone();
+                let workload_id = resolved_handle.id().to_string();
+                let hostnames = http_ingress_hostnames(resolved_handle.host_interfaces());
+                tokio::task::spawn_blocking(move || {
+                    certs.insert_workload(&workload_id, hostnames, source)
+                })
+                .await
+                .context("TLS certificate loading panicked")?
+                .context("invalid TLS certificate")?;
+            }
+            (None, Some(_)) => warn!(
+                workload_id = resolved_handle.id(),
+                "ignoring the workload's TLS certificate: the HTTP ingress does not terminate TLS"
+            ),
+            // A revision swapped in by an update drops its predecessor's.
+            (Some(certs), None)
+                if self
+                    .workload_handles
+                    .read()
+                    .await
+                    .contains_key(resolved_handle.id()) =>
+            {
+                let certs = certs.clone();
+                let workload_id = resolved_handle.id().to_string();
+                tokio::task::spawn_blocking(move || certs.remove_workload(&workload_id))
+                    .await
+                    .context("TLS certificate reload panicked")?;
+            }
+            (_, None) => {}
+        }
+
         // Tell the egress transport how much concurrency this component
         // declared, before it serves anything: its outbound connection burst
         // scales with the calls it runs at once, and a pool built without
@@ -1205,6 +1391,21 @@ impl<T: Router, O: OutgoingHandler> HostHandler for Ingress<T, O> {
         // Only components that export wasi:http are routable HTTP entrypoints.
         // Anything else stays unregistered and routes to a 404.
         if crate::engine::exports_wasi_http(instance_pre.component()) {
+            // Insert or clear each setting, so a revision swapped in by an
+            // update under the same workload ID doesn't inherit its
+            // predecessor's.
+            if policy.is_unlimited() {
+                self.admission.write().await.remove(resolved_handle.id());
+            } else {
+                self.admission.write().await.insert(
+                    resolved_handle.id().to_string(),
+                    Arc::new(Admission::new(policy)),
+                );
+            }
+            match limits {
+                Some(limits) => self.limits.insert(resolved_handle.id(), limits).await,
+                None => self.limits.remove(resolved_handle.id()).await,
+            }
             self.workload_handles.write().await.insert(
                 resolved_handle.id().to_string(),
                 (
@@ -1223,6 +1424,14 @@ impl<T: Router, O: OutgoingHandler> HostHandler for Ingress<T, O> {
 
         self.workload_handles.write().await.remove(workload_id);
         self.service_handlers.write().await.remove(workload_id);
+        self.admission.write().await.remove(workload_id);
+        self.limits.remove(workload_id).await;
+        if let Some(certs) = self.tls_certs.clone() {
+            let workload_id = workload_id.to_string();
+            tokio::task::spawn_blocking(move || certs.remove_workload(&workload_id))
+                .await
+                .context("TLS certificate reload panicked")?;
+        }
         self.messaging_handlers.write().await.remove(workload_id);
         // Drop the stopped workload's egress state (pooled connections, TLS
         // session store, pinned connection permits) instead of letting it
@@ -1420,12 +1629,11 @@ fn prepare_accepted_conn(stream: &TcpStream) {
 async fn run_http_server<T: Router>(
     listener: TcpListener,
     handler: Arc<T>,
-    workload_handles: WorkloadHandles,
-    service_handlers: ServiceHandlers,
+    state: IngressState,
     shutdown_rx: &mut mpsc::Receiver<()>,
     tls_acceptor: Option<TlsAcceptor>,
-    fuel_meter: FuelConsumptionMeter,
 ) -> anyhow::Result<()> {
+    let host_limits = *state.limits.host();
     loop {
         tokio::select! {
             // Handle shutdown signal
@@ -1441,53 +1649,81 @@ async fn run_http_server<T: Router>(
 
                         prepare_accepted_conn(&client);
 
-                        let handles_clone = workload_handles.clone();
-                        let service_handlers_clone = service_handlers.clone();
+                        let state_clone = state.clone();
                         let tls_acceptor_clone = tls_acceptor.clone();
                         let handler_clone = handler.clone();
-                        let fuel_meter = fuel_meter.clone();
                         tokio::spawn(async move {
-                            let service = hyper::service::service_fn(move |req| {
-                                let handles = handles_clone.clone();
-                                let service_handlers = service_handlers_clone.clone();
-                                let handler = handler_clone.clone();
-                                let fuel_meter = fuel_meter.clone();
-                                async move {
-                                    let extractor = opentelemetry_http::HeaderExtractor(req.headers());
-                                    let remote_context =
-                                        opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(&extractor));
-
-                                    handle_http_request(handler, req, handles, service_handlers, fuel_meter).with_context(remote_context).await
+                            let activity = ConnActivity::new();
+                            let service = hyper::service::service_fn({
+                                let activity = activity.clone();
+                                move |req| {
+                                    let active = activity.begin();
+                                    let state = state_clone.clone();
+                                    let handler = handler_clone.clone();
+                                    async move {
+                                        let extractor = opentelemetry_http::HeaderExtractor(req.headers());
+                                        let remote_context =
+                                            opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(&extractor));
+
+                                        handle_http_request(handler, req, client_addr, state, active).with_context(remote_context).await
+                                    }
                                 }
                             });
 
                             let mut builder = auto::Builder::new(TokioExecutor::new());
                             builder
                                 .http1()
-                                .keep_alive(true);
+                                .keep_alive(true)
+                                .timer(TokioTimer::new())
+                                .header_read_timeout(host_limits.header_read_timeout)
+                                .max_buf_size(host_limits.http1_buffer());
                             builder
                                 .http2()
                                 .timer(TokioTimer::new())
-                                .keep_alive_interval(Some(Duration::from_secs(20)));
-
-                            let result = if let Some(acceptor) = tls_acceptor_clone {
-                                // Handle HTTPS connection
-                                match acceptor.accept(client).await {
-                                    Ok(tls_stream) => {
-                                        builder
-                                            .serve_connection_with_upgrades(TokioIo::new(tls_stream), service)
-                                            .await
-                                    }
-                                    Err(e) => {
+                                .keep_alive_interval(Some(Duration::from_secs(20)))
+                                .max_header_list_size(
+                                    u32::try_from(host_limits.max_header_bytes).unwrap_or(u32::MAX),
+                                );
+
+                            let io = if let Some(acceptor) = tls_acceptor_clone {
+                                // Handle HTTPS connection. The handshake comes
+                                // before any headers, so it gets the same
+                                // deadline; dropping the stream closes it.
+                                let handshake = acceptor.accept(client);
+                                let handshake = match host_limits.header_read_timeout {
+                                    Some(timeout) => tokio::time::timeout(timeout, handshake).await,
+                                    None => Ok(handshake.await),
+                                };
+                                match handshake {
+                                    Ok(Ok(tls_stream)) => Either::Left(tls_stream),
+                                    Ok(Err(e)) => {
                                         error!(addr = ?client_addr, err = ?e, "TLS handshake failed");
                                         return;
                                     }
+                                    Err(_) => {
+                                        debug!(addr = ?client_addr, "closing connection: TLS handshake timed out");
+                                        return;
+                                    }
                                 }
                             } else {
                                 // Handle HTTP/h2c connection
-                                builder
-                                    .serve_connection_with_upgrades(TokioIo::new(client), service)
-                                    .await
+                                Either::Right(client)
+                            };
+                            let conn = builder.serve_connection_with_upgrades(TokioIo::new(io), service);
+                            let result = match host_limits.idle_timeout {
+                                Some(idle_timeout) => {
+                                    tokio::pin!(conn);
+                                    tokio::select! {
+                                        result = conn.as_mut() => result,
+                                        // Let whatever is mid-flight finish, then close.
+                                        _ = activity.idle_for(idle_timeout) => {
+                                            debug!(addr = ?client_addr, "closing idle HTTP connection");
+                                            conn.as_mut().graceful_shutdown();
+                                            conn.await
+                                        }
+                                    }
+                                }
+                                None => conn.await,
                             };
 
                             if let Err(e) = result {
@@ -1516,6 +1752,16 @@ fn error_response(status: u16) -> hyper::Response<HyperOutgoingBody> {
         .expect("building HTTP response with valid status code should never fail")
 }
 
+/// Build the response for a request admission control turned away.
+#[allow(clippy::expect_used)]
+fn rejection_response(rejection: Rejection) -> hyper::Response<HyperOutgoingBody> {
+    hyper::Response::builder()
+        .status(rejection.status())
+        .header(hyper::header::RETRY_AFTER, rejection.retry_after_secs())
+        .body(HyperOutgoingBody::default())
+        .expect("building HTTP response with valid status code should never fail")
+}
+
 /// Handle individual HTTP requests by looking up workload and invoking component
 ///
 /// HTTP request attributes are emitted under both the current-stable OTel HTTP
@@ -1548,10 +1794,17 @@ fn error_response(status: u16) -> hyper::Response<HyperOutgoingBody> {
 async fn handle_http_request<T: Router>(
     handler: Arc<T>,
     req: hyper::Request<hyper::body::Incoming>,
-    workload_handles: WorkloadHandles,
-    service_handlers: ServiceHandlers,
-    fuel_meter: FuelConsumptionMeter,
+    client_addr: SocketAddr,
+    state: IngressState,
+    active: ActiveRequest,
 ) -> Result<hyper::Response<HyperOutgoingBody>, hyper::Error> {
+    let IngressState {
+        workload_handles,
+        service_handlers,
+        admission,
+        limits,
+        meters,
+    } = state;
     let method = req.method().clone();
     let uri = req.uri().clone();
 
@@ -1578,29 +1831,103 @@ async fn handle_http_request<T: Router>(
         "HTTP request received"
     );
 
+    let limits = limits.get(&workload_id).await;
+    let deadline = limits.deadline();
+    if let Err(exceeded) = limits.check_head(&req) {
+        return Ok(limit_response(&workload_id, exceeded, &meters));
+    }
+
+    // Turn the request away before it costs the workload anything if its
+    // admission policy says so.
+    let admission = admission.read().await.get(&workload_id).cloned();
+    let permit = match admission.map(|a| a.admit(&req, client_addr)).transpose() {
+        Ok(permit) => permit,
+        Err(rejection) => {
+            debug!(host = %workload_id, reason = rejection.reason(), "request rejected by admission control");
+            meters.http_rejections.record(&[
+                KeyValue::new("workload.id", workload_id),
+                KeyValue::new("reason", rejection.reason()),
+                KeyValue::new(HTTP_RESPONSE_STATUS_CODE, i64::from(rejection.status())),
+            ]);
+            let resp = rejection_response(rejection);
+            record_response_status(&resp);
+            return Ok(resp);
+        }
+    };
+
+    let (req, body) = limits.limit_body(req);
+    let dispatch = dispatch_request(
+        &workload_id,
+        req,
+        workload_handles,
+        service_handlers,
+        meters.fuel_consumption.clone(),
+    );
+    let response = match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, dispatch).await.ok(),
+        None => Some(dispatch.await),
+    };
+    // A guest answering a body it never saw the end of is overruled, as is one
+    // that didn't answer in time.
+    let exceeded = match response {
+        _ if body.is_too_large() => Err(LimitExceeded::BodyTooLarge),
+        Some(response) => Ok(response),
+        None => Err(LimitExceeded::Timeout {
+            body_complete: body.is_complete(),
+        }),
+    };
+    let response = match exceeded {
+        Ok(response) => response,
+        Err(exceeded) => return Ok(limit_response(&workload_id, exceeded, &meters)),
+    };
+
+    record_response_status(&response);
+    // Carry the current span on the response body so `http.response.body.size`
+    // is recorded once the HTTP server finishes streaming the body. That
+    // happens after this handler future — and its `#[instrument]` span — has
+    // returned, so the body wrapper keeps a span handle alive to land the
+    // attribute before the span closes.
+    // The body also holds the admission permit and keeps the request active
+    // on its connection, so the request counts as in flight until its
+    // response has been sent, and is cut off at its deadline.
+    let response = response.map(|body| {
+        MeteredBody::new(body, tracing::Span::current())
+            .holding(permit, active)
+            .until(deadline)
+            .boxed_unsync()
+    });
+    Ok(response)
+}
+
+/// Hand a routed request to its workload: the workload's long-lived service
+/// if it serves HTTP, otherwise a component instance.
+async fn dispatch_request(
+    workload_id: &str,
+    req: hyper::Request<IngressBody>,
+    workload_handles: WorkloadHandles,
+    service_handlers: ServiceHandlers,
+    fuel_meter: FuelConsumptionMeter,
+) -> hyper::Response<HyperOutgoingBody> {
     // If this workload's long-lived service serves HTTP, deliver the request to
     // it (preserving its in-memory state) instead of the per-request path.
-    let service_sender = service_handlers.read().await.get(&workload_id).cloned();
+    let service_sender = service_handlers.read().await.get(workload_id).cloned();
     if let Some(sender) = service_sender {
         let (resp_tx, resp_rx) = tokio::sync::oneshot::channel();
-        let response = if sender.send((req, resp_tx)).await.is_err() {
+        if sender.send((req, resp_tx)).await.is_err() {
             error!(host = %workload_id, "service HTTP instance is not running");
-            error_response(503)
-        } else {
-            match resp_rx.await {
-                Ok(Ok(resp)) => resp,
-                Ok(Err(e)) => {
-                    error!(err = ?e, "service HTTP handler failed");
-                    error_response(500)
-                }
-                Err(_) => {
-                    error!("service HTTP instance dropped the response");
-                    error_response(500)
-                }
+            return error_response(503);
+        }
+        return match resp_rx.await {
+            Ok(Ok(resp)) => resp,
+            Ok(Err(e)) => {
+                error!(err = ?e, "service HTTP handler failed");
+                error_response(500)
+            }
+            Err(_) => {
+                error!("service HTTP instance dropped the response");
+                error_response(500)
             }
         };
-        record_response_status(&response);
-        return Ok(response);
     }
 
     // NOTE(lxf): Separate HTTP / GRPC handling
@@ -1609,10 +1936,10 @@ async fn handle_http_request<T: Router>(
     let workload_handle = {
         let handles = workload_handles.read().await;
         debug!(host = %workload_id, "looking up workload handle for host header");
-        handles.get(&workload_id).cloned()
+        handles.get(workload_id).cloned()
     };
 
-    let response = match workload_handle {
+    match workload_handle {
         Some((handle, instance_pre, component_id)) => {
             let req_span = tracing::span!(
                 tracing::Level::INFO,
@@ -1636,17 +1963,25 @@ async fn handle_http_request<T: Router>(
             warn!(host = %workload_id, "No workload bound to host header or wildcard '*'");
             error_response(404)
         }
-    };
+    }
+}
 
-    record_response_status(&response);
-    // Carry the current span on the response body so `http.response.body.size`
-    // is recorded once the HTTP server finishes streaming the body. That
-    // happens after this handler future — and its `#[instrument]` span — has
-    // returned, so the body wrapper keeps a span handle alive to land the
-    // attribute before the span closes.
-    let response =
-        response.map(|body| MeteredBody::new(body, tracing::Span::current()).boxed_unsync());
-    Ok(response)
+/// Answer a request that ran into one of its workload's limits, counting it
+/// as rejected.
+fn limit_response(
+    workload_id: &str,
+    exceeded: LimitExceeded,
+    meters: &Meters,
+) -> hyper::Response<HyperOutgoingBody> {
+    debug!(host = %workload_id, reason = exceeded.reason(), "request exceeded a limit");
+    meters.http_rejections.record(&[
+        KeyValue::new("workload.id", workload_id.to_string()),
+        KeyValue::new("reason", exceeded.reason()),
+        KeyValue::new(HTTP_RESPONSE_STATUS_CODE, i64::from(exceeded.status())),
+    ]);
+    let resp = error_response(exceeded.status());
+    record_response_status(&resp);
+    resp
 }
 
 /// Record the response's status on the current span as the OTel HTTP semconv
@@ -1732,6 +2067,11 @@ struct MeteredBody {
     span: tracing::Span,
     bytes: u64,
     recorded: bool,
+    /// Released once the body is dropped; see [`MeteredBody::holding`].
+    _permit: Option<AdmissionPermit>,
+    _active: Option<ActiveRequest>,
+    /// Cuts the body off when the request's deadline passes.
+    deadline: Option<std::pin::Pin<Box<tokio::time::Sleep>>>,
 }
 
 impl MeteredBody {
@@ -1741,9 +2081,26 @@ impl MeteredBody {
             span,
             bytes: 0,
             recorded: false,
+            _permit: None,
+            _active: None,
+            deadline: None,
         }
     }
 
+    /// Keep the request's admission permit, and its place among its
+    /// connection's requests in flight, until the body is done.
+    fn holding(mut self, permit: Option<AdmissionPermit>, active: ActiveRequest) -> Self {
+        self._permit = permit;
+        self._active = Some(active);
+        self
+    }
+
+    /// Fail the body if it is still streaming at `deadline`.
+    fn until(mut self, deadline: Option<tokio::time::Instant>) -> Self {
+        self.deadline = deadline.map(|at| Box::pin(tokio::time::sleep_until(at)));
+        self
+    }
+
     fn record(&mut self) {
         if !self.recorded {
             self.span.record(HTTP_RESPONSE_BODY_SIZE, self.bytes);
@@ -1776,7 +2133,19 @@ impl hyper::body::Body for MeteredBody {
                 self.record();
                 Poll::Ready(None)
             }
-            Poll::Pending => Poll::Pending,
+            Poll::Pending => {
+                let expired = self
+                    .deadline
+                    .as_mut()
+                    .is_some_and(|deadline| deadline.as_mut().poll(cx).is_ready());
+                if !expired {
+                    return Poll::Pending;
+                }
+                self.record();
+                Poll::Ready(Some(Err(
+                    wasmtime_wasi_http::p2::bindings::http::types::ErrorCode::HttpResponseTimeout,
+                )))
+            }
         }
     }
 
@@ -1829,7 +2198,7 @@ async fn invoke_component_handler(
     workload_handle: ResolvedWorkload,
     instance_pre: InstancePre<SharedCtx>,
     component_id: &str,
-    req: hyper::Request<hyper::body::Incoming>,
+    req: hyper::Request<IngressBody>,
     fuel_meter: FuelConsumptionMeter,
 ) -> anyhow::Result<hyper::Response<HyperOutgoingBody>> {
     if crate::engine::targets_wasip3_http(instance_pre.component()) {
@@ -1907,7 +2276,7 @@ async fn invoke_component_handler(
 pub async fn handle_component_request(
     mut store: Store<SharedCtx>,
     pre: InstancePre<SharedCtx>,
-    req: hyper::Request<hyper::body::Incoming>,
+    req: hyper::Request<IngressBody>,
     fuel_meter: FuelConsumptionMeter,
 ) -> anyhow::Result<hyper::Response<HyperOutgoingBody>> {
     let (sender, receiver) = tokio::sync::oneshot::channel();
@@ -1993,50 +2362,29 @@ pub async fn handle_component_request(
 
 /// Load TLS configuration from certificate and key files
 /// Extracted from wash dev command for reuse in HTTP server plugin
-async fn load_tls_config(
-    cert_path: &Path,
-    key_path: &Path,
-    ca_path: Option<&Path>,
-) -> anyhow::Result<ServerConfig> {
-    // Load certificate chain
-    let cert_data = tokio::fs::read(cert_path).await.context(format!(
-        "Failed to read certificate file: {}",
-        cert_path.display()
-    ))?;
-    let cert_chain: Vec<CertificateDer<'static>> = CertificateDer::pem_slice_iter(&cert_data)
-        .collect::<Result<Vec<_>, _>>()
-        .context(format!(
-            "Failed to parse certificate file: {}",
-            cert_path.display()
-        ))?;
-
+///
+/// Returns the certificate resolver alongside it, for workloads to add to.
+async fn load_tls_config(tls: &TlsConfig) -> anyhow::Result<(ServerConfig, Arc<SniResolver>)> {
     ensure!(
-        !cert_chain.is_empty(),
-        "No certificates found in file: {}",
-        cert_path.display()
+        !tls.reload_interval.is_zero(),
+        "TLS reload interval must be greater than zero"
     );
-
-    // Load private key
-    let key_data = tokio::fs::read(key_path).await.context(format!(
-        "Failed to read private key file: {}",
-        key_path.display()
-    ))?;
-    let key = PrivateKeyDer::from_pem_slice(&key_data).context(format!(
-        "Failed to parse private key file: {}",
-        key_path.display()
-    ))?;
+    let (default_cert, cert_dir) = (tls.default_cert.clone(), tls.cert_dir.clone());
+    let certs = tokio::task::spawn_blocking(move || SniResolver::new(default_cert, cert_dir))
+        .await
+        .context("TLS certificate loading panicked")??;
+    let certs = Arc::new(certs);
 
     // Create rustls server config
     let mut config = ServerConfig::builder()
         .with_no_client_auth()
-        .with_single_cert(cert_chain, key)
-        .context("failed to create TLS configuration")?;
+        .with_cert_resolver(certs.clone());
 
     // Advertise both h2 and http/1.1 via ALPN
     config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
 
     // If CA is provided, configure client certificate verification
-    if let Some(ca_path) = ca_path {
+    if let Some(ca_path) = tls.ca_path.as_deref() {
         let ca_data = tokio::fs::read(ca_path)
             .await
             .context(format!("failed to read CA file: {}", ca_path.display()))?;
@@ -2055,7 +2403,7 @@ async fn load_tls_config(
         debug!("CA certificate loaded, but client certificate verification not yet implemented");
     }
 
-    Ok(config)
+    Ok((config, certs))
 }
 
 /// Checks whether an outgoing request is permitted by the `allowed_hosts`
@@ -2399,6 +2747,27 @@ mod tests {
             .unwrap()
     }
 
+    /// An incoming request `DynamicRouter` selection can be checked against.
+    fn routed_request(method: &str, path: &str, headers: &[(&str, &str)]) -> hyper::Request<()> {
+        let mut builder = hyper::Request::builder().method(method).uri(path);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(()).unwrap()
+    }
+
+    fn any_request() -> hyper::Request<()> {
+        routed_request("GET", "/", &[])
+    }
+
+    fn route_rule(pairs: &[(&str, &str)]) -> RouteRule {
+        let config: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        RouteRule::from_config(&config).unwrap()
+    }
+
     /// `with_connection_limits` rebuilds an eagerly-configured client cache
     /// and must carry the TLS configuration over — losing it would silently
     /// revert a host to the default trust roots.
@@ -3018,7 +3387,7 @@ mod tests {
         let mut counts: std::collections::BTreeMap<String, usize> =
             std::collections::BTreeMap::new();
         for _ in 0..DRAWS {
-            let id = router.select_workload("svc.local").unwrap();
+            let id = router.select_workload("svc.local", &any_request()).unwrap();
             *counts.entry(id).or_default() += 1;
         }
 
@@ -3045,7 +3414,7 @@ mod tests {
         let router = DynamicRouter::default();
         assert!(
             matches!(
-                router.select_workload("svc.local"),
+                router.select_workload("svc.local", &any_request()),
                 Err(RouteError::NoWorkloadForHost(_))
             ),
             "host should not resolve before the service is registered"
@@ -3059,8 +3428,16 @@ mod tests {
             .await
             .unwrap();
 
-        assert_eq!(router.select_workload("svc.local").unwrap(), "svc-1");
-        assert_eq!(router.select_workload("svc.internal").unwrap(), "svc-1");
+        assert_eq!(
+            router.select_workload("svc.local", &any_request()).unwrap(),
+            "svc-1"
+        );
+        assert_eq!(
+            router
+                .select_workload("svc.internal", &any_request())
+                .unwrap(),
+            "svc-1"
+        );
     }
 
     /// A service resolving with no valid hostnames (e.g. under a host-agnostic
@@ -3070,7 +3447,7 @@ mod tests {
         let router = DynamicRouter::default();
         router.on_service_http_resolved("svc-1", &[]).await.unwrap();
         assert!(matches!(
-            router.select_workload("anything.local"),
+            router.select_workload("anything.local", &any_request()),
             Err(RouteError::NoWorkloadForHost(_))
         ));
     }
@@ -3093,7 +3470,7 @@ mod tests {
 
         for _ in 0..4 {
             assert_eq!(
-                router.select_workload("svc.local").unwrap(),
+                router.select_workload("svc.local", &any_request()).unwrap(),
                 "r1",
                 "only the surviving replica should be selected after unbind"
             );
@@ -3116,15 +3493,166 @@ mod tests {
             .on_service_http_resolved("svc-1", &["svc.local".to_string()], tx)
             .await
             .unwrap();
-        assert_eq!(server.router.select_workload("svc.local").unwrap(), "svc-1");
+        assert_eq!(
+            server
+                .router
+                .select_workload("svc.local", &any_request())
+                .unwrap(),
+            "svc-1"
+        );
 
         server.on_service_http_unbind("svc-1").await.unwrap();
         assert!(
             matches!(
-                server.router.select_workload("svc.local"),
+                server.router.select_workload("svc.local", &any_request()),
                 Err(RouteError::NoWorkloadForHost(_))
             ),
             "hostname must stop routing once the service unbinds"
         );
     }
+
+    /// Several workloads behind one hostname split it by path prefix; the
+    /// longest prefix matching a request wins, on whole segments only.
+    #[test]
+    fn dynamic_router_routes_by_longest_path_prefix() {
+        let router = DynamicRouter::default();
+        let host = ["shop.local".to_string()];
+        router.register_hostnames("web", &host, RouteRule::default());
+        router.register_hostnames("api", &host, route_rule(&[("path-prefix", "/api")]));
+        router.register_hostnames("api-v2", &host, route_rule(&[("path-prefix", "/api/v2")]));
+
+        for (path, expected) in [
+            ("/", "web"),
+            ("/static/app.js", "web"),
+            ("/apiary", "web"),
+            ("/api", "api"),
+            ("/api/users?page=2", "api"),
+            ("/api/v2/users", "api-v2"),
+        ] {
+            let req = routed_request("GET", path, &[]);
+            assert_eq!(
+                router.select_workload("shop.local", &req).unwrap(),
+                expected,
+                "{path}"
+            );
+        }
+    }
+
+    /// A canary rule with a header predicate takes the requests carrying
+    /// that header; everything else stays on the stable workload.
+    #[test]
+    fn dynamic_router_routes_canary_by_header_and_method() {
+        let router = DynamicRouter::default();
+        let host = ["svc.local".to_string()];
+        router.register_hostnames("stable", &host, RouteRule::default());
+        router.register_hostnames(
+            "canary",
+            &host,
+            route_rule(&[("match-headers", "x-canary=1"), ("match-methods", "GET")]),
+        );
+
+        let plain = routed_request("GET", "/", &[]);
+        let flagged = routed_request("GET", "/", &[("x-canary", "1")]);
+        let flagged_post = routed_request("POST", "/", &[("x-canary", "1")]);
+        for _ in 0..16 {
+            assert_eq!(
+                router.select_workload("svc.local", &plain).unwrap(),
+                "stable"
+            );
+            assert_eq!(
+                router.select_workload("svc.local", &flagged).unwrap(),
+                "canary"
+            );
+            assert_eq!(
+                router.select_workload("svc.local", &flagged_post).unwrap(),
+                "stable"
+            );
+        }
+    }
+
+    /// Replicas whose rules tie split traffic in proportion to their weight.
+    /// As in `dynamic_router_spreads_load_across_replicas`, the band is wide
+    /// enough that it cannot flake, yet an unweighted 50/50 split fails it.
+    #[test]
+    fn dynamic_router_splits_ties_by_weight() {
+        let router = DynamicRouter::default();
+        let host = ["svc.local".to_string()];
+        router.register_hostnames("heavy", &host, route_rule(&[("weight", "9")]));
+        router.register_hostnames("light", &host, route_rule(&[("weight", "1")]));
+
+        const DRAWS: usize = 4_000;
+        let light = (0..DRAWS)
+            .filter(|_| router.select_workload("svc.local", &any_request()).unwrap() == "light")
+            .count();
+        let expected = DRAWS / 10;
+        assert!(
+            light > expected / 2 && light < expected * 2,
+            "light replica got {light} of {DRAWS}, far from the expected ~{expected}"
+        );
+    }
+
+    /// A hostname whose rules all miss the request is a 404, reported as a
+    /// routing miss rather than an unbound host.
+    #[test]
+    fn dynamic_router_reports_requests_no_rule_matches() {
+        let router = DynamicRouter::default();
+        router.register_hostnames(
+            "api",
+            &["shop.local".to_string()],
+            route_rule(&[("path-prefix", "/api")]),
+        );
+
+        let err = router
+            .select_workload("shop.local", &routed_request("GET", "/static", &[]))
+            .unwrap_err();
+        assert!(
+            matches!(&err, RouteError::NoRouteForRequest { path, .. } if path == "/static"),
+            "{err}"
+        );
+        assert_eq!(err.status(), 404);
+    }
+
+    /// Re-registering a workload replaces its rule instead of binding it twice.
+    #[test]
+    fn dynamic_router_reregister_replaces_rule() {
+        let router = DynamicRouter::default();
+        let host = ["shop.local".to_string()];
+        router.register_hostnames("api", &host, route_rule(&[("path-prefix", "/api")]));
+        router.register_hostnames("api", &host, route_rule(&[("path-prefix", "/v2")]));
+
+        let routes = router.routes.load();
+        assert_eq!(
+            routes.host_to_workload.get("shop.local").map(Vec::len),
+            Some(1)
+        );
+        assert!(
+            router
+                .select_workload("shop.local", &routed_request("GET", "/v2/x", &[]))
+                .is_ok()
+        );
+        assert!(
+            router
+                .select_workload("shop.local", &routed_request("GET", "/api/x", &[]))
+                .is_err()
+        );
+    }
+
+    /// Re-registering a workload under fewer hostnames unroutes the ones it
+    /// dropped.
+    #[test]
+    fn dynamic_router_reregister_drops_old_hostnames() {
+        let router = DynamicRouter::default();
+        let both = ["a.local".to_string(), "b.local".to_string()];
+        router.register_hostnames("web", &both, RouteRule::default());
+        router.register_hostnames("web", &both[..1], RouteRule::default());
+
+        assert_eq!(
+            router.select_workload("a.local", &any_request()).unwrap(),
+            "web"
+        );
+        assert!(matches!(
+            router.select_workload("b.local", &any_request()),
+            Err(RouteError::NoWorkloadForHost(_))
+        ));
+    }
 }
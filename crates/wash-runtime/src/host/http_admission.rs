@@ -0,0 +1,539 @@
+//! Admission control for the HTTP ingress.
+//!
+//! A workload's `wasi:http/incoming-handler` host interface can cap how much
+//! traffic reaches it, in the same flat config as `host`:
+//!
+//! | Key                | Format                                   | Default      |
+//! | ------------------ | ---------------------------------------- | ------------ |
+//! | `rate-limit`       | requests per second (positive number)    | no limit     |
+//! | `rate-limit-burst` | positive integer                         | `rate-limit`, rounded up |
+//! | `rate-limit-key`   | `workload`, `client-ip` or `header:NAME` | `workload`   |
+//! | `max-in-flight`    | positive integer                         | no limit     |
+//!
+//! The policy is parsed once, when the workload resolves, so a malformed one
+//! fails the workload instead of surfacing per request.
+//!
+//! # Semantics
+//!
+//! - `rate-limit` is a token bucket holding up to `rate-limit-burst` tokens and
+//!   refilling at `rate-limit` per second. With `rate-limit-key` set to
+//!   `client-ip` or `header:NAME` each client IP or header value gets its own
+//!   bucket; requests without the header share one. A request finding its
+//!   bucket empty is answered `429 Too Many Requests`, with `Retry-After` set
+//!   to the seconds until the next token.
+//! - `max-in-flight` caps the requests a workload serves at once, counted
+//!   until each response body has been sent. A request over the cap is
+//!   answered `503 Service Unavailable` with `Retry-After: 1`.
+//! - Rejected requests never reach the workload, and are counted in the
+//!   `http.ingress.rejections` meter (see [`crate::observability::Meters`]).
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, ensure};
+use hyper::header::HeaderName;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Config key for the sustained requests per second a workload accepts.
+pub const RATE_LIMIT_KEY: &str = "rate-limit";
+/// Config key for how many requests may arrive at once before the rate limit
+/// applies.
+pub const RATE_LIMIT_BURST_KEY: &str = "rate-limit-burst";
+/// Config key for what a rate limit is counted per.
+pub const RATE_LIMIT_KEY_KEY: &str = "rate-limit-key";
+/// Config key for the requests a workload serves at once.
+pub const MAX_IN_FLIGHT_KEY: &str = "max-in-flight";
+
+/// Rate-limit buckets kept per workload, so a `client-ip` or `header:NAME`
+/// limit can't grow without bound. A new key past it first drops the full
+/// (idle) buckets, then, if every bucket is still in use, the least recently
+/// used [`EVICTED_BUCKETS`] of them.
+const MAX_TRACKED_BUCKETS: usize = 10_000;
+
+/// How many buckets one eviction frees, so that while every tracked key stays
+/// active the scan that finds them runs once per this many new keys rather than
+/// on each.
+const EVICTED_BUCKETS: usize = MAX_TRACKED_BUCKETS / 10;
+
+/// One workload's admission policy. See the module docs for the config format.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AdmissionPolicy {
+    rate_limit: Option<RateLimit>,
+    max_in_flight: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RateLimit {
+    per_second: f64,
+    burst: f64,
+    key: RateLimitKey,
+}
+
+/// What a rate limit is counted per.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RateLimitKey {
+    Workload,
+    ClientIp,
+    Header(HeaderName),
+}
+
+impl AdmissionPolicy {
+    /// Parse the policy from an incoming-handler interface's config. Keys that
+    /// are absent take their defaults; keys that are present must be valid.
+    pub fn from_config(config: &HashMap<String, String>) -> anyhow::Result<Self> {
+        let mut policy = Self::default();
+        if let Some(rate) = config.get(RATE_LIMIT_KEY) {
+            let per_second: f64 = rate
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid {RATE_LIMIT_KEY} {rate:?}"))?;
+            ensure!(
+                per_second.is_finite() && per_second > 0.0,
+                "{RATE_LIMIT_KEY} must be positive"
+            );
+            let burst = match config.get(RATE_LIMIT_BURST_KEY) {
+                Some(burst) => {
+                    let burst: u32 = burst
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("invalid {RATE_LIMIT_BURST_KEY} {burst:?}"))?;
+                    ensure!(burst > 0, "{RATE_LIMIT_BURST_KEY} must be positive");
+                    f64::from(burst)
+                }
+                None => per_second.ceil(),
+            };
+            let key = match config.get(RATE_LIMIT_KEY_KEY).map(|k| k.trim()) {
+                None | Some("workload") => RateLimitKey::Workload,
+                Some("client-ip") => RateLimitKey::ClientIp,
+                Some(other) => {
+                    let name = other.strip_prefix("header:").with_context(|| {
+                        format!(
+                            "invalid {RATE_LIMIT_KEY_KEY} {other:?}: expected `workload`, `client-ip` or `header:NAME`"
+                        )
+                    })?;
+                    RateLimitKey::Header(HeaderName::try_from(name.trim()).with_context(|| {
+                        format!("invalid header name in {RATE_LIMIT_KEY_KEY} {other:?}")
+                    })?)
+                }
+            };
+            policy.rate_limit = Some(RateLimit {
+                per_second,
+                burst,
+                key,
+            });
+        } else {
+            ensure!(
+                !config.contains_key(RATE_LIMIT_BURST_KEY)
+                    && !config.contains_key(RATE_LIMIT_KEY_KEY),
+                "{RATE_LIMIT_BURST_KEY} and {RATE_LIMIT_KEY_KEY} require {RATE_LIMIT_KEY}"
+            );
+        }
+        if let Some(max) = config.get(MAX_IN_FLIGHT_KEY) {
+            let max: usize = max
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid {MAX_IN_FLIGHT_KEY} {max:?}"))?;
+            ensure!(max > 0, "{MAX_IN_FLIGHT_KEY} must be positive");
+            policy.max_in_flight = Some(max);
+        }
+        Ok(policy)
+    }
+
+    /// Whether the policy admits every request, so the ingress needn't track
+    /// the workload at all.
+    pub fn is_unlimited(&self) -> bool {
+        self.rate_limit.is_none() && self.max_in_flight.is_none()
+    }
+}
+
+/// Why a request was turned away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    /// The request's rate-limit bucket was empty; a token is due in the given
+    /// number of seconds.
+    RateLimited { retry_after_secs: u64 },
+    /// The workload was already serving `max-in-flight` requests.
+    Saturated,
+}
+
+impl Rejection {
+    /// The HTTP status the request is answered with.
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::RateLimited { .. } => 429,
+            Self::Saturated => 503,
+        }
+    }
+
+    /// The `Retry-After` the response carries, in seconds.
+    pub fn retry_after_secs(&self) -> u64 {
+        match self {
+            Self::RateLimited { retry_after_secs } => *retry_after_secs,
+            Self::Saturated => 1,
+        }
+    }
+
+    /// A short label for metrics and logs.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::RateLimited { .. } => "rate_limit",
+            Self::Saturated => "max_in_flight",
+        }
+    }
+}
+
+/// A workload's live admission state: its policy, rate-limit buckets and
+/// in-flight slots.
+pub(crate) struct Admission {
+    policy: AdmissionPolicy,
+    buckets: Mutex<HashMap<String, Bucket>>,
+    in_flight: Option<Arc<Semaphore>>,
+}
+
+/// Held for as long as an admitted request is in flight.
+pub(crate) struct AdmissionPermit {
+    _slot: Option<OwnedSemaphorePermit>,
+}
+
+impl Admission {
+    pub(crate) fn new(policy: AdmissionPolicy) -> Self {
+        Self {
+            in_flight: policy
+                .max_in_flight
+                .map(|max| Arc::new(Semaphore::new(max))),
+            policy,
+            buckets: Mutex::default(),
+        }
+    }
+
+    /// Admit `req` from `client`, or say why not.
+    pub(crate) fn admit<B>(
+        &self,
+        req: &hyper::Request<B>,
+        client: SocketAddr,
+    ) -> Result<AdmissionPermit, Rejection> {
+        self.admit_at(req, client, Instant::now())
+    }
+
+    fn admit_at<B>(
+        &self,
+        req: &hyper::Request<B>,
+        client: SocketAddr,
+        now: Instant,
+    ) -> Result<AdmissionPermit, Rejection> {
+        // Take the in-flight slot first, so a request turned away for
+        // saturation doesn't also spend a rate-limit token.
+        let slot = match &self.in_flight {
+            Some(slots) => Some(
+                Arc::clone(slots)
+                    .try_acquire_owned()
+                    .map_err(|_| Rejection::Saturated)?,
+            ),
+            None => None,
+        };
+        if let Some(limit) = &self.policy.rate_limit {
+            let key = match &limit.key {
+                RateLimitKey::Workload => String::new(),
+                RateLimitKey::ClientIp => client.ip().to_string(),
+                RateLimitKey::Header(name) => req
+                    .headers()
+                    .get(name)
+                    .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned())
+                    .unwrap_or_default(),
+            };
+            let mut buckets = self
+                .buckets
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if buckets.len() >= MAX_TRACKED_BUCKETS && !buckets.contains_key(&key) {
+                evict(&mut buckets, limit, now);
+            }
+            buckets
+                .entry(key)
+                .or_insert_with(|| Bucket::full(limit, now))
+                .take(limit, now)?;
+        }
+        Ok(AdmissionPermit { _slot: slot })
+    }
+}
+
+/// Make room in a full bucket map: drop the idle buckets, which a new request
+/// would find just as they are, and failing that the least recently used ones.
+/// A client whose bucket is evicted starts over with a full one.
+fn evict(buckets: &mut HashMap<String, Bucket>, limit: &RateLimit, now: Instant) {
+    buckets.retain(|_, bucket| !bucket.is_full(limit, now));
+    if buckets.len() < MAX_TRACKED_BUCKETS {
+        return;
+    }
+    // A bucket is only refilled when a request takes from it.
+    let mut last_used = buckets
+        .values()
+        .map(|bucket| bucket.refilled_at)
+        .collect::<Vec<_>>();
+    let (_, cutoff, _) = last_used.select_nth_unstable(EVICTED_BUCKETS - 1);
+    let cutoff = *cutoff;
+    let mut excess = buckets.len() + EVICTED_BUCKETS - MAX_TRACKED_BUCKETS;
+    buckets.retain(|_, bucket| {
+        let evict = excess > 0 && bucket.refilled_at <= cutoff;
+        excess -= usize::from(evict);
+        !evict
+    });
+}
+
+/// A token bucket, refilled lazily whenever it is looked at.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    fn full(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: limit.burst,
+            refilled_at: now,
+        }
+    }
+
+    fn tokens_at(&self, limit: &RateLimit, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        (self.tokens + elapsed.as_secs_f64() * limit.per_second).min(limit.burst)
+    }
+
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        self.tokens = self.tokens_at(limit, now);
+        self.refilled_at = now;
+    }
+
+    /// Whether the bucket has refilled completely by `now`. Leaves it as it
+    /// is, so its `refilled_at` still says when it was last used.
+    fn is_full(&self, limit: &RateLimit, now: Instant) -> bool {
+        self.tokens_at(limit, now) >= limit.burst
+    }
+
+    fn take(&mut self, limit: &RateLimit, now: Instant) -> Result<(), Rejection> {
+        self.refill(limit, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        // A tiny rate can put the next token further off than a `Duration`
+        // reaches.
+        let wait = Duration::try_from_secs_f64((1.0 - self.tokens) / limit.per_second)
+            .unwrap_or(Duration::from_secs(u64::MAX));
+        Err(Rejection::RateLimited {
+            retry_after_secs: wait.as_secs_f64().ceil().max(1.0) as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(pairs: &[(&str, &str)]) -> anyhow::Result<AdmissionPolicy> {
+        let config: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        AdmissionPolicy::from_config(&config)
+    }
+
+    fn request(headers: &[(&str, &str)]) -> hyper::Request<()> {
+        let mut builder = hyper::Request::builder().uri("/");
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(()).unwrap()
+    }
+
+    fn client(ip: &str) -> SocketAddr {
+        format!("{ip}:4000").parse().unwrap()
+    }
+
+    #[test]
+    fn no_config_admits_everything() {
+        let policy = policy(&[]).unwrap();
+        assert!(policy.is_unlimited());
+        let admission = Admission::new(policy);
+        for _ in 0..100 {
+            assert!(admission.admit(&request(&[]), client("10.0.0.1")).is_ok());
+        }
+    }
+
+    #[test]
+    fn rate_limit_allows_a_burst_then_refills() {
+        let admission =
+            Admission::new(policy(&[(RATE_LIMIT_KEY, "2"), (RATE_LIMIT_BURST_KEY, "3")]).unwrap());
+        let start = Instant::now();
+        let admit =
+            |at: Duration| admission.admit_at(&request(&[]), client("10.0.0.1"), start + at);
+
+        for _ in 0..3 {
+            assert!(admit(Duration::ZERO).is_ok());
+        }
+        let rejection = admit(Duration::ZERO).err().unwrap();
+        assert_eq!(
+            rejection,
+            Rejection::RateLimited {
+                retry_after_secs: 1
+            }
+        );
+        assert_eq!(rejection.status(), 429);
+
+        // Two tokens a second: one is back after half a second.
+        assert!(admit(Duration::from_millis(500)).is_ok());
+        assert!(admit(Duration::from_millis(500)).is_err());
+    }
+
+    #[test]
+    fn retry_after_rounds_up_to_the_next_token() {
+        let admission = Admission::new(policy(&[(RATE_LIMIT_KEY, "0.1")]).unwrap());
+        let now = Instant::now();
+        assert!(
+            admission
+                .admit_at(&request(&[]), client("10.0.0.1"), now)
+                .is_ok()
+        );
+        assert_eq!(
+            admission
+                .admit_at(&request(&[]), client("10.0.0.1"), now)
+                .err(),
+            Some(Rejection::RateLimited {
+                retry_after_secs: 10
+            })
+        );
+    }
+
+    #[test]
+    fn retry_after_saturates_for_tiny_rates() {
+        let admission = Admission::new(policy(&[(RATE_LIMIT_KEY, "1e-300")]).unwrap());
+        let now = Instant::now();
+        assert!(
+            admission
+                .admit_at(&request(&[]), client("10.0.0.1"), now)
+                .is_ok()
+        );
+        assert_eq!(
+            admission
+                .admit_at(&request(&[]), client("10.0.0.1"), now)
+                .err(),
+            Some(Rejection::RateLimited {
+                retry_after_secs: u64::MAX
+            })
+        );
+    }
+
+    #[test]
+    fn rate_limits_can_be_per_client_ip_or_header() {
+        let per_ip = Admission::new(
+            policy(&[(RATE_LIMIT_KEY, "1"), (RATE_LIMIT_KEY_KEY, "client-ip")]).unwrap(),
+        );
+        assert!(per_ip.admit(&request(&[]), client("10.0.0.1")).is_ok());
+        assert!(per_ip.admit(&request(&[]), client("10.0.0.1")).is_err());
+        assert!(per_ip.admit(&request(&[]), client("10.0.0.2")).is_ok());
+
+        let per_tenant = Admission::new(
+            policy(&[
+                (RATE_LIMIT_KEY, "1"),
+                (RATE_LIMIT_KEY_KEY, "header:X-Tenant"),
+            ])
+            .unwrap(),
+        );
+        let tenant = |t| request(&[("x-tenant", t)]);
+        assert!(per_tenant.admit(&tenant("a"), client("10.0.0.1")).is_ok());
+        assert!(per_tenant.admit(&tenant("a"), client("10.0.0.2")).is_err());
+        assert!(per_tenant.admit(&tenant("b"), client("10.0.0.1")).is_ok());
+        assert!(per_tenant.admit(&request(&[]), client("10.0.0.1")).is_ok());
+        assert!(
+            per_tenant.admit(&request(&[]), client("10.0.0.2")).is_err(),
+            "requests without the header share a bucket"
+        );
+    }
+
+    #[test]
+    fn max_in_flight_holds_a_slot_until_the_permit_drops() {
+        let admission = Admission::new(policy(&[(MAX_IN_FLIGHT_KEY, "1")]).unwrap());
+        let permit = admission.admit(&request(&[]), client("10.0.0.1")).unwrap();
+        let rejection = admission
+            .admit(&request(&[]), client("10.0.0.1"))
+            .err()
+            .unwrap();
+        assert_eq!((rejection.status(), rejection.retry_after_secs()), (503, 1));
+        drop(permit);
+        assert!(admission.admit(&request(&[]), client("10.0.0.1")).is_ok());
+    }
+
+    #[test]
+    fn idle_buckets_are_dropped_once_too_many_are_tracked() {
+        let admission = Admission::new(
+            policy(&[
+                (RATE_LIMIT_KEY, "1000"),
+                (RATE_LIMIT_KEY_KEY, "header:x-id"),
+            ])
+            .unwrap(),
+        );
+        let start = Instant::now();
+        for i in 0..MAX_TRACKED_BUCKETS {
+            let req = request(&[("x-id", &i.to_string())]);
+            assert!(admission.admit_at(&req, client("10.0.0.1"), start).is_ok());
+        }
+        let later = start + Duration::from_secs(1);
+        let req = request(&[("x-id", "new")]);
+        assert!(admission.admit_at(&req, client("10.0.0.1"), later).is_ok());
+        assert_eq!(admission.buckets.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn the_least_recently_used_buckets_go_when_none_are_idle() {
+        let admission = Admission::new(
+            policy(&[
+                (RATE_LIMIT_KEY, "1"),
+                (RATE_LIMIT_BURST_KEY, "2"),
+                (RATE_LIMIT_KEY_KEY, "header:x-id"),
+            ])
+            .unwrap(),
+        );
+        // Every key spends a token and none has refilled by the time the map
+        // is full, so none is idle: the oldest are evicted instead.
+        let start = Instant::now();
+        for i in 0..MAX_TRACKED_BUCKETS {
+            let req = request(&[("x-id", &i.to_string())]);
+            let at = start + Duration::from_micros(i as u64);
+            assert!(admission.admit_at(&req, client("10.0.0.1"), at).is_ok());
+        }
+        let later = start + Duration::from_millis(100);
+        let req = request(&[("x-id", "new")]);
+        assert!(admission.admit_at(&req, client("10.0.0.1"), later).is_ok());
+
+        let buckets = admission.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), MAX_TRACKED_BUCKETS - EVICTED_BUCKETS + 1);
+        assert!(!buckets.contains_key("0"), "the oldest bucket is evicted");
+        assert!(
+            !buckets.contains_key(&(EVICTED_BUCKETS - 1).to_string()),
+            "as are the rest of the oldest batch"
+        );
+        assert!(buckets.contains_key(&EVICTED_BUCKETS.to_string()));
+        assert!(buckets.contains_key("new"));
+    }
+
+    #[test]
+    fn malformed_policies_are_rejected() {
+        for pairs in [
+            &[(RATE_LIMIT_KEY, "0")][..],
+            &[(RATE_LIMIT_KEY, "fast")],
+            &[(RATE_LIMIT_KEY, "1"), (RATE_LIMIT_BURST_KEY, "0")],
+            &[(RATE_LIMIT_KEY, "1"), (RATE_LIMIT_KEY_KEY, "cookie")],
+            &[
+                (RATE_LIMIT_KEY, "1"),
+                (RATE_LIMIT_KEY_KEY, "header:bad header"),
+            ],
+            &[(RATE_LIMIT_BURST_KEY, "5")],
+            &[(MAX_IN_FLIGHT_KEY, "0")],
+        ] {
+            assert!(policy(pairs).is_err(), "{pairs:?} should be rejected");
+        }
+    }
+}
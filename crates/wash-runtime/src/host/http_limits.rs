@@ -0,0 +1,622 @@
+//! Request size limits and timeouts for the HTTP ingress.
+//!
+//! The host sets defaults for every workload with [`IngressLimits`] (see
+//! [`IngressBuilder::limits`](crate::host::http::IngressBuilder::limits)). A
+//! workload's `wasi:http/incoming-handler` host interface can override the
+//! per-request ones, in the same flat config as `host`:
+//!
+//! | Key                | Format                                  | Default           |
+//! | ------------------ | --------------------------------------- | ----------------- |
+//! | `max-header-bytes` | positive integer, at most the host's    | the host's        |
+//! | `max-body-bytes`   | positive integer                        | the host's        |
+//! | `request-timeout`  | duration, e.g. `30s` or `500ms`         | the host's        |
+//!
+//! Overrides are parsed once, when the workload resolves, so a malformed one
+//! fails the workload instead of surfacing per request.
+//!
+//! # Semantics
+//!
+//! - A request's header bytes are each field's name and value plus four (the
+//!   `: ` and line break). Headers over the host's limit are refused by the
+//!   connection before the request is routed: `431 Request Header Fields Too
+//!   Large` on HTTP/1, a stream reset on HTTP/2. Headers within the host's
+//!   limit but over the workload's are answered `431`.
+//! - A request whose `Content-Length` exceeds `max-body-bytes` is answered
+//!   `413 Content Too Large` without reaching the workload. A body that
+//!   outgrows the limit while streaming fails the guest's read with
+//!   `HTTP-request-body-size`; unless the guest had already answered, the
+//!   client gets `413` whatever the guest replied.
+//! - `request-timeout` bounds the whole exchange. Without a response head by
+//!   then, the request is answered `408 Request Timeout` if its body was still
+//!   arriving and `504 Gateway Timeout` otherwise. A response body still
+//!   streaming at the deadline is cut off.
+//! - The header read and idle timeouts are host-wide only: both elapse before
+//!   a request has been routed to any workload. A client that doesn't finish
+//!   sending its headers in time is disconnected, and a connection with no
+//!   request in flight for the idle timeout is closed gracefully.
+//! - Requests turned away or cut short are counted in the
+//!   `http.ingress.rejections` meter (see [`crate::observability::Meters`]).
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use anyhow::{Context as _, ensure};
+use tokio::sync::{Notify, RwLock};
+use tokio::time::Instant;
+use wasmtime_wasi_http::p2::bindings::http::types::ErrorCode as P2ErrorCode;
+use wasmtime_wasi_http::p3::bindings::http::types::ErrorCode as P3ErrorCode;
+
+/// Config key for the largest header section a workload accepts, in bytes.
+pub const MAX_HEADER_BYTES_KEY: &str = "max-header-bytes";
+/// Config key for the largest request body a workload accepts, in bytes.
+pub const MAX_BODY_BYTES_KEY: &str = "max-body-bytes";
+/// Config key for how long a workload's requests may take, end to end.
+pub const REQUEST_TIMEOUT_KEY: &str = "request-timeout";
+
+/// Smallest read buffer hyper's HTTP/1 parser accepts. A lower header limit
+/// is still enforced, per request rather than by the parser.
+const MIN_HTTP1_BUFFER: usize = 8192;
+
+/// Host-wide limits on the requests the ingress accepts. See the module docs
+/// for what each one answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IngressLimits {
+    /// Largest request header section, in bytes. Defaults to 64 KiB.
+    pub max_header_bytes: usize,
+    /// Largest request body, in bytes. Defaults to no limit.
+    pub max_body_bytes: Option<u64>,
+    /// How long a client may take to send a request's headers. Defaults to
+    /// 30 seconds.
+    pub header_read_timeout: Option<Duration>,
+    /// How long a connection may stay open without a request in flight.
+    /// Defaults to 90 seconds.
+    pub idle_timeout: Option<Duration>,
+    /// How long a request may take, from its headers arriving to its response
+    /// body having been sent. Defaults to no limit.
+    pub request_timeout: Option<Duration>,
+}
+
+impl Default for IngressLimits {
+    fn default() -> Self {
+        Self {
+            max_header_bytes: 64 * 1024,
+            max_body_bytes: None,
+            header_read_timeout: Some(Duration::from_secs(30)),
+            idle_timeout: Some(Duration::from_secs(90)),
+            request_timeout: None,
+        }
+    }
+}
+
+impl IngressLimits {
+    /// Check that every limit leaves room for a request at all.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        ensure!(
+            self.max_header_bytes > 0,
+            "max header bytes must be positive"
+        );
+        ensure!(
+            self.max_body_bytes != Some(0),
+            "max body bytes must be positive"
+        );
+        for (name, timeout) in [
+            ("header read timeout", self.header_read_timeout),
+            ("idle timeout", self.idle_timeout),
+            ("request timeout", self.request_timeout),
+        ] {
+            ensure!(timeout != Some(Duration::ZERO), "{name} must be positive");
+        }
+        Ok(())
+    }
+
+    /// The read buffer an HTTP/1 connection needs to parse the largest header
+    /// section allowed.
+    pub(crate) fn http1_buffer(&self) -> usize {
+        self.max_header_bytes.max(MIN_HTTP1_BUFFER)
+    }
+
+    /// The limits a request gets when its workload overrides none of them.
+    fn request_limits(&self) -> RequestLimits {
+        RequestLimits {
+            max_header_bytes: self.max_header_bytes,
+            max_body_bytes: self.max_body_bytes,
+            request_timeout: self.request_timeout,
+        }
+    }
+
+    /// Apply a workload's overrides from its incoming-handler interface's
+    /// config. Keys that are absent keep the host's limit.
+    pub(crate) fn for_workload(
+        &self,
+        config: &HashMap<String, String>,
+    ) -> anyhow::Result<RequestLimits> {
+        let mut limits = self.request_limits();
+        if let Some(max) = config.get(MAX_HEADER_BYTES_KEY) {
+            let max: usize = max
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid {MAX_HEADER_BYTES_KEY} {max:?}"))?;
+            ensure!(max > 0, "{MAX_HEADER_BYTES_KEY} must be positive");
+            ensure!(
+                max <= self.max_header_bytes,
+                "{MAX_HEADER_BYTES_KEY} {max} exceeds the host's limit of {}",
+                self.max_header_bytes
+            );
+            limits.max_header_bytes = max;
+        }
+        if let Some(max) = config.get(MAX_BODY_BYTES_KEY) {
+            let max: u64 = max
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid {MAX_BODY_BYTES_KEY} {max:?}"))?;
+            ensure!(max > 0, "{MAX_BODY_BYTES_KEY} must be positive");
+            limits.max_body_bytes = Some(max);
+        }
+        if let Some(timeout) = config.get(REQUEST_TIMEOUT_KEY) {
+            let timeout = humantime::parse_duration(timeout.trim())
+                .with_context(|| format!("invalid {REQUEST_TIMEOUT_KEY} {timeout:?}"))?;
+            ensure!(!timeout.is_zero(), "{REQUEST_TIMEOUT_KEY} must be positive");
+            limits.request_timeout = Some(timeout);
+        }
+        Ok(limits)
+    }
+}
+
+/// The limits that apply to one workload's requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RequestLimits {
+    max_header_bytes: usize,
+    max_body_bytes: Option<u64>,
+    request_timeout: Option<Duration>,
+}
+
+impl RequestLimits {
+    /// Check a request's head, before it reaches the workload.
+    pub(crate) fn check_head<B>(&self, req: &hyper::Request<B>) -> Result<(), LimitExceeded> {
+        let header_bytes: usize = req
+            .headers()
+            .iter()
+            .map(|(name, value)| name.as_str().len() + value.len() + 4)
+            .sum();
+        if header_bytes > self.max_header_bytes {
+            return Err(LimitExceeded::HeaderTooLarge);
+        }
+        let content_length = req
+            .headers()
+            .get(hyper::header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<u64>().ok());
+        if let (Some(max), Some(length)) = (self.max_body_bytes, content_length)
+            && length > max
+        {
+            return Err(LimitExceeded::BodyTooLarge);
+        }
+        Ok(())
+    }
+
+    /// Wrap a request's body so it fails once it outgrows the limit.
+    pub(crate) fn limit_body(
+        &self,
+        req: hyper::Request<hyper::body::Incoming>,
+    ) -> (hyper::Request<IngressBody>, Arc<BodyProgress>) {
+        let (parts, body) = req.into_parts();
+        let body = IngressBody::new(body, self.max_body_bytes);
+        let progress = Arc::clone(&body.progress);
+        (hyper::Request::from_parts(parts, body), progress)
+    }
+
+    /// When a request arriving now must be done by.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.request_timeout.map(|timeout| Instant::now() + timeout)
+    }
+}
+
+/// The host's limits and each workload's overrides of them.
+pub(crate) struct WorkloadLimits {
+    host: IngressLimits,
+    overrides: RwLock<HashMap<String, RequestLimits>>,
+}
+
+impl WorkloadLimits {
+    pub(crate) fn new(host: IngressLimits) -> Self {
+        Self {
+            host,
+            overrides: RwLock::default(),
+        }
+    }
+
+    pub(crate) fn host(&self) -> &IngressLimits {
+        &self.host
+    }
+
+    /// Record a workload's limits, as parsed by [`IngressLimits::for_workload`].
+    pub(crate) async fn insert(&self, workload_id: &str, limits: RequestLimits) {
+        let mut overrides = self.overrides.write().await;
+        if limits == self.host.request_limits() {
+            overrides.remove(workload_id);
+        } else {
+            overrides.insert(workload_id.to_string(), limits);
+        }
+    }
+
+    pub(crate) async fn remove(&self, workload_id: &str) {
+        self.overrides.write().await.remove(workload_id);
+    }
+
+    /// The limits for a workload's requests.
+    pub(crate) async fn get(&self, workload_id: &str) -> RequestLimits {
+        self.overrides
+            .read()
+            .await
+            .get(workload_id)
+            .copied()
+            .unwrap_or_else(|| self.host.request_limits())
+    }
+}
+
+/// Which limit a request ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LimitExceeded {
+    HeaderTooLarge,
+    BodyTooLarge,
+    /// The request timeout passed before the response head; `body_complete`
+    /// says whether the request body had fully arrived.
+    Timeout {
+        body_complete: bool,
+    },
+}
+
+impl LimitExceeded {
+    /// The HTTP status the request is answered with.
+    pub(crate) fn status(self) -> u16 {
+        match self {
+            Self::HeaderTooLarge => 431,
+            Self::BodyTooLarge => 413,
+            Self::Timeout {
+                body_complete: false,
+            } => 408,
+            Self::Timeout {
+                body_complete: true,
+            } => 504,
+        }
+    }
+
+    /// A short label for metrics and logs.
+    pub(crate) fn reason(self) -> &'static str {
+        match self {
+            Self::HeaderTooLarge => "max_header_bytes",
+            Self::BodyTooLarge => "max_body_bytes",
+            Self::Timeout { .. } => "request_timeout",
+        }
+    }
+}
+
+/// How far reading a request's body got, shared between the body and the
+/// ingress answering the request.
+#[derive(Debug, Default)]
+pub(crate) struct BodyProgress {
+    complete: AtomicBool,
+    too_large: AtomicBool,
+}
+
+impl BodyProgress {
+    /// Whether the whole body arrived.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.complete.load(Ordering::Acquire)
+    }
+
+    /// Whether the body outgrew its limit.
+    pub(crate) fn is_too_large(&self) -> bool {
+        self.too_large.load(Ordering::Acquire)
+    }
+}
+
+/// Why reading an [`IngressBody`] failed.
+#[derive(Debug)]
+pub enum IngressBodyError {
+    /// The connection failed while the body was being read.
+    Read(hyper::Error),
+    /// The body outgrew the workload's `max-body-bytes`.
+    TooLarge { limit: u64 },
+}
+
+impl From<IngressBodyError> for P2ErrorCode {
+    fn from(err: IngressBodyError) -> Self {
+        match err {
+            IngressBodyError::Read(e) => e.into(),
+            IngressBodyError::TooLarge { limit } => Self::HttpRequestBodySize(Some(limit)),
+        }
+    }
+}
+
+impl From<IngressBodyError> for P3ErrorCode {
+    fn from(err: IngressBodyError) -> Self {
+        match err {
+            IngressBodyError::Read(e) => Self::InternalError(Some(e.to_string())),
+            IngressBodyError::TooLarge { limit } => Self::HttpRequestBodySize(Some(limit)),
+        }
+    }
+}
+
+/// An inbound request body, failing once it outgrows its workload's
+/// `max-body-bytes`.
+pub struct IngressBody {
+    inner: hyper::body::Incoming,
+    limit: Option<u64>,
+    read: u64,
+    progress: Arc<BodyProgress>,
+}
+
+impl IngressBody {
+    fn new(inner: hyper::body::Incoming, limit: Option<u64>) -> Self {
+        let progress = Arc::new(BodyProgress::default());
+        if hyper::body::Body::is_end_stream(&inner) {
+            progress.complete.store(true, Ordering::Release);
+        }
+        Self {
+            inner,
+            limit,
+            read: 0,
+            progress,
+        }
+    }
+}
+
+/// An unlimited body.
+impl From<hyper::body::Incoming> for IngressBody {
+    fn from(inner: hyper::body::Incoming) -> Self {
+        Self::new(inner, None)
+    }
+}
+
+impl hyper::body::Body for IngressBody {
+    type Data = bytes::Bytes;
+    type Error = IngressBodyError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<hyper::body::Frame<Self::Data>, Self::Error>>> {
+        match Pin::new(&mut self.inner).poll_frame(cx) {
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(data) = frame.data_ref() {
+                    self.read += data.len() as u64;
+                }
+                if let Some(limit) = self.limit
+                    && self.read > limit
+                {
+                    self.progress.too_large.store(true, Ordering::Release);
+                    return Poll::Ready(Some(Err(IngressBodyError::TooLarge { limit })));
+                }
+                Poll::Ready(Some(Ok(frame)))
+            }
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(IngressBodyError::Read(e)))),
+            Poll::Ready(None) => {
+                self.progress.complete.store(true, Ordering::Release);
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> hyper::body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Whether a connection has requests in flight, and since when it has had
+/// none, for the idle timeout.
+pub(crate) struct ConnActivity {
+    state: Mutex<Activity>,
+    changed: Notify,
+}
+
+struct Activity {
+    in_flight: usize,
+    idle_since: Instant,
+}
+
+/// Counts a request as in flight on its connection until dropped.
+pub(crate) struct ActiveRequest(Arc<ConnActivity>);
+
+impl ConnActivity {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(Activity {
+                in_flight: 0,
+                idle_since: Instant::now(),
+            }),
+            changed: Notify::new(),
+        })
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, Activity> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Count a request as in flight until the returned guard drops.
+    pub(crate) fn begin(self: &Arc<Self>) -> ActiveRequest {
+        self.state().in_flight += 1;
+        ActiveRequest(Arc::clone(self))
+    }
+
+    /// Resolve once the connection has had no request in flight for `timeout`.
+    pub(crate) async fn idle_for(&self, timeout: Duration) {
+        loop {
+            // Registered before the state is read, so a change in between
+            // still wakes this loop.
+            let changed = self.changed.notified();
+            let idle_since = {
+                let state = self.state();
+                (state.in_flight == 0).then_some(state.idle_since)
+            };
+            match idle_since {
+                Some(since) if since + timeout <= Instant::now() => return,
+                Some(since) => {
+                    tokio::select! {
+                        _ = tokio::time::sleep_until(since + timeout) => {}
+                        _ = changed => {}
+                    }
+                }
+                None => changed.await,
+            }
+        }
+    }
+}
+
+impl Drop for ActiveRequest {
+    fn drop(&mut self) {
+        {
+            let mut state = self.0.state();
+            state.in_flight -= 1;
+            if state.in_flight == 0 {
+                state.idle_since = Instant::now();
+            }
+        }
+        self.0.changed.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn request(headers: &[(&str, &str)]) -> hyper::Request<()> {
+        let mut builder = hyper::Request::builder().uri("/");
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(()).unwrap()
+    }
+
+    #[test]
+    fn workloads_inherit_and_override_the_host_limits() {
+        let host = IngressLimits {
+            max_body_bytes: Some(1024),
+            request_timeout: Some(Duration::from_secs(10)),
+            ..Default::default()
+        };
+        assert_eq!(
+            host.for_workload(&config(&[])).unwrap(),
+            host.request_limits()
+        );
+
+        let limits = host
+            .for_workload(&config(&[
+                (MAX_HEADER_BYTES_KEY, "512"),
+                (MAX_BODY_BYTES_KEY, "1048576"),
+                (REQUEST_TIMEOUT_KEY, "250ms"),
+            ]))
+            .unwrap();
+        assert_eq!(
+            limits,
+            RequestLimits {
+                max_header_bytes: 512,
+                max_body_bytes: Some(1_048_576),
+                request_timeout: Some(Duration::from_millis(250)),
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_overrides_are_rejected() {
+        let host = IngressLimits::default();
+        for pairs in [
+            [(MAX_HEADER_BYTES_KEY, "0")],
+            [(MAX_HEADER_BYTES_KEY, "1048576")],
+            [(MAX_BODY_BYTES_KEY, "-1")],
+            [(MAX_BODY_BYTES_KEY, "0")],
+            [(REQUEST_TIMEOUT_KEY, "soon")],
+            [(REQUEST_TIMEOUT_KEY, "0s")],
+        ] {
+            assert!(host.for_workload(&config(&pairs)).is_err(), "{pairs:?}");
+        }
+        assert!(
+            IngressLimits {
+                idle_timeout: Some(Duration::ZERO),
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn heads_over_the_limits_are_refused() {
+        let limits = IngressLimits {
+            max_body_bytes: Some(10),
+            ..Default::default()
+        }
+        .for_workload(&config(&[(MAX_HEADER_BYTES_KEY, "64")]))
+        .unwrap();
+
+        assert_eq!(limits.check_head(&request(&[("x-a", "b")])), Ok(()));
+        let big = "v".repeat(64);
+        let err = limits.check_head(&request(&[("x-a", &big)])).unwrap_err();
+        assert_eq!((err, err.status()), (LimitExceeded::HeaderTooLarge, 431));
+
+        assert_eq!(
+            limits.check_head(&request(&[("content-length", "10")])),
+            Ok(())
+        );
+        let err = limits
+            .check_head(&request(&[("content-length", "11")]))
+            .unwrap_err();
+        assert_eq!((err, err.status()), (LimitExceeded::BodyTooLarge, 413));
+    }
+
+    #[test]
+    fn timeouts_blame_the_side_still_sending() {
+        assert_eq!(
+            LimitExceeded::Timeout {
+                body_complete: false
+            }
+            .status(),
+            408
+        );
+        assert_eq!(
+            LimitExceeded::Timeout {
+                body_complete: true
+            }
+            .status(),
+            504
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn connections_idle_only_without_requests_in_flight() {
+        let activity = ConnActivity::new();
+        let timeout = Duration::from_secs(5);
+
+        let request = activity.begin();
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert!(
+            tokio::time::timeout(Duration::from_secs(1), activity.idle_for(timeout))
+                .await
+                .is_err(),
+            "a request is in flight"
+        );
+
+        drop(request);
+        let started = Instant::now();
+        activity.idle_for(timeout).await;
+        assert_eq!(started.elapsed(), timeout);
+    }
+}
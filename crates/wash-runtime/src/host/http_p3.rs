@@ -10,6 +10,7 @@
 //! [`crate::host::http::OutgoingHandler`] trait via its `send_request_p3` method.
 
 use crate::engine::instance_pool::ComponentInstance;
+use crate::host::http_limits::IngressBody;
 use crate::observability::FuelConsumptionMeter;
 use http_body_util::BodyExt;
 use tracing::Instrument;
@@ -71,16 +72,13 @@ impl hyper::body::Body for ChannelBody {
 /// memory.
 pub(crate) async fn handle_component_request_p3(
     warm: ComponentInstance,
-    req: hyper::Request<hyper::body::Incoming>,
+    req: hyper::Request<IngressBody>,
     fuel_meter: FuelConsumptionMeter,
 ) -> anyhow::Result<hyper::Response<P3Body>> {
     let _ = &fuel_meter; // fuel metering integration deferred to match P2's observe() pattern
 
-    // Convert the hyper request body — map error type since hyper::Error doesn't impl Into<ErrorCode>
     let (parts, body) = req.into_parts();
-    let body = body
-        .map_err(|e| ErrorCode::InternalError(Some(e.to_string())))
-        .boxed_unsync();
+    let body = body.map_err(ErrorCode::from).boxed_unsync();
     let req = hyper::Request::from_parts(parts, body);
     let (wasi_req, req_io) = wasmtime_wasi_http::p3::Request::from_http(req);
 
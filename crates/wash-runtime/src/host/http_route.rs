@@ -0,0 +1,260 @@
+//! Route rules for [`DynamicRouter`](crate::host::http::DynamicRouter).
+//!
+//! A workload's `wasi:http/incoming-handler` host interface carries its route
+//! rule in the same flat config as `host`:
+//!
+//! | Key             | Format                               | Default |
+//! | --------------- | ------------------------------------ | ------- |
+//! | `path-prefix`   | `/segment[/segment...]`              | `/`     |
+//! | `match-headers` | comma-separated `name=value` pairs   | none    |
+//! | `match-methods` | comma-separated methods (`GET,POST`) | any     |
+//! | `weight`        | positive integer                     | `1`     |
+//!
+//! The rule applies under every hostname the workload is bound to. It is
+//! parsed once, when the workload resolves, so a malformed rule fails the
+//! workload instead of surfacing per request.
+//!
+//! # Matching semantics
+//!
+//! - A prefix matches whole path segments: `/api` matches `/api` and
+//!   `/api/users` but not `/apiary`. A trailing `/` on the prefix is ignored.
+//! - Every `match-headers` pair must be present on the request. Header names
+//!   are case-insensitive; values compare exactly. A repeated header matches
+//!   if any of its values does.
+//! - Among the rules on a hostname that match a request, the most specific
+//!   wins: the longest prefix first, then the most header and method
+//!   predicates. A canary rule carrying a header predicate therefore takes
+//!   precedence over the plain rule beside it.
+//! - Replicas whose rules tie are picked at random in proportion to their
+//!   `weight`.
+
+use std::str::FromStr;
+
+use anyhow::{Context, ensure};
+use hyper::header::{HeaderName, HeaderValue};
+
+/// Config key for the path prefix a workload serves under.
+pub const PATH_PREFIX_KEY: &str = "path-prefix";
+/// Config key for the header predicates a request must carry.
+pub const MATCH_HEADERS_KEY: &str = "match-headers";
+/// Config key for the request methods a workload serves.
+pub const MATCH_METHODS_KEY: &str = "match-methods";
+/// Config key for a replica's share of the traffic its rule matches.
+pub const WEIGHT_KEY: &str = "weight";
+
+/// One workload's route rule on a hostname. See the module docs for the
+/// config format and matching semantics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteRule {
+    /// Normalized: starts with `/`, no trailing `/` unless it is the root.
+    path_prefix: String,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    methods: Vec<hyper::Method>,
+    weight: u32,
+}
+
+impl Default for RouteRule {
+    /// The rule a workload gets when it configures none: every request on its
+    /// hostnames, at weight 1.
+    fn default() -> Self {
+        Self {
+            path_prefix: "/".to_string(),
+            headers: Vec::new(),
+            methods: Vec::new(),
+            weight: 1,
+        }
+    }
+}
+
+/// Orders rules by how narrowly they match; the greatest wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Specificity {
+    prefix_len: usize,
+    predicates: usize,
+}
+
+impl RouteRule {
+    /// Parse the rule from an incoming-handler interface's config. Keys that
+    /// are absent take their defaults; keys that are present must be valid.
+    pub fn from_config(config: &std::collections::HashMap<String, String>) -> anyhow::Result<Self> {
+        let mut rule = Self::default();
+        if let Some(prefix) = config.get(PATH_PREFIX_KEY) {
+            rule.path_prefix = parse_path_prefix(prefix)?;
+        }
+        if let Some(headers) = config.get(MATCH_HEADERS_KEY) {
+            rule.headers = split_list(headers)
+                .map(parse_header_predicate)
+                .collect::<anyhow::Result<_>>()?;
+        }
+        if let Some(methods) = config.get(MATCH_METHODS_KEY) {
+            rule.methods = split_list(methods)
+                .map(|method| {
+                    hyper::Method::from_str(&method.to_ascii_uppercase())
+                        .with_context(|| format!("invalid {MATCH_METHODS_KEY} entry {method:?}"))
+                })
+                .collect::<anyhow::Result<_>>()?;
+        }
+        if let Some(weight) = config.get(WEIGHT_KEY) {
+            rule.weight = weight
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid {WEIGHT_KEY} {weight:?}"))?;
+            ensure!(rule.weight > 0, "{WEIGHT_KEY} must be positive");
+        }
+        Ok(rule)
+    }
+
+    /// The path prefix this rule serves under.
+    pub fn path_prefix(&self) -> &str {
+        &self.path_prefix
+    }
+
+    /// This replica's share of the traffic the rule matches.
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+
+    /// Whether `req` falls under this rule.
+    pub fn matches<B>(&self, req: &hyper::Request<B>) -> bool {
+        self.matches_path(req.uri().path())
+            && (self.methods.is_empty() || self.methods.contains(req.method()))
+            && self.headers.iter().all(|(name, value)| {
+                req.headers()
+                    .get_all(name)
+                    .iter()
+                    .any(|candidate| candidate == value)
+            })
+    }
+
+    fn matches_path(&self, path: &str) -> bool {
+        if self.path_prefix == "/" {
+            return true;
+        }
+        path.strip_prefix(self.path_prefix.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    }
+
+    pub(crate) fn specificity(&self) -> Specificity {
+        Specificity {
+            prefix_len: self.path_prefix.len(),
+            predicates: self.headers.len() + usize::from(!self.methods.is_empty()),
+        }
+    }
+}
+
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn parse_path_prefix(prefix: &str) -> anyhow::Result<String> {
+    let prefix = prefix.trim();
+    ensure!(
+        prefix.starts_with('/'),
+        "{PATH_PREFIX_KEY} {prefix:?} must start with '/'"
+    );
+    ensure!(
+        !prefix.contains(['?', '#']),
+        "{PATH_PREFIX_KEY} {prefix:?} must be a bare path, without a query or fragment"
+    );
+    let trimmed = prefix.trim_end_matches('/');
+    Ok(if trimmed.is_empty() { "/" } else { trimmed }.to_string())
+}
+
+fn parse_header_predicate(predicate: &str) -> anyhow::Result<(HeaderName, HeaderValue)> {
+    let (name, value) = predicate
+        .split_once('=')
+        .with_context(|| format!("{MATCH_HEADERS_KEY} entry {predicate:?} is not name=value"))?;
+    let name = HeaderName::from_str(name.trim()).with_context(|| {
+        format!("invalid header name in {MATCH_HEADERS_KEY} entry {predicate:?}")
+    })?;
+    let value = HeaderValue::from_str(value.trim()).with_context(|| {
+        format!("invalid header value in {MATCH_HEADERS_KEY} entry {predicate:?}")
+    })?;
+    Ok((name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn rule(pairs: &[(&str, &str)]) -> anyhow::Result<RouteRule> {
+        let config: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        RouteRule::from_config(&config)
+    }
+
+    fn request(method: &str, path: &str, headers: &[(&str, &str)]) -> hyper::Request<()> {
+        let mut builder = hyper::Request::builder().method(method).uri(path);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(()).unwrap()
+    }
+
+    #[test]
+    fn default_rule_matches_everything() {
+        let rule = rule(&[]).unwrap();
+        assert_eq!(rule, RouteRule::default());
+        assert!(rule.matches(&request("DELETE", "/any/path?q=1", &[])));
+    }
+
+    #[test]
+    fn prefix_matches_whole_segments() {
+        let rule = rule(&[(PATH_PREFIX_KEY, "/api/")]).unwrap();
+        assert_eq!(rule.path_prefix(), "/api");
+        assert!(rule.matches(&request("GET", "/api", &[])));
+        assert!(rule.matches(&request("GET", "/api/users?page=2", &[])));
+        assert!(!rule.matches(&request("GET", "/apiary", &[])));
+        assert!(!rule.matches(&request("GET", "/", &[])));
+    }
+
+    #[test]
+    fn header_and_method_predicates_must_all_hold() {
+        let rule = rule(&[
+            (MATCH_HEADERS_KEY, "X-Canary=1, x-team=web"),
+            (MATCH_METHODS_KEY, "get,post"),
+        ])
+        .unwrap();
+        let both = [("x-canary", "1"), ("x-team", "web")];
+        assert!(rule.matches(&request("GET", "/", &both)));
+        assert!(rule.matches(&request("POST", "/", &both)));
+        assert!(!rule.matches(&request("PUT", "/", &both)));
+        assert!(!rule.matches(&request("GET", "/", &[("x-canary", "1")])));
+        assert!(!rule.matches(&request(
+            "GET",
+            "/",
+            &[("x-canary", "2"), ("x-team", "web")]
+        )));
+    }
+
+    #[test]
+    fn specificity_prefers_longer_prefixes_then_predicates() {
+        let root = rule(&[]).unwrap();
+        let api = rule(&[(PATH_PREFIX_KEY, "/api")]).unwrap();
+        let canary = rule(&[(MATCH_HEADERS_KEY, "x-canary=1")]).unwrap();
+        let api_canary =
+            rule(&[(PATH_PREFIX_KEY, "/api"), (MATCH_HEADERS_KEY, "x-canary=1")]).unwrap();
+        assert!(root.specificity() < canary.specificity());
+        assert!(canary.specificity() < api.specificity());
+        assert!(api.specificity() < api_canary.specificity());
+    }
+
+    #[test]
+    fn malformed_rules_are_rejected() {
+        for pairs in [
+            [(PATH_PREFIX_KEY, "api")],
+            [(PATH_PREFIX_KEY, "/api?x=1")],
+            [(MATCH_HEADERS_KEY, "x-canary")],
+            [(MATCH_HEADERS_KEY, "bad header=1")],
+            [(MATCH_METHODS_KEY, "GE T")],
+            [(WEIGHT_KEY, "0")],
+            [(WEIGHT_KEY, "heavy")],
+        ] {
+            assert!(rule(&pairs).is_err(), "{pairs:?} should be rejected");
+        }
+    }
+}
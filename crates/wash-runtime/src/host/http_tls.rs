@@ -0,0 +1,594 @@
+//! SNI certificate selection for the HTTP ingress.
+//!
+//! A TLS-terminating ingress (see [`TlsConfig`](crate::host::http::TlsConfig))
+//! picks each connection's certificate from the name the client sent in its
+//! ClientHello, trying in order:
+//!
+//! 1. A workload certificate. A workload's `wasi:http/incoming-handler` host
+//!    interface can set one in the same flat config as `host`; it is served
+//!    for the workload's `host` and `host-aliases`.
+//! 2. The certificate directory. Every `NAME.crt` or `NAME.pem` in it with a
+//!    `NAME.key` beside it is served for the DNS names in the certificate's
+//!    subjectAltName, wildcards included. The directory isn't searched
+//!    recursively. An exact name is preferred over a wildcard; between two
+//!    certificates for the same name, the first in path order is served.
+//! 3. The default certificate, which also answers clients that send no name.
+//!
+//! A handshake none of them covers fails.
+//!
+//! | Key        | Format                                                 | Default |
+//! | ---------- | ------------------------------------------------------ | ------- |
+//! | `tls-cert` | path to a PEM certificate chain, in the cert directory | none    |
+//! | `tls-key`  | path to the chain's PEM key, in the cert directory     | none    |
+//!
+//! Both paths are relative to the ingress's certificate directory, which the
+//! host configures, and may not leave it: absolute paths and `..` are
+//! rejected, so a workload can't have the host read any file it can name. An
+//! ingress without a certificate directory takes no workload certificates.
+//! Keeping them in a subdirectory keeps the directory scan from also serving
+//! them for every name they cover.
+//!
+//! A workload certificate is loaded when the workload resolves, so a missing,
+//! malformed or misplaced one fails the workload instead of its handshakes.
+//!
+//! # Reloading
+//!
+//! Each reload interval the ingress rereads the directory listing and every
+//! certificate file, and reloads the certificates whose files changed, so
+//! renewed, added and removed certificates take effect without restarting it.
+//! A certificate that fails to reload keeps serving its previous version
+//! until its files change again.
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use anyhow::{Context, ensure};
+use arc_swap::ArcSwap;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use tracing::{debug, warn};
+
+/// Config key for the path of a workload's certificate chain.
+pub const TLS_CERT_KEY: &str = "tls-cert";
+/// Config key for the path of a workload certificate's private key.
+pub const TLS_KEY_KEY: &str = "tls-key";
+
+/// How often certificate files are checked for changes unless
+/// [`TlsConfig::with_reload_interval`](crate::host::http::TlsConfig::with_reload_interval)
+/// says otherwise.
+pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Certificate file extensions the directory scan picks up.
+const CERT_EXTENSIONS: [&str; 2] = ["crt", "pem"];
+
+/// A certificate chain file and its private key file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct CertSource {
+    cert: PathBuf,
+    key: PathBuf,
+}
+
+/// A hash of a certificate's files; `None` for a file that couldn't be read.
+type Fingerprint = [Option<u64>; 2];
+
+impl CertSource {
+    pub(crate) fn new(cert: impl Into<PathBuf>, key: impl Into<PathBuf>) -> Self {
+        Self {
+            cert: cert.into(),
+            key: key.into(),
+        }
+    }
+
+    /// The workload certificate set in an incoming-handler config, if any,
+    /// with its paths still relative to the certificate directory. Fails on a
+    /// path that could leave the directory.
+    pub(crate) fn from_config(config: &HashMap<String, String>) -> anyhow::Result<Option<Self>> {
+        match (config.get(TLS_CERT_KEY), config.get(TLS_KEY_KEY)) {
+            (Some(cert), Some(key)) => {
+                for (name, path) in [(TLS_CERT_KEY, cert), (TLS_KEY_KEY, key)] {
+                    ensure!(
+                        is_contained(Path::new(path)),
+                        "`{name}` must be a path inside the ingress's certificate directory, got `{path}`"
+                    );
+                }
+                Ok(Some(Self::new(cert, key)))
+            }
+            (None, None) => Ok(None),
+            _ => anyhow::bail!("`{TLS_CERT_KEY}` and `{TLS_KEY_KEY}` must be set together"),
+        }
+    }
+
+    /// These paths, taken relative to `dir`.
+    fn within(&self, dir: &Path) -> Self {
+        Self::new(dir.join(&self.cert), dir.join(&self.key))
+    }
+
+    /// Hashed rather than compared by modification time, which can miss a
+    /// renewal written within the filesystem's timestamp granularity.
+    fn fingerprint(&self) -> Fingerprint {
+        [&self.cert, &self.key].map(|path| {
+            let mut hasher = DefaultHasher::new();
+            std::fs::read(path).ok()?.hash(&mut hasher);
+            Some(hasher.finish())
+        })
+    }
+
+    fn load(&self) -> anyhow::Result<Arc<CertifiedKey>> {
+        let cert_data = std::fs::read(&self.cert).context(format!(
+            "failed to read certificate file: {}",
+            self.cert.display()
+        ))?;
+        let cert_chain: Vec<CertificateDer<'static>> = CertificateDer::pem_slice_iter(&cert_data)
+            .collect::<Result<Vec<_>, _>>()
+            .context(format!(
+                "failed to parse certificate file: {}",
+                self.cert.display()
+            ))?;
+        ensure!(
+            !cert_chain.is_empty(),
+            "no certificates found in file: {}",
+            self.cert.display()
+        );
+
+        let key_data = std::fs::read(&self.key).context(format!(
+            "failed to read private key file: {}",
+            self.key.display()
+        ))?;
+        let key = PrivateKeyDer::from_pem_slice(&key_data).context(format!(
+            "failed to parse private key file: {}",
+            self.key.display()
+        ))?;
+
+        let provider = CryptoProvider::get_default().context("no TLS crypto provider installed")?;
+        let certified = CertifiedKey::from_der(cert_chain, key, provider).context(format!(
+            "private key {} does not match certificate {}",
+            self.key.display(),
+            self.cert.display()
+        ))?;
+        Ok(Arc::new(certified))
+    }
+}
+
+/// Whether `path` names something inside whatever directory it is joined to:
+/// relative, not empty, and made of plain names only.
+fn is_contained(path: &Path) -> bool {
+    path.components().next().is_some()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Where a [`SniResolver`] finds its certificates.
+#[derive(Debug, Clone, Default)]
+struct Sources {
+    default: Option<CertSource>,
+    dir: Option<PathBuf>,
+    /// Workload certificates and the hostnames they're served for, by
+    /// workload ID. Ordered so that, when two workloads claim a hostname, the
+    /// same one wins every reload.
+    workloads: BTreeMap<String, (Vec<String>, CertSource)>,
+}
+
+impl Sources {
+    /// The certificate pairs in the directory, in path order.
+    fn scan_dir(dir: &Path) -> anyhow::Result<Vec<CertSource>> {
+        let entries = std::fs::read_dir(dir).context(format!(
+            "failed to read certificate directory: {}",
+            dir.display()
+        ))?;
+        let mut pairs: Vec<CertSource> = entries
+            .filter_map(|entry| {
+                let cert = entry.ok()?.path();
+                let extension = cert.extension()?.to_str()?;
+                if !CERT_EXTENSIONS.contains(&extension) {
+                    return None;
+                }
+                let key = cert.with_extension("key");
+                key.is_file().then(|| CertSource::new(cert, key))
+            })
+            .collect();
+        pairs.sort();
+        Ok(pairs)
+    }
+}
+
+/// A loaded certificate, or the last attempt at loading one.
+#[derive(Debug, Clone)]
+struct Loaded {
+    fingerprint: Fingerprint,
+    /// The newest version that loaded, if any has.
+    key: Option<Arc<CertifiedKey>>,
+}
+
+/// The directory certificates, indexed by the DNS names in their
+/// subjectAltNames so a handshake is a map lookup rather than a parse of every
+/// certificate.
+#[derive(Debug, Clone, Default)]
+struct SanIndex {
+    /// Certificates by lowercase exact name.
+    exact: HashMap<String, Arc<CertifiedKey>>,
+    /// Wildcard certificates by the lowercase name their `*.` stands in front
+    /// of: `*.b.test` is under `b.test`.
+    wildcard: HashMap<String, Arc<CertifiedKey>>,
+}
+
+impl SanIndex {
+    /// Index `key` under its names, leaving names an earlier certificate took.
+    fn insert(&mut self, key: &Arc<CertifiedKey>) {
+        let Ok(cert) = key.end_entity_cert() else {
+            return;
+        };
+        let Ok(cert) = webpki::EndEntityCert::try_from(cert) else {
+            return;
+        };
+        for name in cert.valid_dns_names() {
+            let name = name.to_ascii_lowercase();
+            let (map, name) = match name.strip_prefix("*.") {
+                Some(parent) => (&mut self.wildcard, parent.to_string()),
+                None => (&mut self.exact, name),
+            };
+            map.entry(name).or_insert_with(|| key.clone());
+        }
+    }
+
+    /// A wildcard stands for exactly one leftmost label.
+    fn lookup(&self, name: &str) -> Option<Arc<CertifiedKey>> {
+        self.exact
+            .get(name)
+            .or_else(|| {
+                let (_, parent) = name.split_once('.')?;
+                self.wildcard.get(parent)
+            })
+            .cloned()
+    }
+}
+
+/// The certificates a [`SniResolver`] currently serves.
+#[derive(Debug, Default)]
+struct CertTable {
+    default: Option<Arc<CertifiedKey>>,
+    /// Workload certificates by lowercase hostname.
+    by_host: HashMap<String, Arc<CertifiedKey>>,
+    /// Directory certificates by the names they cover.
+    dir: SanIndex,
+    loaded: HashMap<CertSource, Loaded>,
+}
+
+impl CertTable {
+    fn lookup(&self, server_name: &str) -> Option<Arc<CertifiedKey>> {
+        let name = server_name.to_ascii_lowercase();
+        self.by_host
+            .get(&name)
+            .cloned()
+            .or_else(|| self.dir.lookup(&name))
+    }
+}
+
+/// Chooses the ingress's certificate for each TLS handshake by SNI; see the
+/// module docs.
+#[derive(Debug)]
+pub(crate) struct SniResolver {
+    sources: Mutex<Sources>,
+    certs: ArcSwap<CertTable>,
+}
+
+impl SniResolver {
+    /// Load the default certificate and the directory's. Fails if either
+    /// can't be read; a directory pair that doesn't load is skipped with a
+    /// warning, as it would be on reload.
+    pub(crate) fn new(default: Option<CertSource>, dir: Option<PathBuf>) -> anyhow::Result<Self> {
+        if let Some(default) = &default {
+            default.load()?;
+        }
+        if let Some(dir) = &dir {
+            Sources::scan_dir(dir)?;
+        }
+        let resolver = Self {
+            sources: Mutex::new(Sources {
+                default,
+                dir,
+                workloads: BTreeMap::new(),
+            }),
+            certs: ArcSwap::default(),
+        };
+        resolver.reload();
+        Ok(resolver)
+    }
+
+    /// Serve `source`, with paths relative to the certificate directory (see
+    /// [`CertSource::from_config`]), for `hostnames` while the workload runs.
+    /// Fails, changing nothing, if there is no certificate directory or the
+    /// certificate doesn't load.
+    pub(crate) fn insert_workload(
+        &self,
+        workload_id: &str,
+        hostnames: Vec<String>,
+        source: CertSource,
+    ) -> anyhow::Result<()> {
+        let dir = self
+            .sources
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .dir
+            .clone()
+            .context(
+                "the HTTP ingress has no certificate directory to take workload certificates from",
+            )?;
+        let source = source.within(&dir);
+        source.load()?;
+        self.sources
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .workloads
+            .insert(workload_id.to_string(), (hostnames, source));
+        self.reload();
+        Ok(())
+    }
+
+    /// Stop serving a workload's certificate, if it had one.
+    pub(crate) fn remove_workload(&self, workload_id: &str) {
+        let removed = self
+            .sources
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .workloads
+            .remove(workload_id)
+            .is_some();
+        if removed {
+            self.reload();
+        }
+    }
+
+    /// Reread whichever certificates changed on disk and swap in the result.
+    /// Blocks on the filesystem.
+    pub(crate) fn reload(&self) {
+        // Held throughout, so concurrent reloads can't swap in stale tables.
+        let sources = self.sources.lock().unwrap_or_else(|e| e.into_inner());
+        let previous = self.certs.load();
+        let mut loaded = HashMap::new();
+        let mut load = |source: &CertSource| -> Option<Arc<CertifiedKey>> {
+            let fingerprint = source.fingerprint();
+            let last = previous.loaded.get(source);
+            let entry = match last {
+                Some(last) if last.fingerprint == fingerprint => last.clone(),
+                _ => match source.load() {
+                    Ok(key) => {
+                        if last.is_some() {
+                            debug!(cert = %source.cert.display(), "reloaded TLS certificate");
+                        }
+                        Loaded {
+                            fingerprint,
+                            key: Some(key),
+                        }
+                    }
+                    Err(e) => {
+                        warn!(err = ?e, "failed to load TLS certificate; keeping the previous one");
+                        Loaded {
+                            fingerprint,
+                            key: last.and_then(|last| last.key.clone()),
+                        }
+                    }
+                },
+            };
+            let key = entry.key.clone();
+            loaded.insert(source.clone(), entry);
+            key
+        };
+
+        let default = sources.default.as_ref().and_then(&mut load);
+        let mut dir_failed = false;
+        let dir = match sources.dir.as_deref().map(Sources::scan_dir) {
+            Some(Ok(pairs)) => {
+                let mut index = SanIndex::default();
+                for key in pairs.iter().filter_map(&mut load) {
+                    index.insert(&key);
+                }
+                index
+            }
+            Some(Err(e)) => {
+                warn!(err = ?e, "failed to scan TLS certificate directory; keeping its certificates");
+                dir_failed = true;
+                previous.dir.clone()
+            }
+            None => SanIndex::default(),
+        };
+        let mut by_host = HashMap::new();
+        for (hostnames, source) in sources.workloads.values() {
+            if let Some(key) = load(source) {
+                for hostname in hostnames {
+                    by_host
+                        .entry(hostname.to_ascii_lowercase())
+                        .or_insert_with(|| key.clone());
+                }
+            }
+        }
+        if dir_failed {
+            // Remember the kept certificates' files, so they aren't all reread
+            // once the directory is readable again.
+            for (source, entry) in &previous.loaded {
+                loaded
+                    .entry(source.clone())
+                    .or_insert_with(|| entry.clone());
+            }
+        }
+
+        self.certs.store(Arc::new(CertTable {
+            default,
+            by_host,
+            dir,
+            loaded,
+        }));
+    }
+}
+
+impl ResolvesServerCert for SniResolver {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        self.certificate_for(client_hello.server_name())
+    }
+}
+
+impl SniResolver {
+    fn certificate_for(&self, server_name: Option<&str>) -> Option<Arc<CertifiedKey>> {
+        let certs = self.certs.load();
+        server_name
+            .and_then(|name| certs.lookup(name))
+            .or_else(|| certs.default.clone())
+    }
+}
+
+/// Reload `resolver`'s certificates every `interval` until it's dropped.
+pub(crate) async fn watch(resolver: Weak<SniResolver>, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick completes immediately, and the certificates were just
+    // loaded.
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        let Some(resolver) = resolver.upgrade() else {
+            return;
+        };
+        if tokio::task::spawn_blocking(move || resolver.reload())
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)]
+
+    use super::*;
+
+    /// Write a self-signed certificate for `names` to `STEM.crt` and
+    /// `STEM.key` in `dir`, returning its DER.
+    fn write_cert(dir: &Path, stem: &str, names: &[&str]) -> (CertSource, Vec<u8>) {
+        let names = names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        let certified = rcgen::generate_simple_self_signed(names).unwrap();
+        let source = CertSource::new(
+            dir.join(format!("{stem}.crt")),
+            dir.join(format!("{stem}.key")),
+        );
+        std::fs::write(&source.cert, certified.cert.pem()).unwrap();
+        std::fs::write(&source.key, certified.signing_key.serialize_pem()).unwrap();
+        (source, certified.cert.der().to_vec())
+    }
+
+    fn served(resolver: &SniResolver, server_name: Option<&str>) -> Option<Vec<u8>> {
+        let key = resolver.certificate_for(server_name)?;
+        Some(key.end_entity_cert().unwrap().to_vec())
+    }
+
+    #[test]
+    fn certificates_are_chosen_by_sni_then_default() {
+        crate::init_crypto();
+        let default_dir = tempfile::TempDir::new().unwrap();
+        let cert_dir = tempfile::TempDir::new().unwrap();
+        let workload_dir = cert_dir.path().join("workloads");
+        std::fs::create_dir(&workload_dir).unwrap();
+        let (default, default_der) = write_cert(default_dir.path(), "default", &["localhost"]);
+        let (_, exact_der) = write_cert(cert_dir.path(), "a.test", &["a.test"]);
+        let (_, wildcard_der) = write_cert(cert_dir.path(), "wildcard", &["*.b.test"]);
+        let (_, b_der) = write_cert(cert_dir.path(), "b.test", &["b.test"]);
+        std::fs::write(cert_dir.path().join("notes.txt"), "not a certificate").unwrap();
+        let (_, workload_der) = write_cert(&workload_dir, "w", &["a.test"]);
+
+        let resolver =
+            SniResolver::new(Some(default), Some(cert_dir.path().to_path_buf())).unwrap();
+        assert_eq!(served(&resolver, Some("a.test")), Some(exact_der.clone()));
+        assert_eq!(served(&resolver, Some("x.B.test")), Some(wildcard_der));
+        assert_eq!(served(&resolver, Some("b.test")), Some(b_der));
+        // A wildcard covers one label, no more.
+        assert_eq!(
+            served(&resolver, Some("y.x.b.test")),
+            Some(default_der.clone())
+        );
+        assert_eq!(served(&resolver, Some("c.test")), Some(default_der.clone()));
+        assert_eq!(served(&resolver, None), Some(default_der));
+        // Not served by the directory scan, which doesn't recurse.
+        assert_ne!(
+            served(&resolver, Some("a.test")),
+            Some(workload_der.clone())
+        );
+
+        // A workload's certificate, from under the directory, wins for its
+        // own hostnames until it stops.
+        let workload = CertSource::new("workloads/w.crt", "workloads/w.key");
+        resolver
+            .insert_workload("w", vec!["A.test".to_string()], workload)
+            .unwrap();
+        assert_eq!(served(&resolver, Some("a.test")), Some(workload_der));
+        resolver.remove_workload("w");
+        assert_eq!(served(&resolver, Some("a.test")), Some(exact_der));
+
+        let missing = CertSource::new("workloads/missing.crt", "workloads/missing.key");
+        assert!(resolver.insert_workload("x", vec![], missing).is_err());
+
+        // Without a directory there is nowhere to take one from.
+        let dirless = SniResolver::new(None, None).unwrap();
+        let workload = CertSource::new("workloads/w.crt", "workloads/w.key");
+        assert!(dirless.insert_workload("w", vec![], workload).is_err());
+    }
+
+    #[test]
+    fn workload_certificates_must_stay_in_the_directory() {
+        let config = |cert: &str, key: &str| {
+            HashMap::from([
+                (TLS_CERT_KEY.to_string(), cert.to_string()),
+                (TLS_KEY_KEY.to_string(), key.to_string()),
+            ])
+        };
+        assert!(
+            CertSource::from_config(&config("shop/tls.crt", "shop/tls.key"))
+                .unwrap()
+                .is_some()
+        );
+        for (cert, key) in [
+            ("/etc/shadow", "shop/tls.key"),
+            ("shop/tls.crt", "/root/.ssh/id_ed25519"),
+            ("../tls.crt", "shop/tls.key"),
+            ("shop/../../tls.crt", "shop/tls.key"),
+            ("./tls.crt", "tls.key"),
+            ("", "tls.key"),
+        ] {
+            assert!(
+                CertSource::from_config(&config(cert, key)).is_err(),
+                "{cert} / {key} must be rejected"
+            );
+        }
+        assert!(CertSource::from_config(&HashMap::new()).unwrap().is_none());
+    }
+
+    #[test]
+    fn changed_certificates_reload_and_broken_ones_keep_serving() {
+        crate::init_crypto();
+        let dir = tempfile::TempDir::new().unwrap();
+        let (_, first) = write_cert(dir.path(), "a.test", &["a.test"]);
+        let resolver = SniResolver::new(None, Some(dir.path().to_path_buf())).unwrap();
+        assert_eq!(served(&resolver, Some("a.test")), Some(first));
+        assert_eq!(served(&resolver, Some("b.test")), None, "no default");
+
+        let (source, renewed) = write_cert(dir.path(), "a.test", &["a.test"]);
+        let (_, added) = write_cert(dir.path(), "b.test", &["b.test"]);
+        resolver.reload();
+        assert_eq!(served(&resolver, Some("a.test")), Some(renewed.clone()));
+        assert_eq!(served(&resolver, Some("b.test")), Some(added));
+
+        std::fs::write(&source.cert, "half-written").unwrap();
+        std::fs::remove_file(dir.path().join("b.test.key")).unwrap();
+        resolver.reload();
+        assert_eq!(served(&resolver, Some("a.test")), Some(renewed));
+        assert_eq!(served(&resolver, Some("b.test")), None);
+    }
+}
@@ -139,6 +139,21 @@ impl JobRegistry {
         true
     }
 
+    /// Ask every job `workload_id` owns to cancel, on behalf of the host: its
+    /// workload is stopping and the drain deadline has passed. Returns how many
+    /// jobs were marked.
+    pub fn cancel_workload(&self, workload_id: &str) -> usize {
+        let mut inner = self.lock();
+        let mut cancelled = 0;
+        for entry in inner.jobs.values_mut() {
+            if entry.owner.workload_id.as_ref() == workload_id && !entry.cancelled {
+                entry.cancelled = true;
+                cancelled += 1;
+            }
+        }
+        cancelled
+    }
+
     /// Whether `job` has been asked to cancel, for the guest's cooperative poll.
     pub fn is_cancelled(&self, job: JobId) -> bool {
         self.lock()
@@ -281,6 +296,22 @@ mod tests {
         assert!(!reg.is_cancelled(999));
     }
 
+    #[test]
+    fn cancel_workload_marks_only_its_jobs() {
+        let reg = JobRegistry::new();
+        let first = reg.admit(caller("wl-a", "comp-a"));
+        let second = reg.admit(caller("wl-a", "comp-other"));
+        let other = reg.admit(caller("wl-b", "comp-b"));
+
+        assert_eq!(reg.cancel_workload("wl-a"), 2);
+        assert!(reg.is_cancelled(first) && reg.is_cancelled(second));
+        assert!(
+            !reg.is_cancelled(other),
+            "another workload's job is untouched"
+        );
+        assert_eq!(reg.cancel_workload("wl-a"), 0, "already marked");
+    }
+
     #[test]
     fn guard_retires_job_on_drop() {
         let reg = JobRegistry::new();
@@ -52,21 +52,28 @@ use tokio::sync::RwLock;
 use tracing::{debug, info, instrument, trace, warn};
 use wasmtime::component::Component;
 
-use crate::engine::workload::ResolvedWorkload;
+use crate::engine::workload::{ResolvedWorkload, UnresolvedWorkload};
 use crate::engine::{Engine, uses_wasi_http};
 use crate::observability::Meters;
 use crate::plugin::{HostPlugin, WorkloadFailure, WorkloadFailureSink};
 use crate::types::*;
 use crate::wit::{WitInterface, WitWorld};
+use events::{HostEvent, HostEventSink};
 
 mod sysinfo;
 use sysinfo::SystemMonitor;
 
 pub mod allowed_hosts;
 pub mod allowed_ip_name;
+pub mod allowed_socket_addr;
+pub mod events;
 pub mod http;
+pub mod http_admission;
 pub mod http_client;
+pub mod http_limits;
 pub mod http_p3;
+pub mod http_route;
+pub mod http_tls;
 #[cfg(feature = "host-component-plugins")]
 pub(crate) mod job_registry;
 pub mod trigger_service;
@@ -127,6 +134,44 @@ pub trait HostApi {
         &self,
         request: WorkloadStopRequest,
     ) -> impl Future<Output = anyhow::Result<WorkloadStopResponse>>;
+    /// Replace a running workload with a new revision without dropping traffic.
+    ///
+    /// The new revision is compiled alongside the old one, then swapped in
+    /// under the same workload ID: the old revision's plugins are unbound
+    /// before the new one binds, and the new revision's HTTP route replaces
+    /// the old one's in place. The old revision then drains and stops.
+    ///
+    /// # Arguments
+    /// * `request` - Contains the workload ID to update and its new configuration
+    ///
+    /// # Returns
+    /// A `WorkloadUpdateResponse` with the status of the updated workload. If
+    /// the new revision fails to compile or to bind, the status is an error
+    /// and the old revision keeps serving; one that failed to bind is bound
+    /// again from the spec it was started or last updated with.
+    ///
+    /// # Errors
+    /// Returns an error if the update cannot be attempted.
+    fn workload_update(
+        &self,
+        request: WorkloadUpdateRequest,
+    ) -> impl Future<Output = anyhow::Result<WorkloadUpdateResponse>>;
+    /// List every workload on this host.
+    ///
+    /// # Arguments
+    /// * `request` - Currently carries no filters
+    ///
+    /// # Returns
+    /// A `WorkloadListResponse` with a summary of each workload, ordered by
+    /// workload ID. Running workloads also report the live status of each of
+    /// their components.
+    ///
+    /// # Errors
+    /// Returns an error if the workloads cannot be listed.
+    fn workload_list(
+        &self,
+        request: WorkloadListRequest,
+    ) -> impl Future<Output = anyhow::Result<WorkloadListResponse>>;
 }
 
 // Helper trait impl that helps with Arc-ing the Host
@@ -152,6 +197,18 @@ impl<T: HostApi> HostApi for Arc<T> {
     ) -> anyhow::Result<WorkloadStatusResponse> {
         self.as_ref().workload_status(request).await
     }
+    async fn workload_update(
+        &self,
+        request: WorkloadUpdateRequest,
+    ) -> anyhow::Result<WorkloadUpdateResponse> {
+        self.as_ref().workload_update(request).await
+    }
+    async fn workload_list(
+        &self,
+        request: WorkloadListRequest,
+    ) -> anyhow::Result<WorkloadListResponse> {
+        self.as_ref().workload_list(request).await
+    }
 }
 
 /// Internal representation of a workload's state within the host.
@@ -160,20 +217,43 @@ impl<T: HostApi> HostApi for Arc<T> {
 /// through running to stopping or error states.
 #[derive(Debug, Clone)]
 pub enum HostWorkload {
-    Starting,
+    Starting(WorkloadName),
     // Boxed to reduce size of the enum
     Running(Box<ResolvedWorkload>),
-    Stopping,
-    Error(String),
+    Stopping(WorkloadName),
+    Error(WorkloadName, String),
+}
+
+/// The name and namespace a workload was started with, kept for reporting
+/// while it isn't running.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkloadName {
+    pub name: String,
+    pub namespace: String,
+}
+
+impl HostWorkload {
+    /// The name and namespace this workload was started with.
+    pub fn name(&self) -> WorkloadName {
+        match self {
+            HostWorkload::Starting(name)
+            | HostWorkload::Stopping(name)
+            | HostWorkload::Error(name, _) => name.clone(),
+            HostWorkload::Running(rw) => WorkloadName {
+                name: rw.name().to_string(),
+                namespace: rw.namespace().to_string(),
+            },
+        }
+    }
 }
 
 impl std::fmt::Display for HostWorkload {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            HostWorkload::Starting => write!(f, "Starting"),
+            HostWorkload::Starting(_) => write!(f, "Starting"),
             HostWorkload::Running(_) => write!(f, "Running"),
-            HostWorkload::Stopping => write!(f, "Stopping"),
-            HostWorkload::Error(err) => write!(f, "Error: {err}"),
+            HostWorkload::Stopping(_) => write!(f, "Stopping"),
+            HostWorkload::Error(_, err) => write!(f, "Error: {err}"),
         }
     }
 }
@@ -181,10 +261,10 @@ impl std::fmt::Display for HostWorkload {
 impl From<&HostWorkload> for WorkloadState {
     fn from(hw: &HostWorkload) -> Self {
         match hw {
-            HostWorkload::Starting => WorkloadState::Starting,
+            HostWorkload::Starting(_) => WorkloadState::Starting,
             HostWorkload::Running(_) => WorkloadState::Running,
-            HostWorkload::Stopping => WorkloadState::Stopping,
-            HostWorkload::Error(_) => WorkloadState::Error,
+            HostWorkload::Stopping(_) => WorkloadState::Stopping,
+            HostWorkload::Error(..) => WorkloadState::Error,
         }
     }
 }
@@ -200,6 +280,9 @@ pub struct Host {
     engine: Engine,
     /// Workloads mapped from ID to the workload and its current state
     workloads: Arc<RwLock<HashMap<String, HostWorkload>>>,
+    /// The spec each running workload was started or last updated with, so an
+    /// update whose new revision fails to bind can bind the current one again
+    revision_specs: RwLock<HashMap<String, Workload>>,
     /// Plugins in a map from their ID to the plugin itself
     plugins: HashMap<&'static str, Arc<dyn HostPlugin>>,
     /// Host metadata
@@ -216,8 +299,14 @@ pub struct Host {
     pub(crate) http_handler: std::sync::Arc<dyn crate::host::http::HostHandler>,
     config: HostConfig,
     meters: Meters,
+    /// Broadcasts workload and plugin lifecycle events to subscribers
+    events: tokio::sync::broadcast::Sender<HostEvent>,
 }
 
+/// How many events a slow subscriber of [`Host::subscribe_events`] may fall
+/// behind by before it starts missing them.
+const HOST_EVENT_CAPACITY: usize = 256;
+
 impl Host {
     /// Create a new builder for the host.
     pub fn builder() -> HostBuilder {
@@ -338,6 +427,13 @@ impl Host {
         // the workload to a failed state.
         let (failure_tx, failure_rx) = tokio::sync::mpsc::unbounded_channel();
         let failure_sink = WorkloadFailureSink::new(failure_tx);
+        // Component stores report a resource-limit breach on the same sink.
+        self.engine.set_workload_failure_sink(failure_sink.clone());
+        // Service supervisors and plugins report restarts and crash loops as
+        // host events, relayed to subscribers by another background task.
+        let (event_tx, event_rx) = tokio::sync::mpsc::unbounded_channel();
+        let event_sink = HostEventSink::new(event_tx);
+        self.engine.set_host_event_sink(event_sink.clone());
 
         // Start all plugins, any errors means the host fails to start. The
         // failure sink is injected before `start` so a plugin that evicts a
@@ -345,6 +441,7 @@ impl Host {
         for (id, plugin) in &self.plugins {
             plugin.inject_meters(&self.meters).await;
             plugin.set_workload_failure_sink(failure_sink.clone());
+            plugin.set_host_event_sink(event_sink.clone());
 
             if let Err(e) = plugin.start().await {
                 tracing::error!(id = id, err = ?e, "failed to start plugin");
@@ -361,9 +458,25 @@ impl Host {
         // component. `Host::stop` cannot break it either: it stops the plugins
         // but never drops them.
         tokio::spawn(consume_workload_failures(Arc::downgrade(&host), failure_rx));
+        // Weak for the same reason: the engine and plugins hold the event sinks.
+        tokio::spawn(relay_host_events(Arc::downgrade(&host), event_rx));
         Ok(host)
     }
 
+    /// Subscribe to the host's workload and plugin lifecycle events.
+    ///
+    /// Each receiver sees every event emitted after it subscribed. One that
+    /// falls more than a few hundred events behind misses the oldest and is
+    /// told how many by [`tokio::sync::broadcast::error::RecvError::Lagged`].
+    pub fn subscribe_events(&self) -> tokio::sync::broadcast::Receiver<HostEvent> {
+        self.events.subscribe()
+    }
+
+    /// Broadcast `event` to subscribers. Having none is not an error.
+    fn emit(&self, event: HostEvent) {
+        let _ = self.events.send(event);
+    }
+
     /// Transition a running workload to a failed state on a plugin's report
     /// (e.g. an evicted crash-looping bind): swap it to `Error`, so its status
     /// reports failed, and tear down its resources like a stop would. A workload
@@ -373,7 +486,8 @@ impl Host {
             let mut workloads = self.workloads.write().await;
             match workloads.get_mut(workload_id) {
                 Some(slot) => {
-                    let previous = std::mem::replace(slot, HostWorkload::Error(reason.clone()));
+                    let failed = HostWorkload::Error(slot.name(), reason.clone());
+                    let previous = std::mem::replace(slot, failed);
                     match previous {
                         HostWorkload::Running(rw) => Some(*rw),
                         // Not running (starting/stopping/already error): leave the
@@ -389,16 +503,22 @@ impl Host {
             if let Err(e) = resolved.unbind_all_plugins().await {
                 warn!(workload_id, error = ?e, "error unbinding plugins while failing workload");
             }
+            self.emit(HostEvent::WorkloadFailed {
+                workload_id: workload_id.to_string(),
+                reason: reason.clone(),
+            });
         }
         warn!(
             workload_id,
-            reason, "workload failed by a plugin; marked as errored"
+            reason, "workload failed out of band; marked as errored"
         );
     }
 
-    /// Stop the host and shut down all plugins.
+    /// Stop the host, its workloads and all plugins.
     ///
-    /// Attempts to gracefully stop all plugins, allowing each the
+    /// Each workload is stopped as by [`HostApi::workload_stop`], draining its
+    /// in-flight calls for up to [`HostConfig::drain_timeout`]; the workloads
+    /// drain concurrently. Then attempts to gracefully stop all plugins, allowing each the
     /// `WASH_PLUGIN_STOP_TIMEOUT_SECS` budget plus a one-second grace.
     /// Errors are logged but don't prevent other plugins from being
     /// stopped.
@@ -406,6 +526,27 @@ impl Host {
     /// # Returns
     /// Ok if the shutdown process completes (even with plugin errors).
     pub async fn stop(self: Arc<Self>) -> anyhow::Result<()> {
+        // Stop every workload first, each drained for up to the configured
+        // drain timeout, so requests already in flight are answered before
+        // the HTTP server and the plugins serving them go away.
+        // Snapshot the ids so the read lock is released before each stop
+        // takes the write lock.
+        let workload_ids: Vec<String> = {
+            let workloads = self.workloads.read().await;
+            workloads.keys().cloned().collect()
+        };
+        let stops = workload_ids.into_iter().map(|workload_id| {
+            self.workload_stop(WorkloadStopRequest {
+                workload_id,
+                drain_timeout: None,
+            })
+        });
+        for stopped in futures::future::join_all(stops).await {
+            if let Err(e) = stopped {
+                warn!(err = ?e, "failed to stop workload during host shutdown, continuing");
+            }
+        }
+
         self.http_handler
             .stop()
             .await
@@ -617,26 +758,88 @@ impl Host {
         request: WorkloadStartRequest,
     ) -> anyhow::Result<ResolvedWorkload> {
         let service_present = request.workload.service.is_some();
-
-        // Initialize the workload using the engine, receiving the unresolved workload
         let unresolved_workload = self
-            .engine
-            .initialize_workload(&request.workload_id, request.workload)?;
+            .initialize_workload(&request.workload_id, request.workload)
+            .await?;
+        self.resolve_workload(&request.workload_id, unresolved_workload, service_present)
+            .await
+    }
+
+    /// Initialize a workload using the engine, receiving the unresolved
+    /// workload. Compiling its components, and reading or writing the
+    /// compilation disk cache, blocks, so it runs off the async workers.
+    async fn initialize_workload(
+        &self,
+        workload_id: &str,
+        workload: Workload,
+    ) -> anyhow::Result<UnresolvedWorkload> {
+        let engine = self.engine.clone();
+        let workload_id = workload_id.to_string();
+        tokio::task::spawn_blocking(move || engine.initialize_workload(&workload_id, workload))
+            .await
+            .context("workload initialization panicked")?
+    }
 
+    /// Bind an initialized workload to the plugins and the HTTP handler, and
+    /// run its service if it has one.
+    async fn resolve_workload(
+        &self,
+        workload_id: &str,
+        unresolved_workload: UnresolvedWorkload,
+        service_present: bool,
+    ) -> anyhow::Result<ResolvedWorkload> {
         let mut resolved_workload = unresolved_workload
             .resolve(Some(&self.plugins), self.http_handler.clone())
             .await?;
 
-        // If the service didn't run and we had one, warn
-        if service_present && resolved_workload.execute_service().await?.is_none() {
-            warn!(
-                workload_id = request.workload_id,
-                "service did not properly execute"
-            );
+        // If the service didn't run and we had one, warn. One that failed to
+        // start takes its bindings with it, like any other resolve failure.
+        if service_present {
+            match resolved_workload.execute_service().await {
+                Ok(Some(_)) => {}
+                Ok(None) => warn!(workload_id, "service did not properly execute"),
+                Err(e) => {
+                    let _ = resolved_workload.unbind_all_plugins().await;
+                    return Err(e);
+                }
+            }
         }
 
         Ok(resolved_workload)
     }
+
+    /// Bind a workload's running revision again, from the spec it was started
+    /// or last updated with, after [`HostApi::workload_update`] unbound it for
+    /// a new revision that then failed to bind.
+    async fn restore_revision(&self, workload_id: &str) -> anyhow::Result<ResolvedWorkload> {
+        let workload = self
+            .revision_specs
+            .read()
+            .await
+            .get(workload_id)
+            .cloned()
+            .context("no spec kept for the running revision")?;
+        self.workload_start_inner(WorkloadStartRequest {
+            workload_id: workload_id.to_string(),
+            workload,
+        })
+        .await
+    }
+
+    /// Let a revision replaced by [`HostApi::workload_update`] finish its
+    /// in-flight work, for up to `WASH_WORKLOAD_UPDATE_DRAIN_SECS`, then stop
+    /// it. It is already unbound; see [`ResolvedWorkload::retire`].
+    fn retire_revision(&self, revision: ResolvedWorkload) {
+        tokio::spawn(async move {
+            revision
+                .retire(crate::timeouts::workload_update_drain())
+                .await;
+            debug!(
+                workload_id = revision.id(),
+                "replaced revision drained and stopped"
+            );
+        });
+    }
 }
 
 impl HostApi for Host {
@@ -726,11 +929,27 @@ impl HostApi for Host {
                     },
                 });
             }
-            workloads.insert(request.workload_id.clone(), HostWorkload::Starting);
+            workloads.insert(
+                request.workload_id.clone(),
+                HostWorkload::Starting(WorkloadName {
+                    name: request.workload.name.clone(),
+                    namespace: request.workload.namespace.clone(),
+                }),
+            );
         }
 
         let workload_id = request.workload_id.clone();
+        self.emit(HostEvent::WorkloadStarting {
+            workload_id: workload_id.clone(),
+        });
+        let spec = request.workload.clone();
         let resolved_workload = self.workload_start_inner(request).await;
+        if resolved_workload.is_ok() {
+            self.revision_specs
+                .write()
+                .await
+                .insert(workload_id.clone(), spec);
+        }
 
         let (workload_state, message) = if let Err(ref err) = resolved_workload {
             (WorkloadState::Error, err.to_string())
@@ -750,9 +969,19 @@ impl HostApi for Host {
                 Ok(resolved_workload) => {
                     *workload = HostWorkload::Running(Box::new(resolved_workload))
                 }
-                Err(err) => *workload = HostWorkload::Error(err.to_string()),
+                Err(err) => *workload = HostWorkload::Error(workload.name(), err.to_string()),
             });
 
+        self.emit(match workload_state {
+            WorkloadState::Running => HostEvent::WorkloadRunning {
+                workload_id: workload_id.clone(),
+            },
+            _ => HostEvent::WorkloadFailed {
+                workload_id: workload_id.clone(),
+                reason: message.clone(),
+            },
+        });
+
         Ok(WorkloadStartResponse {
             workload_status: WorkloadStatus {
                 workload_id,
@@ -788,6 +1017,41 @@ impl HostApi for Host {
         }
     }
 
+    #[instrument(skip_all)]
+    async fn workload_list(
+        &self,
+        _request: WorkloadListRequest,
+    ) -> anyhow::Result<WorkloadListResponse> {
+        // Snapshot under the lock, then read component status without it so
+        // a slow listing never holds up a start or stop.
+        let mut snapshot = self
+            .workloads
+            .read()
+            .await
+            .iter()
+            .map(|(id, workload)| (id.clone(), workload.clone()))
+            .collect::<Vec<_>>();
+        snapshot.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut workloads = Vec::with_capacity(snapshot.len());
+        for (workload_id, workload) in snapshot {
+            let WorkloadName { name, namespace } = workload.name();
+            let mut summary = WorkloadSummary {
+                workload_id,
+                name,
+                namespace,
+                workload_state: (&workload).into(),
+                message: format!("Workload is {workload}"),
+                components: Vec::new(),
+            };
+            if let HostWorkload::Running(resolved) = &workload {
+                summary.components = resolved.component_statuses().await;
+            }
+            workloads.push(summary);
+        }
+        Ok(WorkloadListResponse { workloads })
+    }
+
     #[instrument(skip_all, fields(workload.id = request.workload_id))]
     async fn workload_stop(
         &self,
@@ -807,47 +1071,47 @@ impl HostApi for Host {
                     workload_id = request.workload_id,
                     "updating workload state to stopping"
                 );
-                // Insert Stopping state, extract the running workload if it was running
-                workloads
-                    .insert(request.workload_id.clone(), HostWorkload::Stopping)
-                    .and_then(|hw| match hw {
+                // Swap in the Stopping state, extract the running workload if it was running
+                workloads.get_mut(&request.workload_id).and_then(|slot| {
+                    let stopping = HostWorkload::Stopping(slot.name());
+                    match std::mem::replace(slot, stopping) {
                         HostWorkload::Running(rw) => Some(*rw),
                         _ => None,
-                    })
+                    }
+                })
             };
 
             // Stop the workload:
-            // 1. Unbind from all plugins
-            // 2. Clean up resources (drop will handle wasmtime cleanup)
-            // 3. Remove from active workloads
+            // 1. Unbind from all plugins, so nothing new is routed to it
+            // 2. Drain the calls in flight, cancelling any left at the deadline
+            // 3. Stop the service and remove from active workloads (drop will
+            //    handle wasmtime cleanup)
             if let Some(resolved_workload) = resolved_workload {
+                let drain_timeout = request.drain_timeout.unwrap_or(self.config.drain_timeout);
                 debug!(
                     workload_id = request.workload_id,
                     workload_name = resolved_workload.name(),
+                    ?drain_timeout,
                     "stopping workload"
                 );
-
-                // Stop the service if running
-                resolved_workload.stop_service();
-
-                // Unbind all plugins from the workload
-                if let Err(e) = resolved_workload.unbind_all_plugins().await {
-                    warn!(
-                        workload_id = request.workload_id,
-                        error = ?e,
-                        "error unbinding plugins during workload stop, continuing"
-                    );
-                }
+                resolved_workload.shut_down(drain_timeout).await;
             }
 
             // Remove the workload from the active workloads map
             // This will drop the workload and clean up wasmtime resources
             self.workloads.write().await.remove(&request.workload_id);
+            self.revision_specs
+                .write()
+                .await
+                .remove(&request.workload_id);
 
             debug!(
                 workload_id = request.workload_id,
                 "workload stopped successfully"
             );
+            self.emit(HostEvent::WorkloadStopped {
+                workload_id: request.workload_id.clone(),
+            });
 
             (
                 WorkloadState::Stopping,
@@ -865,6 +1129,196 @@ impl HostApi for Host {
             },
         })
     }
+
+    /// Update a workload
+    ///
+    /// The new revision is compiled while the current one keeps serving, then
+    /// swapped in under the same workload ID, which is also the ID plugins see.
+    /// The current revision's plugins are unbound before the new revision's
+    /// are bound, so no message is handled by both, and the new revision's
+    /// HTTP route replaces the current one's in place, so requests keep being
+    /// answered. Both are done before the update returns; the replaced
+    /// revision then finishes its calls in flight and stops. An update that
+    /// drops the workload's HTTP route or its service unroutes the current
+    /// revision before the new one binds. Plugin state dropped on unbind (e.g.
+    /// in-memory keyvalue buckets) starts over, as on a restart.
+    ///
+    /// Plugins can't take back a revision they unbound, so when the new
+    /// revision fails to bind, the current one is bound again from its spec
+    /// and swapped back in, while the unbound one drains like a replaced one.
+    #[instrument(skip_all, fields(workload.id = request.workload_id, workload.name = request.workload.name, workload.namespace = request.workload.namespace))]
+    async fn workload_update(
+        &self,
+        request: WorkloadUpdateRequest,
+    ) -> anyhow::Result<WorkloadUpdateResponse> {
+        let workload_id = request.workload_id;
+        let response = |workload_state, message| WorkloadUpdateResponse {
+            workload_status: WorkloadStatus {
+                workload_id: workload_id.clone(),
+                workload_state,
+                message,
+            },
+        };
+
+        match self.workloads.read().await.get(&workload_id) {
+            Some(HostWorkload::Running(_)) => {}
+            Some(workload) => {
+                return Ok(response(
+                    WorkloadState::Error,
+                    format!("Workload is {workload}; only a running workload can be updated"),
+                ));
+            }
+            None => {
+                return Ok(response(
+                    WorkloadState::NotFound,
+                    format!("Workload not found: {workload_id}"),
+                ));
+            }
+        }
+
+        // Compile the new revision while the current one keeps serving; one
+        // that fails to compile leaves it untouched.
+        let service_present = request.workload.service.is_some();
+        let spec = request.workload.clone();
+        let unresolved_workload = match self
+            .initialize_workload(&workload_id, request.workload)
+            .await
+        {
+            Ok(unresolved_workload) => unresolved_workload,
+            Err(err) => {
+                warn!(
+                    workload_id,
+                    error = ?err,
+                    "new revision failed to start, keeping the current one"
+                );
+                return Ok(response(WorkloadState::Error, err.to_string()));
+            }
+        };
+
+        // Take the current revision out for the swap, leaving the workload
+        // starting until it is done. A stop, failure or update that got in
+        // first wins over this update.
+        let current = {
+            let mut workloads = self.workloads.write().await;
+            match workloads.get_mut(&workload_id) {
+                Some(slot) if matches!(slot, HostWorkload::Running(_)) => {
+                    let starting = HostWorkload::Starting(slot.name());
+                    match std::mem::replace(slot, starting) {
+                        HostWorkload::Running(rw) => Some(*rw),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        };
+        let Some(current) = current else {
+            return Ok(response(
+                WorkloadState::Error,
+                "Workload changed while the update was in progress".to_string(),
+            ));
+        };
+
+        // Plugins track workloads by ID, so the current revision's bindings
+        // must be gone before the new revision's are made under the same one.
+        // Its HTTP registrations stay until the new revision's overwrite them,
+        // unless the new revision won't make them all.
+        current.unbind_host_plugins().await;
+        if !current.is_replaced_in_place_by(&unresolved_workload)
+            && let Err(e) = self.http_handler.on_workload_unbind(&workload_id).await
+        {
+            warn!(workload_id, error = ?e, "error unrouting the replaced revision, continuing");
+        }
+
+        let (revision, outcome) = match self
+            .resolve_workload(&workload_id, unresolved_workload, service_present)
+            .await
+        {
+            Ok(resolved_workload) => (resolved_workload, Ok(())),
+            Err(err) => {
+                warn!(
+                    workload_id,
+                    error = ?err,
+                    "new revision failed to bind, restoring the current one"
+                );
+                match self.restore_revision(&workload_id).await {
+                    Ok(restored) => (restored, Err(err)),
+                    Err(restore_err) => {
+                        let reason = format!(
+                            "{err}; binding the current revision again failed: {restore_err}"
+                        );
+                        warn!(workload_id, reason, "failing the workload");
+                        if let Some(slot) = self.workloads.write().await.get_mut(&workload_id)
+                            && matches!(slot, HostWorkload::Starting(_))
+                        {
+                            *slot = HostWorkload::Error(slot.name(), reason.clone());
+                        }
+                        let drain_timeout = self.config.drain_timeout;
+                        tokio::spawn(async move { current.shut_down(drain_timeout).await });
+                        self.emit(HostEvent::WorkloadFailed {
+                            workload_id: workload_id.clone(),
+                            reason: reason.clone(),
+                        });
+                        return Ok(response(WorkloadState::Error, reason));
+                    }
+                }
+            }
+        };
+
+        let abandoned = {
+            let mut workloads = self.workloads.write().await;
+            match workloads.get_mut(&workload_id) {
+                Some(slot) if matches!(slot, HostWorkload::Starting(_)) => {
+                    *slot = HostWorkload::Running(Box::new(revision));
+                    if outcome.is_ok() {
+                        self.revision_specs
+                            .write()
+                            .await
+                            .insert(workload_id.clone(), spec);
+                    }
+                    None
+                }
+                _ => Some(revision),
+            }
+        };
+        self.retire_revision(current);
+
+        match (abandoned, outcome) {
+            (None, Ok(())) => {
+                debug!(
+                    workload_id,
+                    "new revision running, draining the replaced one"
+                );
+                self.emit(HostEvent::WorkloadRunning {
+                    workload_id: workload_id.clone(),
+                });
+                Ok(response(
+                    WorkloadState::Running,
+                    "Workload updated successfully".to_string(),
+                ))
+            }
+            (None, Err(err)) => {
+                debug!(
+                    workload_id,
+                    "current revision restored after a failed update"
+                );
+                Ok(response(WorkloadState::Error, err.to_string()))
+            }
+            (Some(abandoned), _) => {
+                abandoned.stop_service();
+                if let Err(e) = abandoned.unbind_all_plugins().await {
+                    warn!(
+                        workload_id,
+                        error = ?e,
+                        "error unbinding plugins from abandoned revision, continuing"
+                    );
+                }
+                Ok(response(
+                    WorkloadState::Error,
+                    "Workload changed while the update was in progress".to_string(),
+                ))
+            }
+        }
+    }
 }
 
 impl std::fmt::Debug for Host {
@@ -905,12 +1359,40 @@ async fn consume_workload_failures(
     }
 }
 
+/// Relay out-of-band events reported on a [`HostEventSink`] to the host's
+/// subscribers. Ends when the host is dropped.
+///
+/// Holds the host weakly, like [`consume_workload_failures`].
+async fn relay_host_events(
+    host: std::sync::Weak<Host>,
+    mut rx: tokio::sync::mpsc::UnboundedReceiver<HostEvent>,
+) {
+    while let Some(event) = rx.recv().await {
+        let Some(host) = host.upgrade() else {
+            break;
+        };
+        host.emit(event);
+    }
+}
+
 /// Config for the [`Host`]
 #[derive(Clone, Debug)]
 pub struct HostConfig {
     pub allow_oci_insecure: bool,
     pub oci_pull_timeout: Option<Duration>,
     pub oci_cache_dir: Option<PathBuf>,
+    /// Which workload images must be signed before they are run, and by
+    /// whom. The default checks nothing.
+    #[cfg(feature = "oci")]
+    pub oci_signature_policy: crate::oci::signature::SignaturePolicy,
+    /// Directory compiled components are kept in across restarts, set on the
+    /// host's engine at build. `None` compiles every component afresh on
+    /// each start. See [`crate::engine::EngineBuilder::with_compilation_disk_cache`].
+    pub compilation_cache_dir: Option<PathBuf>,
+    /// How long a stopping workload's in-flight calls may take to finish
+    /// before they are cancelled, unless the stop request sets its own. Also
+    /// bounds each workload's drain when the host shuts down.
+    pub drain_timeout: Duration,
 }
 
 impl Default for HostConfig {
@@ -919,6 +1401,10 @@ impl Default for HostConfig {
             allow_oci_insecure: false,
             oci_pull_timeout: Duration::from_secs(30).into(),
             oci_cache_dir: None,
+            #[cfg(feature = "oci")]
+            oci_signature_policy: Default::default(),
+            compilation_cache_dir: None,
+            drain_timeout: Duration::from_secs(30),
         }
     }
 }
@@ -1109,13 +1595,18 @@ impl HostBuilder {
     /// A new `Host` instance ready to be started.
     ///
     /// # Errors
-    /// Returns an error if the default engine cannot be created (when no engine is provided).
+    /// Returns an error if the default engine cannot be created (when no engine is provided),
+    /// or if the configured compilation cache directory cannot be created.
     pub fn build(self) -> anyhow::Result<Host> {
         let engine = if let Some(engine) = self.engine {
             engine
         } else {
             Engine::builder().build()?
         };
+        let config = self.config.unwrap_or_default();
+        if let Some(dir) = &config.compilation_cache_dir {
+            engine.set_compilation_disk_cache(dir)?;
+        }
 
         // Get hostname from system if not provided
         let hostname = self.hostname.unwrap_or_else(|| {
@@ -1142,6 +1633,7 @@ impl HostBuilder {
         Ok(Host {
             engine,
             workloads: Arc::default(),
+            revision_specs: RwLock::default(),
             plugins: self.plugins,
             id: self.id,
             hostname,
@@ -1152,8 +1644,9 @@ impl HostBuilder {
             started_at: chrono::Utc::now(),
             system_monitor: Arc::new(RwLock::new(SystemMonitor::new())),
             http_handler,
-            config: self.config.unwrap_or_default(),
+            config,
             meters: self.meters,
+            events: tokio::sync::broadcast::channel(HOST_EVENT_CAPACITY).0,
         })
     }
 }
@@ -1226,6 +1719,134 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_workload_lifecycle_emits_events() {
+        let host = Host::builder().build().expect("failed to build host");
+        let mut events = host.subscribe_events();
+
+        host.workload_start(empty_workload_start_request("evented"))
+            .await
+            .expect("start should return a response");
+        host.workload_update(WorkloadUpdateRequest {
+            workload_id: "evented".to_string(),
+            workload: empty_workload_start_request("evented").workload,
+        })
+        .await
+        .expect("update should return a response");
+        host.fail_workload("evented", "evicted".to_string()).await;
+        host.workload_stop(WorkloadStopRequest {
+            workload_id: "evented".to_string(),
+            drain_timeout: None,
+        })
+        .await
+        .expect("stop should return a response");
+
+        let id = || "evented".to_string();
+        let expected = [
+            HostEvent::WorkloadStarting { workload_id: id() },
+            HostEvent::WorkloadRunning { workload_id: id() },
+            HostEvent::WorkloadRunning { workload_id: id() },
+            HostEvent::WorkloadFailed {
+                workload_id: id(),
+                reason: "evicted".to_string(),
+            },
+            HostEvent::WorkloadStopped { workload_id: id() },
+        ];
+        for expected in expected {
+            assert_eq!(events.try_recv().expect("event emitted"), expected);
+        }
+        assert!(events.try_recv().is_err(), "no further events");
+    }
+
+    #[tokio::test]
+    async fn test_workload_start_failure_emits_failed_event() {
+        let host = Host::builder().build().expect("failed to build host");
+        let mut events = host.subscribe_events();
+        let mut request = empty_workload_start_request("broken");
+        request.workload.components.push(Component {
+            name: "broken".to_string(),
+            digest: None,
+            bytes: bytes::Bytes::from_static(b"not a component"),
+            local_resources: Default::default(),
+            pool_size: 0,
+            max_invocations: 0,
+            max_concurrency: 0,
+            max_execution_time: None,
+        });
+
+        let response = host
+            .workload_start(request)
+            .await
+            .expect("start should return a response");
+        assert_eq!(
+            response.workload_status.workload_state,
+            WorkloadState::Error
+        );
+
+        assert_eq!(
+            events.try_recv().expect("starting event"),
+            HostEvent::WorkloadStarting {
+                workload_id: "broken".to_string()
+            }
+        );
+        match events.try_recv().expect("failed event") {
+            HostEvent::WorkloadFailed {
+                workload_id,
+                reason,
+            } => {
+                assert_eq!(workload_id, "broken");
+                assert_eq!(reason, response.workload_status.message);
+            }
+            other => panic!("expected a failed event, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_workload_list_reports_each_workload_in_id_order() {
+        let host = Host::builder().build().expect("failed to build host");
+        let mut broken = empty_workload_start_request("a-broken");
+        broken.workload.components.push(Component {
+            name: "broken".to_string(),
+            digest: None,
+            bytes: bytes::Bytes::from_static(b"not a component"),
+            local_resources: Default::default(),
+            pool_size: 0,
+            max_invocations: 0,
+            max_concurrency: 0,
+            max_execution_time: None,
+        });
+        host.workload_start(empty_workload_start_request("b-running"))
+            .await
+            .expect("start should return a response");
+        host.workload_start(broken)
+            .await
+            .expect("start should return a response");
+
+        let listed = host
+            .workload_list(WorkloadListRequest::default())
+            .await
+            .expect("list should return a response")
+            .workloads;
+        assert_eq!(listed.len(), 2);
+
+        let failed = &listed[0];
+        assert_eq!(failed.workload_id, "a-broken");
+        assert_eq!(failed.workload_state, WorkloadState::Error);
+        assert!(failed.message.starts_with("Workload is Error:"));
+        // A failed workload keeps the name it was started with, but has no
+        // components to report.
+        assert_eq!(failed.name, "empty");
+        assert_eq!(failed.namespace, "wasmcloud");
+        assert!(failed.components.is_empty());
+
+        let running = &listed[1];
+        assert_eq!(running.workload_id, "b-running");
+        assert_eq!(running.workload_state, WorkloadState::Running);
+        assert_eq!(running.message, "Workload is Running");
+        assert_eq!(running.name, "empty");
+        assert_eq!(running.namespace, "wasmcloud");
+    }
+
     #[tokio::test]
     async fn test_workload_start_rejects_existing_id() {
         let host = Host::builder().build().expect("failed to build host");
@@ -1319,6 +1940,7 @@ mod tests {
                         pool_size: 1,
                         max_invocations: 100,
                         max_concurrency: 1,
+                        max_execution_time: None,
                     }],
                     host_interfaces: vec![],
                     volumes: vec![],
@@ -1353,6 +1975,7 @@ mod tests {
                         digest: None,
                         local_resources: Default::default(),
                         max_restarts: 0,
+                        ..Default::default()
                     }),
                     components: vec![],
                     host_interfaces: vec![],
@@ -47,13 +47,19 @@ impl hyper::body::Body for ChannelBody {
 /// re-registers) a fresh instance. See `test_trigger_service_http_restarts_on_fault`.
 pub(crate) struct HttpTask {
     pub(crate) service: Arc<Service>,
-    pub(crate) req: hyper::Request<hyper::body::Incoming>,
+    pub(crate) req: hyper::Request<crate::host::http_limits::IngressBody>,
     pub(crate) resp_tx:
         tokio::sync::oneshot::Sender<anyhow::Result<hyper::Response<HyperOutgoingBody>>>,
     /// This call's tether to a pooled instance: holds its in-flight slot and
     /// can retire the instance. `None` for a service, whose singleton instance
     /// is not the pool's to retire.
     pub(crate) pool_slot: Option<crate::engine::instance_driver::PoolSlot>,
+    /// Counts this call as in flight in the service's stats, so a stop can
+    /// wait for it. `None` for a pooled call, whose slot counts it instead.
+    pub(crate) call: Option<crate::engine::stats::InFlightCall>,
+    /// Times this call against the service's `max_execution_time` for as long
+    /// as it runs. `None` for a pooled call, whose slot times it instead.
+    pub(crate) call_timer: Option<crate::engine::epoch::CallTimer>,
 }
 
 impl AccessorTask<SharedCtx> for HttpTask {
@@ -63,12 +69,12 @@ impl AccessorTask<SharedCtx> for HttpTask {
             req,
             resp_tx,
             pool_slot,
+            call: _call,
+            call_timer: _call_timer,
         } = self;
 
         let (parts, body) = req.into_parts();
-        let body = body
-            .map_err(|e| ErrorCode::InternalError(Some(e.to_string())))
-            .boxed_unsync();
+        let body = body.map_err(ErrorCode::from).boxed_unsync();
         let req = hyper::Request::from_parts(parts, body);
         let (wasi_req, req_io) = wasmtime_wasi_http::p3::Request::from_http(req);
 
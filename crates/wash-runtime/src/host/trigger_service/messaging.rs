@@ -1,5 +1,6 @@
 //! The [`Ingress::Messaging`] path: `wasmcloud:messaging/handler@0.2.0`
-//! invocations served on the shared service instance.
+//! invocations served on the shared service instance. The messaging plugins
+//! refuse to bind a service that exports the handler at any other version.
 //!
 //! [`Ingress::Messaging`]: super::Ingress::Messaging
 
@@ -35,11 +36,21 @@ pub(super) const HANDLE_MESSAGE: &str = "handle-message";
 /// unenterable for every later message, so after reporting it the task returns
 /// the error — faulting `run_concurrent` so the driver exits and the service
 /// supervisor restarts (and re-registers) a fresh instance.
+///
+/// Either failure is retried, and finally dead-lettered, by the messaging
+/// plugin that delivered the message, per its `retry_count` and
+/// `dead_letter_subject` config.
 pub(super) struct MessagingTask {
     pub(super) instance: Instance,
     pub(super) func_idx: ComponentExportIndex,
     pub(super) msg: BrokerMessage,
     pub(super) result_tx: tokio::sync::oneshot::Sender<Result<(), String>>,
+    /// Counts this call as in flight in the service's stats, so a stop can
+    /// wait for it.
+    pub(super) call: Option<crate::engine::stats::InFlightCall>,
+    /// Times this call against the service's `max_execution_time` for as long
+    /// as it runs.
+    pub(super) call_timer: Option<crate::engine::epoch::CallTimer>,
 }
 
 impl AccessorTask<SharedCtx> for MessagingTask {
@@ -49,6 +60,8 @@ impl AccessorTask<SharedCtx> for MessagingTask {
             func_idx,
             msg,
             result_tx,
+            call: _call,
+            call_timer: _call_timer,
         } = self;
 
         let func = match accessor.with(|mut store| instance.get_func(&mut store, func_idx)) {
@@ -39,6 +39,8 @@ use wasmtime_wasi::p3::bindings::Command;
 use wasmtime_wasi_http::p3::bindings::Service;
 
 use crate::engine::ctx::SharedCtx;
+use crate::engine::epoch::ExecutionClock;
+use crate::engine::stats::ComponentStats;
 use crate::host::http::ServiceHttpJob;
 #[cfg(feature = "host-component-plugins")]
 use crate::host::job_registry::JobRegistry;
@@ -206,16 +208,21 @@ impl PreparedIngress {
     async fn serve(&mut self, accessor: &Accessor<SharedCtx>) -> ServeOutcome {
         match self {
             PreparedIngress::Http { service, rx } => {
+                let stats = call_stats(accessor);
+                let clock = execution_clock(accessor);
                 while let Some((req, resp_tx)) = rx.recv().await {
                     if let Err(e) = accessor.spawn(HttpTask {
                         service: Arc::clone(service),
                         req,
                         resp_tx,
                         pool_slot: None,
+                        call: stats.as_ref().map(ComponentStats::begin),
+                        call_timer: clock.as_ref().map(ExecutionClock::begin),
                     }) {
                         tracing::error!(err = %e, "failed to spawn HTTP invocation task");
                     }
                 }
+                drain_calls(stats).await;
                 ServeOutcome::Shutdown
             }
             PreparedIngress::Messaging {
@@ -223,16 +230,21 @@ impl PreparedIngress {
                 func_idx,
                 rx,
             } => {
+                let stats = call_stats(accessor);
+                let clock = execution_clock(accessor);
                 while let Some((msg, result_tx)) = rx.recv().await {
                     if let Err(e) = accessor.spawn(MessagingTask {
                         instance: *instance,
                         func_idx: *func_idx,
                         msg,
                         result_tx,
+                        call: stats.as_ref().map(ComponentStats::begin),
+                        call_timer: clock.as_ref().map(ExecutionClock::begin),
                     }) {
                         tracing::error!(err = %e, "failed to spawn messaging invocation task");
                     }
                 }
+                drain_calls(stats).await;
                 ServeOutcome::Shutdown
             }
             #[cfg(feature = "host-component-plugins")]
@@ -319,6 +331,27 @@ impl PreparedIngress {
     }
 }
 
+/// The stats the calls served on this store are counted against, if any.
+fn call_stats(accessor: &Accessor<SharedCtx>) -> Option<Arc<ComponentStats>> {
+    accessor.with(|mut access| access.get().call_stats.clone())
+}
+
+/// The clock the calls served on this store are timed against, if the service
+/// has a `max_execution_time`.
+fn execution_clock(accessor: &Accessor<SharedCtx>) -> Option<Arc<ExecutionClock>> {
+    accessor.with(|mut access| access.get().execution_clock.clone())
+}
+
+/// Once an ingress channel has closed — the workload is stopping — keep the
+/// driver in `run_concurrent` until the calls it already took have finished,
+/// rather than stepping out and stranding them on the store. The stop's drain
+/// deadline bounds this: past it, the service is aborted along with its store.
+async fn drain_calls(stats: Option<Arc<ComponentStats>>) {
+    if let Some(stats) = stats {
+        stats.idle().await;
+    }
+}
+
 /// Await one replayed bind's completion, bounded by
 /// [`crate::timeouts::plugin_lifecycle_call`], and log its outcome. The wait
 /// must be bounded: a replayed bind that itself calls one of the plugin's own
@@ -154,6 +154,7 @@ fn directive(directive: impl AsRef<str>) -> anyhow::Result<Directive> {
 #[derive(Clone, Default)]
 pub struct Meters {
     pub fuel_consumption: FuelConsumptionMeter,
+    pub http_rejections: HttpRejectionMeter,
     /// User-defined meters
     pub meters: HashMap<String, Arc<dyn Any + Send + Sync + 'static>>,
 }
@@ -162,6 +163,7 @@ impl Meters {
     pub fn new(enabled: bool) -> Self {
         Self {
             fuel_consumption: FuelConsumptionMeter::new(enabled),
+            http_rejections: HttpRejectionMeter::new(enabled),
             meters: Default::default(),
         }
     }
@@ -208,6 +210,32 @@ impl FuelConsumptionMeter {
     }
 }
 
+/// Counts requests the HTTP ingress turned away or cut short, by admission
+/// control (see [`crate::host::http_admission`]) or for exceeding a request
+/// limit (see [`crate::host::http_limits`]).
+#[derive(Clone, Default)]
+pub struct HttpRejectionMeter {
+    counter: Option<opentelemetry::metrics::Counter<u64>>,
+}
+
+impl HttpRejectionMeter {
+    pub(crate) fn new(enabled: bool) -> Self {
+        let counter = enabled.then(|| {
+            opentelemetry::global::meter("wash-runtime")
+                .u64_counter("http.ingress.rejections")
+                .with_description("Requests the HTTP ingress rejected or cut short")
+                .build()
+        });
+        Self { counter }
+    }
+
+    pub fn record(&self, attributes: &[KeyValue]) {
+        if let Some(counter) = &self.counter {
+            counter.add(1, attributes);
+        }
+    }
+}
+
 /// Generate histogram boundaries for fuel consumption metrics.
 ///
 /// Produces boundaries following multipliers [1, 2.5, 5, 7.5] per decade,
@@ -0,0 +1,688 @@
+//! On-disk cache of pulled components, stored once per image digest.
+//!
+//! ```text
+//! <cache_dir>/
+//!   blobs/<algorithm>/<encoded>   component bytes of the image with that digest
+//!   refs/<reference>_<hash>       the digest a reference last resolved to
+//! ```
+//!
+//! A reference is an index entry pointing at a blob, so the same image pulled
+//! under two tags is stored once. Reading a blob bumps its modification time,
+//! which is what [`CacheManager::collect`] orders by when the cache is over its
+//! size limit.
+//!
+//! Blobs and index entries are written aside and renamed into place, so a
+//! concurrent reader never sees a partial file.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result, bail};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+/// Directory holding one file per image digest
+const BLOBS_DIR: &str = "blobs";
+/// Directory holding one file per reference
+const REFS_DIR: &str = "refs";
+/// Suffix of a file still being written
+const STAGING_SUFFIX: &str = ".tmp";
+
+/// Limits the artifact cleaner holds the cache to. Blobs of running workloads
+/// are kept whatever the limits say.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheLimits {
+    /// Remove blobs that have not been pulled or read for this long
+    pub max_age: Option<Duration>,
+    /// Remove the least recently used blobs until the cache is no larger than
+    /// this many bytes
+    pub max_bytes: Option<u64>,
+}
+
+/// What a run of the artifact cleaner removed and what it left behind
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheCleanup {
+    /// Component blobs removed
+    pub blobs_removed: usize,
+    /// References removed because the blob they pointed at is gone
+    pub references_removed: usize,
+    /// Bytes freed on disk
+    pub bytes_freed: u64,
+    /// Bytes of component blobs still cached
+    pub bytes_retained: u64,
+}
+
+/// A cached blob, as seen by the cleaner
+struct Blob {
+    path: PathBuf,
+    /// `None` for a file left behind by an interrupted write
+    digest: Option<String>,
+    size: u64,
+    last_used: SystemTime,
+}
+
+/// Cache manager for OCI artifacts
+pub(super) struct CacheManager {
+    cache_dir: PathBuf,
+}
+
+impl CacheManager {
+    /// Create a new cache manager with the specified cache directory
+    pub(super) fn new(cache_dir: PathBuf) -> Self {
+        Self { cache_dir }
+    }
+
+    /// The path of the index entry for a reference
+    fn ref_path(&self, reference: &str) -> PathBuf {
+        // Hash for uniqueness, but keep the reference in the path for readability
+        let hash = format!("{:x}", Sha256::digest(reference.as_bytes()));
+        let short_hash = hash.get(..8).unwrap_or(&hash);
+
+        // Sanitize the reference for filesystem use
+        let sanitized = reference.replace(['/', ':', '@'], "_");
+
+        // File: <cache_dir>/refs/<sanitized_reference>_<short_hash>
+        self.cache_dir
+            .join(REFS_DIR)
+            .join(format!("{sanitized}_{short_hash}"))
+    }
+
+    /// The path of the blob for an image digest
+    ///
+    /// # Errors
+    /// Returns an error if the digest isn't `<algorithm>:<encoded>` as the OCI
+    /// spec spells it, which also keeps it from naming a path outside the cache.
+    fn blob_path(&self, digest: &str) -> Result<PathBuf> {
+        let Some((algorithm, encoded)) = digest.split_once(':') else {
+            bail!("invalid digest {digest:?}: missing algorithm");
+        };
+        let valid_algorithm = !algorithm.is_empty()
+            && algorithm
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "+._-".contains(c));
+        let valid_encoded = !encoded.is_empty()
+            && encoded
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "=_-".contains(c));
+        if !valid_algorithm || !valid_encoded || algorithm.starts_with('.') {
+            bail!("invalid digest {digest:?}");
+        }
+        Ok(self.cache_dir.join(BLOBS_DIR).join(algorithm).join(encoded))
+    }
+
+    /// The digest a reference last resolved to, if it is cached
+    pub(super) async fn resolve(&self, reference: &str) -> Option<String> {
+        let ref_path = self.ref_path(reference);
+        match tokio::fs::read_to_string(&ref_path).await {
+            Ok(digest) => Some(digest.trim().to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                warn!(path = %ref_path.display(), error = %e, "failed to read cached reference");
+                None
+            }
+        }
+    }
+
+    /// Read the cached component for an image digest, marking it recently used
+    pub(super) async fn read_blob(&self, digest: &str) -> Result<Option<Vec<u8>>> {
+        let blob_path = self.blob_path(digest)?;
+        debug!(blob_path = %blob_path.display(), "reading cached artifact");
+
+        let data = match tokio::fs::read(&blob_path).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("failed to read cached component at {}", blob_path.display())
+                });
+            }
+        };
+        touch(&blob_path).await;
+        Ok(Some(data))
+    }
+
+    /// Point a reference at an image digest whose blob is already cached
+    pub(super) async fn link(&self, reference: &str, digest: &str) -> Result<()> {
+        let ref_path = self.ref_path(reference);
+        write_atomically(&ref_path, digest.as_bytes())
+            .await
+            .with_context(|| format!("failed to write cached reference at {}", ref_path.display()))
+    }
+
+    /// Write artifact to cache under its digest and point the reference at it
+    pub(super) async fn write_to_cache(
+        &self,
+        reference: &str,
+        data: &[u8],
+        digest: &str,
+    ) -> Result<()> {
+        let blob_path = self.blob_path(digest)?;
+        debug!(blob_path = %blob_path.display(), "writing to cache");
+
+        // The same digest is the same bytes, so a blob already present under
+        // another reference is reused
+        if tokio::fs::try_exists(&blob_path).await.unwrap_or(false) {
+            touch(&blob_path).await;
+        } else {
+            write_atomically(&blob_path, data).await.with_context(|| {
+                format!(
+                    "failed to write component to cache at {}",
+                    blob_path.display()
+                )
+            })?;
+        }
+
+        self.link(reference, digest).await
+    }
+
+    /// Remove what `limits` no longer allow, sparing the blobs whose digest is
+    /// in `in_use`, then the references left pointing at nothing
+    ///
+    /// Directories left in the cache by the layout before this one, which
+    /// stored one directory per reference, are removed once they exceed
+    /// `limits.max_age`.
+    pub(super) async fn collect(
+        &self,
+        limits: CacheLimits,
+        in_use: &HashSet<String>,
+    ) -> Result<CacheCleanup> {
+        let mut cleanup = CacheCleanup::default();
+        if !tokio::fs::try_exists(&self.cache_dir)
+            .await
+            .unwrap_or(false)
+        {
+            return Ok(cleanup);
+        }
+        let now = SystemTime::now();
+        let expired = |last_used: SystemTime| {
+            limits.max_age.is_some_and(|max_age| {
+                now.duration_since(last_used)
+                    .is_ok_and(|elapsed| elapsed > max_age)
+            })
+        };
+
+        self.remove_legacy_entries(&expired, &mut cleanup).await?;
+
+        let mut blobs = self.blobs().await?;
+        // Oldest first, so the size pass below evicts the least recently used
+        blobs.sort_by_key(|blob| blob.last_used);
+        let mut retained = Vec::with_capacity(blobs.len());
+        for blob in blobs {
+            let in_use = blob
+                .digest
+                .as_ref()
+                .is_some_and(|digest| in_use.contains(digest));
+            if !in_use && expired(blob.last_used) {
+                remove_blob(&blob, &mut cleanup).await?;
+            } else {
+                retained.push((blob, in_use));
+            }
+        }
+
+        let mut total: u64 = retained.iter().map(|(blob, _)| blob.size).sum();
+        if let Some(max_bytes) = limits.max_bytes {
+            for (blob, in_use) in &retained {
+                if total <= max_bytes {
+                    break;
+                }
+                // A file still being written is spared too: it is about to
+                // become the newest blob in the cache
+                if *in_use || blob.digest.is_none() {
+                    continue;
+                }
+                remove_blob(blob, &mut cleanup).await?;
+                total = total.saturating_sub(blob.size);
+            }
+            if total > max_bytes {
+                warn!(
+                    max_bytes,
+                    cached_bytes = total,
+                    "OCI cache is over its size limit with only components in use left"
+                );
+            }
+        }
+        cleanup.bytes_retained = total;
+
+        self.remove_dangling_refs(&mut cleanup).await?;
+        Ok(cleanup)
+    }
+
+    /// Every file under the blobs directory
+    async fn blobs(&self) -> Result<Vec<Blob>> {
+        let mut blobs = Vec::new();
+        let blobs_dir = self.cache_dir.join(BLOBS_DIR);
+        for algorithm_dir in list_dir(&blobs_dir).await? {
+            let Some(algorithm) = algorithm_dir.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let algorithm = algorithm.to_string();
+            for path in list_dir(&algorithm_dir).await? {
+                let metadata = tokio::fs::metadata(&path)
+                    .await
+                    .with_context(|| format!("failed to read metadata of {}", path.display()))?;
+                if !metadata.is_file() {
+                    continue;
+                }
+                let digest = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .filter(|name| !name.ends_with(STAGING_SUFFIX))
+                    .map(|encoded| format!("{algorithm}:{encoded}"));
+                blobs.push(Blob {
+                    size: metadata.len(),
+                    last_used: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                    path,
+                    digest,
+                });
+            }
+        }
+        Ok(blobs)
+    }
+
+    /// Remove references whose blob is gone
+    async fn remove_dangling_refs(&self, cleanup: &mut CacheCleanup) -> Result<()> {
+        for ref_path in list_dir(&self.cache_dir.join(REFS_DIR)).await? {
+            if ref_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|name| name.ends_with(STAGING_SUFFIX))
+            {
+                continue;
+            }
+            let blob_exists = match tokio::fs::read_to_string(&ref_path).await {
+                Ok(digest) => match self.blob_path(digest.trim()) {
+                    Ok(blob_path) => tokio::fs::try_exists(blob_path).await.unwrap_or(true),
+                    Err(_) => false,
+                },
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(e).with_context(|| {
+                        format!("failed to read cached reference {}", ref_path.display())
+                    });
+                }
+            };
+            if !blob_exists {
+                debug!(path = %ref_path.display(), "removing dangling cached reference");
+                remove_file(&ref_path).await?;
+                cleanup.references_removed += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove expired per-reference directories written by earlier versions
+    ///
+    /// Only directories laid out the way those versions wrote them are touched;
+    /// anything else an operator keeps under the cache directory is left alone.
+    /// A directory that cannot be removed is logged and skipped, so one stuck
+    /// entry does not keep the rest of the cache from being collected.
+    async fn remove_legacy_entries(
+        &self,
+        expired: &impl Fn(SystemTime) -> bool,
+        cleanup: &mut CacheCleanup,
+    ) -> Result<()> {
+        for path in list_dir(&self.cache_dir).await? {
+            if path.ends_with(BLOBS_DIR) || path.ends_with(REFS_DIR) {
+                continue;
+            }
+            let metadata = match tokio::fs::symlink_metadata(&path).await {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    warn!(path = %path.display(), error = %e, "failed to read cache entry metadata");
+                    continue;
+                }
+            };
+            if !metadata.is_dir() || !metadata.modified().is_ok_and(expired) {
+                continue;
+            }
+            if !is_legacy_entry(&path).await {
+                debug!(path = %path.display(), "leaving unrecognized cache entry in place");
+                continue;
+            }
+            debug!(path = %path.display(), "expiring cached artifact");
+            let size = match dir_size(&path).await {
+                Ok(size) => size,
+                Err(e) => {
+                    warn!(path = %path.display(), error = %e, "failed to size expired cache entry");
+                    continue;
+                }
+            };
+            match tokio::fs::remove_dir_all(&path).await {
+                Ok(()) => cleanup.bytes_freed += size,
+                Err(e) => {
+                    warn!(path = %path.display(), error = %e, "failed to remove expired cache entry")
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `dir` is a per-reference directory of the layout before this one:
+/// named `<sanitized reference>_<8 hex digits>` and holding nothing but the
+/// reference's `digest` file and its `.wasm` component.
+async fn is_legacy_entry(dir: &Path) -> bool {
+    let named_like_one = dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.rsplit_once('_'))
+        .is_some_and(|(reference, hash)| {
+            !reference.is_empty()
+                && hash.len() == 8
+                && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        });
+    if !named_like_one {
+        return false;
+    }
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return false;
+    };
+    let mut has_digest = false;
+    loop {
+        let entry = match entries.next_entry().await {
+            Ok(Some(entry)) => entry,
+            Ok(None) => break,
+            Err(_) => return false,
+        };
+        if !entry.file_type().await.is_ok_and(|ty| ty.is_file()) {
+            return false;
+        }
+        let name = entry.file_name();
+        match name.to_str() {
+            Some("digest") => has_digest = true,
+            Some(name) if name.ends_with(".wasm") => {}
+            _ => return false,
+        }
+    }
+    has_digest
+}
+
+/// Remove a blob and account for it
+async fn remove_blob(blob: &Blob, cleanup: &mut CacheCleanup) -> Result<()> {
+    debug!(path = %blob.path.display(), digest = ?blob.digest, "evicting cached artifact");
+    if remove_file(&blob.path).await? {
+        cleanup.blobs_removed += 1;
+        cleanup.bytes_freed += blob.size;
+    }
+    Ok(())
+}
+
+/// Remove a file another process may have removed first, returning whether
+/// this call removed it
+async fn remove_file(path: &Path) -> Result<bool> {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e).with_context(|| format!("failed to remove {}", path.display())),
+    }
+}
+
+/// The entries of a directory, or none if it doesn't exist
+async fn list_dir(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("failed to read {}", dir.display()));
+        }
+    };
+    let mut paths = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .with_context(|| format!("failed to read entry of {}", dir.display()))?
+    {
+        paths.push(entry.path());
+    }
+    Ok(paths)
+}
+
+/// Total size of the files under a directory
+async fn dir_size(dir: &Path) -> Result<u64> {
+    let mut size = 0;
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for path in list_dir(&dir).await? {
+            let metadata = tokio::fs::symlink_metadata(&path)
+                .await
+                .with_context(|| format!("failed to read metadata of {}", path.display()))?;
+            if metadata.is_dir() {
+                pending.push(path);
+            } else {
+                size += metadata.len();
+            }
+        }
+    }
+    Ok(size)
+}
+
+/// Write a file aside and rename it into place, creating its directory
+async fn write_atomically(path: &Path, data: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("failed to create cache directory {}", parent.display()))?;
+    }
+    let mut staging = path.as_os_str().to_owned();
+    staging.push(format!(
+        ".{}{STAGING_SUFFIX}",
+        uuid::Uuid::new_v4().simple()
+    ));
+    let staging = PathBuf::from(staging);
+    tokio::fs::write(&staging, data).await?;
+    if let Err(e) = tokio::fs::rename(&staging, path).await {
+        let _ = tokio::fs::remove_file(&staging).await;
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Mark a blob as used now, for least-recently-used eviction
+async fn touch(path: &Path) {
+    let result = match tokio::fs::OpenOptions::new().write(true).open(path).await {
+        Ok(file) => file.into_std().await.set_modified(SystemTime::now()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        debug!(path = %path.display(), error = %e, "failed to update cached artifact access time");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Age a file as if it was last used `age` ago
+    fn age(path: &Path, age: Duration) {
+        let file = std::fs::File::open(path).unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    #[test]
+    fn test_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CacheManager::new(temp_dir.path().to_path_buf());
+
+        let ref_path = cache.ref_path("localhost:5000/test:latest");
+        assert!(ref_path.starts_with(temp_dir.path().join(REFS_DIR)));
+        assert!(
+            ref_path
+                .file_name()
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .starts_with("localhost_5000_test_latest_")
+        );
+
+        assert_eq!(
+            cache.blob_path("sha256:abcd1234").unwrap(),
+            temp_dir
+                .path()
+                .join(BLOBS_DIR)
+                .join("sha256")
+                .join("abcd1234")
+        );
+        for invalid in ["abcd", "sha256:", ":abcd", "sha256:../../etc", "../x:abcd"] {
+            assert!(cache.blob_path(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_same_digest_is_stored_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CacheManager::new(temp_dir.path().to_path_buf());
+        let data = b"test component data";
+        let digest = "sha256:abcd1234";
+
+        assert_eq!(cache.resolve("localhost:5000/test:v1").await, None);
+
+        cache
+            .write_to_cache("localhost:5000/test:v1", data, digest)
+            .await
+            .unwrap();
+        cache
+            .write_to_cache("localhost:5000/test:latest", data, digest)
+            .await
+            .unwrap();
+
+        for reference in ["localhost:5000/test:v1", "localhost:5000/test:latest"] {
+            let resolved = cache.resolve(reference).await.unwrap();
+            assert_eq!(resolved, digest);
+            assert_eq!(cache.read_blob(&resolved).await.unwrap().unwrap(), data);
+        }
+        assert_eq!(cache.blobs().await.unwrap().len(), 1);
+        assert_eq!(cache.read_blob("sha256:ffff").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_collect_by_age_spares_blobs_in_use() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CacheManager::new(temp_dir.path().to_path_buf());
+        cache
+            .write_to_cache("reg/old:v1", b"old", "sha256:01d")
+            .await
+            .unwrap();
+        cache
+            .write_to_cache("reg/running:v1", b"running", "sha256:2a2")
+            .await
+            .unwrap();
+        cache
+            .write_to_cache("reg/new:v1", b"new", "sha256:0e3")
+            .await
+            .unwrap();
+        let hour = Duration::from_secs(3600);
+        age(&cache.blob_path("sha256:01d").unwrap(), 2 * hour);
+        age(&cache.blob_path("sha256:2a2").unwrap(), 2 * hour);
+
+        let in_use = HashSet::from(["sha256:2a2".to_string()]);
+        let limits = CacheLimits {
+            max_age: Some(hour),
+            max_bytes: None,
+        };
+        let cleanup = cache.collect(limits, &in_use).await.unwrap();
+
+        assert_eq!(
+            cleanup,
+            CacheCleanup {
+                blobs_removed: 1,
+                references_removed: 1,
+                bytes_freed: 3,
+                bytes_retained: 10,
+            }
+        );
+        assert_eq!(cache.resolve("reg/old:v1").await, None);
+        assert!(cache.resolve("reg/running:v1").await.is_some());
+        assert!(cache.resolve("reg/new:v1").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_collect_by_size_evicts_least_recently_used() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CacheManager::new(temp_dir.path().to_path_buf());
+        for (i, digest) in ["sha256:a1", "sha256:a2", "sha256:a3", "sha256:a4"]
+            .into_iter()
+            .enumerate()
+        {
+            cache
+                .write_to_cache(&format!("reg/c{i}:v1"), &[0; 100], digest)
+                .await
+                .unwrap();
+            age(
+                &cache.blob_path(digest).unwrap(),
+                Duration::from_secs(100 - 10 * i as u64),
+            );
+        }
+        // Reading the oldest makes it the most recently used
+        cache.read_blob("sha256:a1").await.unwrap().unwrap();
+
+        // a2 is the least recently used but running, so a3 and a4 go instead
+        let in_use = HashSet::from(["sha256:a2".to_string()]);
+        let limits = CacheLimits {
+            max_age: None,
+            max_bytes: Some(250),
+        };
+        let cleanup = cache.collect(limits, &in_use).await.unwrap();
+
+        assert_eq!(cleanup.blobs_removed, 2);
+        assert_eq!(cleanup.bytes_freed, 200);
+        assert_eq!(cleanup.bytes_retained, 200);
+        let mut left = cache
+            .blobs()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter_map(|blob| blob.digest)
+            .collect::<Vec<_>>();
+        left.sort();
+        assert_eq!(left, ["sha256:a1", "sha256:a2"]);
+    }
+
+    #[tokio::test]
+    async fn test_collect_removes_expired_legacy_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let legacy = temp_dir.path().join("localhost_5000_test_latest_12345678");
+        std::fs::create_dir_all(&legacy).unwrap();
+        std::fs::write(legacy.join("test_latest.wasm"), b"component").unwrap();
+        std::fs::write(legacy.join("digest"), b"sha256:abcd").unwrap();
+        age(&legacy, Duration::from_secs(7200));
+
+        let cache = CacheManager::new(temp_dir.path().to_path_buf());
+        let limits = CacheLimits {
+            max_age: Some(Duration::from_secs(3600)),
+            max_bytes: None,
+        };
+        let cleanup = cache.collect(limits, &HashSet::new()).await.unwrap();
+
+        assert_eq!(cleanup.bytes_freed, 20);
+        assert!(!legacy.exists());
+    }
+
+    #[tokio::test]
+    async fn test_collect_keeps_entries_not_in_the_legacy_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        // Named like a legacy entry, but holding something it never did
+        let lookalike = temp_dir.path().join("backups_0123abcd");
+        std::fs::create_dir_all(&lookalike).unwrap();
+        std::fs::write(lookalike.join("digest"), b"sha256:abcd").unwrap();
+        std::fs::write(lookalike.join("notes.txt"), b"keep me").unwrap();
+        age(&lookalike, Duration::from_secs(7200));
+        // Not named like one at all
+        let other = temp_dir.path().join("operator-data");
+        std::fs::create_dir_all(&other).unwrap();
+        std::fs::write(other.join("digest"), b"sha256:abcd").unwrap();
+        age(&other, Duration::from_secs(7200));
+
+        let cache = CacheManager::new(temp_dir.path().to_path_buf());
+        let limits = CacheLimits {
+            max_age: Some(Duration::from_secs(3600)),
+            max_bytes: None,
+        };
+        let cleanup = cache.collect(limits, &HashSet::new()).await.unwrap();
+
+        assert_eq!(cleanup.bytes_freed, 0);
+        assert!(lookalike.exists());
+        assert!(other.exists());
+    }
+}
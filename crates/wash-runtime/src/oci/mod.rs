@@ -4,6 +4,12 @@
 //! WebAssembly components, including docker credential integration and
 //! file-based caching. This module is only available when the `oci` feature is enabled.
 //!
+//! Pulls can also be required to carry a signature by a trusted key; see
+//! [`signature`].
+//!
+//! An image's manifest and its repository's tags can be read without pulling
+//! any layers; see [`fetch_manifest`] and [`list_tags`].
+//!
 //! # Examples
 //!
 //! ```no_run
@@ -36,14 +42,20 @@ use oci_client::{
     secrets::RegistryAuth,
 };
 use oci_wasm::{ToConfig, WASM_LAYER_MEDIA_TYPE, WasmConfig};
-use sha2::{Digest, Sha256};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     path::{Path, PathBuf},
     time::Duration,
 };
 use tracing::{debug, instrument, warn};
 
+mod cache;
+pub mod signature;
+
+use cache::CacheManager;
+pub use cache::{CacheCleanup, CacheLimits};
+use signature::SignaturePolicy;
+
 #[allow(deprecated)]
 #[deprecated = "old media type used before Wasm WG standardization"]
 const WASMCLOUD_MEDIA_TYPE: &str = "application/vnd.module.wasm.content.layer.v1+wasm";
@@ -70,6 +82,9 @@ pub struct OciConfig {
     /// Timeout for OCI operations (pull, push, etc.)
     /// If None, uses default timeout from oci-client
     pub timeout: Option<Duration>,
+    /// Which pulled images must be signed, and by whom. The default checks
+    /// nothing.
+    pub signature_policy: SignaturePolicy,
 }
 
 impl OciConfig {
@@ -110,154 +125,24 @@ impl OciConfig {
         self.timeout = Some(timeout);
         self
     }
-}
-
-/// Cache manager for OCI artifacts
-struct CacheManager {
-    cache_dir: PathBuf,
-}
-
-impl CacheManager {
-    /// Create a new cache manager with the specified cache directory
-    fn new(cache_dir: PathBuf) -> Self {
-        Self { cache_dir }
-    }
-
-    /// Expire old artifacts from the cache
-    async fn expire_artifacts(&self, age: Duration) -> Result<()> {
-        // walk the cache directory, looking for artifact dirs
-        // and remove those older than the specified age
-        let mut dir_entries = match tokio::fs::read_dir(&self.cache_dir).await {
-            Ok(entries) => entries,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
-            Err(e) => return Err(e).context("failed to read cache directory"),
-        };
-        while let Some(entry) = dir_entries
-            .next_entry()
-            .await
-            .context("failed to read cache entry")?
-        {
-            let metadata = entry
-                .metadata()
-                .await
-                .context("failed to read cache entry metadata")?;
-            if let Ok(modified) = metadata.modified() {
-                let modified_duration = modified
-                    .elapsed()
-                    .context("failed to compute modified duration")?;
-                if modified_duration > age {
-                    debug!(path = %entry.path().display(), "expiring cached artifact");
-                    tokio::fs::remove_dir_all(entry.path())
-                        .await
-                        .context("failed to remove expired cache entry")?;
-                }
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Get the cache directory for a given OCI reference
-    fn get_cache_dir(&self, reference: &str) -> PathBuf {
-        // Hash for uniqueness, but keep the reference in the path for readability
-        let mut hasher = Sha256::new();
-        hasher.update(reference.as_bytes());
-        let hash = format!("{:x}", hasher.finalize());
-        let short_hash = &hash[..8];
-
-        // Sanitize the reference for filesystem use
-        let sanitized = reference.replace(['/', ':', '@'], "_");
-
-        // Directory: <cache_dir>/<sanitized_reference>_<short_hash>/
-        self.cache_dir.join(format!("{sanitized}_{short_hash}"))
-    }
-
-    /// Get the cache path for the component .wasm data
-    fn get_component_path(&self, reference: &str) -> PathBuf {
-        let cache_dir = self.get_cache_dir(reference);
-
-        // Use the last segment as the artifact name (after last '/')
-        let artifact_name = reference
-            .rsplit('/')
-            .next()
-            .unwrap_or("artifact")
-            .replace([':', '@'], "_");
-
-        cache_dir.join(format!("{artifact_name}.wasm"))
-    }
-
-    /// Get the cache path for the digest file
-    fn get_digest_path(&self, reference: &str) -> PathBuf {
-        let cache_dir = self.get_cache_dir(reference);
-        cache_dir.join("digest")
-    }
-
-    /// Check if an artifact is cached (both component and digest must exist)
-    async fn is_cached(&self, reference: &str) -> bool {
-        let component_path = self.get_component_path(reference);
-        let digest_path = self.get_digest_path(reference);
-        tokio::fs::metadata(&component_path).await.is_ok()
-            && tokio::fs::metadata(&digest_path).await.is_ok()
-    }
-
-    /// Read cached artifact, returning (component_data, digest)
-    async fn read_cached(&self, reference: &str) -> Result<(Vec<u8>, String)> {
-        let component_path = self.get_component_path(reference);
-        let digest_path = self.get_digest_path(reference);
-
-        debug!(component_path = %component_path.display(), digest_path = %digest_path.display(), "reading cached artifact");
-
-        let component_data = tokio::fs::read(&component_path).await.with_context(|| {
-            format!(
-                "failed to read cached component at {}",
-                component_path.display()
-            )
-        })?;
-
-        let digest = tokio::fs::read_to_string(&digest_path)
-            .await
-            .with_context(|| {
-                format!("failed to read cached digest at {}", digest_path.display())
-            })?;
 
-        Ok((component_data, digest.trim().to_string()))
+    /// Set the signature policy for this config
+    pub fn with_signature_policy(mut self, signature_policy: SignaturePolicy) -> Self {
+        self.signature_policy = signature_policy;
+        self
     }
+}
 
-    /// Write artifact and digest to cache
-    async fn write_to_cache(&self, reference: &str, data: &[u8], digest: &str) -> Result<()> {
-        let component_path = self.get_component_path(reference);
-        let digest_path = self.get_digest_path(reference);
-
-        debug!(component_path = %component_path.display(), digest_path = %digest_path.display(), "writing to cache");
-
-        // Create cache directory
-        let cache_dir = self.get_cache_dir(reference);
-        tokio::fs::create_dir_all(&cache_dir)
-            .await
-            .with_context(|| format!("failed to create cache directory {}", cache_dir.display()))?;
-
-        // Write component data
-        tokio::fs::write(&component_path, data)
-            .await
-            .with_context(|| {
-                format!(
-                    "failed to write component to cache at {}",
-                    component_path.display()
-                )
-            })?;
-
-        // Write digest
-        tokio::fs::write(&digest_path, digest)
-            .await
-            .with_context(|| {
-                format!(
-                    "failed to write digest to cache at {}",
-                    digest_path.display()
-                )
-            })?;
-
-        Ok(())
-    }
+/// An OCI client speaking HTTP to an insecure registry and HTTPS otherwise
+fn new_client(insecure: bool) -> Client {
+    Client::new(ClientConfig {
+        protocol: if insecure {
+            ClientProtocol::Http
+        } else {
+            ClientProtocol::Https
+        },
+        ..Default::default()
+    })
 }
 
 /// Credential resolver that implements the precedence: explicit → docker creds → anonymous
@@ -364,6 +249,7 @@ impl std::str::FromStr for OciPullPolicy {
 /// - The registry is unreachable
 /// - Authentication fails
 /// - The pulled artifact is not a valid WebAssembly component
+/// - The config's [`SignaturePolicy`] requires a signature the image lacks
 /// - Caching operations fail
 ///
 /// # Examples
@@ -389,16 +275,7 @@ pub async fn pull_component(
         .with_context(|| format!("invalid OCI reference: {reference}"))?;
 
     // Configure OCI client
-    let client_config = ClientConfig {
-        protocol: if config.insecure {
-            ClientProtocol::Http
-        } else {
-            ClientProtocol::Https
-        },
-        ..Default::default()
-    };
-
-    let client = Client::new(client_config);
+    let client = new_client(config.insecure);
 
     // Setup credential resolver
     let credential_resolver = CredentialResolver::new(config.credentials);
@@ -413,16 +290,29 @@ pub async fn pull_component(
         .map(|dir| CacheManager::new(dir.clone()));
     if let Some(cache_manager) = &cache_manager {
         // Check cache first
-        if pull_policy != OciPullPolicy::Always && cache_manager.is_cached(reference).await {
+        if pull_policy != OciPullPolicy::Always
+            && let Some(cached_digest) = cache_manager.resolve(reference).await
+        {
             debug!("Found cached artifact");
-            let (component_data, digest) = cache_manager.read_cached(reference).await?;
-
             let fetched_digest = client
                 .fetch_manifest_digest(&reference_parsed, &auth)
                 .await?;
 
-            if digest == fetched_digest {
-                return Ok((component_data, digest));
+            // The reference may have moved to an image already cached under
+            // another one
+            if let Some(component_data) = cache_manager.read_blob(&fetched_digest).await? {
+                signature::enforce(
+                    &config.signature_policy,
+                    &client,
+                    &reference_parsed,
+                    &auth,
+                    &fetched_digest,
+                )
+                .await?;
+                if fetched_digest != cached_digest {
+                    cache_manager.link(reference, &fetched_digest).await?;
+                }
+                return Ok((component_data, fetched_digest));
             }
 
             debug!("Cached artifact expired; pulling new component version");
@@ -474,6 +364,16 @@ pub async fn pull_component(
         .await
         .with_context(|| "pulled artifact is not a valid WebAssembly component")?;
 
+    // Check who published it before anything else trusts it, the cache included
+    signature::enforce(
+        &config.signature_policy,
+        &client,
+        &reference_parsed,
+        &auth,
+        &digest,
+    )
+    .await?;
+
     // Cache the component with its digest
     if let Some(cache_manager) = &cache_manager {
         cache_manager
@@ -550,16 +450,7 @@ pub async fn push_component(
         .await;
 
     // Configure OCI client
-    let client_config = ClientConfig {
-        protocol: if config.insecure {
-            ClientProtocol::Http
-        } else {
-            ClientProtocol::Https
-        },
-        ..Default::default()
-    };
-
-    let client = Client::new(client_config);
+    let client = new_client(config.insecure);
 
     // Create the WebAssembly configuration and layer using oci-wasm
     let (wasm_config, image_layer) = WasmConfig::from_raw_component(component_data.to_vec(), None)
@@ -646,6 +537,137 @@ pub async fn push_component(
     Ok(digest)
 }
 
+/// An image's manifest and config, as the registry serves them
+#[derive(Debug, Clone)]
+pub struct ImageManifest {
+    /// The digest of the manifest
+    pub digest: String,
+    /// The reference pinned to [`Self::digest`], which keeps naming this
+    /// image after its tag moves on
+    pub pinned_reference: String,
+    /// The manifest itself
+    pub manifest: OciImageManifest,
+    /// The image config blob, as served (JSON)
+    pub config: String,
+}
+
+impl ImageManifest {
+    /// Total size of the image's layers in bytes
+    pub fn layers_size(&self) -> u64 {
+        self.manifest
+            .layers
+            .iter()
+            .map(|layer| u64::try_from(layer.size).unwrap_or_default())
+            .sum()
+    }
+}
+
+/// Fetch the manifest and config of an image without pulling its layers
+///
+/// # Arguments
+/// * `reference` - OCI reference (e.g., "ghcr.io/wasmcloud/components/http-hello-world:latest")
+/// * `config` - Configuration for the registry
+///
+/// # Errors
+/// Returns an error if:
+/// - The reference is invalid
+/// - Authentication fails
+/// - The registry is unreachable or doesn't have the image
+/// - The request exceeds the config's timeout
+#[instrument(skip(config), fields(reference = %reference))]
+pub async fn fetch_manifest(reference: &str, config: OciConfig) -> Result<ImageManifest> {
+    let reference_parsed = Reference::try_from(reference)
+        .with_context(|| format!("invalid OCI reference: {reference}"))?;
+    let client = new_client(config.insecure);
+    let auth = CredentialResolver::new(config.credentials)
+        .resolve_credentials(reference_parsed.registry())
+        .await;
+
+    let fetch_future = client.pull_manifest_and_config(&reference_parsed, &auth);
+    let (manifest, digest, image_config) = if let Some(timeout) = config.timeout {
+        tokio::time::timeout(timeout, fetch_future)
+            .await
+            .with_context(|| {
+                format!("timeout fetching manifest of {reference} after {timeout:?}")
+            })?
+    } else {
+        fetch_future.await
+    }
+    .with_context(|| format!("failed to fetch manifest of {reference}"))?;
+
+    let pinned_reference = Reference::with_digest(
+        reference_parsed.registry().to_string(),
+        reference_parsed.repository().to_string(),
+        digest.clone(),
+    )
+    .whole();
+
+    Ok(ImageManifest {
+        digest,
+        pinned_reference,
+        manifest,
+        config: image_config,
+    })
+}
+
+/// Most tags asked for per request when listing a repository's tags
+const TAGS_PAGE_SIZE: usize = 1000;
+
+/// List the tags of the repository an OCI reference names
+///
+/// Any tag or digest on the reference is ignored. Tags are returned in the
+/// order the registry lists them, following its pagination to the end.
+///
+/// # Errors
+/// Returns an error if:
+/// - The reference is invalid
+/// - Authentication fails
+/// - The registry is unreachable or doesn't have the repository
+/// - A request exceeds the config's timeout
+#[instrument(skip(config), fields(reference = %reference))]
+pub async fn list_tags(reference: &str, config: OciConfig) -> Result<Vec<String>> {
+    let reference_parsed = Reference::try_from(reference)
+        .with_context(|| format!("invalid OCI reference: {reference}"))?;
+    let repository = reference_parsed.repository();
+    let client = new_client(config.insecure);
+    let auth = CredentialResolver::new(config.credentials)
+        .resolve_credentials(reference_parsed.registry())
+        .await;
+
+    let mut tags: Vec<String> = Vec::new();
+    loop {
+        let list_future = client.list_tags(
+            &reference_parsed,
+            &auth,
+            Some(TAGS_PAGE_SIZE),
+            tags.last().map(String::as_str),
+        );
+        let page = if let Some(timeout) = config.timeout {
+            tokio::time::timeout(timeout, list_future)
+                .await
+                .with_context(|| {
+                    format!("timeout listing tags of {repository} after {timeout:?}")
+                })?
+        } else {
+            list_future.await
+        }
+        .with_context(|| format!("failed to list tags of {repository}"))?;
+
+        // A page that lists the last tag again means the registry ignores
+        // `last` and started over, so it adds nothing and asking again won't
+        // help. A short page is the last one, as is every tag at once from a
+        // registry that ignores `n`.
+        if tags.last().is_some_and(|last| page.tags.contains(last)) {
+            return Ok(tags);
+        }
+        let full_page = page.tags.len() >= TAGS_PAGE_SIZE;
+        tags.extend(page.tags);
+        if !full_page {
+            return Ok(tags);
+        }
+    }
+}
+
 /// Validate that the provided bytes represent a valid WebAssembly component
 ///
 /// This function parses the WebAssembly bytes and validates that they form
@@ -677,10 +699,20 @@ pub async fn validate_component(data: &[u8]) -> Result<()> {
 }
 
 /// Cleanup cached OCI artifacts
-#[instrument(skip(cache_dir))]
-pub async fn cleanup_cache(cache_dir: impl AsRef<Path>, age: Duration) -> Result<()> {
+///
+/// Removes the cached components `limits` no longer allow, except those whose
+/// image digest is in `in_use`, along with the references that pointed at them.
+///
+/// # Returns
+/// What was removed and how much is still cached
+#[instrument(skip(cache_dir, in_use))]
+pub async fn cleanup_cache(
+    cache_dir: impl AsRef<Path>,
+    limits: CacheLimits,
+    in_use: &HashSet<String>,
+) -> Result<CacheCleanup> {
     let cache_manager = CacheManager::new(cache_dir.as_ref().to_path_buf());
-    cache_manager.expire_artifacts(age).await
+    cache_manager.collect(limits, in_use).await
 }
 
 #[cfg(test)]
@@ -711,48 +743,6 @@ mod tests {
         assert!(config.insecure);
     }
 
-    #[test]
-    fn test_cache_manager_path_generation() {
-        let temp_dir = TempDir::new().unwrap();
-        let cache_manager = CacheManager::new(temp_dir.path().to_path_buf());
-
-        let reference = "localhost:5000/test:latest";
-        let component_path = cache_manager.get_component_path(reference);
-        let digest_path = cache_manager.get_digest_path(reference);
-
-        assert!(component_path.starts_with(temp_dir.path()));
-        assert!(component_path.extension().unwrap() == "wasm");
-        assert!(digest_path.starts_with(temp_dir.path()));
-        assert!(digest_path.file_name().unwrap() == "digest");
-    }
-
-    #[tokio::test]
-    async fn test_cache_manager_operations() {
-        let temp_dir = TempDir::new().unwrap();
-        let cache_manager = CacheManager::new(temp_dir.path().to_path_buf());
-
-        let reference = "localhost:5000/test:v1.0.0";
-        let test_data = b"test component data";
-        let test_digest = "sha256:abcd1234";
-
-        // Should not be cached initially
-        assert!(!cache_manager.is_cached(reference).await);
-
-        // Cache the data with digest
-        cache_manager
-            .write_to_cache(reference, test_data, test_digest)
-            .await
-            .unwrap();
-
-        // Should now be cached
-        assert!(cache_manager.is_cached(reference).await);
-
-        // Should be able to read the cached data and digest
-        let (cached_data, cached_digest) = cache_manager.read_cached(reference).await.unwrap();
-        assert_eq!(cached_data, test_data);
-        assert_eq!(cached_digest, test_digest);
-    }
-
     #[tokio::test]
     async fn test_validate_component_invalid_data() {
         let invalid_data = b"not wasm data";
@@ -796,6 +786,48 @@ mod tests {
         }
     }
 
+    /// Serve a fake registry whose tag list is always the same full page,
+    /// whatever `last` asks for. Returns its address.
+    async fn serve_registry_ignoring_last() -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let tags: Vec<String> = (0..TAGS_PAGE_SIZE).map(|i| format!("v{i:04}")).collect();
+        let body = serde_json::json!({ "name": "repo", "tags": tags }).to_string();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let body = body.clone();
+                tokio::spawn(async move {
+                    let service = hyper::service::service_fn(move |req: hyper::Request<_>| {
+                        let body = if req.uri().path().ends_with("/tags/list") {
+                            body.clone()
+                        } else {
+                            "{}".to_string()
+                        };
+                        async move {
+                            Ok::<_, std::convert::Infallible>(hyper::Response::new(
+                                http_body_util::Full::new(bytes::Bytes::from(body)),
+                            ))
+                        }
+                    });
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(hyper_util::rt::TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn list_tags_stops_when_the_registry_ignores_last() {
+        let addr = serve_registry_ignoring_last().await;
+        let tags = list_tags(&format!("{addr}/repo:latest"), OciConfig::new_insecure())
+            .await
+            .unwrap();
+        assert_eq!(tags.len(), TAGS_PAGE_SIZE, "the repeated page is dropped");
+        assert_eq!(tags.first().map(String::as_str), Some("v0000"));
+    }
+
     #[test]
     fn test_oci_config_with_cache() {
         let temp_dir = TempDir::new().unwrap();
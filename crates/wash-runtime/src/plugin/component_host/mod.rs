@@ -169,6 +169,9 @@ struct ComponentHostPluginState {
     /// health becomes failed. Injected once at start; absent when the plugin is
     /// used without a host (e.g. driven directly in tests).
     failure_sink: ArcSwapOption<crate::plugin::WorkloadFailureSink>,
+    /// Sink for reporting that the driver exhausted its restart budget.
+    /// Injected once at start; absent when the plugin is used without a host.
+    event_sink: ArcSwapOption<crate::host::events::HostEventSink>,
     /// Per-call budget for a lifecycle (bind/unbind) delivery, in milliseconds.
     /// Defaults to [`crate::timeouts::plugin_lifecycle_call`]; overridable via
     /// [`ComponentHostPlugin::with_lifecycle_call_timeout`]. Read on every
@@ -306,6 +309,7 @@ impl ComponentHostPlugin {
             poison: Mutex::new(BTreeMap::new()),
             bind_trap_log: Mutex::new(Vec::new()),
             failure_sink: ArcSwapOption::empty(),
+            event_sink: ArcSwapOption::empty(),
             lifecycle_timeout_ms: AtomicU64::new(
                 crate::timeouts::plugin_lifecycle_call().as_millis() as u64,
             ),
@@ -481,6 +485,16 @@ impl HostPlugin for ComponentHostPlugin {
         self.state.failure_sink.store(Some(Arc::new(sink)));
     }
 
+    fn set_host_event_sink(&self, sink: crate::host::events::HostEventSink) {
+        self.state.event_sink.store(Some(Arc::new(sink)));
+    }
+
+    fn cancel_workload_calls(&self, workload_id: &str) -> usize {
+        self.state
+            .registry()
+            .map_or(0, |registry| registry.cancel_workload(workload_id))
+    }
+
     async fn start(&self) -> anyhow::Result<()> {
         let (tx, rx) = tokio::sync::mpsc::channel(CAPABILITY_CHANNEL_CAPACITY);
         // Publish the sender and snapshot the bound workloads atomically (see
@@ -1392,6 +1406,12 @@ async fn run_supervisor(
                 );
                 state.tx.store(None);
                 state.registry.store(None);
+                if let Some(sink) = state.event_sink.load_full() {
+                    sink.emit(crate::host::events::HostEvent::PluginCrashLooped {
+                        plugin_id: state.id.to_string(),
+                        restarts,
+                    });
+                }
                 return;
             }
             restarts += 1;
@@ -1464,11 +1484,12 @@ fn build_plugin_store(
     allowed_ip_name_lookups: &Arc<[crate::host::allowed_ip_name::AllowedIpName]>,
     http_handler: Option<Arc<dyn crate::host::http::HostHandler>>,
 ) -> Store<SharedCtx> {
-    // Same policy a non-service workload component gets: DNS lookup gated by
-    // `allowed_ip_name_lookups`, `wasi:http` gated by `allowed_hosts` (via
-    // `Ctx::with_allowed_hosts` + the existing `check_allowed_hosts`), raw
-    // socket connect otherwise unrestricted — a plugin never binds a listen
-    // socket, so `TcpBind`/`UdpBind` are always denied.
+    // DNS lookup gated by `allowed_ip_name_lookups` and `wasi:http` gated by
+    // `allowed_hosts` (via `Ctx::with_allowed_hosts` + the existing
+    // `check_allowed_hosts`), as for a workload component. Raw socket connect
+    // is left unrestricted: plugins are host-installed, not workload-supplied,
+    // so the workload `allowed_socket_addrs` policy does not apply. A plugin
+    // never binds a listen socket, so `TcpBind`/`UdpBind` are always denied.
     let sockets_ctx = crate::sockets::WasiSocketsCtx {
         socket_addr_check: crate::sockets::SocketAddrCheck::new(move |_addr, reason| {
             Box::pin(async move {
@@ -1498,7 +1519,11 @@ fn build_plugin_store(
     let ctx = ctx_builder.build();
     // The registry marks this as the plugin (real) side of the resource bridge
     // and keeps the resources it hands out across the boundary alive.
-    Store::new(engine.inner(), SharedCtx::new(ctx).with_resource_registry())
+    let mut store = Store::new(engine.inner(), SharedCtx::new(ctx).with_resource_registry());
+    // A host plugin serves the host for as long as it runs; it has no
+    // execution deadline and no time slice.
+    crate::engine::epoch::install(&mut store, None, None);
+    store
 }
 
 /// Introspect a plugin component's exported interfaces and their functions from
@@ -21,7 +21,8 @@
 //! - [`wasi_keyvalue`] - Key-value storage (`wasi:keyvalue`)
 //! - [`wasi_logging`] - Structured logging (`wasi:logging`)
 //! - [`wasi_otel`] - OpenTelemetry tracing, metrics, and logs (`wasi:otel/*`)
-//! - [`wasmcloud_secrets`] - Secrets delivery from bind-time config (`wasmcloud:secrets`)
+//! - [`wasi_messaging`] - Messaging over NATS or in-process (`wasi:messaging`)
+//! - [`wasmcloud_secrets`] - Secrets from bind-time config or an external backend (`wasmcloud:secrets`)
 
 use std::collections::HashMap;
 use std::future::Future;
@@ -52,6 +53,8 @@ pub mod wasmcloud_postgres;
 #[cfg(feature = "wasi-otel")]
 pub mod wasi_otel;
 
+pub mod wasi_messaging;
+
 pub mod wasmcloud_messaging;
 
 pub mod wasmcloud_secrets;
@@ -123,7 +126,8 @@ impl<'a> WitInterfaces<'a> {
 /// A workload a plugin has failed out of band (after it was already running),
 /// delivered to the host so it can transition the workload to a failed state.
 /// Used when a host component plugin evicts a workload whose `on-workload-bind`
-/// crash-loops the shared store.
+/// crash-loops the shared store, and when a component grows past its memory
+/// limit (see [`crate::engine::limits`]).
 pub struct WorkloadFailure {
     /// The workload to fail.
     pub workload_id: String,
@@ -214,6 +218,21 @@ pub trait HostPlugin: std::any::Any + Send + Sync + 'static {
     /// component plugin evicting a workload whose lifecycle bind crash-loops).
     fn set_workload_failure_sink(&self, _sink: WorkloadFailureSink) {}
 
+    /// Inject a sink the plugin can use to report a [`crate::host::events::HostEvent`]
+    /// the host cannot observe itself (e.g. a host component plugin giving up on
+    /// a crash-looping driver). Called once during host start, before
+    /// [`HostPlugin::start`]. The default ignores it.
+    fn set_host_event_sink(&self, _sink: crate::host::events::HostEventSink) {}
+
+    /// Ask the plugin to cancel whatever it is still doing on behalf of
+    /// `workload_id`, whose stop has run past its drain deadline. Cancellation
+    /// is cooperative: the plugin signals the work and it unwinds itself.
+    /// Returns how many calls were asked to cancel. The default has nothing to
+    /// cancel.
+    fn cancel_workload_calls(&self, _workload_id: &str) -> usize {
+        0
+    }
+
     /// Called when the plugin is started during host initialization.
     ///
     /// This method allows plugins to perform any necessary setup before
@@ -510,13 +529,15 @@ pub fn multiplexed_plugins() -> Vec<std::sync::Arc<dyn HostPlugin>> {
             wasi_blobstore::MultiplexedBlobstore::new()
                 .with_provider(Arc::new(wasi_blobstore::InMemoryProvider))
                 .with_provider(Arc::new(wasi_blobstore::FilesystemProvider))
-                .with_provider(Arc::new(wasi_blobstore::NatsBlobProvider)),
+                .with_provider(Arc::new(wasi_blobstore::NatsBlobProvider))
+                .with_provider(Arc::new(wasi_blobstore::S3BlobProvider::default())),
         ));
         plugins.push(Arc::new(
             wasi_blobstore::MultiplexedAsyncBlobstore::new()
                 .with_provider(Arc::new(wasi_blobstore::InMemoryProvider))
                 .with_provider(Arc::new(wasi_blobstore::FilesystemProvider))
-                .with_provider(Arc::new(wasi_blobstore::NatsBlobProvider)),
+                .with_provider(Arc::new(wasi_blobstore::NatsBlobProvider))
+                .with_provider(Arc::new(wasi_blobstore::S3BlobProvider::default())),
         ));
     }
 
@@ -5,6 +5,7 @@ mod multiplexed;
 #[cfg(feature = "wasm_component_model_implements")]
 mod multiplexed_async;
 mod nats;
+mod s3;
 
 pub use filesystem::FilesystemBlobstore;
 pub use in_memory::InMemoryBlobstore;
@@ -12,7 +13,9 @@ pub use in_memory::InMemoryBlobstore;
 pub use multiplexed::{
     BlobBackend, BlobBackendError, BlobId, BlobProvider, FilesystemBackend, FilesystemProvider,
     InMemoryBackend, InMemoryProvider, MultiplexedBlobstore, NatsBlobBackend, NatsBlobProvider,
+    S3BlobBackend, S3BlobProvider,
 };
 #[cfg(feature = "wasm_component_model_implements")]
 pub use multiplexed_async::MultiplexedAsyncBlobstore;
 pub use nats::NatsBlobstore;
+pub use s3::S3Blobstore;
@@ -555,10 +555,12 @@ impl<'a> bindings::wasi::blobstore::types::Host for ActiveCtx<'a> {}
 mod filesystem;
 mod in_memory;
 mod nats;
+mod s3;
 
 pub use filesystem::{FilesystemBackend, FilesystemProvider};
 pub use in_memory::{InMemoryBackend, InMemoryProvider};
 pub use nats::{NatsBlobBackend, NatsBlobProvider};
+pub use s3::{S3BlobBackend, S3BlobProvider};
 
 /// A blobstore backend provider: a [`BackendProvider`] producing [`BlobId`]s.
 pub type BlobProvider = dyn BackendProvider<BlobId>;
@@ -0,0 +1,205 @@
+//! S3-compatible [`BlobBackend`] for the multiplexed blobstore plugin.
+//!
+//! Each container maps to the bucket `<bucket_prefix><container>` on the
+//! configured endpoint; the config keys are those of the standalone
+//! [`S3Blobstore`](crate::plugin::wasi_blobstore::S3Blobstore) plugin. Object
+//! bodies arrive whole from the host layers, and are written in one `PUT` or,
+//! past the part size, as a multipart upload.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::plugin::multiplex::BackendProvider;
+use crate::plugin::wasi_blobstore::s3::client::{S3Client, S3Error};
+
+use super::{BlobBackend, BlobBackendError, BlobId, BlobResult, ContainerInfo, ObjectInfo};
+
+/// An S3-backed [`BlobBackend`].
+pub struct S3BlobBackend {
+    client: S3Client,
+}
+
+impl S3BlobBackend {
+    /// Map an error from a request on `container`, where a missing object is
+    /// `object`.
+    fn err(container: &str, object: &str) -> impl FnOnce(S3Error) -> BlobBackendError {
+        move |e| match e {
+            S3Error::NoSuchBucket => BlobBackendError::NoSuchContainer(container.to_string()),
+            S3Error::NoSuchKey => BlobBackendError::NoSuchObject(object.to_string()),
+            S3Error::BucketAlreadyExists => {
+                BlobBackendError::ContainerAlreadyExists(container.to_string())
+            }
+            e => BlobBackendError::Other(format!("S3 error: {e}")),
+        }
+    }
+
+    /// Fail with `NoSuchContainer` unless the bucket exists.
+    async fn require(&self, container: &str) -> BlobResult<()> {
+        match self.client.bucket_exists(container).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(BlobBackendError::NoSuchContainer(container.to_string())),
+            Err(e) => Err(Self::err(container, "")(e)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobBackend for S3BlobBackend {
+    async fn create_container(&self, name: &str) -> BlobResult<()> {
+        self.client
+            .create_bucket(name)
+            .await
+            .map_err(Self::err(name, ""))
+    }
+
+    async fn get_container(&self, name: &str) -> BlobResult<()> {
+        self.require(name).await
+    }
+
+    async fn delete_container(&self, name: &str) -> BlobResult<()> {
+        // Idempotent: deleting a missing container is not an error. S3 only
+        // deletes empty buckets, so empty it first.
+        match self.client.clear_bucket(name).await {
+            Ok(()) => {}
+            Err(S3Error::NoSuchBucket) => return Ok(()),
+            Err(e) => return Err(Self::err(name, "")(e)),
+        }
+        match self.client.delete_bucket(name).await {
+            Ok(()) | Err(S3Error::NoSuchBucket) => Ok(()),
+            Err(e) => Err(Self::err(name, "")(e)),
+        }
+    }
+
+    async fn container_exists(&self, name: &str) -> BlobResult<bool> {
+        self.client
+            .bucket_exists(name)
+            .await
+            .map_err(Self::err(name, ""))
+    }
+
+    async fn container_info(&self, name: &str) -> BlobResult<ContainerInfo> {
+        self.require(name).await?;
+        Ok(ContainerInfo {
+            name: name.to_string(),
+            // Only ListBuckets reports a creation date.
+            created_at: 0,
+        })
+    }
+
+    async fn clear_container(&self, name: &str) -> BlobResult<()> {
+        self.client
+            .clear_bucket(name)
+            .await
+            .map_err(Self::err(name, ""))
+    }
+
+    async fn get_data(
+        &self,
+        container: &str,
+        object: &str,
+        start: u64,
+        end: u64,
+    ) -> BlobResult<Vec<u8>> {
+        self.client
+            .get_object(container, object, start, end)
+            .await
+            .map_err(Self::err(container, object))?
+            .bytes()
+            .await
+            .map_err(Self::err(container, object))
+    }
+
+    async fn write_data(&self, container: &str, object: &str, data: Vec<u8>) -> BlobResult<()> {
+        let len = data.len() as u64;
+        self.client
+            .upload(container, object, std::io::Cursor::new(data), len)
+            .await
+            .map_err(Self::err(container, object))
+    }
+
+    async fn list_objects(&self, container: &str) -> BlobResult<Vec<String>> {
+        self.client
+            .list_objects(container)
+            .await
+            .map_err(Self::err(container, ""))
+    }
+
+    async fn delete_object(&self, container: &str, object: &str) -> BlobResult<()> {
+        // S3 deletes are already idempotent.
+        self.client
+            .delete_object(container, object)
+            .await
+            .map_err(Self::err(container, object))
+    }
+
+    async fn delete_objects(&self, container: &str, objects: &[String]) -> BlobResult<()> {
+        for object in objects {
+            self.delete_object(container, object).await?;
+        }
+        Ok(())
+    }
+
+    async fn has_object(&self, container: &str, object: &str) -> BlobResult<bool> {
+        match self.client.head_object(container, object).await {
+            Ok(_) => Ok(true),
+            // A bare 404 can't tell a missing bucket from a missing key.
+            Err(S3Error::NoSuchKey) => self.require(container).await.map(|()| false),
+            Err(e) => Err(Self::err(container, object)(e)),
+        }
+    }
+
+    async fn object_info(&self, container: &str, object: &str) -> BlobResult<ObjectInfo> {
+        let meta = self
+            .client
+            .head_object(container, object)
+            .await
+            .map_err(Self::err(container, object))?;
+        Ok(ObjectInfo {
+            name: object.to_string(),
+            container: container.to_string(),
+            created_at: meta.last_modified,
+            size: meta.size,
+        })
+    }
+
+    async fn copy_object(
+        &self,
+        src_container: &str,
+        src_object: &str,
+        dest_container: &str,
+        dest_object: &str,
+    ) -> BlobResult<()> {
+        self.client
+            .copy_object(src_container, src_object, dest_container, dest_object)
+            .await
+            .map_err(Self::err(src_container, src_object))
+    }
+}
+
+/// Provider for [`S3BlobBackend`], selected by `config.backend = "s3"`. Not
+/// pooled: a backend is a thin signer over a shared HTTP client, and pooling
+/// by config would key the pool on credentials.
+pub struct S3BlobProvider {
+    http: reqwest::Client,
+}
+
+impl Default for S3BlobProvider {
+    fn default() -> Self {
+        Self {
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BackendProvider<BlobId> for S3BlobProvider {
+    fn backend_type(&self) -> &'static str {
+        "s3"
+    }
+
+    async fn instantiate(&self, config: &HashMap<String, String>) -> anyhow::Result<BlobId> {
+        Ok(Arc::new(S3BlobBackend {
+            client: S3Client::from_config(self.http.clone(), config)?,
+        }))
+    }
+}
@@ -74,8 +74,8 @@ pub struct OutgoingValueHandle {
 /// Unlike `AsyncWriteStream`, this does not spawn a background task, so there
 /// is no race between the writer and a subsequent `finish()` that reopens
 /// the file. Tempfile I/O is typically very fast (often backed by tmpfs).
-struct TempFileOutputStream {
-    file: std::fs::File,
+pub(super) struct TempFileOutputStream {
+    pub(super) file: std::fs::File,
 }
 
 #[async_trait::async_trait]
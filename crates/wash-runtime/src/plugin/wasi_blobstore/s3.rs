@@ -0,0 +1,799 @@
+//! S3-compatible `wasi:blobstore` plugin.
+//!
+//! Unlike the NATS plugin, which serves every workload from one connection,
+//! each workload brings its own endpoint, region, bucket prefix and
+//! credentials in its `wasi:blobstore` host-interface config; see
+//! [`S3Client::from_config`] for the keys. Credentials are meant to arrive
+//! through `secretFrom`, so they never sit in the workload spec itself.
+//! `buckets` optionally limits which containers the workload may open, and
+//! `read_only = "true"` refuses writes.
+//!
+//! Reads stream straight from the `GET` response. Writes are buffered to a
+//! temporary file, then uploaded on `finish`: in one `PUT` up to the part
+//! size, as a multipart upload beyond it.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+
+use crate::engine::ctx::{ActiveCtx, SharedCtx, extract_active_ctx};
+use crate::engine::workload::WorkloadItem;
+use crate::plugin::WorkloadTracker;
+use crate::plugin::{HostPlugin, WitInterfaces};
+use crate::wit::{WitInterface, WitWorld};
+use tokio::sync::RwLock;
+use tracing::instrument;
+use wasmtime::component::Resource;
+use wasmtime::error::Context;
+use wasmtime_wasi::p2::pipe::AsyncReadStream;
+use wasmtime_wasi::p2::{InputStream, OutputStream};
+
+use super::nats::TempFileOutputStream;
+
+pub(super) mod client;
+
+use client::{ObjectBody, S3Client};
+
+const PLUGIN_BLOBSTORE_ID: &str = "wasi-blobstore";
+
+mod bindings {
+    wasmtime::component::bindgen!({
+        world: "blobstore",
+        imports: { default: async | trappable | tracing },
+        with: {
+            "wasi:io": ::wasmtime_wasi_io::bindings::wasi::io,
+            "wasi:blobstore/container.container": crate::plugin::wasi_blobstore::s3::ContainerData,
+            "wasi:blobstore/container.stream-object-names": crate::plugin::wasi_blobstore::s3::StreamObjectNamesHandle,
+            "wasi:blobstore/types.incoming-value": crate::plugin::wasi_blobstore::s3::IncomingValueHandle,
+            "wasi:blobstore/types.outgoing-value": crate::plugin::wasi_blobstore::s3::OutgoingValueHandle,
+        },
+    });
+}
+
+use bindings::wasi::blobstore::container::Error as ContainerError;
+use bindings::wasi::blobstore::types::{
+    ContainerMetadata, ContainerName, Error as BlobstoreError, ObjectId, ObjectMetadata, ObjectName,
+};
+
+/// A container, and the client of the workload that opened it
+#[derive(Clone)]
+pub struct ContainerData {
+    pub name: String,
+    client: Arc<S3Client>,
+}
+
+pub struct WorkloadData {
+    client: Arc<S3Client>,
+    /// Which buckets (containers) are accessible to this workload, or any
+    /// under the prefix if unset
+    pub buckets: Option<HashSet<String>>,
+    /// Whether the blobstore is read-only for this workload
+    pub read_only: bool,
+}
+
+/// Resource representation for an incoming value (data being read)
+pub struct IncomingValueHandle {
+    body: ObjectBody,
+}
+
+/// Resource representation for an outgoing value (data being written)
+/// The upload is delayed until `finish` is called
+pub struct OutgoingValueHandle {
+    pub temp_file: tempfile::NamedTempFile,
+    pub container: Option<ContainerData>,
+    pub object_name: Option<String>,
+}
+
+/// Resource representation for streaming object names, fetched a page at a
+/// time as the guest reads
+pub struct StreamObjectNamesHandle {
+    container: ContainerData,
+    names: VecDeque<String>,
+    continuation_token: Option<String>,
+    exhausted: bool,
+}
+
+impl StreamObjectNamesHandle {
+    /// The next object name, fetching another page when the buffered one
+    /// runs out.
+    async fn next(&mut self) -> Result<Option<String>, String> {
+        while self.names.is_empty() && !self.exhausted {
+            let (names, next) = self
+                .container
+                .client
+                .list_page(&self.container.name, self.continuation_token.as_deref())
+                .await
+                .map_err(|e| format!("failed to list objects: {e}"))?;
+            self.names.extend(names);
+            self.exhausted = next.is_none();
+            self.continuation_token = next;
+        }
+        Ok(self.names.pop_front())
+    }
+}
+
+/// S3 blobstore plugin
+#[derive(Clone)]
+pub struct S3Blobstore {
+    http: reqwest::Client,
+    tracker: Arc<RwLock<WorkloadTracker<WorkloadData, ()>>>,
+}
+
+impl Default for S3Blobstore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl S3Blobstore {
+    pub fn new() -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            tracker: Arc::default(),
+        }
+    }
+
+    /// The workload's client, if it may open `container_name` (for writing,
+    /// if `is_write`).
+    async fn workload_permit(
+        &self,
+        workload_id: &str,
+        container_name: &str,
+        is_write: bool,
+    ) -> Option<Arc<S3Client>> {
+        let tracker = self.tracker.read().await;
+        let data = tracker.get_workload_data(workload_id)?;
+        if is_write && data.read_only {
+            return None;
+        }
+        match &data.buckets {
+            Some(buckets) if !buckets.contains(container_name) => None,
+            _ => Some(data.client.clone()),
+        }
+    }
+}
+
+// Implementation for the main blobstore interface
+impl<'a> bindings::wasi::blobstore::blobstore::Host for ActiveCtx<'a> {
+    #[instrument(name = "wasi.blobstore.create_container", skip(self))]
+    async fn create_container(
+        &mut self,
+        name: ContainerName,
+    ) -> wasmtime::Result<Result<Resource<ContainerData>, BlobstoreError>> {
+        let plugin = self.try_get_plugin::<S3Blobstore>(PLUGIN_BLOBSTORE_ID)?;
+
+        let Some(client) = plugin.workload_permit(&self.workload_id, &name, true).await else {
+            return Ok(Err("unauthorized".to_string()));
+        };
+
+        if let Err(e) = client.create_bucket(&name).await {
+            return Ok(Err(format!("failed to create bucket: {e}")));
+        }
+
+        let resource = self.table.push(ContainerData { name, client })?;
+        Ok(Ok(resource))
+    }
+
+    #[instrument(name = "wasi.blobstore.get_container", skip(self))]
+    async fn get_container(
+        &mut self,
+        name: ContainerName,
+    ) -> wasmtime::Result<Result<Resource<ContainerData>, BlobstoreError>> {
+        let plugin = self.try_get_plugin::<S3Blobstore>(PLUGIN_BLOBSTORE_ID)?;
+
+        let Some(client) = plugin
+            .workload_permit(&self.workload_id, &name, false)
+            .await
+        else {
+            return Ok(Err("unauthorized".to_string()));
+        };
+
+        match client.bucket_exists(&name).await {
+            Ok(true) => {}
+            Ok(false) => return Ok(Err(format!("container '{name}' does not exist"))),
+            Err(e) => return Ok(Err(format!("failed to get bucket: {e}"))),
+        }
+
+        let resource = self.table.push(ContainerData { name, client })?;
+        Ok(Ok(resource))
+    }
+
+    #[instrument(name = "wasi.blobstore.delete_container", skip(self))]
+    async fn delete_container(
+        &mut self,
+        name: ContainerName,
+    ) -> wasmtime::Result<Result<(), BlobstoreError>> {
+        let plugin = self.try_get_plugin::<S3Blobstore>(PLUGIN_BLOBSTORE_ID)?;
+
+        let Some(client) = plugin.workload_permit(&self.workload_id, &name, true).await else {
+            return Ok(Err("unauthorized".to_string()));
+        };
+
+        // S3 only deletes empty buckets.
+        if let Err(e) = client.clear_bucket(&name).await {
+            return Ok(Err(format!("failed to empty bucket: {e}")));
+        }
+        if let Err(e) = client.delete_bucket(&name).await {
+            return Ok(Err(format!("failed to delete bucket: {e}")));
+        }
+
+        Ok(Ok(()))
+    }
+
+    #[instrument(name = "wasi.blobstore.container_exists", skip(self))]
+    async fn container_exists(
+        &mut self,
+        name: ContainerName,
+    ) -> wasmtime::Result<Result<bool, BlobstoreError>> {
+        let plugin = self.try_get_plugin::<S3Blobstore>(PLUGIN_BLOBSTORE_ID)?;
+
+        let Some(client) = plugin
+            .workload_permit(&self.workload_id, &name, false)
+            .await
+        else {
+            return Ok(Err("unauthorized".to_string()));
+        };
+
+        Ok(client
+            .bucket_exists(&name)
+            .await
+            .map_err(|e| format!("failed to check bucket: {e}")))
+    }
+
+    #[instrument(name = "wasi.blobstore.copy_object", skip(self))]
+    async fn copy_object(
+        &mut self,
+        src: ObjectId,
+        dest: ObjectId,
+    ) -> wasmtime::Result<Result<(), BlobstoreError>> {
+        let plugin = self.try_get_plugin::<S3Blobstore>(PLUGIN_BLOBSTORE_ID)?;
+
+        if plugin
+            .workload_permit(&self.workload_id, &src.container, false)
+            .await
+            .is_none()
+        {
+            return Ok(Err("unauthorized read".to_string()));
+        }
+
+        let Some(client) = plugin
+            .workload_permit(&self.workload_id, &dest.container, true)
+            .await
+        else {
+            return Ok(Err("unauthorized write".to_string()));
+        };
+
+        Ok(client
+            .copy_object(&src.container, &src.object, &dest.container, &dest.object)
+            .await
+            .map_err(|e| format!("failed to copy object: {e}")))
+    }
+
+    #[instrument(name = "wasi.blobstore.move_object", skip(self))]
+    async fn move_object(
+        &mut self,
+        src: ObjectId,
+        dest: ObjectId,
+    ) -> wasmtime::Result<Result<(), BlobstoreError>> {
+        let plugin = self.try_get_plugin::<S3Blobstore>(PLUGIN_BLOBSTORE_ID)?;
+
+        // requires an extra write permit on the src container to delete the object after copy
+        let Some(client) = plugin
+            .workload_permit(&self.workload_id, &src.container, true)
+            .await
+        else {
+            return Ok(Err("unauthorized delete".to_string()));
+        };
+
+        let copy = self.copy_object(src.clone(), dest).await?;
+        if let Err(e) = copy {
+            return Ok(Err(format!("failed to copy object during move: {e}")));
+        }
+
+        Ok(client
+            .delete_object(&src.container, &src.object)
+            .await
+            .map_err(|e| format!("failed to delete source object: {e}")))
+    }
+}
+
+impl<'a> bindings::wasi::blobstore::container::HostContainer for ActiveCtx<'a> {
+    async fn name(
+        &mut self,
+        container: Resource<ContainerData>,
+    ) -> wasmtime::Result<Result<String, ContainerError>> {
+        let container_data = self.table.get(&container)?;
+        Ok(Ok(container_data.name.clone()))
+    }
+
+    async fn info(
+        &mut self,
+        container: Resource<ContainerData>,
+    ) -> wasmtime::Result<Result<ContainerMetadata, ContainerError>> {
+        let container_data = self.table.get(&container)?;
+
+        Ok(Ok(ContainerMetadata {
+            name: container_data.name.clone(),
+            // Only ListBuckets reports a creation date.
+            created_at: 0,
+        }))
+    }
+
+    #[instrument(name = "wasi.blobstore.get_data", skip(self, container))]
+    async fn get_data(
+        &mut self,
+        container: Resource<ContainerData>,
+        name: ObjectName,
+        start: u64,
+        end: u64,
+    ) -> wasmtime::Result<Result<Resource<IncomingValueHandle>, ContainerError>> {
+        let container_data = self.table.get(&container)?;
+
+        let body = match container_data
+            .client
+            .get_object(&container_data.name, &name, start, end)
+            .await
+        {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!(
+                    container = container_data.name,
+                    object = name,
+                    "Failed to get object from S3: {e}"
+                );
+                return Ok(Err(format!("failed to get object '{name}': {e}")));
+            }
+        };
+
+        let resource = self.table.push(IncomingValueHandle { body })?;
+
+        Ok(Ok(resource))
+    }
+
+    #[instrument(name = "wasi.blobstore.write_data", skip(self, container, data))]
+    async fn write_data(
+        &mut self,
+        container: Resource<ContainerData>,
+        name: ObjectName,
+        data: Resource<OutgoingValueHandle>,
+    ) -> wasmtime::Result<Result<(), ContainerError>> {
+        let plugin = self.try_get_plugin::<S3Blobstore>(PLUGIN_BLOBSTORE_ID)?;
+
+        let container_data = self.table.get(&container).cloned()?;
+        if plugin
+            .workload_permit(&self.workload_id, &container_data.name, true)
+            .await
+            .is_none()
+        {
+            return Ok(Err("unauthorized write".to_string()));
+        }
+
+        // prepare the write operation
+        // it actually happens on 'finish'
+        let handle = self.table.get_mut(&data)?;
+        handle.container = Some(container_data);
+        handle.object_name = Some(name);
+
+        Ok(Ok(()))
+    }
+
+    #[instrument(name = "wasi.blobstore.list_objects", skip(self, container))]
+    async fn list_objects(
+        &mut self,
+        container: Resource<ContainerData>,
+    ) -> wasmtime::Result<Result<Resource<StreamObjectNamesHandle>, ContainerError>> {
+        let container_data = self.table.get(&container).cloned()?;
+
+        let resource = self.table.push(StreamObjectNamesHandle {
+            container: container_data,
+            names: VecDeque::new(),
+            continuation_token: None,
+            exhausted: false,
+        })?;
+
+        Ok(Ok(resource))
+    }
+
+    #[instrument(name = "wasi.blobstore.delete_object", skip(self, container))]
+    async fn delete_object(
+        &mut self,
+        container: Resource<ContainerData>,
+        name: ObjectName,
+    ) -> wasmtime::Result<Result<(), ContainerError>> {
+        let plugin = self.try_get_plugin::<S3Blobstore>(PLUGIN_BLOBSTORE_ID)?;
+
+        let container_data = self.table.get(&container)?;
+        if plugin
+            .workload_permit(&self.workload_id, &container_data.name, true)
+            .await
+            .is_none()
+        {
+            return Ok(Err("unauthorized write".to_string()));
+        }
+
+        Ok(container_data
+            .client
+            .delete_object(&container_data.name, &name)
+            .await
+            .map_err(|e| format!("failed to delete object: {e}")))
+    }
+
+    #[instrument(name = "wasi.blobstore.delete_objects", skip(self, container, names))]
+    async fn delete_objects(
+        &mut self,
+        container: Resource<ContainerData>,
+        names: Vec<ObjectName>,
+    ) -> wasmtime::Result<Result<(), ContainerError>> {
+        let plugin = self.try_get_plugin::<S3Blobstore>(PLUGIN_BLOBSTORE_ID)?;
+
+        let container_data = self.table.get(&container)?;
+        if plugin
+            .workload_permit(&self.workload_id, &container_data.name, true)
+            .await
+            .is_none()
+        {
+            return Ok(Err("unauthorized write".to_string()));
+        }
+
+        for name in names {
+            if let Err(e) = container_data
+                .client
+                .delete_object(&container_data.name, &name)
+                .await
+            {
+                return Ok(Err(format!("failed to delete object: {e}")));
+            }
+        }
+
+        Ok(Ok(()))
+    }
+
+    #[instrument(name = "wasi.blobstore.has_object", skip(self, container))]
+    async fn has_object(
+        &mut self,
+        container: Resource<ContainerData>,
+        name: ObjectName,
+    ) -> wasmtime::Result<Result<bool, ContainerError>> {
+        let container_data = self.table.get(&container)?;
+        match container_data
+            .client
+            .head_object(&container_data.name, &name)
+            .await
+        {
+            Ok(_) => Ok(Ok(true)),
+            Err(client::S3Error::NoSuchKey) => Ok(Ok(false)),
+            Err(e) => Ok(Err(format!("failed to check object: {e}"))),
+        }
+    }
+
+    #[instrument(name = "wasi.blobstore.object_info", skip(self, container))]
+    async fn object_info(
+        &mut self,
+        container: Resource<ContainerData>,
+        name: ObjectName,
+    ) -> wasmtime::Result<Result<ObjectMetadata, ContainerError>> {
+        let container_data = self.table.get(&container)?;
+        match container_data
+            .client
+            .head_object(&container_data.name, &name)
+            .await
+        {
+            Ok(meta) => Ok(Ok(ObjectMetadata {
+                name,
+                container: container_data.name.clone(),
+                created_at: meta.last_modified,
+                size: meta.size,
+            })),
+            Err(e) => Ok(Err(format!("failed to get object '{name}': {e}"))),
+        }
+    }
+
+    #[instrument(name = "wasi.blobstore.clear", skip(self, container))]
+    async fn clear(
+        &mut self,
+        container: Resource<ContainerData>,
+    ) -> wasmtime::Result<Result<(), ContainerError>> {
+        let plugin = self.try_get_plugin::<S3Blobstore>(PLUGIN_BLOBSTORE_ID)?;
+
+        let container_data = self.table.get(&container)?;
+        if plugin
+            .workload_permit(&self.workload_id, &container_data.name, true)
+            .await
+            .is_none()
+        {
+            return Ok(Err("unauthorized delete".to_string()));
+        }
+
+        Ok(container_data
+            .client
+            .clear_bucket(&container_data.name)
+            .await
+            .map_err(|e| {
+                format!(
+                    "failed to clear objects in container '{}': {e}",
+                    container_data.name
+                )
+            }))
+    }
+
+    async fn drop(&mut self, rep: Resource<ContainerData>) -> wasmtime::Result<()> {
+        // Container resource cleanup - resource table handles this automatically
+        tracing::debug!(
+            workload_id = self.workload_id.as_ref(),
+            component_id = self.component_id.as_ref(),
+            resource_id = ?rep,
+            "Dropping container resource"
+        );
+        self.table.delete(rep)?;
+        Ok(())
+    }
+}
+
+impl<'a> bindings::wasi::blobstore::container::HostStreamObjectNames for ActiveCtx<'a> {
+    async fn read_stream_object_names(
+        &mut self,
+        stream: Resource<StreamObjectNamesHandle>,
+        len: u64,
+    ) -> wasmtime::Result<Result<(Vec<ObjectName>, bool), ContainerError>> {
+        let stream_handle = self.table.get_mut(&stream)?;
+
+        let mut objects = Vec::<ObjectName>::new();
+        for _ in 0..len {
+            match stream_handle.next().await {
+                Ok(Some(name)) => objects.push(name),
+                Ok(None) => return Ok(Ok((objects, true))),
+                Err(e) => return Ok(Err(e)),
+            }
+        }
+
+        Ok(Ok((objects, false)))
+    }
+
+    async fn skip_stream_object_names(
+        &mut self,
+        stream: Resource<StreamObjectNamesHandle>,
+        num: u64,
+    ) -> wasmtime::Result<Result<(u64, bool), ContainerError>> {
+        let stream_handle = self.table.get_mut(&stream)?;
+
+        for i in 0..num {
+            match stream_handle.next().await {
+                Ok(Some(_)) => {}
+                Ok(None) => return Ok(Ok((i, true))),
+                Err(e) => return Ok(Err(e)),
+            }
+        }
+
+        Ok(Ok((num, false)))
+    }
+
+    async fn drop(&mut self, rep: Resource<StreamObjectNamesHandle>) -> wasmtime::Result<()> {
+        tracing::debug!(
+            workload_id = self.workload_id.as_ref(),
+            component_id = self.component_id.as_ref(),
+            resource_id = ?rep,
+            "Dropping StreamObjectNames resource"
+        );
+        self.table.delete(rep)?;
+        Ok(())
+    }
+}
+
+impl<'a> bindings::wasi::blobstore::types::HostOutgoingValue for ActiveCtx<'a> {
+    #[instrument(name = "wasi.blobstore.new_outgoing_value", skip(self))]
+    async fn new_outgoing_value(&mut self) -> wasmtime::Result<Resource<OutgoingValueHandle>> {
+        let temp_file = tempfile::Builder::new()
+            .tempfile()
+            .context("failed to create buffer file")?;
+
+        let handle = OutgoingValueHandle {
+            temp_file,
+            container: None,
+            object_name: None,
+        };
+
+        let resource = self.table.push(handle)?;
+        Ok(resource)
+    }
+
+    #[instrument(name = "wasi.blobstore.outgoing_value_write_body", skip(self))]
+    async fn outgoing_value_write_body(
+        &mut self,
+        outgoing_value: Resource<OutgoingValueHandle>,
+    ) -> wasmtime::Result<Result<Resource<bindings::wasi::io0_2_1::streams::OutputStream>, ()>>
+    {
+        let handle = self.table.get_mut(&outgoing_value)?;
+
+        // Reopen the tempfile so the OutputStream has its own file descriptor.
+        let file = handle.temp_file.reopen()?;
+        let stream = TempFileOutputStream { file };
+
+        let boxed: Box<dyn OutputStream> = Box::new(stream);
+        let resource = self.table.push(boxed)?;
+        Ok(Ok(resource))
+    }
+
+    #[instrument(name = "wasi.blobstore.finish", skip(self))]
+    async fn finish(
+        &mut self,
+        outgoing_value: Resource<OutgoingValueHandle>,
+    ) -> wasmtime::Result<Result<(), BlobstoreError>> {
+        let handle = self.table.delete(outgoing_value)?;
+        let Some(container_data) = handle.container else {
+            return Ok(Err(
+                "outgoing value not associated with a container".to_string()
+            ));
+        };
+        let Some(object_name) = handle.object_name else {
+            return Ok(Err(
+                "outgoing value not associated with an object name".to_string()
+            ));
+        };
+
+        let file = handle.temp_file.reopen()?;
+        let len = file.metadata()?.len();
+        let file = tokio::fs::File::from_std(file);
+
+        match container_data
+            .client
+            .upload(&container_data.name, &object_name, file, len)
+            .await
+        {
+            Ok(()) => Ok(Ok(())),
+            Err(e) => Ok(Err(format!("failed to write object data: {e}"))),
+        }
+    }
+
+    async fn drop(&mut self, rep: Resource<OutgoingValueHandle>) -> wasmtime::Result<()> {
+        self.finish(rep).await.map(|_| ())
+    }
+}
+
+impl<'a> bindings::wasi::blobstore::types::HostIncomingValue for ActiveCtx<'a> {
+    #[instrument(name = "wasi.blobstore.incoming_value_consume_sync", skip(self))]
+    async fn incoming_value_consume_sync(
+        &mut self,
+        incoming_value: Resource<IncomingValueHandle>,
+    ) -> wasmtime::Result<Result<Vec<u8>, BlobstoreError>> {
+        let data = self.table.delete(incoming_value)?;
+
+        Ok(data
+            .body
+            .bytes()
+            .await
+            .map_err(|e| format!("failed to read object data: {e}")))
+    }
+
+    #[instrument(name = "wasi.blobstore.incoming_value_consume_async", skip(self))]
+    async fn incoming_value_consume_async(
+        &mut self,
+        incoming_value: Resource<IncomingValueHandle>,
+    ) -> wasmtime::Result<
+        Result<Resource<bindings::wasi::blobstore::types::IncomingValueAsyncBody>, BlobstoreError>,
+    > {
+        let data = self.table.delete(incoming_value)?;
+
+        let stream: Box<dyn InputStream> = Box::new(AsyncReadStream::new(data.body.into_reader()));
+        let stream = self.table.push(stream)?;
+
+        Ok(Ok(stream))
+    }
+
+    async fn size(
+        &mut self,
+        incoming_value: Resource<IncomingValueHandle>,
+    ) -> wasmtime::Result<u64> {
+        let data = self.table.get(&incoming_value)?;
+        Ok(data.body.size())
+    }
+
+    async fn drop(&mut self, rep: Resource<IncomingValueHandle>) -> wasmtime::Result<()> {
+        tracing::debug!(
+            workload_id = self.workload_id.as_ref(),
+            resource_id = ?rep,
+            "Dropping IncomingValue resource"
+        );
+        self.table.delete(rep)?;
+        Ok(())
+    }
+}
+
+// Implement the main types Host trait that combines all resource types
+impl<'a> bindings::wasi::blobstore::types::Host for ActiveCtx<'a> {}
+
+// Implement the main container Host trait that combines all resource types
+impl<'a> bindings::wasi::blobstore::container::Host for ActiveCtx<'a> {}
+
+#[async_trait::async_trait]
+impl HostPlugin for S3Blobstore {
+    fn id(&self) -> &'static str {
+        PLUGIN_BLOBSTORE_ID
+    }
+    fn world(&self) -> WitWorld {
+        WitWorld {
+            imports: HashSet::from([WitInterface::from(
+                "wasi:blobstore/blobstore,container,types@0.2.0-draft",
+            )]),
+            ..Default::default()
+        }
+    }
+
+    async fn on_workload_item_bind<'a>(
+        &self,
+        component_handle: &mut WorkloadItem<'a>,
+        interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        if !interfaces.contains("wasi", "blobstore", &[]) {
+            return Ok(());
+        }
+
+        tracing::debug!(
+            workload_id = component_handle.workload_id(),
+            component_id = component_handle.id(),
+            "Adding blobstore interfaces"
+        );
+        let linker = component_handle.linker();
+
+        bindings::wasi::blobstore::blobstore::add_to_linker::<_, SharedCtx>(
+            linker,
+            extract_active_ctx,
+        )?;
+        bindings::wasi::blobstore::container::add_to_linker::<_, SharedCtx>(
+            linker,
+            extract_active_ctx,
+        )?;
+        bindings::wasi::blobstore::types::add_to_linker::<_, SharedCtx>(
+            linker,
+            extract_active_ctx,
+        )?;
+
+        Ok(())
+    }
+
+    async fn on_workload_bind(
+        &self,
+        workload: &crate::engine::workload::UnresolvedWorkload,
+        host_interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        let Some(interface) = host_interfaces.get("wasi", "blobstore", &[]) else {
+            return Ok(());
+        };
+
+        let client = S3Client::from_config(self.http.clone(), &interface.config)
+            .map_err(|e| e.context("invalid wasi:blobstore S3 configuration"))?;
+
+        let buckets = interface
+            .config
+            .get("buckets")
+            .map(|buckets| buckets.split(',').map(|s| s.trim().to_string()).collect());
+
+        let read_only = interface
+            .config
+            .get("read_only")
+            .is_some_and(|v| v == "true");
+
+        self.tracker.write().await.add_unresolved_workload(
+            workload,
+            WorkloadData {
+                client: Arc::new(client),
+                buckets,
+                read_only,
+            },
+        );
+
+        Ok(())
+    }
+
+    async fn on_workload_unbind(
+        &self,
+        workload_id: &str,
+        _interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        self.tracker
+            .write()
+            .await
+            .remove_workload(workload_id)
+            .await;
+
+        Ok(())
+    }
+}
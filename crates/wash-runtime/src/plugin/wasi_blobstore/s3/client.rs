@@ -0,0 +1,674 @@
+//! Signed requests against an S3-compatible service, shared by the
+//! [`S3Blobstore`](super::S3Blobstore) plugin and the multiplexed `s3`
+//! backend.
+//!
+//! Requests are presigned with [`rusty_s3`] and sent with `reqwest`, so any
+//! service speaking the S3 API works (AWS, MinIO, Ceph, R2, ...). Each
+//! blobstore container is one bucket, named `<bucket_prefix><container>`.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context as _, bail, ensure};
+use futures::StreamExt as _;
+use futures::TryStreamExt as _;
+use futures::stream::BoxStream;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_LENGTH, ETAG, LAST_MODIFIED, RANGE};
+use rusty_s3::actions::{
+    AbortMultipartUpload, CompleteMultipartUpload, CreateBucket, CreateMultipartUpload,
+    DeleteBucket, DeleteObject, GetObject, HeadBucket, HeadObject, ListObjectsV2, PutObject,
+    UploadPart,
+};
+use rusty_s3::{Bucket, Credentials, S3Action as _, UrlStyle};
+use tokio::io::{AsyncRead, AsyncReadExt as _};
+use tokio_util::io::StreamReader;
+use url::Url;
+
+/// How long a presigned request stays valid. Every request is signed right
+/// before it is sent, so this only has to cover clock skew and one slow part.
+const SIGNATURE_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Region used when the config names none.
+const DEFAULT_REGION: &str = "us-east-1";
+
+/// Smallest part S3 accepts in a multipart upload, other than the last.
+const MIN_PART_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Default part size, and the largest object written with a single `PUT`.
+const DEFAULT_PART_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Most parts one multipart upload may have.
+const MAX_PARTS: u64 = 10_000;
+
+/// Characters escaped in the `x-amz-copy-source` key: everything but the
+/// unreserved set and the `/` separating key segments.
+const COPY_SOURCE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~')
+    .remove(b'/');
+
+/// Why an S3 request failed.
+#[derive(Debug)]
+pub(crate) enum S3Error {
+    NoSuchBucket,
+    NoSuchKey,
+    BucketAlreadyExists,
+    InvalidRange,
+    Other(String),
+}
+
+impl S3Error {
+    fn other(e: impl std::fmt::Display) -> Self {
+        Self::Other(e.to_string())
+    }
+}
+
+impl std::fmt::Display for S3Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoSuchBucket => write!(f, "bucket does not exist"),
+            Self::NoSuchKey => write!(f, "object does not exist"),
+            Self::BucketAlreadyExists => write!(f, "bucket already exists"),
+            Self::InvalidRange => write!(f, "requested range is not satisfiable"),
+            Self::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+pub(crate) type S3Result<T> = Result<T, S3Error>;
+
+/// Metadata of one stored object.
+pub(crate) struct ObjectMeta {
+    pub size: u64,
+    /// Seconds since the Unix epoch, or 0 if the service didn't say.
+    pub last_modified: u64,
+}
+
+/// The body of a `GET`, not yet read.
+pub(crate) struct ObjectBody {
+    response: Option<reqwest::Response>,
+    size: u64,
+}
+
+impl ObjectBody {
+    /// Length of the requested range.
+    pub(crate) fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub(crate) async fn bytes(self) -> S3Result<Vec<u8>> {
+        match self.response {
+            Some(response) => Ok(response.bytes().await.map_err(S3Error::other)?.to_vec()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The body as a reader that pulls from the connection as it is read.
+    pub(crate) fn into_reader(self) -> impl AsyncRead + Send + Unpin + 'static {
+        let stream: BoxStream<'static, std::io::Result<bytes::Bytes>> = match self.response {
+            Some(response) => response
+                .bytes_stream()
+                .map_err(std::io::Error::other)
+                .boxed(),
+            None => futures::stream::empty().boxed(),
+        };
+        StreamReader::new(stream)
+    }
+}
+
+/// One endpoint, region and set of credentials, as configured on a
+/// `wasi:blobstore` host interface.
+#[derive(Clone)]
+pub(crate) struct S3Client {
+    http: reqwest::Client,
+    endpoint: Url,
+    url_style: UrlStyle,
+    region: String,
+    bucket_prefix: String,
+    credentials: Option<Credentials>,
+    part_size: u64,
+}
+
+impl S3Client {
+    /// Build a client from host-interface config:
+    ///
+    /// - `endpoint`: base URL of the service. Defaults to AWS in `region`.
+    /// - `region`: signing region, `us-east-1` if unset.
+    /// - `bucket_prefix`: prepended to every container name to form its bucket.
+    /// - `url_style`: `path` (the default with an `endpoint`) or `virtual-host`
+    ///   (the default for AWS).
+    /// - `access_key_id`, `secret_access_key` and optionally `session_token`,
+    ///   typically supplied through `secretFrom`. Without them requests go
+    ///   unsigned.
+    /// - `part_size`: bytes per part of a multipart upload, at least 5 MiB.
+    ///   Objects up to this size are written with a single `PUT`.
+    pub(crate) fn from_config(
+        http: reqwest::Client,
+        config: &HashMap<String, String>,
+    ) -> anyhow::Result<Self> {
+        let region = config
+            .get("region")
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_REGION.to_string());
+
+        let (endpoint, default_style) = match config.get("endpoint") {
+            Some(endpoint) => (
+                endpoint
+                    .parse::<Url>()
+                    .with_context(|| format!("invalid S3 endpoint '{endpoint}'"))?,
+                UrlStyle::Path,
+            ),
+            None => (
+                format!("https://s3.{region}.amazonaws.com")
+                    .parse::<Url>()
+                    .with_context(|| format!("invalid S3 region '{region}'"))?,
+                UrlStyle::VirtualHost,
+            ),
+        };
+        let url_style = match config.get("url_style").map(String::as_str) {
+            None => default_style,
+            Some("path") => UrlStyle::Path,
+            Some("virtual-host") => UrlStyle::VirtualHost,
+            Some(other) => {
+                bail!("invalid S3 url_style '{other}': expected 'path' or 'virtual-host'")
+            }
+        };
+
+        let credentials = match (config.get("access_key_id"), config.get("secret_access_key")) {
+            (Some(key), Some(secret)) => Some(match config.get("session_token") {
+                Some(token) => Credentials::new_with_token(key, secret, token),
+                None => Credentials::new(key, secret),
+            }),
+            (None, None) => None,
+            _ => bail!("S3 credentials need both access_key_id and secret_access_key"),
+        };
+
+        let part_size = match config.get("part_size") {
+            Some(size) => {
+                let size = size
+                    .parse::<u64>()
+                    .with_context(|| format!("invalid S3 part_size '{size}'"))?;
+                ensure!(
+                    size >= MIN_PART_SIZE,
+                    "S3 part_size must be at least {MIN_PART_SIZE} bytes"
+                );
+                size
+            }
+            None => DEFAULT_PART_SIZE,
+        };
+
+        // Surface a bad endpoint now rather than on the first request.
+        Bucket::new(endpoint.clone(), url_style, "bucket", region.clone())
+            .with_context(|| format!("invalid S3 endpoint '{endpoint}'"))?;
+
+        Ok(Self {
+            http,
+            endpoint,
+            url_style,
+            region,
+            bucket_prefix: config.get("bucket_prefix").cloned().unwrap_or_default(),
+            credentials,
+            part_size,
+        })
+    }
+
+    fn bucket(&self, container: &str) -> S3Result<Bucket> {
+        Bucket::new(
+            self.endpoint.clone(),
+            self.url_style,
+            format!("{}{container}", self.bucket_prefix),
+            self.region.clone(),
+        )
+        .map_err(|e| S3Error::Other(format!("invalid bucket for container '{container}': {e}")))
+    }
+
+    fn signing_credentials(&self) -> S3Result<&Credentials> {
+        self.credentials
+            .as_ref()
+            .ok_or_else(|| S3Error::Other("managing buckets requires credentials".to_string()))
+    }
+
+    pub(crate) async fn create_bucket(&self, container: &str) -> S3Result<()> {
+        let bucket = self.bucket(container)?;
+        let url = CreateBucket::new(&bucket, self.signing_credentials()?).sign(SIGNATURE_TTL);
+        let mut request = self.http.put(url);
+        // Outside us-east-1, S3 wants the region restated in the body.
+        if self.region != DEFAULT_REGION {
+            request = request.body(format!(
+                "<CreateBucketConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+                 <LocationConstraint>{}</LocationConstraint>\
+                 </CreateBucketConfiguration>",
+                self.region
+            ));
+        }
+        execute(request, S3Error::NoSuchBucket).await?;
+        Ok(())
+    }
+
+    /// Delete a bucket, which S3 only allows once it is empty.
+    pub(crate) async fn delete_bucket(&self, container: &str) -> S3Result<()> {
+        let bucket = self.bucket(container)?;
+        let url = DeleteBucket::new(&bucket, self.signing_credentials()?).sign(SIGNATURE_TTL);
+        execute(self.http.delete(url), S3Error::NoSuchBucket).await?;
+        Ok(())
+    }
+
+    pub(crate) async fn bucket_exists(&self, container: &str) -> S3Result<bool> {
+        let bucket = self.bucket(container)?;
+        let url = HeadBucket::new(&bucket, self.credentials.as_ref()).sign(SIGNATURE_TTL);
+        match execute(self.http.head(url), S3Error::NoSuchBucket).await {
+            Ok(_) => Ok(true),
+            Err(S3Error::NoSuchBucket) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// One page of object keys, and the token for the next page if there is one.
+    pub(crate) async fn list_page(
+        &self,
+        container: &str,
+        continuation_token: Option<&str>,
+    ) -> S3Result<(Vec<String>, Option<String>)> {
+        let bucket = self.bucket(container)?;
+        let mut action = ListObjectsV2::new(&bucket, self.credentials.as_ref());
+        if let Some(token) = continuation_token {
+            action.with_continuation_token(token);
+        }
+        let url = action.sign(SIGNATURE_TTL);
+        let body = execute(self.http.get(url), S3Error::NoSuchBucket)
+            .await?
+            .text()
+            .await
+            .map_err(S3Error::other)?;
+        let page = ListObjectsV2::parse_response(&body)
+            .map_err(|e| S3Error::Other(format!("invalid list response: {e}")))?;
+        let keys = page.contents.into_iter().map(|object| object.key).collect();
+        Ok((keys, page.next_continuation_token))
+    }
+
+    /// Every object key in a bucket.
+    pub(crate) async fn list_objects(&self, container: &str) -> S3Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut token = None;
+        loop {
+            let (page, next) = self.list_page(container, token.as_deref()).await?;
+            keys.extend(page);
+            match next {
+                Some(next) => token = Some(next),
+                None => return Ok(keys),
+            }
+        }
+    }
+
+    /// Read the inclusive byte range `[start, end]` of an object, clamped to
+    /// its length.
+    pub(crate) async fn get_object(
+        &self,
+        container: &str,
+        key: &str,
+        start: u64,
+        end: u64,
+    ) -> S3Result<ObjectBody> {
+        if end < start {
+            return Ok(ObjectBody {
+                response: None,
+                size: 0,
+            });
+        }
+        let bucket = self.bucket(container)?;
+        let url = GetObject::new(&bucket, self.credentials.as_ref(), key).sign(SIGNATURE_TTL);
+        let mut request = self.http.get(url);
+        if end != u64::MAX {
+            request = request.header(RANGE, format!("bytes={start}-{end}"));
+        } else if start > 0 {
+            request = request.header(RANGE, format!("bytes={start}-"));
+        }
+        let response = match execute(request, S3Error::NoSuchKey).await {
+            Ok(response) => response,
+            // The range starts past the end of the object.
+            Err(S3Error::InvalidRange) => {
+                return Ok(ObjectBody {
+                    response: None,
+                    size: 0,
+                });
+            }
+            Err(e) => return Err(e),
+        };
+        let size = header_u64(&response, CONTENT_LENGTH).unwrap_or_default();
+        Ok(ObjectBody {
+            response: Some(response),
+            size,
+        })
+    }
+
+    pub(crate) async fn head_object(&self, container: &str, key: &str) -> S3Result<ObjectMeta> {
+        let bucket = self.bucket(container)?;
+        let url = HeadObject::new(&bucket, self.credentials.as_ref(), key).sign(SIGNATURE_TTL);
+        let response = execute(self.http.head(url), S3Error::NoSuchKey).await?;
+        let last_modified = response
+            .headers()
+            .get(LAST_MODIFIED)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| chrono::DateTime::parse_from_rfc2822(value).ok())
+            .and_then(|time| u64::try_from(time.timestamp()).ok())
+            .unwrap_or_default();
+        Ok(ObjectMeta {
+            size: header_u64(&response, CONTENT_LENGTH).unwrap_or_default(),
+            last_modified,
+        })
+    }
+
+    pub(crate) async fn put_object(
+        &self,
+        container: &str,
+        key: &str,
+        data: Vec<u8>,
+    ) -> S3Result<()> {
+        let bucket = self.bucket(container)?;
+        let url = PutObject::new(&bucket, self.credentials.as_ref(), key).sign(SIGNATURE_TTL);
+        execute(self.http.put(url).body(data), S3Error::NoSuchBucket).await?;
+        Ok(())
+    }
+
+    /// Write `len` bytes read from `reader` to an object: a single `PUT` up to
+    /// the part size, a multipart upload holding one part in memory at a time
+    /// beyond it.
+    pub(crate) async fn upload(
+        &self,
+        container: &str,
+        key: &str,
+        mut reader: impl AsyncRead + Unpin,
+        len: u64,
+    ) -> S3Result<()> {
+        // Grow the parts if the configured size would need more than S3 allows.
+        let part_size = self.part_size.max(len.div_ceil(MAX_PARTS));
+        if len <= part_size {
+            let data = read_part(&mut reader, len).await?;
+            return self.put_object(container, key, data).await;
+        }
+
+        let bucket = self.bucket(container)?;
+        let credentials = self.credentials.as_ref();
+        let url = CreateMultipartUpload::new(&bucket, credentials, key).sign(SIGNATURE_TTL);
+        let body = execute(self.http.post(url), S3Error::NoSuchBucket)
+            .await?
+            .text()
+            .await
+            .map_err(S3Error::other)?;
+        let upload = CreateMultipartUpload::parse_response(&body)
+            .map_err(|e| S3Error::Other(format!("invalid multipart upload response: {e}")))?;
+        let upload_id = upload.upload_id();
+
+        let result = async {
+            let etags = self
+                .upload_parts(&bucket, key, upload_id, &mut reader, len, part_size)
+                .await?;
+            let action = CompleteMultipartUpload::new(
+                &bucket,
+                credentials,
+                key,
+                upload_id,
+                etags.iter().map(String::as_str),
+            );
+            let url = action.sign(SIGNATURE_TTL);
+            let response = execute(
+                self.http.post(url).body(action.body()),
+                S3Error::NoSuchBucket,
+            )
+            .await?;
+            // A completion that fails late still answers 200, with the error
+            // in the body.
+            check_body(response).await
+        }
+        .await;
+
+        if result.is_err() {
+            let url =
+                AbortMultipartUpload::new(&bucket, credentials, key, upload_id).sign(SIGNATURE_TTL);
+            if let Err(e) = execute(self.http.delete(url), S3Error::NoSuchKey).await {
+                tracing::warn!(
+                    bucket = bucket.name(),
+                    key,
+                    upload_id,
+                    error = %e,
+                    "failed to abort multipart upload"
+                );
+            }
+        }
+        result
+    }
+
+    async fn upload_parts(
+        &self,
+        bucket: &Bucket,
+        key: &str,
+        upload_id: &str,
+        reader: &mut (impl AsyncRead + Unpin),
+        len: u64,
+        part_size: u64,
+    ) -> S3Result<Vec<String>> {
+        let mut etags = Vec::new();
+        let mut remaining = len;
+        while remaining > 0 {
+            let part = read_part(reader, remaining.min(part_size)).await?;
+            remaining -= part.len() as u64;
+            let part_number = u16::try_from(etags.len() + 1)
+                .map_err(|_| S3Error::Other("too many parts in multipart upload".to_string()))?;
+            let url = UploadPart::new(
+                bucket,
+                self.credentials.as_ref(),
+                key,
+                part_number,
+                upload_id,
+            )
+            .sign(SIGNATURE_TTL);
+            let response = execute(self.http.put(url).body(part), S3Error::NoSuchKey).await?;
+            let etag = response
+                .headers()
+                .get(ETAG)
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| {
+                    S3Error::Other(format!("part {part_number} was uploaded without an ETag"))
+                })?;
+            etags.push(etag.to_string());
+        }
+        Ok(etags)
+    }
+
+    /// Copy an object server-side. S3 copies objects up to 5 GiB this way.
+    pub(crate) async fn copy_object(
+        &self,
+        src_container: &str,
+        src_key: &str,
+        dest_container: &str,
+        dest_key: &str,
+    ) -> S3Result<()> {
+        let source = format!(
+            "/{}{src_container}/{}",
+            self.bucket_prefix,
+            utf8_percent_encode(src_key, COPY_SOURCE)
+        );
+        let bucket = self.bucket(dest_container)?;
+        let mut action = PutObject::new(&bucket, self.credentials.as_ref(), dest_key);
+        action
+            .headers_mut()
+            .insert("x-amz-copy-source", source.clone());
+        let url = action.sign(SIGNATURE_TTL);
+        let response = execute(
+            self.http.put(url).header("x-amz-copy-source", source),
+            S3Error::NoSuchKey,
+        )
+        .await?;
+        check_body(response).await
+    }
+
+    /// Delete an object. Deleting one that doesn't exist succeeds.
+    pub(crate) async fn delete_object(&self, container: &str, key: &str) -> S3Result<()> {
+        let bucket = self.bucket(container)?;
+        let url = DeleteObject::new(&bucket, self.credentials.as_ref(), key).sign(SIGNATURE_TTL);
+        execute(self.http.delete(url), S3Error::NoSuchBucket).await?;
+        Ok(())
+    }
+
+    /// Delete every object in a bucket.
+    pub(crate) async fn clear_bucket(&self, container: &str) -> S3Result<()> {
+        for key in self.list_objects(container).await? {
+            self.delete_object(container, &key).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Send a request, turning an error response into an [`S3Error`] by the code
+/// in its body. `not_found` is what a bare `404` means for this request, as
+/// `HEAD` responses carry no body to tell.
+async fn execute(
+    request: reqwest::RequestBuilder,
+    not_found: S3Error,
+) -> S3Result<reqwest::Response> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| S3Error::Other(format!("S3 request failed: {e}")))?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(match xml_element(&body, "Code") {
+        Some("NoSuchBucket") => S3Error::NoSuchBucket,
+        Some("NoSuchKey") => S3Error::NoSuchKey,
+        Some("BucketAlreadyExists" | "BucketAlreadyOwnedByYou") => S3Error::BucketAlreadyExists,
+        Some("InvalidRange") => S3Error::InvalidRange,
+        None if status == StatusCode::RANGE_NOT_SATISFIABLE => S3Error::InvalidRange,
+        None if status == StatusCode::NOT_FOUND => not_found,
+        code => S3Error::Other(describe(
+            status.as_u16(),
+            code,
+            xml_element(&body, "Message"),
+        )),
+    })
+}
+
+/// Fail on the error document some operations return with a `200`.
+async fn check_body(response: reqwest::Response) -> S3Result<()> {
+    let body = response.text().await.map_err(S3Error::other)?;
+    match xml_element(&body, "Code") {
+        Some(code) if body.contains("<Error>") => Err(S3Error::Other(describe(
+            200,
+            Some(code),
+            xml_element(&body, "Message"),
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn describe(status: u16, code: Option<&str>, message: Option<&str>) -> String {
+    let mut description = format!("S3 returned {status}");
+    if let Some(code) = code {
+        description.push_str(&format!(" {code}"));
+    }
+    if let Some(message) = message {
+        description.push_str(&format!(": {message}"));
+    }
+    description
+}
+
+/// The text of the first `<name>` element in an S3 error document.
+fn xml_element<'a>(body: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<{name}>");
+    let start = body.find(&open)? + open.len();
+    let rest = body.get(start..)?;
+    rest.get(..rest.find(&format!("</{name}>"))?)
+}
+
+fn header_u64(response: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<u64> {
+    response.headers().get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// Read exactly `len` bytes, failing if the reader ends first.
+async fn read_part(reader: &mut (impl AsyncRead + Unpin), len: u64) -> S3Result<Vec<u8>> {
+    let mut part = Vec::with_capacity(usize::try_from(len).unwrap_or_default());
+    reader
+        .take(len)
+        .read_to_end(&mut part)
+        .await
+        .map_err(|e| S3Error::Other(format!("failed to read object body: {e}")))?;
+    if (part.len() as u64) < len {
+        return Err(S3Error::Other(format!(
+            "object body ended after {} of {len} bytes",
+            part.len()
+        )));
+    }
+    Ok(part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn config_defaults_to_aws() {
+        let client = S3Client::from_config(reqwest::Client::new(), &config(&[])).unwrap();
+        assert_eq!(
+            client.endpoint.as_str(),
+            "https://s3.us-east-1.amazonaws.com/"
+        );
+        assert!(matches!(client.url_style, UrlStyle::VirtualHost));
+        assert!(client.credentials.is_none());
+        assert_eq!(client.part_size, DEFAULT_PART_SIZE);
+    }
+
+    #[test]
+    fn config_is_validated() {
+        let http = reqwest::Client::new();
+        for bad in [
+            config(&[("endpoint", "not a url")]),
+            config(&[("endpoint", "ftp://minio:9000")]),
+            config(&[("url_style", "sideways")]),
+            config(&[("access_key_id", "key")]),
+            config(&[("part_size", "1024")]),
+        ] {
+            assert!(
+                S3Client::from_config(http.clone(), &bad).is_err(),
+                "{bad:?}"
+            );
+        }
+
+        let client = S3Client::from_config(
+            http,
+            &config(&[
+                ("endpoint", "http://127.0.0.1:9000"),
+                ("bucket_prefix", "prod-"),
+                ("access_key_id", "key"),
+                ("secret_access_key", "secret"),
+            ]),
+        )
+        .unwrap();
+        assert!(matches!(client.url_style, UrlStyle::Path));
+        assert_eq!(client.bucket("media").unwrap().name(), "prod-media");
+    }
+
+    #[test]
+    fn error_documents_are_read() {
+        let body = "<?xml version=\"1.0\"?><Error><Code>SlowDown</Code>\
+                    <Message>Please reduce your request rate.</Message></Error>";
+        assert_eq!(xml_element(body, "Code"), Some("SlowDown"));
+        assert_eq!(
+            describe(503, xml_element(body, "Code"), xml_element(body, "Message")),
+            "S3 returned 503 SlowDown: Please reduce your request rate."
+        );
+        assert_eq!(xml_element("<Error></Error>", "Code"), None);
+    }
+}
@@ -4,18 +4,22 @@
 //! Implements `wasi:keyvalue@0.2.0-draft` over the local filesystem. The actual
 //! storage lives in the shared [`FsKvStore`](super::fs_store::FsKvStore) — this
 //! module is the host-binding adapter (the unnamed/default `wasi:keyvalue`
-//! instance); the multiplexed `FilesystemBackend` is the other adapter.
+//! instance); the multiplexed `FilesystemBackend` is the other adapter. Key
+//! expiry is configured per workload; see [`super::ttl`].
 
 use std::collections::HashSet;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 const PLUGIN_KEYVALUE_ID: &str = "wasi-keyvalue";
 use crate::engine::ctx::{ActiveCtx, SharedCtx, extract_active_ctx};
-use crate::engine::workload::WorkloadItem;
+use crate::engine::workload::{UnresolvedWorkload, WorkloadItem};
 use crate::plugin::wasi_keyvalue::fs_store::{FsKvError, FsKvStore};
+use crate::plugin::wasi_keyvalue::ttl::{self, WorkloadTtls};
 use crate::plugin::{HostPlugin, WitInterfaces};
 use crate::wit::{WitInterface, WitWorld};
+use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 use wasmtime::component::Resource;
 
@@ -44,9 +48,11 @@ fn to_store_error(e: FsKvError) -> StoreError {
 }
 
 /// Resource representation for a bucket: its identifier (a subdirectory of the
-/// store root, resolved per-op by [`FsKvStore`]).
+/// store root, resolved per-op by [`FsKvStore`]) and the TTL of writes that
+/// don't pick their own.
 pub struct BucketHandle {
     id: String,
+    ttl: Option<Duration>,
 }
 
 /// Filesystem-based keyvalue plugin.
@@ -54,6 +60,8 @@ pub struct BucketHandle {
 pub struct FilesystemKeyValue {
     store: FsKvStore,
     metrics: Arc<WasiKeyvalueMetrics>,
+    ttls: WorkloadTtls,
+    sweeper: CancellationToken,
 }
 
 struct WasiKeyvalueMetrics {
@@ -77,6 +85,8 @@ impl FilesystemKeyValue {
         Self {
             store: FsKvStore::new(root),
             metrics: Arc::new(metrics),
+            ttls: WorkloadTtls::default(),
+            sweeper: CancellationToken::new(),
         }
     }
 
@@ -103,7 +113,11 @@ impl<'a> bindings::wasi::keyvalue::store::Host for ActiveCtx<'a> {
             return Ok(Err(to_store_error(e)));
         }
 
-        let resource = self.table.push(BucketHandle { id: identifier })?;
+        let ttl = plugin.ttls.for_bucket(&self.workload_id, &identifier).await;
+        let resource = self.table.push(BucketHandle {
+            id: identifier,
+            ttl,
+        })?;
         Ok(Ok(resource))
     }
 }
@@ -133,10 +147,10 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
         let plugin = self.try_get_plugin::<FilesystemKeyValue>(PLUGIN_KEYVALUE_ID)?;
         plugin.record_operation("set");
 
-        let id = self.table.get(&bucket)?.id.clone();
+        let BucketHandle { id, ttl } = self.table.get(&bucket)?;
         Ok(plugin
             .store
-            .set(&id, &key, &value)
+            .set(id, &key, &value, *ttl)
             .await
             .map_err(to_store_error))
     }
@@ -151,7 +165,12 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
         plugin.record_operation("delete");
 
         let id = self.table.get(&bucket)?.id.clone();
-        Ok(plugin.store.delete(&id, &key).await.map_err(to_store_error))
+        Ok(plugin
+            .store
+            .delete(&id, &key)
+            .await
+            .map(|_| ())
+            .map_err(to_store_error))
     }
 
     #[instrument(name = "wasi.keyvalue.exists", skip(self, bucket))]
@@ -210,10 +229,10 @@ impl<'a> bindings::wasi::keyvalue::atomics::Host for ActiveCtx<'a> {
         let plugin = self.try_get_plugin::<FilesystemKeyValue>(PLUGIN_KEYVALUE_ID)?;
         plugin.record_operation("increment");
 
-        let id = self.table.get(&bucket)?.id.clone();
+        let BucketHandle { id, ttl } = self.table.get(&bucket)?;
         Ok(plugin
             .store
-            .increment(&id, &key, delta)
+            .increment(id, &key, delta, *ttl)
             .await
             .map_err(to_store_error))
     }
@@ -251,9 +270,9 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
         let plugin = self.try_get_plugin::<FilesystemKeyValue>(PLUGIN_KEYVALUE_ID)?;
         plugin.record_operation("set_many");
 
-        let id = self.table.get(&bucket)?.id.clone();
+        let BucketHandle { id, ttl } = self.table.get(&bucket)?;
         for (key, value) in key_values {
-            if let Err(e) = plugin.store.set(&id, &key, &value).await {
+            if let Err(e) = plugin.store.set(id, &key, &value, *ttl).await {
                 return Ok(Err(to_store_error(e)));
             }
         }
@@ -279,6 +298,32 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
     }
 }
 
+// Implementation for the key expiry extension
+impl<'a> bindings::wasmcloud::keyvalue0_1_0_draft::ttl::Host for ActiveCtx<'a> {
+    #[instrument(name = "wasmcloud.keyvalue.set_with_ttl", skip(self, bucket, value))]
+    async fn set_with_ttl(
+        &mut self,
+        bucket: Resource<BucketHandle>,
+        key: String,
+        value: Vec<u8>,
+        ttl_ms: u64,
+    ) -> wasmtime::Result<Result<(), StoreError>> {
+        let plugin = self.try_get_plugin::<FilesystemKeyValue>(PLUGIN_KEYVALUE_ID)?;
+        plugin.record_operation("set_with_ttl");
+
+        let ttl = match ttl::requested_ttl(ttl_ms) {
+            Ok(ttl) => ttl,
+            Err(e) => return Ok(Err(StoreError::Other(e))),
+        };
+        let id = &self.table.get(&bucket)?.id;
+        Ok(plugin
+            .store
+            .set(id, &key, &value, Some(ttl))
+            .await
+            .map_err(to_store_error))
+    }
+}
+
 #[async_trait::async_trait]
 impl HostPlugin for FilesystemKeyValue {
     fn id(&self) -> &'static str {
@@ -287,11 +332,40 @@ impl HostPlugin for FilesystemKeyValue {
 
     fn world(&self) -> WitWorld {
         WitWorld {
-            imports: HashSet::from([WitInterface::from("wasi:keyvalue/store,atomics,batch")]),
+            imports: HashSet::from([
+                WitInterface::from("wasi:keyvalue/store,atomics,batch"),
+                WitInterface::from("wasmcloud:keyvalue/ttl@0.1.0-draft"),
+            ]),
             ..Default::default()
         }
     }
 
+    async fn start(&self) -> anyhow::Result<()> {
+        let store = self.store.clone();
+        ttl::spawn_sweeper(self.sweeper.clone(), move || {
+            let store = store.clone();
+            async move {
+                if let Err(FsKvError::Io(e)) = store.sweep().await {
+                    tracing::warn!(error = %e, "failed to sweep expired keyvalue keys");
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn stop(&self) -> anyhow::Result<()> {
+        self.sweeper.cancel();
+        Ok(())
+    }
+
+    async fn on_workload_bind(
+        &self,
+        workload: &UnresolvedWorkload,
+        interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        self.ttls.bind(workload.id(), &interfaces).await
+    }
+
     async fn on_workload_item_bind<'a>(
         &self,
         component_handle: &mut WorkloadItem<'a>,
@@ -318,6 +392,12 @@ impl HostPlugin for FilesystemKeyValue {
             extract_active_ctx,
         )?;
         bindings::wasi::keyvalue::batch::add_to_linker::<_, SharedCtx>(linker, extract_active_ctx)?;
+        if interfaces.contains("wasmcloud", "keyvalue", &["ttl"]) {
+            bindings::wasmcloud::keyvalue0_1_0_draft::ttl::add_to_linker::<_, SharedCtx>(
+                linker,
+                extract_active_ctx,
+            )?;
+        }
 
         let id = component_handle.id();
         tracing::debug!(
@@ -335,6 +415,7 @@ impl HostPlugin for FilesystemKeyValue {
         workload_id: &str,
         _interfaces: WitInterfaces<'_>,
     ) -> anyhow::Result<()> {
+        self.ttls.unbind(workload_id).await;
         tracing::debug!("WasiKeyvalue plugin unbound from workload '{workload_id}'");
 
         Ok(())
@@ -7,13 +7,28 @@
 //! [`FilesystemBackend`] (an `(implements ..)` named route) — each is a thin
 //! adapter that maps [`FsKvError`] to its own interface error type.
 //!
+//! A key written with a TTL has its deadline (unix milliseconds) in
+//! `<bucket>/.expires/<key>`. Expired keys read as missing and are removed on
+//! access or by [`FsKvStore::sweep`].
+//!
 //! [`FilesystemKeyValue`]: super::filesystem::FilesystemKeyValue
 //! [`FilesystemBackend`]: super::multiplexed::FilesystemBackend
 
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::plugin::lock_root;
 
+/// Directory within each bucket holding the deadlines of keys with a TTL.
+const EXPIRES_DIR: &str = ".expires";
+
+/// Milliseconds since the unix epoch.
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
 /// An error from the shared filesystem key-value store. Adapters map this to
 /// their own (`wasi:keyvalue/store`) error type.
 pub(crate) enum FsKvError {
@@ -43,9 +58,69 @@ impl FsKvStore {
 
     /// Resolve (and traversal-check) a key's file under its bucket directory.
     fn key_path(&self, bucket: &str, key: &str) -> Result<PathBuf, FsKvError> {
+        // The deadline directory isn't a key, nor is anything inside it.
+        let first = Path::new(key)
+            .components()
+            .find(|c| !matches!(c, std::path::Component::CurDir));
+        if first.is_some_and(|c| c.as_os_str() == EXPIRES_DIR) {
+            return Err(FsKvError::InvalidIdentifier);
+        }
         lock_root(self.bucket_root(bucket)?, key).map_err(|_| FsKvError::InvalidIdentifier)
     }
 
+    /// Resolve (and traversal-check) the deadline file of a key.
+    fn expiry_path(&self, bucket: &str, key: &str) -> Result<PathBuf, FsKvError> {
+        lock_root(self.bucket_root(bucket)?.join(EXPIRES_DIR), key)
+            .map_err(|_| FsKvError::InvalidIdentifier)
+    }
+
+    /// Whether the deadline in `path` has passed. A missing or unreadable
+    /// deadline means the key never expires.
+    async fn deadline_passed(path: &Path, now: u64) -> bool {
+        match tokio::fs::read_to_string(path).await {
+            Ok(s) => s
+                .trim()
+                .parse::<u64>()
+                .is_ok_and(|deadline| deadline <= now),
+            Err(_) => false,
+        }
+    }
+
+    /// Remove a key if its TTL has run out, returning whether it had.
+    async fn purge_expired(&self, bucket: &str, key: &str) -> Result<bool, FsKvError> {
+        let expiry = self.expiry_path(bucket, key)?;
+        if !Self::deadline_passed(&expiry, now_ms()).await {
+            return Ok(false);
+        }
+        self.delete(bucket, key).await?;
+        Ok(true)
+    }
+
+    /// Record when a key expires, or clear its deadline when `ttl` is `None`.
+    async fn set_expiry(
+        &self,
+        bucket: &str,
+        key: &str,
+        ttl: Option<Duration>,
+    ) -> Result<(), FsKvError> {
+        let path = self.expiry_path(bucket, key)?;
+        match ttl {
+            Some(ttl) => {
+                if let Some(dir) = path.parent() {
+                    tokio::fs::create_dir_all(dir)
+                        .await
+                        .map_err(FsKvError::Io)?;
+                }
+                let ttl_ms = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+                let deadline = now_ms().saturating_add(ttl_ms);
+                tokio::fs::write(path, deadline.to_string())
+                    .await
+                    .map_err(FsKvError::Io)
+            }
+            None => remove_if_present(&path).await.map(|_| ()),
+        }
+    }
+
     /// Create the bucket directory (idempotent); also validates the identifier.
     pub(crate) async fn create_bucket(&self, bucket: &str) -> Result<(), FsKvError> {
         let root = self.bucket_root(bucket)?;
@@ -56,6 +131,9 @@ impl FsKvStore {
 
     pub(crate) async fn get(&self, bucket: &str, key: &str) -> Result<Option<Vec<u8>>, FsKvError> {
         let path = self.key_path(bucket, key)?;
+        if self.purge_expired(bucket, key).await? {
+            return Ok(None);
+        }
         match tokio::fs::read(path).await {
             Ok(bytes) => Ok(Some(bytes)),
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
@@ -63,23 +141,32 @@ impl FsKvStore {
         }
     }
 
-    pub(crate) async fn set(&self, bucket: &str, key: &str, value: &[u8]) -> Result<(), FsKvError> {
+    /// Write a key, expiring it after `ttl` if one is given.
+    pub(crate) async fn set(
+        &self,
+        bucket: &str,
+        key: &str,
+        value: &[u8],
+        ttl: Option<Duration>,
+    ) -> Result<(), FsKvError> {
         let path = self.key_path(bucket, key)?;
+        self.set_expiry(bucket, key, ttl).await?;
         tokio::fs::write(path, value).await.map_err(FsKvError::Io)
     }
 
-    /// Delete a key. A missing key is a no-op (success).
-    pub(crate) async fn delete(&self, bucket: &str, key: &str) -> Result<(), FsKvError> {
+    /// Delete a key, returning whether it existed. A missing key is a no-op
+    /// (success).
+    pub(crate) async fn delete(&self, bucket: &str, key: &str) -> Result<bool, FsKvError> {
         let path = self.key_path(bucket, key)?;
-        match tokio::fs::remove_file(path).await {
-            Ok(()) => Ok(()),
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
-            Err(e) => Err(FsKvError::Io(e)),
-        }
+        remove_if_present(&self.expiry_path(bucket, key)?).await?;
+        remove_if_present(&path).await
     }
 
     pub(crate) async fn exists(&self, bucket: &str, key: &str) -> Result<bool, FsKvError> {
         let path = self.key_path(bucket, key)?;
+        if self.purge_expired(bucket, key).await? {
+            return Ok(false);
+        }
         match tokio::fs::metadata(&path).await {
             // Directories are buckets, not keys.
             Ok(meta) => Ok(!meta.is_dir()),
@@ -90,7 +177,7 @@ impl FsKvStore {
 
     /// List up to `batch` key names starting at `cursor`, returning the names
     /// and the next cursor (`Some` if more remain). A missing bucket directory
-    /// yields an empty page.
+    /// yields an empty page. Expired keys are left out.
     pub(crate) async fn list_keys(
         &self,
         bucket: &str,
@@ -104,11 +191,17 @@ impl FsKvStore {
             Err(e) => return Err(FsKvError::Io(e)),
         };
 
+        let expires = root.join(EXPIRES_DIR);
+        let now = now_ms();
         let skip = cursor.unwrap_or(0) as usize;
         let mut remaining_skip = skip;
         let mut keys = Vec::new();
         let mut next_cursor = None;
         while let Some(entry) = entries.next_entry().await.map_err(FsKvError::Io)? {
+            let name = entry.file_name();
+            if name == EXPIRES_DIR || Self::deadline_passed(&expires.join(&name), now).await {
+                continue;
+            }
             if remaining_skip != 0 {
                 remaining_skip -= 1;
                 continue;
@@ -117,27 +210,31 @@ impl FsKvStore {
                 next_cursor = Some(skip as u64 + batch as u64);
                 break;
             }
-            keys.push(entry.file_name().to_string_lossy().to_string());
+            keys.push(name.to_string_lossy().to_string());
         }
         Ok((keys, next_cursor))
     }
 
     /// Atomically-ish increment a decimal counter stored as a string. A missing
     /// or unparseable value is treated as 0; the result is saturating so an
-    /// overflow can't panic-trap a guest.
+    /// overflow can't panic-trap a guest. An expired counter restarts at 0, and
+    /// the written value expires after `ttl` if one is given.
     pub(crate) async fn increment(
         &self,
         bucket: &str,
         key: &str,
         delta: u64,
+        ttl: Option<Duration>,
     ) -> Result<u64, FsKvError> {
         let path = self.key_path(bucket, key)?;
+        self.purge_expired(bucket, key).await?;
         let current = match tokio::fs::read_to_string(&path).await {
             Ok(s) => s.trim().parse::<u64>().unwrap_or(0),
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
             Err(e) => return Err(FsKvError::Io(e)),
         };
         let next = current.saturating_add(delta);
+        self.set_expiry(bucket, key, ttl).await?;
         tokio::fs::write(&path, next.to_string())
             .await
             .map_err(FsKvError::Io)?;
@@ -172,4 +269,38 @@ impl FsKvStore {
             .map_err(FsKvError::Io)?;
         Ok(next)
     }
+
+    /// Remove every key whose TTL has run out, across all buckets.
+    pub(crate) async fn sweep(&self) -> Result<(), FsKvError> {
+        let mut buckets = match tokio::fs::read_dir(&self.root).await {
+            Ok(buckets) => buckets,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(FsKvError::Io(e)),
+        };
+        let now = now_ms();
+        while let Some(bucket) = buckets.next_entry().await.map_err(FsKvError::Io)? {
+            let bucket_dir = bucket.path();
+            let mut deadlines = match tokio::fs::read_dir(bucket_dir.join(EXPIRES_DIR)).await {
+                Ok(deadlines) => deadlines,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(FsKvError::Io(e)),
+            };
+            while let Some(deadline) = deadlines.next_entry().await.map_err(FsKvError::Io)? {
+                if Self::deadline_passed(&deadline.path(), now).await {
+                    remove_if_present(&deadline.path()).await?;
+                    remove_if_present(&bucket_dir.join(deadline.file_name())).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Remove a file, returning whether it existed.
+async fn remove_if_present(path: &Path) -> Result<bool, FsKvError> {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(FsKvError::Io(e)),
+    }
 }
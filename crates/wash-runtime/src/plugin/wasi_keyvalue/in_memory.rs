@@ -2,23 +2,31 @@
 //!
 //! This module implements an in-memory keyvalue plugin for the wasmCloud runtime,
 //! providing the `wasi:keyvalue@0.2.0-draft` interfaces for development and testing scenarios.
+//! Expired keys are hidden from reads straight away and removed by a periodic
+//! sweep; see [`super::ttl`].
 
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 const WASI_KEYVALUE_ID: &str = "wasi-keyvalue";
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 use wasmtime::component::Resource;
 
 use crate::{
+    engine::workload::UnresolvedWorkload,
     engine::{
         ctx::{ActiveCtx, SharedCtx, extract_active_ctx},
         workload::WorkloadItem,
     },
-    plugin::{HostPlugin, WitInterfaces},
+    plugin::{
+        HostPlugin, WitInterfaces,
+        wasi_keyvalue::ttl::{self, WorkloadTtls},
+    },
     wit::{WitInterface, WitWorld},
 };
 
@@ -35,26 +43,103 @@ mod bindings {
 use bindings::wasi::keyvalue::store::{Error as StoreError, KeyResponse};
 
 /// In-memory bucket representation
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct BucketData {
     pub data: HashMap<String, Vec<u8>>,
+    /// When each key written with a TTL expires
+    pub expires: HashMap<String, Instant>,
+}
+
+impl BucketData {
+    /// The value of `key`, unless it has expired.
+    fn get(&self, key: &str) -> Option<&Vec<u8>> {
+        if self
+            .expires
+            .get(key)
+            .is_some_and(|at| *at <= Instant::now())
+        {
+            return None;
+        }
+        self.data.get(key)
+    }
+
+    /// Write `key`, expiring it after `ttl` if one is given.
+    fn insert(&mut self, key: String, value: Vec<u8>, ttl: Option<Duration>) {
+        match ttl.and_then(|ttl| Instant::now().checked_add(ttl)) {
+            Some(at) => {
+                self.expires.insert(key.clone(), at);
+            }
+            None => {
+                self.expires.remove(&key);
+            }
+        }
+        self.data.insert(key, value);
+    }
+
+    fn remove(&mut self, key: &str) {
+        self.data.remove(key);
+        self.expires.remove(key);
+    }
+
+    /// Remove every key that expired by `now`.
+    fn sweep(&mut self, now: Instant) {
+        let data = &mut self.data;
+        self.expires.retain(|key, at| {
+            let live = *at > now;
+            if !live {
+                data.remove(key);
+            }
+            live
+        });
+    }
 }
 
 /// Resource representation for a bucket (key-value store)
-pub type BucketHandle = String;
+pub struct BucketHandle {
+    name: String,
+    /// TTL of writes that don't pick their own
+    ttl: Option<Duration>,
+}
 
 /// Memory-based keyvalue plugin
 #[derive(Clone, Default)]
 pub struct InMemoryKeyValue {
     /// Storage for all buckets, keyed by workload ID, then bucket name
     storage: Arc<RwLock<HashMap<String, HashMap<String, BucketData>>>>,
+    ttls: WorkloadTtls,
+    sweeper: CancellationToken,
 }
 
 impl InMemoryKeyValue {
     pub fn new() -> Self {
-        Self {
-            storage: Arc::new(RwLock::new(HashMap::new())),
+        Self::default()
+    }
+}
+
+/// Write `key` to the bucket with `ttl`, or the bucket's default TTL if `None`.
+async fn put(
+    ctx: &mut ActiveCtx<'_>,
+    bucket: &Resource<BucketHandle>,
+    key: String,
+    value: Vec<u8>,
+    ttl: Option<Duration>,
+) -> wasmtime::Result<Result<(), StoreError>> {
+    let handle = ctx.table.get(bucket)?;
+
+    let plugin = ctx.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
+
+    let mut storage = plugin.storage.write().await;
+    let workload_storage = storage.entry(ctx.workload_id.to_string()).or_default();
+
+    match workload_storage.get_mut(&handle.name) {
+        Some(bucket_data) => {
+            bucket_data.insert(key, value, ttl.or(handle.ttl));
+            Ok(Ok(()))
         }
+        None => Ok(Err(StoreError::Other(format!(
+            "bucket '{}' does not exist",
+            handle.name
+        )))),
     }
 }
 
@@ -66,19 +151,18 @@ impl<'a> bindings::wasi::keyvalue::store::Host for ActiveCtx<'a> {
         identifier: String,
     ) -> wasmtime::Result<Result<Resource<BucketHandle>, StoreError>> {
         let plugin = self.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
+        let ttl = plugin.ttls.for_bucket(&self.workload_id, &identifier).await;
 
         let mut storage = plugin.storage.write().await;
         let workload_storage = storage.entry(self.workload_id.to_string()).or_default();
 
         // Create bucket if it doesn't exist
-        if !workload_storage.contains_key(&identifier) {
-            let bucket_data = BucketData {
-                data: HashMap::new(),
-            };
-            workload_storage.insert(identifier.clone(), bucket_data);
-        }
+        workload_storage.entry(identifier.clone()).or_default();
 
-        let resource = self.table.push(identifier)?;
+        let resource = self.table.push(BucketHandle {
+            name: identifier,
+            ttl,
+        })?;
         Ok(Ok(resource))
     }
 }
@@ -91,7 +175,7 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
         bucket: Resource<BucketHandle>,
         key: String,
     ) -> wasmtime::Result<Result<Option<Vec<u8>>, StoreError>> {
-        let bucket_name = self.table.get(&bucket)?;
+        let bucket_name = &self.table.get(&bucket)?.name;
 
         let plugin = self.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
 
@@ -103,7 +187,7 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
 
         match workload_storage.get(bucket_name) {
             Some(bucket_data) => {
-                let value = bucket_data.data.get(&key).cloned();
+                let value = bucket_data.get(&key).cloned();
                 Ok(Ok(value))
             }
             None => Ok(Err(StoreError::Other(format!(
@@ -119,22 +203,7 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
         key: String,
         value: Vec<u8>,
     ) -> wasmtime::Result<Result<(), StoreError>> {
-        let bucket_name = self.table.get(&bucket)?;
-
-        let plugin = self.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
-
-        let mut storage = plugin.storage.write().await;
-        let workload_storage = storage.entry(self.workload_id.to_string()).or_default();
-
-        match workload_storage.get_mut(bucket_name) {
-            Some(bucket_data) => {
-                bucket_data.data.insert(key, value);
-                Ok(Ok(()))
-            }
-            None => Ok(Err(StoreError::Other(format!(
-                "bucket '{bucket_name}' does not exist"
-            )))),
-        }
+        put(self, &bucket, key, value, None).await
     }
 
     #[instrument(name = "wasi.keyvalue.delete", skip(self, bucket))]
@@ -143,7 +212,7 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
         bucket: Resource<BucketHandle>,
         key: String,
     ) -> wasmtime::Result<Result<(), StoreError>> {
-        let bucket_name = self.table.get(&bucket)?;
+        let bucket_name = &self.table.get(&bucket)?.name;
 
         let plugin = self.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
 
@@ -152,7 +221,7 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
 
         match workload_storage.get_mut(bucket_name) {
             Some(bucket_data) => {
-                bucket_data.data.remove(&key);
+                bucket_data.remove(&key);
                 Ok(Ok(()))
             }
             None => Ok(Err(StoreError::Other(format!(
@@ -167,7 +236,7 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
         bucket: Resource<BucketHandle>,
         key: String,
     ) -> wasmtime::Result<Result<bool, StoreError>> {
-        let bucket_name = self.table.get(&bucket)?;
+        let bucket_name = &self.table.get(&bucket)?.name;
 
         let plugin = self.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
 
@@ -178,7 +247,7 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
             .unwrap_or(&empty_map);
 
         match workload_storage.get(bucket_name) {
-            Some(bucket_data) => Ok(Ok(bucket_data.data.contains_key(&key))),
+            Some(bucket_data) => Ok(Ok(bucket_data.get(&key).is_some())),
             None => Ok(Err(StoreError::Other(format!(
                 "bucket '{bucket_name}' does not exist"
             )))),
@@ -191,7 +260,7 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
         bucket: Resource<BucketHandle>,
         cursor: Option<u64>,
     ) -> wasmtime::Result<Result<KeyResponse, StoreError>> {
-        let bucket_name = self.table.get(&bucket)?;
+        let bucket_name = &self.table.get(&bucket)?.name;
 
         let plugin = self.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
 
@@ -203,7 +272,12 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
 
         match workload_storage.get(bucket_name) {
             Some(bucket_data) => {
-                let mut keys: Vec<String> = bucket_data.data.keys().cloned().collect();
+                let mut keys: Vec<String> = bucket_data
+                    .data
+                    .keys()
+                    .filter(|key| bucket_data.get(key).is_some())
+                    .cloned()
+                    .collect();
                 keys.sort(); // Ensure consistent ordering
 
                 // Simple cursor-based pagination - cursor is the index from previous page
@@ -255,7 +329,10 @@ impl<'a> bindings::wasi::keyvalue::atomics::Host for ActiveCtx<'a> {
         key: String,
         delta: u64,
     ) -> wasmtime::Result<Result<u64, StoreError>> {
-        let bucket_name = self.table.get(&bucket)?;
+        let BucketHandle {
+            name: bucket_name,
+            ttl,
+        } = self.table.get(&bucket)?;
 
         let plugin = self.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
 
@@ -265,7 +342,7 @@ impl<'a> bindings::wasi::keyvalue::atomics::Host for ActiveCtx<'a> {
         match workload_storage.get_mut(bucket_name) {
             Some(bucket_data) => {
                 // Get current value, treating missing key as 0
-                let current_bytes = bucket_data.data.get(&key);
+                let current_bytes = bucket_data.get(&key);
                 let current_value = if let Some(bytes) = current_bytes {
                     // Try to parse as u64 from 8-byte array
                     if bytes.len() == 8 {
@@ -281,9 +358,7 @@ impl<'a> bindings::wasi::keyvalue::atomics::Host for ActiveCtx<'a> {
                 let new_value = current_value.saturating_add(delta);
 
                 // Store as 8-byte little-endian representation
-                bucket_data
-                    .data
-                    .insert(key, new_value.to_le_bytes().to_vec());
+                bucket_data.insert(key, new_value.to_le_bytes().to_vec(), *ttl);
 
                 Ok(Ok(new_value))
             }
@@ -303,7 +378,7 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
         bucket: Resource<BucketHandle>,
         keys: Vec<String>,
     ) -> wasmtime::Result<Result<Vec<Option<(String, Vec<u8>)>>, StoreError>> {
-        let bucket_name = self.table.get(&bucket)?;
+        let bucket_name = &self.table.get(&bucket)?.name;
 
         let plugin = self.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
 
@@ -317,13 +392,7 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
             Some(bucket_data) => {
                 let results: Vec<Option<(String, Vec<u8>)>> = keys
                     .into_iter()
-                    .map(|key| {
-                        bucket_data
-                            .data
-                            .get(&key)
-                            .cloned()
-                            .map(|value| (key, value))
-                    })
+                    .map(|key| bucket_data.get(&key).cloned().map(|value| (key, value)))
                     .collect();
                 Ok(Ok(results))
             }
@@ -339,7 +408,10 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
         bucket: Resource<BucketHandle>,
         key_values: Vec<(String, Vec<u8>)>,
     ) -> wasmtime::Result<Result<(), StoreError>> {
-        let bucket_name = self.table.get(&bucket)?;
+        let BucketHandle {
+            name: bucket_name,
+            ttl,
+        } = self.table.get(&bucket)?;
 
         let plugin = self.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
 
@@ -349,7 +421,7 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
         match workload_storage.get_mut(bucket_name) {
             Some(bucket_data) => {
                 for (key, value) in key_values {
-                    bucket_data.data.insert(key, value);
+                    bucket_data.insert(key, value, *ttl);
                 }
                 Ok(Ok(()))
             }
@@ -365,7 +437,7 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
         bucket: Resource<BucketHandle>,
         keys: Vec<String>,
     ) -> wasmtime::Result<Result<(), StoreError>> {
-        let bucket_name = self.table.get(&bucket)?;
+        let bucket_name = &self.table.get(&bucket)?.name;
 
         let plugin = self.try_get_plugin::<InMemoryKeyValue>(WASI_KEYVALUE_ID)?;
 
@@ -375,7 +447,7 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
         match workload_storage.get_mut(bucket_name) {
             Some(bucket_data) => {
                 for key in keys {
-                    bucket_data.data.remove(&key);
+                    bucket_data.remove(&key);
                 }
                 Ok(Ok(()))
             }
@@ -386,6 +458,23 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
     }
 }
 
+// Implementation for the key expiry extension
+impl<'a> bindings::wasmcloud::keyvalue0_1_0_draft::ttl::Host for ActiveCtx<'a> {
+    #[instrument(name = "wasmcloud.keyvalue.set_with_ttl", skip(self, bucket, value))]
+    async fn set_with_ttl(
+        &mut self,
+        bucket: Resource<BucketHandle>,
+        key: String,
+        value: Vec<u8>,
+        ttl_ms: u64,
+    ) -> wasmtime::Result<Result<(), StoreError>> {
+        match ttl::requested_ttl(ttl_ms) {
+            Ok(ttl) => put(self, &bucket, key, value, Some(ttl)).await,
+            Err(e) => Ok(Err(StoreError::Other(e))),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl HostPlugin for InMemoryKeyValue {
     fn id(&self) -> &'static str {
@@ -394,13 +483,38 @@ impl HostPlugin for InMemoryKeyValue {
 
     fn world(&self) -> WitWorld {
         WitWorld {
-            imports: HashSet::from([WitInterface::from(
-                "wasi:keyvalue/store,atomics,batch@0.2.0-draft",
-            )]),
+            imports: HashSet::from([
+                WitInterface::from("wasi:keyvalue/store,atomics,batch@0.2.0-draft"),
+                WitInterface::from("wasmcloud:keyvalue/ttl@0.1.0-draft"),
+            ]),
             ..Default::default()
         }
     }
 
+    async fn start(&self) -> anyhow::Result<()> {
+        let storage = self.storage.clone();
+        ttl::spawn_sweeper(self.sweeper.clone(), move || {
+            let storage = storage.clone();
+            async move {
+                let now = Instant::now();
+                for buckets in storage.write().await.values_mut() {
+                    for bucket in buckets.values_mut() {
+                        bucket.sweep(now);
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn on_workload_bind(
+        &self,
+        workload: &UnresolvedWorkload,
+        interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        self.ttls.bind(workload.id(), &interfaces).await
+    }
+
     async fn on_workload_item_bind<'a>(
         &self,
         component_handle: &mut WorkloadItem<'a>,
@@ -427,6 +541,12 @@ impl HostPlugin for InMemoryKeyValue {
             extract_active_ctx,
         )?;
         bindings::wasi::keyvalue::batch::add_to_linker::<_, SharedCtx>(linker, extract_active_ctx)?;
+        if interfaces.contains("wasmcloud", "keyvalue", &["ttl"]) {
+            bindings::wasmcloud::keyvalue0_1_0_draft::ttl::add_to_linker::<_, SharedCtx>(
+                linker,
+                extract_active_ctx,
+            )?;
+        }
 
         let id = component_handle.workload_id();
         tracing::debug!(
@@ -451,11 +571,17 @@ impl HostPlugin for InMemoryKeyValue {
         // Clean up storage for this workload
         let mut storage = self.storage.write().await;
         storage.remove(workload_id);
+        self.ttls.unbind(workload_id).await;
 
         tracing::debug!("WasiKeyvalue plugin unbound from workload '{workload_id}'");
 
         Ok(())
     }
+
+    async fn stop(&self) -> anyhow::Result<()> {
+        self.sweeper.cancel();
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -474,6 +600,7 @@ mod tests {
                 "default".to_string(),
                 BucketData {
                     data: HashMap::from([("key".to_string(), b"w1-value".to_vec())]),
+                    ..Default::default()
                 },
             );
 
@@ -482,6 +609,7 @@ mod tests {
                 "default".to_string(),
                 BucketData {
                     data: HashMap::from([("key".to_string(), b"w2-value".to_vec())]),
+                    ..Default::default()
                 },
             );
         }
@@ -500,12 +628,7 @@ mod tests {
         {
             let mut storage = kv.storage.write().await;
             let ws = storage.entry(workload.clone()).or_default();
-            ws.insert(
-                "my-bucket".to_string(),
-                BucketData {
-                    data: HashMap::new(),
-                },
-            );
+            ws.insert("my-bucket".to_string(), BucketData::default());
             ws.get_mut("my-bucket")
                 .unwrap()
                 .data
@@ -550,12 +673,14 @@ mod tests {
                 "bucket-a".to_string(),
                 BucketData {
                     data: HashMap::from([("k".to_string(), b"a".to_vec())]),
+                    ..Default::default()
                 },
             );
             ws.insert(
                 "bucket-b".to_string(),
                 BucketData {
                     data: HashMap::from([("k".to_string(), b"b".to_vec())]),
+                    ..Default::default()
                 },
             );
         }
@@ -7,14 +7,15 @@ mod multiplexed;
 mod multiplexed_async;
 mod nats;
 mod redis;
+mod ttl;
 
 pub use filesystem::FilesystemKeyValue;
 pub use in_memory::InMemoryKeyValue;
 #[cfg(feature = "wasm_component_model_implements")]
 pub use multiplexed::{
     FilesystemBackend, FilesystemProvider, InMemoryBackend, InMemoryProvider, KeyResponse,
-    KvBackend, KvId, KvProvider, MultiplexedKeyValue, NatsBackend, NatsProvider, RedisBackend,
-    RedisProvider, StoreError,
+    KvBackend, KvEvent, KvEventStream, KvId, KvProvider, MultiplexedKeyValue, NatsBackend,
+    NatsProvider, RedisBackend, RedisProvider, StoreError,
 };
 #[cfg(feature = "wasm_component_model_implements")]
 pub use multiplexed_async::MultiplexedAsyncKeyValue;
@@ -97,6 +97,78 @@ pub enum CasOutcome {
     Stale(Option<Versioned>),
 }
 
+/// A change observed on a watched bucket, delivered to a component exporting
+/// `wasmcloud:keyvalue/watcher` as `on-set` / `on-delete`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KvEvent {
+    /// `key` was written (set, batch set, increment, swap, ...) with `value`.
+    Set { key: String, value: Vec<u8> },
+    /// `key` was removed (delete, purge, or expiry where the backend reports it).
+    Delete { key: String },
+}
+
+/// The change stream returned by [`KvBackend::watch`]. Ends when the backend
+/// side of the feed goes away (connection lost, backend dropped).
+pub type KvEventStream = futures::stream::BoxStream<'static, KvEvent>;
+
+/// An in-process change feed for backends with no native notification
+/// mechanism (in-memory, filesystem). Every write through the backend is
+/// published here; [`ChangeFeed::subscribe`] yields the events of one bucket.
+///
+/// Backed by a bounded broadcast channel, so a watcher that falls behind skips
+/// the events it missed (logged) rather than stalling writers.
+pub(crate) struct ChangeFeed {
+    tx: tokio::sync::broadcast::Sender<(String, KvEvent)>,
+}
+
+impl Default for ChangeFeed {
+    fn default() -> Self {
+        Self {
+            tx: tokio::sync::broadcast::channel(CHANGE_FEED_CAPACITY).0,
+        }
+    }
+}
+
+impl ChangeFeed {
+    /// Publish a change to `bucket`. A no-op when nobody is watching.
+    pub(crate) fn publish(&self, bucket: &str, event: KvEvent) {
+        if self.tx.receiver_count() > 0 {
+            let _ = self.tx.send((bucket.to_string(), event));
+        }
+    }
+
+    /// Stream the changes made to `bucket` from now on.
+    pub(crate) fn subscribe(&self, bucket: &str) -> KvEventStream {
+        use futures::StreamExt as _;
+        use tokio::sync::broadcast::error::RecvError;
+
+        let bucket = bucket.to_string();
+        futures::stream::unfold(self.tx.subscribe(), move |mut rx| {
+            let bucket = bucket.clone();
+            async move {
+                loop {
+                    match rx.recv().await {
+                        Ok((b, event)) if b == bucket => return Some((event, rx)),
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(skipped)) => {
+                            tracing::warn!(
+                                bucket,
+                                skipped,
+                                "keyvalue watcher fell behind; change events were dropped"
+                            );
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+/// Events buffered per [`ChangeFeed`] before a slow watcher starts lagging.
+const CHANGE_FEED_CAPACITY: usize = 1024;
+
 /// A keyvalue backend (redis, NATS, in-memory, ...). Operations are keyed by
 /// the opened bucket `name` plus the per-call key(s); the backend owns its own
 /// connection/state. This is the unified surface that the per-interface host
@@ -175,6 +247,24 @@ pub trait KvBackend: Send + Sync {
             "compare-and-swap is not supported by this backend".to_string(),
         ))
     }
+
+    /// Whether every bucket opened on this backend reads the same keys, as on a
+    /// flat keyspace where the bucket name does not namespace them. A watch of
+    /// any one such bucket then sees every change, so a watcher subscribes to
+    /// only one of them. Default: buckets are separate.
+    fn buckets_share_keys(&self) -> bool {
+        false
+    }
+
+    /// Subscribe to changes made to `bucket` from now on (the feed behind
+    /// `wasmcloud:keyvalue/watcher`). Backends use their native notification
+    /// mechanism where one exists (NATS KV watch, redis keyspace notifications).
+    /// Default: watching is unsupported.
+    async fn watch(&self, _bucket: &str) -> Result<KvEventStream, StoreError> {
+        Err(StoreError::Other(
+            "watching is not supported by this backend".to_string(),
+        ))
+    }
 }
 
 use crate::engine::ctx::ActiveCtx;
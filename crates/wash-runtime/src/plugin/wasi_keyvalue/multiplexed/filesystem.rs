@@ -1,7 +1,9 @@
 //! Filesystem backend for the multiplexed `wasi:keyvalue` plugin.
 //!
 //! A thin [`KvBackend`] adapter over the shared [`FsKvStore`] — the same
-//! storage the standalone `FilesystemKeyValue` plugin uses.
+//! storage the standalone `FilesystemKeyValue` plugin uses. Writes made through
+//! the backend are published on an in-process [`ChangeFeed`] for watchers;
+//! files edited on disk behind the host's back are not observed.
 
 use std::collections::HashMap;
 use std::path::Path;
@@ -10,18 +12,23 @@ use std::sync::Arc;
 use crate::plugin::multiplex::BackendProvider;
 use crate::plugin::wasi_keyvalue::fs_store::{FsKvError, FsKvStore};
 
-use super::{KeyResponse, KvBackend, KvId, LIST_KEYS_BATCH_SIZE, StoreError};
+use super::{
+    ChangeFeed, KeyResponse, KvBackend, KvEvent, KvEventStream, KvId, LIST_KEYS_BATCH_SIZE,
+    StoreError,
+};
 
 /// A filesystem [`KvBackend`] rooted at a directory: buckets are subdirectories,
 /// keys are files (path-traversal guarded). Backed by the shared [`FsKvStore`].
 pub struct FilesystemBackend {
     store: FsKvStore,
+    changes: ChangeFeed,
 }
 
 impl FilesystemBackend {
     pub fn new(root: impl AsRef<Path>) -> Self {
         Self {
             store: FsKvStore::new(root),
+            changes: ChangeFeed::default(),
         }
     }
 }
@@ -50,13 +57,34 @@ impl KvBackend for FilesystemBackend {
 
     async fn set(&self, bucket: &str, key: &str, value: Vec<u8>) -> Result<(), StoreError> {
         self.store
-            .set(bucket, key, &value)
+            .set(bucket, key, &value, None)
             .await
-            .map_err(to_store_error)
+            .map_err(to_store_error)?;
+        self.changes.publish(
+            bucket,
+            KvEvent::Set {
+                key: key.to_string(),
+                value,
+            },
+        );
+        Ok(())
     }
 
     async fn delete(&self, bucket: &str, key: &str) -> Result<(), StoreError> {
-        self.store.delete(bucket, key).await.map_err(to_store_error)
+        if self
+            .store
+            .delete(bucket, key)
+            .await
+            .map_err(to_store_error)?
+        {
+            self.changes.publish(
+                bucket,
+                KvEvent::Delete {
+                    key: key.to_string(),
+                },
+            );
+        }
+        Ok(())
     }
 
     async fn exists(&self, bucket: &str, key: &str) -> Result<bool, StoreError> {
@@ -77,10 +105,20 @@ impl KvBackend for FilesystemBackend {
     }
 
     async fn increment(&self, bucket: &str, key: &str, delta: i64) -> Result<i64, StoreError> {
-        self.store
+        let next = self
+            .store
             .increment_signed(bucket, key, delta)
             .await
-            .map_err(to_store_error)
+            .map_err(to_store_error)?;
+        // The counter is stored as decimal text; report the bytes on disk.
+        self.changes.publish(
+            bucket,
+            KvEvent::Set {
+                key: key.to_string(),
+                value: next.to_string().into_bytes(),
+            },
+        );
+        Ok(next)
     }
 
     async fn get_many(
@@ -112,6 +150,14 @@ impl KvBackend for FilesystemBackend {
         }
         Ok(())
     }
+
+    async fn watch(&self, bucket: &str) -> Result<KvEventStream, StoreError> {
+        self.store
+            .create_bucket(bucket)
+            .await
+            .map_err(to_store_error)?;
+        Ok(self.changes.subscribe(bucket))
+    }
 }
 
 /// Provider for [`FilesystemBackend`], selected by `config.backend =
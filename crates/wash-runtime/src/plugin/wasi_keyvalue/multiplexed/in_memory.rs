@@ -15,7 +15,8 @@ use tokio::sync::RwLock;
 use crate::plugin::multiplex::BackendProvider;
 
 use super::{
-    CasGuard, CasOutcome, DEFAULT_BACKEND, KeyResponse, KvBackend, KvId, StoreError, Versioned,
+    CasGuard, CasOutcome, ChangeFeed, DEFAULT_BACKEND, KeyResponse, KvBackend, KvEvent,
+    KvEventStream, KvId, StoreError, Versioned,
 };
 
 /// One stored value plus the version stamped at its last write.
@@ -45,10 +46,12 @@ impl Bucket {
     }
 }
 
-/// An in-memory [`KvBackend`]. Each instance is an isolated store.
+/// An in-memory [`KvBackend`]. Each instance is an isolated store, with its own
+/// change feed for [`KvBackend::watch`].
 #[derive(Default)]
 pub struct InMemoryBackend {
     buckets: RwLock<HashMap<String, Bucket>>,
+    changes: ChangeFeed,
 }
 
 impl InMemoryBackend {
@@ -59,6 +62,25 @@ impl InMemoryBackend {
     fn err_missing(bucket: &str) -> StoreError {
         StoreError::Other(format!("bucket '{bucket}' does not exist"))
     }
+
+    fn publish_set(&self, bucket: &str, key: &str, value: &[u8]) {
+        self.changes.publish(
+            bucket,
+            KvEvent::Set {
+                key: key.to_string(),
+                value: value.to_vec(),
+            },
+        );
+    }
+
+    fn publish_delete(&self, bucket: &str, key: &str) {
+        self.changes.publish(
+            bucket,
+            KvEvent::Delete {
+                key: key.to_string(),
+            },
+        );
+    }
 }
 
 #[async_trait::async_trait]
@@ -83,6 +105,7 @@ impl KvBackend for InMemoryBackend {
         let b = store
             .get_mut(bucket)
             .ok_or_else(|| Self::err_missing(bucket))?;
+        self.publish_set(bucket, key, &value);
         b.put(key, value);
         Ok(())
     }
@@ -92,7 +115,9 @@ impl KvBackend for InMemoryBackend {
         let b = store
             .get_mut(bucket)
             .ok_or_else(|| Self::err_missing(bucket))?;
-        b.entries.remove(key);
+        if b.entries.remove(key).is_some() {
+            self.publish_delete(bucket, key);
+        }
         Ok(())
     }
 
@@ -145,7 +170,9 @@ impl KvBackend for InMemoryBackend {
         let next = current
             .checked_add(delta)
             .ok_or_else(|| StoreError::Other("counter overflow".to_string()))?;
-        b.put(key, next.to_le_bytes().to_vec());
+        let value = next.to_le_bytes().to_vec();
+        self.publish_set(bucket, key, &value);
+        b.put(key, value);
         Ok(next)
     }
 
@@ -172,6 +199,7 @@ impl KvBackend for InMemoryBackend {
             .get_mut(bucket)
             .ok_or_else(|| Self::err_missing(bucket))?;
         for (k, v) in key_values {
+            self.publish_set(bucket, &k, &v);
             b.put(&k, v);
         }
         Ok(())
@@ -183,7 +211,9 @@ impl KvBackend for InMemoryBackend {
             .get_mut(bucket)
             .ok_or_else(|| Self::err_missing(bucket))?;
         for k in keys {
-            b.entries.remove(&k);
+            if b.entries.remove(&k).is_some() {
+                self.publish_delete(bucket, &k);
+            }
         }
         Ok(())
     }
@@ -203,6 +233,7 @@ impl KvBackend for InMemoryBackend {
         if b.entries.contains_key(key) {
             return Ok(false);
         }
+        self.publish_set(bucket, key, &value);
         b.put(key, value);
         Ok(true)
     }
@@ -248,9 +279,17 @@ impl KvBackend for InMemoryBackend {
         {
             return Ok(stale());
         }
+        self.publish_set(bucket, key, &value);
         b.put(key, value);
         Ok(CasOutcome::Swapped)
     }
+
+    async fn watch(&self, bucket: &str) -> Result<KvEventStream, StoreError> {
+        if !self.buckets.read().await.contains_key(bucket) {
+            return Err(Self::err_missing(bucket));
+        }
+        Ok(self.changes.subscribe(bucket))
+    }
 }
 
 /// In-memory provider. Each named interface gets its own isolated store.
@@ -367,4 +406,48 @@ mod tests {
             .unwrap();
         assert!(be.increment("b", "max", 1).await.is_err());
     }
+
+    #[tokio::test]
+    async fn watch_reports_writes_to_the_watched_bucket_only() {
+        use futures::StreamExt as _;
+
+        let be = InMemoryBackend::new();
+        be.open("b").await.unwrap();
+        be.open("other").await.unwrap();
+        let mut events = be.watch("b").await.unwrap();
+
+        be.set("other", "ignored", b"x".to_vec()).await.unwrap();
+        be.set("b", "k", b"v".to_vec()).await.unwrap();
+        be.increment("b", "c", 2).await.unwrap();
+        // Deleting an absent key is not a change.
+        be.delete("b", "missing").await.unwrap();
+        be.delete("b", "k").await.unwrap();
+
+        assert_eq!(
+            events.next().await,
+            Some(KvEvent::Set {
+                key: "k".to_string(),
+                value: b"v".to_vec()
+            })
+        );
+        assert_eq!(
+            events.next().await,
+            Some(KvEvent::Set {
+                key: "c".to_string(),
+                value: 2i64.to_le_bytes().to_vec()
+            })
+        );
+        assert_eq!(
+            events.next().await,
+            Some(KvEvent::Delete {
+                key: "k".to_string()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn watch_rejects_unopened_bucket() {
+        let be = InMemoryBackend::new();
+        assert!(be.watch("nope").await.is_err());
+    }
 }
@@ -10,7 +10,8 @@ use tokio::sync::RwLock;
 use crate::plugin::multiplex::BackendProvider;
 
 use super::{
-    CasGuard, CasOutcome, KeyResponse, KvBackend, KvId, LIST_KEYS_BATCH_SIZE, StoreError, Versioned,
+    CasGuard, CasOutcome, KeyResponse, KvBackend, KvEvent, KvEventStream, KvId,
+    LIST_KEYS_BATCH_SIZE, StoreError, Versioned,
 };
 
 /// A NATS JetStream KV-backed [`KvBackend`]. Each bucket maps to a JetStream KV
@@ -296,6 +297,36 @@ impl KvBackend for NatsBackend {
             Err(e) => Err(Self::err(e)),
         }
     }
+
+    async fn watch(&self, bucket: &str) -> Result<KvEventStream, StoreError> {
+        use async_nats::jetstream::kv::Operation;
+        let s = self.store(bucket).await?;
+        // Native KV watch, delivering only updates made from now on. A watcher
+        // error (e.g. a missed sequence after a reconnect) is logged and skipped;
+        // the underlying ordered consumer recovers on its own.
+        let bucket = bucket.to_string();
+        let watch = s.watch_all().await.map_err(Self::err)?;
+        Ok(watch
+            .filter_map(move |entry| {
+                let event = match entry {
+                    Ok(e) => match e.operation {
+                        Operation::Put => Some(KvEvent::Set {
+                            key: e.key,
+                            value: e.value.to_vec(),
+                        }),
+                        Operation::Delete | Operation::Purge => {
+                            Some(KvEvent::Delete { key: e.key })
+                        }
+                    },
+                    Err(e) => {
+                        tracing::warn!(bucket, error = %e, "NATS KV watch error");
+                        None
+                    }
+                };
+                std::future::ready(event)
+            })
+            .boxed())
+    }
 }
 
 /// Read a key's current value + revision, treating a delete/purge tombstone as
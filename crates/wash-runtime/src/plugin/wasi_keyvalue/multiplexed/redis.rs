@@ -3,11 +3,19 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use futures::StreamExt as _;
 use redis::AsyncCommands;
 
 use crate::plugin::multiplex::BackendProvider;
 
-use super::{KeyResponse, KvBackend, KvId, LIST_KEYS_BATCH_SIZE, StoreError};
+use super::{
+    KeyResponse, KvBackend, KvEvent, KvEventStream, KvId, LIST_KEYS_BATCH_SIZE, StoreError,
+};
+
+/// Keyspace-notification classes [`KvBackend::watch`] needs: `K` (keyspace
+/// channel), `$` (string commands), `g` (generic: `DEL`, ...) and `x`/`e`
+/// (expired/evicted). `A` stands in for every class but `K`.
+const KEYSPACE_EVENT_FLAGS: &[char] = &['K', '$', 'g', 'x', 'e'];
 
 /// A redis-backed [`KvBackend`] over a **flat keyspace**, so it interoperates
 /// with a redis dataset created or managed outside this host:
@@ -21,6 +29,9 @@ use super::{KeyResponse, KvBackend, KvId, LIST_KEYS_BATCH_SIZE, StoreError};
 /// connection (pooled per url+prefix by the provider).
 pub struct RedisBackend {
     conn: redis::aio::MultiplexedConnection,
+    /// The client the connection came from; watchers open their own pub/sub
+    /// connection from it.
+    client: redis::Client,
     /// Optional operator-configured key prefix, default none.
     /// Lets a deployment namespace within a shared DB or match an external key
     /// convention. It is operator-set, not guest-controlled, so a guest cannot
@@ -53,6 +64,42 @@ impl RedisBackend {
         }
         out
     }
+
+    /// Check that the server publishes the keyspace notifications a watch
+    /// relies on. The server's configuration is the operator's to set (it is
+    /// shared with everything else using the server), so a missing flag fails
+    /// the watch rather than being turned on here. Managed redis deployments
+    /// often forbid `CONFIG`; when the setting can't be read the watch goes
+    /// ahead with a warning, since it may well be enabled.
+    async fn check_keyspace_events(&self) -> Result<(), StoreError> {
+        let mut conn = self.conn.clone();
+        let current: Result<(String, String), _> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg("notify-keyspace-events")
+            .query_async(&mut conn)
+            .await;
+        let flags = match current {
+            Ok((_, flags)) => flags,
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    "cannot read redis notify-keyspace-events; keyvalue watchers require 'K$gxe' to be enabled"
+                );
+                return Ok(());
+            }
+        };
+        let missing: String = KEYSPACE_EVENT_FLAGS
+            .iter()
+            .filter(|f| !flags.contains(**f) && (**f == 'K' || !flags.contains('A')))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(StoreError::Other(format!(
+            "redis keyspace notifications are not enabled: notify-keyspace-events is '{flags}' \
+             but keyvalue watchers need '{missing}' as well"
+        )))
+    }
 }
 
 #[async_trait::async_trait]
@@ -196,6 +243,62 @@ impl KvBackend for RedisBackend {
             .await
             .map_err(Self::err)
     }
+
+    fn buckets_share_keys(&self) -> bool {
+        true
+    }
+
+    async fn watch(&self, _bucket: &str) -> Result<KvEventStream, StoreError> {
+        // Like every other operation, the bucket name does not namespace keys:
+        // a watch covers the connection's keyspace under the operator prefix,
+        // which both the subscription pattern and the key check below enforce.
+        self.check_keyspace_events().await?;
+
+        let db = self.client.get_connection_info().redis.db;
+        let channel_prefix = format!("__keyspace@{db}__:");
+        let key_prefix = self.prefix.clone().unwrap_or_default();
+        let mut pubsub = self.client.get_async_pubsub().await.map_err(Self::err)?;
+        pubsub
+            .psubscribe(format!(
+                "{channel_prefix}{}*",
+                Self::glob_escape(&key_prefix)
+            ))
+            .await
+            .map_err(Self::err)?;
+
+        // A keyspace notification carries only the key and the command name, so
+        // a write is followed by a `GET` for the value. If the key is gone again
+        // by then, the set is skipped; its delete notification follows.
+        let conn = self.conn.clone();
+        let events = pubsub.into_on_message().filter_map(move |msg| {
+            let mut conn = conn.clone();
+            let key = msg
+                .get_channel_name()
+                .strip_prefix(channel_prefix.as_str())
+                .map(str::to_string);
+            let command = msg.get_payload::<String>().ok();
+            let key_prefix = key_prefix.clone();
+            async move {
+                let redis_key = key?;
+                let key = redis_key.strip_prefix(key_prefix.as_str())?.to_string();
+                match command?.as_str() {
+                    "del" | "expired" | "evicted" | "unlink" => Some(KvEvent::Delete { key }),
+                    "set" | "incrby" | "decrby" | "incr" | "decr" | "append" | "setrange" => {
+                        match conn.get::<_, Option<Vec<u8>>>(&redis_key).await {
+                            Ok(Some(value)) => Some(KvEvent::Set { key, value }),
+                            Ok(None) => None,
+                            Err(e) => {
+                                tracing::warn!(key, error = %e, "failed to read changed redis key");
+                                None
+                            }
+                        }
+                    }
+                    _ => None,
+                }
+            }
+        });
+        Ok(events.boxed())
+    }
 }
 
 /// Provider for [`RedisBackend`], selected by `config.backend = "redis"`.
@@ -224,6 +327,10 @@ impl BackendProvider<KvId> for RedisProvider {
         let client = redis::Client::open(url.as_str())?;
         let conn = client.get_multiplexed_async_connection().await?;
         let prefix = config.get("prefix").filter(|p| !p.is_empty()).cloned();
-        Ok(Arc::new(RedisBackend { conn, prefix }))
+        Ok(Arc::new(RedisBackend {
+            conn,
+            client,
+            prefix,
+        }))
     }
 }
@@ -24,19 +24,38 @@
 //!   unlike `wasi:keyvalue`'s unsigned counter).
 //! * **`list-keys`** maps the opaque string cursor to the backend's `u64` cursor
 //!   and filters by `prefix` host-side.
+//!
+//! ## Watchers
+//!
+//! A component that exports `wasmcloud:keyvalue/watcher` is invoked with
+//! `on-set` / `on-delete` whenever a key changes in a bucket it watches. The
+//! buckets are listed (comma-separated) in the `watch` config of a keyvalue host
+//! interface entry, and each entry's buckets are watched on that entry's
+//! backend through [`KvBackend::watch`](super::KvBackend::watch). Every change
+//! is delivered to a fresh instance, with an owned `bucket` handle opened on the
+//! same backend, and at most [`MAX_WATCH_DELIVERIES`] changes per component are
+//! delivered at once. On a backend whose buckets share one keyspace (redis),
+//! only the first of the buckets listed is watched, since it already sees
+//! every change.
 
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use futures::stream::StreamExt;
+use tokio::sync::RwLock;
+use tracing::{Instrument, debug, warn};
 use wasmtime::component::{Accessor, Resource};
+use wasmtime::error::Context as _;
 
 use crate::engine::ctx::{ActiveCtx, SharedCtx, extract_active_ctx};
-use crate::engine::workload::WorkloadItem;
+use crate::engine::workload::{ResolvedWorkload, WorkloadItem};
 use crate::plugin::multiplex::Multiplexer;
-use crate::plugin::{HostPlugin, WitInterfaces};
+use crate::plugin::{HostPlugin, WitInterfaces, WorkloadTracker};
 use crate::wit::{WitInterface, WitWorld};
 
-use super::multiplexed::{CasGuard, CasOutcome, KvBucket, KvId, KvProvider, StoreError, Versioned};
+use super::multiplexed::{
+    CasGuard, CasOutcome, KvBucket, KvEvent, KvId, KvProvider, StoreError, Versioned,
+};
 
 mod bindings {
     wasmtime::component::bindgen!({
@@ -55,11 +74,29 @@ mod bindings {
     });
 }
 
-use bindings::wasmcloud::keyvalue::cas::{CasOptions, CasResult, Entry};
-use bindings::wasmcloud::keyvalue::types::{Error as AsyncKvError, KeyResponse, SetOptions};
+/// Bindings for the host-invoked `wasmcloud:keyvalue/watcher` export. The
+/// `types` interface (and so the `bucket` resource) is shared with [`bindings`].
+mod watcher_bindings {
+    wasmtime::component::bindgen!({
+        world: "async-keyvalue-watcher",
+        exports: { default: async | store | tracing },
+        with: {
+            "wasmcloud:keyvalue/types": super::bindings::wasmcloud::keyvalue0_2_0::types,
+        },
+    });
+}
+
+use bindings::wasmcloud::keyvalue0_2_0::cas::{CasOptions, CasResult, Entry};
+use bindings::wasmcloud::keyvalue0_2_0::types::{Error as AsyncKvError, KeyResponse, SetOptions};
 
 const DEFAULT_BACKEND: &str = "in-memory";
 const MULTIPLEXED_ASYNC_KEYVALUE_ID: &str = "wasmcloud-keyvalue-multiplexed";
+/// Host-interface config key listing the buckets a watcher component watches.
+const WATCH_CONFIG: &str = "watch";
+
+/// Changes delivered to one watcher component at once. Past it, the component
+/// stops reading its watch streams until a delivery finishes.
+const MAX_WATCH_DELIVERIES: usize = 64;
 
 impl From<StoreError> for AsyncKvError {
     fn from(e: StoreError) -> Self {
@@ -84,7 +121,9 @@ fn bucket_ref<T>(
 // The `bucket` resource lives in the `types` interface (shared by store/atomics/
 // cas/batch), so its methods are bound standalone — the `KvBucket` carries the
 // backend it was opened through. Only `store.open` is label-routed.
-impl<T: 'static + Send> bindings::wasmcloud::keyvalue::types::HostBucketWithStore<T> for SharedCtx {
+impl<T: 'static + Send> bindings::wasmcloud::keyvalue0_2_0::types::HostBucketWithStore<T>
+    for SharedCtx
+{
     async fn get(
         accessor: &Accessor<T, Self>,
         self_: Resource<KvBucket>,
@@ -171,17 +210,17 @@ impl<T: 'static + Send> bindings::wasmcloud::keyvalue::types::HostBucketWithStor
     }
 }
 
-impl bindings::wasmcloud::keyvalue::types::HostBucket for ActiveCtx<'_> {
+impl bindings::wasmcloud::keyvalue0_2_0::types::HostBucket for ActiveCtx<'_> {
     async fn drop(&mut self, rep: Resource<KvBucket>) -> wasmtime::Result<()> {
         self.table.delete(rep)?;
         Ok(())
     }
 }
 
-impl bindings::wasmcloud::keyvalue::types::Host for ActiveCtx<'_> {}
+impl bindings::wasmcloud::keyvalue0_2_0::types::Host for ActiveCtx<'_> {}
 
 // `store` keeps only `open`, routed per `(implements ..)` label.
-impl<T: 'static + Send> bindings::named_imports::wasmcloud::keyvalue::store::HostWithStore<T>
+impl<T: 'static + Send> bindings::named_imports::wasmcloud::keyvalue0_2_0::store::HostWithStore<T>
     for SharedCtx
 {
     async fn open(
@@ -197,14 +236,14 @@ impl<T: 'static + Send> bindings::named_imports::wasmcloud::keyvalue::store::Hos
     }
 }
 
-impl bindings::named_imports::wasmcloud::keyvalue::store::Host for ActiveCtx<'_> {}
+impl bindings::named_imports::wasmcloud::keyvalue0_2_0::store::Host for ActiveCtx<'_> {}
 
 /// A plain (unlabeled) `store.open`: route to the workload's default backend
 /// (recorded on the multiplexer at bind) so a component that imports
 /// `wasmcloud:keyvalue/store` *without* an `(implements ..)` label still gets a
 /// working backend — no label required. The label-routed `open` above is
 /// identical but for taking its `KvId` from the label instead of the default.
-impl<T: 'static + Send> bindings::wasmcloud::keyvalue::store::HostWithStore<T> for SharedCtx {
+impl<T: 'static + Send> bindings::wasmcloud::keyvalue0_2_0::store::HostWithStore<T> for SharedCtx {
     async fn open(
         accessor: &Accessor<T, Self>,
         identifier: String,
@@ -220,7 +259,7 @@ impl<T: 'static + Send> bindings::wasmcloud::keyvalue::store::HostWithStore<T> f
     }
 }
 
-impl bindings::wasmcloud::keyvalue::store::Host for ActiveCtx<'_> {}
+impl bindings::wasmcloud::keyvalue0_2_0::store::Host for ActiveCtx<'_> {}
 
 /// The workload's default `wasmcloud:keyvalue` backend for a PLAIN (unlabeled)
 /// import, recorded on the multiplexer at bind. `None` when the workload
@@ -247,7 +286,9 @@ fn no_default_backend<T2>() -> wasmtime::Result<Result<T2, AsyncKvError>> {
     )))
 }
 
-impl<T: 'static + Send> bindings::wasmcloud::keyvalue::atomics::HostWithStore<T> for SharedCtx {
+impl<T: 'static + Send> bindings::wasmcloud::keyvalue0_2_0::atomics::HostWithStore<T>
+    for SharedCtx
+{
     async fn increment(
         accessor: &Accessor<T, Self>,
         bucket: Resource<KvBucket>,
@@ -265,9 +306,9 @@ impl<T: 'static + Send> bindings::wasmcloud::keyvalue::atomics::HostWithStore<T>
     }
 }
 
-impl bindings::wasmcloud::keyvalue::atomics::Host for ActiveCtx<'_> {}
+impl bindings::wasmcloud::keyvalue0_2_0::atomics::Host for ActiveCtx<'_> {}
 
-impl<T: 'static + Send> bindings::wasmcloud::keyvalue::cas::HostWithStore<T> for SharedCtx {
+impl<T: 'static + Send> bindings::wasmcloud::keyvalue0_2_0::cas::HostWithStore<T> for SharedCtx {
     async fn current(
         accessor: &Accessor<T, Self>,
         bucket: Resource<KvBucket>,
@@ -318,9 +359,9 @@ fn into_entry(v: Versioned) -> Entry {
     }
 }
 
-impl bindings::wasmcloud::keyvalue::cas::Host for ActiveCtx<'_> {}
+impl bindings::wasmcloud::keyvalue0_2_0::cas::Host for ActiveCtx<'_> {}
 
-impl<T: 'static + Send> bindings::wasmcloud::keyvalue::batch::HostWithStore<T> for SharedCtx {
+impl<T: 'static + Send> bindings::wasmcloud::keyvalue0_2_0::batch::HostWithStore<T> for SharedCtx {
     #[allow(clippy::type_complexity)]
     async fn get_many(
         accessor: &Accessor<T, Self>,
@@ -353,13 +394,66 @@ impl<T: 'static + Send> bindings::wasmcloud::keyvalue::batch::HostWithStore<T> f
     }
 }
 
-impl bindings::wasmcloud::keyvalue::batch::Host for ActiveCtx<'_> {}
+impl bindings::wasmcloud::keyvalue0_2_0::batch::Host for ActiveCtx<'_> {}
+
+/// Returns `true` if the world exports `wasmcloud:keyvalue/watcher` at any
+/// version.
+fn exports_watcher(world: &WitWorld) -> bool {
+    let watcher = WitInterface::from("wasmcloud:keyvalue/watcher");
+    world.exports.iter().any(|e| e.contains(&watcher))
+}
+
+/// Parses a comma-separated `watch` config value into trimmed, non-empty bucket
+/// names.
+fn parse_watch_buckets(raw: Option<&str>) -> Vec<String> {
+    raw.map(|s| {
+        s.split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Drops every watch after the first on a backend whose buckets share their
+/// keys (see [`KvBackend::buckets_share_keys`](super::KvBackend::buckets_share_keys)),
+/// which would otherwise deliver each change once per bucket.
+fn without_shared_keyspaces(
+    component_id: &str,
+    watches: Vec<(KvId, String)>,
+) -> Vec<(KvId, String)> {
+    let mut kept: Vec<(KvId, String)> = Vec::with_capacity(watches.len());
+    for (backend, bucket) in watches {
+        if backend.buckets_share_keys()
+            && let Some((_, watched)) = kept.iter().find(|(b, _)| Arc::ptr_eq(b, &backend))
+        {
+            warn!(
+                component_id,
+                bucket,
+                watched,
+                "buckets on this keyvalue backend share one keyspace; not watching it twice"
+            );
+            continue;
+        }
+        kept.push((backend, bucket));
+    }
+    kept
+}
+
+/// Per-component watcher state: the `(backend, bucket)` pairs it watches and
+/// the delivery task started once the workload resolves.
+struct WatcherData {
+    watches: Vec<(KvId, String)>,
+    cancel_token: tokio_util::sync::CancellationToken,
+    task_handle: Option<tokio::task::JoinHandle<()>>,
+}
 
 /// A keyvalue [`HostPlugin`] that multiplexes async `wasmcloud:keyvalue` across
 /// backends selected per `(implements ..)` import. Shares the [`KvBackend`]
 /// providers with [`super::multiplexed::MultiplexedKeyValue`].
 pub struct MultiplexedAsyncKeyValue {
     mux: Multiplexer<KvId>,
+    watchers: Arc<RwLock<WorkloadTracker<(), WatcherData>>>,
 }
 
 impl Default for MultiplexedAsyncKeyValue {
@@ -372,6 +466,7 @@ impl MultiplexedAsyncKeyValue {
     pub fn new() -> Self {
         Self {
             mux: Multiplexer::new("wasmcloud", "keyvalue", DEFAULT_BACKEND),
+            watchers: Arc::default(),
         }
     }
 
@@ -402,7 +497,7 @@ impl HostPlugin for MultiplexedAsyncKeyValue {
             imports: HashSet::from([WitInterface::from(
                 "wasmcloud:keyvalue/store,atomics,cas,batch@0.2.0",
             )]),
-            ..Default::default()
+            exports: HashSet::from([WitInterface::from("wasmcloud:keyvalue/watcher@0.2.0")]),
         }
     }
 
@@ -448,7 +543,7 @@ impl HostPlugin for MultiplexedAsyncKeyValue {
         // a `bucket` whose `KvBucket` already carries the backend it was opened
         // through, so they route via the resource rather than a label.
         if has_labeled {
-            bindings::named_imports::wasmcloud::keyvalue::store::add_to_linker::<_, SharedCtx>(
+            bindings::named_imports::wasmcloud::keyvalue0_2_0::store::add_to_linker::<_, SharedCtx>(
                 linker,
                 &component,
                 |name| self.mux.resolve(&registry, name),
@@ -458,27 +553,219 @@ impl HostPlugin for MultiplexedAsyncKeyValue {
         // A plain (unlabeled) `store` import: bind the standard interface to the
         // workload's default backend — no label required.
         if has_plain {
-            bindings::wasmcloud::keyvalue::store::add_to_linker::<_, SharedCtx>(
+            bindings::wasmcloud::keyvalue0_2_0::store::add_to_linker::<_, SharedCtx>(
                 linker,
                 extract_active_ctx,
             )?;
         }
-        bindings::wasmcloud::keyvalue::types::add_to_linker::<_, SharedCtx>(
+        bindings::wasmcloud::keyvalue0_2_0::types::add_to_linker::<_, SharedCtx>(
             linker,
             extract_active_ctx,
         )?;
-        bindings::wasmcloud::keyvalue::atomics::add_to_linker::<_, SharedCtx>(
+        bindings::wasmcloud::keyvalue0_2_0::atomics::add_to_linker::<_, SharedCtx>(
             linker,
             extract_active_ctx,
         )?;
-        bindings::wasmcloud::keyvalue::cas::add_to_linker::<_, SharedCtx>(
+        bindings::wasmcloud::keyvalue0_2_0::cas::add_to_linker::<_, SharedCtx>(
             linker,
             extract_active_ctx,
         )?;
-        bindings::wasmcloud::keyvalue::batch::add_to_linker::<_, SharedCtx>(
+        bindings::wasmcloud::keyvalue0_2_0::batch::add_to_linker::<_, SharedCtx>(
             linker,
             extract_active_ctx,
         )?;
+
+        // Each keyvalue entry's `watch` buckets are watched on that entry's
+        // backend. Only components are invoked per change; a long-lived service
+        // has no per-event instance to call.
+        if let WorkloadItem::Component(component) = item
+            && exports_watcher(&component.world())
+        {
+            let watches: Vec<(KvId, String)> = interfaces
+                .iter()
+                .filter(|i| i.namespace == "wasmcloud" && i.package == "keyvalue")
+                .flat_map(|i| {
+                    let backend = registry.get(i.name.as_deref().unwrap_or_default());
+                    parse_watch_buckets(i.config.get(WATCH_CONFIG).map(String::as_str))
+                        .into_iter()
+                        .filter_map(move |bucket| backend.map(|b| (b.clone(), bucket)))
+                })
+                .collect();
+            let watches = without_shared_keyspaces(component.id(), watches);
+            if watches.is_empty() {
+                warn!(
+                    component_id = component.id(),
+                    "component exports wasmcloud:keyvalue/watcher but no `{WATCH_CONFIG}` buckets are configured"
+                );
+            } else {
+                debug!(
+                    component_id = component.id(),
+                    buckets = ?watches.iter().map(|(_, b)| b).collect::<Vec<_>>(),
+                    "tracking keyvalue watcher component"
+                );
+                self.watchers.write().await.add_component(
+                    component,
+                    WatcherData {
+                        watches,
+                        cancel_token: tokio_util::sync::CancellationToken::new(),
+                        task_handle: None,
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn on_workload_resolved(
+        &self,
+        workload: &ResolvedWorkload,
+        component_id: &str,
+    ) -> anyhow::Result<()> {
+        let (watches, cancel_token) = {
+            let lock = self.watchers.read().await;
+            match lock.get_component_data(component_id) {
+                Some(data) => (data.watches.clone(), data.cancel_token.clone()),
+                None => return Ok(()),
+            }
+        };
+
+        let pre = watcher_bindings::AsyncKeyvalueWatcherPre::new(
+            workload.instantiate_pre(component_id).await?,
+        )
+        .context("failed to instantiate keyvalue watcher pre")?;
+
+        // Subscribe before returning so no change made after the workload
+        // reports running is missed.
+        let mut streams = Vec::with_capacity(watches.len());
+        for (backend, bucket) in watches {
+            backend
+                .open(&bucket)
+                .await
+                .map_err(|e| anyhow::anyhow!("failed to open watched bucket '{bucket}': {e:?}"))?;
+            let events = backend
+                .watch(&bucket)
+                .await
+                .map_err(|e| anyhow::anyhow!("failed to watch bucket '{bucket}': {e:?}"))?;
+            streams.push(
+                events
+                    .map(move |event| (backend.clone(), bucket.clone(), event))
+                    .boxed(),
+            );
+        }
+        let mut events = futures::stream::select_all(streams);
+
+        let workload = workload.clone();
+        let component_id = component_id.to_string();
+        let tracker_component_id = component_id.clone();
+        let span = tracing::Span::current();
+        let deliveries = Arc::new(tokio::sync::Semaphore::new(MAX_WATCH_DELIVERIES));
+        let handle = tokio::spawn(async move {
+            loop {
+                // Wait for a free delivery slot before reading on, so a slow
+                // watcher pushes back on its streams instead of piling up tasks.
+                let slot = tokio::select! {
+                    slot = Arc::clone(&deliveries).acquire_owned() => match slot {
+                        Ok(slot) => slot,
+                        Err(_) => break,
+                    },
+                    _ = cancel_token.cancelled() => {
+                        debug!(parent: &span, component_id = %component_id, "keyvalue watcher loop cancelled");
+                        break;
+                    }
+                };
+                tokio::select! {
+                    next = events.next() => {
+                        let Some((backend, bucket, event)) = next else {
+                            warn!(
+                                parent: &span,
+                                component_id = %component_id,
+                                "keyvalue watch streams closed; watcher will stop receiving changes"
+                            );
+                            break;
+                        };
+                        let mut store = match workload.new_store(&component_id).await {
+                            Ok(store) => store,
+                            Err(e) => {
+                                warn!(component_id = %component_id, error = %e, "failed to create store for keyvalue watcher");
+                                continue;
+                            }
+                        };
+                        let watcher = match pre.instantiate_async(&mut store).await {
+                            Ok(watcher) => watcher,
+                            Err(e) => {
+                                warn!(component_id = %component_id, error = %e, "failed to instantiate keyvalue watcher");
+                                continue;
+                            }
+                        };
+                        let call_span = tracing::span!(
+                            tracing::Level::INFO,
+                            "keyvalue_watch_event",
+                            bucket = %bucket,
+                        );
+                        tokio::spawn(
+                            async move {
+                                let _slot = slot;
+                                let result = store
+                                    .run_concurrent(async move |accessor| {
+                                        let handle = accessor.with(|mut access| {
+                                            access.get().table.push(KvBucket::new(backend, bucket))
+                                        })?;
+                                        let watcher = watcher.wasmcloud_keyvalue0_2_0_watcher();
+                                        match event {
+                                            KvEvent::Set { key, value } => {
+                                                watcher.call_on_set(accessor, handle, key, value).await
+                                            }
+                                            KvEvent::Delete { key } => {
+                                                watcher.call_on_delete(accessor, handle, key).await
+                                            }
+                                        }
+                                    })
+                                    .await;
+                                match result {
+                                    Ok(Ok(())) => debug!("keyvalue watcher handled change"),
+                                    Ok(Err(e)) | Err(e) => {
+                                        warn!(error = %e, "keyvalue watcher failed to handle change")
+                                    }
+                                }
+                            }
+                            .instrument(call_span),
+                        );
+                    }
+                    _ = cancel_token.cancelled() => {
+                        debug!(parent: &span, component_id = %component_id, "keyvalue watcher loop cancelled");
+                        break;
+                    }
+                }
+            }
+        });
+
+        let mut lock = self.watchers.write().await;
+        match lock.get_component_data_mut(&tracker_component_id) {
+            Some(data) => data.task_handle = Some(handle),
+            None => handle.abort(),
+        }
+        Ok(())
+    }
+
+    async fn on_workload_unbind(
+        &self,
+        workload_id: &str,
+        _interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        self.watchers
+            .write()
+            .await
+            .remove_workload_with_cleanup(
+                workload_id,
+                |_| async {},
+                |data: WatcherData| async move {
+                    data.cancel_token.cancel();
+                    if let Some(handle) = data.task_handle {
+                        handle.abort();
+                    }
+                },
+            )
+            .await;
         Ok(())
     }
 }
@@ -502,6 +789,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_watch_buckets() {
+        assert_eq!(
+            parse_watch_buckets(Some(" sessions, cache ,,")),
+            vec!["sessions".to_string(), "cache".to_string()]
+        );
+        assert!(parse_watch_buckets(None).is_empty());
+    }
+
+    #[test]
+    fn recognizes_exported_watcher() {
+        let watcher = WitWorld {
+            imports: HashSet::new(),
+            exports: HashSet::from([WitInterface::from("wasmcloud:keyvalue/watcher@0.2.0")]),
+        };
+        assert!(exports_watcher(&watcher));
+
+        let importer = WitWorld {
+            imports: HashSet::from([WitInterface::from("wasmcloud:keyvalue/store@0.2.0")]),
+            exports: HashSet::new(),
+        };
+        assert!(!exports_watcher(&importer));
+    }
+
     #[test]
     fn store_error_maps_to_async_variant() {
         assert!(matches!(
@@ -3,15 +3,20 @@
 //! This module implements `wasi:keyvalue@0.2.0-draft` interfaces using
 //! NATS JetStream as the backend storage.
 //! Atomics are stored in Network Byte Order (big-endian) format.
+//! Key expiry (see [`super::ttl`]) uses JetStream per-message TTLs, which need
+//! NATS 2.11 or newer.
 
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 
+use async_nats::jetstream::context::CreateKeyValueErrorKind;
 use bytes::{Buf, Bytes};
 
 const PLUGIN_KEYVALUE_ID: &str = "wasi-keyvalue";
 use crate::engine::ctx::{ActiveCtx, SharedCtx, extract_active_ctx};
-use crate::engine::workload::WorkloadItem;
+use crate::engine::workload::{UnresolvedWorkload, WorkloadItem};
+use crate::plugin::wasi_keyvalue::ttl::{self, WorkloadTtls};
 use crate::plugin::{HostPlugin, WitInterfaces};
 use crate::wit::{WitInterface, WitWorld};
 use futures::StreamExt;
@@ -20,6 +25,10 @@ use wasmtime::component::Resource;
 
 const LIST_KEYS_BATCH_SIZE: usize = 1000;
 
+/// How long JetStream keeps the marker it leaves when a key expires, so
+/// watchers see the expiry as a delete.
+const LIMIT_MARKER_TTL: Duration = Duration::from_secs(60);
+
 mod bindings {
     wasmtime::component::bindgen!({
         world: "keyvalue",
@@ -73,6 +82,73 @@ where
 /// Resource representation for a bucket (key-value store)
 pub struct BucketHandle {
     kv: async_nats::jetstream::kv::Store,
+    js: Arc<async_nats::jetstream::Context>,
+    /// TTL of writes that don't pick their own
+    ttl: Option<Duration>,
+}
+
+impl BucketHandle {
+    /// Write `key`, expiring it after `ttl` if one is given. With a `revision`
+    /// the write only succeeds if that is still the key's latest revision.
+    ///
+    /// `Store` has no TTL'd put, so those writes are published to the key's
+    /// subject with a `Nats-TTL` header, the way `Store::put` publishes.
+    async fn write(
+        &self,
+        key: &str,
+        value: Bytes,
+        ttl: Option<Duration>,
+        revision: Option<u64>,
+    ) -> Result<(), async_nats::Error> {
+        let Some(ttl) = ttl else {
+            match revision {
+                Some(revision) => self.kv.update(key, value, revision).await?,
+                None => self.kv.put(key, value).await?,
+            };
+            return Ok(());
+        };
+        if !is_valid_key(key) {
+            return Err("invalid key".into());
+        }
+
+        let subject = format!(
+            "{}{key}",
+            self.kv.put_prefix.as_ref().unwrap_or(&self.kv.prefix)
+        );
+        let mut headers = async_nats::HeaderMap::new();
+        headers.insert(
+            async_nats::header::NATS_MESSAGE_TTL,
+            async_nats::HeaderValue::from(ttl_secs(ttl)),
+        );
+        if let Some(revision) = revision {
+            headers.insert(
+                async_nats::header::NATS_EXPECTED_LAST_SUBJECT_SEQUENCE,
+                async_nats::HeaderValue::from(revision),
+            );
+        }
+        self.js
+            .publish_with_headers(subject, headers, value)
+            .await?
+            .await?;
+        Ok(())
+    }
+}
+
+/// A TTL in the whole seconds `Nats-TTL` takes, rounded up so a key never
+/// expires early.
+fn ttl_secs(ttl: Duration) -> u64 {
+    ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0)
+}
+
+/// Whether JetStream accepts `key` as a KV key. `Store::put` checks this itself,
+/// but the check isn't public and TTL'd writes bypass `put`.
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with('.')
+        && !key.ends_with('.')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '/' | '_' | '=' | '.'))
 }
 
 /// Memory-based keyvalue plugin
@@ -80,6 +156,7 @@ pub struct BucketHandle {
 pub struct NatsKeyValue {
     client: Arc<async_nats::jetstream::Context>,
     metrics: Arc<WasiKeyvalueMetrics>,
+    ttls: WorkloadTtls,
 }
 
 struct WasiKeyvalueMetrics {
@@ -103,6 +180,7 @@ impl NatsKeyValue {
         Self {
             client: async_nats::jetstream::new(client.clone()).into(),
             metrics: Arc::new(metrics),
+            ttls: WorkloadTtls::default(),
         }
     }
 
@@ -123,9 +201,13 @@ impl NatsKeyValue {
     /// opens racing here all succeed. Verified by
     /// `tests::test_reopening_bucket_preserves_entries`.
     ///
-    /// Any error is therefore a genuine failure (permission, connection,
-    /// JetStream disabled, or a pre-existing bucket created with a *different*
-    /// config) and is surfaced rather than masked.
+    /// Buckets are created with limit markers, which turns on the per-message
+    /// TTLs key expiry relies on; on a server too old for them the bucket is
+    /// created without. A bucket that already exists with a *different* config
+    /// (such as one created before key expiry, without limit markers) fails the
+    /// create, and is then opened as it is; TTL'd writes to it fail with a
+    /// JetStream error. Any other error is a genuine failure (permission,
+    /// connection, JetStream disabled) and is surfaced rather than masked.
     ///
     /// Note this means `open` requires stream-create permission even for an
     /// already-existing bucket. That is fine today because the NATS connection —
@@ -137,20 +219,40 @@ impl NatsKeyValue {
         &self,
         identifier: &str,
     ) -> Result<async_nats::jetstream::kv::Store, String> {
-        self.client
-            .create_key_value(async_nats::jetstream::kv::Config {
-                bucket: identifier.to_string(),
-                ..Default::default()
-            })
-            .await
-            .map_err(|e| {
-                tracing::error!(
-                    error = ?e,
-                    bucket = %identifier,
-                    "Failed to open keyvalue bucket in JetStream"
-                );
-                format!("failed to open keyvalue bucket in JetStream({identifier}): {e}")
-            })
+        let config = async_nats::jetstream::kv::Config {
+            bucket: identifier.to_string(),
+            limit_markers: Some(LIMIT_MARKER_TTL),
+            ..Default::default()
+        };
+        let created = match self.client.create_key_value(config.clone()).await {
+            Err(e) if e.kind() == CreateKeyValueErrorKind::LimitMarkersNotSupported => {
+                self.client
+                    .create_key_value(async_nats::jetstream::kv::Config {
+                        limit_markers: None,
+                        ..config
+                    })
+                    .await
+            }
+            created => created,
+        };
+        let error = match created {
+            Ok(store) => return Ok(store),
+            Err(e) if e.kind() == CreateKeyValueErrorKind::BucketCreate => {
+                match self.client.get_key_value(identifier).await {
+                    Ok(store) => return Ok(store),
+                    Err(_) => e,
+                }
+            }
+            Err(e) => e,
+        };
+        tracing::error!(
+            error = ?error,
+            bucket = %identifier,
+            "Failed to open keyvalue bucket in JetStream"
+        );
+        Err(format!(
+            "failed to open keyvalue bucket in JetStream({identifier}): {error}"
+        ))
     }
 }
 
@@ -170,7 +272,12 @@ impl<'a> bindings::wasi::keyvalue::store::Host for ActiveCtx<'a> {
             Err(e) => return Ok(Err(StoreError::Other(e))),
         };
 
-        let bucket = BucketHandle { kv };
+        let ttl = plugin.ttls.for_bucket(&self.workload_id, &identifier).await;
+        let bucket = BucketHandle {
+            kv,
+            js: plugin.client.clone(),
+            ttl,
+        };
 
         let resource = self.table.push(bucket)?;
         Ok(Ok(resource))
@@ -218,8 +325,11 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
 
         let bucket_handle = self.table.get(&bucket)?;
 
-        match bucket_handle.kv.put(key, value.into()).await {
-            Ok(_) => Ok(Ok(())),
+        match bucket_handle
+            .write(&key, value.into(), bucket_handle.ttl, None)
+            .await
+        {
+            Ok(()) => Ok(Ok(())),
             Err(e) => {
                 tracing::error!("JetStream error setting key: {e}");
                 Ok(Err(StoreError::Other(format!("JetStream error: {e}"))))
@@ -352,26 +462,14 @@ impl<'a> bindings::wasi::keyvalue::atomics::Host for ActiveCtx<'a> {
         // Here's were CAS happens
         // If we have a revision, we try to update the entry with it
         // If we don't have a revision, we try to create the entry
-        match entry_revision {
-            Some(rev) => {
-                let res = bucket_handle.kv.update(&key, entry_bytes, rev).await;
-                match res {
-                    Ok(_) => Ok(Ok(new_value)),
-                    Err(e) => {
-                        tracing::error!("JetStream error updating key: {e}");
-                        Ok(Err(StoreError::Other(format!("JetStream error: {e}"))))
-                    }
-                }
-            }
-            None => {
-                let res = bucket_handle.kv.put(key.clone(), entry_bytes).await;
-                match res {
-                    Ok(_) => Ok(Ok(new_value)),
-                    Err(e) => {
-                        tracing::error!("JetStream error putting key: {e}");
-                        Ok(Err(StoreError::Other(format!("JetStream error: {e}"))))
-                    }
-                }
+        let res = bucket_handle
+            .write(&key, entry_bytes, bucket_handle.ttl, entry_revision)
+            .await;
+        match res {
+            Ok(()) => Ok(Ok(new_value)),
+            Err(e) => {
+                tracing::error!("JetStream error updating key: {e}");
+                Ok(Err(StoreError::Other(format!("JetStream error: {e}"))))
             }
         }
     }
@@ -432,11 +530,10 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
         let values = futures::stream::FuturesOrdered::from_iter(key_values.iter().map(
             |(key, value)| async {
                 match bucket_handle
-                    .kv
-                    .put(key.clone(), value.to_vec().into())
+                    .write(key, value.to_vec().into(), bucket_handle.ttl, None)
                     .await
                 {
-                    Ok(_) => Ok(()),
+                    Ok(()) => Ok(()),
                     Err(e) => {
                         tracing::error!("JetStream error putting key: {e}");
                         Err(StoreError::Other(format!("JetStream error: {e}")))
@@ -492,6 +589,39 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
     }
 }
 
+// Implementation for the key expiry extension
+impl<'a> bindings::wasmcloud::keyvalue0_1_0_draft::ttl::Host for ActiveCtx<'a> {
+    #[instrument(name = "wasmcloud.keyvalue.set_with_ttl", skip(self, bucket, value))]
+    async fn set_with_ttl(
+        &mut self,
+        bucket: Resource<BucketHandle>,
+        key: String,
+        value: Vec<u8>,
+        ttl_ms: u64,
+    ) -> wasmtime::Result<Result<(), StoreError>> {
+        let plugin = self.try_get_plugin::<NatsKeyValue>(PLUGIN_KEYVALUE_ID)?;
+
+        plugin.record_operation("set_with_ttl");
+
+        let ttl = match ttl::requested_ttl(ttl_ms) {
+            Ok(ttl) => ttl,
+            Err(e) => return Ok(Err(StoreError::Other(e))),
+        };
+        let bucket_handle = self.table.get(&bucket)?;
+
+        match bucket_handle
+            .write(&key, value.into(), Some(ttl), None)
+            .await
+        {
+            Ok(()) => Ok(Ok(())),
+            Err(e) => {
+                tracing::error!("JetStream error setting key: {e}");
+                Ok(Err(StoreError::Other(format!("JetStream error: {e}"))))
+            }
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl HostPlugin for NatsKeyValue {
     fn id(&self) -> &'static str {
@@ -500,11 +630,22 @@ impl HostPlugin for NatsKeyValue {
 
     fn world(&self) -> WitWorld {
         WitWorld {
-            imports: HashSet::from([WitInterface::from("wasi:keyvalue/store,atomics,batch")]),
+            imports: HashSet::from([
+                WitInterface::from("wasi:keyvalue/store,atomics,batch"),
+                WitInterface::from("wasmcloud:keyvalue/ttl@0.1.0-draft"),
+            ]),
             ..Default::default()
         }
     }
 
+    async fn on_workload_bind(
+        &self,
+        workload: &UnresolvedWorkload,
+        interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        self.ttls.bind(workload.id(), &interfaces).await
+    }
+
     async fn on_workload_item_bind<'a>(
         &self,
         component_handle: &mut WorkloadItem<'a>,
@@ -531,6 +672,12 @@ impl HostPlugin for NatsKeyValue {
             extract_active_ctx,
         )?;
         bindings::wasi::keyvalue::batch::add_to_linker::<_, SharedCtx>(linker, extract_active_ctx)?;
+        if interfaces.contains("wasmcloud", "keyvalue", &["ttl"]) {
+            bindings::wasmcloud::keyvalue0_1_0_draft::ttl::add_to_linker::<_, SharedCtx>(
+                linker,
+                extract_active_ctx,
+            )?;
+        }
 
         let id = component_handle.id();
         tracing::debug!(
@@ -548,6 +695,7 @@ impl HostPlugin for NatsKeyValue {
         workload_id: &str,
         _interfaces: WitInterfaces<'_>,
     ) -> anyhow::Result<()> {
+        self.ttls.unbind(workload_id).await;
         tracing::debug!("WasiKeyvalue plugin unbound from workload '{workload_id}'");
 
         Ok(())
@@ -666,6 +814,24 @@ mod tests {
             .expect_err("must surface the error even though it was skipped over");
         assert!(err.contains("connection reset"));
     }
+
+    #[test]
+    fn ttl_rounds_up_to_whole_seconds() {
+        assert_eq!(ttl_secs(Duration::from_secs(5)), 5);
+        assert_eq!(ttl_secs(Duration::from_millis(1)), 1);
+        assert_eq!(ttl_secs(Duration::from_millis(2500)), 3);
+    }
+
+    #[test]
+    fn ttl_writes_check_keys_like_store_put() {
+        assert!(is_valid_key("user/42=active_v-1.json"));
+        assert!(!is_valid_key(""));
+        assert!(!is_valid_key(".hidden"));
+        assert!(!is_valid_key("trailing."));
+        assert!(!is_valid_key("with space"));
+        assert!(!is_valid_key("wild*card"));
+    }
+
     use testcontainers::{
         ContainerAsync, GenericImage, ImageExt,
         core::{IntoContainerPort, WaitFor},
@@ -736,4 +902,44 @@ mod tests {
 
         Ok(())
     }
+
+    /// A TTL'd write is readable until its TTL runs out, then reads as
+    /// missing; an untouched key in the same bucket stays.
+    #[tokio::test]
+    #[ignore = "requires Docker (NATS); run with `cargo test --include-ignored`"]
+    async fn test_ttl_write_expires() -> anyhow::Result<()> {
+        let (_container, client) = start_nats_jetstream().await?;
+        let kv = NatsKeyValue::new(&client);
+        let bucket = format!("kv-{}", uuid::Uuid::new_v4());
+
+        let store = kv
+            .get_or_create_bucket(&bucket)
+            .await
+            .map_err(|e| anyhow::anyhow!("open failed: {e}"))?;
+        let handle = BucketHandle {
+            kv: store,
+            js: kv.client.clone(),
+            ttl: None,
+        };
+        handle
+            .write(
+                "session",
+                Bytes::from_static(b"token"),
+                Some(Duration::from_secs(1)),
+                None,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("ttl write failed: {e}"))?;
+        handle
+            .write("profile", Bytes::from_static(b"kept"), None, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("write failed: {e}"))?;
+        assert!(handle.kv.get("session").await?.is_some());
+
+        tokio::time::sleep(Duration::from_secs(3)).await;
+        assert!(handle.kv.get("session").await?.is_none());
+        assert!(handle.kv.get("profile").await?.is_some());
+
+        Ok(())
+    }
 }
@@ -3,14 +3,17 @@
 //! This module implements `wasi:keyvalue@0.2.0-draft` interfaces using
 //! Redis as the backend storage.
 //! The `open` identifier is used as a key prefix (`{identifier}:{key}`) to
-//! namespace keys within a single Redis database.
+//! namespace keys within a single Redis database. Key expiry (see
+//! [`super::ttl`]) maps to Redis' native `PSETEX`/`PEXPIRE`.
 
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 
 const PLUGIN_KEYVALUE_ID: &str = "wasi-keyvalue";
 use crate::engine::ctx::{ActiveCtx, SharedCtx, extract_active_ctx};
-use crate::engine::workload::WorkloadItem;
+use crate::engine::workload::{UnresolvedWorkload, WorkloadItem};
+use crate::plugin::wasi_keyvalue::ttl::{self, WorkloadTtls};
 use crate::plugin::{HostPlugin, WitInterfaces};
 use crate::wit::{WitInterface, WitWorld};
 use futures::StreamExt;
@@ -36,6 +39,8 @@ use bindings::wasi::keyvalue::store::{Error as StoreError, KeyResponse};
 pub struct BucketHandle {
     conn: redis::aio::MultiplexedConnection,
     prefix: String,
+    /// TTL of writes that don't pick their own
+    ttl: Option<Duration>,
 }
 
 impl BucketHandle {
@@ -44,11 +49,37 @@ impl BucketHandle {
     }
 }
 
+/// A TTL in whole milliseconds, as `PSETEX`/`PEXPIRE` take it.
+fn ttl_millis(ttl: Duration) -> u64 {
+    u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX).max(1)
+}
+
+/// Write `value` to `redis_key`, expiring it after `ttl` if one is given.
+async fn set_key(
+    conn: &mut redis::aio::MultiplexedConnection,
+    redis_key: String,
+    value: Vec<u8>,
+    ttl: Option<Duration>,
+) -> Result<(), StoreError> {
+    let result = match ttl {
+        Some(ttl) => {
+            conn.pset_ex::<_, _, ()>(redis_key, value, ttl_millis(ttl))
+                .await
+        }
+        None => conn.set::<_, _, ()>(redis_key, value).await,
+    };
+    result.map_err(|e| {
+        tracing::error!("Redis error setting key: {}", e);
+        StoreError::Other(format!("Redis error: {e}"))
+    })
+}
+
 /// Redis-based keyvalue plugin
 #[derive(Clone)]
 pub struct RedisKeyValue {
     client: redis::Client,
     metrics: Arc<WasiKeyvalueMetrics>,
+    ttls: WorkloadTtls,
 }
 
 struct WasiKeyvalueMetrics {
@@ -72,6 +103,7 @@ impl RedisKeyValue {
         Self {
             client,
             metrics: Arc::new(metrics),
+            ttls: WorkloadTtls::default(),
         }
     }
 
@@ -110,9 +142,11 @@ impl<'a> bindings::wasi::keyvalue::store::Host for ActiveCtx<'a> {
             }
         };
 
+        let ttl = plugin.ttls.for_bucket(&self.workload_id, &identifier).await;
         let bucket = BucketHandle {
             conn,
             prefix: identifier,
+            ttl,
         };
 
         let resource = self.table.push(bucket)?;
@@ -160,13 +194,7 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
         let mut conn = bucket_handle.conn.clone();
         let redis_key = bucket_handle.prefixed_key(&key);
 
-        match conn.set::<_, _, ()>(redis_key, value).await {
-            Ok(_) => Ok(Ok(())),
-            Err(e) => {
-                tracing::error!("Redis error setting key: {}", e);
-                Ok(Err(StoreError::Other(format!("Redis error: {e}"))))
-            }
-        }
+        Ok(set_key(&mut conn, redis_key, value, bucket_handle.ttl).await)
     }
 
     #[instrument(name = "wasi.keyvalue.delete", skip(self, bucket))]
@@ -278,7 +306,9 @@ impl<'a> bindings::wasi::keyvalue::store::HostBucket for ActiveCtx<'a> {
 
 // Implementation for the atomics interface
 // Atomics use Redis' native INCRBY for atomic increment, storing values as
-// Redis integer strings rather than big-endian bytes.
+// Redis integer strings rather than big-endian bytes. The expiry is reset in
+// the same transaction, so an increment refreshes (or clears) the key's TTL like
+// any other write.
 impl<'a> bindings::wasi::keyvalue::atomics::Host for ActiveCtx<'a> {
     #[instrument(name = "wasi.keyvalue.increment", skip(self, bucket))]
     async fn increment(
@@ -297,8 +327,19 @@ impl<'a> bindings::wasi::keyvalue::atomics::Host for ActiveCtx<'a> {
 
         let delta_i64 = i64::try_from(delta)
             .map_err(|_| wasmtime::format_err!("delta value {} exceeds i64::MAX", delta))?;
-        match conn.incr::<_, _, i64>(redis_key, delta_i64).await {
-            Ok(new_value) => Ok(Ok(new_value as u64)),
+        let mut pipe = redis::pipe();
+        pipe.atomic().incr(&redis_key, delta_i64);
+        match bucket_handle.ttl {
+            Some(ttl) => pipe
+                .pexpire(
+                    &redis_key,
+                    i64::try_from(ttl_millis(ttl)).unwrap_or(i64::MAX),
+                )
+                .ignore(),
+            None => pipe.persist(&redis_key).ignore(),
+        };
+        match pipe.query_async::<(i64,)>(&mut conn).await {
+            Ok((new_value,)) => Ok(Ok(new_value as u64)),
             Err(e) => {
                 tracing::error!("Redis error incrementing key: {}", e);
                 Ok(Err(StoreError::Other(format!("Redis error: {e}"))))
@@ -368,7 +409,20 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
             .map(|(key, value)| (bucket_handle.prefixed_key(&key), value))
             .collect();
 
-        match conn.mset::<_, _, ()>(pairs.as_slice()).await {
+        // MSET can't set an expiry, so TTL'd batches go out as one transaction
+        // of PSETEX commands instead.
+        let result = match bucket_handle.ttl {
+            Some(ttl) => {
+                let mut pipe = redis::pipe();
+                pipe.atomic();
+                for (key, value) in pairs {
+                    pipe.pset_ex(key, value, ttl_millis(ttl)).ignore();
+                }
+                pipe.query_async::<()>(&mut conn).await
+            }
+            None => conn.mset::<_, _, ()>(pairs.as_slice()).await,
+        };
+        match result {
             Ok(_) => Ok(Ok(())),
             Err(e) => {
                 tracing::error!("Redis error setting keys: {}", e);
@@ -415,6 +469,32 @@ impl<'a> bindings::wasi::keyvalue::batch::Host for ActiveCtx<'a> {
     }
 }
 
+// Implementation for the key expiry extension
+impl<'a> bindings::wasmcloud::keyvalue0_1_0_draft::ttl::Host for ActiveCtx<'a> {
+    #[instrument(name = "wasmcloud.keyvalue.set_with_ttl", skip(self, bucket, value))]
+    async fn set_with_ttl(
+        &mut self,
+        bucket: Resource<BucketHandle>,
+        key: String,
+        value: Vec<u8>,
+        ttl_ms: u64,
+    ) -> wasmtime::Result<Result<(), StoreError>> {
+        let plugin = self.try_get_plugin::<RedisKeyValue>(PLUGIN_KEYVALUE_ID)?;
+
+        plugin.record_operation("set_with_ttl");
+
+        let ttl = match ttl::requested_ttl(ttl_ms) {
+            Ok(ttl) => ttl,
+            Err(e) => return Ok(Err(StoreError::Other(e))),
+        };
+        let bucket_handle = self.table.get(&bucket)?;
+        let mut conn = bucket_handle.conn.clone();
+        let redis_key = bucket_handle.prefixed_key(&key);
+
+        Ok(set_key(&mut conn, redis_key, value, Some(ttl)).await)
+    }
+}
+
 #[async_trait::async_trait]
 impl HostPlugin for RedisKeyValue {
     fn id(&self) -> &'static str {
@@ -423,11 +503,22 @@ impl HostPlugin for RedisKeyValue {
 
     fn world(&self) -> WitWorld {
         WitWorld {
-            imports: HashSet::from([WitInterface::from("wasi:keyvalue/store,atomics,batch")]),
+            imports: HashSet::from([
+                WitInterface::from("wasi:keyvalue/store,atomics,batch"),
+                WitInterface::from("wasmcloud:keyvalue/ttl@0.1.0-draft"),
+            ]),
             ..Default::default()
         }
     }
 
+    async fn on_workload_bind(
+        &self,
+        workload: &UnresolvedWorkload,
+        interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        self.ttls.bind(workload.id(), &interfaces).await
+    }
+
     async fn on_workload_item_bind<'a>(
         &self,
         component_handle: &mut WorkloadItem<'a>,
@@ -454,6 +545,12 @@ impl HostPlugin for RedisKeyValue {
             extract_active_ctx,
         )?;
         bindings::wasi::keyvalue::batch::add_to_linker::<_, SharedCtx>(linker, extract_active_ctx)?;
+        if interfaces.contains("wasmcloud", "keyvalue", &["ttl"]) {
+            bindings::wasmcloud::keyvalue0_1_0_draft::ttl::add_to_linker::<_, SharedCtx>(
+                linker,
+                extract_active_ctx,
+            )?;
+        }
 
         let id = component_handle.id();
         tracing::debug!(
@@ -471,6 +568,7 @@ impl HostPlugin for RedisKeyValue {
         workload_id: &str,
         _interfaces: WitInterfaces<'_>,
     ) -> anyhow::Result<()> {
+        self.ttls.unbind(workload_id).await;
         tracing::debug!("WasiKeyvalue plugin unbound from workload '{workload_id}'");
 
         Ok(())
@@ -0,0 +1,167 @@
+//! Key expiry shared by the standalone `wasi:keyvalue` plugins.
+//!
+//! A workload gives its buckets a default TTL in its `wasi:keyvalue`
+//! host-interface config, as a duration such as `30s` or `15m`:
+//!
+//! - `ttl`: the default for every bucket the workload opens
+//! - `ttl.<bucket>`: the default for one bucket, overriding `ttl`
+//!
+//! Guests choose a TTL for a single write with
+//! `wasmcloud:keyvalue/ttl.set-with-ttl`. Either way a TTL runs from the write
+//! that set it, and a write with no TTL leaves the key without expiry. Redis
+//! and NATS expire keys natively; the in-memory and filesystem stores hide
+//! expired keys from reads and remove them on a periodic sweep.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+use crate::plugin::WitInterfaces;
+
+/// Config key for the default TTL of every bucket.
+const TTL_KEY: &str = "ttl";
+
+/// Config key prefix for the default TTL of a single bucket.
+const BUCKET_TTL_PREFIX: &str = "ttl.";
+
+/// How often the in-memory and filesystem stores remove expired keys.
+pub(crate) const SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The default bucket TTLs of one workload.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TtlConfig {
+    default: Option<Duration>,
+    buckets: HashMap<String, Duration>,
+}
+
+impl TtlConfig {
+    pub(crate) fn from_config(config: &HashMap<String, String>) -> anyhow::Result<Self> {
+        let mut ttls = Self::default();
+        for (key, value) in config {
+            let bucket = if key == TTL_KEY {
+                None
+            } else if let Some(bucket) = key.strip_prefix(BUCKET_TTL_PREFIX) {
+                Some(bucket)
+            } else {
+                continue;
+            };
+            let ttl = humantime::parse_duration(value)
+                .with_context(|| format!("invalid keyvalue TTL `{key}`: {value}"))?;
+            anyhow::ensure!(!ttl.is_zero(), "keyvalue TTL `{key}` must be non-zero");
+            match bucket {
+                Some(bucket) => {
+                    ttls.buckets.insert(bucket.to_string(), ttl);
+                }
+                None => ttls.default = Some(ttl),
+            }
+        }
+        Ok(ttls)
+    }
+
+    /// The TTL writes to `bucket` get when they don't pick their own.
+    pub(crate) fn for_bucket(&self, bucket: &str) -> Option<Duration> {
+        self.buckets.get(bucket).copied().or(self.default)
+    }
+}
+
+/// The [`TtlConfig`] of each bound workload, by workload ID.
+#[derive(Clone, Default)]
+pub(crate) struct WorkloadTtls(Arc<RwLock<HashMap<String, TtlConfig>>>);
+
+impl WorkloadTtls {
+    /// Read the workload's TTLs from its `wasi:keyvalue` interface config.
+    pub(crate) async fn bind(
+        &self,
+        workload_id: &str,
+        interfaces: &WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        let Some(interface) = interfaces.get("wasi", "keyvalue", &[]) else {
+            return Ok(());
+        };
+        let ttls = TtlConfig::from_config(&interface.config)?;
+        self.0.write().await.insert(workload_id.to_string(), ttls);
+        Ok(())
+    }
+
+    pub(crate) async fn unbind(&self, workload_id: &str) {
+        self.0.write().await.remove(workload_id);
+    }
+
+    pub(crate) async fn for_bucket(&self, workload_id: &str, bucket: &str) -> Option<Duration> {
+        self.0
+            .read()
+            .await
+            .get(workload_id)
+            .and_then(|ttls| ttls.for_bucket(bucket))
+    }
+}
+
+/// Validate the `ttl-ms` of a `set-with-ttl`.
+pub(crate) fn requested_ttl(ttl_ms: u64) -> Result<Duration, String> {
+    if ttl_ms == 0 {
+        return Err("ttl-ms must be greater than zero".to_string());
+    }
+    Ok(Duration::from_millis(ttl_ms))
+}
+
+/// Call `sweep` every [`SWEEP_INTERVAL`] until `cancel` fires.
+pub(crate) fn spawn_sweeper<F, Fut>(cancel: CancellationToken, sweep: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = interval.tick() => sweep().await,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn bucket_ttl_overrides_the_default() {
+        let ttls = TtlConfig::from_config(&config(&[
+            ("ttl", "1h"),
+            ("ttl.sessions", "30m"),
+            ("url", "ignored"),
+        ]))
+        .expect("valid config");
+        assert_eq!(ttls.for_bucket("sessions"), Some(Duration::from_secs(1800)));
+        assert_eq!(ttls.for_bucket("other"), Some(Duration::from_secs(3600)));
+
+        let ttls =
+            TtlConfig::from_config(&config(&[("ttl.sessions", "500ms")])).expect("valid config");
+        assert_eq!(
+            ttls.for_bucket("sessions"),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(ttls.for_bucket("other"), None);
+    }
+
+    #[test]
+    fn invalid_ttls_are_rejected() {
+        assert!(TtlConfig::from_config(&config(&[("ttl", "soon")])).is_err());
+        assert!(TtlConfig::from_config(&config(&[("ttl.counters", "0s")])).is_err());
+        assert!(requested_ttl(0).is_err());
+        assert_eq!(requested_ttl(1500), Ok(Duration::from_millis(1500)));
+    }
+}
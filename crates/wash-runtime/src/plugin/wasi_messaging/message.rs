@@ -0,0 +1,87 @@
+//! Host-side state behind the `wasi:messaging` resources.
+
+use std::time::Duration;
+
+use async_nats::HeaderMap;
+
+use crate::plugin::wasmcloud_messaging::{header_map, wit_headers};
+
+/// NATS header carrying a message's `content-type`. Everything else in a
+/// message's metadata travels as a header of its own.
+pub(super) const CONTENT_TYPE_HEADER: &str = "content-type";
+
+/// A `wasi:messaging/types.client`. Both transports are connected when the
+/// plugin is built, so a client only records the name it was asked for.
+#[derive(Debug)]
+pub struct Client {
+    pub(super) name: String,
+}
+
+/// A `wasi:messaging/types.message`, either built by a component or received
+/// from the transport.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Message {
+    /// The subject the message was received on; `None` until it is.
+    pub(super) topic: Option<String>,
+    pub(super) data: Vec<u8>,
+    pub(super) content_type: Option<String>,
+    pub(super) metadata: Vec<(String, String)>,
+    /// Where `request-reply.reply` sends its answer.
+    pub(super) reply_to: Option<String>,
+}
+
+impl Message {
+    pub(super) fn new(data: Vec<u8>) -> Self {
+        Self {
+            data,
+            ..Default::default()
+        }
+    }
+
+    /// Sets `key`, replacing every value it already had.
+    pub(super) fn add_metadata(&mut self, key: String, value: String) {
+        self.remove_metadata(&key);
+        self.metadata.push((key, value));
+    }
+
+    pub(super) fn remove_metadata(&mut self, key: &str) {
+        self.metadata.retain(|(k, _)| k != key);
+    }
+
+    /// The NATS headers carrying this message's metadata and content type.
+    pub(super) fn headers(&self) -> Result<HeaderMap, String> {
+        let mut headers = self.metadata.clone();
+        if let Some(content_type) = &self.content_type {
+            headers.retain(|(k, _)| !k.eq_ignore_ascii_case(CONTENT_TYPE_HEADER));
+            headers.push((CONTENT_TYPE_HEADER.to_string(), content_type.clone()));
+        }
+        header_map(&headers)
+    }
+
+    /// Converts a received NATS message, lifting its `content-type` header
+    /// out of the metadata.
+    pub(super) fn from_nats(msg: async_nats::Message) -> Self {
+        let mut metadata = wit_headers(msg.headers.as_ref());
+        let content_type = metadata
+            .iter()
+            .position(|(k, _)| k.eq_ignore_ascii_case(CONTENT_TYPE_HEADER))
+            .map(|i| metadata.remove(i).1);
+        Self {
+            topic: Some(msg.subject.to_string()),
+            data: msg.payload.into(),
+            content_type,
+            metadata,
+            reply_to: msg.reply.map(|r| r.to_string()),
+        }
+    }
+}
+
+/// A `wasi:messaging/request-reply.request-options`.
+#[derive(Clone, Debug, Default)]
+pub struct RequestOptions {
+    /// How long to wait for replies; without one a request gives up after
+    /// [`DEFAULT_REQUEST_TIMEOUT`](super::transport::DEFAULT_REQUEST_TIMEOUT).
+    pub(super) timeout: Option<Duration>,
+    /// How many replies to wait for; one when unset.
+    pub(super) expected_replies: Option<u32>,
+}
@@ -0,0 +1,546 @@
+//! Native `wasi:messaging@0.2.0-draft` plugin.
+//!
+//! Runs components built against the upstream `wasi:messaging` proposal over
+//! the same brokers as [`crate::plugin::wasmcloud_messaging`]: NATS
+//! ([`WasiMessaging::nats`]) or an in-process bus ([`WasiMessaging::in_memory`]).
+//! Message metadata and content type travel as NATS headers, so over NATS a
+//! `wasi:messaging` component and a `wasmcloud:messaging@0.3.0` one can talk
+//! to each other and see the same headers. The in-process bus is this
+//! plugin's own, so there the two families don't see each other's messages.
+//!
+//! A component exporting `incoming-handler` is subscribed the way a
+//! `wasmcloud:messaging` handler is: `subscriptions` and `consumer_group`
+//! come from its own `LocalResources.config`, falling back to the workload's
+//! `wasi:messaging` host interface config. The in-process bus has no consumer
+//! groups, so it refuses a `consumer_group` other than `broadcast`. Each
+//! message is handled by a fresh instance; a failed one is logged and
+//! dropped.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::StreamExt;
+use opentelemetry::KeyValue;
+use tokio::sync::RwLock;
+use tracing::{Instrument, debug, instrument, warn};
+use wasmtime::component::Resource;
+use wasmtime::error::Context as _;
+
+mod message;
+mod transport;
+
+use message::{Client, Message, RequestOptions};
+use transport::{InMemoryBus, Transport};
+
+mod bindings {
+    crate::wasmtime::component::bindgen!({
+        world: "wasi-messaging",
+        imports: { default: async | trappable | tracing },
+        exports: { default: async | tracing },
+        with: {
+            "wasi:messaging/types.client": super::message::Client,
+            "wasi:messaging/types.message": super::message::Message,
+            "wasi:messaging/request-reply.request-options": super::message::RequestOptions,
+        },
+    });
+}
+
+use bindings::wasi::messaging::types::{Error, Metadata, Topic};
+use bindings::wasi::messaging::{producer, request_reply, types};
+
+use crate::engine::ctx::{ActiveCtx, SharedCtx, extract_active_ctx};
+use crate::engine::workload::{ResolvedWorkload, WorkloadItem};
+use crate::observability::{FuelConsumptionMeter, Meters};
+use crate::plugin::wasmcloud_messaging::{
+    CONSUMER_GROUP_CONFIG, ConsumerGroup, parse_subscriptions,
+};
+use crate::plugin::{HostPlugin, WitInterfaces, WorkloadTracker};
+use crate::wit::{WitInterface, WitWorld};
+
+const PLUGIN_WASI_MESSAGING_ID: &str = "wasi-messaging";
+
+struct ComponentData {
+    subscriptions: Vec<String>,
+    consumer_group: ConsumerGroup,
+    cancel_token: tokio_util::sync::CancellationToken,
+    task_handle: Option<tokio::task::JoinHandle<()>>,
+}
+
+#[derive(Clone)]
+pub struct WasiMessaging {
+    transport: Transport,
+    tracker: Arc<RwLock<WorkloadTracker<(), ComponentData>>>,
+    meters: Arc<RwLock<Meters>>,
+}
+
+impl WasiMessaging {
+    /// Sends, requests and subscribes through `client`.
+    pub fn nats(client: Arc<async_nats::Client>) -> Self {
+        Self::new(Transport::Nats(client))
+    }
+
+    /// Routes messages between the components of each workload in-process.
+    pub fn in_memory() -> Self {
+        Self::new(Transport::InMemory(InMemoryBus::default()))
+    }
+
+    fn new(transport: Transport) -> Self {
+        Self {
+            transport,
+            tracker: Arc::new(RwLock::new(WorkloadTracker::default())),
+            meters: Default::default(),
+        }
+    }
+}
+
+impl<'a> types::Host for ActiveCtx<'a> {}
+
+impl<'a> types::HostClient for ActiveCtx<'a> {
+    async fn connect(&mut self, name: String) -> wasmtime::Result<Result<Resource<Client>, Error>> {
+        debug!(%name, "wasi:messaging client connected");
+        Ok(Ok(self.table.push(Client { name })?))
+    }
+
+    async fn disconnect(
+        &mut self,
+        client: Resource<Client>,
+    ) -> wasmtime::Result<Result<(), Error>> {
+        debug!(name = %self.table.get(&client)?.name, "wasi:messaging client disconnected");
+        Ok(Ok(()))
+    }
+
+    async fn drop(&mut self, client: Resource<Client>) -> wasmtime::Result<()> {
+        self.table.delete(client)?;
+        Ok(())
+    }
+}
+
+impl<'a> types::HostMessage for ActiveCtx<'a> {
+    async fn new(&mut self, data: Vec<u8>) -> wasmtime::Result<Resource<Message>> {
+        Ok(self.table.push(Message::new(data))?)
+    }
+
+    async fn topic(&mut self, msg: Resource<Message>) -> wasmtime::Result<Option<Topic>> {
+        Ok(self.table.get(&msg)?.topic.clone())
+    }
+
+    async fn content_type(&mut self, msg: Resource<Message>) -> wasmtime::Result<Option<String>> {
+        Ok(self.table.get(&msg)?.content_type.clone())
+    }
+
+    async fn set_content_type(
+        &mut self,
+        msg: Resource<Message>,
+        content_type: String,
+    ) -> wasmtime::Result<()> {
+        self.table.get_mut(&msg)?.content_type = Some(content_type);
+        Ok(())
+    }
+
+    async fn data(&mut self, msg: Resource<Message>) -> wasmtime::Result<Vec<u8>> {
+        Ok(self.table.get(&msg)?.data.clone())
+    }
+
+    async fn set_data(&mut self, msg: Resource<Message>, data: Vec<u8>) -> wasmtime::Result<()> {
+        self.table.get_mut(&msg)?.data = data;
+        Ok(())
+    }
+
+    async fn metadata(&mut self, msg: Resource<Message>) -> wasmtime::Result<Option<Metadata>> {
+        let metadata = &self.table.get(&msg)?.metadata;
+        Ok((!metadata.is_empty()).then(|| metadata.clone()))
+    }
+
+    async fn add_metadata(
+        &mut self,
+        msg: Resource<Message>,
+        key: String,
+        value: String,
+    ) -> wasmtime::Result<()> {
+        self.table.get_mut(&msg)?.add_metadata(key, value);
+        Ok(())
+    }
+
+    async fn set_metadata(
+        &mut self,
+        msg: Resource<Message>,
+        meta: Metadata,
+    ) -> wasmtime::Result<()> {
+        self.table.get_mut(&msg)?.metadata = meta;
+        Ok(())
+    }
+
+    async fn remove_metadata(
+        &mut self,
+        msg: Resource<Message>,
+        key: String,
+    ) -> wasmtime::Result<()> {
+        self.table.get_mut(&msg)?.remove_metadata(&key);
+        Ok(())
+    }
+
+    async fn drop(&mut self, msg: Resource<Message>) -> wasmtime::Result<()> {
+        self.table.delete(msg)?;
+        Ok(())
+    }
+}
+
+impl<'a> producer::Host for ActiveCtx<'a> {
+    #[instrument(name = "wasi.messaging.send", skip_all, fields(topic = %topic))]
+    async fn send(
+        &mut self,
+        _client: Resource<Client>,
+        topic: Topic,
+        msg: Resource<Message>,
+    ) -> wasmtime::Result<Result<(), Error>> {
+        let plugin = self.try_get_plugin::<WasiMessaging>(PLUGIN_WASI_MESSAGING_ID)?;
+        let msg = self.table.delete(msg)?;
+        Ok(plugin.transport.send(&self.workload_id, topic, msg).await)
+    }
+}
+
+impl<'a> request_reply::Host for ActiveCtx<'a> {
+    #[instrument(name = "wasi.messaging.request", skip_all, fields(topic = %topic))]
+    async fn request(
+        &mut self,
+        _client: Resource<Client>,
+        topic: Topic,
+        msg: Resource<Message>,
+        options: Option<Resource<RequestOptions>>,
+    ) -> wasmtime::Result<Result<Vec<Resource<Message>>, Error>> {
+        let plugin = self.try_get_plugin::<WasiMessaging>(PLUGIN_WASI_MESSAGING_ID)?;
+        let msg = self.table.get(&msg)?.clone();
+        let options = match options {
+            Some(options) => self.table.delete(options)?,
+            None => RequestOptions::default(),
+        };
+        let replies = match plugin
+            .transport
+            .request(&self.workload_id, topic, msg, options)
+            .await
+        {
+            Ok(replies) => replies,
+            Err(e) => return Ok(Err(e)),
+        };
+        let replies = replies
+            .into_iter()
+            .map(|reply| self.table.push(reply))
+            .collect::<Result<_, _>>()?;
+        Ok(Ok(replies))
+    }
+
+    #[instrument(name = "wasi.messaging.reply", skip_all)]
+    async fn reply(
+        &mut self,
+        reply_to: Resource<Message>,
+        msg: Resource<Message>,
+    ) -> wasmtime::Result<Result<(), Error>> {
+        let plugin = self.try_get_plugin::<WasiMessaging>(PLUGIN_WASI_MESSAGING_ID)?;
+        let msg = self.table.delete(msg)?;
+        let Some(subject) = self.table.get(&reply_to)?.reply_to.clone() else {
+            return Ok(Err(Error::Other(
+                "message has no reply subject".to_string(),
+            )));
+        };
+        Ok(plugin.transport.send(&self.workload_id, subject, msg).await)
+    }
+}
+
+impl<'a> request_reply::HostRequestOptions for ActiveCtx<'a> {
+    async fn new(&mut self) -> wasmtime::Result<Resource<RequestOptions>> {
+        Ok(self.table.push(RequestOptions::default())?)
+    }
+
+    async fn set_timeout_ms(
+        &mut self,
+        options: Resource<RequestOptions>,
+        timeout_ms: u32,
+    ) -> wasmtime::Result<()> {
+        self.table.get_mut(&options)?.timeout = Some(Duration::from_millis(timeout_ms.into()));
+        Ok(())
+    }
+
+    async fn set_expected_replies(
+        &mut self,
+        options: Resource<RequestOptions>,
+        expected_replies: u32,
+    ) -> wasmtime::Result<()> {
+        self.table.get_mut(&options)?.expected_replies = Some(expected_replies);
+        Ok(())
+    }
+
+    async fn drop(&mut self, options: Resource<RequestOptions>) -> wasmtime::Result<()> {
+        self.table.delete(options)?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl HostPlugin for WasiMessaging {
+    fn id(&self) -> &'static str {
+        PLUGIN_WASI_MESSAGING_ID
+    }
+
+    fn world(&self) -> WitWorld {
+        WitWorld {
+            imports: HashSet::from([WitInterface::from(
+                "wasi:messaging/types,producer,request-reply@0.2.0-draft",
+            )]),
+            exports: HashSet::from([WitInterface::from(
+                "wasi:messaging/incoming-handler@0.2.0-draft",
+            )]),
+        }
+    }
+
+    async fn inject_meters(&self, meters: &Meters) {
+        *self.meters.write().await = meters.clone();
+    }
+
+    async fn on_workload_item_bind<'a>(
+        &self,
+        component_handle: &mut WorkloadItem<'a>,
+        interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        let Some(interface) = interfaces.get("wasi", "messaging", &[]) else {
+            return Ok(());
+        };
+        let interface_subscriptions = interface.config.get("subscriptions").cloned();
+        let interface_consumer_group = interface.config.get(CONSUMER_GROUP_CONFIG).cloned();
+
+        types::add_to_linker::<_, SharedCtx>(component_handle.linker(), extract_active_ctx)?;
+        producer::add_to_linker::<_, SharedCtx>(component_handle.linker(), extract_active_ctx)?;
+        request_reply::add_to_linker::<_, SharedCtx>(
+            component_handle.linker(),
+            extract_active_ctx,
+        )?;
+
+        let handler = WitInterface::from("wasi:messaging/incoming-handler");
+        let exports_handler = component_handle
+            .world()
+            .exports
+            .iter()
+            .any(|e| e.contains(&handler));
+        let WorkloadItem::Component(component) = component_handle else {
+            return Ok(());
+        };
+        if !exports_handler {
+            return Ok(());
+        }
+
+        let local_config = &component.local_resources().config;
+        let subscriptions = parse_subscriptions(
+            local_config
+                .get("subscriptions")
+                .or(interface_subscriptions.as_ref())
+                .map(String::as_str),
+        );
+        let consumer_group = self.transport.consumer_group(
+            local_config
+                .get(CONSUMER_GROUP_CONFIG)
+                .or(interface_consumer_group.as_ref())
+                .map(String::as_str),
+            component.workload_namespace(),
+            component.workload_name(),
+            component.name(),
+        )?;
+
+        debug!(
+            component_id = component.id(),
+            ?subscriptions,
+            consumer_group = ?consumer_group.name(),
+            "tracking wasi:messaging handler component"
+        );
+        self.tracker.write().await.add_component(
+            component,
+            ComponentData {
+                subscriptions,
+                consumer_group,
+                cancel_token: tokio_util::sync::CancellationToken::new(),
+                task_handle: None,
+            },
+        );
+        Ok(())
+    }
+
+    #[instrument(name = "wasi.messaging.on_workload_resolved", skip_all, fields(component_id = %component_id, workload.id = %workload.id()))]
+    async fn on_workload_resolved(
+        &self,
+        workload: &ResolvedWorkload,
+        component_id: &str,
+    ) -> anyhow::Result<()> {
+        let (subjects, consumer_group, cancel_token) = {
+            let lock = self.tracker.read().await;
+            match lock.get_component_data(component_id) {
+                Some(data) => (
+                    data.subscriptions.clone(),
+                    data.consumer_group.clone(),
+                    data.cancel_token.clone(),
+                ),
+                None => return Ok(()),
+            }
+        };
+        if subjects.is_empty() {
+            debug!("no subscriptions configured, skipping subscription setup");
+            return Ok(());
+        }
+
+        let pre = bindings::WasiMessagingPre::new(workload.instantiate_pre(component_id).await?)
+            .context("failed to instantiate wasi:messaging pre")?;
+        let handler = Arc::new(MessageHandler {
+            workload: workload.clone(),
+            component_id: component_id.to_string(),
+            pre,
+            fuel_meter: self.meters.read().await.fuel_consumption.clone(),
+        });
+        let mut messages = self
+            .transport
+            .subscribe(workload.id(), &subjects, &consumer_group)
+            .await?;
+
+        let span = tracing::Span::current();
+        let handle = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    maybe_msg = messages.next() => {
+                        let Some(msg) = maybe_msg else {
+                            warn!(parent: &span, "wasi:messaging subscription closed; handler will stop receiving messages");
+                            break;
+                        };
+                        let handler = handler.clone();
+                        tokio::spawn(async move { handler.deliver(msg).await });
+                    }
+                    _ = cancel_token.cancelled() => break,
+                }
+            }
+        });
+
+        if let Some(data) = self
+            .tracker
+            .write()
+            .await
+            .get_component_data_mut(component_id)
+        {
+            data.task_handle = Some(handle);
+        }
+        Ok(())
+    }
+
+    async fn on_workload_unbind(
+        &self,
+        workload_id: &str,
+        _interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        let workload_cleanup = |_| async {};
+        let component_cleanup = |component_data: ComponentData| async move {
+            component_data.cancel_token.cancel();
+            if let Some(handle) = component_data.task_handle {
+                handle.abort();
+            }
+        };
+        self.tracker
+            .write()
+            .await
+            .remove_workload_with_cleanup(workload_id, workload_cleanup, component_cleanup)
+            .await;
+        self.transport.remove_workload(workload_id).await;
+        Ok(())
+    }
+}
+
+/// Everything needed to hand a message to a fresh instance of one handler
+/// component.
+struct MessageHandler {
+    workload: ResolvedWorkload,
+    component_id: String,
+    pre: bindings::WasiMessagingPre<SharedCtx>,
+    fuel_meter: FuelConsumptionMeter,
+}
+
+impl MessageHandler {
+    async fn handle(&self, msg: Message) -> anyhow::Result<Result<(), Error>> {
+        let topic = msg.topic.clone().unwrap_or_default();
+        let mut store = self.workload.new_store(&self.component_id).await?;
+        let msg = store.data_mut().table.push(msg)?;
+        let span = tracing::span!(tracing::Level::INFO, "incoming_wasi_message", topic = %topic);
+        self.fuel_meter
+            .observe(
+                &[
+                    KeyValue::new("plugin", PLUGIN_WASI_MESSAGING_ID),
+                    KeyValue::new("subject", topic),
+                ],
+                &mut store,
+                async move |store| {
+                    let proxy =
+                        self.pre
+                            .instantiate_async(&mut *store)
+                            .await
+                            .with_context(|| {
+                                format!("failed to instantiate component {}", self.component_id)
+                            })?;
+                    proxy
+                        .wasi_messaging_incoming_handler()
+                        .call_handle(store, msg)
+                        .instrument(span)
+                        .await
+                        .map_err(Into::into)
+                },
+            )
+            .await
+    }
+
+    async fn deliver(&self, msg: Message) {
+        let topic = msg.topic.clone().unwrap_or_default();
+        match self.handle(msg).await {
+            Ok(Ok(())) => debug!(%topic, "Message handled successfully"),
+            Ok(Err(e)) => warn!(%topic, error = ?e, "Handler failed to handle message"),
+            Err(e) => warn!(%topic, error = ?e, "Error handling message"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_and_content_type_travel_as_nats_headers() {
+        let mut msg = Message::new(b"hi".to_vec());
+        msg.add_metadata("x-id".to_string(), "1".to_string());
+        msg.add_metadata("x-id".to_string(), "2".to_string());
+        msg.add_metadata("x-trace".to_string(), "t".to_string());
+        msg.content_type = Some("text/plain".to_string());
+
+        let headers = msg.headers().unwrap();
+        assert_eq!(
+            headers.get("x-id").map(|v| v.as_str()),
+            Some("2"),
+            "add-metadata overwrites a key"
+        );
+        let received = Message::from_nats(async_nats::Message {
+            subject: "greetings".into(),
+            reply: Some("_INBOX.1".into()),
+            payload: msg.data.clone().into(),
+            headers: Some(headers),
+            status: None,
+            description: None,
+            length: 0,
+        });
+        assert_eq!(
+            received,
+            Message {
+                topic: Some("greetings".to_string()),
+                data: b"hi".to_vec(),
+                content_type: Some("text/plain".to_string()),
+                metadata: vec![
+                    ("x-id".to_string(), "2".to_string()),
+                    ("x-trace".to_string(), "t".to_string()),
+                ],
+                reply_to: Some("_INBOX.1".to_string()),
+            }
+        );
+
+        msg.add_metadata("bad key".to_string(), "v".to_string());
+        assert!(msg.headers().is_err(), "NATS can't carry the header");
+    }
+}
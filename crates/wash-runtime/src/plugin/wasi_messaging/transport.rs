@@ -0,0 +1,438 @@
+//! The brokers a `wasi:messaging` component talks to: NATS, or an in-process
+//! bus for local development and tests. Both carry the same [`Message`]s, so
+//! a component behaves the same on either, except that the bus has no
+//! consumer groups: every matching subscriber gets every message.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_nats::{StatusCode, Subscriber};
+use futures::stream::{BoxStream, Stream, StreamExt};
+use tokio::sync::{RwLock, mpsc};
+use tracing::{debug, warn};
+
+use super::bindings::wasi::messaging::types::Error;
+use super::message::{Message, RequestOptions};
+use crate::plugin::wasmcloud_messaging::{
+    ConsumerGroup, MAX_QUEUE_SIZE, subject_matches, sync_with_server,
+};
+
+/// How long a request waits for replies when the component set no timeout,
+/// so an expected reply that never comes can't hang the caller forever.
+pub(super) const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone)]
+pub(super) enum Transport {
+    Nats(Arc<async_nats::Client>),
+    InMemory(InMemoryBus),
+}
+
+impl Transport {
+    /// Publishes `msg` on `topic`. The message's own reply subject is not
+    /// carried over; only [`Transport::request`] sets one.
+    pub(super) async fn send(
+        &self,
+        workload_id: &str,
+        topic: String,
+        msg: Message,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Nats(client) => {
+                let headers = msg.headers().map_err(Error::Other)?;
+                client
+                    .publish_with_headers(topic, headers, msg.data.into())
+                    .await
+                    .map_err(|e| Error::Other(e.to_string()))
+            }
+            Self::InMemory(bus) => {
+                let msg = Message {
+                    topic: Some(topic),
+                    reply_to: None,
+                    ..msg
+                };
+                bus.publish(workload_id, msg).await.map(drop)
+            }
+        }
+    }
+
+    /// Publishes `msg` on `topic` with a fresh reply subject and collects the
+    /// replies per `options`.
+    pub(super) async fn request(
+        &self,
+        workload_id: &str,
+        topic: String,
+        msg: Message,
+        options: RequestOptions,
+    ) -> Result<Vec<Message>, Error> {
+        match self {
+            Self::Nats(client) => {
+                let headers = msg.headers().map_err(Error::Other)?;
+                let inbox = client.new_inbox();
+                let replies = client
+                    .subscribe(inbox.clone())
+                    .await
+                    .map_err(|e| Error::Other(e.to_string()))?;
+                client
+                    .publish_with_reply_and_headers(topic.clone(), inbox, headers, msg.data.into())
+                    .await
+                    .map_err(|e| Error::Other(e.to_string()))?;
+                collect_replies(nats_replies(replies, topic), options).await
+            }
+            Self::InMemory(bus) => {
+                let inbox = format!("_INBOX.{}", uuid::Uuid::new_v4().simple());
+                let (tx, mut rx) = mpsc::channel(MAX_QUEUE_SIZE);
+                bus.add_inbox(workload_id, inbox.clone(), tx).await;
+                let msg = Message {
+                    topic: Some(topic.clone()),
+                    reply_to: Some(inbox.clone()),
+                    ..msg
+                };
+                let result = match bus.publish(workload_id, msg).await {
+                    Ok(0) => Err(Error::Other(format!("no responders for {topic}"))),
+                    Ok(_) => {
+                        let replies = futures::stream::poll_fn(move |cx| rx.poll_recv(cx));
+                        collect_replies(replies.map(Ok), options).await
+                    }
+                    Err(e) => Err(e),
+                };
+                bus.remove_inbox(workload_id, &inbox).await;
+                result
+            }
+        }
+    }
+
+    /// Resolves a handler's `consumer_group` config. The in-memory bus hands
+    /// every message to every matching subscriber, so it only accepts an
+    /// unset or `broadcast` group rather than pretend to split messages.
+    pub(super) fn consumer_group(
+        &self,
+        configured: Option<&str>,
+        workload_namespace: &str,
+        workload_name: &str,
+        component_name: &str,
+    ) -> anyhow::Result<ConsumerGroup> {
+        let group = ConsumerGroup::resolve(
+            configured,
+            workload_namespace,
+            workload_name,
+            component_name,
+        )?;
+        match (self, configured, group) {
+            (Self::Nats(_), _, group) => Ok(group),
+            (Self::InMemory(_), Some(_), ConsumerGroup::Grouped(group)) => anyhow::bail!(
+                "consumer_group `{group}` needs NATS: the in-memory wasi:messaging bus \
+                 delivers every message to every subscriber"
+            ),
+            (Self::InMemory(_), _, _) => Ok(ConsumerGroup::Broadcast),
+        }
+    }
+
+    /// Subscribes a handler component to `subjects`. On NATS, replicas sharing
+    /// a grouped `consumer_group` split the messages between them; the
+    /// in-memory bus only takes [`ConsumerGroup::Broadcast`].
+    pub(super) async fn subscribe(
+        &self,
+        workload_id: &str,
+        subjects: &[String],
+        consumer_group: &ConsumerGroup,
+    ) -> anyhow::Result<BoxStream<'static, Message>> {
+        match self {
+            Self::Nats(client) => {
+                let mut subscriptions = Vec::<Subscriber>::new();
+                for subject in subjects {
+                    let sub = match consumer_group {
+                        ConsumerGroup::Grouped(group) => {
+                            client.queue_subscribe(subject.clone(), group.clone()).await
+                        }
+                        ConsumerGroup::Broadcast => client.subscribe(subject.clone()).await,
+                    }
+                    .map_err(|e| {
+                        anyhow::anyhow!(e).context(format!("failed to subscribe to {subject}"))
+                    })?;
+                    debug!(%subject, consumer_group = ?consumer_group.name(), "subscribed to NATS subject");
+                    subscriptions.push(sub);
+                }
+                // Don't report the handler ready before the server has the
+                // subscriptions; see `sync_with_server`.
+                if let Err(e) = sync_with_server(client).await {
+                    warn!(error = ?e, "failed to sync subscriptions with NATS server");
+                }
+                Ok(futures::stream::select_all(subscriptions)
+                    .map(Message::from_nats)
+                    .boxed())
+            }
+            Self::InMemory(bus) => {
+                anyhow::ensure!(
+                    *consumer_group == ConsumerGroup::Broadcast,
+                    "the in-memory wasi:messaging bus has no consumer groups"
+                );
+                let (tx, mut rx) = mpsc::channel(MAX_QUEUE_SIZE);
+                bus.add_subscriber(workload_id, subjects.to_vec(), tx).await;
+                Ok(futures::stream::poll_fn(move |cx| rx.poll_recv(cx)).boxed())
+            }
+        }
+    }
+
+    /// Forgets a workload's in-memory subscriptions.
+    pub(super) async fn remove_workload(&self, workload_id: &str) {
+        if let Self::InMemory(bus) = self {
+            bus.workloads.write().await.remove(workload_id);
+        }
+    }
+}
+
+/// Replies on a NATS inbox, with the server's "no responders" status turned
+/// into an error.
+fn nats_replies(
+    replies: Subscriber,
+    topic: String,
+) -> impl Stream<Item = Result<Message, Error>> + Unpin {
+    replies.map(move |msg| {
+        if msg.status == Some(StatusCode::NO_RESPONDERS) {
+            Err(Error::Other(format!("no responders for {topic}")))
+        } else {
+            Ok(Message::from_nats(msg))
+        }
+    })
+}
+
+/// Waits for the expected number of replies (one by default), or for the
+/// timeout ([`DEFAULT_REQUEST_TIMEOUT`] when unset), whichever comes first.
+/// Running out of time before any reply arrived is [`Error::Timeout`].
+async fn collect_replies(
+    mut replies: impl Stream<Item = Result<Message, Error>> + Unpin,
+    options: RequestOptions,
+) -> Result<Vec<Message>, Error> {
+    let expected = options.expected_replies.unwrap_or(1).max(1) as usize;
+    let deadline = tokio::time::Instant::now() + options.timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT);
+    let mut received = Vec::new();
+    while received.len() < expected {
+        let next = match tokio::time::timeout_at(deadline, replies.next()).await {
+            Ok(next) => next,
+            Err(_) if received.is_empty() => return Err(Error::Timeout),
+            Err(_) => break,
+        };
+        match next {
+            Some(Ok(msg)) => received.push(msg),
+            Some(Err(e)) if received.is_empty() => return Err(e),
+            None if received.is_empty() => return Err(Error::Connection),
+            Some(Err(_)) | None => break,
+        }
+    }
+    Ok(received)
+}
+
+/// In-process stand-in for a broker. Like the `wasmcloud:messaging`
+/// in-memory plugin, it only routes messages between the components of one
+/// workload. It is not that plugin's bus, so in-memory `wasi:messaging` and
+/// `wasmcloud:messaging` components don't see each other's messages; over
+/// NATS they do.
+#[derive(Clone, Default)]
+pub(super) struct InMemoryBus {
+    workloads: Arc<RwLock<HashMap<String, WorkloadBus>>>,
+}
+
+#[derive(Default)]
+struct WorkloadBus {
+    subscribers: Vec<(Vec<String>, mpsc::Sender<Message>)>,
+    /// Reply subjects of in-flight requests. A message sent to one goes only
+    /// to the waiting request, never to subscribers.
+    inboxes: HashMap<String, mpsc::Sender<Message>>,
+}
+
+impl InMemoryBus {
+    async fn add_subscriber(
+        &self,
+        workload_id: &str,
+        subjects: Vec<String>,
+        tx: mpsc::Sender<Message>,
+    ) {
+        let mut workloads = self.workloads.write().await;
+        let bus = workloads.entry(workload_id.to_string()).or_default();
+        bus.subscribers.push((subjects, tx));
+    }
+
+    async fn add_inbox(&self, workload_id: &str, inbox: String, tx: mpsc::Sender<Message>) {
+        let mut workloads = self.workloads.write().await;
+        let bus = workloads.entry(workload_id.to_string()).or_default();
+        bus.inboxes.insert(inbox, tx);
+    }
+
+    async fn remove_inbox(&self, workload_id: &str, inbox: &str) {
+        if let Some(bus) = self.workloads.write().await.get_mut(workload_id) {
+            bus.inboxes.remove(inbox);
+        }
+    }
+
+    /// Queues `msg` for every subscriber whose subjects match its topic, and
+    /// returns how many there were. Subscribers whose handler stopped are
+    /// dropped along the way.
+    async fn publish(&self, workload_id: &str, msg: Message) -> Result<usize, Error> {
+        let topic = msg.topic.clone().unwrap_or_default();
+        let mut workloads = self.workloads.write().await;
+        let Some(bus) = workloads.get_mut(workload_id) else {
+            return Ok(0);
+        };
+        if let Some(inbox) = bus.inboxes.get(&topic) {
+            return try_queue(inbox, msg).map(|()| 1);
+        }
+        bus.subscribers.retain(|(_, tx)| !tx.is_closed());
+        let mut delivered = 0;
+        for (subjects, tx) in &bus.subscribers {
+            if subjects.iter().any(|s| subject_matches(s, &topic)) {
+                try_queue(tx, msg.clone())?;
+                delivered += 1;
+            }
+        }
+        Ok(delivered)
+    }
+}
+
+fn try_queue(tx: &mpsc::Sender<Message>, msg: Message) -> Result<(), Error> {
+    tx.try_send(msg).map_err(|e| match e {
+        mpsc::error::TrySendError::Full(_) => Error::Other("queue full".to_string()),
+        mpsc::error::TrySendError::Closed(_) => Error::Connection,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn in_memory_bus_routes_by_subject_within_a_workload() {
+        let bus = InMemoryBus::default();
+        let (orders, mut orders_rx) = mpsc::channel(8);
+        let (all, mut all_rx) = mpsc::channel(8);
+        bus.add_subscriber("w1", vec!["orders.*".to_string()], orders)
+            .await;
+        bus.add_subscriber("w1", vec![">".to_string()], all).await;
+
+        let msg = |topic: &str| Message {
+            topic: Some(topic.to_string()),
+            ..Message::new(b"x".to_vec())
+        };
+        assert_eq!(bus.publish("w1", msg("orders.new")).await.unwrap(), 2);
+        assert_eq!(bus.publish("w1", msg("users.new")).await.unwrap(), 1);
+        assert_eq!(
+            bus.publish("w2", msg("orders.new")).await.unwrap(),
+            0,
+            "other workloads see nothing"
+        );
+        assert_eq!(
+            orders_rx.try_recv().unwrap().topic.as_deref(),
+            Some("orders.new")
+        );
+        assert!(orders_rx.try_recv().is_err());
+        assert_eq!(
+            all_rx.try_recv().unwrap().topic.as_deref(),
+            Some("orders.new")
+        );
+        assert_eq!(
+            all_rx.try_recv().unwrap().topic.as_deref(),
+            Some("users.new")
+        );
+
+        drop(orders_rx);
+        assert_eq!(
+            bus.publish("w1", msg("orders.new")).await.unwrap(),
+            1,
+            "a stopped subscriber is dropped"
+        );
+    }
+
+    #[tokio::test]
+    async fn in_memory_requests_collect_replies_or_time_out() {
+        let transport = Transport::InMemory(InMemoryBus::default());
+        let mut handler = transport
+            .subscribe("w1", &["svc".to_string()], &ConsumerGroup::Broadcast)
+            .await
+            .unwrap();
+        let responder = transport.clone();
+        tokio::spawn(async move {
+            while let Some(request) = handler.next().await {
+                let reply_to = request.reply_to.clone().unwrap();
+                for data in [b"one", b"two"] {
+                    let reply = Message::new(data.to_vec());
+                    responder.send("w1", reply_to.clone(), reply).await.unwrap();
+                }
+            }
+        });
+
+        let options = RequestOptions {
+            timeout: Some(Duration::from_secs(5)),
+            expected_replies: Some(2),
+        };
+        let replies = transport
+            .request("w1", "svc".to_string(), Message::new(vec![]), options)
+            .await
+            .unwrap();
+        let data: Vec<_> = replies.iter().map(|m| m.data.as_slice()).collect();
+        assert_eq!(data, [b"one", b"two"]);
+
+        let options = RequestOptions {
+            timeout: Some(Duration::from_millis(50)),
+            expected_replies: Some(3),
+        };
+        let replies = transport
+            .request("w1", "svc".to_string(), Message::new(vec![]), options)
+            .await
+            .unwrap();
+        assert_eq!(replies.len(), 2, "the timeout ends a partial collection");
+
+        let err = transport
+            .request(
+                "w1",
+                "nobody".to_string(),
+                Message::new(vec![]),
+                RequestOptions::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Other(e) if e.contains("no responders")));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn requests_without_a_timeout_give_up_after_the_default() {
+        let transport = Transport::InMemory(InMemoryBus::default());
+        let _silent = transport
+            .subscribe("w1", &["svc".to_string()], &ConsumerGroup::Broadcast)
+            .await
+            .unwrap();
+
+        let started = tokio::time::Instant::now();
+        let err = transport
+            .request(
+                "w1",
+                "svc".to_string(),
+                Message::new(vec![]),
+                RequestOptions::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Timeout));
+        assert_eq!(started.elapsed(), DEFAULT_REQUEST_TIMEOUT);
+    }
+
+    #[tokio::test]
+    async fn in_memory_bus_refuses_consumer_groups() {
+        let transport = Transport::InMemory(InMemoryBus::default());
+        let resolve = |configured| transport.consumer_group(configured, "ns", "wl", "comp");
+        assert_eq!(resolve(None).unwrap(), ConsumerGroup::Broadcast);
+        assert_eq!(
+            resolve(Some("broadcast")).unwrap(),
+            ConsumerGroup::Broadcast
+        );
+        let err = resolve(Some("workers")).unwrap_err().to_string();
+        assert!(err.contains("needs NATS"), "{err}");
+
+        let grouped = ConsumerGroup::Grouped("workers".to_string());
+        assert!(
+            transport
+                .subscribe("w1", &["svc".to_string()], &grouped)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,385 @@
+//! Delivery of received messages to `wasmcloud:messaging/handler` components,
+//! shared by the NATS and in-memory backends.
+//!
+//! A backend's receive loop hands each message to [`MessageHandler::submit`],
+//! which never waits on the handler itself. Per-message instances run
+//! concurrently, while a long-lived trigger service is fed one message at a
+//! time from its own queue. A failed delivery is retried from a timer task per
+//! the handler's [`RetryPolicy`], so its backoff holds up neither the receive
+//! loop nor the messages queued behind it, and stops when the component is
+//! unbound.
+
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use futures::FutureExt as _;
+use futures::future::BoxFuture;
+use opentelemetry::KeyValue;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, debug, trace, warn};
+
+use super::HandlerOutcome;
+use super::retry::{DeadLetter, RetryPolicy};
+use crate::engine::ctx::{ActiveCtx, SharedCtx};
+use crate::engine::workload::ResolvedWorkload;
+use crate::observability::FuelConsumptionMeter;
+
+pub mod bindings {
+    crate::wasmtime::component::bindgen!({
+        world: "messaging",
+        imports: { default: async | trappable | tracing },
+        exports: { default: async | tracing },
+    });
+}
+
+pub mod reply_bindings {
+    crate::wasmtime::component::bindgen!({
+        world: "messaging-replies",
+        imports: { default: async | trappable | tracing },
+        exports: { default: async | tracing },
+    });
+}
+
+pub use bindings::wasmcloud::messaging0_2_0 as v0_2;
+pub use reply_bindings::wasmcloud::messaging0_3_0::types;
+
+/// Messages a trigger service may have queued before the receive loop waits.
+const TRIGGER_SERVICE_QUEUE: usize = super::MAX_QUEUE_SIZE;
+
+impl<'a> types::Host for ActiveCtx<'a> {}
+
+impl<'a> v0_2::types::Host for ActiveCtx<'a> {}
+
+impl From<v0_2::types::BrokerMessage> for types::BrokerMessage {
+    fn from(msg: v0_2::types::BrokerMessage) -> Self {
+        Self {
+            subject: msg.subject,
+            body: msg.body,
+            reply_to: msg.reply_to,
+            headers: Vec::new(),
+        }
+    }
+}
+
+impl From<types::BrokerMessage> for v0_2::types::BrokerMessage {
+    fn from(msg: types::BrokerMessage) -> Self {
+        Self {
+            subject: msg.subject,
+            body: msg.body,
+            reply_to: msg.reply_to,
+        }
+    }
+}
+
+/// Where a backend sends the replies and dead letters its handlers produce.
+#[async_trait::async_trait]
+pub(crate) trait Outbox: Send + Sync {
+    /// Publishes `msg` on behalf of the workload `workload_id`.
+    async fn publish(&self, workload_id: &str, msg: types::BrokerMessage) -> Result<(), String>;
+}
+
+/// A handler component pre-instantiated against the `handler` version it
+/// exports.
+enum HandlerPre {
+    Messaging(bindings::MessagingPre<SharedCtx>),
+    /// `handler@0.3.0`, whose result answers the message.
+    Replies(reply_bindings::MessagingRepliesPre<SharedCtx>),
+}
+
+impl HandlerPre {
+    fn new(instance_pre: wasmtime::component::InstancePre<SharedCtx>) -> anyhow::Result<Self> {
+        if let Ok(pre) = reply_bindings::MessagingRepliesPre::new(instance_pre.clone()) {
+            return Ok(Self::Replies(pre));
+        }
+        let pre = bindings::MessagingPre::new(instance_pre)
+            .map_err(anyhow::Error::from)
+            .context("failed to instantiate messaging pre")?;
+        Ok(Self::Messaging(pre))
+    }
+}
+
+/// A message on its way to the handler, and which attempt this is.
+struct Delivery {
+    msg: types::BrokerMessage,
+    attempt: u32,
+}
+
+/// Everything needed to deliver a message to one handler component, and to
+/// dead-letter it once delivery has failed for good.
+pub(crate) struct MessageHandler {
+    plugin_id: &'static str,
+    workload: ResolvedWorkload,
+    component_id: String,
+    /// `None` for a long-lived handler service, which is delivered to through
+    /// its trigger service instead of per-message instances.
+    pre: Option<HandlerPre>,
+    fuel_meter: FuelConsumptionMeter,
+    retry: RetryPolicy,
+    outbox: Arc<dyn Outbox>,
+    /// Cancelled when the component is unbound, ending its queue and any
+    /// pending retries.
+    cancel_token: CancellationToken,
+    trigger_service_queue: mpsc::Sender<Delivery>,
+}
+
+impl MessageHandler {
+    /// Prepares delivery to `component_id` and starts the queue that feeds
+    /// its trigger service, if it runs one.
+    pub(crate) async fn new(
+        plugin_id: &'static str,
+        workload: &ResolvedWorkload,
+        component_id: &str,
+        fuel_meter: FuelConsumptionMeter,
+        retry: RetryPolicy,
+        outbox: Arc<dyn Outbox>,
+        cancel_token: CancellationToken,
+    ) -> anyhow::Result<Arc<Self>> {
+        // A long-lived handler service has no per-component instance to
+        // pre-instantiate; messages are delivered to the running service
+        // instead. Only components get a `HandlerPre` for per-message work.
+        let pre = match workload.instantiate_pre(component_id).await {
+            Ok(instance_pre) => Some(HandlerPre::new(instance_pre)?),
+            Err(e) => {
+                trace!(component_id, error = %e, "no per-message instance (long-lived service); messages delivered to the service");
+                None
+            }
+        };
+        let (trigger_service_queue, queued) = mpsc::channel(TRIGGER_SERVICE_QUEUE);
+        let handler = Arc::new(Self {
+            plugin_id,
+            workload: workload.clone(),
+            component_id: component_id.to_string(),
+            pre,
+            fuel_meter,
+            retry,
+            outbox,
+            cancel_token,
+            trigger_service_queue,
+        });
+        tokio::spawn(handler.clone().feed_trigger_service(queued));
+        Ok(handler)
+    }
+
+    pub(crate) fn component_id(&self) -> &str {
+        &self.component_id
+    }
+
+    /// Hands a received message to the handler, retrying it with backoff per
+    /// the handler's [`RetryPolicy`] and dead-lettering it once retries run
+    /// out. A `handler@0.3.0` component's answer is published to the
+    /// message's `reply-to`. Returns once the message is queued or running,
+    /// not once it has been handled.
+    pub(crate) async fn submit(self: &Arc<Self>, msg: types::BrokerMessage) {
+        self.clone().schedule(Delivery { msg, attempt: 1 }).await;
+    }
+
+    /// Queues `delivery` for the workload's trigger service, which is handed
+    /// one message at a time to keep its in-memory state consistent, or runs
+    /// it on a fresh instance concurrently with any others.
+    fn schedule(self: Arc<Self>, delivery: Delivery) -> BoxFuture<'static, ()> {
+        async move {
+            let workload = &self.workload;
+            if workload
+                .http_handler()
+                .has_trigger_service_messaging(workload.id())
+                .await
+            {
+                tokio::select! {
+                    _ = self.cancel_token.cancelled() => {}
+                    _ = self.trigger_service_queue.send(delivery) => {}
+                }
+            } else {
+                tokio::spawn(self.clone().attempt(delivery));
+            }
+        }
+        .boxed()
+    }
+
+    /// Delivers queued messages to the trigger service in order until the
+    /// component is unbound.
+    async fn feed_trigger_service(self: Arc<Self>, mut queued: mpsc::Receiver<Delivery>) {
+        loop {
+            let delivery = tokio::select! {
+                _ = self.cancel_token.cancelled() => break,
+                next = queued.recv() => match next {
+                    Some(delivery) => delivery,
+                    None => break,
+                },
+            };
+            tokio::select! {
+                _ = self.cancel_token.cancelled() => break,
+                _ = self.clone().attempt(delivery) => {}
+            }
+        }
+    }
+
+    /// Makes one delivery attempt. A failure that has attempts left is
+    /// scheduled again once its backoff has passed.
+    async fn attempt(self: Arc<Self>, Delivery { msg, attempt }: Delivery) {
+        let error = match self.dispatch(msg.clone()).await {
+            Ok(Ok(reply)) => {
+                debug!(subject = %msg.subject, attempt, "Message handled successfully");
+                if let Some(body) = reply {
+                    self.reply(&msg, Ok(body)).await;
+                }
+                return;
+            }
+            Ok(Err(e)) => e,
+            Err(e) => format!("{e:#}"),
+        };
+        if !self.retry.should_retry(attempt) {
+            warn!(subject = %msg.subject, attempt, error = %error, "Error handling message");
+            self.dead_letter(&msg, &error, i64::from(attempt)).await;
+            self.reply(&msg, Err(&error)).await;
+            return;
+        }
+        let delay = self.retry.retry_delay(attempt);
+        warn!(subject = %msg.subject, attempt, error = %error, ?delay, "Error handling message; retrying");
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = self.cancel_token.cancelled() => {}
+                _ = tokio::time::sleep(delay) => {
+                    let attempt = attempt + 1;
+                    self.clone().schedule(Delivery { msg, attempt }).await;
+                }
+            }
+        });
+    }
+
+    /// Hands one message to the workload's trigger service when it runs one,
+    /// or to a fresh instance of the component otherwise, and returns the
+    /// handler's verdict.
+    pub(crate) async fn dispatch(&self, msg: types::BrokerMessage) -> HandlerOutcome {
+        let workload = &self.workload;
+        if workload
+            .http_handler()
+            .has_trigger_service_messaging(workload.id())
+            .await
+        {
+            // Only `handler@0.2.0` services are bound (see
+            // `check_service_handler`), and that version never answers.
+            let broker = crate::host::trigger_service::BrokerMessage {
+                subject: msg.subject,
+                body: msg.body,
+                reply_to: msg.reply_to,
+            };
+            return workload
+                .http_handler()
+                .deliver_trigger_service_message(workload.id(), broker)
+                .await
+                .map(|result| result.map(|()| None));
+        }
+
+        let Some(pre) = &self.pre else {
+            anyhow::bail!("no trigger service registered and no per-message instance");
+        };
+        let mut store = workload.new_store(&self.component_id).await?;
+        let span = tracing::span!(
+            tracing::Level::INFO,
+            "incoming_wasmcloud_message",
+            plugin = self.plugin_id,
+            subject = %msg.subject,
+            reply_to = %msg.reply_to.as_deref().unwrap_or("<none>"),
+        );
+        self.fuel_meter
+            .observe(
+                &[
+                    KeyValue::new("plugin", self.plugin_id),
+                    KeyValue::new("subject", msg.subject.to_string()),
+                ],
+                &mut store,
+                async move |store| match pre {
+                    HandlerPre::Messaging(pre) => {
+                        let proxy = pre
+                            .instantiate_async(&mut *store)
+                            .await
+                            .map_err(anyhow::Error::from)
+                            .with_context(|| {
+                                format!("failed to instantiate component {}", self.component_id)
+                            })?;
+                        let result = proxy
+                            .wasmcloud_messaging0_2_0_handler()
+                            .call_handle_message(store, &msg.into())
+                            .instrument(span)
+                            .await?;
+                        Ok(result.map(|()| None))
+                    }
+                    HandlerPre::Replies(pre) => {
+                        let proxy = pre
+                            .instantiate_async(&mut *store)
+                            .await
+                            .map_err(anyhow::Error::from)
+                            .with_context(|| {
+                                format!("failed to instantiate component {}", self.component_id)
+                            })?;
+                        proxy
+                            .wasmcloud_messaging0_3_0_handler()
+                            .call_handle_message(store, &msg)
+                            .instrument(span)
+                            .await
+                            .map_err(Into::into)
+                    }
+                },
+            )
+            .await
+    }
+
+    /// Answers a request a `handler@0.3.0` component is done with: the body it
+    /// returned, or an empty body with the error it failed with. Messages
+    /// without a `reply-to`, and older handlers, get no answer.
+    async fn reply(&self, msg: &types::BrokerMessage, result: Result<Vec<u8>, &str>) {
+        let (Some(reply_to), Some(HandlerPre::Replies(_))) = (&msg.reply_to, &self.pre) else {
+            return;
+        };
+        let (body, headers) = super::reply_parts(result);
+        let reply = types::BrokerMessage {
+            subject: reply_to.clone(),
+            reply_to: None,
+            body,
+            headers,
+        };
+        match self.outbox.publish(self.workload.id(), reply).await {
+            Ok(()) => trace!(subject = %msg.subject, %reply_to, "published reply"),
+            Err(e) => {
+                warn!(subject = %msg.subject, %reply_to, error = %e, "failed to publish reply")
+            }
+        }
+    }
+
+    /// Publishes a message that will not be retried again to the configured
+    /// dead-letter subject, if any.
+    pub(crate) async fn dead_letter(&self, msg: &types::BrokerMessage, error: &str, attempts: i64) {
+        // A dead letter that itself fails to be handled is not dead-lettered
+        // again, which would loop forever.
+        let Some(dead_letter_subject) = self
+            .retry
+            .dead_letter_subject
+            .as_ref()
+            .filter(|dead_letter_subject| **dead_letter_subject != msg.subject)
+        else {
+            return;
+        };
+        let letter = DeadLetter {
+            subject: &msg.subject,
+            reply_to: msg.reply_to.as_deref(),
+            body: &msg.body,
+            workload_id: self.workload.id(),
+            component_id: &self.component_id,
+            error,
+            attempts,
+        };
+        let dead = types::BrokerMessage {
+            subject: dead_letter_subject.clone(),
+            reply_to: None,
+            body: letter.to_bytes(),
+            headers: Vec::new(),
+        };
+        match self.outbox.publish(self.workload.id(), dead).await {
+            Ok(()) => debug!(subject = %msg.subject, %dead_letter_subject, "dead-lettered message"),
+            Err(e) => {
+                warn!(subject = %msg.subject, %dead_letter_subject, error = %e, "failed to publish dead letter")
+            }
+        }
+    }
+}
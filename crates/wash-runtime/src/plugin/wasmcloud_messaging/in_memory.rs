@@ -1,33 +1,56 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
+use super::jetstream::JetStreamConfig;
+use super::retry::RetryPolicy;
 use crate::engine::ctx::{ActiveCtx, SharedCtx, extract_active_ctx};
 use crate::engine::workload::{ResolvedWorkload, UnresolvedWorkload, WorkloadItem};
 use crate::observability::Meters;
 use crate::plugin::{HostPlugin, WitInterfaces};
 use crate::wit::{WitInterface, WitWorld};
-use anyhow::Context;
-use opentelemetry::KeyValue;
 use tokio::sync::{Notify, RwLock, oneshot};
-use tracing::{Instrument, debug, instrument, trace, warn};
+use tracing::{debug, instrument, warn};
 
 const PLUGIN_MESSAGING_MEMORY_ID: &str = "wasmcloud-messaging-memory";
-const MAX_QUEUE_SIZE: usize = 10000;
+pub(crate) const MAX_QUEUE_SIZE: usize = 10000;
 
 /// A component's message inbox, shared between the publisher side
 /// (`route_to_subscribers`) and the component's processing task.
-type Inbox = Arc<RwLock<VecDeque<types::BrokerMessage>>>;
+type Inbox = Arc<RwLock<VecDeque<Delivery>>>;
+
+/// Durable queues by `(stream, durable)`, standing in for the consumer state
+/// a JetStream server would hold.
+type Durables = HashMap<(String, String), (Inbox, Arc<Notify>)>;
+
+/// A queued message and how many times delivering it has already failed.
+struct Delivery {
+    msg: types::BrokerMessage,
+    failed_attempts: i64,
+}
 
 mod bindings {
     crate::wasmtime::component::bindgen!({
         world: "messaging",
         imports: { default: async | trappable | tracing },
         exports: { default: async | tracing },
+        with: { "wasmcloud:messaging/types@0.2.0": crate::plugin::wasmcloud_messaging::handler::v0_2::types },
+    });
+}
+
+mod reply_bindings {
+    crate::wasmtime::component::bindgen!({
+        world: "messaging-replies",
+        imports: { default: async | trappable | tracing },
+        exports: { default: async | tracing },
+        with: { "wasmcloud:messaging/types@0.3.0": crate::plugin::wasmcloud_messaging::handler::types },
     });
 }
 
-use bindings::wasmcloud::messaging::consumer::Host;
-use bindings::wasmcloud::messaging::types;
+use super::handler::{MessageHandler, Outbox, types, v0_2};
+use bindings::wasmcloud::messaging0_2_0::consumer as v0_2_consumer;
+use reply_bindings::wasmcloud::messaging0_3_0::consumer::Host;
+
+use super::HandlerOutcome;
 
 use crate::plugin::WorkloadTracker;
 
@@ -53,11 +76,15 @@ struct ComponentData {
     /// here; the component's processing task drains it.
     inbox: Inbox,
     notify: Arc<Notify>,
+    /// Set when the component is configured for JetStream delivery. Its inbox
+    /// is then the shared durable queue from [`InMemoryMessaging::durables`].
+    jetstream: Option<JetStreamConfig>,
+    retry: RetryPolicy,
 }
 
 /// Returns whether `subject` matches NATS subscription `pattern`, where `*`
 /// matches exactly one token and `>` matches one or more trailing tokens.
-fn subject_matches(pattern: &str, subject: &str) -> bool {
+pub(crate) fn subject_matches(pattern: &str, subject: &str) -> bool {
     let mut subject_tokens = subject.split('.');
     let mut pattern_tokens = pattern.split('.').peekable();
     while let Some(pat) = pattern_tokens.next() {
@@ -89,17 +116,27 @@ async fn route_to_subscribers(
     workload_id: &str,
     msg: &types::BrokerMessage,
 ) -> Result<(), String> {
-    let targets: Vec<(Inbox, Arc<Notify>)> = {
+    let mut targets: Vec<(Inbox, Arc<Notify>)> = Vec::new();
+    {
         let lock = plugin.tracker.read().await;
         let Some(item) = lock.workloads.get(workload_id) else {
             return Err("workload state not found".to_string());
         };
-        item.components
+        // Components bound to the same durable share one inbox and receive
+        // each message once between them, like pullers on a JetStream consumer.
+        for c in item
+            .components
             .values()
             .filter(|c| subscriptions_match(&c.subscriptions, &msg.subject))
-            .map(|c| (c.inbox.clone(), c.notify.clone()))
-            .collect()
-    };
+        {
+            if !targets
+                .iter()
+                .any(|(inbox, _)| Arc::ptr_eq(inbox, &c.inbox))
+            {
+                targets.push((c.inbox.clone(), c.notify.clone()));
+            }
+        }
+    }
 
     for (inbox, notify) in targets {
         {
@@ -107,7 +144,10 @@ async fn route_to_subscribers(
             if queue.len() >= MAX_QUEUE_SIZE {
                 return Err("message queue full".to_string());
             }
-            queue.push_back(msg.clone());
+            queue.push_back(Delivery {
+                msg: msg.clone(),
+                failed_attempts: 0,
+            });
         }
         notify.notify_one();
     }
@@ -119,10 +159,18 @@ async fn route_to_subscribers(
 /// Messages published by a workload are only handled within that same workload
 /// (per-workload isolation). This is useful for testing and development where
 /// a full NATS server is not needed.
+///
+/// Components configured with `jetstream_stream` get the same ack/redelivery
+/// semantics as the NATS plugin's JetStream mode. Their queue is keyed by
+/// stream and durable name and outlives the workload, so messages still queued
+/// or awaiting redelivery when a handler workload restarts are picked up by
+/// the next workload bound to the same durable. A queue left empty once its
+/// last handler is unbound is dropped.
 #[derive(Clone)]
 pub struct InMemoryMessaging {
     tracker: Arc<RwLock<WorkloadTracker<WorkloadData, ComponentData>>>,
     meters: Arc<RwLock<Meters>>,
+    durables: Arc<RwLock<Durables>>,
 }
 
 impl InMemoryMessaging {
@@ -130,6 +178,7 @@ impl InMemoryMessaging {
         Self {
             tracker: Arc::new(RwLock::new(WorkloadTracker::default())),
             meters: Default::default(),
+            durables: Default::default(),
         }
     }
 
@@ -150,6 +199,7 @@ impl InMemoryMessaging {
                 subject: subject.to_string(),
                 reply_to: None,
                 body,
+                headers: Vec::new(),
             },
         )
         .await
@@ -162,25 +212,27 @@ impl Default for InMemoryMessaging {
     }
 }
 
-impl<'a> Host for ActiveCtx<'a> {
-    #[instrument(name = "wasmcloud.messaging.request", skip_all, fields(subject = %subject, timeout_ms))]
+impl InMemoryMessaging {
+    async fn pending_requests(
+        &self,
+        workload_id: &str,
+    ) -> wasmtime::Result<Arc<RwLock<HashMap<String, oneshot::Sender<types::BrokerMessage>>>>> {
+        let lock = self.tracker.read().await;
+        match lock.get_workload_data(workload_id) {
+            Some(data) => Ok(data.pending_requests.clone()),
+            None => wasmtime::bail!("workload state not found"),
+        }
+    }
+
     async fn request(
-        &mut self,
+        &self,
+        workload_id: &str,
         subject: String,
         body: Vec<u8>,
+        headers: types::Headers,
         timeout_ms: u32,
     ) -> wasmtime::Result<Result<types::BrokerMessage, String>> {
-        let plugin = self.try_get_plugin::<InMemoryMessaging>(PLUGIN_MESSAGING_MEMORY_ID)?;
-
-        let workload_id = self.ctx.workload_id.to_string();
-
-        let pending_requests = {
-            let lock = plugin.tracker.read().await;
-            match lock.get_workload_data(&workload_id) {
-                Some(data) => data.pending_requests.clone(),
-                None => wasmtime::bail!("workload state not found"),
-            }
-        };
+        let pending_requests = self.pending_requests(workload_id).await?;
 
         // Generate a unique reply-to subject
         let reply_to = format!("_INBOX.{}", uuid::Uuid::new_v4());
@@ -199,11 +251,12 @@ impl<'a> Host for ActiveCtx<'a> {
             subject,
             reply_to: Some(reply_to.clone()),
             body,
+            headers,
         };
 
         debug!(subject = %msg.subject, reply_to = %msg.reply_to.as_deref().unwrap_or("<none>"), "Sending request");
         // Route the request to subscribers of its subject.
-        if let Err(e) = route_to_subscribers(&plugin, &workload_id, &msg).await {
+        if let Err(e) = route_to_subscribers(self, workload_id, &msg).await {
             pending_requests.write().await.remove(&reply_to);
             return Ok(Err(e));
         }
@@ -228,18 +281,12 @@ impl<'a> Host for ActiveCtx<'a> {
         }
     }
 
-    #[instrument(name = "wasmcloud.messaging.publish", skip_all, fields(subject = %msg.subject, reply_to = %msg.reply_to.as_deref().unwrap_or("<none>")))]
-    async fn publish(&mut self, msg: types::BrokerMessage) -> wasmtime::Result<Result<(), String>> {
-        let plugin = self.try_get_plugin::<InMemoryMessaging>(PLUGIN_MESSAGING_MEMORY_ID)?;
-
-        let workload_id = self.ctx.workload_id.to_string();
-        let pending_requests = {
-            let lock = plugin.tracker.read().await;
-            match lock.get_workload_data(&workload_id) {
-                Some(data) => data.pending_requests.clone(),
-                None => wasmtime::bail!("workload state not found"),
-            }
-        };
+    async fn publish_message(
+        &self,
+        workload_id: &str,
+        msg: types::BrokerMessage,
+    ) -> wasmtime::Result<Result<(), String>> {
+        let pending_requests = self.pending_requests(workload_id).await?;
 
         {
             let mut lock = pending_requests.write().await;
@@ -256,14 +303,61 @@ impl<'a> Host for ActiveCtx<'a> {
         debug!(subject = %msg.subject, reply_to = %msg.reply_to.as_deref().unwrap_or("<none>"), "Publishing message");
 
         // Regular publish - deliver to every subscriber of this subject.
-        match route_to_subscribers(&plugin, &workload_id, &msg).await {
+        match route_to_subscribers(self, workload_id, &msg).await {
             Ok(()) => Ok(Ok(())),
             Err(e) => Ok(Err(e)),
         }
     }
 }
 
-impl<'a> types::Host for ActiveCtx<'a> {}
+impl<'a> Host for ActiveCtx<'a> {
+    #[instrument(name = "wasmcloud.messaging.request", skip_all, fields(subject = %subject, timeout_ms))]
+    async fn request(
+        &mut self,
+        subject: String,
+        body: Vec<u8>,
+        headers: types::Headers,
+        timeout_ms: u32,
+    ) -> wasmtime::Result<Result<types::BrokerMessage, String>> {
+        let plugin = self.try_get_plugin::<InMemoryMessaging>(PLUGIN_MESSAGING_MEMORY_ID)?;
+        plugin
+            .request(&self.ctx.workload_id, subject, body, headers, timeout_ms)
+            .await
+    }
+
+    #[instrument(name = "wasmcloud.messaging.publish", skip_all, fields(subject = %msg.subject, reply_to = %msg.reply_to.as_deref().unwrap_or("<none>")))]
+    async fn publish(&mut self, msg: types::BrokerMessage) -> wasmtime::Result<Result<(), String>> {
+        let plugin = self.try_get_plugin::<InMemoryMessaging>(PLUGIN_MESSAGING_MEMORY_ID)?;
+        plugin.publish_message(&self.ctx.workload_id, msg).await
+    }
+}
+
+impl<'a> v0_2_consumer::Host for ActiveCtx<'a> {
+    #[instrument(name = "wasmcloud.messaging.request", skip_all, fields(subject = %subject, timeout_ms))]
+    async fn request(
+        &mut self,
+        subject: String,
+        body: Vec<u8>,
+        timeout_ms: u32,
+    ) -> wasmtime::Result<Result<v0_2::types::BrokerMessage, String>> {
+        let plugin = self.try_get_plugin::<InMemoryMessaging>(PLUGIN_MESSAGING_MEMORY_ID)?;
+        let response = plugin
+            .request(&self.ctx.workload_id, subject, body, Vec::new(), timeout_ms)
+            .await?;
+        Ok(response.map(Into::into))
+    }
+
+    #[instrument(name = "wasmcloud.messaging.publish", skip_all, fields(subject = %msg.subject, reply_to = %msg.reply_to.as_deref().unwrap_or("<none>")))]
+    async fn publish(
+        &mut self,
+        msg: v0_2::types::BrokerMessage,
+    ) -> wasmtime::Result<Result<(), String>> {
+        let plugin = self.try_get_plugin::<InMemoryMessaging>(PLUGIN_MESSAGING_MEMORY_ID)?;
+        plugin
+            .publish_message(&self.ctx.workload_id, msg.into())
+            .await
+    }
+}
 
 #[async_trait::async_trait]
 impl HostPlugin for InMemoryMessaging {
@@ -273,10 +367,14 @@ impl HostPlugin for InMemoryMessaging {
 
     fn world(&self) -> WitWorld {
         WitWorld {
-            imports: HashSet::from([WitInterface::from(
-                "wasmcloud:messaging/consumer,types@0.2.0",
-            )]),
-            exports: HashSet::from([WitInterface::from("wasmcloud:messaging/handler@0.2.0")]),
+            imports: HashSet::from([
+                WitInterface::from("wasmcloud:messaging/consumer,types@0.2.0"),
+                WitInterface::from("wasmcloud:messaging/consumer,types@0.3.0"),
+            ]),
+            exports: HashSet::from([
+                WitInterface::from("wasmcloud:messaging/handler@0.2.0"),
+                WitInterface::from("wasmcloud:messaging/handler@0.3.0"),
+            ]),
         }
     }
 
@@ -305,15 +403,18 @@ impl HostPlugin for InMemoryMessaging {
         component_handle: &mut WorkloadItem<'a>,
         interfaces: WitInterfaces<'_>,
     ) -> anyhow::Result<()> {
-        if !interfaces.contains("wasmcloud", "messaging", &[]) {
+        let Some(interface) = interfaces.get("wasmcloud", "messaging", &[]) else {
             return Ok(());
-        }
+        };
+        let interface_config = interface.config.clone();
 
-        bindings::wasmcloud::messaging::types::add_to_linker::<_, SharedCtx>(
+        v0_2::types::add_to_linker::<_, SharedCtx>(component_handle.linker(), extract_active_ctx)?;
+        v0_2_consumer::add_to_linker::<_, SharedCtx>(
             component_handle.linker(),
             extract_active_ctx,
         )?;
-        bindings::wasmcloud::messaging::consumer::add_to_linker::<_, SharedCtx>(
+        types::add_to_linker::<_, SharedCtx>(component_handle.linker(), extract_active_ctx)?;
+        reply_bindings::wasmcloud::messaging0_3_0::consumer::add_to_linker::<_, SharedCtx>(
             component_handle.linker(),
             extract_active_ctx,
         )?;
@@ -335,15 +436,54 @@ impl HostPlugin for InMemoryMessaging {
         // subscriber loop is set up either way (and its receive loop delivers to
         // the running service when one is registered).
         if super::exports_messaging_handler(&component_handle.world()) {
-            debug!(?subscriptions, "Tracking component in in-memory messaging");
+            let component_name = match component_handle {
+                WorkloadItem::Component(component) => component.name().to_string(),
+                WorkloadItem::Service(_) => {
+                    super::check_service_handler(&component_handle.world())?;
+                    "service".to_string()
+                }
+            };
+            // Mirror the NATS plugin's durable naming so a config that works
+            // against a real server behaves the same here.
+            let default_durable = super::nats::default_consumer_group(
+                component_handle.workload_namespace(),
+                component_handle.workload_name(),
+                &component_name,
+            );
+            let local_config = &component_handle.local_resources().config;
+            let lookup = |key: &str| {
+                local_config
+                    .get(key)
+                    .or_else(|| interface_config.get(key))
+                    .map(String::as_str)
+            };
+            let jetstream = JetStreamConfig::from_config(lookup, Some(&default_durable))?;
+            let retry = RetryPolicy::from_config(lookup)?;
+            if let Some(jetstream) = &jetstream {
+                jetstream.check_subscriptions(&subscriptions)?;
+            }
+            let (inbox, notify) = match &jetstream {
+                Some(js) => self
+                    .durables
+                    .write()
+                    .await
+                    .entry((js.stream.clone(), js.durable.clone()))
+                    .or_insert_with(|| (Arc::default(), Arc::new(Notify::new())))
+                    .clone(),
+                None => (Arc::default(), Arc::new(Notify::new())),
+            };
+
+            debug!(?subscriptions, jetstream = ?jetstream, "Tracking component in in-memory messaging");
             self.tracker.write().await.add_component(
                 component_handle,
                 ComponentData {
                     cancel_token: tokio_util::sync::CancellationToken::new(),
                     task_handle: None,
                     subscriptions,
-                    inbox: Arc::default(),
-                    notify: Arc::new(Notify::new()),
+                    inbox,
+                    notify,
+                    jetstream,
+                    retry,
                 },
             );
         }
@@ -356,41 +496,36 @@ impl HostPlugin for InMemoryMessaging {
         workload: &ResolvedWorkload,
         component_id: &str,
     ) -> anyhow::Result<()> {
-        let (inbox, notify, cancel_token) = {
+        let (inbox, notify, cancel_token, jetstream, retry) = {
             let lock = self.tracker.read().await;
             match lock.get_component_data(component_id) {
                 Some(data) => (
                     data.inbox.clone(),
                     data.notify.clone(),
                     data.cancel_token.clone(),
+                    data.jetstream.clone(),
+                    data.retry.clone(),
                 ),
                 None => return Ok(()),
             }
         };
 
-        // A long-lived handler service has no per-component instance to
-        // pre-instantiate; its receive loop delivers to the running service
-        // instead. Only components get a `MessagingPre` for per-message work.
-        let pre = match workload.instantiate_pre(component_id).await {
-            Ok(instance_pre) => Some(
-                bindings::MessagingPre::new(instance_pre)
-                    .map_err(anyhow::Error::from)
-                    .context("failed to instantiate messaging pre")?,
-            ),
-            Err(e) => {
-                trace!(component_id, error = %e, "no per-message instance (long-lived service); messages delivered to the service");
-                None
-            }
-        };
-
-        let workload = workload.clone();
-        let component_id = component_id.to_string();
-
         debug!("Spawning messaging processor for component {component_id}");
 
         // Spawn the message processing task
-        let task_component_id = component_id.clone();
-        let fuel_meter = self.meters.read().await.fuel_consumption.clone();
+        let task_component_id = component_id.to_string();
+        let handler = MessageHandler::new(
+            PLUGIN_MESSAGING_MEMORY_ID,
+            workload,
+            component_id,
+            self.meters.read().await.fuel_consumption.clone(),
+            retry,
+            Arc::new(self.clone()),
+            cancel_token.clone(),
+        )
+        .await?;
+        let inbox_is_empty = inbox.read().await.is_empty();
+        let notify_backlog = notify.clone();
 
         let handle = tokio::spawn(async move {
             loop {
@@ -402,108 +537,46 @@ impl HostPlugin for InMemoryMessaging {
                         // Drain every message queued since the last wakeup, so a
                         // coalesced notification can't strand a message.
                         loop {
-                        let msg = inbox.write().await.pop_front();
+                        let delivery = inbox.write().await.pop_front();
 
-                        let Some(msg) = msg else {
+                        let Some(delivery) = delivery else {
                             break;
                         };
 
-                        debug!(subject = %msg.subject, reply_to = %msg.reply_to.as_deref().unwrap_or("<none>"), "Processing message");
-
-                        // If this workload runs a long-lived trigger service for
-                        // messaging, deliver to it (preserving its in-memory
-                        // state) rather than instantiating a component per message.
-                        if workload
-                            .http_handler()
-                            .has_trigger_service_messaging(workload.id())
-                            .await
-                        {
-                            let broker = crate::host::trigger_service::BrokerMessage {
-                                subject: msg.subject.clone(),
-                                body: msg.body.clone(),
-                                reply_to: msg.reply_to.clone(),
-                            };
-                            match workload
-                                .http_handler()
-                                .deliver_trigger_service_message(workload.id(), broker)
-                                .await
-                            {
-                                Ok(Ok(())) => debug!(subject = %msg.subject, "trigger service handled message"),
-                                Ok(Err(e)) => {
-                                    warn!(subject = %msg.subject, error = %e, "trigger service message handler returned error")
-                                }
-                                Err(e) => {
-                                    warn!(subject = %msg.subject, error = %e, "failed to deliver message to trigger service")
+                        if let Some(jetstream) = &jetstream {
+                            let handler = handler.clone();
+                            let jetstream = jetstream.clone();
+                            let inbox = inbox.clone();
+                            let notify = notify.clone();
+                            tokio::spawn(async move {
+                                let msg = delivery.msg.clone();
+                                let delivered = delivery.failed_attempts + 1;
+                                let outcome = handler.dispatch(msg.clone()).await;
+                                if let Some(error) =
+                                    settle_durable_message(&jetstream, &inbox, &notify, delivery, outcome)
+                                        .await
+                                {
+                                    handler.dead_letter(&msg, &error, delivered).await;
                                 }
-                            }
+                            });
                             continue;
                         }
+                        let msg = delivery.msg;
 
-                        let Some(pre) = &pre else {
-                            warn!(
-                                subject = %msg.subject,
-                                component_id = %component_id,
-                                "no trigger service registered and no per-message instance; dropping message"
-                            );
-                            continue;
-                        };
-                        let mut store = match workload.new_store(&component_id).await {
-                            Err(e) => {
-                                warn!("failed to create store for component {component_id}: {e}");
-                                continue;
-                            }
-                            Ok(s) => s,
-                        };
-
-                        let proxy = match pre.instantiate_async(&mut store).await {
-                            Err(e) => {
-                                warn!("failed to instantiate component {component_id}: {e}");
-                                continue;
-                            }
-                            Ok(p) => p,
-                        };
-
-                        let span = tracing::span!(
-                            tracing::Level::INFO,
-                            "incoming_wasmcloud_message_memory",
-                            subject = %msg.subject,
-                            reply_to = %msg.reply_to.as_deref().unwrap_or("<none>"),
-                        );
-
-                        let fuel_meter = fuel_meter.clone();
-
-                        tokio::spawn(async move {
-                            let result = fuel_meter.observe(
-                                &[
-                                    KeyValue::new("plugin", PLUGIN_MESSAGING_MEMORY_ID),
-                                    KeyValue::new("subject", msg.subject.to_string()),
-                                ],
-                                &mut store,
-                                async move |store| {
-                                    proxy
-                                        .wasmcloud_messaging_handler()
-                                        .call_handle_message(store, &msg)
-                                        .instrument(span)
-                                        .await
-                                        .map_err(Into::into)
-                                }
-                            ).await;
-
-                            match result {
-                                Ok(_) => {
-                                    debug!("Message handled successfully");
-                                }
-                                Err(e) => {
-                                    warn!("Error handling message: {e}");
-                                }
-                            };
-                        });
+                        debug!(subject = %msg.subject, reply_to = %msg.reply_to.as_deref().unwrap_or("<none>"), "Processing message");
+                        handler.submit(msg).await;
                         }
                     }
                 }
             }
         });
 
+        // A durable queue may already hold messages left over from a previous
+        // workload bound to it; wake the new loop so it drains them.
+        if !inbox_is_empty {
+            notify_backlog.notify_one();
+        }
+
         // Store the task handle for tracking panics and cleanup
         {
             let mut lock = self.tracker.write().await;
@@ -526,6 +599,9 @@ impl HostPlugin for InMemoryMessaging {
             component_data.cancel_token.cancel();
             if let Some(handle) = component_data.task_handle {
                 handle.abort();
+                // Wait for the loop to drop its hold on a durable inbox, so
+                // the prune below sees it released.
+                let _ = handle.await;
             }
         };
 
@@ -534,14 +610,75 @@ impl HostPlugin for InMemoryMessaging {
             .await
             .remove_workload_with_cleanup(workload_id, workload_cleanup, component_cleanup)
             .await;
+        prune_durables(&mut *self.durables.write().await);
 
         Ok(())
     }
 }
 
+/// Drops the durable queues nothing can use any more: empty, and held by no
+/// bound component or pending redelivery. A queue with messages left is kept
+/// for the next workload bound to the same durable.
+fn prune_durables(durables: &mut Durables) {
+    durables.retain(|_, (inbox, _)| {
+        Arc::strong_count(inbox) > 1 || inbox.try_read().map_or(true, |queue| !queue.is_empty())
+    });
+}
+
+#[async_trait::async_trait]
+impl Outbox for InMemoryMessaging {
+    /// Replies and dead letters are routed like any other in-workload publish.
+    async fn publish(&self, workload_id: &str, msg: types::BrokerMessage) -> Result<(), String> {
+        self.publish_message(workload_id, msg)
+            .await
+            .map_err(|e| format!("{e:#}"))
+            .and_then(|result| result)
+    }
+}
+
+/// The in-memory counterpart of acking or nak'ing a JetStream message: a
+/// failed delivery is re-queued after its backoff while redeliveries remain.
+/// Returns the error once they are exhausted, so the caller can dead-letter it.
+async fn settle_durable_message(
+    config: &JetStreamConfig,
+    inbox: &Inbox,
+    notify: &Notify,
+    delivery: Delivery,
+    outcome: HandlerOutcome,
+) -> Option<String> {
+    let delivered = delivery.failed_attempts + 1;
+    let subject = &delivery.msg.subject;
+    let error = match outcome {
+        Ok(Ok(_)) => {
+            debug!(%subject, delivered, "durable message handled successfully");
+            return None;
+        }
+        Ok(Err(e)) => e,
+        Err(e) => format!("{e:#}"),
+    };
+
+    if !config.should_redeliver(delivered) {
+        warn!(%subject, delivered, error = %error, "error handling durable message; not redelivering");
+        return Some(error);
+    }
+    let delay = config.redelivery_delay(delivered);
+    warn!(%subject, delivered, error = %error, ?delay, "error handling durable message; scheduling redelivery");
+    tokio::time::sleep(delay).await;
+    inbox.write().await.push_back(Delivery {
+        failed_attempts: delivered,
+        ..delivery
+    });
+    notify.notify_one();
+    None
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{subject_matches, subscriptions_match};
+    use super::{
+        Delivery, Durables, Inbox, JetStreamConfig, Notify, prune_durables, settle_durable_message,
+        subject_matches, subscriptions_match, types,
+    };
+    use std::sync::Arc;
 
     #[test]
     fn exact_and_literal_tokens() {
@@ -582,4 +719,97 @@ mod tests {
         assert!(subscriptions_match(&subs, "tasks.leet"));
         assert!(!subscriptions_match(&subs, "tasks.reverse"));
     }
+
+    fn durable_config(max_deliver: &str) -> JetStreamConfig {
+        let config = std::collections::HashMap::from([
+            ("jetstream_stream", "ORDERS"),
+            ("jetstream_max_deliver", max_deliver),
+            ("jetstream_backoff_ms", "1"),
+        ]);
+        JetStreamConfig::from_config(|key| config.get(key).copied(), Some("orders"))
+            .ok()
+            .flatten()
+            .expect("valid JetStream config")
+    }
+
+    fn delivery(failed_attempts: i64) -> Delivery {
+        Delivery {
+            msg: types::BrokerMessage {
+                subject: "orders.created".to_string(),
+                reply_to: None,
+                body: b"order".to_vec(),
+                headers: Vec::new(),
+            },
+            failed_attempts,
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_durable_message_is_requeued_with_its_attempt_count() {
+        let config = durable_config("3");
+        let inbox = Inbox::default();
+        let notify = Notify::new();
+
+        let gave_up = settle_durable_message(
+            &config,
+            &inbox,
+            &notify,
+            delivery(0),
+            Ok(Err("boom".to_string())),
+        )
+        .await;
+
+        assert_eq!(gave_up, None);
+        let queue = inbox.read().await;
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.front().map(|d| d.failed_attempts), Some(1));
+    }
+
+    #[tokio::test]
+    async fn durable_message_is_dropped_on_success_or_after_max_deliver() {
+        let config = durable_config("3");
+        let inbox = Inbox::default();
+        let notify = Notify::new();
+
+        let handled =
+            settle_durable_message(&config, &inbox, &notify, delivery(0), Ok(Ok(None))).await;
+        assert_eq!(handled, None);
+        // The third delivery is the last one `max_deliver = 3` allows, so its
+        // error is handed back to be dead-lettered.
+        let gave_up = settle_durable_message(
+            &config,
+            &inbox,
+            &notify,
+            delivery(2),
+            Err(anyhow::anyhow!("trapped")),
+        )
+        .await;
+        assert_eq!(gave_up.as_deref(), Some("trapped"));
+
+        assert!(inbox.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn only_unused_empty_durables_are_pruned() {
+        let durable = |name: &str| ("ORDERS".to_string(), name.to_string());
+        let mut durables = Durables::new();
+        for name in ["idle", "backlog", "bound"] {
+            durables.insert(durable(name), (Inbox::default(), Arc::new(Notify::new())));
+        }
+        if let Some((inbox, _)) = durables.get(&durable("backlog")) {
+            inbox.write().await.push_back(delivery(0));
+        }
+        let bound = durables
+            .get(&durable("bound"))
+            .map(|(inbox, _)| inbox.clone());
+
+        prune_durables(&mut durables);
+
+        let mut left: Vec<_> = durables.keys().map(|(_, name)| name.as_str()).collect();
+        left.sort_unstable();
+        assert_eq!(left, ["backlog", "bound"]);
+        drop(bound);
+        prune_durables(&mut durables);
+        assert_eq!(durables.len(), 1);
+    }
 }
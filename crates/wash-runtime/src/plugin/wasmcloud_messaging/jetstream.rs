@@ -0,0 +1,299 @@
+//! Opt-in JetStream delivery for `wasmcloud:messaging` handlers.
+//!
+//! Setting `jetstream_stream` on the `wasmcloud:messaging` host-interface
+//! config (or on a component's local config, which takes precedence) switches
+//! a handler from core NATS subscriptions to a durable pull consumer on that
+//! stream. A message is acked only once `handle-message` returns `Ok`; an
+//! `Err` or a trap naks it with an exponential backoff until `max_deliver`
+//! deliveries have been attempted. The handler's single `subscriptions`
+//! subject (wildcards allowed) becomes the consumer's filter subject.
+//!
+//! Messages are handled concurrently, so the `all` ack policy, under which
+//! acking one message also acks every earlier one still in flight or awaiting
+//! redelivery, is refused.
+//!
+//! | key                     | default                  |
+//! |-------------------------|--------------------------|
+//! | `jetstream_stream`      | (unset: core NATS)       |
+//! | `jetstream_durable`     | derived consumer group   |
+//! | `jetstream_ack_policy`  | `explicit`               |
+//! | `jetstream_max_deliver` | `5` (`-1` for unlimited) |
+//! | `jetstream_backoff_ms`  | `1000`                   |
+
+use std::time::Duration;
+
+pub(crate) const STREAM_CONFIG: &str = "jetstream_stream";
+pub(crate) const DURABLE_CONFIG: &str = "jetstream_durable";
+pub(crate) const ACK_POLICY_CONFIG: &str = "jetstream_ack_policy";
+pub(crate) const MAX_DELIVER_CONFIG: &str = "jetstream_max_deliver";
+pub(crate) const BACKOFF_CONFIG: &str = "jetstream_backoff_ms";
+
+const DEFAULT_MAX_DELIVER: i64 = 5;
+const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// How handler outcomes are acknowledged back to the stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AckPolicy {
+    /// Every message is acked or nak'd individually.
+    Explicit,
+    /// Messages are never acked; a failed message is not redelivered.
+    None,
+}
+
+impl AckPolicy {
+    fn parse(value: &str) -> anyhow::Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "explicit" => Ok(Self::Explicit),
+            "none" => Ok(Self::None),
+            "all" => anyhow::bail!(
+                "`{ACK_POLICY_CONFIG}` `all` is not supported: messages are handled concurrently, so acking one would also ack earlier messages still in flight or awaiting redelivery; use `explicit`"
+            ),
+            other => anyhow::bail!(
+                "invalid `{ACK_POLICY_CONFIG}` `{other}`: expected `explicit` or `none`"
+            ),
+        }
+    }
+
+    /// Whether handler outcomes are reported back (and so failures redelivered).
+    pub(crate) fn acks(self) -> bool {
+        self != Self::None
+    }
+}
+
+impl From<AckPolicy> for async_nats::jetstream::consumer::AckPolicy {
+    fn from(policy: AckPolicy) -> Self {
+        match policy {
+            AckPolicy::Explicit => Self::Explicit,
+            AckPolicy::None => Self::None,
+        }
+    }
+}
+
+/// Resolved JetStream settings for one handler component.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct JetStreamConfig {
+    pub(crate) stream: String,
+    pub(crate) durable: String,
+    pub(crate) ack_policy: AckPolicy,
+    /// Maximum number of deliveries per message; `-1` means unlimited.
+    pub(crate) max_deliver: i64,
+    pub(crate) backoff: Duration,
+}
+
+impl JetStreamConfig {
+    /// Reads the `jetstream_*` keys through `lookup`. Returns `Ok(None)` when
+    /// `jetstream_stream` is unset. `default_durable` names the consumer when
+    /// `jetstream_durable` is unset; it is sanitized into a valid durable name.
+    pub(crate) fn from_config<'a>(
+        lookup: impl Fn(&str) -> Option<&'a str>,
+        default_durable: Option<&str>,
+    ) -> anyhow::Result<Option<Self>> {
+        let Some(stream) = lookup(STREAM_CONFIG).map(str::trim) else {
+            return Ok(None);
+        };
+        anyhow::ensure!(!stream.is_empty(), "`{STREAM_CONFIG}` cannot be empty");
+
+        let durable = match (lookup(DURABLE_CONFIG).map(str::trim), default_durable) {
+            (Some(durable), _) => {
+                validate_durable(durable)?;
+                durable.to_string()
+            }
+            (None, Some(default)) => sanitize_durable(default),
+            (None, None) => anyhow::bail!(
+                "`{DURABLE_CONFIG}` is required when JetStream delivery is used without a consumer group"
+            ),
+        };
+
+        let ack_policy = lookup(ACK_POLICY_CONFIG)
+            .map(AckPolicy::parse)
+            .transpose()?
+            .unwrap_or(AckPolicy::Explicit);
+
+        let max_deliver = match lookup(MAX_DELIVER_CONFIG) {
+            None => DEFAULT_MAX_DELIVER,
+            Some(raw) => {
+                let value: i64 = raw
+                    .trim()
+                    .parse()
+                    .map_err(|e| anyhow::anyhow!("invalid `{MAX_DELIVER_CONFIG}` `{raw}`: {e}"))?;
+                anyhow::ensure!(
+                    value == -1 || value >= 1,
+                    "invalid `{MAX_DELIVER_CONFIG}` `{value}`: must be -1 (unlimited) or at least 1"
+                );
+                value
+            }
+        };
+
+        let backoff = match lookup(BACKOFF_CONFIG) {
+            None => DEFAULT_BACKOFF,
+            Some(raw) => Duration::from_millis(
+                raw.trim()
+                    .parse()
+                    .map_err(|e| anyhow::anyhow!("invalid `{BACKOFF_CONFIG}` `{raw}`: {e}"))?,
+            ),
+        };
+
+        Ok(Some(Self {
+            stream: stream.to_string(),
+            durable,
+            ack_policy,
+            max_deliver,
+            backoff,
+        }))
+    }
+
+    /// A durable consumer filters on one subject, so a JetStream handler must
+    /// subscribe to exactly one (a wildcard can cover several).
+    pub(crate) fn check_subscriptions(&self, subscriptions: &[String]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            subscriptions.len() == 1,
+            "JetStream delivery from stream `{}` needs exactly one subscription subject, got {}; use a wildcard to cover several",
+            self.stream,
+            subscriptions.len()
+        );
+        Ok(())
+    }
+
+    /// Whether a message that failed on its `delivered`-th attempt (1-based)
+    /// should be delivered again.
+    pub(crate) fn should_redeliver(&self, delivered: i64) -> bool {
+        self.ack_policy.acks() && (self.max_deliver < 0 || delivered < self.max_deliver)
+    }
+
+    /// Delay before redelivering a message that failed on its `delivered`-th
+    /// attempt: the configured backoff, doubled per earlier attempt.
+    pub(crate) fn redelivery_delay(&self, delivered: i64) -> Duration {
+        super::retry::backoff_delay(self.backoff, delivered)
+    }
+}
+
+fn validate_durable(value: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(!value.is_empty(), "`{DURABLE_CONFIG}` cannot be empty");
+    anyhow::ensure!(
+        value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        "invalid `{DURABLE_CONFIG}` `{value}`: durable names may only contain ASCII letters, digits, `-`, and `_`"
+    );
+    Ok(())
+}
+
+/// Maps a consumer group name onto the narrower durable-name alphabet.
+fn sanitize_durable(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::plugin::wasmcloud_messaging::retry::MAX_BACKOFF;
+    use std::collections::HashMap;
+
+    fn parse(
+        pairs: &[(&str, &str)],
+        default_durable: Option<&str>,
+    ) -> anyhow::Result<Option<JetStreamConfig>> {
+        let config: HashMap<&str, &str> = pairs.iter().copied().collect();
+        JetStreamConfig::from_config(|key| config.get(key).copied(), default_durable)
+    }
+
+    #[test]
+    fn disabled_without_a_stream() {
+        assert_eq!(parse(&[(DURABLE_CONFIG, "orders")], None).unwrap(), None);
+    }
+
+    #[test]
+    fn defaults_apply_when_only_the_stream_is_set() {
+        let config = parse(&[(STREAM_CONFIG, "ORDERS")], Some("wasmcloud.ns.wl.c.0123"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.stream, "ORDERS");
+        assert_eq!(config.durable, "wasmcloud_ns_wl_c_0123");
+        assert_eq!(config.ack_policy, AckPolicy::Explicit);
+        assert_eq!(config.max_deliver, DEFAULT_MAX_DELIVER);
+        assert_eq!(config.backoff, DEFAULT_BACKOFF);
+    }
+
+    #[test]
+    fn explicit_settings_are_honored() {
+        let config = parse(
+            &[
+                (STREAM_CONFIG, "ORDERS"),
+                (DURABLE_CONFIG, "order-workers"),
+                (ACK_POLICY_CONFIG, "None"),
+                (MAX_DELIVER_CONFIG, "-1"),
+                (BACKOFF_CONFIG, "250"),
+            ],
+            None,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(config.durable, "order-workers");
+        assert_eq!(config.ack_policy, AckPolicy::None);
+        assert_eq!(config.max_deliver, -1);
+        assert_eq!(config.backoff, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        for pairs in [
+            &[(STREAM_CONFIG, "")][..],
+            &[(STREAM_CONFIG, "S"), (DURABLE_CONFIG, "has.dot")],
+            &[(STREAM_CONFIG, "S"), (ACK_POLICY_CONFIG, "sometimes")],
+            &[(STREAM_CONFIG, "S"), (ACK_POLICY_CONFIG, "all")],
+            &[(STREAM_CONFIG, "S"), (MAX_DELIVER_CONFIG, "0")],
+            &[(STREAM_CONFIG, "S"), (BACKOFF_CONFIG, "soon")],
+        ] {
+            assert!(parse(pairs, Some("group")).is_err(), "{pairs:?}");
+        }
+        // Broadcast consumers have no group to derive a durable name from.
+        assert!(parse(&[(STREAM_CONFIG, "S")], None).is_err());
+    }
+
+    #[test]
+    fn redelivery_backs_off_and_stops_at_max_deliver() {
+        let config = parse(
+            &[(STREAM_CONFIG, "S"), (MAX_DELIVER_CONFIG, "3")],
+            Some("g"),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(config.redelivery_delay(1), Duration::from_secs(1));
+        assert_eq!(config.redelivery_delay(3), Duration::from_secs(4));
+        assert_eq!(config.redelivery_delay(40), MAX_BACKOFF);
+        assert!(config.should_redeliver(2));
+        assert!(!config.should_redeliver(3));
+
+        let unacked = JetStreamConfig {
+            ack_policy: AckPolicy::None,
+            ..config
+        };
+        assert!(!unacked.should_redeliver(1));
+    }
+
+    #[test]
+    fn requires_exactly_one_subscription() {
+        let config = parse(&[(STREAM_CONFIG, "S")], Some("g")).unwrap().unwrap();
+        assert!(
+            config
+                .check_subscriptions(&["orders.>".to_string()])
+                .is_ok()
+        );
+        assert!(config.check_subscriptions(&[]).is_err());
+        assert!(
+            config
+                .check_subscriptions(&["a".to_string(), "b".to_string()])
+                .is_err()
+        );
+    }
+}
@@ -1,7 +1,10 @@
+mod handler;
 mod in_memory;
+mod jetstream;
 #[cfg(feature = "wasm_component_model_implements")]
 mod multiplexed;
 mod nats;
+mod retry;
 
 pub use in_memory::InMemoryMessaging;
 #[cfg(feature = "wasm_component_model_implements")]
@@ -11,6 +14,12 @@ pub use multiplexed::{
 };
 pub use nats::NatsMessaging;
 
+// Shared with the `wasi:messaging` plugin, which runs over the same transports.
+pub(crate) use in_memory::{MAX_QUEUE_SIZE, subject_matches};
+pub(crate) use nats::{
+    CONSUMER_GROUP_CONFIG, ConsumerGroup, header_map, sync_with_server, wit_headers,
+};
+
 /// Returns `true` if the world exports the `wasmcloud:messaging/handler`
 /// interface at any version. Matches via [`WitInterface::contains`] rather
 /// than set equality, so an exported `handler@0.2.x` is recognized no matter
@@ -20,6 +29,42 @@ pub(crate) fn exports_messaging_handler(world: &crate::wit::WitWorld) -> bool {
     world.exports.iter().any(|e| e.contains(&handler))
 }
 
+/// A long-lived service is handed messages through its trigger service, which
+/// drives only `handler@0.2.0`: refuses a service exporting the handler at any
+/// other version, instead of binding one no message could reach. Components
+/// run per message and may export `handler@0.3.0`.
+pub(crate) fn check_service_handler(world: &crate::wit::WitWorld) -> anyhow::Result<()> {
+    let handler = crate::wit::WitInterface::from("wasmcloud:messaging/handler@0.2.0");
+    anyhow::ensure!(
+        world.exports.iter().any(|e| e.contains(&handler)),
+        "a service can only handle messages through wasmcloud:messaging/handler@0.2.0; export that version, or move a handler@0.3.0 export into a component"
+    );
+    Ok(())
+}
+
+/// Header carrying the error when a `handler@0.3.0` component fails a request
+/// for good; the reply's body is then empty.
+pub(crate) const REPLY_ERROR_HEADER: &str = "wasmcloud-error";
+
+/// What a handler made of one message: the reply body a `handler@0.3.0`
+/// component answered with, if any, or its error. A trap or instantiation
+/// failure is the outer `Err`.
+pub(crate) type HandlerOutcome = anyhow::Result<Result<Option<Vec<u8>>, String>>;
+
+/// The body and headers of the reply to a request, given the body a handler
+/// answered with or the error it finally failed with. The error goes in
+/// [`REPLY_ERROR_HEADER`], with line breaks (which a NATS header value can't
+/// hold) flattened to spaces.
+pub(crate) fn reply_parts(result: Result<Vec<u8>, &str>) -> (Vec<u8>, Vec<(String, String)>) {
+    match result {
+        Ok(body) => (body, Vec::new()),
+        Err(error) => {
+            let error = error.replace(['\r', '\n'], " ");
+            (Vec::new(), vec![(REPLY_ERROR_HEADER.to_string(), error)])
+        }
+    }
+}
+
 /// Parses a comma-separated `subscriptions` config value into trimmed,
 /// non-empty subjects. Shared by the in-memory and NATS backends so they
 /// agree on how a configured subscription string maps to subjects.
@@ -35,7 +80,10 @@ pub(crate) fn parse_subscriptions(raw: Option<&str>) -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{exports_messaging_handler, parse_subscriptions};
+    use super::{
+        REPLY_ERROR_HEADER, check_service_handler, exports_messaging_handler, parse_subscriptions,
+        reply_parts,
+    };
     use crate::wit::{WitInterface, WitWorld};
     use std::collections::HashSet;
 
@@ -45,6 +93,7 @@ mod tests {
             "wasmcloud:messaging/handler",
             "wasmcloud:messaging/handler@0.2.0",
             "wasmcloud:messaging/handler@0.2.2",
+            "wasmcloud:messaging/handler@0.3.0",
         ] {
             let world = WitWorld {
                 imports: HashSet::new(),
@@ -71,6 +120,20 @@ mod tests {
         assert!(!exports_messaging_handler(&consumer));
     }
 
+    #[test]
+    fn services_must_export_the_handler_at_0_2_0() {
+        let exporting = |export: &str| WitWorld {
+            imports: HashSet::new(),
+            exports: HashSet::from([WitInterface::from(export)]),
+        };
+        assert!(check_service_handler(&exporting("wasmcloud:messaging/handler@0.2.0")).is_ok());
+        let err = check_service_handler(&exporting("wasmcloud:messaging/handler@0.3.0"))
+            .err()
+            .map(|e| e.to_string())
+            .unwrap_or_default();
+        assert!(err.contains("handler@0.2.0"), "{err}");
+    }
+
     #[test]
     fn parses_single_subject() {
         assert_eq!(
@@ -95,4 +158,22 @@ mod tests {
         );
         assert!(parse_subscriptions(None).is_empty());
     }
+
+    #[test]
+    fn replies_carry_the_body_or_the_error_header() {
+        assert_eq!(
+            reply_parts(Ok(b"pong".to_vec())),
+            (b"pong".to_vec(), vec![])
+        );
+        assert_eq!(
+            reply_parts(Err("bad input\nat line 2\r")),
+            (
+                vec![],
+                vec![(
+                    REPLY_ERROR_HEADER.to_string(),
+                    "bad input at line 2 ".to_string()
+                )]
+            )
+        );
+    }
 }
@@ -41,7 +41,7 @@ mod bindings {
     });
 }
 
-pub use bindings::wasmcloud::messaging::types::BrokerMessage;
+pub use bindings::wasmcloud::messaging0_2_0::types::BrokerMessage;
 
 /// The "implements id" threaded through every consumer host method: the backend
 /// a given named import is bound to. `Arc` so it is cheaply `Clone`d into each
@@ -62,7 +62,7 @@ pub trait MsgBackend: Send + Sync {
     async fn publish(&self, msg: BrokerMessage) -> Result<(), String>;
 }
 
-impl<'a> bindings::named_imports::wasmcloud::messaging::consumer::Host for ActiveCtx<'a> {
+impl<'a> bindings::named_imports::wasmcloud::messaging0_2_0::consumer::Host for ActiveCtx<'a> {
     #[instrument(name = "wasmcloud.messaging.request", skip_all, fields(subject = %subject, timeout_ms = timeout_ms))]
     async fn request(
         &mut self,
@@ -86,7 +86,7 @@ impl<'a> bindings::named_imports::wasmcloud::messaging::consumer::Host for Activ
 
 // `types` has no host functions or resources, so it is bound via the regular
 // (non-named) path; only `consumer` is multiplexed per import.
-impl<'a> bindings::wasmcloud::messaging::types::Host for ActiveCtx<'a> {}
+impl<'a> bindings::wasmcloud::messaging0_2_0::types::Host for ActiveCtx<'a> {}
 
 const DEFAULT_BACKEND: &str = "in-memory";
 const MULTIPLEXED_MESSAGING_ID: &str = "wasmcloud-messaging-multiplexed";
@@ -163,11 +163,11 @@ impl HostPlugin for MultiplexedMessaging {
 
         // `types` carries only record definitions; bind it via the regular
         // path (no per-import routing needed).
-        bindings::wasmcloud::messaging::types::add_to_linker::<_, SharedCtx>(
+        bindings::wasmcloud::messaging0_2_0::types::add_to_linker::<_, SharedCtx>(
             linker,
             extract_active_ctx,
         )?;
-        bindings::named_imports::wasmcloud::messaging::consumer::add_to_linker::<_, SharedCtx>(
+        bindings::named_imports::wasmcloud::messaging0_2_0::consumer::add_to_linker::<_, SharedCtx>(
             linker,
             &component,
             |name| self.mux.resolve(&registry, name),
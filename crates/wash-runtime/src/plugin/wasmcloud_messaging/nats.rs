@@ -1,11 +1,10 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
-use async_nats::Subscriber;
+use async_nats::{HeaderMap, HeaderName, HeaderValue, Subscriber};
 use futures::stream::StreamExt;
-use opentelemetry::KeyValue;
 use tokio::sync::RwLock;
-use tracing::{Instrument, debug, instrument, trace, warn};
+use tracing::{debug, instrument, warn};
 use wasmtime::error::Context as _;
 
 mod bindings {
@@ -13,12 +12,26 @@ mod bindings {
         world: "messaging",
         imports: { default: async | trappable | tracing },
         exports: { default: async | tracing },
+        with: { "wasmcloud:messaging/types@0.2.0": crate::plugin::wasmcloud_messaging::handler::v0_2::types },
     });
 }
 
-use bindings::wasmcloud::messaging::consumer::Host;
-use bindings::wasmcloud::messaging::types;
+mod reply_bindings {
+    crate::wasmtime::component::bindgen!({
+        world: "messaging-replies",
+        imports: { default: async | trappable | tracing },
+        exports: { default: async | tracing },
+        with: { "wasmcloud:messaging/types@0.3.0": crate::plugin::wasmcloud_messaging::handler::types },
+    });
+}
+
+use super::handler::{MessageHandler, Outbox, types, v0_2};
+use bindings::wasmcloud::messaging0_2_0::consumer as v0_2_consumer;
+use reply_bindings::wasmcloud::messaging0_3_0::consumer::Host;
 
+use super::HandlerOutcome;
+use super::jetstream::JetStreamConfig;
+use super::retry::RetryPolicy;
 use crate::engine::ctx::{ActiveCtx, SharedCtx, extract_active_ctx};
 use crate::engine::workload::{ResolvedWorkload, WorkloadItem};
 use crate::observability::Meters;
@@ -26,7 +39,7 @@ use crate::plugin::{HostPlugin, WitInterfaces, WorkloadTracker};
 use crate::wit::{WitInterface, WitWorld};
 
 const PLUGIN_MESSAGING_ID: &str = "wasmcloud-messaging";
-const CONSUMER_GROUP_CONFIG: &str = "consumer_group";
+pub(crate) const CONSUMER_GROUP_CONFIG: &str = "consumer_group";
 const BROADCAST_CONSUMER_GROUP: &str = "broadcast";
 const DEFAULT_CONSUMER_GROUP_PREFIX: &str = "wasmcloud";
 const MAX_DEFAULT_CONSUMER_GROUP_LEN: usize = 128;
@@ -34,18 +47,22 @@ const MAX_DEFAULT_CONSUMER_GROUP_LEN: usize = 128;
 pub struct ComponentData {
     subscriptions: Vec<String>,
     consumer_group: ConsumerGroup,
+    /// Set when the handler consumes from a JetStream durable instead of core
+    /// NATS subscriptions.
+    jetstream: Option<JetStreamConfig>,
+    retry: RetryPolicy,
     cancel_token: tokio_util::sync::CancellationToken,
     task_handle: Option<tokio::task::JoinHandle<()>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-enum ConsumerGroup {
+pub(crate) enum ConsumerGroup {
     Grouped(String),
     Broadcast,
 }
 
 impl ConsumerGroup {
-    fn resolve(
+    pub(crate) fn resolve(
         configured: Option<&str>,
         workload_namespace: &str,
         workload_name: &str,
@@ -65,7 +82,7 @@ impl ConsumerGroup {
         }
     }
 
-    fn name(&self) -> Option<&str> {
+    pub(crate) fn name(&self) -> Option<&str> {
         match self {
             Self::Grouped(name) => Some(name),
             Self::Broadcast => None,
@@ -90,54 +107,49 @@ impl NatsMessaging {
     }
 }
 
-impl<'a> Host for ActiveCtx<'a> {
-    #[instrument(name = "wasmcloud.messaging.request", skip_all, fields(subject = %subject, timeout_ms))]
+impl NatsMessaging {
     async fn request(
-        &mut self,
+        &self,
         subject: String,
         body: Vec<u8>,
+        headers: &[(String, String)],
         timeout_ms: u32,
-    ) -> wasmtime::Result<Result<types::BrokerMessage, String>> {
-        let plugin = self.try_get_plugin::<NatsMessaging>(PLUGIN_MESSAGING_ID)?;
-
+    ) -> Result<types::BrokerMessage, String> {
+        let headers = header_map(headers)?;
         let timeout_duration = std::time::Duration::from_millis(timeout_ms as u64);
-        let request_future = plugin.client.request(subject, body.into());
+        let request_future = self
+            .client
+            .request_with_headers(subject, headers, body.into());
 
         let resp = match tokio::time::timeout(timeout_duration, request_future).await {
             Ok(Ok(msg)) => msg,
             Ok(Err(e)) => {
                 warn!("failed to send request: {e}");
-                return Ok(Err(format!("failed to send request: {e}")));
+                return Err(format!("failed to send request: {e}"));
             }
             Err(_) => {
                 warn!("request timed out after {timeout_ms}ms");
-                return Ok(Err(format!("request timed out after {timeout_ms}ms")));
+                return Err(format!("request timed out after {timeout_ms}ms"));
             }
         };
-        let reply_to = resp.reply.as_ref().map(|r| r.to_string());
-        Ok(Ok(types::BrokerMessage {
-            subject: resp.subject.to_string(),
-            reply_to,
-            body: resp.payload.into(),
-        }))
+        Ok(broker_message(resp))
     }
 
-    #[instrument(name = "wasmcloud.messaging.publish", skip_all, fields(subject = %msg.subject, reply_to = %msg.reply_to.as_deref().unwrap_or("<none>")))]
-    async fn publish(&mut self, msg: types::BrokerMessage) -> wasmtime::Result<Result<(), String>> {
-        let plugin = self.try_get_plugin::<NatsMessaging>(PLUGIN_MESSAGING_ID)?;
-
+    async fn publish(&self, msg: types::BrokerMessage) -> wasmtime::Result<Result<(), String>> {
+        let headers = match header_map(&msg.headers) {
+            Ok(headers) => headers,
+            Err(e) => return Ok(Err(e)),
+        };
         let subject = msg.subject;
 
         if let Some(reply_to) = msg.reply_to {
-            plugin
-                .client
-                .publish_with_reply(subject, reply_to, msg.body.into())
+            self.client
+                .publish_with_reply_and_headers(subject, reply_to, headers, msg.body.into())
                 .await
                 .context("failed to send message")?;
         } else {
-            plugin
-                .client
-                .publish(subject, msg.body.into())
+            self.client
+                .publish_with_headers(subject, headers, msg.body.into())
                 .await
                 .context("failed to send message")?;
         }
@@ -146,7 +158,50 @@ impl<'a> Host for ActiveCtx<'a> {
     }
 }
 
-impl<'a> types::Host for ActiveCtx<'a> {}
+impl<'a> Host for ActiveCtx<'a> {
+    #[instrument(name = "wasmcloud.messaging.request", skip_all, fields(subject = %subject, timeout_ms))]
+    async fn request(
+        &mut self,
+        subject: String,
+        body: Vec<u8>,
+        headers: types::Headers,
+        timeout_ms: u32,
+    ) -> wasmtime::Result<Result<types::BrokerMessage, String>> {
+        let plugin = self.try_get_plugin::<NatsMessaging>(PLUGIN_MESSAGING_ID)?;
+        Ok(plugin.request(subject, body, &headers, timeout_ms).await)
+    }
+
+    #[instrument(name = "wasmcloud.messaging.publish", skip_all, fields(subject = %msg.subject, reply_to = %msg.reply_to.as_deref().unwrap_or("<none>")))]
+    async fn publish(&mut self, msg: types::BrokerMessage) -> wasmtime::Result<Result<(), String>> {
+        let plugin = self.try_get_plugin::<NatsMessaging>(PLUGIN_MESSAGING_ID)?;
+        plugin.publish(msg).await
+    }
+}
+
+impl<'a> v0_2_consumer::Host for ActiveCtx<'a> {
+    #[instrument(name = "wasmcloud.messaging.request", skip_all, fields(subject = %subject, timeout_ms))]
+    async fn request(
+        &mut self,
+        subject: String,
+        body: Vec<u8>,
+        timeout_ms: u32,
+    ) -> wasmtime::Result<Result<v0_2::types::BrokerMessage, String>> {
+        let plugin = self.try_get_plugin::<NatsMessaging>(PLUGIN_MESSAGING_ID)?;
+        Ok(plugin
+            .request(subject, body, &[], timeout_ms)
+            .await
+            .map(Into::into))
+    }
+
+    #[instrument(name = "wasmcloud.messaging.publish", skip_all, fields(subject = %msg.subject, reply_to = %msg.reply_to.as_deref().unwrap_or("<none>")))]
+    async fn publish(
+        &mut self,
+        msg: v0_2::types::BrokerMessage,
+    ) -> wasmtime::Result<Result<(), String>> {
+        let plugin = self.try_get_plugin::<NatsMessaging>(PLUGIN_MESSAGING_ID)?;
+        plugin.publish(msg.into()).await
+    }
+}
 
 #[async_trait::async_trait]
 impl HostPlugin for NatsMessaging {
@@ -156,11 +211,15 @@ impl HostPlugin for NatsMessaging {
 
     fn world(&self) -> WitWorld {
         WitWorld {
-            imports: HashSet::from([WitInterface::from(
-                "wasmcloud:messaging/consumer,types@0.2.0",
-            )]),
-
-            exports: HashSet::from([WitInterface::from("wasmcloud:messaging/handler@0.2.0")]),
+            imports: HashSet::from([
+                WitInterface::from("wasmcloud:messaging/consumer,types@0.2.0"),
+                WitInterface::from("wasmcloud:messaging/consumer,types@0.3.0"),
+            ]),
+
+            exports: HashSet::from([
+                WitInterface::from("wasmcloud:messaging/handler@0.2.0"),
+                WitInterface::from("wasmcloud:messaging/handler@0.3.0"),
+            ]),
         }
     }
 
@@ -183,12 +242,15 @@ impl HostPlugin for NatsMessaging {
         // the host-interface fallback before borrowing the component.
         let interface_subscriptions = interface.config.get("subscriptions").cloned();
         let interface_consumer_group = interface.config.get(CONSUMER_GROUP_CONFIG).cloned();
+        let interface_config = interface.config.clone();
 
-        bindings::wasmcloud::messaging::types::add_to_linker::<_, SharedCtx>(
+        v0_2::types::add_to_linker::<_, SharedCtx>(component_handle.linker(), extract_active_ctx)?;
+        v0_2_consumer::add_to_linker::<_, SharedCtx>(
             component_handle.linker(),
             extract_active_ctx,
         )?;
-        bindings::wasmcloud::messaging::consumer::add_to_linker::<_, SharedCtx>(
+        types::add_to_linker::<_, SharedCtx>(component_handle.linker(), extract_active_ctx)?;
+        reply_bindings::wasmcloud::messaging0_3_0::consumer::add_to_linker::<_, SharedCtx>(
             component_handle.linker(),
             extract_active_ctx,
         )?;
@@ -215,7 +277,10 @@ impl HostPlugin for NatsMessaging {
             let raw_subscriptions = super::parse_subscriptions(raw.as_deref());
             let component_name = match component_handle {
                 WorkloadItem::Component(component) => component.name().to_string(),
-                WorkloadItem::Service(_) => "service".to_string(),
+                WorkloadItem::Service(_) => {
+                    super::check_service_handler(&component_handle.world())?;
+                    "service".to_string()
+                }
             };
             let consumer_group = ConsumerGroup::resolve(
                 local_consumer_group
@@ -225,11 +290,24 @@ impl HostPlugin for NatsMessaging {
                 component_handle.workload_name(),
                 &component_name,
             )?;
+            let local_config = &component_handle.local_resources().config;
+            let lookup = |key: &str| {
+                local_config
+                    .get(key)
+                    .or_else(|| interface_config.get(key))
+                    .map(String::as_str)
+            };
+            let jetstream = JetStreamConfig::from_config(lookup, consumer_group.name())?;
+            let retry = RetryPolicy::from_config(lookup)?;
+            if let Some(jetstream) = &jetstream {
+                jetstream.check_subscriptions(&raw_subscriptions)?;
+            }
 
             debug!(
                 component_id = component_handle.id(),
                 subscriptions = ?raw_subscriptions,
                 consumer_group = consumer_group.name().unwrap_or(BROADCAST_CONSUMER_GROUP),
+                jetstream_stream = jetstream.as_ref().map(|js| js.stream.as_str()),
                 "tracking handler component for NATS messaging"
             );
             self.tracker.write().await.add_component(
@@ -238,6 +316,8 @@ impl HostPlugin for NatsMessaging {
                     cancel_token: tokio_util::sync::CancellationToken::new(),
                     subscriptions: raw_subscriptions,
                     consumer_group,
+                    jetstream,
+                    retry,
                     task_handle: None,
                 },
             );
@@ -254,13 +334,15 @@ impl HostPlugin for NatsMessaging {
     ) -> anyhow::Result<()> {
         debug!("on_workload_resolved entered for NATS messaging");
 
-        let (cancel_token, subjects, consumer_group) = {
+        let (cancel_token, subjects, consumer_group, jetstream, retry) = {
             let lock = self.tracker.read().await;
             match lock.get_component_data(component_id) {
                 Some(data) => (
                     data.cancel_token.clone(),
                     data.subscriptions.clone(),
                     data.consumer_group.clone(),
+                    data.jetstream.clone(),
+                    data.retry.clone(),
                 ),
                 None => {
                     debug!("no tracker entry for component, skipping subscription setup");
@@ -276,24 +358,79 @@ impl HostPlugin for NatsMessaging {
             return Ok(());
         }
 
-        // A long-lived handler service has no per-component instance to
-        // pre-instantiate; its receive loop delivers to the running service
-        // instead. Only components get a `MessagingPre` for per-message work.
-        let pre = match workload.instantiate_pre(component_id).await {
-            Ok(instance_pre) => Some(
-                bindings::MessagingPre::new(instance_pre)
-                    .context("failed to instantiate messaging pre")?,
-            ),
-            Err(e) => {
-                trace!(component_id, error = %e, "no per-message instance (long-lived service); messages delivered to the service");
-                None
+        let tracker_component_id = component_id.to_string();
+        let handler = MessageHandler::new(
+            PLUGIN_MESSAGING_ID,
+            workload,
+            component_id,
+            self.meters.read().await.fuel_consumption.clone(),
+            retry,
+            Arc::new(self.clone()),
+            cancel_token.clone(),
+        )
+        .await?;
+
+        let handle = match jetstream {
+            Some(jetstream) => {
+                self.consume_jetstream(handler, subjects, jetstream, cancel_token)
+                    .await?
+            }
+            None => {
+                self.subscribe_core(handler, subjects, consumer_group, cancel_token)
+                    .await?
+            }
+        };
+
+        {
+            let mut lock = self.tracker.write().await;
+            if let Some(data) = lock.get_component_data_mut(&tracker_component_id) {
+                data.task_handle = Some(handle);
+            } else {
+                warn!(
+                    component_id = %tracker_component_id,
+                    "tracker entry vanished before task handle could be stored"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn on_workload_unbind(
+        &self,
+        workload_id: &str,
+        _interfaces: WitInterfaces<'_>,
+    ) -> anyhow::Result<()> {
+        let workload_cleanup = |_| async {};
+        let component_cleanup = |component_data: ComponentData| async move {
+            component_data.cancel_token.cancel();
+            if let Some(handle) = component_data.task_handle {
+                handle.abort();
             }
         };
 
-        let workload = workload.clone();
-        let component_id = component_id.to_string();
-        let tracker_component_id = component_id.clone();
+        self.tracker
+            .write()
+            .await
+            .remove_workload_with_cleanup(workload_id, workload_cleanup, component_cleanup)
+            .await;
 
+        Ok(())
+    }
+}
+
+impl NatsMessaging {
+    /// Subscribes to `subjects` with core NATS (queue) subscriptions and spawns
+    /// the loop that hands each message to the component. A failed delivery is
+    /// retried in-process per the handler's [`RetryPolicy`] and dead-lettered
+    /// once retries run out; nothing survives a host restart.
+    async fn subscribe_core(
+        &self,
+        handler: Arc<MessageHandler>,
+        subjects: Vec<String>,
+        consumer_group: ConsumerGroup,
+        cancel_token: tokio_util::sync::CancellationToken,
+    ) -> anyhow::Result<tokio::task::JoinHandle<()>> {
         let mut subscriptions = Vec::<Subscriber>::new();
         for subject in &subjects {
             debug!(
@@ -345,7 +482,6 @@ impl HostPlugin for NatsMessaging {
         }
 
         let mut messages = futures::stream::select_all(subscriptions);
-        let fuel_meter = self.meters.read().await.fuel_consumption.clone();
 
         let span = tracing::Span::current();
         let handle = tokio::spawn(async move {
@@ -361,7 +497,7 @@ impl HostPlugin for NatsMessaging {
                             None => {
                                 warn!(
                                     parent: &span,
-                                    component_id = %component_id,
+                                    component_id = %handler.component_id(),
                                     "NATS subscriber stream closed unexpectedly; handler will stop receiving messages"
                                 );
                                 break;
@@ -371,151 +507,225 @@ impl HostPlugin for NatsMessaging {
                             }
                         };
 
-                        let subject = msg.subject.to_string();
-                        let reply_to = msg.reply.as_ref().map(|r| r.to_string());
-                        let body: Vec<u8> = msg.payload.into();
-
-                        // If this workload runs a long-lived trigger service for
-                        // messaging, deliver to it (preserving its in-memory
-                        // state) rather than instantiating a component per message.
-                        if workload
-                            .http_handler()
-                            .has_trigger_service_messaging(workload.id())
-                            .await
-                        {
-                            let broker = crate::host::trigger_service::BrokerMessage {
-                                subject: subject.clone(),
-                                body,
-                                reply_to,
-                            };
-                            match workload
-                                .http_handler()
-                                .deliver_trigger_service_message(workload.id(), broker)
-                                .await
-                            {
-                                Ok(Ok(())) => debug!(%subject, "trigger service handled message"),
-                                Ok(Err(e)) => {
-                                    warn!(%subject, error = %e, "trigger service message handler returned error")
-                                }
-                                Err(e) => {
-                                    warn!(%subject, error = %e, "failed to deliver message to trigger service")
-                                }
-                            }
-                            continue;
-                        }
-
-                        let Some(pre) = &pre else {
-                            warn!(
-                                %subject,
-                                component_id = %component_id,
-                                "no trigger service registered and no per-message instance; dropping message"
-                            );
-                            continue;
-                        };
-                        let mut store = match workload.new_store(&component_id).await {
-                            Err(e) => {
-                                warn!("failed to create store for component {component_id}: {e}");
-                                continue;
+                        handler.submit(broker_message(msg)).await;
+                    }
+                    _ = cancel_token.cancelled() => {
+                        debug!(
+                            parent: &span,
+                            component_id = %handler.component_id(),
+                            "NATS subscriber loop cancelled"
+                        );
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(handle)
+    }
+
+    /// Binds a durable pull consumer on the configured stream, filtered to
+    /// `subjects`, and spawns the loop that hands each message to the
+    /// component. A message is acked once the handler returns `Ok`; an `Err`
+    /// or a trap naks it with backoff until `max_deliver` is reached, after
+    /// which it is terminated so the server stops redelivering it and is
+    /// dead-lettered.
+    async fn consume_jetstream(
+        &self,
+        handler: Arc<MessageHandler>,
+        subjects: Vec<String>,
+        config: JetStreamConfig,
+        cancel_token: tokio_util::sync::CancellationToken,
+    ) -> anyhow::Result<tokio::task::JoinHandle<()>> {
+        use async_nats::jetstream::consumer::pull;
+
+        let context = async_nats::jetstream::new((*self.client).clone());
+        let stream = context
+            .get_stream(&config.stream)
+            .await
+            .with_context(|| format!("failed to look up JetStream stream {}", config.stream))?;
+        // An existing durable keeps the configuration it was created with;
+        // the settings here only apply the first time it is created.
+        let filter_subject = subjects.first().cloned().unwrap_or_default();
+        let consumer = stream
+            .get_or_create_consumer(
+                &config.durable,
+                pull::Config {
+                    durable_name: Some(config.durable.clone()),
+                    ack_policy: config.ack_policy.into(),
+                    max_deliver: config.max_deliver,
+                    filter_subject,
+                    ..Default::default()
+                },
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to bind durable consumer {} on stream {}",
+                    config.durable, config.stream
+                )
+            })?;
+        let mut messages = consumer
+            .messages()
+            .await
+            .context("failed to start JetStream consumer")?;
+        debug!(
+            stream = %config.stream,
+            durable = %config.durable,
+            ?subjects,
+            "bound JetStream durable consumer"
+        );
+
+        let span = tracing::Span::current();
+        Ok(tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    next = messages.next() => {
+                        let message = match next {
+                            None => {
+                                warn!(
+                                    parent: &span,
+                                    component_id = %handler.component_id(),
+                                    "JetStream consumer stream closed unexpectedly; handler will stop receiving messages"
+                                );
+                                break;
                             }
-                            Ok(s) => s,
-                        };
-                        let proxy = match pre.instantiate_async(&mut store).await {
-                            Err(e) => {
-                                warn!("failed to instantiate component {component_id}: {e}");
+                            Some(Err(e)) => {
+                                warn!(parent: &span, error = %e, "failed to receive JetStream message");
                                 continue;
                             }
-                            Ok(p) => p,
+                            Some(Ok(message)) => message,
                         };
+
+                        // The reply subject of a JetStream message is its ack
+                        // subject, not a reply inbox the handler could answer.
                         let msg = types::BrokerMessage {
-                            subject,
-                            reply_to,
-                            body,
+                            subject: message.subject.to_string(),
+                            reply_to: None,
+                            body: message.payload.to_vec(),
+                            headers: wit_headers(message.headers.as_ref()),
                         };
+                        let delivered = message.info().map(|info| info.delivered).unwrap_or(1);
 
-                        let span = tracing::span!(
-                            tracing::Level::INFO,
-                            "incoming_wasmcloud_message",
-                            subject = %msg.subject,
-                            reply_to = %msg.reply_to.as_deref().unwrap_or("<none>"),
-                        );
-
-                        let fuel_meter = fuel_meter.clone();
-
+                        let handler = handler.clone();
+                        let config = config.clone();
                         tokio::spawn(async move {
-                            let result = fuel_meter.observe(
-                                &[
-                                    KeyValue::new("plugin", PLUGIN_MESSAGING_ID),
-                                    KeyValue::new("subject", msg.subject.to_string()),
-                                ],
-                                &mut store,
-                                async move |store| {
-                                    proxy
-                                        .wasmcloud_messaging_handler()
-                                        .call_handle_message(store, &msg)
-                                        .instrument(span)
-                                        .await
-                                        .map_err(Into::into)
-                                }
-                            ).await;
-
-                            match result {
-                                Ok(_) => {
-                                    debug!("Message handled successfully");
-                                }
-                                Err(e) => {
-                                    warn!("Error handling message: {e}");
-                                }
-                            };
+                            let outcome = handler.dispatch(msg.clone()).await;
+                            settle_jetstream_message(&handler, &message, &config, delivered, &msg, outcome)
+                                .await;
                         });
                     }
                     _ = cancel_token.cancelled() => {
                         debug!(
                             parent: &span,
-                            component_id = %component_id,
-                            "NATS subscriber loop cancelled"
+                            component_id = %handler.component_id(),
+                            "JetStream consumer loop cancelled"
                         );
                         break;
                     }
                 }
             }
-        });
-
-        {
-            let mut lock = self.tracker.write().await;
-            if let Some(data) = lock.get_component_data_mut(&tracker_component_id) {
-                data.task_handle = Some(handle);
-            } else {
-                warn!(
-                    component_id = %tracker_component_id,
-                    "tracker entry vanished before task handle could be stored"
-                );
-            }
-        }
+        }))
+    }
+}
 
-        Ok(())
+#[async_trait::async_trait]
+impl Outbox for NatsMessaging {
+    async fn publish(&self, _workload_id: &str, msg: types::BrokerMessage) -> Result<(), String> {
+        NatsMessaging::publish(self, msg)
+            .await
+            .map_err(|e| format!("{e:#}"))
+            .and_then(|result| result)
     }
+}
 
-    async fn on_workload_unbind(
-        &self,
-        workload_id: &str,
-        _interfaces: WitInterfaces<'_>,
-    ) -> anyhow::Result<()> {
-        let workload_cleanup = |_| async {};
-        let component_cleanup = |component_data: ComponentData| async move {
-            component_data.cancel_token.cancel();
-            if let Some(handle) = component_data.task_handle {
-                handle.abort();
+/// Reports a handler outcome back to JetStream: ack on success, a delayed nak
+/// while redeliveries remain, and a terminate plus dead letter once they are
+/// exhausted.
+async fn settle_jetstream_message(
+    handler: &MessageHandler,
+    message: &async_nats::jetstream::Message,
+    config: &JetStreamConfig,
+    delivered: i64,
+    msg: &types::BrokerMessage,
+    outcome: HandlerOutcome,
+) {
+    use async_nats::jetstream::AckKind;
+
+    let subject = &msg.subject;
+    let error = match outcome {
+        Ok(Ok(_)) => {
+            debug!(%subject, delivered, "JetStream message handled successfully");
+            if config.ack_policy.acks()
+                && let Err(e) = message.ack().await
+            {
+                warn!(%subject, error = %e, "failed to ack JetStream message");
             }
-        };
+            return;
+        }
+        Ok(Err(e)) => e,
+        Err(e) => format!("{e:#}"),
+    };
+
+    if !config.ack_policy.acks() {
+        warn!(%subject, delivered, error = %error, "error handling JetStream message; ack policy is none, not redelivering");
+        handler.dead_letter(msg, &error, delivered).await;
+        return;
+    }
+    let kind = if config.should_redeliver(delivered) {
+        let delay = config.redelivery_delay(delivered);
+        warn!(%subject, delivered, error = %error, ?delay, "error handling JetStream message; scheduling redelivery");
+        AckKind::Nak(Some(delay))
+    } else {
+        warn!(%subject, delivered, error = %error, "error handling JetStream message; max deliveries reached");
+        handler.dead_letter(msg, &error, delivered).await;
+        AckKind::Term
+    };
+    if let Err(e) = message.ack_with(kind).await {
+        warn!(%subject, error = %e, "failed to nak JetStream message");
+    }
+}
 
-        self.tracker
-            .write()
-            .await
-            .remove_workload_with_cleanup(workload_id, workload_cleanup, component_cleanup)
-            .await;
+/// Converts a received NATS message to the WIT message a handler sees.
+fn broker_message(msg: async_nats::Message) -> types::BrokerMessage {
+    types::BrokerMessage {
+        subject: msg.subject.to_string(),
+        reply_to: msg.reply.as_ref().map(|r| r.to_string()),
+        headers: wit_headers(msg.headers.as_ref()),
+        body: msg.payload.into(),
+    }
+}
 
-        Ok(())
+/// Builds NATS headers from WIT ones, rejecting a name or value NATS can't
+/// carry instead of panicking on it.
+pub(crate) fn header_map(headers: &[(String, String)]) -> Result<HeaderMap, String> {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        let name = name
+            .parse::<HeaderName>()
+            .map_err(|_| format!("invalid header name {name:?}"))?;
+        let value = value
+            .parse::<HeaderValue>()
+            .map_err(|_| format!("invalid value for header {name}"))?;
+        map.append(name, value);
     }
+    Ok(map)
+}
+
+/// Flattens NATS headers to `(name, value)` pairs, sorted by name so a
+/// component sees them in a stable order.
+pub(crate) fn wit_headers(headers: Option<&HeaderMap>) -> Vec<(String, String)> {
+    let mut pairs: Vec<_> = headers
+        .into_iter()
+        .flat_map(HeaderMap::iter)
+        .flat_map(|(name, values)| {
+            values
+                .iter()
+                .map(move |value| (name.to_string(), value.to_string()))
+        })
+        .collect();
+    pairs.sort_by(|a, b| a.0.cmp(&b.0));
+    pairs
 }
 
 fn validate_consumer_group(value: &str) -> anyhow::Result<()> {
@@ -536,7 +746,7 @@ fn validate_consumer_group(value: &str) -> anyhow::Result<()> {
 /// component. The readable prefix helps operators identify the consumer while
 /// the FNV-1a suffix preserves distinctions lost through sanitization or
 /// truncation without adding a hashing dependency to the runtime.
-fn default_consumer_group(namespace: &str, workload: &str, component: &str) -> String {
+pub(super) fn default_consumer_group(namespace: &str, workload: &str, component: &str) -> String {
     let identity = format!("{namespace}\0{workload}\0{component}");
     let mut hash = 0xcbf29ce484222325_u64;
     for byte in identity.bytes() {
@@ -580,7 +790,7 @@ fn default_consumer_group(namespace: &str, workload: &str, component: &str) -> S
 /// single byte to it, and awaits the round-tripped message. NATS processes
 /// per-connection commands in order, so once we receive the sentinel back
 /// every earlier SUB on this connection is guaranteed to be active.
-async fn sync_with_server(client: &async_nats::Client) -> anyhow::Result<()> {
+pub(crate) async fn sync_with_server(client: &async_nats::Client) -> anyhow::Result<()> {
     use futures::stream::StreamExt;
 
     let inbox = client.new_inbox();
@@ -645,6 +855,8 @@ mod tests {
                     cancel_token,
                     subscriptions: vec!["tasks.x".to_string()],
                     consumer_group: ConsumerGroup::Grouped("workers".to_string()),
+                    jetstream: None,
+                    retry: RetryPolicy::default(),
                     task_handle: None,
                 },
             );
@@ -758,6 +970,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn headers_round_trip_and_reject_what_nats_cannot_carry() {
+        let headers = vec![
+            ("x-request-id".to_string(), "42".to_string()),
+            ("x-tag".to_string(), "a".to_string()),
+            ("x-tag".to_string(), "b".to_string()),
+        ];
+        let map = header_map(&headers).unwrap();
+        assert_eq!(wit_headers(Some(&map)), headers);
+        assert!(wit_headers(None).is_empty());
+
+        assert!(header_map(&[("bad:name".to_string(), "v".to_string())]).is_err());
+        assert!(header_map(&[("x-ok".to_string(), "line\nbreak".to_string())]).is_err());
+    }
+
     #[test]
     fn consumer_group_configuration_rejects_invalid_values() {
         for value in ["", "two groups", "workers.*", "workers.>"] {
@@ -0,0 +1,207 @@
+//! Retry and dead-letter policy for failed `handle-message` deliveries.
+//!
+//! A delivery fails when the handler returns `Err(string)`, traps, or cannot
+//! be instantiated. Core NATS and in-memory deliveries are retried in-process
+//! `retry_count` times with an exponential backoff. JetStream deliveries are
+//! redelivered by the stream instead (see [`super::jetstream`]), so only the
+//! dead-letter subject applies to them. Once a message has no attempts left it
+//! is published to `dead_letter_subject`, when one is configured, wrapped in a
+//! [`DeadLetter`] JSON envelope whose `body` is the original payload in
+//! standard base64.
+//!
+//! | key                   | default          |
+//! |-----------------------|------------------|
+//! | `retry_count`         | `0`              |
+//! | `retry_backoff_ms`    | `1000`           |
+//! | `dead_letter_subject` | (unset: dropped) |
+
+use std::time::Duration;
+
+use base64::Engine as _;
+use serde::{Serialize, Serializer};
+
+pub(crate) const RETRY_COUNT_CONFIG: &str = "retry_count";
+pub(crate) const RETRY_BACKOFF_CONFIG: &str = "retry_backoff_ms";
+pub(crate) const DEAD_LETTER_SUBJECT_CONFIG: &str = "dead_letter_subject";
+
+const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+/// Upper bound on a single retry delay, however many attempts came before.
+pub(crate) const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// `base` doubled once per failed attempt before `attempt` (1-based), capped
+/// at [`MAX_BACKOFF`].
+pub(crate) fn backoff_delay(base: Duration, attempt: i64) -> Duration {
+    let doublings = attempt.saturating_sub(1).clamp(0, 16) as u32;
+    base.saturating_mul(1 << doublings).min(MAX_BACKOFF)
+}
+
+/// How one handler component's failed deliveries are retried and where they
+/// go once retries run out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RetryPolicy {
+    /// In-process retries after the first failed attempt.
+    pub(crate) retry_count: u32,
+    pub(crate) backoff: Duration,
+    pub(crate) dead_letter_subject: Option<String>,
+}
+
+impl RetryPolicy {
+    /// Reads the retry keys through `lookup`, defaulting to no retries and no
+    /// dead-letter subject.
+    pub(crate) fn from_config<'a>(
+        lookup: impl Fn(&str) -> Option<&'a str>,
+    ) -> anyhow::Result<Self> {
+        let retry_count = match lookup(RETRY_COUNT_CONFIG) {
+            None => 0,
+            Some(raw) => raw
+                .trim()
+                .parse()
+                .map_err(|e| anyhow::anyhow!("invalid `{RETRY_COUNT_CONFIG}` `{raw}`: {e}"))?,
+        };
+        let backoff =
+            match lookup(RETRY_BACKOFF_CONFIG) {
+                None => DEFAULT_BACKOFF,
+                Some(raw) => Duration::from_millis(raw.trim().parse().map_err(|e| {
+                    anyhow::anyhow!("invalid `{RETRY_BACKOFF_CONFIG}` `{raw}`: {e}")
+                })?),
+            };
+        let dead_letter_subject = match lookup(DEAD_LETTER_SUBJECT_CONFIG).map(str::trim) {
+            None => None,
+            Some(subject) => {
+                anyhow::ensure!(
+                    !subject.is_empty()
+                        && !subject
+                            .chars()
+                            .any(|c| c.is_whitespace() || c == '*' || c == '>'),
+                    "invalid `{DEAD_LETTER_SUBJECT_CONFIG}` `{subject}`: must be a literal subject without whitespace or wildcards"
+                );
+                Some(subject.to_string())
+            }
+        };
+        Ok(Self {
+            retry_count,
+            backoff,
+            dead_letter_subject,
+        })
+    }
+
+    /// Whether a delivery that failed on its `attempt`-th try (1-based)
+    /// should be tried again in-process.
+    pub(crate) fn should_retry(&self, attempt: u32) -> bool {
+        attempt <= self.retry_count
+    }
+
+    /// Delay before the retry that follows a failed `attempt`.
+    pub(crate) fn retry_delay(&self, attempt: u32) -> Duration {
+        backoff_delay(self.backoff, i64::from(attempt))
+    }
+}
+
+/// The JSON body published to a dead-letter subject: the original message
+/// plus enough context to tell which handler gave up on it and why.
+#[derive(Debug, Serialize)]
+pub(crate) struct DeadLetter<'a> {
+    pub(crate) subject: &'a str,
+    pub(crate) reply_to: Option<&'a str>,
+    #[serde(serialize_with = "base64_body")]
+    pub(crate) body: &'a [u8],
+    pub(crate) workload_id: &'a str,
+    pub(crate) component_id: &'a str,
+    pub(crate) error: &'a str,
+    pub(crate) attempts: i64,
+}
+
+impl DeadLetter<'_> {
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        // Every field is a plain string or number, which always serializes.
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+}
+
+fn base64_body<S: Serializer>(body: &&[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(body))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use std::collections::HashMap;
+
+    fn parse(pairs: &[(&str, &str)]) -> anyhow::Result<RetryPolicy> {
+        let config: HashMap<&str, &str> = pairs.iter().copied().collect();
+        RetryPolicy::from_config(|key| config.get(key).copied())
+    }
+
+    #[test]
+    fn defaults_to_no_retries_and_no_dead_letter() {
+        let policy = parse(&[]).unwrap();
+        assert_eq!(policy.retry_count, 0);
+        assert_eq!(policy.backoff, DEFAULT_BACKOFF);
+        assert_eq!(policy.dead_letter_subject, None);
+        assert!(!policy.should_retry(1));
+    }
+
+    #[test]
+    fn retries_up_to_the_configured_count_with_backoff() {
+        let policy = parse(&[
+            (RETRY_COUNT_CONFIG, "2"),
+            (RETRY_BACKOFF_CONFIG, "100"),
+            (DEAD_LETTER_SUBJECT_CONFIG, "orders.dead"),
+        ])
+        .unwrap();
+        assert!(policy.should_retry(1));
+        assert!(policy.should_retry(2));
+        assert!(!policy.should_retry(3));
+        assert_eq!(policy.retry_delay(1), Duration::from_millis(100));
+        assert_eq!(policy.retry_delay(2), Duration::from_millis(200));
+        assert_eq!(policy.dead_letter_subject.as_deref(), Some("orders.dead"));
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        for pairs in [
+            &[(RETRY_COUNT_CONFIG, "-1")][..],
+            &[(RETRY_BACKOFF_CONFIG, "later")],
+            &[(DEAD_LETTER_SUBJECT_CONFIG, "")],
+            &[(DEAD_LETTER_SUBJECT_CONFIG, "dead.>")],
+        ] {
+            assert!(parse(pairs).is_err(), "{pairs:?}");
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        assert_eq!(
+            backoff_delay(Duration::from_secs(1), 3),
+            Duration::from_secs(4)
+        );
+        assert_eq!(backoff_delay(Duration::from_secs(1), 40), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn dead_letter_carries_message_and_failure_context() {
+        let letter = DeadLetter {
+            subject: "orders.created",
+            reply_to: None,
+            body: b"hi",
+            workload_id: "wl-1",
+            component_id: "c-1",
+            error: "boom",
+            attempts: 3,
+        };
+        let value: serde_json::Value = serde_json::from_slice(&letter.to_bytes()).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "subject": "orders.created",
+                "reply_to": null,
+                "body": "aGk=",
+                "workload_id": "wl-1",
+                "component_id": "c-1",
+                "error": "boom",
+                "attempts": 3,
+            })
+        );
+    }
+}
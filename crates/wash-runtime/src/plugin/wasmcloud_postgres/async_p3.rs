@@ -14,20 +14,28 @@
 //! guest exerts backpressure), while the completion future carries any error that
 //! surfaces partway through. The value model (`pg-value`) is byte-identical to
 //! the sync binding, so the whole `conversions` body is `include!`d and reused.
+//!
+//! A `transaction` resource shares its pinned connection (see
+//! [`PgTransaction`]) with the fetch tasks of the queries run on it, so a guest
+//! may start the next statement before draining the previous row stream;
+//! Postgres runs them in order on the one session.
 
 use core::pin::Pin;
 use core::task::{Context, Poll};
+use std::sync::Arc;
 
 use futures::TryStreamExt as _;
 use tokio::sync::{mpsc, oneshot};
 use tokio_postgres::types::{ToSql, Type as PgType};
 use wasmtime::StoreContextMut;
 use wasmtime::component::{
-    Accessor, Destination, FutureReader, Linker, StreamProducer, StreamReader, StreamResult,
+    Accessor, Destination, FutureReader, Linker, Resource, StreamProducer, StreamReader,
+    StreamResult,
 };
 
 use crate::engine::ctx::{ActiveCtx, SharedCtx, extract_active_ctx};
 
+use super::transaction::{Isolation, PgTransaction, PinnedClient};
 use super::{PLUGIN_POSTGRES_ID, PreparedEntry, WasmcloudPostgres};
 
 #[cfg(feature = "wasm_component_model_implements")]
@@ -46,6 +54,7 @@ const ROW_CHANNEL_CAPACITY: usize = 16;
 
 mod bindings;
 
+use bindings::wasmcloud::postgres0_2_0::transaction as txn;
 use bindings::wasmcloud::postgres0_2_0::types::{DbError, Error, PgValue, Row};
 use bindings::wasmcloud::postgres0_2_0::{prepared, query, types};
 
@@ -126,7 +135,7 @@ async fn client_for(
 /// the error, if one surfaces mid-stream.
 async fn stream_query<U>(
     store: &Accessor<U, SharedCtx>,
-    client: deadpool_postgres::Client,
+    client: Arc<PinnedClient>,
     q: String,
     params: Vec<PgValue>,
 ) -> wasmtime::Result<Result<(Vec<String>, StreamReader<Row>, CompletionFuture), Error>> {
@@ -157,7 +166,7 @@ async fn stream_query<U>(
 /// Body of the row-fetch task: execute the prepared statement and forward each
 /// converted row to `row_tx`, stopping early if the guest drops the stream.
 async fn drain_query(
-    client: deadpool_postgres::Client,
+    client: Arc<PinnedClient>,
     stmt: tokio_postgres::Statement,
     params: Vec<PgValue>,
     row_tx: mpsc::Sender<Row>,
@@ -325,7 +334,7 @@ impl<U> query::HostWithStore<U> for SharedCtx {
             Ok(c) => c,
             Err(e) => return Ok(Err(e)),
         };
-        stream_query(store, client, q, params).await
+        stream_query(store, Arc::new(PinnedClient::new(client)), q, params).await
     }
 
     async fn query_batch(
@@ -381,6 +390,111 @@ impl<U> prepared::HostWithStore<U> for SharedCtx {
     }
 }
 
+impl From<txn::IsolationLevel> for Isolation {
+    fn from(level: txn::IsolationLevel) -> Self {
+        match level {
+            txn::IsolationLevel::ReadUncommitted => Isolation::ReadUncommitted,
+            txn::IsolationLevel::ReadCommitted => Isolation::ReadCommitted,
+            txn::IsolationLevel::RepeatableRead => Isolation::RepeatableRead,
+            txn::IsolationLevel::Serializable => Isolation::Serializable,
+        }
+    }
+}
+
+/// The pinned connection behind a `transaction` handle, cloned out of the
+/// resource table so it can be used across awaits.
+fn transaction_client<U>(
+    store: &Accessor<U, SharedCtx>,
+    tx: &Resource<PgTransaction>,
+) -> wasmtime::Result<Arc<PinnedClient>> {
+    store.with(|mut access| {
+        let tx = access.get().table.get(tx)?;
+        wasmtime::Result::Ok(Arc::clone(tx.client()))
+    })
+}
+
+impl txn::Host for ActiveCtx<'_> {}
+
+impl txn::HostTransaction for ActiveCtx<'_> {}
+
+impl<U> txn::HostTransactionWithStore<U> for SharedCtx {
+    /// Dropping an unfinished transaction rolls it back (see [`PgTransaction`]).
+    async fn drop(store: &Accessor<U, Self>, tx: Resource<PgTransaction>) -> wasmtime::Result<()> {
+        store.with(|mut access| access.get().table.delete(tx))?;
+        Ok(())
+    }
+
+    async fn begin(
+        store: &Accessor<U, Self>,
+        isolation: txn::IsolationLevel,
+    ) -> wasmtime::Result<Result<Resource<PgTransaction>, Error>> {
+        let (plugin, component_id) = plugin_and_component(store)?;
+        let Some(database) = plugin.database_for_component(&component_id).await else {
+            return Ok(Err(Error::Other(
+                "no database configured for this component".to_string(),
+            )));
+        };
+        let Some(slot) = plugin.transaction_slot(&component_id).await else {
+            return Ok(Err(Error::Other(
+                "too many open transactions for this component".to_string(),
+            )));
+        };
+        let client = match client_for(&plugin, &database).await {
+            Ok(c) => c,
+            Err(e) => return Ok(Err(e)),
+        };
+        let tx = match PgTransaction::begin(client, isolation.into(), slot).await {
+            Ok(tx) => tx,
+            Err(e) => return Ok(Err(to_wit_error(&e))),
+        };
+        let tx = store.with(|mut access| access.get().table.push(tx))?;
+        Ok(Ok(tx))
+    }
+
+    async fn query(
+        store: &Accessor<U, Self>,
+        tx: Resource<PgTransaction>,
+        q: String,
+        params: Vec<PgValue>,
+    ) -> wasmtime::Result<Result<(Vec<String>, StreamReader<Row>, CompletionFuture), Error>> {
+        let client = transaction_client(store, &tx)?;
+        stream_query(store, client, q, params).await
+    }
+
+    async fn exec(
+        store: &Accessor<U, Self>,
+        tx: Resource<PgTransaction>,
+        statement: String,
+        params: Vec<PgValue>,
+    ) -> wasmtime::Result<Result<u64, Error>> {
+        let client = transaction_client(store, &tx)?;
+        let param_refs = params
+            .iter()
+            .map(|p| p as &(dyn ToSql + Sync))
+            .collect::<Vec<_>>();
+        Ok(client
+            .execute_raw(&statement, param_refs)
+            .await
+            .map_err(|e| to_wit_error(&e)))
+    }
+
+    async fn commit(
+        store: &Accessor<U, Self>,
+        tx: Resource<PgTransaction>,
+    ) -> wasmtime::Result<Result<(), Error>> {
+        let tx = store.with(|mut access| access.get().table.delete(tx))?;
+        Ok(tx.commit().await.map_err(|e| to_wit_error(&e)))
+    }
+
+    async fn rollback(
+        store: &Accessor<U, Self>,
+        tx: Resource<PgTransaction>,
+    ) -> wasmtime::Result<Result<(), Error>> {
+        let tx = store.with(|mut access| access.get().table.delete(tx))?;
+        Ok(tx.rollback().await.map_err(|e| to_wit_error(&e)))
+    }
+}
+
 // Same shape as the default impls, but the connection comes from the resolved
 // per-credential `PgId` pool rather than the component's configured database.
 
@@ -401,7 +515,7 @@ impl<U> bindings::named_imports::wasmcloud::postgres0_2_0::query::HostWithStore<
             Ok(c) => c,
             Err(e) => return Ok(Err(Error::ConnectionFailed(e))),
         };
-        stream_query(store, client, q, params).await
+        stream_query(store, Arc::new(PinnedClient::new(client)), q, params).await
     }
 
     async fn query_batch(
@@ -461,10 +575,11 @@ pub(super) fn add_types_to_linker(linker: &mut Linker<SharedCtx>) -> wasmtime::R
     types::add_to_linker::<_, SharedCtx>(linker, extract_active_ctx)
 }
 
-/// Link the default (unnamed) `query`/`prepared` interfaces.
+/// Link the default (unnamed) `query`/`prepared`/`transaction` interfaces.
 pub(super) fn add_default_to_linker(linker: &mut Linker<SharedCtx>) -> wasmtime::Result<()> {
     query::add_to_linker::<_, SharedCtx>(linker, extract_active_ctx)?;
     prepared::add_to_linker::<_, SharedCtx>(linker, extract_active_ctx)?;
+    txn::add_to_linker::<_, SharedCtx>(linker, extract_active_ctx)?;
     Ok(())
 }
 
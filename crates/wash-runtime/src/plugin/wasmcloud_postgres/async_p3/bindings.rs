@@ -10,6 +10,10 @@
 crate::wasmtime::component::bindgen!({
     world: "async-postgres",
     imports: { default: store | async | trappable | tracing },
+    with: {
+        "wasmcloud:postgres/transaction.transaction":
+            crate::plugin::wasmcloud_postgres::transaction::PgTransaction,
+    },
 });
 
 #[cfg(feature = "wasm_component_model_implements")]
@@ -20,4 +24,8 @@ crate::wasmtime::component::bindgen!({
         "wasmcloud:postgres/query@0.2.0": crate::plugin::wasmcloud_postgres::PgId,
         "wasmcloud:postgres/prepared@0.2.0": crate::plugin::wasmcloud_postgres::PgId,
     },
+    with: {
+        "wasmcloud:postgres/transaction.transaction":
+            crate::plugin::wasmcloud_postgres::transaction::PgTransaction,
+    },
 });
@@ -27,7 +27,7 @@ use crate::wit::WitInterface;
 use super::{
     DEFAULT_POOL_SIZE, PLUGIN_POSTGRES_ID, PgValue, PreparedEntry, PreparedStatementExecError,
     QueryError, ResultRow, StatementPrepareError, WasmcloudPostgres, bindings, execute_query,
-    extract_pool_size, extract_tls_requirement, pg_error_string,
+    extract_pool_size, extract_tls_requirement, pg_error_string, tls_connector,
 };
 
 /// The single `wasmcloud:postgres` backend type. Unlike `wasi:keyvalue` (which
@@ -61,13 +61,7 @@ fn build_pool_from_url(url: &str) -> anyhow::Result<Pool> {
         recycling_method: RecyclingMethod::Fast,
     };
     let pool = if extract_tls_requirement(&parsed) {
-        let tls_config = rustls::ClientConfig::builder()
-            .with_root_certificates(rustls::RootCertStore {
-                roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
-            })
-            .with_no_client_auth();
-        let tls = tokio_postgres_rustls::MakeRustlsConnect::new(tls_config);
-        let mgr = Manager::from_config(pg_config, tls, mgr_config);
+        let mgr = Manager::from_config(pg_config, tls_connector(), mgr_config);
         Pool::builder(mgr)
             .max_size(pool_size)
             .build()
@@ -0,0 +1,449 @@
+//! Postgres `LISTEN`/`NOTIFY` delivery to components that export
+//! `wasmcloud:postgres/notifications`, either the sync `0.1.1-draft` or the
+//! async `0.2.0` interface.
+//!
+//! The channels are listed (comma-separated) in the `listen` config of a
+//! postgres host interface entry. Each entry is one credential: the plugin's
+//! bouncer URL with the entry's `database`, or a named entry's own `url`. For
+//! every bound credential the plugin holds a dedicated connection outside the
+//! pool, since a pooled connection is handed to other callers and would carry
+//! its `LISTEN`s with it.
+//!
+//! `LISTEN` registers on the server session it runs in, so it only works over
+//! a direct connection or one pooled in session mode. A bouncer in transaction
+//! pooling mode (PgBouncer's `pool_mode = transaction`) hands the session to
+//! another client as soon as the `LISTEN` completes, and the notifications go
+//! with it. When the entry's credential goes through such a bouncer, set
+//! `listen_url` on the entry to a direct or session-pooled URL (database
+//! included); the listening connection is opened there instead.
+//!
+//! If the listening connection is lost it reconnects with backoff
+//! and re-issues `LISTEN` for each channel. Postgres does not queue
+//! notifications for a listener that is not connected, so anything sent while
+//! reconnecting is lost.
+//!
+//! Like `wasmcloud:messaging/handler`, each notification is delivered to a
+//! fresh instance of the component.
+
+use std::time::Duration;
+
+use futures::stream::StreamExt;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
+use tokio_postgres::{AsyncMessage, Connection, Notification};
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, debug, warn};
+use url::Url;
+use wasmtime::error::Context as _;
+
+use crate::engine::ctx::SharedCtx;
+use crate::engine::workload::ResolvedWorkload;
+use crate::wit::{WitInterface, WitWorld};
+
+use super::{extract_tls_requirement, pg_error_string, tls_connector, without_pool_size};
+
+mod bindings {
+    crate::wasmtime::component::bindgen!({
+        world: "postgres-notifications",
+        exports: { default: async | tracing },
+    });
+}
+
+mod async_bindings {
+    crate::wasmtime::component::bindgen!({
+        world: "async-postgres-notifications",
+        exports: { default: async | store | tracing },
+    });
+}
+
+/// Host-interface config key listing the channels to `LISTEN` on.
+pub(super) const LISTEN_CONFIG: &str = "listen";
+
+/// Host-interface config key for a URL to `LISTEN` through instead of the
+/// entry's own credential, for when that goes through a transaction-pooling
+/// bouncer.
+pub(super) const LISTEN_URL_CONFIG: &str = "listen_url";
+
+/// Delay before the first reconnect attempt after the listening connection is
+/// lost; doubled after every failed attempt up to [`MAX_RECONNECT_DELAY`].
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(250);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Returns the version of `wasmcloud:postgres/notifications` the world
+/// exports, or `None` if it does not export it. An unversioned export counts as
+/// the sync interface.
+pub(super) fn exported_notifications(world: &WitWorld) -> Option<Option<semver::Version>> {
+    let notifications = WitInterface::from("wasmcloud:postgres/notifications");
+    world
+        .exports
+        .iter()
+        .find(|e| e.contains(&notifications))
+        .map(|e| e.version.clone())
+}
+
+/// Parses a comma-separated `listen` config value into trimmed, non-empty
+/// channel names.
+pub(super) fn parse_channels(raw: Option<&str>) -> Vec<String> {
+    raw.map(|s| {
+        s.split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Quote a channel name as a Postgres identifier. Channels are used verbatim,
+/// so they are case-sensitive just as with `pg_notify`.
+fn quote_channel(channel: &str) -> String {
+    format!("\"{}\"", channel.replace('"', "\"\""))
+}
+
+/// One credential's listening connection: where to connect, and the channels
+/// to `LISTEN` on once connected.
+#[derive(Clone)]
+pub(super) struct ListenTarget {
+    config: tokio_postgres::Config,
+    tls: bool,
+    channels: Vec<String>,
+}
+
+impl ListenTarget {
+    pub(super) fn new(config: tokio_postgres::Config, tls: bool, channels: Vec<String>) -> Self {
+        Self {
+            config,
+            tls,
+            channels,
+        }
+    }
+
+    /// A target for an interface that carries its own full URL: a named
+    /// interface's `url`, or any entry's `listen_url`.
+    pub(super) fn from_url(url: &str, channels: Vec<String>) -> anyhow::Result<Self> {
+        let parsed = Url::parse(url).context("failed to parse postgres URL")?;
+        let config = without_pool_size(&parsed)
+            .as_str()
+            .parse::<tokio_postgres::Config>()
+            .context("failed to parse postgres config")?;
+        Ok(Self::new(
+            config,
+            extract_tls_requirement(&parsed),
+            channels,
+        ))
+    }
+
+    /// Open a fresh connection and `LISTEN` on every channel.
+    async fn connect(&self) -> Result<Listening, tokio_postgres::Error> {
+        let listening = if self.tls {
+            Listening::spawn(self.config.connect(tls_connector()).await?)
+        } else {
+            Listening::spawn(self.config.connect(tokio_postgres::NoTls).await?)
+        };
+        for channel in &self.channels {
+            listening
+                .client
+                .batch_execute(&format!("LISTEN {}", quote_channel(channel)))
+                .await?;
+        }
+        Ok(listening)
+    }
+}
+
+/// An open listening connection. Dropping it closes the connection.
+struct Listening {
+    client: tokio_postgres::Client,
+    /// Closes once the connection is lost.
+    notifications: mpsc::UnboundedReceiver<Notification>,
+}
+
+impl Listening {
+    /// Spawn the task that drives `connection` and forwards its notifications.
+    fn spawn<S, T>((client, mut connection): (tokio_postgres::Client, Connection<S, T>)) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+        T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (tx, notifications) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut messages = futures::stream::poll_fn(|cx| connection.poll_message(cx));
+            while let Some(message) = messages.next().await {
+                match message {
+                    Ok(AsyncMessage::Notification(notification)) => {
+                        if tx.send(notification).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        debug!(error = %pg_error_string(&e), "postgres listening connection closed");
+                        break;
+                    }
+                }
+            }
+        });
+        Self {
+            client,
+            notifications,
+        }
+    }
+}
+
+/// The component's `notifications` export, pre-instantiated for whichever
+/// version it exports.
+enum NotificationsPre {
+    Sync(bindings::PostgresNotificationsPre<SharedCtx>),
+    Async(async_bindings::AsyncPostgresNotificationsPre<SharedCtx>),
+}
+
+/// Per-component listener state: the credentials it listens through and the
+/// tasks started once the workload resolves.
+pub(super) struct ListenerData {
+    pub(super) targets: Vec<ListenTarget>,
+    pub(super) is_async: bool,
+    pub(super) cancel_token: CancellationToken,
+    pub(super) task_handles: Vec<tokio::task::JoinHandle<()>>,
+}
+
+impl ListenerData {
+    pub(super) fn new(targets: Vec<ListenTarget>, is_async: bool) -> Self {
+        Self {
+            targets,
+            is_async,
+            cancel_token: CancellationToken::new(),
+            task_handles: Vec::new(),
+        }
+    }
+
+    /// Stop listening and close every listening connection.
+    pub(super) fn stop(self) {
+        self.cancel_token.cancel();
+        for handle in self.task_handles {
+            handle.abort();
+        }
+    }
+}
+
+/// Connect every target and spawn the loops that deliver their notifications
+/// to `component_id` until `cancel_token` fires. The first connection of each
+/// target is made before returning, so no notification sent after the
+/// workload reports running is missed.
+pub(super) async fn start(
+    workload: &ResolvedWorkload,
+    component_id: &str,
+    targets: Vec<ListenTarget>,
+    is_async: bool,
+    cancel_token: CancellationToken,
+) -> anyhow::Result<Vec<tokio::task::JoinHandle<()>>> {
+    let instance_pre = workload.instantiate_pre(component_id).await?;
+    let pre = if is_async {
+        NotificationsPre::Async(
+            async_bindings::AsyncPostgresNotificationsPre::new(instance_pre)
+                .context("failed to instantiate postgres notifications pre")?,
+        )
+    } else {
+        NotificationsPre::Sync(
+            bindings::PostgresNotificationsPre::new(instance_pre)
+                .context("failed to instantiate postgres notifications pre")?,
+        )
+    };
+    let handler = std::sync::Arc::new(NotificationHandler {
+        workload: workload.clone(),
+        component_id: component_id.to_string(),
+        pre,
+    });
+
+    let mut listening = Vec::with_capacity(targets.len());
+    for target in targets {
+        let connection = target.connect().await.map_err(|e| {
+            anyhow::anyhow!(
+                "failed to listen on postgres channels {:?}: {}",
+                target.channels,
+                pg_error_string(&e)
+            )
+        })?;
+        debug!(component_id, channels = ?target.channels, "listening for postgres notifications");
+        listening.push((target, connection));
+    }
+
+    let span = tracing::Span::current();
+    Ok(listening
+        .into_iter()
+        .map(|(target, connection)| {
+            tokio::spawn(
+                listen(target, connection, handler.clone(), cancel_token.clone())
+                    .instrument(span.clone()),
+            )
+        })
+        .collect())
+}
+
+/// Deliver each notification on `connection`, reconnecting when it is lost,
+/// until `cancel_token` fires.
+async fn listen(
+    target: ListenTarget,
+    mut connection: Listening,
+    handler: std::sync::Arc<NotificationHandler>,
+    cancel_token: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            next = connection.notifications.recv() => {
+                let Some(notification) = next else {
+                    warn!(
+                        component_id = %handler.component_id,
+                        channels = ?target.channels,
+                        "postgres listening connection lost; reconnecting"
+                    );
+                    match reconnect(&target, &cancel_token).await {
+                        Some(reconnected) => connection = reconnected,
+                        None => break,
+                    }
+                    continue;
+                };
+                let handler = handler.clone();
+                tokio::spawn(async move {
+                    handler
+                        .deliver(notification.channel().to_string(), notification.payload().to_string())
+                        .await
+                });
+            }
+            _ = cancel_token.cancelled() => {
+                debug!(component_id = %handler.component_id, "postgres listener cancelled");
+                break;
+            }
+        }
+    }
+}
+
+/// Reconnect `target` with exponential backoff. Returns `None` if cancelled
+/// first.
+async fn reconnect(target: &ListenTarget, cancel_token: &CancellationToken) -> Option<Listening> {
+    let mut delay = INITIAL_RECONNECT_DELAY;
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = cancel_token.cancelled() => return None,
+        }
+        match target.connect().await {
+            Ok(connection) => {
+                debug!(channels = ?target.channels, "postgres listening connection re-established");
+                return Some(connection);
+            }
+            Err(e) => {
+                warn!(
+                    channels = ?target.channels,
+                    error = %pg_error_string(&e),
+                    ?delay,
+                    "failed to re-establish postgres listening connection"
+                );
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+        }
+    }
+}
+
+/// Everything needed to deliver a notification to one component.
+struct NotificationHandler {
+    workload: ResolvedWorkload,
+    component_id: String,
+    pre: NotificationsPre,
+}
+
+impl NotificationHandler {
+    /// Call `on-notify` on a fresh instance of the component and return its
+    /// verdict. A trap or instantiation failure surfaces as the outer `Err`.
+    async fn dispatch(
+        &self,
+        channel: String,
+        payload: String,
+    ) -> anyhow::Result<Result<(), String>> {
+        let mut store = self.workload.new_store(&self.component_id).await?;
+        match &self.pre {
+            NotificationsPre::Sync(pre) => {
+                let instance = pre.instantiate_async(&mut store).await.with_context(|| {
+                    format!("failed to instantiate component {}", self.component_id)
+                })?;
+                instance
+                    .wasmcloud_postgres0_1_1_draft_notifications()
+                    .call_on_notify(&mut store, &channel, &payload)
+                    .await
+                    .map_err(Into::into)
+            }
+            NotificationsPre::Async(pre) => {
+                let instance = pre.instantiate_async(&mut store).await.with_context(|| {
+                    format!("failed to instantiate component {}", self.component_id)
+                })?;
+                store
+                    .run_concurrent(async move |accessor| {
+                        instance
+                            .wasmcloud_postgres0_2_0_notifications()
+                            .call_on_notify(accessor, channel, payload)
+                            .await
+                    })
+                    .await?
+                    .map_err(Into::into)
+            }
+        }
+    }
+
+    async fn deliver(&self, channel: String, payload: String) {
+        let span = tracing::span!(
+            tracing::Level::INFO,
+            "incoming_postgres_notification",
+            channel = %channel,
+        );
+        match self
+            .dispatch(channel.clone(), payload)
+            .instrument(span)
+            .await
+        {
+            Ok(Ok(())) => debug!(%channel, "postgres notification handled successfully"),
+            Ok(Err(e)) => warn!(%channel, error = %e, "error handling postgres notification"),
+            Err(e) => {
+                warn!(%channel, error = %format!("{e:#}"), "failed to deliver postgres notification")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn parses_listen_channels() {
+        assert_eq!(
+            parse_channels(Some(" orders, Inventory ,,")),
+            vec!["orders".to_string(), "Inventory".to_string()]
+        );
+        assert!(parse_channels(None).is_empty());
+    }
+
+    #[test]
+    fn quotes_channels_as_identifiers() {
+        assert_eq!(quote_channel("orders"), "\"orders\"");
+        assert_eq!(quote_channel("a\"b; DROP"), "\"a\"\"b; DROP\"");
+    }
+
+    #[test]
+    fn finds_exported_notifications_version() {
+        let world = |exports: &[&str]| WitWorld {
+            imports: HashSet::new(),
+            exports: exports.iter().map(|e| WitInterface::from(*e)).collect(),
+        };
+        assert_eq!(
+            exported_notifications(&world(&["wasmcloud:postgres/notifications@0.2.0"])),
+            Some(Some(semver::Version::new(0, 2, 0)))
+        );
+        assert_eq!(
+            exported_notifications(&world(&["wasmcloud:postgres/notifications"])),
+            Some(None)
+        );
+        assert_eq!(
+            exported_notifications(&world(&["wasmcloud:postgres/query@0.2.0"])),
+            None
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! A database transaction pinned to one pooled connection, backing the
+//! `transaction` resource of both the sync (`0.1.1-draft`) and async (`0.2.0`)
+//! bindings.
+//!
+//! The transaction is driven with plain `BEGIN`/`COMMIT`/`ROLLBACK` statements
+//! rather than `tokio_postgres::Transaction`, whose borrow of the client cannot
+//! live in a resource table. The connection sits behind an `Arc` so the async
+//! binding's row-fetch task can keep using it while the guest issues the next
+//! statement; it goes back to the pool once the transaction and any row stream
+//! still reading from it are gone, unless it was marked broken on the way (see
+//! [`PinnedClient`]).
+//!
+//! Each open transaction holds one of its component's transaction slots (see
+//! [`transaction_slots`]), so a component can't pin every connection of a pool
+//! it shares with others.
+
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use deadpool_postgres::Client;
+use tokio::sync::OwnedSemaphorePermit;
+
+/// How many transactions one component may hold open at once on a pool of
+/// `pool_size` connections: half the pool, and at least one.
+pub(super) fn transaction_slots(pool_size: usize) -> usize {
+    (pool_size / 2).max(1)
+}
+
+/// A pooled connection shared by a transaction and the row streams reading
+/// from it.
+///
+/// Once its session state can't be trusted — a `ROLLBACK` failed, or the
+/// transaction was dropped where it couldn't be rolled back — it is marked
+/// broken, and whichever holder goes last detaches it from the pool, closing
+/// it, instead of handing it to the next caller.
+pub(super) struct PinnedClient {
+    client: Option<Client>,
+    broken: AtomicBool,
+}
+
+impl PinnedClient {
+    pub(super) fn new(client: Client) -> Self {
+        Self {
+            client: Some(client),
+            broken: AtomicBool::new(false),
+        }
+    }
+
+    fn mark_broken(&self) {
+        self.broken.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Deref for PinnedClient {
+    type Target = Client;
+
+    /// The client is only taken out in `drop`.
+    #[allow(clippy::expect_used)]
+    fn deref(&self) -> &Client {
+        self.client
+            .as_ref()
+            .expect("pinned client is present until dropped")
+    }
+}
+
+impl Drop for PinnedClient {
+    fn drop(&mut self) {
+        if *self.broken.get_mut()
+            && let Some(client) = self.client.take()
+        {
+            drop(deadpool_postgres::Object::take(client));
+        }
+    }
+}
+
+/// Transaction isolation level, shared by both bindings' `isolation-level`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Isolation {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl Isolation {
+    /// The statement that opens a transaction at this level.
+    fn begin_statement(self) -> &'static str {
+        match self {
+            Isolation::ReadUncommitted => "BEGIN ISOLATION LEVEL READ UNCOMMITTED",
+            Isolation::ReadCommitted => "BEGIN ISOLATION LEVEL READ COMMITTED",
+            Isolation::RepeatableRead => "BEGIN ISOLATION LEVEL REPEATABLE READ",
+            Isolation::Serializable => "BEGIN ISOLATION LEVEL SERIALIZABLE",
+        }
+    }
+}
+
+/// Backing state for the `transaction` resource: the connection the
+/// transaction was opened on, held until it is committed or rolled back.
+///
+/// Dropping an unfinished transaction (the guest dropped the resource, or the
+/// workload's store went away) rolls it back in the background so the
+/// connection never returns to the pool mid-transaction. Without a runtime to
+/// do that on, the connection is closed instead.
+pub struct PgTransaction {
+    client: Arc<PinnedClient>,
+    finished: bool,
+    _slot: OwnedSemaphorePermit,
+}
+
+impl PgTransaction {
+    /// Open a transaction at `isolation` on `client`, holding `slot` until it
+    /// is finished or dropped.
+    pub(super) async fn begin(
+        client: Client,
+        isolation: Isolation,
+        slot: OwnedSemaphorePermit,
+    ) -> Result<Self, tokio_postgres::Error> {
+        client.batch_execute(isolation.begin_statement()).await?;
+        Ok(Self {
+            client: Arc::new(PinnedClient::new(client)),
+            finished: false,
+            _slot: slot,
+        })
+    }
+
+    /// The connection the transaction runs on.
+    pub(super) fn client(&self) -> &Arc<PinnedClient> {
+        &self.client
+    }
+
+    /// Commit the transaction.
+    ///
+    /// Once any statement has failed, Postgres leaves the transaction aborted
+    /// and answers a `COMMIT` with a successful `ROLLBACK`, which would look
+    /// like a commit to the guest. The no-op `SELECT` sent ahead of it fails in
+    /// that state instead (SQLSTATE `25P02`), so the error is reported and
+    /// dropping `self` rolls the transaction back.
+    pub(super) async fn commit(mut self) -> Result<(), tokio_postgres::Error> {
+        self.client.batch_execute("SELECT 1; COMMIT").await?;
+        self.finished = true;
+        Ok(())
+    }
+
+    /// Roll the transaction back. On failure `self` is still unfinished, so
+    /// dropping it makes one more attempt before giving up on the connection.
+    pub(super) async fn rollback(mut self) -> Result<(), tokio_postgres::Error> {
+        self.client.batch_execute("ROLLBACK").await?;
+        self.finished = true;
+        Ok(())
+    }
+}
+
+impl Drop for PgTransaction {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        let client = Arc::clone(&self.client);
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn(async move {
+                    if let Err(e) = roll_back(client).await {
+                        tracing::warn!(error = %e, "failed to roll back dropped postgres transaction");
+                    }
+                });
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "postgres transaction dropped outside a runtime; closing its connection"
+                );
+                client.mark_broken();
+            }
+        }
+    }
+}
+
+/// Run `ROLLBACK` on `client`. If that fails the session state is unknown, so
+/// the connection is marked broken rather than handed to the next caller.
+async fn roll_back(client: Arc<PinnedClient>) -> Result<(), tokio_postgres::Error> {
+    let result = client.batch_execute("ROLLBACK").await;
+    if result.is_err() {
+        client.mark_broken();
+    }
+    result
+}
@@ -0,0 +1,121 @@
+//! Where `wasmcloud:secrets` values come from.
+//!
+//! A component's bind-time `wasmcloud:secrets` config maps each key it may
+//! read to a *reference*; [`SecretBackend::get`] turns a reference into the
+//! current value every time the component asks for it. What a reference means
+//! is up to the backend: for [`StaticBackend`] it is the value itself, for
+//! [`super::NatsKvBackend`] a key in its bucket, for [`super::VaultBackend`] a
+//! `path#field` under its KV v2 mount.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use zeroize::Zeroizing;
+
+/// A resolved secret value, scrubbed when the last copy is dropped.
+pub type SecretString = Arc<Zeroizing<String>>;
+
+/// Resolves secret references to values at `get` time.
+///
+/// Authorization happens before a backend is asked: the plugin only resolves
+/// references that appear in the calling component's own bind-time config, so
+/// a backend never needs to know who is asking.
+#[async_trait::async_trait]
+pub trait SecretBackend: Send + Sync + 'static {
+    /// Resolve `reference` to its current value, or `None` if the backend has
+    /// no such secret.
+    async fn get(&self, reference: &str) -> anyhow::Result<Option<SecretString>>;
+}
+
+/// The bind-time config values are the secrets themselves, as the platform
+/// resolved them from `secretFrom` before the workload deployed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StaticBackend;
+
+#[async_trait::async_trait]
+impl SecretBackend for StaticBackend {
+    async fn get(&self, reference: &str) -> anyhow::Result<Option<SecretString>> {
+        Ok(Some(Arc::new(Zeroizing::new(reference.to_string()))))
+    }
+}
+
+/// Remembers what another backend resolved for `ttl`, so a busy component
+/// doesn't round-trip to Vault or NATS on every `get`. Only found values are
+/// cached: a missing secret shows up as soon as it is written, and a failed
+/// lookup is retried on the next call.
+pub struct CachingBackend<B> {
+    inner: B,
+    cache: moka::sync::Cache<String, SecretString>,
+}
+
+impl<B: SecretBackend> CachingBackend<B> {
+    pub fn new(inner: B, ttl: Duration) -> Self {
+        Self {
+            inner,
+            cache: moka::sync::Cache::builder().time_to_live(ttl).build(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<B: SecretBackend> SecretBackend for CachingBackend<B> {
+    async fn get(&self, reference: &str) -> anyhow::Result<Option<SecretString>> {
+        if let Some(value) = self.cache.get(reference) {
+            return Ok(Some(value));
+        }
+        let value = self.inner.get(reference).await?;
+        if let Some(value) = &value {
+            self.cache.insert(reference.to_string(), value.clone());
+        }
+        Ok(value)
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretBackend for Arc<dyn SecretBackend> {
+    async fn get(&self, reference: &str) -> anyhow::Result<Option<SecretString>> {
+        (**self).get(reference).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// Counts lookups and answers with the lookup number, so a test can tell
+    /// a cached value from a fresh one.
+    #[derive(Default)]
+    struct Counting(AtomicUsize);
+
+    #[async_trait::async_trait]
+    impl SecretBackend for Counting {
+        async fn get(&self, reference: &str) -> anyhow::Result<Option<SecretString>> {
+            let n = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok((reference != "missing").then(|| Arc::new(Zeroizing::new(n.to_string()))))
+        }
+    }
+
+    #[tokio::test]
+    async fn caching_backend_serves_values_until_they_expire() {
+        let backend = CachingBackend::new(Counting::default(), Duration::from_millis(100));
+
+        let first = backend.get("db").await.unwrap().unwrap();
+        let again = backend.get("db").await.unwrap().unwrap();
+        assert_eq!((first.as_str(), again.as_str()), ("1", "1"));
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let refreshed = backend.get("db").await.unwrap().unwrap();
+        assert_eq!(refreshed.as_str(), "2", "an expired value is fetched again");
+    }
+
+    #[tokio::test]
+    async fn caching_backend_does_not_remember_missing_secrets() {
+        let backend = CachingBackend::new(Counting::default(), Duration::from_secs(60));
+
+        assert!(backend.get("missing").await.unwrap().is_none());
+        assert!(backend.get("missing").await.unwrap().is_none());
+        assert_eq!(backend.inner.0.load(Ordering::SeqCst), 2);
+    }
+}
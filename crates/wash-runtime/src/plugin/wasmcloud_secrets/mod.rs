@@ -1,10 +1,13 @@
 //! Native `wasmcloud:secrets` plugin.
 //!
-//! Serves secrets from each component's bind-time `wasmcloud:secrets`
-//! interface config — the platform sources those values from `secretFrom`
-//! (or, locally, `dev.host_interfaces`) before the workload deploys. This
-//! plugin does not itself talk to an external secrets backend; it is the
-//! delivery mechanism for whatever the platform already resolved.
+//! Each component's bind-time `wasmcloud:secrets` interface config names the
+//! keys it may read. With the default [`StaticBackend`] the config values are
+//! the secrets themselves — the platform sources them from `secretFrom` (or,
+//! locally, `dev.host_interfaces`) before the workload deploys. With an
+//! external [`SecretBackend`] ([`NatsKvBackend`], [`VaultBackend`]) they are
+//! references the backend resolves on every `get`, so a rotated secret
+//! reaches running components without a redeploy, once the plugin's cached
+//! copy (see [`WasmcloudSecrets::with_backend`]) expires.
 //!
 //! Values leave only as opaque `secret` resource handles the caller unwraps
 //! with `reveal`. Bindings are captured per component (`on_workload_item_bind`,
@@ -27,22 +30,31 @@
 
 use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::sync::RwLock;
 use tracing::instrument;
 use wasmtime::component::{Accessor, Resource};
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
 
 use crate::engine::ctx::{ActiveCtx, SharedCtx, extract_active_ctx};
 use crate::engine::workload::WorkloadItem;
 use crate::plugin::{HostPlugin, WitInterfaces};
 use crate::wit::{WitInterface, WitWorld};
 
-/// The resolved value behind one labeled `wasmcloud:secrets/secret` import,
-/// looked up once per label at bind time (see `named_imports` below) and
-/// cloned per call. A plain alias, not `Arc<str>` spelled out inline in the
-/// macro — `named_imports` splices this string into a generated module path,
-/// which can't contain `<`/`>`.
+mod backend;
+mod nats;
+mod vault;
+
+pub use backend::{CachingBackend, SecretBackend, SecretString, StaticBackend};
+pub use nats::NatsKvBackend;
+pub use vault::VaultBackend;
+
+/// The reference behind one labeled `wasmcloud:secrets/secret` import, looked
+/// up once per label at bind time (see `named_imports` below) and resolved
+/// through the backend per call. A plain alias, not `Arc<str>` spelled out
+/// inline in the macro — `named_imports` splices this string into a generated
+/// module path, which can't contain `<`/`>`.
 pub type SecretRef = Arc<str>;
 
 mod bindings {
@@ -83,16 +95,53 @@ impl Drop for SecretState {
 type ComponentSecrets = BTreeMap<String, String>;
 
 /// Native `wasmcloud:secrets` plugin. Bind-time secret configuration, keyed
-/// by workload id then by component id.
-#[derive(Clone, Default)]
+/// by workload id then by component id, and the backend that resolves it.
+#[derive(Clone)]
 pub struct WasmcloudSecrets {
     binds: Arc<RwLock<BTreeMap<String, BTreeMap<String, ComponentSecrets>>>>,
+    backend: Arc<dyn SecretBackend>,
+}
+
+impl Default for WasmcloudSecrets {
+    fn default() -> Self {
+        Self {
+            binds: Arc::default(),
+            backend: Arc::new(StaticBackend),
+        }
+    }
 }
 
 impl WasmcloudSecrets {
+    /// Serve the bind-time config values themselves ([`StaticBackend`]).
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Resolve bind-time config values through `backend` on every `get`,
+    /// remembering each value for `cache_ttl` (not at all if zero).
+    pub fn with_backend(backend: impl SecretBackend, cache_ttl: Duration) -> Self {
+        let backend: Arc<dyn SecretBackend> = if cache_ttl.is_zero() {
+            Arc::new(backend)
+        } else {
+            Arc::new(CachingBackend::new(backend, cache_ttl))
+        };
+        Self {
+            binds: Arc::default(),
+            backend,
+        }
+    }
+
+    /// Resolve `reference` through the backend into a fresh `secret` value.
+    async fn resolve_reference(&self, reference: &str) -> Result<SecretValue, SecretsError> {
+        match self.backend.get(reference).await {
+            Ok(Some(value)) => Ok(SecretValue::String(value.as_str().to_string())),
+            Ok(None) => Err(SecretsError::NotFound),
+            Err(e) => {
+                tracing::warn!(error = format!("{e:#}"), "failed to resolve secret");
+                Err(SecretsError::Upstream(format!("{e:#}")))
+            }
+        }
+    }
 }
 
 /// Zeroizes every value in a captured component's secret config before it's
@@ -147,24 +196,24 @@ impl<T: 'static + Send> bindings::wasmcloud::secrets::store::HostWithStore<T> fo
                 ctx.component_id.clone(),
             ))
         })?;
-        let value = {
+        let reference = {
             let binds = plugin.binds.read().await;
             binds
                 .get(&*workload_id)
                 .and_then(|components| resolve(components, &component_id))
                 .and_then(|config| config.get(&key).cloned())
+                .map(Zeroizing::new)
         };
-        match value {
-            Some(value) => {
-                let resource = accessor.with(|mut access| {
-                    access.get().table.push(SecretState {
-                        value: SecretValue::String(value),
-                    })
-                })?;
-                Ok(Ok(resource))
-            }
-            None => Ok(Err(SecretsError::NotFound)),
-        }
+        let Some(reference) = reference else {
+            return Ok(Err(SecretsError::NotFound));
+        };
+        let value = match plugin.resolve_reference(&reference).await {
+            Ok(value) => value,
+            Err(e) => return Ok(Err(e)),
+        };
+        let resource =
+            accessor.with(|mut access| access.get().table.push(SecretState { value }))?;
+        Ok(Ok(resource))
     }
 }
 
@@ -193,12 +242,14 @@ impl<T: 'static + Send> bindings::wasmcloud::secrets::reveal::HostWithStore<T> f
 impl bindings::wasmcloud::secrets::reveal::Host for ActiveCtx<'_> {}
 
 /// Serves a labeled `wasmcloud:secrets/secret` import's nullary `get()`: the
-/// resolved value for this specific label, looked up once per label when the
+/// reference for this specific label, looked up once per label when the
 /// bind's `add_to_linker` call is made (see [`WasmcloudSecrets::on_workload_item_bind`]),
-/// then cloned per call — never a per-call lookup keyed by a guest-supplied
-/// string, since there is no such string in this interface.
+/// then resolved through the backend per call — never a per-call lookup keyed
+/// by a guest-supplied string, since there is no such string in this
+/// interface. `get` has no error case, so a secret the backend can't produce
+/// traps the call.
 ///
-/// Unlike `store`'s `binds` map, the resolved value baked in here has no
+/// Unlike `store`'s `binds` map, the reference baked in here has no
 /// zeroize-on-unbind path: it lives inside the linker's per-label closure for
 /// as long as the component's `InstancePre` does — for a host component
 /// plugin, that spans every incarnation, not just one bind — rather than
@@ -212,11 +263,16 @@ impl<T: 'static + Send> bindings::named_imports::wasmcloud::secrets::secret::Hos
         accessor: &Accessor<T, Self>,
         id: SecretRef,
     ) -> wasmtime::Result<Resource<SecretState>> {
-        let resource = accessor.with(|mut access| {
-            access.get().table.push(SecretState {
-                value: SecretValue::String(id.to_string()),
-            })
+        let plugin = accessor.with(|mut access| {
+            access
+                .get()
+                .try_get_plugin::<WasmcloudSecrets>(WASMCLOUD_SECRETS_ID)
+        })?;
+        let value = plugin.resolve_reference(&id).await.map_err(|e| {
+            wasmtime::Error::msg(format!("failed to resolve labeled secret: {e:?}"))
         })?;
+        let resource =
+            accessor.with(|mut access| access.get().table.push(SecretState { value }))?;
         Ok(resource)
     }
 }
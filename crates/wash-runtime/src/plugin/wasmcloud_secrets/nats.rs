@@ -0,0 +1,166 @@
+//! Secrets kept encrypted in a NATS JetStream KV bucket.
+//!
+//! Each entry is `nonce || AES-256-GCM ciphertext`, sealed with the host's
+//! encryption key and the entry's own key name as associated data, so a value
+//! copied under another name fails to decrypt rather than being served as the
+//! wrong secret. Anyone who can read the bucket but doesn't hold the key sees
+//! only ciphertext.
+
+use std::sync::Arc;
+
+use anyhow::{Context as _, bail, ensure};
+use aws_lc_rs::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use aws_lc_rs::rand::{SecureRandom as _, SystemRandom};
+use base64::Engine as _;
+use zeroize::Zeroizing;
+
+use super::backend::{SecretBackend, SecretString};
+
+/// [`SecretBackend`] over a JetStream KV bucket of encrypted values. A
+/// reference is the entry's key.
+pub struct NatsKvBackend {
+    store: async_nats::jetstream::kv::Store,
+    key: LessSafeKey,
+}
+
+impl NatsKvBackend {
+    /// Open `bucket`, creating it if it doesn't exist yet. `encryption_key` is
+    /// the 32-byte AES-256 key, standard base64-encoded.
+    pub async fn new(
+        client: &async_nats::Client,
+        bucket: &str,
+        encryption_key: &str,
+    ) -> anyhow::Result<Self> {
+        let key = decode_key(encryption_key)?;
+        let jetstream = async_nats::jetstream::new(client.clone());
+        let store = match jetstream.get_key_value(bucket).await {
+            Ok(store) => store,
+            Err(_) => jetstream
+                .create_key_value(async_nats::jetstream::kv::Config {
+                    bucket: bucket.to_string(),
+                    history: 1,
+                    ..Default::default()
+                })
+                .await
+                .with_context(|| format!("failed to open secrets bucket {bucket:?}"))?,
+        };
+        Ok(Self { store, key })
+    }
+
+    /// Encrypt `value` and store it under `name`, replacing any previous
+    /// value. Components see the new value once their cached copy, if any,
+    /// expires.
+    pub async fn put(&self, name: &str, value: &str) -> anyhow::Result<()> {
+        let sealed = seal(&self.key, name, value.as_bytes())?;
+        self.store
+            .put(name, sealed.into())
+            .await
+            .with_context(|| format!("failed to store secret {name:?}"))?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretBackend for NatsKvBackend {
+    async fn get(&self, reference: &str) -> anyhow::Result<Option<SecretString>> {
+        let Some(sealed) = self
+            .store
+            .get(reference)
+            .await
+            .with_context(|| format!("failed to read secret {reference:?}"))?
+        else {
+            return Ok(None);
+        };
+        let value = open(&self.key, reference, &sealed)?;
+        let value = String::from_utf8(value.to_vec())
+            .with_context(|| format!("secret {reference:?} is not UTF-8"))?;
+        Ok(Some(Arc::new(Zeroizing::new(value))))
+    }
+}
+
+fn decode_key(encoded: &str) -> anyhow::Result<LessSafeKey> {
+    let bytes = Zeroizing::new(
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .context("secrets encryption key is not valid base64")?,
+    );
+    ensure!(
+        bytes.len() == AES_256_GCM.key_len(),
+        "secrets encryption key must be {} bytes, got {}",
+        AES_256_GCM.key_len(),
+        bytes.len()
+    );
+    let key = UnboundKey::new(&AES_256_GCM, &bytes)
+        .map_err(|_| anyhow::anyhow!("invalid secrets encryption key"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn seal(key: &LessSafeKey, name: &str, plaintext: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| anyhow::anyhow!("failed to generate a nonce"))?;
+    let mut sealed = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(name.as_bytes()),
+        &mut sealed,
+    )
+    .map_err(|_| anyhow::anyhow!("failed to encrypt secret {name:?}"))?;
+    sealed.splice(0..0, nonce);
+    Ok(sealed)
+}
+
+fn open(key: &LessSafeKey, name: &str, sealed: &[u8]) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+    let Some((nonce, ciphertext)) = sealed.split_first_chunk::<NONCE_LEN>() else {
+        bail!("secret {name:?} is too short to be encrypted");
+    };
+    let mut buffer = Zeroizing::new(ciphertext.to_vec());
+    let len = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(*nonce),
+            Aad::from(name.as_bytes()),
+            &mut buffer,
+        )
+        .map_err(|_| anyhow::anyhow!("failed to decrypt secret {name:?}"))?
+        .len();
+    buffer.truncate(len);
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+    #[test]
+    fn sealed_values_open_only_under_their_own_name() {
+        let key = decode_key(KEY).unwrap();
+        let sealed = seal(&key, "db-password", b"hunter2").unwrap();
+        assert!(
+            !sealed.windows(7).any(|w| w == b"hunter2"),
+            "the stored value must not contain the plaintext"
+        );
+
+        assert_eq!(
+            open(&key, "db-password", &sealed).unwrap().as_slice(),
+            b"hunter2"
+        );
+        assert!(
+            open(&key, "api-key", &sealed).is_err(),
+            "a value moved to another key must not decrypt"
+        );
+
+        let other =
+            decode_key(&base64::engine::general_purpose::STANDARD.encode([7u8; 32])).unwrap();
+        assert!(open(&other, "db-password", &sealed).is_err());
+    }
+
+    #[test]
+    fn rejects_keys_of_the_wrong_length() {
+        let short = base64::engine::general_purpose::STANDARD.encode([0u8; 16]);
+        assert!(decode_key(&short).is_err());
+        assert!(decode_key("not base64!").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Secrets read over HTTP from a Vault-compatible KV v2 secrets engine.
+//!
+//! A reference is `path#field`: the secret at `path` under the backend's
+//! mount, and the field of its data to serve (`value` when omitted). Each
+//! lookup reads the latest version with `GET /v1/<mount>/data/<path>`,
+//! authenticated with the backend's token.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context as _, bail, ensure};
+use bytes::Bytes;
+use http_body_util::{BodyExt as _, Full};
+use hyper::StatusCode;
+use hyper_util::client::legacy::{Client, connect::HttpConnector};
+use hyper_util::rt::TokioExecutor;
+use zeroize::Zeroizing;
+
+use super::backend::{SecretBackend, SecretString};
+use crate::host::http_client::default_client_tls_config;
+
+/// The field a reference without `#field` reads.
+const DEFAULT_FIELD: &str = "value";
+
+/// How long one lookup may take, connecting included.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+type HttpsClient = Client<hyper_rustls::HttpsConnector<HttpConnector>, Full<Bytes>>;
+
+/// [`SecretBackend`] for a Vault (or OpenBao) KV v2 secrets engine.
+pub struct VaultBackend {
+    addr: String,
+    mount: String,
+    token: Zeroizing<String>,
+    namespace: Option<String>,
+    client: HttpsClient,
+}
+
+impl VaultBackend {
+    /// A backend for the server at `addr` (e.g. `https://vault:8200`) using
+    /// the KV v2 engine mounted at `secret`.
+    pub fn new(addr: &str, token: impl Into<String>) -> anyhow::Result<Self> {
+        let addr = addr.trim_end_matches('/').to_string();
+        let uri: http::Uri = addr
+            .parse()
+            .with_context(|| format!("invalid Vault address {addr:?}"))?;
+        ensure!(
+            matches!(uri.scheme_str(), Some("http" | "https")),
+            "Vault address {addr:?} must be an http or https URL"
+        );
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        let connector = hyper_rustls::HttpsConnectorBuilder::new()
+            .with_tls_config((*default_client_tls_config()).clone())
+            .https_or_http()
+            .enable_http1()
+            .wrap_connector(http);
+        Ok(Self {
+            addr,
+            mount: "secret".to_string(),
+            token: Zeroizing::new(token.into()),
+            namespace: None,
+            client: Client::builder(TokioExecutor::new()).build(connector),
+        })
+    }
+
+    /// Read from the KV v2 engine mounted at `mount` instead of `secret`.
+    pub fn with_mount(mut self, mount: &str) -> Self {
+        self.mount = mount.trim_matches('/').to_string();
+        self
+    }
+
+    /// Send requests in a Vault Enterprise namespace.
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    async fn read(&self, path: &str) -> anyhow::Result<Option<serde_json::Value>> {
+        let mut request =
+            hyper::Request::get(format!("{}/v1/{}/data/{path}", self.addr, self.mount))
+                .header("X-Vault-Token", self.token.as_str());
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        let request = request.body(Full::default())?;
+
+        let response = tokio::time::timeout(REQUEST_TIMEOUT, async {
+            let response = self.client.request(request).await?;
+            let status = response.status();
+            let body = response.into_body().collect().await?.to_bytes();
+            anyhow::Ok((status, body))
+        })
+        .await
+        .context("timed out reading from Vault")?
+        .context("failed to read from Vault")?;
+
+        match response {
+            (StatusCode::NOT_FOUND, _) => Ok(None),
+            (status, body) if status.is_success() => serde_json::from_slice(&body)
+                .map(Some)
+                .context("Vault returned an invalid response"),
+            (status, body) => bail!("Vault returned {status}: {}", vault_errors(&body)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretBackend for VaultBackend {
+    async fn get(&self, reference: &str) -> anyhow::Result<Option<SecretString>> {
+        let (path, field) = parse_reference(reference)?;
+        let Some(response) = self.read(path).await? else {
+            return Ok(None);
+        };
+        let data = response
+            .pointer("/data/data")
+            .context("Vault response has no secret data")?;
+        // A deleted version reads as `"data": null`.
+        let value = match data.get(field) {
+            None | Some(serde_json::Value::Null) => return Ok(None),
+            Some(serde_json::Value::String(value)) => value.clone(),
+            Some(other) => other.to_string(),
+        };
+        Ok(Some(Arc::new(Zeroizing::new(value))))
+    }
+}
+
+/// Split `path#field` and check the path is safe to put in a URL: plain
+/// segments only, so a reference can't escape the mount.
+fn parse_reference(reference: &str) -> anyhow::Result<(&str, &str)> {
+    let (path, field) = reference
+        .split_once('#')
+        .unwrap_or((reference, DEFAULT_FIELD));
+    ensure!(
+        !path.is_empty()
+            && path.split('/').all(|segment| {
+                !segment.is_empty()
+                    && segment != "."
+                    && segment != ".."
+                    && segment
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b"-_.".contains(&b))
+            }),
+        "invalid Vault secret path {path:?}"
+    );
+    ensure!(
+        !field.is_empty(),
+        "empty field in Vault reference {reference:?}"
+    );
+    Ok((path, field))
+}
+
+/// The messages of a Vault error response (`{"errors": [..]}`), or the raw
+/// body if it isn't one.
+fn vault_errors(body: &[u8]) -> String {
+    serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| {
+            let errors = v.get("errors")?.as_array()?;
+            Some(
+                errors
+                    .iter()
+                    .filter_map(|e| e.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            )
+        })
+        .unwrap_or_else(|| String::from_utf8_lossy(body).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_references() {
+        assert_eq!(
+            parse_reference("apps/orders/db#password").unwrap(),
+            ("apps/orders/db", "password")
+        );
+        assert_eq!(parse_reference("api-key").unwrap(), ("api-key", "value"));
+        for bad in ["", "a//b", "../sys/raw", "a/./b", "a?x=1", "a b", "a#"] {
+            assert!(parse_reference(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+}
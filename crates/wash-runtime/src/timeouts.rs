@@ -62,6 +62,10 @@ declare_timeouts! {
     shared_store_call = ("WASH_SHARED_STORE_CALL_TIMEOUT_SECS", 30);
     /// Max wall-clock for a trigger service to produce an HTTP response.
     http_response = ("WASH_HTTP_RESPONSE_TIMEOUT_SECS", 600);
+    /// How long a revision replaced by a workload update may keep running
+    /// after it is unrouted, so calls already in flight can finish before its
+    /// service is stopped.
+    workload_update_drain = ("WASH_WORKLOAD_UPDATE_DRAIN_SECS", 30);
     /// The per-plugin stop budget. A host component plugin's `stop()` waits
     /// this long for its supervisor to exit before aborting it; `Host::stop`
     /// caps every plugin's `stop()` at this budget plus a one-second grace so
@@ -15,10 +15,11 @@
 //!   [`EmptyDirVolume`], [`HostPathVolume`]
 
 use bytes::Bytes;
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use crate::host::allowed_hosts::AllowedHost;
 use crate::host::allowed_ip_name::AllowedIpName;
+use crate::host::allowed_socket_addr::AllowedSocketAddrs;
 use crate::wit::WitInterface;
 
 /// Represents a deployable workload containing one or more WebAssembly components.
@@ -50,12 +51,17 @@ pub enum WorkloadState {
 
 /// Configuration for a long-running service component that handles requests.
 /// Services can be restarted if they fail and have resource limits.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Service {
     pub bytes: Bytes,
     pub digest: Option<String>,
     pub local_resources: LocalResources,
     pub max_restarts: u64,
+    /// How long one host-invoked call into the service (an HTTP request or a
+    /// message it handles) may run before it is interrupted and trapped. The
+    /// service's own `wasi:cli/run` is not a call and is never timed. `None`
+    /// is unbounded. See [`crate::engine::epoch`].
+    pub max_execution_time: Option<Duration>,
 }
 
 /// A WebAssembly component that can be executed as part of a workload.
@@ -82,13 +88,23 @@ pub struct Component {
     /// blocks — a guest driving its own executor with `block_on` must stay at
     /// one.
     pub max_concurrency: i32,
+    /// How long one call into the component may run before it is interrupted
+    /// and trapped, measured in wall-clock time. `None` is unbounded. See
+    /// [`crate::engine::epoch`].
+    pub max_execution_time: Option<Duration>,
 }
 
 /// Resource limits and configuration for a component or service.
 /// Defines memory, CPU limits, configuration values, and volume mounts.
 #[derive(Debug, Clone, PartialEq)]
 pub struct LocalResources {
+    /// Linear memory and table space, in MiB, every store built for this
+    /// component may hold. Zero or below means unlimited. See
+    /// [`crate::engine::limits`].
     pub memory_limit_mb: i32,
+    /// Time slice, in epoch ticks, after which a running guest yields to the
+    /// async executor. Not a cap: larger values give longer slices. Zero or
+    /// below means no slicing. See [`crate::engine::limits`].
     pub cpu_limit: i32,
     /// Opaque key-value configuration shared between operator + runtime + plugins.
     /// Allows passing arbitrary configuration values to influence implementation behavior for all component interfaces.
@@ -121,6 +137,12 @@ pub struct LocalResources {
     /// the wire (proto / wash YAML) are parsed at conversion time, so the
     /// resolve path matches against the typed enum directly.
     pub allowed_ip_name_lookups: Arc<[AllowedIpName]>,
+    /// Which addresses the component may connect and bind through raw
+    /// `wasi:sockets` TCP and UDP, and whether the workload-local loopback
+    /// network is available. The default denies every non-loopback connect
+    /// and bind. See [`crate::host::allowed_socket_addr`] for the accepted
+    /// entry forms.
+    pub allowed_socket_addrs: Arc<AllowedSocketAddrs>,
 }
 
 impl Default for LocalResources {
@@ -133,6 +155,7 @@ impl Default for LocalResources {
             volume_mounts: Vec::new(),
             allowed_hosts: Default::default(),
             allowed_ip_name_lookups: Default::default(),
+            allowed_socket_addrs: Default::default(),
         }
     }
 }
@@ -222,6 +245,20 @@ pub struct WorkloadStartResponse {
     pub workload_status: WorkloadStatus,
 }
 
+/// Request to replace a running workload with a new revision of it, without
+/// dropping traffic in between. See [`crate::host::HostApi::workload_update`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadUpdateRequest {
+    pub workload_id: String,
+    pub workload: Workload,
+}
+
+/// Response after attempting to update a workload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadUpdateResponse {
+    pub workload_status: WorkloadStatus,
+}
+
 /// Request to get the status of a specific workload.
 #[derive(Debug, Clone, PartialEq)]
 pub struct WorkloadStatusRequest {
@@ -235,9 +272,13 @@ pub struct WorkloadStatusResponse {
 }
 
 /// Request to stop a running workload.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct WorkloadStopRequest {
     pub workload_id: String,
+    /// How long calls already in flight may take to finish once the workload
+    /// is unrouted. `None` uses the host's
+    /// [`HostConfig::drain_timeout`](crate::host::HostConfig::drain_timeout).
+    pub drain_timeout: Option<std::time::Duration>,
 }
 
 /// Response after attempting to stop a workload.
@@ -245,3 +286,43 @@ pub struct WorkloadStopRequest {
 pub struct WorkloadStopResponse {
     pub workload_status: WorkloadStatus,
 }
+
+/// Request to list every workload on the host.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WorkloadListRequest {}
+
+/// Response listing every workload on the host, ordered by workload ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadListResponse {
+    pub workloads: Vec<WorkloadSummary>,
+}
+
+/// One workload in a [`WorkloadListResponse`]. `components` are only known
+/// for a running workload and are left empty for one that is starting,
+/// stopping or has failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadSummary {
+    pub workload_id: String,
+    pub name: String,
+    pub namespace: String,
+    pub workload_state: WorkloadState,
+    pub message: String,
+    pub components: Vec<ComponentStatus>,
+}
+
+/// Live status of one component of a running workload, or of its service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentStatus {
+    pub name: String,
+    /// The digest the component was pulled by, if it came from a registry.
+    pub digest: Option<String>,
+    /// The most instances the component keeps warm; zero when it keeps none.
+    pub pool_size: usize,
+    /// Warm instances currently live and admitting calls.
+    pub warm_instances: usize,
+    /// Calls running in the component right now.
+    pub in_flight: usize,
+    /// Warm instances, or the service, replaced after a fault since the
+    /// workload started.
+    pub restarts: u64,
+}
@@ -1,15 +1,16 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
 use crate::component_source::{ComponentSource, LoadedComponent};
+use crate::host::events::HostEvent;
 use crate::host::{Host, HostApi, HostConfig};
 use crate::oci::{self, OciConfig};
 use crate::plugin::HostPlugin;
 use anyhow::{Context as _, anyhow};
 use futures::StreamExt as _;
 use tokio::sync::oneshot;
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 
 pub const HOST_API_PREFIX: &str = "runtime.host";
 pub const OPERATOR_API_PREFIX: &str = "runtime.operator";
@@ -34,6 +35,7 @@ pub struct ClusterHostBuilder {
     heartbeat_interval: Option<Duration>,
     cleanup_interval: Option<Duration>,
     cleanup_age: Option<Duration>,
+    max_cache_bytes: Option<u64>,
     host_config: Option<HostConfig>,
 }
 
@@ -105,6 +107,14 @@ impl ClusterHostBuilder {
         self
     }
 
+    /// Caps the OCI cache at `max_bytes`. Each artifact cleaner run evicts
+    /// the least recently used components past the cap, never those of a
+    /// running workload.
+    pub fn with_max_cache_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_cache_bytes = Some(max_bytes);
+        self
+    }
+
     pub fn with_engine(mut self, engine: crate::engine::Engine) -> Self {
         self.host_builder = self.host_builder.with_engine(engine);
         self
@@ -154,7 +164,10 @@ impl ClusterHostBuilder {
             nats_client,
             heartbeat_interval,
             cleanup_interval: self.cleanup_interval.unwrap_or(Duration::from_secs(300)),
-            cleanup_age: self.cleanup_age.unwrap_or(Duration::from_secs(3600)),
+            cache_limits: oci::CacheLimits {
+                max_age: Some(self.cleanup_age.unwrap_or(Duration::from_secs(3600))),
+                max_bytes: self.max_cache_bytes,
+            },
         })
     }
 }
@@ -164,7 +177,7 @@ pub struct ClusterHost {
     nats_client: Arc<async_nats::Client>,
     heartbeat_interval: Duration,
     cleanup_interval: Duration,
-    cleanup_age: Duration,
+    cache_limits: oci::CacheLimits,
 }
 
 impl ClusterHost {
@@ -199,12 +212,16 @@ impl ClusterHost {
 
         host.log_interfaces();
 
+        // Subscribe before the host can take any request, so no event is missed.
+        let mut events = host.subscribe_events();
+
         let task = tokio::task::spawn({
             let host = host.clone();
             async move {
                 let host_subject = host_subject(host_id.as_ref());
 
                 let heartbeat_subject = heartbeat_subject(host_id.as_ref());
+                let events_subject = events_subject(host_id.as_ref());
 
                 let mut api_subscription = nats_client
                     .subscribe(host_subject)
@@ -213,6 +230,9 @@ impl ClusterHost {
                 let mut heartbeat_timer = tokio::time::interval(heartbeat_interval);
 
                 let mut oci_cleanup_timer = tokio::time::interval(cleanup_interval);
+                // Cleared if the host's event sender goes away, so the closed
+                // channel isn't polled again.
+                let mut events_open = true;
 
                 loop {
                     tokio::select! {
@@ -223,9 +243,8 @@ impl ClusterHost {
                         }
                         // OCI cache cleanup
                         _ = oci_cleanup_timer.tick() => {
-                            if let Some(cache_dir) = host.config().oci_cache_dir.as_ref() &&
-                            let Err(e) = oci::cleanup_cache(cache_dir, self.cleanup_age).await {
-                                error!("error during OCI cache cleanup: {e}");
+                            if let Some(cache_dir) = host.config().oci_cache_dir.as_ref() {
+                                clean_oci_cache(host.as_ref(), cache_dir, self.cache_limits).await;
                             }
                         }
                         // Send heartbeat
@@ -235,6 +254,22 @@ impl ClusterHost {
                                 .context("failed to serialize heartbeat")?;
                             nats_client.publish(heartbeat_subject.clone(), heartbeat_bytes.into()).await.context("failed to publish heartbeat")?;
                         }
+                        // Publish workload and plugin lifecycle events
+                        event = events.recv(), if events_open => match event {
+                            Ok(event) => {
+                                let event = host_event(&host_id, event);
+                                let event_bytes = to_api(&event)
+                                    .context("failed to serialize host event")?;
+                                nats_client.publish(events_subject.clone(), event_bytes.into()).await.context("failed to publish host event")?;
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                                warn!(skipped, "host event publisher fell behind; events were dropped");
+                            }
+                            Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                                warn!("host event sender closed; no longer publishing host events");
+                                events_open = false;
+                            }
+                        },
                         // Handle API requests
                         Some(msg) = api_subscription.next() => {
                             let response = handle_command(host.as_ref(), &msg, host.config()).await;
@@ -328,6 +363,10 @@ pub fn heartbeat_subject(host_id: &str) -> String {
     format!("{OPERATOR_API_PREFIX}.heartbeat.{host_id}")
 }
 
+pub fn events_subject(host_id: &str) -> String {
+    format!("{OPERATOR_API_PREFIX}.events.{host_id}")
+}
+
 /// Helper function to serialize a message to the API format.
 fn to_api<T: prost::Message + serde::Serialize>(msg: &T) -> Result<Vec<u8>, anyhow::Error> {
     serde_json::to_vec_pretty(msg).map_err(anyhow::Error::new)
@@ -368,6 +407,16 @@ async fn handle_command(
             let res = workload_status(host, req).await?;
             to_api(&res)
         }
+        "workload.update" => {
+            let req: types::v2::WorkloadUpdateRequest = from_api(payload)?;
+            let res = workload_update(host, req, config).await?;
+            to_api(&res)
+        }
+        "workload.list" => {
+            let req: types::v2::WorkloadListRequest = from_api(payload)?;
+            let res = workload_list(host, req).await?;
+            to_api(&res)
+        }
         // catch-all
         _ => anyhow::bail!("unknown command: {command}"),
     }
@@ -385,6 +434,7 @@ fn image_pull_secret_to_oci_config(
     oci_config.cache_dir = config.oci_cache_dir.clone();
     oci_config.insecure = config.allow_oci_insecure;
     oci_config.timeout = config.oci_pull_timeout;
+    oci_config.signature_policy = config.oci_signature_policy.clone();
 
     oci_config
 }
@@ -410,6 +460,8 @@ fn component_from_wire(
         pool_size: wire.pool_size,
         max_invocations: wire.max_invocations,
         max_concurrency: wire.max_concurrency,
+        max_execution_time: (wire.max_execution_time_ms > 0)
+            .then(|| std::time::Duration::from_millis(wire.max_execution_time_ms)),
     }
 }
 
@@ -420,6 +472,42 @@ async fn host_heartbeat(host: &impl HostApi) -> anyhow::Result<types::v2::HostHe
     Ok(hb.into())
 }
 
+/// Run the artifact cleaner over the OCI cache, sparing the images of running
+/// workloads, and log what it freed.
+#[instrument(level = "debug", skip(host))]
+async fn clean_oci_cache(host: &impl HostApi, cache_dir: &Path, limits: oci::CacheLimits) {
+    let in_use = match host
+        .workload_list(crate::types::WorkloadListRequest {})
+        .await
+    {
+        Ok(list) => list
+            .workloads
+            .into_iter()
+            .flat_map(|workload| workload.components)
+            .filter_map(|component| component.digest)
+            .collect(),
+        Err(e) => {
+            // Without knowing what runs, anything evicted could be in use.
+            error!("skipping OCI cache cleanup, failed to list workloads: {e:#}");
+            return;
+        }
+    };
+    match oci::cleanup_cache(cache_dir, limits, &in_use).await {
+        Ok(cleanup) if cleanup.bytes_freed > 0 || cleanup.references_removed > 0 => info!(
+            blobs_removed = cleanup.blobs_removed,
+            references_removed = cleanup.references_removed,
+            bytes_freed = cleanup.bytes_freed,
+            bytes_retained = cleanup.bytes_retained,
+            "cleaned up OCI cache"
+        ),
+        Ok(cleanup) => debug!(
+            bytes_retained = cleanup.bytes_retained,
+            "OCI cache cleanup freed nothing"
+        ),
+        Err(e) => error!("error during OCI cache cleanup: {e}"),
+    }
+}
+
 #[instrument(skip_all, fields(
     workload_id = %req.workload_id,
     workload.name=?req.workload.as_ref().map(|w| &w.name).unwrap_or(&"<none>".to_string()),
@@ -430,15 +518,7 @@ async fn workload_start(
     req: types::v2::WorkloadStartRequest,
     config: &HostConfig,
 ) -> anyhow::Result<types::v2::WorkloadStartResponse> {
-    let Some(types::v2::Workload {
-        namespace,
-        name,
-        annotations,
-        service,
-        wit_world,
-        volumes,
-    }) = req.workload
-    else {
+    let Some(workload) = req.workload else {
         anyhow::bail!("workload is required");
     };
 
@@ -447,6 +527,99 @@ async fn workload_start(
         anyhow::bail!("workload_id is required");
     }
 
+    let workload = match pull_workload(workload, config).await {
+        Ok(workload) => workload,
+        Err(message) => {
+            return Ok(types::v2::WorkloadStartResponse {
+                workload_status: Some(error_status(workload_id, message)),
+            });
+        }
+    };
+
+    let request = crate::types::WorkloadStartRequest {
+        workload_id: workload_id.clone(),
+        workload,
+    };
+
+    info!(
+        workload_id=?workload_id,
+        namespace=?request.workload.namespace,
+        name=?request.workload.name,
+        "Starting workload");
+
+    Ok(host.workload_start(request).await?.into())
+}
+
+#[instrument(skip_all, fields(
+    workload_id = %req.workload_id,
+    workload.name=?req.workload.as_ref().map(|w| &w.name).unwrap_or(&"<none>".to_string()),
+    workload.namespace=?req.workload.as_ref().map(|w| &w.namespace).unwrap_or(&"<none>".to_string())),
+    )]
+async fn workload_update(
+    host: &impl HostApi,
+    req: types::v2::WorkloadUpdateRequest,
+    config: &HostConfig,
+) -> anyhow::Result<types::v2::WorkloadUpdateResponse> {
+    let Some(workload) = req.workload else {
+        anyhow::bail!("workload is required");
+    };
+
+    let workload_id = req.workload_id.clone();
+    if workload_id.is_empty() {
+        anyhow::bail!("workload_id is required");
+    }
+
+    // Pull before touching the running workload: a bad image or config fails
+    // the update and leaves the current revision serving.
+    let workload = match pull_workload(workload, config).await {
+        Ok(workload) => workload,
+        Err(message) => {
+            return Ok(types::v2::WorkloadUpdateResponse {
+                workload_status: Some(error_status(workload_id, message)),
+            });
+        }
+    };
+
+    let request = crate::types::WorkloadUpdateRequest {
+        workload_id: workload_id.clone(),
+        workload,
+    };
+
+    info!(
+        workload_id=?workload_id,
+        namespace=?request.workload.namespace,
+        name=?request.workload.name,
+        "Updating workload");
+
+    Ok(host.workload_update(request).await?.into())
+}
+
+/// An `Error` workload status, for a request that failed before reaching the
+/// host.
+fn error_status(workload_id: String, message: String) -> types::v2::WorkloadStatus {
+    types::v2::WorkloadStatus {
+        workload_id,
+        workload_state: types::v2::WorkloadState::Error.into(),
+        message,
+    }
+}
+
+/// Pull a wire workload's component and service images and convert it to the
+/// runtime's [`Workload`](crate::types::Workload). A failure is returned as the
+/// message for the workload's error status.
+async fn pull_workload(
+    workload: types::v2::Workload,
+    config: &HostConfig,
+) -> Result<crate::types::Workload, String> {
+    let types::v2::Workload {
+        namespace,
+        name,
+        annotations,
+        service,
+        wit_world,
+        volumes,
+    } = workload;
+
     let (components, host_interfaces) = if let Some(wit_world) = wit_world {
         let mut pulled_components = Vec::with_capacity(wit_world.components.len());
         for component in &wit_world.components {
@@ -458,37 +631,18 @@ async fn workload_start(
             // `load` already names the reference it failed on; this says which
             // of the workload's components asked for it, so a multi-component
             // start reports something the operator can act on.
-            let loaded =
-                match source.load(oci_config).await.with_context(|| {
-                    format!("failed to pull image for component '{}'", component.name)
-                }) {
-                    Ok(loaded) => loaded,
-                    Err(e) => {
-                        return Ok(types::v2::WorkloadStartResponse {
-                            workload_status: Some(types::v2::WorkloadStatus {
-                                workload_id: workload_id.clone(),
-                                workload_state: types::v2::WorkloadState::Error.into(),
-                                message: format!("{e:#}"),
-                            }),
-                        });
-                    }
-                };
+            let loaded = source
+                .load(oci_config)
+                .await
+                .with_context(|| format!("failed to pull image for component '{}'", component.name))
+                .map_err(|e| format!("{e:#}"))?;
             let local_resources = match component.local_resources.clone() {
-                Some(lr) => match crate::types::LocalResources::try_from(lr) {
-                    Ok(lr) => lr,
-                    Err(e) => {
-                        return Ok(types::v2::WorkloadStartResponse {
-                            workload_status: Some(types::v2::WorkloadStatus {
-                                workload_id: workload_id.clone(),
-                                workload_state: types::v2::WorkloadState::Error.into(),
-                                message: format!(
-                                    "invalid local_resources for component {}: {e:#}",
-                                    component.name
-                                ),
-                            }),
-                        });
-                    }
-                },
+                Some(lr) => crate::types::LocalResources::try_from(lr).map_err(|e| {
+                    format!(
+                        "invalid local_resources for component {}: {e:#}",
+                        component.name
+                    )
+                })?,
                 None => crate::types::LocalResources::default(),
             };
             pulled_components.push(component_from_wire(component, loaded, local_resources))
@@ -513,35 +667,14 @@ async fn workload_start(
         };
         // Distinguishes a service pull failure from a component one; both
         // otherwise report the same reference and cause.
-        let loaded = match source
+        let loaded = source
             .load(oci_config)
             .await
             .context("failed to pull image for the workload service")
-        {
-            Ok(loaded) => loaded,
-            Err(e) => {
-                return Ok(types::v2::WorkloadStartResponse {
-                    workload_status: Some(types::v2::WorkloadStatus {
-                        workload_id: workload_id.clone(),
-                        workload_state: types::v2::WorkloadState::Error.into(),
-                        message: format!("{e:#}"),
-                    }),
-                });
-            }
-        };
+            .map_err(|e| format!("{e:#}"))?;
         let local_resources = match service.local_resources.clone() {
-            Some(lr) => match crate::types::LocalResources::try_from(lr) {
-                Ok(lr) => lr,
-                Err(e) => {
-                    return Ok(types::v2::WorkloadStartResponse {
-                        workload_status: Some(types::v2::WorkloadStatus {
-                            workload_id: workload_id.clone(),
-                            workload_state: types::v2::WorkloadState::Error.into(),
-                            message: format!("invalid local_resources for service: {e:#}"),
-                        }),
-                    });
-                }
-            },
+            Some(lr) => crate::types::LocalResources::try_from(lr)
+                .map_err(|e| format!("invalid local_resources for service: {e:#}"))?,
             None => crate::types::LocalResources::default(),
         };
         Some(crate::types::Service {
@@ -549,33 +682,22 @@ async fn workload_start(
             digest: loaded.digest,
             local_resources,
             max_restarts: service.max_restarts,
+            max_execution_time: (service.max_execution_time_ms > 0)
+                .then(|| std::time::Duration::from_millis(service.max_execution_time_ms)),
         })
     } else {
         None
     };
 
-    let volumes = volumes.into_iter().map(Into::into).collect();
-
-    let request = crate::types::WorkloadStartRequest {
-        workload_id: workload_id.clone(),
-        workload: crate::types::Workload {
-            namespace,
-            name,
-            annotations,
-            service,
-            components,
-            host_interfaces,
-            volumes,
-        },
-    };
-
-    info!(
-        workload_id=?workload_id,
-        namespace=?request.workload.namespace,
-        name=?request.workload.name,
-        "Starting workload");
-
-    Ok(host.workload_start(request).await?.into())
+    Ok(crate::types::Workload {
+        namespace,
+        name,
+        annotations,
+        service,
+        components,
+        host_interfaces,
+        volumes: volumes.into_iter().map(Into::into).collect(),
+    })
 }
 
 #[instrument(skip_all, fields(workload_id = %req.workload_id))]
@@ -604,6 +726,16 @@ async fn workload_status(
         .map(|resp| resp.into())
 }
 
+#[instrument(skip_all)]
+async fn workload_list(
+    host: &impl HostApi,
+    req: types::v2::WorkloadListRequest,
+) -> anyhow::Result<types::v2::WorkloadListResponse> {
+    debug!("Listing workloads");
+
+    host.workload_list(req.into()).await.map(|resp| resp.into())
+}
+
 impl From<types::v2::WitInterface> for crate::wit::WitInterface {
     fn from(wi: types::v2::WitInterface) -> Self {
         crate::wit::WitInterface {
@@ -664,12 +796,33 @@ impl TryFrom<types::v2::LocalResources> for crate::types::LocalResources {
             cpu_limit: lr.cpu_limit,
             config: lr.config,
             volume_mounts: lr.volume_mounts.into_iter().map(Into::into).collect(),
-            allowed_hosts: parse_policy_entries(&lr.allowed_hosts, "allowed_hosts")?,
+            allowed_hosts: parse_policy_entries(&lr.allowed_hosts, "allowed_hosts")?.into(),
             environment: lr.environment,
             allowed_ip_name_lookups: parse_policy_entries(
                 &lr.allowed_ip_name_lookups,
                 "allowed_ip_name_lookups",
-            )?,
+            )?
+            .into(),
+            allowed_socket_addrs: Arc::new(
+                lr.allowed_socket_addrs
+                    .map(TryInto::try_into)
+                    .transpose()?
+                    .unwrap_or_default(),
+            ),
+        })
+    }
+}
+
+impl TryFrom<types::v2::AllowedSocketAddrs>
+    for crate::host::allowed_socket_addr::AllowedSocketAddrs
+{
+    type Error = anyhow::Error;
+
+    fn try_from(addrs: types::v2::AllowedSocketAddrs) -> Result<Self, Self::Error> {
+        Ok(Self {
+            connect: parse_policy_entries(&addrs.connect, "allowed_socket_addrs.connect")?,
+            bind: parse_policy_entries(&addrs.bind, "allowed_socket_addrs.bind")?,
+            loopback: !addrs.deny_loopback,
         })
     }
 }
@@ -682,7 +835,7 @@ impl TryFrom<types::v2::LocalResources> for crate::types::LocalResources {
 /// Failures are collected and joined into one message, rendered as a
 /// heading line plus a bullet per bad entry, so a workload with several bad
 /// entries doesn't have to be fixed one at a time.
-fn parse_policy_entries<T>(entries: &[String], field: &str) -> anyhow::Result<Arc<[T]>>
+fn parse_policy_entries<T>(entries: &[String], field: &str) -> anyhow::Result<Vec<T>>
 where
     T: std::str::FromStr<Err = anyhow::Error>,
 {
@@ -697,7 +850,7 @@ where
     if !errors.is_empty() {
         return Err(anyhow!("invalid {field}:\n  - {}", errors.join("\n  - ")));
     }
-    Ok(parsed.into())
+    Ok(parsed)
 }
 
 impl From<crate::types::HostHeartbeat> for types::v2::HostHeartbeat {
@@ -725,6 +878,50 @@ impl From<crate::types::HostHeartbeat> for types::v2::HostHeartbeat {
     }
 }
 
+/// Stamp a host event with its publishing host and time, in wire form.
+fn host_event(host_id: &str, event: HostEvent) -> types::v2::HostEvent {
+    use types::v2::host_event::Event;
+    let event = match event {
+        HostEvent::WorkloadStarting { workload_id } => {
+            Event::WorkloadStarting(types::v2::WorkloadStartingEvent { workload_id })
+        }
+        HostEvent::WorkloadRunning { workload_id } => {
+            Event::WorkloadRunning(types::v2::WorkloadRunningEvent { workload_id })
+        }
+        HostEvent::WorkloadFailed {
+            workload_id,
+            reason,
+        } => Event::WorkloadFailed(types::v2::WorkloadFailedEvent {
+            workload_id,
+            reason,
+        }),
+        HostEvent::WorkloadStopped { workload_id } => {
+            Event::WorkloadStopped(types::v2::WorkloadStoppedEvent { workload_id })
+        }
+        HostEvent::ComponentRestarted {
+            workload_id,
+            component_id,
+            reason,
+        } => Event::ComponentRestarted(types::v2::ComponentRestartedEvent {
+            workload_id,
+            component_id,
+            reason,
+        }),
+        HostEvent::PluginCrashLooped {
+            plugin_id,
+            restarts,
+        } => Event::PluginCrashLooped(types::v2::PluginCrashLoopedEvent {
+            plugin_id,
+            restarts,
+        }),
+    };
+    types::v2::HostEvent {
+        host_id: host_id.to_string(),
+        timestamp: Some(chrono::Utc::now().into()),
+        event: Some(event),
+    }
+}
+
 impl From<crate::wit::WitInterface> for types::v2::WitInterface {
     fn from(wi: crate::wit::WitInterface) -> Self {
         types::v2::WitInterface {
@@ -744,6 +941,8 @@ impl From<types::v2::WorkloadStopRequest> for crate::types::WorkloadStopRequest
     fn from(req: types::v2::WorkloadStopRequest) -> Self {
         crate::types::WorkloadStopRequest {
             workload_id: req.workload_id,
+            drain_timeout: (req.drain_timeout_ms > 0)
+                .then(|| std::time::Duration::from_millis(req.drain_timeout_ms)),
         }
     }
 }
@@ -756,6 +955,12 @@ impl From<types::v2::WorkloadStatusRequest> for crate::types::WorkloadStatusRequ
     }
 }
 
+impl From<types::v2::WorkloadListRequest> for crate::types::WorkloadListRequest {
+    fn from(_req: types::v2::WorkloadListRequest) -> Self {
+        crate::types::WorkloadListRequest {}
+    }
+}
+
 // Conversions from runtime::host response types to API v2 types
 
 impl From<crate::types::WorkloadStartResponse> for types::v2::WorkloadStartResponse {
@@ -782,6 +987,49 @@ impl From<crate::types::WorkloadStatusResponse> for types::v2::WorkloadStatusRes
     }
 }
 
+impl From<crate::types::WorkloadUpdateResponse> for types::v2::WorkloadUpdateResponse {
+    fn from(resp: crate::types::WorkloadUpdateResponse) -> Self {
+        types::v2::WorkloadUpdateResponse {
+            workload_status: Some(resp.workload_status.into()),
+        }
+    }
+}
+
+impl From<crate::types::WorkloadListResponse> for types::v2::WorkloadListResponse {
+    fn from(resp: crate::types::WorkloadListResponse) -> Self {
+        types::v2::WorkloadListResponse {
+            workloads: resp.workloads.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::types::WorkloadSummary> for types::v2::WorkloadSummary {
+    fn from(summary: crate::types::WorkloadSummary) -> Self {
+        types::v2::WorkloadSummary {
+            workload_id: summary.workload_id,
+            name: summary.name,
+            namespace: summary.namespace,
+            workload_state: summary.workload_state as i32,
+            message: summary.message,
+            components: summary.components.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::types::ComponentStatus> for types::v2::ComponentStatus {
+    fn from(status: crate::types::ComponentStatus) -> Self {
+        let saturate = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        types::v2::ComponentStatus {
+            name: status.name,
+            digest: status.digest.unwrap_or_default(),
+            pool_size: saturate(status.pool_size),
+            warm_instances: saturate(status.warm_instances),
+            in_flight: saturate(status.in_flight),
+            restarts: status.restarts,
+        }
+    }
+}
+
 impl From<crate::types::WorkloadStatus> for types::v2::WorkloadStatus {
     fn from(status: crate::types::WorkloadStatus) -> Self {
         types::v2::WorkloadStatus {
@@ -822,6 +1070,7 @@ mod tests {
             pool_size: 4,
             max_invocations: 100,
             max_concurrency: 8,
+            max_execution_time_ms: 250,
             ..Default::default()
         };
 
@@ -838,6 +1087,10 @@ mod tests {
         assert_eq!(component.pool_size, 4);
         assert_eq!(component.max_invocations, 100);
         assert_eq!(component.max_concurrency, 8);
+        assert_eq!(
+            component.max_execution_time,
+            Some(std::time::Duration::from_millis(250))
+        );
 
         // And the runtime reads those limits as the policy they name.
         assert_eq!(
@@ -867,6 +1120,7 @@ mod tests {
                 "https://api.example.com".to_string(),
             ],
             allowed_ip_name_lookups: vec!["*.example.com".to_string(), "127.0.0.1".to_string()],
+            allowed_socket_addrs: None,
         };
         let lr = crate::types::LocalResources::try_from(proto).expect("conversion should succeed");
         assert_eq!(lr.allowed_ip_name_lookups.len(), 2);
@@ -888,6 +1142,39 @@ mod tests {
         assert!(matches!(lr.allowed_hosts[3], AllowedHost::Url(_)));
     }
 
+    #[test]
+    fn try_from_v2_local_resources_parses_allowed_socket_addrs() {
+        let mut proto = types::v2::LocalResources::default();
+        let lr = crate::types::LocalResources::try_from(proto.clone())
+            .expect("conversion should succeed");
+        assert_eq!(
+            *lr.allowed_socket_addrs,
+            crate::host::allowed_socket_addr::AllowedSocketAddrs::default(),
+            "an absent policy denies everything but loopback"
+        );
+
+        proto.allowed_socket_addrs = Some(types::v2::AllowedSocketAddrs {
+            connect: vec!["10.0.0.0/8:5432".to_string(), "*:443".to_string()],
+            bind: vec!["0.0.0.0:5353".to_string()],
+            deny_loopback: true,
+        });
+        let lr = crate::types::LocalResources::try_from(proto.clone())
+            .expect("conversion should succeed");
+        assert_eq!(lr.allowed_socket_addrs.connect.len(), 2);
+        assert_eq!(lr.allowed_socket_addrs.bind.len(), 1);
+        assert!(!lr.allowed_socket_addrs.loopback);
+
+        proto.allowed_socket_addrs = Some(types::v2::AllowedSocketAddrs {
+            connect: vec!["10.0.0.1/8".to_string()],
+            ..Default::default()
+        });
+        let err = crate::types::LocalResources::try_from(proto)
+            .expect_err("conversion should reject a block with host bits set");
+        let msg = format!("{err:#}");
+        assert!(msg.contains("allowed_socket_addrs.connect"), "{msg}");
+        assert!(msg.contains("10.0.0.1/8"), "{msg}");
+    }
+
     #[test]
     fn try_from_v2_local_resources_rejects_bad_allowed_hosts_entry() {
         // An ambiguous wildcard (`*com` matches every .com) must be
@@ -903,6 +1190,7 @@ mod tests {
             volume_mounts: vec![],
             allowed_hosts: vec!["*com".to_string()],
             allowed_ip_name_lookups: vec![],
+            allowed_socket_addrs: None,
         };
         let err = crate::types::LocalResources::try_from(proto)
             .expect_err("conversion should reject ambiguous wildcard");
@@ -931,6 +1219,7 @@ mod tests {
                 "example.com:notaport".to_string(),       // bad port
             ],
             allowed_ip_name_lookups: vec![],
+            allowed_socket_addrs: None,
         };
         let err = crate::types::LocalResources::try_from(proto)
             .expect_err("conversion should reject all bad entries");
@@ -969,4 +1258,22 @@ mod tests {
             Some(("testuser".to_string(), "testpass".to_string()))
         );
     }
+
+    #[test]
+    fn test_image_pull_secret_to_oci_config_carries_signature_policy() {
+        let host_config = HostConfig {
+            oci_signature_policy: oci::signature::SignaturePolicy {
+                trusted_keys: vec![],
+                rules: vec!["ghcr.io/acme=require".parse().unwrap()],
+            },
+            ..Default::default()
+        };
+        let config = image_pull_secret_to_oci_config(&host_config, &None);
+        assert_eq!(
+            config
+                .signature_policy
+                .mode_for(&"ghcr.io/acme/kv:1".parse().unwrap()),
+            oci::signature::VerificationMode::Require
+        );
+    }
 }
@@ -227,6 +227,7 @@ pub fn component_workload_request(
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces,
             volumes: vec![],
@@ -246,6 +247,7 @@ pub fn default_counter_resources() -> LocalResources {
         volume_mounts: vec![],
         allowed_hosts: vec!["example.com".parse().unwrap()].into(),
         allowed_ip_name_lookups: Default::default(),
+        allowed_socket_addrs: Default::default(),
     }
 }
 
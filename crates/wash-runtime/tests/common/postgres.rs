@@ -27,17 +27,18 @@ use wash_runtime::{
 use super::http_incoming_handler_interface;
 
 /// The single async postgres p3 fixture; its query is chosen by request path.
-const STREAM_FIXTURE_WASM: &[u8] = include_bytes!("../wasm/postgres_stream_p3.wasm");
+pub const STREAM_FIXTURE_WASM: &[u8] = include_bytes!("../wasm/postgres_stream_p3.wasm");
 
-/// The unnamed (default) async `wasmcloud:postgres@0.2.0` interface. `types` and
-/// `query` are imported unlabeled; `database` selects the target database within
-/// the plugin's bouncer connection.
+/// The unnamed (default) async `wasmcloud:postgres@0.2.0` interface. `types`,
+/// `query` and `transaction` are imported unlabeled; `database` selects the
+/// target database within the plugin's bouncer connection.
 pub fn async_pg_interface(database: &str) -> WitInterface {
     WitInterface {
         namespace: "wasmcloud".to_string(),
         package: "postgres".to_string(),
-        interfaces: ["types".to_string(), "query".to_string()]
+        interfaces: ["types", "query", "transaction"]
             .into_iter()
+            .map(String::from)
             .collect(),
         version: Some(semver::Version::parse("0.2.0").unwrap()),
         config: HashMap::from([("database".to_string(), database.to_string())]),
@@ -87,17 +88,29 @@ pub async fn start_postgres_workload(
     host_addr: &str,
     host_header: &str,
 ) -> Result<(std::net::SocketAddr, impl HostApi)> {
+    // Base URL without a database — the plugin strips it and the workload's
+    // interface config supplies `database`.
+    start_postgres_workload_with_url(
+        &format!("postgres://postgres:postgres@{host_addr}/"),
+        host_header,
+    )
+    .await
+}
+
+/// [`start_postgres_workload`], with the plugin connecting through
+/// `bouncer_url` (e.g. to cap its `pool_size`).
+pub async fn start_postgres_workload_with_url(
+    bouncer_url: &str,
+    host_header: &str,
+) -> Result<(std::net::SocketAddr, impl HostApi + use<>)> {
     let engine = Engine::builder().build()?;
     let ingress = Ingress::new(DevRouter::default(), "127.0.0.1:0".parse()?).await?;
     let addr = ingress.addr();
 
-    // Base URL without a database — the plugin strips it and the workload's
-    // interface config supplies `database`.
-    let bouncer_url = format!("postgres://postgres:postgres@{host_addr}/");
     let host = HostBuilder::new()
         .with_engine(engine)
         .with_http_handler(Arc::new(ingress))
-        .with_plugin(Arc::new(WasmcloudPostgres::new(&bouncer_url)?))?
+        .with_plugin(Arc::new(WasmcloudPostgres::new(bouncer_url)?))?
         .build()?;
     let host = host.start().await.context("failed to start host")?;
 
@@ -116,6 +129,7 @@ pub async fn start_postgres_workload(
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: vec![
                 http_incoming_handler_interface(host_header, None),
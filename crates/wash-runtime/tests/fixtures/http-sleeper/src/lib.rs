@@ -21,7 +21,11 @@
 //! on purpose and check what that costs: the calls sharing that instance, and
 //! nothing else. `/wedge` parks for an hour before producing the response
 //! head — a guest wedged awaiting I/O that will never arrive — so a test can
-//! check what the host's per-call timeout does about it.
+//! check what the host's per-call timeout does about it. `/spin` never
+//! yields at all: it loops forever without returning to the host, so only the
+//! component's `max_execution_time` can end it. `/drain` reads the whole
+//! request body and replies with its length, or `400` if reading it failed,
+//! so a test can check how the host bounds a body on its way in.
 //!
 //! Each reply also carries `served`, this instance's own request count. That
 //! is how a test tells a *retired* instance from a merely recovered slot: a
@@ -55,6 +59,25 @@ impl HttpGuest for Component {
 
         let served = SERVED.fetch_add(1, Ordering::SeqCst) + 1;
 
+        if path.starts_with("/drain") {
+            let (_res_tx, res_rx) = bindings::wit_future::new(|| Ok(()));
+            let (body, trailers) = Request::consume_body(request, res_rx);
+            let read = body.collect().await.len();
+            return Ok(match trailers.await {
+                Ok(_) => make_response(200, format!("{{\"read\":{read}}}").into_bytes()),
+                Err(e) => make_response(400, format!("{{\"error\":\"{e:?}\"}}").into_bytes()),
+            });
+        }
+
+        // Spinning: busy, never awaiting, so no host-side timeout ever gets
+        // the chance to fire. Only an epoch deadline interrupts this.
+        if path.starts_with("/spin") {
+            let mut spins = 0u64;
+            loop {
+                spins = std::hint::black_box(spins.wrapping_add(1));
+            }
+        }
+
         // Wedged: the response head never comes. What bounds the caller's wait
         // (and this instance's fate) is the host's per-call timeout alone.
         if path.starts_with("/wedge") {
@@ -0,0 +1,115 @@
+//! Key expiry fixture.
+//!
+//! Exports an HTTP handler that drives one `wasi:keyvalue` bucket, `cache`, so
+//! the test can observe when keys expire:
+//!
+//! - `PUT /<key>?value=<v>[&ttl_ms=<n>]`: `set-with-ttl` when `ttl_ms` is given,
+//!   otherwise a plain `store.set`
+//! - `GET /<key>`: the value, or 404 once the key is gone
+//! - `POST /<key>`: `atomics.increment` by one, returning the new count
+//! - `GET /`: the bucket's keys, sorted, one per line
+
+use anyhow::{Context, Result, bail};
+
+mod bindings {
+    wit_bindgen::generate!({
+        generate_all,
+    });
+}
+
+use bindings::{
+    exports::wasi::http::incoming_handler::Guest,
+    wasi::{
+        http::types::{
+            Fields, IncomingRequest, Method, OutgoingBody, OutgoingResponse, ResponseOutparam,
+        },
+        keyvalue::{
+            atomics::increment,
+            store::{Bucket, open},
+        },
+    },
+    wasmcloud::keyvalue::ttl::set_with_ttl,
+};
+
+struct Component;
+
+const BUCKET: &str = "cache";
+
+impl Guest for Component {
+    fn handle(request: IncomingRequest, response_out: ResponseOutparam) {
+        let (status, body_text) = match handle_request(&request) {
+            Ok(Some(body)) => (200, body),
+            Ok(None) => (404, String::new()),
+            Err(e) => (500, format!("error: {e:#}")),
+        };
+
+        let response = OutgoingResponse::new(Fields::new());
+        response.set_status_code(status).unwrap();
+        let body = response.body().unwrap();
+        ResponseOutparam::set(response_out, Ok(response));
+
+        let stream = body.write().unwrap();
+        stream.blocking_write_and_flush(body_text.as_bytes()).unwrap();
+        drop(stream);
+        OutgoingBody::finish(body, None).unwrap();
+    }
+}
+
+fn handle_request(request: &IncomingRequest) -> Result<Option<String>> {
+    let path_with_query = request.path_with_query().unwrap_or_default();
+    let (path, query) = path_with_query
+        .split_once('?')
+        .unwrap_or((path_with_query.as_str(), ""));
+    let key = path.trim_start_matches('/');
+    let bucket = open(BUCKET).context("open bucket")?;
+
+    match (request.method(), key) {
+        (Method::Get, "") => list_keys(&bucket).map(Some),
+        (Method::Get, key) => {
+            let value = bucket.get(key).context("get")?;
+            Ok(value.map(|v| String::from_utf8_lossy(&v).into_owned()))
+        }
+        (Method::Put, key) => {
+            let value = query_param(query, "value").unwrap_or_default();
+            match query_param(query, "ttl_ms") {
+                Some(ttl_ms) => {
+                    let ttl_ms = ttl_ms.parse().context("parse ttl_ms")?;
+                    set_with_ttl(&bucket, key, value.as_bytes(), ttl_ms)
+                        .context("set-with-ttl")?;
+                }
+                None => bucket.set(key, value.as_bytes()).context("set")?,
+            }
+            Ok(Some(String::new()))
+        }
+        (Method::Post, key) => {
+            let count = increment(&bucket, key, 1).context("increment")?;
+            Ok(Some(count.to_string()))
+        }
+        (method, _) => bail!("unsupported method {method:?}"),
+    }
+}
+
+fn list_keys(bucket: &Bucket) -> Result<String> {
+    let mut keys = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = bucket.list_keys(cursor).context("list keys")?;
+        keys.extend(page.keys);
+        cursor = page.cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    keys.sort();
+    Ok(keys.join("\n"))
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == name)
+        .map(|(_, v)| v)
+}
+
+bindings::export!(Component with_types_in bindings);
@@ -0,0 +1,123 @@
+//! Real-guest fixture for the `wasmcloud:keyvalue/watcher` export.
+//!
+//! HTTP requests drive the `watched` bucket through the plain `store` import:
+//!
+//! - `PUT /<key>` with a body: set `key` to the body
+//! - `DELETE /<key>`: delete `key`
+//! - `GET /<key>`: answer with `key`'s value, or 404
+//!
+//! The watcher records every change it is told about back into the bucket it
+//! is handed: a set of `key` to `value` appends `value` to `log:set:<key>`,
+//! and a delete of `key` appends `.` to `log:delete:<key>`. The `log:` keys are
+//! themselves watched changes, so the watcher ignores them. Appending rather
+//! than overwriting lets the host's test see a change delivered twice.
+
+mod bindings {
+    wit_bindgen::generate!({
+        generate_all,
+    });
+}
+
+use bindings::exports::wasi::http::handler::Guest as Handler;
+use bindings::exports::wasmcloud::keyvalue::watcher::Guest as Watcher;
+use bindings::wasi::http::types::{ErrorCode, Fields, Method, Request, Response};
+use bindings::wasmcloud::keyvalue::store;
+use bindings::wasmcloud::keyvalue::types::Bucket;
+
+struct Component;
+
+const BUCKET: &str = "watched";
+const LOG_PREFIX: &str = "log:";
+
+fn internal(msg: String) -> ErrorCode {
+    ErrorCode::InternalError(Some(msg))
+}
+
+fn respond(status: u16, body_bytes: Vec<u8>) -> Result<Response, ErrorCode> {
+    let headers = Fields::new();
+    let (mut tx, rx) = bindings::wit_stream::new();
+    let (trailers_tx, trailers_rx) = bindings::wit_future::new(|| todo!());
+
+    wit_bindgen::spawn_local(async move {
+        tx.write_all(body_bytes).await;
+        drop(tx);
+        let _ = trailers_tx.write(Ok(None)).await;
+    });
+
+    let (response, _result) = Response::new(headers, Some(rx), trailers_rx);
+    response
+        .set_status_code(status)
+        .map_err(|()| internal("failed to set status".into()))?;
+    Ok(response)
+}
+
+async fn read_body(request: Request) -> Vec<u8> {
+    let (_, result_tx) = bindings::wit_future::new(|| Ok(()));
+    let (body, _trailers) = Request::consume_body(request, result_tx);
+    body.collect().await
+}
+
+async fn run(request: Request) -> Result<(u16, Vec<u8>), String> {
+    let key = request
+        .get_path_with_query()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+    let method = request.get_method();
+    let bucket = store::open(BUCKET.to_string())
+        .await
+        .map_err(|e| format!("open: {e:?}"))?;
+    match method {
+        Method::Put => {
+            let value = read_body(request).await;
+            bucket
+                .set(key, value, None)
+                .await
+                .map_err(|e| format!("set: {e:?}"))?;
+            Ok((200, Vec::new()))
+        }
+        Method::Delete => {
+            bucket
+                .delete(key)
+                .await
+                .map_err(|e| format!("delete: {e:?}"))?;
+            Ok((200, Vec::new()))
+        }
+        _ => match bucket.get(key).await.map_err(|e| format!("get: {e:?}"))? {
+            Some(value) => Ok((200, value)),
+            None => Ok((404, Vec::new())),
+        },
+    }
+}
+
+/// Append `value` to the log entry `log_key` in `bucket`.
+async fn append(bucket: &Bucket, log_key: String, value: &[u8]) {
+    let mut logged = bucket.get(log_key.clone()).await.ok().flatten().unwrap_or_default();
+    logged.extend_from_slice(value);
+    let _ = bucket.set(log_key, logged, None).await;
+}
+
+impl Handler for Component {
+    async fn handle(request: Request) -> Result<Response, ErrorCode> {
+        match run(request).await {
+            Ok((status, body)) => respond(status, body),
+            Err(e) => respond(500, format!("error: {e}").into_bytes()),
+        }
+    }
+}
+
+impl Watcher for Component {
+    async fn on_set(bucket: Bucket, key: String, value: Vec<u8>) {
+        if !key.starts_with(LOG_PREFIX) {
+            append(&bucket, format!("{LOG_PREFIX}set:{key}"), &value).await;
+        }
+    }
+
+    async fn on_delete(bucket: Bucket, key: String) {
+        if !key.starts_with(LOG_PREFIX) {
+            append(&bucket, format!("{LOG_PREFIX}delete:{key}"), b".").await;
+        }
+    }
+}
+
+bindings::export!(Component with_types_in bindings);
@@ -0,0 +1,92 @@
+//! Request-reply fixture for `wasmcloud:messaging@0.3.0`.
+//!
+//! The handler answers by returning a body rather than publishing one:
+//! - `reply.echo` answers `"{body}|{x-request-id}"`
+//! - `reply.fail` fails, so the host answers with an error header
+//! - `reply.none` answers nothing
+//!
+//! `GET /request?subject=..&body=..&id=..` sends a request with an
+//! `x-request-id: {id}` header and responds with the reply body, or with
+//! `502` and the `wasmcloud-error` header's value, or with `504` when the
+//! request itself fails.
+
+mod bindings {
+    wit_bindgen::generate!({
+        generate_all,
+    });
+}
+
+use bindings::exports::wasi::http::incoming_handler::Guest as HttpGuest;
+use bindings::exports::wasmcloud::messaging::handler::Guest as HandlerGuest;
+use bindings::wasi::http::types::{
+    Fields, IncomingRequest, OutgoingBody, OutgoingResponse, ResponseOutparam,
+};
+use bindings::wasmcloud::messaging::consumer;
+use bindings::wasmcloud::messaging::types::BrokerMessage;
+
+const ERROR_HEADER: &str = "wasmcloud-error";
+
+struct Component;
+
+impl HandlerGuest for Component {
+    fn handle_message(msg: BrokerMessage) -> Result<Option<Vec<u8>>, String> {
+        let body = String::from_utf8_lossy(&msg.body);
+        match msg.subject.as_str() {
+            "reply.echo" => {
+                let id = header(&msg, "x-request-id").unwrap_or("<none>");
+                Ok(Some(format!("{body}|{id}").into_bytes()))
+            }
+            "reply.fail" => Err(format!("refused {body}")),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl HttpGuest for Component {
+    fn handle(request: IncomingRequest, response_out: ResponseOutparam) {
+        let path_with_query = request.path_with_query().unwrap_or_default();
+        let query = path_with_query
+            .split_once('?')
+            .map(|(_, q)| q)
+            .unwrap_or_default();
+        let param = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+                .unwrap_or_default()
+        };
+
+        let headers = vec![("x-request-id".to_string(), param("id"))];
+        let (status, body) =
+            match consumer::request(&param("subject"), param("body").as_bytes(), &headers, 2_000) {
+                Ok(reply) => match header(&reply, ERROR_HEADER) {
+                    Some(error) => (502, error.to_string()),
+                    None => (200, String::from_utf8_lossy(&reply.body).into_owned()),
+                },
+                Err(e) => (504, e),
+            };
+        respond(response_out, status, body.as_bytes());
+    }
+}
+
+fn header<'a>(msg: &'a BrokerMessage, name: &str) -> Option<&'a str> {
+    msg.headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+fn respond(response_out: ResponseOutparam, status: u16, body: &[u8]) {
+    let response = OutgoingResponse::new(Fields::new());
+    response.set_status_code(status).unwrap();
+    let outgoing = response.body().unwrap();
+    ResponseOutparam::set(response_out, Ok(response));
+    let stream = outgoing.write().unwrap();
+    stream.blocking_write_and_flush(body).unwrap();
+    drop(stream);
+    OutgoingBody::finish(outgoing, None).unwrap();
+}
+
+bindings::export!(Component with_types_in bindings);
@@ -0,0 +1,29 @@
+//! Notifications fixture for the sync `wasmcloud:postgres@0.1.1-draft`.
+//!
+//! Every notification is recorded as a `(channel, payload)` row of the
+//! `received` table.
+
+mod bindings {
+    wit_bindgen::generate!({
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcloud::postgres::notifications::Guest;
+use bindings::wasmcloud::postgres::query::query;
+use bindings::wasmcloud::postgres::types::PgValue;
+
+struct Component;
+
+impl Guest for Component {
+    fn on_notify(channel: String, payload: String) -> Result<(), String> {
+        query(
+            "INSERT INTO received (channel, payload) VALUES ($1, $2)",
+            &[PgValue::Text(channel), PgValue::Text(payload)],
+        )
+        .map(|_| ())
+        .map_err(|e| format!("{e:?}"))
+    }
+}
+
+bindings::export!(Component with_types_in bindings);
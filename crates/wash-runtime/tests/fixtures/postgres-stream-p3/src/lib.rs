@@ -9,7 +9,14 @@
 //!   - `/paced` — eight rows emitted ~120ms apart (a per-row `pg_sleep`),
 //!     forwarded to the response body as each arrives. A test times the chunks
 //!     to prove rows stream through incrementally rather than being buffered.
+//!   - `/txn/<action>/<id>` — inserts `id` into `ledger` inside a
+//!     `transaction`, then ends it per `action` (`commit`, `rollback`, `drop`,
+//!     or `aborted`: a duplicate insert followed by a commit attempt). A commit
+//!     answers with the isolation level and the row count seen inside it.
 //!   - anything else — reads `items` and forwards it (a small sanity default).
+//!
+//! It also exports `notifications`, recording every notification it receives
+//! as a `(channel, payload)` row of the `received` table.
 
 mod bindings {
     wit_bindgen::generate!({
@@ -17,14 +24,22 @@ mod bindings {
         async: [
             "import:wasmcloud:postgres/query@0.2.0#query",
             "import:wasmcloud:postgres/query@0.2.0#query-batch",
+            "import:wasmcloud:postgres/transaction@0.2.0#[static]transaction.begin",
+            "import:wasmcloud:postgres/transaction@0.2.0#[method]transaction.query",
+            "import:wasmcloud:postgres/transaction@0.2.0#[method]transaction.exec",
+            "import:wasmcloud:postgres/transaction@0.2.0#[static]transaction.commit",
+            "import:wasmcloud:postgres/transaction@0.2.0#[static]transaction.rollback",
             "export:wasi:http/handler@0.3.0#handle",
+            "export:wasmcloud:postgres/notifications@0.2.0#on-notify",
         ],
     });
 }
 
 use bindings::exports::wasi::http::handler::Guest as Handler;
+use bindings::exports::wasmcloud::postgres::notifications::Guest as Notifications;
 use bindings::wasi::http::types::{ErrorCode, Fields, Request, Response};
 use bindings::wasmcloud::postgres::query;
+use bindings::wasmcloud::postgres::transaction::{IsolationLevel, Transaction};
 use bindings::wasmcloud::postgres::types::PgValue;
 
 struct Component;
@@ -85,9 +100,90 @@ fn respond(body: Vec<u8>) -> Response {
     response
 }
 
+/// Run `/txn/<action>/<id>` (see the module docs), returning the response body.
+async fn transaction(path: &str) -> Result<String, String> {
+    let (action, id) = path
+        .split_once('/')
+        .ok_or_else(|| format!("bad path {path}"))?;
+    let id: i32 = id.parse().map_err(|e| format!("bad id: {e}"))?;
+
+    let tx = Transaction::begin(IsolationLevel::Serializable)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+    insert(&tx, id).await?;
+
+    match action {
+        "commit" => {
+            let isolation = first_text(first_row(&tx, "SHOW transaction_isolation").await?)
+                .ok_or("isolation level is not text")?;
+            let visible = first_text(first_row(&tx, "SELECT count(*)::text FROM ledger").await?)
+                .ok_or("row count is not text")?;
+            Transaction::commit(tx)
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+            Ok(format!("committed isolation={isolation} visible={visible}"))
+        }
+        "rollback" => {
+            Transaction::rollback(tx)
+                .await
+                .map_err(|e| format!("{e:?}"))?;
+            Ok("rolled-back".to_string())
+        }
+        "drop" => {
+            drop(tx);
+            Ok("dropped".to_string())
+        }
+        "aborted" => {
+            if insert(&tx, id).await.is_ok() {
+                return Err("duplicate insert succeeded".to_string());
+            }
+            match Transaction::commit(tx).await {
+                Ok(()) => Ok("committed".to_string()),
+                Err(_) => Ok("commit-refused".to_string()),
+            }
+        }
+        other => Err(format!("unknown action {other}")),
+    }
+}
+
+async fn insert(tx: &Transaction, id: i32) -> Result<(), String> {
+    let inserted = tx
+        .exec(
+            "INSERT INTO ledger (id) VALUES ($1)".to_string(),
+            vec![PgValue::Int4(id)],
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+    if inserted == 1 {
+        Ok(())
+    } else {
+        Err(format!("inserted {inserted} rows"))
+    }
+}
+
+/// The first row `sql` returns inside `tx`, draining the rest of the stream.
+async fn first_row(tx: &Transaction, sql: &str) -> Result<Vec<PgValue>, String> {
+    let (_columns, mut rows, completion) = tx
+        .query(sql.to_string(), Vec::new())
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+    let mut first = None;
+    while let Some(row) = rows.next().await {
+        first.get_or_insert(row);
+    }
+    completion.await.map_err(|e| format!("{e:?}"))?;
+    first.ok_or_else(|| format!("{sql} returned no rows"))
+}
+
 impl Handler for Component {
     async fn handle(request: Request) -> Result<Response, ErrorCode> {
         let path = request.get_path_with_query().unwrap_or_default();
+        if let Some(txn_path) = path.strip_prefix("/txn/") {
+            let body = transaction(txn_path)
+                .await
+                .unwrap_or_else(|e| format!("error: {e}"));
+            return Ok(respond(body.into_bytes()));
+        }
         let (sql, forward) = route(&path);
 
         let (columns, mut rows, completion) =
@@ -136,4 +232,17 @@ impl Handler for Component {
     }
 }
 
+impl Notifications for Component {
+    async fn on_notify(channel: String, payload: String) -> Result<(), String> {
+        let (_columns, mut rows, completion) = query::query(
+            "INSERT INTO received (channel, payload) VALUES ($1, $2)".to_string(),
+            vec![PgValue::Text(channel), PgValue::Text(payload)],
+        )
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+        while rows.next().await.is_some() {}
+        completion.await.map_err(|e| format!("{e:?}"))
+    }
+}
+
 bindings::export!(Component with_types_in bindings);
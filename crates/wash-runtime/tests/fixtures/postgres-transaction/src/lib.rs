@@ -0,0 +1,120 @@
+//! Transaction fixture for the sync `wasmcloud:postgres@0.1.1-draft`.
+//!
+//! Each request inserts `id` into the `ledger` table inside a transaction and
+//! then ends it the way the path asks:
+//!
+//! - `/commit/<id>`: commit, answering with the isolation level the
+//!   transaction saw and how many `ledger` rows were visible inside it
+//! - `/rollback/<id>`: roll back explicitly
+//! - `/drop/<id>`: drop the transaction without finishing it
+//! - `/aborted/<id>`: insert `id` a second time (a unique violation), then try
+//!   to commit
+//! - `/nested/<id>`: try to begin a second transaction while the first is
+//!   open, then drop both
+
+mod bindings {
+    wit_bindgen::generate!({
+        generate_all,
+    });
+}
+
+use bindings::exports::wasi::http::incoming_handler::Guest;
+use bindings::wasi::http::types::{
+    Fields, IncomingRequest, OutgoingBody, OutgoingResponse, ResponseOutparam,
+};
+use bindings::wasmcloud::postgres::transaction::{IsolationLevel, Transaction};
+use bindings::wasmcloud::postgres::types::PgValue;
+
+struct Component;
+
+impl Guest for Component {
+    fn handle(request: IncomingRequest, response_out: ResponseOutparam) {
+        let path = request.path_with_query().unwrap_or_default();
+        let body = match run(&path) {
+            Ok(body) => body,
+            Err(e) => format!("error: {e}"),
+        };
+
+        let response = OutgoingResponse::new(Fields::new());
+        response.set_status_code(200).unwrap();
+        let out_body = response.body().unwrap();
+        ResponseOutparam::set(response_out, Ok(response));
+
+        let stream = out_body.write().unwrap();
+        stream.blocking_write_and_flush(body.as_bytes()).unwrap();
+        drop(stream);
+        OutgoingBody::finish(out_body, None).unwrap();
+    }
+}
+
+fn run(path: &str) -> Result<String, String> {
+    let (action, id) = path
+        .trim_start_matches('/')
+        .split_once('/')
+        .ok_or_else(|| format!("bad path {path}"))?;
+    let id: i32 = id.parse().map_err(|e| format!("bad id: {e}"))?;
+
+    let tx = Transaction::begin(IsolationLevel::Serializable).map_err(|e| format!("{e:?}"))?;
+    insert(&tx, id)?;
+
+    match action {
+        "commit" => {
+            let isolation = match first_value(&tx, "SHOW transaction_isolation")? {
+                PgValue::Text(level) => level,
+                other => return Err(format!("unexpected isolation value {other:?}")),
+            };
+            let visible = match first_value(&tx, "SELECT count(*) FROM ledger")? {
+                PgValue::BigInt(n) | PgValue::Int8(n) => n,
+                other => return Err(format!("unexpected count value {other:?}")),
+            };
+            Transaction::commit(tx).map_err(|e| format!("{e:?}"))?;
+            Ok(format!("committed isolation={isolation} visible={visible}"))
+        }
+        "rollback" => {
+            Transaction::rollback(tx).map_err(|e| format!("{e:?}"))?;
+            Ok("rolled-back".to_string())
+        }
+        "drop" => {
+            drop(tx);
+            Ok("dropped".to_string())
+        }
+        "aborted" => {
+            if insert(&tx, id).is_ok() {
+                return Err("duplicate insert succeeded".to_string());
+            }
+            match Transaction::commit(tx) {
+                Ok(()) => Ok("committed".to_string()),
+                Err(_) => Ok("commit-refused".to_string()),
+            }
+        }
+        "nested" => match Transaction::begin(IsolationLevel::ReadCommitted) {
+            Ok(_) => Ok("nested-opened".to_string()),
+            Err(e) => Ok(format!("nested-refused: {e:?}")),
+        },
+        other => Err(format!("unknown action {other}")),
+    }
+}
+
+fn insert(tx: &Transaction, id: i32) -> Result<(), String> {
+    let inserted = tx
+        .exec("INSERT INTO ledger (id) VALUES ($1)", &[PgValue::Int4(id)])
+        .map_err(|e| format!("{e:?}"))?;
+    if inserted == 1 {
+        Ok(())
+    } else {
+        Err(format!("inserted {inserted} rows"))
+    }
+}
+
+/// The first column of the first row `sql` returns inside `tx`.
+fn first_value(tx: &Transaction, sql: &str) -> Result<PgValue, String> {
+    tx.query(sql, &[])
+        .map_err(|e| format!("{e:?}"))?
+        .into_iter()
+        .next()
+        .and_then(|row| row.into_iter().next())
+        .map(|entry| entry.value)
+        .ok_or_else(|| format!("{sql} returned no rows"))
+}
+
+bindings::export!(Component with_types_in bindings);
@@ -9,6 +9,9 @@
 //! A request for `/boom` traps instead of responding, so a test can fault the
 //! shared instance and watch the supervisor restart it. Both counters live in
 //! instance memory, so a fresh incarnation starts them over at zero.
+//!
+//! A request for `/spin` loops forever without yielding, so only the service's
+//! `max_execution_time` can end it.
 
 mod bindings;
 
@@ -42,6 +45,11 @@ impl HttpGuest for Component {
         if path.starts_with("/boom") {
             panic!("svc-counter boom: deliberate handler trap for the restart test");
         }
+        if path.starts_with("/spin") {
+            loop {
+                std::hint::spin_loop();
+            }
+        }
         let http_calls = HTTP_CALLS.fetch_add(1, Ordering::SeqCst) + 1;
         let cli_ticks = CLI_TICKS.load(Ordering::SeqCst);
         let body = format!("{{\"cli_ticks\":{cli_ticks},\"http_calls\":{http_calls}}}");
@@ -0,0 +1,118 @@
+//! Request-reply fixture for `wasi:messaging@0.2.0-draft`.
+//!
+//! The handler answers with `request-reply.reply`:
+//! - `echo.twice` answers twice, every other `echo.*` topic once, each time
+//!   with `"{data}|{x-request-id}"` and the request's content type
+//! - `fail` fails without answering
+//!
+//! `GET /request?topic=..&body=..&id=..&replies=..` sends a `text/plain`
+//! request with an `x-request-id: {id}` metadata entry, waiting up to two
+//! seconds for `replies` (default 1) answers. It responds with the answers as
+//! `"{data} ({content-type})"`, comma-separated, or with `504` and the error.
+
+mod bindings {
+    wit_bindgen::generate!({
+        generate_all,
+    });
+}
+
+use bindings::exports::wasi::http::incoming_handler::Guest as HttpGuest;
+use bindings::exports::wasi::messaging::incoming_handler::Guest as MessagingGuest;
+use bindings::wasi::http::types::{
+    Fields, IncomingRequest, OutgoingBody, OutgoingResponse, ResponseOutparam,
+};
+use bindings::wasi::messaging::request_reply::{self, RequestOptions};
+use bindings::wasi::messaging::types::{Client, Error, Message};
+
+struct Component;
+
+impl MessagingGuest for Component {
+    fn handle(message: Message) -> Result<(), Error> {
+        let topic = message.topic().unwrap_or_default();
+        if topic == "fail" {
+            return Err(Error::Other("refused".to_string()));
+        }
+        if !topic.starts_with("echo.") {
+            return Ok(());
+        }
+        let id = metadata(&message, "x-request-id").unwrap_or_else(|| "<none>".to_string());
+        let data = format!("{}|{id}", String::from_utf8_lossy(&message.data()));
+        let replies = if topic == "echo.twice" { 2 } else { 1 };
+        for _ in 0..replies {
+            let reply = Message::new(data.as_bytes());
+            if let Some(content_type) = message.content_type() {
+                reply.set_content_type(&content_type);
+            }
+            reply.add_metadata("x-request-id", &id);
+            request_reply::reply(&message, reply)?;
+        }
+        Ok(())
+    }
+}
+
+impl HttpGuest for Component {
+    fn handle(request: IncomingRequest, response_out: ResponseOutparam) {
+        let path_with_query = request.path_with_query().unwrap_or_default();
+        let query = path_with_query
+            .split_once('?')
+            .map(|(_, q)| q)
+            .unwrap_or_default();
+        let param = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+                .unwrap_or_default()
+        };
+
+        let message = Message::new(param("body").as_bytes());
+        message.set_content_type("text/plain");
+        message.add_metadata("x-request-id", &param("id"));
+        let options = RequestOptions::new();
+        options.set_timeout_ms(2_000);
+        options.set_expected_replies(param("replies").parse().unwrap_or(1));
+
+        let result = Client::connect("default").and_then(|client| {
+            request_reply::request(&client, &param("topic"), &message, Some(options))
+        });
+        let (status, body) = match result {
+            Ok(replies) => {
+                let replies: Vec<_> = replies
+                    .iter()
+                    .map(|reply| {
+                        format!(
+                            "{} ({})",
+                            String::from_utf8_lossy(&reply.data()),
+                            reply.content_type().unwrap_or_default()
+                        )
+                    })
+                    .collect();
+                (200, replies.join(", "))
+            }
+            Err(e) => (504, format!("{e:?}")),
+        };
+        respond(response_out, status, body.as_bytes());
+    }
+}
+
+fn metadata(message: &Message, key: &str) -> Option<String> {
+    message
+        .metadata()?
+        .into_iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .map(|(_, v)| v)
+}
+
+fn respond(response_out: ResponseOutparam, status: u16, body: &[u8]) {
+    let response = OutgoingResponse::new(Fields::new());
+    response.set_status_code(status).unwrap();
+    let outgoing = response.body().unwrap();
+    ResponseOutparam::set(response_out, Ok(response));
+    let stream = outgoing.write().unwrap();
+    stream.blocking_write_and_flush(body).unwrap();
+    drop(stream);
+    OutgoingBody::finish(outgoing, None).unwrap();
+}
+
+bindings::export!(Component with_types_in bindings);
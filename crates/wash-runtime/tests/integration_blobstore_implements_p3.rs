@@ -113,6 +113,7 @@ async fn p3_guest_streams_blobstore_through_nats() -> Result<()> {
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces,
             volumes: vec![],
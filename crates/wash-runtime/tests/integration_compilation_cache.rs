@@ -0,0 +1,102 @@
+//! A host configured with a compilation cache directory keeps the components
+//! it compiles there, and a host started later over the same directory loads
+//! them instead of compiling again.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use wash_runtime::engine::Engine;
+use wash_runtime::host::http::{DynamicRouter, Ingress};
+use wash_runtime::host::{HostApi, HostBuilder, HostConfig};
+use wash_runtime::types::{LocalResources, WorkloadStartRequest};
+
+mod common;
+use common::{component_workload_request, http_only_host_interfaces, req};
+
+const HTTP_SLEEPER_WASM: &[u8] = include_bytes!("wasm/http_sleeper.wasm");
+
+async fn start_host(cache_dir: &Path) -> Result<(std::net::SocketAddr, impl HostApi)> {
+    let ingress = Ingress::new(DynamicRouter::default(), "127.0.0.1:0".parse()?).await?;
+    let addr = ingress.addr();
+    let host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress))
+        .with_config(HostConfig {
+            compilation_cache_dir: Some(cache_dir.to_path_buf()),
+            ..Default::default()
+        })
+        .build()?
+        .start()
+        .await?;
+    Ok((addr, host))
+}
+
+fn sleeper_workload() -> WorkloadStartRequest {
+    let mut request = component_workload_request(
+        "sleeper",
+        "cached",
+        HTTP_SLEEPER_WASM,
+        LocalResources::default(),
+        http_only_host_interfaces("cached"),
+    );
+    for component in &mut request.workload.components {
+        component.digest = Some("sha256:compilation-cache-test".to_string());
+    }
+    request
+}
+
+/// Every compiled component stored under `cache_dir`.
+fn entries(cache_dir: &Path) -> Vec<PathBuf> {
+    walk(cache_dir)
+        .into_iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "cwasm"))
+        .collect()
+}
+
+fn walk(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .flat_map(|entry| {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                walk(&path)
+            } else {
+                vec![path]
+            }
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn a_restarted_host_loads_what_the_last_one_compiled() -> Result<()> {
+    let cache_dir = tempfile::tempdir()?;
+    let client = reqwest::Client::new();
+
+    let (addr, host) = start_host(cache_dir.path()).await?;
+    host.workload_start(sleeper_workload()).await?;
+    let (status, body) = req(&client, &addr, "cached", "/").await?;
+    assert!(status.is_success(), "{status} {body}");
+
+    let stored = entries(cache_dir.path());
+    assert_eq!(stored.len(), 1, "one compiled component stored: {stored:?}");
+    let written = std::fs::metadata(stored.first().unwrap())?.modified()?;
+
+    // A fresh engine has nothing in memory, so this start either loads the
+    // stored entry or compiles and rewrites it.
+    let (addr, host) = start_host(cache_dir.path()).await?;
+    host.workload_start(sleeper_workload()).await?;
+    let (status, body) = req(&client, &addr, "cached", "/").await?;
+    assert!(status.is_success(), "{status} {body}");
+
+    assert_eq!(entries(cache_dir.path()), stored);
+    assert_eq!(
+        std::fs::metadata(stored.first().unwrap())?.modified()?,
+        written,
+        "the entry was loaded, not rewritten"
+    );
+    Ok(())
+}
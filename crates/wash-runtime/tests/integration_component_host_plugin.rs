@@ -152,6 +152,7 @@ async fn test_component_plugin_state_survives_workload_restart() -> Result<()> {
 
     h.workload_stop(WorkloadStopRequest {
         workload_id: first_id,
+        ..Default::default()
     })
     .await?;
 
@@ -105,6 +105,7 @@ fn service_workload(name: &str) -> wash_runtime::types::WorkloadStartRequest {
                 bytes: bytes::Bytes::from_static(KV_PLUGIN_SERVICE_WASM),
                 local_resources: LocalResources::default(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![],
             host_interfaces: vec![acme_kv_interface()],
@@ -305,6 +306,7 @@ async fn test_lifecycle_unbind_on_workload_stop() -> Result<()> {
 
     h.workload_stop(WorkloadStopRequest {
         workload_id: first_id.clone(),
+        ..Default::default()
     })
     .await?;
 
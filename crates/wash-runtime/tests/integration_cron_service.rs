@@ -51,6 +51,7 @@ async fn test_cron_service_integration() -> Result<()> {
                 bytes: bytes::Bytes::from_static(CRON_SERVICE_WASM),
                 local_resources: Default::default(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![Component {
                 name: "cron-component".to_string(),
@@ -60,6 +61,7 @@ async fn test_cron_service_integration() -> Result<()> {
                 max_invocations: 1,
                 max_concurrency: 1,
                 pool_size: 0,
+                ..Default::default()
             }],
             host_interfaces: vec![],
             volumes: vec![],
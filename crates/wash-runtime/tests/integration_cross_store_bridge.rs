@@ -39,6 +39,7 @@ fn bridge_workload(host: &str) -> WorkloadStartRequest {
                 bytes: bytes::Bytes::from_static(BRIDGE_SERVICE_WASM),
                 local_resources: LocalResources::default(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             // The backend is a stateless component linked to the service by its
             // `wasmcloud:bridge/ops` export; the host instantiates it fresh per
@@ -53,6 +54,7 @@ fn bridge_workload(host: &str) -> WorkloadStartRequest {
                 pool_size: 0,
                 max_invocations: 0,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: http_only_host_interfaces(host),
             volumes: vec![],
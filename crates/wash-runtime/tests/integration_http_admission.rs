@@ -0,0 +1,188 @@
+//! Integration tests for HTTP ingress admission control: per-workload rate
+//! limits and in-flight caps configured on `wasi:http/incoming-handler`.
+//!
+//! Each workload is an `http-sleeper`; its `/wedge` path parks the request
+//! for an hour, which holds an in-flight slot for as long as a test needs.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use wash_runtime::host::HostApi;
+use wash_runtime::types::{
+    Component, LocalResources, Workload, WorkloadStartRequest, WorkloadState,
+};
+
+mod common;
+use common::{http_incoming_handler_interface, start_host_with_dynamic_router};
+
+const HTTP_SLEEPER_WASM: &[u8] = include_bytes!("wasm/http_sleeper.wasm");
+
+/// A sleeper workload on `host_header` with extra incoming-handler config.
+fn limited_workload(host_header: &str, limits: &[(&str, &str)]) -> WorkloadStartRequest {
+    let mut http = http_incoming_handler_interface(host_header, None);
+    http.config.extend(
+        limits
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+    );
+    WorkloadStartRequest {
+        workload_id: uuid::Uuid::new_v4().to_string(),
+        workload: Workload {
+            namespace: "test".to_string(),
+            name: host_header.to_string(),
+            annotations: HashMap::new(),
+            service: None,
+            components: vec![Component {
+                name: "sleeper".to_string(),
+                digest: None,
+                bytes: bytes::Bytes::from_static(HTTP_SLEEPER_WASM),
+                local_resources: LocalResources::default(),
+                pool_size: 1,
+                max_invocations: 0,
+                max_concurrency: 0,
+                ..Default::default()
+            }],
+            host_interfaces: vec![http],
+            volumes: vec![],
+        },
+    }
+}
+
+async fn start(host: &impl HostApi, request: WorkloadStartRequest) -> Result<()> {
+    let response = host.workload_start(request).await?;
+    anyhow::ensure!(
+        response.workload_status.workload_state == WorkloadState::Running,
+        "{}",
+        response.workload_status.message
+    );
+    Ok(())
+}
+
+/// GET `path` on `host`, returning the status and any `Retry-After` value.
+async fn get(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+    host: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+) -> Result<(u16, Option<String>)> {
+    let mut request = client
+        .get(format!("http://{addr}{path}"))
+        .header("HOST", host)
+        .timeout(Duration::from_secs(15));
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = request.send().await?;
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .map(|value| value.to_str().unwrap().to_string());
+    Ok((response.status().as_u16(), retry_after))
+}
+
+#[tokio::test]
+async fn rate_limited_requests_get_429_with_retry_after() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    start(
+        &host,
+        limited_workload(
+            "limited.test",
+            &[("rate-limit", "0.1"), ("rate-limit-burst", "2")],
+        ),
+    )
+    .await?;
+    start(&host, limited_workload("open.test", &[])).await?;
+    let client = reqwest::Client::new();
+
+    for _ in 0..2 {
+        assert_eq!(get(&client, addr, "limited.test", "/", &[]).await?.0, 200);
+    }
+    let (status, retry_after) = get(&client, addr, "limited.test", "/", &[]).await?;
+    assert_eq!(status, 429);
+    let retry_after: u64 = retry_after.expect("Retry-After header").parse()?;
+    assert!((1..=10).contains(&retry_after), "{retry_after}");
+
+    // Another workload on the same host is unaffected.
+    for _ in 0..3 {
+        assert_eq!(get(&client, addr, "open.test", "/", &[]).await?.0, 200);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn rate_limits_keyed_by_header_are_separate_per_value() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    start(
+        &host,
+        limited_workload(
+            "tenants.test",
+            &[
+                ("rate-limit", "0.1"),
+                ("rate-limit-burst", "1"),
+                ("rate-limit-key", "header:x-tenant"),
+            ],
+        ),
+    )
+    .await?;
+    let client = reqwest::Client::new();
+
+    let a = [("x-tenant", "a")];
+    let b = [("x-tenant", "b")];
+    assert_eq!(get(&client, addr, "tenants.test", "/", &a).await?.0, 200);
+    assert_eq!(get(&client, addr, "tenants.test", "/", &a).await?.0, 429);
+    assert_eq!(get(&client, addr, "tenants.test", "/", &b).await?.0, 200);
+    Ok(())
+}
+
+#[tokio::test]
+async fn requests_beyond_max_in_flight_get_503() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    start(
+        &host,
+        limited_workload("capped.test", &[("max-in-flight", "1")]),
+    )
+    .await?;
+    let client = reqwest::Client::new();
+
+    // Park one request in the guest; it holds the only slot.
+    let wedged = tokio::spawn({
+        let client = client.clone();
+        async move {
+            client
+                .get(format!("http://{addr}/wedge"))
+                .header("HOST", "capped.test")
+                .send()
+                .await
+        }
+    });
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    let (status, retry_after) = loop {
+        let (status, retry_after) = get(&client, addr, "capped.test", "/", &[]).await?;
+        if status != 200 || tokio::time::Instant::now() > deadline {
+            break (status, retry_after);
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    };
+    assert_eq!(status, 503);
+    assert_eq!(retry_after.as_deref(), Some("1"));
+
+    // Once the wedged request goes away its slot is free again.
+    wedged.abort();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    loop {
+        let (status, _) = get(&client, addr, "capped.test", "/", &[]).await?;
+        if status == 200 {
+            break;
+        }
+        assert!(tokio::time::Instant::now() < deadline, "slot never freed");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    Ok(())
+}
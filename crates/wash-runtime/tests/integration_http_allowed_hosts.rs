@@ -146,10 +146,12 @@ fn allowed_hosts_workload(allowed_hosts: Vec<String>) -> WorkloadStartRequest {
                     volume_mounts: vec![],
                     allowed_hosts: parsed.into(),
                     allowed_ip_name_lookups: Default::default(),
+                    allowed_socket_addrs: Default::default(),
                 },
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: vec![WitInterface {
                 namespace: "wasi".to_string(),
@@ -123,6 +123,7 @@ async fn a_wedged_pooled_instance_is_retired_and_replaced() -> Result<()> {
                 pool_size: 1,
                 max_invocations: 0,
                 max_concurrency: 4,
+                ..Default::default()
             }],
             host_interfaces: http_only_host_interfaces("wedge-pooled"),
             volumes: vec![],
@@ -166,6 +167,7 @@ async fn a_wedged_service_keeps_serving_with_a_bounded_wait() -> Result<()> {
                 bytes: bytes::Bytes::from_static(HTTP_SLEEPER_WASM),
                 local_resources: LocalResources::default(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![],
             host_interfaces: http_only_host_interfaces("wedge-svc"),
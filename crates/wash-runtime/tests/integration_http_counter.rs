@@ -51,6 +51,7 @@ async fn test_http_counter_integration() -> Result<()> {
             // http-counter calls example.com
             allowed_hosts: vec!["example.com".parse().unwrap()].into(),
             allowed_ip_name_lookups: Default::default(),
+            allowed_socket_addrs: Default::default(),
         },
     );
 
@@ -183,10 +183,12 @@ fn egress_workload_with(
                     volume_mounts: vec![],
                     allowed_hosts: vec![allowed].into(),
                     allowed_ip_name_lookups: Default::default(),
+                    allowed_socket_addrs: Default::default(),
                 },
                 pool_size,
                 max_invocations: 1000,
                 max_concurrency,
+                ..Default::default()
             }],
             host_interfaces: vec![WitInterface {
                 namespace: "wasi".to_string(),
@@ -300,6 +302,7 @@ async fn stopping_a_workload_drops_its_pooled_connections() -> Result<()> {
 
     host.workload_stop(WorkloadStopRequest {
         workload_id: first.clone(),
+        ..Default::default()
     })
     .await
     .context("failed to stop the first workload")?;
@@ -0,0 +1,242 @@
+//! Integration tests for HTTP ingress request limits: header and body sizes,
+//! the request deadline, and the connection-level header read and idle
+//! timeouts.
+//!
+//! Each workload is an `http-sleeper`. Its `/wedge` path never answers and
+//! `/drain` reads the whole request body. Requests the limits should cut off
+//! mid-way are written by hand over a raw TCP connection, so the test
+//! controls exactly what the server has and hasn't received.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use wash_runtime::engine::Engine;
+use wash_runtime::host::http::{DynamicRouter, Ingress};
+use wash_runtime::host::http_limits::IngressLimits;
+use wash_runtime::host::{HostApi, HostBuilder};
+use wash_runtime::types::{
+    Component, LocalResources, Workload, WorkloadStartRequest, WorkloadState,
+};
+
+mod common;
+use common::http_incoming_handler_interface;
+
+const HTTP_SLEEPER_WASM: &[u8] = include_bytes!("wasm/http_sleeper.wasm");
+
+async fn start_host(limits: IngressLimits) -> Result<(SocketAddr, impl HostApi)> {
+    let ingress = Ingress::builder(DynamicRouter::default(), "127.0.0.1:0".parse()?)
+        .limits(limits)
+        .build()
+        .await?;
+    let addr = ingress.addr();
+    let host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress))
+        .build()?
+        .start()
+        .await?;
+    Ok((addr, host))
+}
+
+/// Start a sleeper workload on `host_header` with extra incoming-handler
+/// config.
+async fn start_sleeper(
+    host: &impl HostApi,
+    host_header: &str,
+    limits: &[(&str, &str)],
+) -> Result<()> {
+    let mut http = http_incoming_handler_interface(host_header, None);
+    http.config.extend(
+        limits
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+    );
+    let response = host
+        .workload_start(WorkloadStartRequest {
+            workload_id: uuid::Uuid::new_v4().to_string(),
+            workload: Workload {
+                namespace: "test".to_string(),
+                name: host_header.to_string(),
+                annotations: HashMap::new(),
+                service: None,
+                components: vec![Component {
+                    name: "sleeper".to_string(),
+                    digest: None,
+                    bytes: bytes::Bytes::from_static(HTTP_SLEEPER_WASM),
+                    local_resources: LocalResources::default(),
+                    pool_size: 1,
+                    max_invocations: 0,
+                    max_concurrency: 0,
+                    ..Default::default()
+                }],
+                host_interfaces: vec![http],
+                volumes: vec![],
+            },
+        })
+        .await?;
+    anyhow::ensure!(
+        response.workload_status.workload_state == WorkloadState::Running,
+        "{}",
+        response.workload_status.message
+    );
+    Ok(())
+}
+
+/// Write `request` on a fresh connection and return the response's status,
+/// or `None` if the server closed the connection without one.
+async fn raw_status(addr: SocketAddr, request: &[u8]) -> Result<Option<u16>> {
+    let mut stream = TcpStream::connect(addr).await?;
+    stream.write_all(request).await?;
+    read_status(&mut stream).await
+}
+
+async fn read_status(stream: &mut TcpStream) -> Result<Option<u16>> {
+    let mut response = Vec::new();
+    while !response.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = tokio::time::timeout(Duration::from_secs(15), stream.read_buf(&mut response))
+            .await
+            .context("no response")??;
+        if n == 0 {
+            return Ok(None);
+        }
+    }
+    let head = String::from_utf8_lossy(&response);
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .context("no status line")?
+        .parse()?;
+    Ok(Some(status))
+}
+
+#[tokio::test]
+async fn oversized_heads_are_refused_before_the_workload() -> Result<()> {
+    let (addr, host) = start_host(IngressLimits {
+        max_header_bytes: 16 * 1024,
+        ..Default::default()
+    })
+    .await?;
+    start_sleeper(
+        &host,
+        "small.test",
+        &[("max-header-bytes", "1024"), ("max-body-bytes", "16")],
+    )
+    .await?;
+    start_sleeper(&host, "large.test", &[]).await?;
+    let client = reqwest::Client::new();
+    let get = |host: &'static str, header: String| {
+        client
+            .get(format!("http://{addr}/"))
+            .header("HOST", host)
+            .header("x-padding", header)
+            .send()
+    };
+
+    assert_eq!(get("small.test", "x".repeat(64)).await?.status(), 200);
+    assert_eq!(get("small.test", "x".repeat(2048)).await?.status(), 431);
+    assert_eq!(get("large.test", "x".repeat(2048)).await?.status(), 200);
+    assert_eq!(
+        get("large.test", "x".repeat(32 * 1024)).await?.status(),
+        431,
+        "the host's limit applies before routing"
+    );
+
+    let post = |body: &'static str| {
+        client
+            .post(format!("http://{addr}/drain"))
+            .header("HOST", "small.test")
+            .body(body)
+            .send()
+    };
+    assert_eq!(post("sixteen bytes!!!").await?.status(), 200);
+    assert_eq!(post("seventeen bytes!!").await?.status(), 413);
+    Ok(())
+}
+
+#[tokio::test]
+async fn bodies_outgrowing_the_limit_mid_stream_get_413() -> Result<()> {
+    let (addr, host) = start_host(IngressLimits::default()).await?;
+    start_sleeper(&host, "drain.test", &[("max-body-bytes", "16")]).await?;
+
+    let chunked = |chunks: &[&str]| {
+        let mut request = b"POST /drain HTTP/1.1\r\nHost: drain.test\r\nConnection: close\r\n\
+              Transfer-Encoding: chunked\r\n\r\n"
+            .to_vec();
+        for chunk in chunks {
+            request.extend(format!("{:x}\r\n{chunk}\r\n", chunk.len()).into_bytes());
+        }
+        request.extend(b"0\r\n\r\n");
+        request
+    };
+    assert_eq!(
+        raw_status(addr, &chunked(&["eight by", "tes more"])).await?,
+        Some(200)
+    );
+    assert_eq!(
+        raw_status(addr, &chunked(&["eight by", "tes more", "!"])).await?,
+        Some(413)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn requests_past_their_deadline_get_408_or_504() -> Result<()> {
+    let (addr, host) = start_host(IngressLimits::default()).await?;
+    start_sleeper(&host, "slow.test", &[("request-timeout", "500ms")]).await?;
+
+    // The guest never answers.
+    let status = raw_status(
+        addr,
+        b"GET /wedge HTTP/1.1\r\nHost: slow.test\r\nConnection: close\r\n\r\n",
+    )
+    .await?;
+    assert_eq!(status, Some(504));
+
+    // The client never finishes sending its body.
+    let status = raw_status(
+        addr,
+        b"POST /drain HTTP/1.1\r\nHost: slow.test\r\nConnection: close\r\n\
+          Content-Length: 100\r\n\r\nnot a hundred bytes",
+    )
+    .await?;
+    assert_eq!(status, Some(408));
+    Ok(())
+}
+
+#[tokio::test]
+async fn slow_and_idle_connections_are_closed() -> Result<()> {
+    let (addr, host) = start_host(IngressLimits {
+        header_read_timeout: Some(Duration::from_millis(300)),
+        idle_timeout: Some(Duration::from_millis(300)),
+        ..Default::default()
+    })
+    .await?;
+    start_sleeper(&host, "idle.test", &[]).await?;
+
+    // Headers that never finish arriving.
+    assert_eq!(
+        raw_status(addr, b"GET / HTTP/1.1\r\nHost: idle.test\r\n").await?,
+        None
+    );
+
+    // A keep-alive connection is closed once its response has been sent and
+    // nothing follows.
+    let mut stream = TcpStream::connect(addr).await?;
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: idle.test\r\n\r\n")
+        .await?;
+    assert_eq!(read_status(&mut stream).await?, Some(200));
+    let mut rest = Vec::new();
+    tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut rest))
+        .await
+        .context("idle connection was left open")??;
+    Ok(())
+}
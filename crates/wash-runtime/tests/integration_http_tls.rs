@@ -1,23 +1,34 @@
 //! Integration test for TLS-enabled HTTP server
 //!
 //! Verifies that HTTPS requests are correctly handled when the HTTP server
-//! is configured with TLS via `Ingress::new_with_tls()`.
+//! is configured with TLS via `Ingress::new_with_tls()`, and that the server
+//! picks its certificate by SNI and picks up renewed certificates.
 
 #![allow(clippy::unwrap_used, clippy::expect_used)]
 
 use anyhow::{Context, Result};
 use std::{collections::HashMap, path::Path, time::Duration};
+use tokio::io::AsyncReadExt;
 use tokio::time::timeout;
 
 use wash_runtime::{
-    host::HostApi,
-    types::{LocalResources, WorkloadStartRequest},
+    engine::Engine,
+    host::{
+        HostApi, HostBuilder,
+        http::{DevRouter, Ingress, TlsConfig},
+        http_limits::IngressLimits,
+    },
+    types::{Component, LocalResources, Workload, WorkloadStartRequest, WorkloadState},
 };
 
 mod common;
-use common::{component_workload_request, http_counter_host_interfaces, start_host_with_tls};
+use common::{
+    component_workload_request, http_counter_host_interfaces, http_incoming_handler_interface,
+    start_host_with_tls,
+};
 
 const HTTP_COUNTER_WASM: &[u8] = include_bytes!("wasm/http_counter.wasm");
+const HTTP_SLEEPER_WASM: &[u8] = include_bytes!("wasm/http_sleeper.wasm");
 
 /// Generate a self-signed certificate and private key for `localhost`,
 /// write them to a temp directory, and return the paths.
@@ -65,6 +76,7 @@ fn http_counter_request(host_header: &str) -> WorkloadStartRequest {
             // http-counter calls example.com
             allowed_hosts: vec!["example.com".parse().unwrap()].into(),
             allowed_ip_name_lookups: Default::default(),
+            allowed_socket_addrs: Default::default(),
         },
         http_counter_host_interfaces(host_header),
     )
@@ -163,3 +175,224 @@ async fn test_https_rejects_plain_http() -> Result<()> {
 
     Ok(())
 }
+
+/// Write a self-signed certificate for `name` to `STEM.crt` and `STEM.key` in
+/// `dir`, returning its PEM.
+fn write_cert(dir: &Path, stem: &str, name: &str) -> Result<String> {
+    let certified_key = rcgen::generate_simple_self_signed(vec![name.to_string()])?;
+    std::fs::write(dir.join(format!("{stem}.crt")), certified_key.cert.pem())?;
+    std::fs::write(
+        dir.join(format!("{stem}.key")),
+        certified_key.signing_key.serialize_pem(),
+    )?;
+    Ok(certified_key.cert.pem())
+}
+
+async fn start_host_with_tls_config(
+    tls: TlsConfig,
+) -> Result<(std::net::SocketAddr, impl HostApi)> {
+    let ingress = Ingress::builder(DevRouter::default(), "127.0.0.1:0".parse()?)
+        .tls(tls)
+        .build()
+        .await?;
+    let addr = ingress.addr();
+    let host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(std::sync::Arc::new(ingress))
+        .build()?
+        .start()
+        .await?;
+    Ok((addr, host))
+}
+
+/// GET `https://NAME/` from the ingress at `addr`, trusting only `roots`.
+/// Succeeds if the TLS handshake does, whatever the response.
+async fn handshake(addr: std::net::SocketAddr, name: &str, roots: &[&str]) -> Result<()> {
+    let mut client = reqwest::Client::builder()
+        .tls_built_in_root_certs(false)
+        .resolve(name, addr);
+    for root in roots {
+        client = client.add_root_certificate(reqwest::Certificate::from_pem(root.as_bytes())?);
+    }
+    timeout(
+        Duration::from_secs(10),
+        client
+            .build()?
+            .get(format!("https://{name}:{}/", addr.port()))
+            .send(),
+    )
+    .await??;
+    Ok(())
+}
+
+/// Start a workload serving `hostname` with the certificate at `cert` and `key`
+/// in its incoming-handler config.
+async fn start_workload_with_cert(
+    host: &impl HostApi,
+    hostname: &str,
+    cert: &str,
+    key: &str,
+) -> Result<wash_runtime::types::WorkloadStartResponse> {
+    let mut http = http_incoming_handler_interface(hostname, None);
+    http.config.extend([
+        ("tls-cert".to_string(), cert.to_string()),
+        ("tls-key".to_string(), key.to_string()),
+    ]);
+    host.workload_start(WorkloadStartRequest {
+        workload_id: uuid::Uuid::new_v4().to_string(),
+        workload: Workload {
+            namespace: "test".to_string(),
+            name: "sni".to_string(),
+            annotations: HashMap::new(),
+            service: None,
+            components: vec![Component {
+                name: "sleeper".to_string(),
+                bytes: bytes::Bytes::from_static(HTTP_SLEEPER_WASM),
+                pool_size: 1,
+                ..Default::default()
+            }],
+            host_interfaces: vec![http],
+            volumes: vec![],
+        },
+    })
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_https_certificate_is_chosen_by_sni() -> Result<()> {
+    let default_dir = tempfile::TempDir::new()?;
+    let cert_dir = tempfile::TempDir::new()?;
+    let default = write_cert(default_dir.path(), "default", "localhost")?;
+    let exact = write_cert(cert_dir.path(), "a", "a.test")?;
+    let wildcard = write_cert(cert_dir.path(), "wildcard", "*.b.test")?;
+    std::fs::create_dir(cert_dir.path().join("workloads"))?;
+    let workload = write_cert(&cert_dir.path().join("workloads"), "w", "w.test")?;
+    let roots = [
+        default.as_str(),
+        exact.as_str(),
+        wildcard.as_str(),
+        workload.as_str(),
+    ];
+
+    let (addr, host) = start_host_with_tls_config(
+        TlsConfig::new(
+            default_dir.path().join("default.crt"),
+            default_dir.path().join("default.key"),
+        )
+        .with_cert_dir(cert_dir.path()),
+    )
+    .await?;
+
+    let response =
+        start_workload_with_cert(&host, "w.test", "workloads/w.crt", "workloads/w.key").await?;
+    assert_eq!(
+        response.workload_status.workload_state,
+        WorkloadState::Running,
+        "{}",
+        response.workload_status.message
+    );
+
+    // Each name is only trusted with its own certificate, so a handshake
+    // succeeding shows the right one was served.
+    handshake(addr, "a.test", &[&exact]).await?;
+    handshake(addr, "x.b.test", &[&wildcard]).await?;
+    handshake(addr, "w.test", &[&workload]).await?;
+    handshake(addr, "localhost", &[&default]).await?;
+    // Anything else gets the default certificate, which doesn't cover it.
+    assert!(handshake(addr, "c.test", &roots).await.is_err());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_https_serves_renewed_certificates_without_restart() -> Result<()> {
+    let cert_dir = tempfile::TempDir::new()?;
+    let first = write_cert(cert_dir.path(), "a", "a.test")?;
+    let (addr, _host) = start_host_with_tls_config(
+        TlsConfig::from_cert_dir(cert_dir.path()).with_reload_interval(Duration::from_millis(100)),
+    )
+    .await?;
+    handshake(addr, "a.test", &[&first]).await?;
+    assert!(
+        handshake(addr, "new.test", &[&first]).await.is_err(),
+        "no default certificate"
+    );
+
+    let renewed = write_cert(cert_dir.path(), "a", "a.test")?;
+    let added = write_cert(cert_dir.path(), "new", "new.test")?;
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    while handshake(addr, "a.test", &[&renewed]).await.is_err() {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "renewed certificate was never served"
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    handshake(addr, "new.test", &[&added]).await?;
+    Ok(())
+}
+
+/// A workload names its certificate relative to the host's certificate
+/// directory and can't reach outside it: anything else fails the workload
+/// rather than having the host read the file.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_workload_certificates_stay_in_the_cert_dir() -> Result<()> {
+    let cert_dir = tempfile::TempDir::new()?;
+    let outside = tempfile::TempDir::new()?;
+    write_cert(outside.path(), "outside", "w.test")?;
+    let (_addr, host) =
+        start_host_with_tls_config(TlsConfig::from_cert_dir(cert_dir.path())).await?;
+
+    let absolute = outside.path().join("outside");
+    let escaping = format!(
+        "../{}/outside",
+        outside
+            .path()
+            .file_name()
+            .context("temp dir has a name")?
+            .to_string_lossy()
+    );
+    for stem in [absolute.display().to_string(), escaping] {
+        let response = start_workload_with_cert(
+            &host,
+            "w.test",
+            &format!("{stem}.crt"),
+            &format!("{stem}.key"),
+        )
+        .await?;
+        assert_eq!(
+            response.workload_status.workload_state,
+            WorkloadState::Error,
+            "{stem} must be rejected"
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stalled_tls_handshake_is_closed() -> Result<()> {
+    let (_dir, cert_path, key_path) = generate_test_certs()?;
+    let ingress = Ingress::builder(DevRouter::default(), "127.0.0.1:0".parse()?)
+        .tls(TlsConfig::new(cert_path, key_path))
+        .limits(IngressLimits {
+            header_read_timeout: Some(Duration::from_millis(300)),
+            ..Default::default()
+        })
+        .build()
+        .await?;
+    let addr = ingress.addr();
+    let _host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(std::sync::Arc::new(ingress))
+        .build()?
+        .start()
+        .await?;
+
+    // A client that connects and never starts the handshake is cut off by
+    // the header read timeout.
+    let mut stream = tokio::net::TcpStream::connect(addr).await?;
+    let mut rest = Vec::new();
+    timeout(Duration::from_secs(5), stream.read_to_end(&mut rest))
+        .await
+        .context("stalled TLS handshake was left open")??;
+    Ok(())
+}
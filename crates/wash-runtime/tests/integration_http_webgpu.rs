@@ -81,10 +81,12 @@ async fn test_http_webgpu_integration() -> Result<()> {
                     volume_mounts: vec![],
                     allowed_hosts: Default::default(),
                     allowed_ip_name_lookups: Default::default(),
+                    allowed_socket_addrs: Default::default(),
                 },
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: vec![
                 WitInterface {
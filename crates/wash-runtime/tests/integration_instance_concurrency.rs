@@ -71,6 +71,7 @@ async fn start_sleeper_with(
                 pool_size,
                 max_invocations,
                 max_concurrency,
+                ..Default::default()
             }],
             host_interfaces: http_only_host_interfaces(host_header),
             volumes: vec![],
@@ -268,7 +269,10 @@ async fn stopping_a_workload_shuts_down_its_warm_instances() -> Result<()> {
     // Stopping must complete rather than hang on the live drivers...
     tokio::time::timeout(
         Duration::from_secs(30),
-        host.workload_stop(WorkloadStopRequest { workload_id }),
+        host.workload_stop(WorkloadStopRequest {
+            workload_id,
+            ..Default::default()
+        }),
     )
     .await
     .context("workload stop should not hang on warm instances")?
@@ -308,6 +312,7 @@ async fn linked_calls_share_the_warm_instances() -> Result<()> {
                     pool_size: 2,
                     max_invocations: 0,
                     max_concurrency: 4,
+                    ..Default::default()
                 },
                 Component {
                     name: "callee".to_string(),
@@ -317,6 +322,7 @@ async fn linked_calls_share_the_warm_instances() -> Result<()> {
                     pool_size: 1,
                     max_invocations: 0,
                     max_concurrency: 4,
+                    ..Default::default()
                 },
             ],
             host_interfaces: http_only_host_interfaces("linked-conc"),
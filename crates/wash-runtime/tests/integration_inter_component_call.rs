@@ -210,10 +210,12 @@ async fn test_inter_component_call() -> Result<()> {
                         volume_mounts: vec![],
                         allowed_hosts: Default::default(),
                         allowed_ip_name_lookups: Default::default(),
+                        allowed_socket_addrs: Default::default(),
                     },
                     pool_size: 1,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
                 Component {
                     name: "middleware".to_string(),
@@ -227,10 +229,12 @@ async fn test_inter_component_call() -> Result<()> {
                         volume_mounts: vec![],
                         allowed_hosts: Default::default(),
                         allowed_ip_name_lookups: Default::default(),
+                        allowed_socket_addrs: Default::default(),
                     },
                     pool_size: 2,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
                 Component {
                     name: "callee".to_string(),
@@ -244,10 +248,12 @@ async fn test_inter_component_call() -> Result<()> {
                         volume_mounts: vec![],
                         allowed_hosts: Default::default(),
                         allowed_ip_name_lookups: Default::default(),
+                        allowed_socket_addrs: Default::default(),
                     },
                     pool_size: 2,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
             ],
             host_interfaces: vec![
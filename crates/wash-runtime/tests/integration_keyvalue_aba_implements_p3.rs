@@ -93,6 +93,7 @@ async fn p3_guest_cas_swap_detects_aba() -> Result<()> {
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces,
             volumes: vec![],
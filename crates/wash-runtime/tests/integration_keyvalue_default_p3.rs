@@ -90,6 +90,7 @@ async fn p3_guest_plain_keyvalue_uses_default_backend() -> Result<()> {
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces,
             volumes: vec![],
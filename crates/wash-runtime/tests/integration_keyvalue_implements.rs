@@ -100,6 +100,7 @@ async fn implements_imports_route_to_isolated_backends() -> Result<()> {
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces,
             volumes: vec![],
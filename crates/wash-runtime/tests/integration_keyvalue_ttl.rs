@@ -0,0 +1,299 @@
+//! Key expiry (`wasmcloud:keyvalue/ttl`) through a real component, against
+//! each standalone `wasi:keyvalue` plugin.
+//!
+//! The `keyvalue-ttl` fixture maps HTTP requests onto one bucket: `PUT` sets a
+//! key (with `set-with-ttl` when given `ttl_ms`), `GET` reads it, `POST`
+//! increments it and `GET /` lists the bucket. The in-memory and filesystem
+//! plugins run everywhere; the Redis and NATS variants need Docker and are
+//! marked `#[ignore]` (run with `cargo test --include-ignored`).
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use testcontainers::{
+    GenericImage, ImageExt,
+    core::{IntoContainerPort, WaitFor},
+    runners::AsyncRunner,
+};
+
+use wash_runtime::{
+    engine::Engine,
+    host::{
+        HostApi, HostBuilder,
+        http::{DevRouter, Ingress},
+    },
+    plugin::{
+        HostPlugin,
+        wasi_keyvalue::{FilesystemKeyValue, InMemoryKeyValue, NatsKeyValue, RedisKeyValue},
+    },
+    types::{LocalResources, WorkloadState},
+    wit::WitInterface,
+};
+
+mod common;
+use common::{component_workload_request, http_incoming_handler_interface};
+
+const KEYVALUE_TTL_WASM: &[u8] = include_bytes!("wasm/keyvalue_ttl.wasm");
+
+const HOST_HEADER: &str = "kv-ttl";
+
+/// The TTL the tests give keys; NATS only expires in whole seconds.
+const TTL_MS: u64 = 1000;
+
+fn interface(namespace: &str, package: &str, interfaces: &[&str], version: &str) -> WitInterface {
+    WitInterface {
+        namespace: namespace.to_string(),
+        package: package.to_string(),
+        interfaces: interfaces.iter().map(|i| i.to_string()).collect(),
+        version: Some(semver::Version::parse(version).unwrap()),
+        config: HashMap::new(),
+        name: None,
+    }
+}
+
+/// Start a host with `plugin` and the fixture, passing `config` as the
+/// `wasi:keyvalue` interface config.
+async fn start_host(
+    plugin: Arc<dyn HostPlugin>,
+    config: HashMap<String, String>,
+) -> Result<(SocketAddr, impl HostApi)> {
+    let ingress = Ingress::new(DevRouter::default(), "127.0.0.1:0".parse()?).await?;
+    let addr = ingress.addr();
+    let host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress))
+        .with_plugin(plugin)?
+        .build()?
+        .start()
+        .await?;
+
+    let mut keyvalue = interface("wasi", "keyvalue", &["store", "atomics"], "0.2.0-draft");
+    keyvalue.config = config;
+    let response = host
+        .workload_start(component_workload_request(
+            "keyvalue-ttl",
+            "keyvalue-ttl",
+            KEYVALUE_TTL_WASM,
+            LocalResources::default(),
+            vec![
+                http_incoming_handler_interface(HOST_HEADER, None),
+                keyvalue,
+                interface("wasmcloud", "keyvalue", &["ttl"], "0.1.0-draft"),
+            ],
+        ))
+        .await?;
+    anyhow::ensure!(
+        response.workload_status.workload_state == WorkloadState::Running,
+        "{}",
+        response.workload_status.message
+    );
+    Ok((addr, host))
+}
+
+async fn request(
+    addr: SocketAddr,
+    method: reqwest::Method,
+    path: &str,
+) -> Result<(StatusCode, String)> {
+    let response = reqwest::Client::new()
+        .request(method, format!("http://{addr}{path}"))
+        .header("HOST", HOST_HEADER)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .context("request failed")?;
+    let status = response.status();
+    Ok((status, response.text().await?))
+}
+
+async fn put(addr: SocketAddr, key: &str, value: &str, ttl_ms: Option<u64>) -> Result<StatusCode> {
+    let ttl = ttl_ms
+        .map(|ttl| format!("&ttl_ms={ttl}"))
+        .unwrap_or_default();
+    let (status, _) = request(
+        addr,
+        reqwest::Method::PUT,
+        &format!("/{key}?value={value}{ttl}"),
+    )
+    .await?;
+    Ok(status)
+}
+
+async fn get(addr: SocketAddr, key: &str) -> Result<Option<String>> {
+    match request(addr, reqwest::Method::GET, &format!("/{key}")).await? {
+        (StatusCode::OK, body) => Ok(Some(body)),
+        (StatusCode::NOT_FOUND, _) => Ok(None),
+        (status, body) => anyhow::bail!("GET /{key} returned {status}: {body}"),
+    }
+}
+
+async fn increment(addr: SocketAddr, key: &str) -> Result<String> {
+    let (status, body) = request(addr, reqwest::Method::POST, &format!("/{key}")).await?;
+    anyhow::ensure!(status.is_success(), "POST /{key} returned {status}: {body}");
+    Ok(body)
+}
+
+async fn list(addr: SocketAddr) -> Result<String> {
+    let (status, body) = request(addr, reqwest::Method::GET, "/").await?;
+    anyhow::ensure!(status.is_success(), "GET / returned {status}: {body}");
+    Ok(body)
+}
+
+/// Wait for `key` to expire, failing if it is still there after ten seconds.
+async fn wait_until_gone(addr: SocketAddr, key: &str) -> Result<()> {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+    while get(addr, key).await?.is_some() {
+        anyhow::ensure!(
+            tokio::time::Instant::now() < deadline,
+            "key {key} never expired"
+        );
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+    Ok(())
+}
+
+/// `set-with-ttl` expires just that key, and a later plain write clears it.
+async fn per_write_ttls_expire(plugin: Arc<dyn HostPlugin>) -> Result<()> {
+    let (addr, _host) = start_host(plugin, HashMap::new()).await?;
+
+    assert_eq!(
+        put(addr, "session", "abc", Some(TTL_MS)).await?,
+        StatusCode::OK
+    );
+    assert_eq!(put(addr, "profile", "kept", None).await?, StatusCode::OK);
+    assert_eq!(
+        put(addr, "sticky", "1", Some(TTL_MS)).await?,
+        StatusCode::OK
+    );
+    assert_eq!(put(addr, "sticky", "2", None).await?, StatusCode::OK);
+    assert_eq!(get(addr, "session").await?.as_deref(), Some("abc"));
+    assert_eq!(list(addr).await?, "profile\nsession\nsticky");
+
+    wait_until_gone(addr, "session").await?;
+    assert_eq!(get(addr, "profile").await?.as_deref(), Some("kept"));
+    assert_eq!(
+        get(addr, "sticky").await?.as_deref(),
+        Some("2"),
+        "a plain write must clear the TTL of an earlier set-with-ttl"
+    );
+    assert_eq!(list(addr).await?, "profile\nsticky");
+
+    assert_eq!(
+        put(addr, "never", "x", Some(0)).await?,
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "a zero TTL must be rejected"
+    );
+    assert_eq!(get(addr, "never").await?, None);
+
+    Ok(())
+}
+
+/// A bucket's default TTL applies to plain writes and increments, and an
+/// expired counter starts over.
+async fn bucket_default_ttl_applies(plugin: Arc<dyn HostPlugin>) -> Result<()> {
+    let config = HashMap::from([("ttl.cache".to_string(), format!("{TTL_MS}ms"))]);
+    let (addr, _host) = start_host(plugin, config).await?;
+
+    assert_eq!(put(addr, "page", "home", None).await?, StatusCode::OK);
+    assert_eq!(increment(addr, "hits").await?, "1");
+    assert_eq!(increment(addr, "hits").await?, "2");
+
+    wait_until_gone(addr, "page").await?;
+    wait_until_gone(addr, "hits").await?;
+    assert_eq!(increment(addr, "hits").await?, "1");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn in_memory_per_write_ttls_expire() -> Result<()> {
+    per_write_ttls_expire(Arc::new(InMemoryKeyValue::new())).await
+}
+
+#[tokio::test]
+async fn in_memory_bucket_default_ttl_applies() -> Result<()> {
+    bucket_default_ttl_applies(Arc::new(InMemoryKeyValue::new())).await
+}
+
+#[tokio::test]
+async fn filesystem_per_write_ttls_expire() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    per_write_ttls_expire(Arc::new(FilesystemKeyValue::new(dir.path()))).await
+}
+
+#[tokio::test]
+async fn filesystem_bucket_default_ttl_applies() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    bucket_default_ttl_applies(Arc::new(FilesystemKeyValue::new(dir.path()))).await
+}
+
+#[tokio::test]
+async fn invalid_ttl_config_fails_the_workload() -> Result<()> {
+    let config = HashMap::from([("ttl".to_string(), "soon".to_string())]);
+    let err = match start_host(Arc::new(InMemoryKeyValue::new()), config).await {
+        Ok(_) => anyhow::bail!("workload with an invalid TTL should not start"),
+        Err(e) => e,
+    };
+    assert!(
+        err.to_string().contains("keyvalue TTL"),
+        "unexpected error: {err}"
+    );
+    Ok(())
+}
+
+async fn start_redis() -> Result<(testcontainers::ContainerAsync<GenericImage>, String)> {
+    let redis = GenericImage::new("redis", "7-alpine")
+        .with_exposed_port(6379.tcp())
+        .with_wait_for(WaitFor::message_on_stdout("Ready to accept connections"))
+        .start()
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to start redis: {e}"))?;
+    let url = format!(
+        "redis://127.0.0.1:{}",
+        redis.get_host_port_ipv4(6379).await?
+    );
+    Ok((redis, url))
+}
+
+async fn start_nats() -> Result<(
+    testcontainers::ContainerAsync<GenericImage>,
+    async_nats::Client,
+)> {
+    let nats = GenericImage::new("nats", "2.12.8-alpine")
+        .with_exposed_port(4222.tcp())
+        .with_wait_for(WaitFor::message_on_stderr("Server is ready"))
+        .with_cmd(["-js"])
+        .start()
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to start NATS: {e}"))?;
+    let client = async_nats::connect(format!(
+        "nats://127.0.0.1:{}",
+        nats.get_host_port_ipv4(4222).await?
+    ))
+    .await?;
+    Ok((nats, client))
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (redis); run with `cargo test --include-ignored`"]
+async fn redis_ttls_expire() -> Result<()> {
+    let (_redis, url) = start_redis().await?;
+    per_write_ttls_expire(Arc::new(RedisKeyValue::from_url(&url)?)).await?;
+    let (_redis, url) = start_redis().await?;
+    bucket_default_ttl_applies(Arc::new(RedisKeyValue::from_url(&url)?)).await
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (NATS); run with `cargo test --include-ignored`"]
+async fn nats_ttls_expire() -> Result<()> {
+    let (_nats, client) = start_nats().await?;
+    per_write_ttls_expire(Arc::new(NatsKeyValue::new(&client))).await?;
+    let (_nats, client) = start_nats().await?;
+    bucket_default_ttl_applies(Arc::new(NatsKeyValue::new(&client))).await
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "wasm_component_model_implements")]
+//! `wasmcloud:keyvalue/watcher` delivery end to end, through a **real P3
+//! guest**.
+//!
+//! The `keyvalue-watcher-p3` fixture writes and deletes keys in its `watched`
+//! bucket on HTTP requests, and exports the watcher. The host watches that
+//! bucket (the `watch` config of the keyvalue interface), so each change is
+//! handed to the watcher, which logs it back into the bucket under `log:`
+//! keys the test reads over HTTP. The log appends, so it also shows each
+//! change arrived exactly once.
+//!
+//! Uses the in-memory backend, so it needs no external services and is not
+//! `#[ignore]`d.
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use wash_runtime::{
+    engine::Engine,
+    host::{
+        HostApi, HostBuilder,
+        http::{DevRouter, Ingress},
+    },
+    plugin::wasi_keyvalue::{InMemoryProvider, MultiplexedAsyncKeyValue},
+    types::{Component, LocalResources, Workload, WorkloadStartRequest, WorkloadState},
+    wit::WitInterface,
+};
+
+mod common;
+use common::http_incoming_handler_interface;
+
+const KEYVALUE_WATCHER_P3_WASM: &[u8] = include_bytes!("wasm/keyvalue_watcher_p3.wasm");
+
+const HOST_HEADER: &str = "kv-watcher";
+
+/// The default keyvalue route, watching the fixture's `watched` bucket.
+fn watched_kv_iface() -> WitInterface {
+    WitInterface {
+        namespace: "wasmcloud".to_string(),
+        package: "keyvalue".to_string(),
+        interfaces: ["store".to_string()].into_iter().collect(),
+        version: Some(semver::Version::parse("0.2.0").unwrap()),
+        config: HashMap::from([("watch".to_string(), "watched".to_string())]),
+        name: None,
+    }
+}
+
+async fn start_watcher() -> Result<(SocketAddr, impl HostApi)> {
+    let ingress = Ingress::new(DevRouter::default(), "127.0.0.1:0".parse()?).await?;
+    let addr = ingress.addr();
+    let host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress))
+        .with_plugin(Arc::new(
+            MultiplexedAsyncKeyValue::new().with_provider(Arc::new(InMemoryProvider)),
+        ))?
+        .build()?
+        .start()
+        .await
+        .context("failed to start host")?;
+
+    let resp = host
+        .workload_start(WorkloadStartRequest {
+            workload_id: uuid::Uuid::new_v4().to_string(),
+            workload: Workload {
+                namespace: "test".to_string(),
+                name: "keyvalue-watcher-p3".to_string(),
+                annotations: HashMap::new(),
+                service: None,
+                components: vec![Component {
+                    name: "keyvalue-watcher-p3.wasm".to_string(),
+                    digest: None,
+                    bytes: bytes::Bytes::from_static(KEYVALUE_WATCHER_P3_WASM),
+                    local_resources: LocalResources::default(),
+                    pool_size: 1,
+                    max_invocations: 100,
+                    max_concurrency: 1,
+                    ..Default::default()
+                }],
+                host_interfaces: vec![
+                    http_incoming_handler_interface(HOST_HEADER, None),
+                    watched_kv_iface(),
+                ],
+                volumes: vec![],
+            },
+        })
+        .await
+        .context("workload_start call failed")?;
+    assert_eq!(
+        resp.workload_status.workload_state,
+        WorkloadState::Running,
+        "watcher workload should resolve: {}",
+        resp.workload_status.message
+    );
+    Ok((addr, host))
+}
+
+/// Send `method` to `/<key>` with `body`, returning the status and body.
+async fn request(
+    addr: SocketAddr,
+    method: reqwest::Method,
+    key: &str,
+    body: &str,
+) -> Result<(u16, String)> {
+    let response = reqwest::Client::new()
+        .request(method, format!("http://{addr}/{key}"))
+        .header("HOST", HOST_HEADER)
+        .body(body.to_string())
+        .timeout(Duration::from_secs(15))
+        .send()
+        .await
+        .context("request failed")?;
+    Ok((response.status().as_u16(), response.text().await?))
+}
+
+/// Poll `key` until it holds a value, and return it.
+async fn logged(addr: SocketAddr, key: &str) -> Result<String> {
+    for _ in 0..100 {
+        let (status, body) = request(addr, reqwest::Method::GET, key, "").await?;
+        match status {
+            200 => return Ok(body),
+            404 => tokio::time::sleep(Duration::from_millis(50)).await,
+            other => anyhow::bail!("GET {key} returned {other}: {body}"),
+        }
+    }
+    anyhow::bail!("the watcher never logged {key}")
+}
+
+#[tokio::test]
+async fn p3_watcher_sees_each_change_once() -> Result<()> {
+    let (addr, _host) = start_watcher().await?;
+
+    let (status, body) = request(addr, reqwest::Method::PUT, "greeting", "hello").await?;
+    assert_eq!(status, 200, "set failed: {body}");
+    assert_eq!(logged(addr, "log:set:greeting").await?, "hello");
+
+    let (status, body) = request(addr, reqwest::Method::DELETE, "greeting", "").await?;
+    assert_eq!(status, 200, "delete failed: {body}");
+    assert_eq!(logged(addr, "log:delete:greeting").await?, ".");
+
+    // Both changes have been delivered by now; neither was delivered twice.
+    assert_eq!(logged(addr, "log:set:greeting").await?, "hello");
+    assert_eq!(logged(addr, "log:delete:greeting").await?, ".");
+    Ok(())
+}
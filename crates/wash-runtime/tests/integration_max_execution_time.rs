@@ -0,0 +1,210 @@
+//! `Component.max_execution_time` interrupts a guest that never yields.
+//!
+//! The `http-sleeper` fixture's `/spin` loops forever without ever returning
+//! to the host, so no host-side timeout can fire for it. The engine's epoch
+//! ticker is what reaches it: once the call has run past the component's
+//! `max_execution_time` the store traps, the request fails, and the component
+//! goes on serving — from a fresh store, or for a pooled component, a fresh
+//! instance. The `svc-counter` service's `/spin` does the same on a service,
+//! whose `wasi:cli/run` keeps running untimed alongside the calls it serves.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+use wash_runtime::host::HostApi;
+use wash_runtime::types::{Component, LocalResources, Service, Workload, WorkloadStartRequest};
+
+mod common;
+use common::{http_only_host_interfaces, json_u64_field, start_host_with_p3_http_handler};
+
+const HTTP_SLEEPER_WASM: &[u8] = include_bytes!("wasm/http_sleeper.wasm");
+const SVC_COUNTER_WASM: &[u8] = include_bytes!("wasm/svc_counter.wasm");
+
+const MAX_EXECUTION_TIME: Duration = Duration::from_millis(300);
+
+async fn start_sleeper(
+    host_header: &str,
+    pool_size: i32,
+) -> Result<(std::net::SocketAddr, impl HostApi)> {
+    let (addr, host) = start_host_with_p3_http_handler("127.0.0.1:0").await?;
+    host.workload_start(WorkloadStartRequest {
+        workload_id: uuid::Uuid::new_v4().to_string(),
+        workload: Workload {
+            namespace: "test".to_string(),
+            name: host_header.to_string(),
+            annotations: HashMap::new(),
+            service: None,
+            components: vec![Component {
+                name: "sleeper".to_string(),
+                digest: None,
+                bytes: bytes::Bytes::from_static(HTTP_SLEEPER_WASM),
+                local_resources: LocalResources::default(),
+                pool_size,
+                max_invocations: 0,
+                max_concurrency: 0,
+                max_execution_time: Some(MAX_EXECUTION_TIME),
+            }],
+            host_interfaces: http_only_host_interfaces(host_header),
+            volumes: vec![],
+        },
+    })
+    .await
+    .context("sleeper workload should start")?;
+    Ok((addr, host))
+}
+
+fn client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .pool_max_idle_per_host(0)
+        .timeout(Duration::from_secs(15))
+        .build()?)
+}
+
+/// A `/spin` request must fail, and soon after the deadline rather than at
+/// the client's own timeout.
+async fn assert_spin_interrupted(
+    client: &reqwest::Client,
+    addr: std::net::SocketAddr,
+    host_header: &str,
+) -> Result<()> {
+    let started = Instant::now();
+    let outcome = client
+        .get(format!("http://{addr}/spin"))
+        .header("HOST", host_header)
+        .send()
+        .await;
+    let elapsed = started.elapsed();
+    anyhow::ensure!(
+        elapsed < Duration::from_secs(5),
+        "the spinning call must be interrupted near its {MAX_EXECUTION_TIME:?} limit, took {elapsed:?}"
+    );
+    if let Ok(resp) = outcome {
+        anyhow::ensure!(
+            resp.status().is_server_error(),
+            "an interrupted call must fail, got {}",
+            resp.status()
+        );
+    }
+    Ok(())
+}
+
+/// GET `/` and return the instance's `served` count.
+async fn served(
+    client: &reqwest::Client,
+    addr: std::net::SocketAddr,
+    host_header: &str,
+) -> Result<u64> {
+    let resp = client
+        .get(format!("http://{addr}/"))
+        .header("HOST", host_header)
+        .send()
+        .await?;
+    anyhow::ensure!(resp.status().is_success(), "status {}", resp.status());
+    Ok(json_u64_field(&resp.text().await?, "served"))
+}
+
+#[tokio::test]
+async fn a_spinning_call_is_interrupted() -> Result<()> {
+    let (addr, _host) = start_sleeper("spin-ephemeral", 0).await?;
+    let client = client()?;
+
+    assert_spin_interrupted(&client, addr, "spin-ephemeral").await?;
+
+    // Calls within the limit are untouched. The first call on a store pays
+    // the fixture's 100ms setup, well inside it.
+    served(&client, addr, "spin-ephemeral").await?;
+    Ok(())
+}
+
+/// On a warm instance the deadline is per call, not per instance: an instance
+/// that has been up for longer than `max_execution_time` keeps serving, and
+/// the one call that overran it takes the instance down with its trap.
+#[tokio::test]
+async fn a_spinning_call_on_a_warm_instance_is_interrupted() -> Result<()> {
+    let (addr, _host) = start_sleeper("spin-pooled", 1).await?;
+    let client = client()?;
+
+    assert_eq!(served(&client, addr, "spin-pooled").await?, 1);
+    tokio::time::sleep(MAX_EXECUTION_TIME * 2).await;
+    assert_eq!(
+        served(&client, addr, "spin-pooled").await?,
+        2,
+        "an instance older than the limit must keep serving"
+    );
+
+    assert_spin_interrupted(&client, addr, "spin-pooled").await?;
+
+    assert_eq!(
+        served(&client, addr, "spin-pooled").await?,
+        1,
+        "the trapped instance must be replaced by a fresh one"
+    );
+    Ok(())
+}
+
+/// A service's deadline times each call it serves, not the service: its
+/// `cli/run` outlives the limit without being interrupted, a spinning request
+/// is, and the supervisor brings the service back to serve the next one.
+#[tokio::test]
+async fn a_spinning_call_on_a_service_is_interrupted() -> Result<()> {
+    let (addr, host) = start_host_with_p3_http_handler("127.0.0.1:0").await?;
+    host.workload_start(WorkloadStartRequest {
+        workload_id: uuid::Uuid::new_v4().to_string(),
+        workload: Workload {
+            namespace: "test".to_string(),
+            name: "spin-service".to_string(),
+            annotations: HashMap::new(),
+            service: Some(Service {
+                bytes: bytes::Bytes::from_static(SVC_COUNTER_WASM),
+                max_restarts: 1,
+                max_execution_time: Some(MAX_EXECUTION_TIME),
+                ..Default::default()
+            }),
+            components: vec![],
+            host_interfaces: http_only_host_interfaces("spin-service"),
+            volumes: vec![],
+        },
+    })
+    .await
+    .context("service workload should start")?;
+    let client = client()?;
+    let http_calls = || async {
+        let resp = client
+            .get(format!("http://{addr}/"))
+            .header("HOST", "spin-service")
+            .send()
+            .await?;
+        anyhow::ensure!(resp.status().is_success(), "status {}", resp.status());
+        Ok::<_, anyhow::Error>(json_u64_field(&resp.text().await?, "http_calls"))
+    };
+
+    tokio::time::sleep(MAX_EXECUTION_TIME * 2).await;
+    assert_eq!(
+        http_calls().await?,
+        1,
+        "a service up for longer than the limit must keep serving"
+    );
+
+    assert_spin_interrupted(&client, addr, "spin-service").await?;
+
+    // The trap takes the service's instance with it; the supervisor restarts
+    // it, and the fresh incarnation counts from zero again.
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        match http_calls().await {
+            Ok(calls) => {
+                assert_eq!(calls, 1, "the restarted service must be a fresh instance");
+                break;
+            }
+            Err(_) if Instant::now() < deadline => {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+            Err(e) => return Err(e.context("the service must be restarted")),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,206 @@
+//! Request-reply through a `wasmcloud:messaging/handler@0.3.0` component's
+//! return value.
+//!
+//! The `messaging-reply` fixture answers `reply.*` subjects by returning a
+//! body, and sends requests (with an `x-request-id` header) from its HTTP
+//! endpoint. The in-memory tests route both sides within one workload; the
+//! NATS test needs Docker and is marked `#[ignore]` (run with
+//! `cargo test --include-ignored`).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures::StreamExt;
+
+use wash_runtime::{
+    engine::Engine,
+    host::{
+        HostApi, HostBuilder,
+        http::{DevRouter, Ingress},
+    },
+    plugin::{
+        HostPlugin,
+        wasmcloud_messaging::{InMemoryMessaging, NatsMessaging},
+    },
+    types::{LocalResources, WorkloadState},
+    wit::WitInterface,
+};
+
+mod common;
+use common::{component_workload_request, http_incoming_handler_interface, req};
+
+const MESSAGING_REPLY_WASM: &[u8] = include_bytes!("wasm/messaging_reply.wasm");
+
+const HOST_HEADER: &str = "messaging-reply";
+
+fn messaging_interface(config: &[(&str, &str)]) -> WitInterface {
+    WitInterface {
+        namespace: "wasmcloud".to_string(),
+        package: "messaging".to_string(),
+        interfaces: ["consumer".to_string(), "handler".to_string()]
+            .into_iter()
+            .collect(),
+        version: Some(semver::Version::new(0, 3, 0)),
+        config: config
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>(),
+        name: None,
+    }
+}
+
+/// Start a host with `plugin` and a `messaging-reply` workload subscribed to
+/// `reply.>` that retries a failed message once and then dead-letters it to
+/// `dead.reply`.
+async fn start_host(plugin: impl HostPlugin) -> Result<(std::net::SocketAddr, impl HostApi)> {
+    let ingress = Ingress::new(DevRouter::default(), "127.0.0.1:0".parse()?).await?;
+    let addr = ingress.addr();
+    let host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress))
+        .with_plugin(Arc::new(plugin))?
+        .build()?
+        .start()
+        .await?;
+    let response = host
+        .workload_start(component_workload_request(
+            "messaging-reply",
+            HOST_HEADER,
+            MESSAGING_REPLY_WASM,
+            LocalResources::default(),
+            vec![
+                http_incoming_handler_interface(HOST_HEADER, None),
+                messaging_interface(&[
+                    ("subscriptions", "reply.>"),
+                    ("retry_count", "1"),
+                    ("retry_backoff_ms", "10"),
+                    ("dead_letter_subject", "dead.reply"),
+                ]),
+            ],
+        ))
+        .await?;
+    anyhow::ensure!(
+        response.workload_status.workload_state == WorkloadState::Running,
+        "{}",
+        response.workload_status.message
+    );
+    Ok((addr, host))
+}
+
+#[tokio::test]
+async fn handler_return_value_answers_in_memory_requests() -> Result<()> {
+    let (addr, _host) = start_host(InMemoryMessaging::new()).await?;
+    let client = reqwest::Client::new();
+
+    let (status, body) = req(
+        &client,
+        &addr,
+        HOST_HEADER,
+        "/request?subject=reply.echo&body=hi&id=42",
+    )
+    .await?;
+    assert_eq!(
+        (status.as_u16(), body.as_str()),
+        (200, "hi|42"),
+        "the reply is the returned body, and the handler saw the request's headers"
+    );
+
+    let (status, body) = req(
+        &client,
+        &addr,
+        HOST_HEADER,
+        "/request?subject=reply.fail&body=hi&id=1",
+    )
+    .await?;
+    assert_eq!(
+        (status.as_u16(), body.as_str()),
+        (502, "refused hi"),
+        "a failed handler is answered with its error once retries run out"
+    );
+
+    let (status, body) = req(
+        &client,
+        &addr,
+        HOST_HEADER,
+        "/request?subject=reply.none&body=hi&id=2",
+    )
+    .await?;
+    assert_eq!(status.as_u16(), 504, "returning `none` sends no reply");
+    assert!(body.contains("timed out"), "{body}");
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (NATS); run with `cargo test --include-ignored`"]
+async fn handler_return_value_answers_nats_requests() -> Result<()> {
+    use testcontainers::{
+        GenericImage,
+        core::{IntoContainerPort, WaitFor},
+        runners::AsyncRunner,
+    };
+
+    let nats = GenericImage::new("nats", "2.12.8-alpine")
+        .with_exposed_port(4222.tcp())
+        .with_wait_for(WaitFor::message_on_stderr("Server is ready"))
+        .start()
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to start NATS: {e}"))?;
+    let url = format!("nats://127.0.0.1:{}", nats.get_host_port_ipv4(4222).await?);
+    let plugin = NatsMessaging::new(Arc::new(async_nats::connect(&url).await?));
+    let (addr, _host) = start_host(plugin).await?;
+
+    // A component's request reaches the handler with its headers.
+    let (status, body) = req(
+        &reqwest::Client::new(),
+        &addr,
+        HOST_HEADER,
+        "/request?subject=reply.echo&body=hi&id=42",
+    )
+    .await?;
+    assert_eq!((status.as_u16(), body.as_str()), (200, "hi|42"));
+
+    // So does a request from any other NATS client, and a failure comes back
+    // as an error header.
+    let client = async_nats::connect(&url).await?;
+    let mut headers = async_nats::HeaderMap::new();
+    headers.insert("x-request-id", "7");
+    let reply = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.request_with_headers("reply.echo", headers, "ping".into()),
+    )
+    .await
+    .context("no reply")??;
+    assert_eq!(reply.payload.as_ref(), b"ping|7");
+
+    let mut dead_letters = client.subscribe("dead.reply").await?;
+    client.flush().await?;
+    let reply = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.request("reply.fail", "ping".into()),
+    )
+    .await
+    .context("no reply")??;
+    assert!(reply.payload.is_empty());
+    assert_eq!(
+        reply
+            .headers
+            .as_ref()
+            .and_then(|h| h.get("wasmcloud-error"))
+            .map(|v| v.as_str()),
+        Some("refused ping")
+    );
+
+    // The failed request was also dead-lettered, its payload in base64.
+    let dead = tokio::time::timeout(Duration::from_secs(5), dead_letters.next())
+        .await
+        .context("no dead letter")?
+        .context("dead-letter subscription closed")?;
+    let letter: serde_json::Value = serde_json::from_slice(&dead.payload)?;
+    assert_eq!(letter["subject"], "reply.fail");
+    assert_eq!(letter["body"], "cGluZw==");
+    assert_eq!(letter["error"], "refused ping");
+    assert_eq!(letter["attempts"], 2);
+    Ok(())
+}
@@ -230,10 +230,12 @@ async fn setup(latency: Duration) -> Result<TestHarness> {
                     volume_mounts: vec![],
                     allowed_hosts: Default::default(),
                     allowed_ip_name_lookups: Default::default(),
+                    allowed_socket_addrs: Default::default(),
                 },
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: vec![WitInterface {
                 namespace: "wasmcloud".to_string(),
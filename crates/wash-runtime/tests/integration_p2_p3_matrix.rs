@@ -73,6 +73,7 @@ async fn test_p3_http_handler_serves_request() -> Result<()> {
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: p3_http_host_interfaces("p3-handler"),
             volumes: vec![],
@@ -126,6 +127,7 @@ async fn test_p3_http_blobstore() -> Result<()> {
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: p3_http_blobstore_host_interfaces("p3-blobstore"),
             volumes: vec![],
@@ -182,6 +184,7 @@ async fn test_p3_http_concurrent_requests() -> Result<()> {
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: p3_http_host_interfaces("p3-concurrent"),
             volumes: vec![],
@@ -245,6 +248,7 @@ async fn test_p3_service_runs() -> Result<()> {
                 bytes: bytes::Bytes::from_static(CLI_SERVICE_P3_WASM),
                 local_resources: LocalResources::default(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![],
             host_interfaces: vec![],
@@ -313,6 +317,7 @@ async fn test_p3_caller_p2_middleware_p2_callee() -> Result<()> {
                     pool_size: 1,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
                 Component {
                     name: "p2-middleware".to_string(),
@@ -322,6 +327,7 @@ async fn test_p3_caller_p2_middleware_p2_callee() -> Result<()> {
                     pool_size: 1,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
                 Component {
                     name: "p2-callee".to_string(),
@@ -331,6 +337,7 @@ async fn test_p3_caller_p2_middleware_p2_callee() -> Result<()> {
                     pool_size: 1,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
             ],
             host_interfaces: inter_component_host_interfaces("p3-p2-p2"),
@@ -390,6 +397,7 @@ async fn test_p2_caller_p2_middleware_p3_callee() -> Result<()> {
                     pool_size: 1,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
                 Component {
                     name: "p2-middleware".to_string(),
@@ -399,6 +407,7 @@ async fn test_p2_caller_p2_middleware_p3_callee() -> Result<()> {
                     pool_size: 1,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
                 Component {
                     name: "p3-callee".to_string(),
@@ -408,6 +417,7 @@ async fn test_p2_caller_p2_middleware_p3_callee() -> Result<()> {
                     pool_size: 1,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
             ],
             host_interfaces: inter_component_host_interfaces("p2-p2-p3"),
@@ -463,6 +473,7 @@ async fn test_p3_caller_p2_middleware_p3_callee() -> Result<()> {
                     pool_size: 1,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
                 Component {
                     name: "p2-middleware".to_string(),
@@ -472,6 +483,7 @@ async fn test_p3_caller_p2_middleware_p3_callee() -> Result<()> {
                     pool_size: 1,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
                 Component {
                     name: "p3-callee".to_string(),
@@ -481,6 +493,7 @@ async fn test_p3_caller_p2_middleware_p3_callee() -> Result<()> {
                     pool_size: 1,
                     max_invocations: 100,
                     max_concurrency: 1,
+                    ..Default::default()
                 },
             ],
             host_interfaces: inter_component_host_interfaces("p3-p2-p3"),
@@ -533,6 +546,7 @@ async fn test_all_p3_workload() -> Result<()> {
                 bytes: bytes::Bytes::from_static(CLI_SERVICE_P3_WASM),
                 local_resources: LocalResources::default(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![Component {
                 name: "http-blobstore-p3.wasm".to_string(),
@@ -542,6 +556,7 @@ async fn test_all_p3_workload() -> Result<()> {
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: p3_http_blobstore_host_interfaces("all-p3"),
             volumes: vec![],
@@ -603,10 +618,12 @@ async fn test_p2_regression_with_p3_enabled() -> Result<()> {
                     // http-counter calls example.com
                     allowed_hosts: vec!["example.com".parse().unwrap()].into(),
                     allowed_ip_name_lookups: Default::default(),
+                    allowed_socket_addrs: Default::default(),
                 },
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: http_counter_host_interfaces("p2-regression"),
             volumes: vec![],
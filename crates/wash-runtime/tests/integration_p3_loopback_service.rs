@@ -41,6 +41,7 @@ fn echo_workload(host: &str) -> WorkloadStartRequest {
                 bytes: bytes::Bytes::from_static(SVC_TCP_ECHO_WASM),
                 local_resources: LocalResources::default(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![Component {
                 name: "http-loopback-gateway".to_string(),
@@ -50,6 +51,7 @@ fn echo_workload(host: &str) -> WorkloadStartRequest {
                 pool_size: 1,
                 max_invocations: 1000,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: http_only_host_interfaces(host),
             volumes: vec![],
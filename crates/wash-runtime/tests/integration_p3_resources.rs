@@ -51,6 +51,7 @@ fn component(name: &str, bytes: &'static [u8]) -> Component {
         pool_size: 1,
         max_invocations: 100,
         max_concurrency: 1,
+        ..Default::default()
     }
 }
 
@@ -51,6 +51,7 @@ async fn test_p3_tcp_loopback() -> Result<()> {
                 bytes: bytes::Bytes::from_static(SOCKET_TEST_P3_WASM),
                 local_resources: Default::default(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![],
             host_interfaces: vec![],
@@ -88,6 +89,7 @@ async fn test_p3_socket_component_initialization() -> Result<()> {
             pool_size: 1,
             max_invocations: 10,
             max_concurrency: 1,
+            ..Default::default()
         }],
         host_interfaces: vec![],
         volumes: vec![],
@@ -158,6 +158,7 @@ async fn implements_imports_route_to_per_credential_connections() -> Result<()>
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces,
             volumes: vec![],
@@ -0,0 +1,282 @@
+//! `wasmcloud:postgres` `notifications` e2e: Postgres `NOTIFY` delivered to a
+//! component's `on-notify`, for both the sync (`0.1.1-draft`) and async
+//! (`0.2.0`) exports.
+//!
+//! The `postgres-notifications` (p2) fixture and `postgres-stream-p3` both
+//! record each notification they receive as a row of `received`. Beyond plain
+//! delivery, each test terminates the plugin's listening connection from the
+//! server side and checks that notifications flow again once the plugin has
+//! reconnected and re-issued `LISTEN`, and that stopping the workload closes
+//! the connection.
+//!
+//! Requires Docker; marked `#[ignore]`, so it runs only under
+//! `cargo test --include-ignored` (CI's Linux leg) and not a plain `cargo test`.
+#![cfg(feature = "wasmcloud-postgres")]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use wash_runtime::{
+    engine::Engine,
+    host::{
+        HostApi, HostBuilder,
+        http::{DevRouter, Ingress},
+    },
+    plugin::wasmcloud_postgres::WasmcloudPostgres,
+    types::{LocalResources, WorkloadStartRequest, WorkloadState, WorkloadStopRequest},
+    wit::WitInterface,
+};
+
+mod common;
+use common::postgres::{STREAM_FIXTURE_WASM, admin_client, start_postgres};
+use common::{component_workload_request, http_incoming_handler_interface};
+
+const POSTGRES_NOTIFICATIONS_WASM: &[u8] = include_bytes!("wasm/postgres_notifications.wasm");
+
+/// The channels every workload listens on. `Audit` is mixed case to check that
+/// channel names are used verbatim.
+const LISTEN: &str = "orders, Audit";
+
+/// Counts the plugin's listening connections: their last statement is a
+/// `LISTEN`.
+const LISTENING_BACKENDS: &str = "SELECT count(*) FROM pg_stat_activity \
+     WHERE query LIKE 'LISTEN %' AND pid <> pg_backend_pid()";
+
+/// Start postgres and create the empty `received` table; returns the container
+/// (kept alive by the caller), its `host:port`, and an admin client for sending
+/// notifications and checking what was delivered.
+async fn start_received() -> Result<(
+    testcontainers::ContainerAsync<testcontainers::GenericImage>,
+    String,
+    tokio_postgres::Client,
+)> {
+    let (container, host_addr) = start_postgres().await?;
+    let admin = admin_client(&format!(
+        "postgres://postgres:postgres@{host_addr}/postgres"
+    ))
+    .await?;
+    admin
+        .batch_execute("CREATE TABLE received (channel TEXT NOT NULL, payload TEXT NOT NULL)")
+        .await
+        .context("failed to create received table")?;
+    Ok((container, host_addr, admin))
+}
+
+/// A `wasmcloud:postgres` host interface for the `postgres` database that
+/// listens on [`LISTEN`].
+fn listening_interface(version: &str, interfaces: &[&str]) -> WitInterface {
+    WitInterface {
+        namespace: "wasmcloud".to_string(),
+        package: "postgres".to_string(),
+        interfaces: interfaces.iter().map(|i| i.to_string()).collect(),
+        version: Some(semver::Version::parse(version).unwrap()),
+        config: HashMap::from([
+            ("database".to_string(), "postgres".to_string()),
+            ("listen".to_string(), LISTEN.to_string()),
+        ]),
+        name: None,
+    }
+}
+
+/// Start a host with the postgres plugin and run `request` on it.
+async fn start_workload(host_addr: &str, request: WorkloadStartRequest) -> Result<impl HostApi> {
+    let ingress = Ingress::new(DevRouter::default(), "127.0.0.1:0".parse()?).await?;
+    let host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress))
+        .with_plugin(Arc::new(WasmcloudPostgres::new(&format!(
+            "postgres://postgres:postgres@{host_addr}/"
+        ))?))?
+        .build()?
+        .start()
+        .await?;
+    let response = host.workload_start(request).await?;
+    assert_eq!(
+        response.workload_status.workload_state,
+        WorkloadState::Running,
+        "workload should resolve: {}",
+        response.workload_status.message
+    );
+    Ok(host)
+}
+
+/// Everything recorded in `received`, sorted.
+async fn received(admin: &tokio_postgres::Client) -> Result<Vec<(String, String)>> {
+    Ok(admin
+        .query(
+            "SELECT channel, payload FROM received ORDER BY channel, payload",
+            &[],
+        )
+        .await?
+        .iter()
+        .map(|row| (row.get(0), row.get(1)))
+        .collect())
+}
+
+async fn listening_backends(admin: &tokio_postgres::Client) -> Result<i64> {
+    Ok(admin.query_one(LISTENING_BACKENDS, &[]).await?.get(0))
+}
+
+/// Poll `check` every 100ms until it returns `true`, failing after 15s.
+async fn eventually<F, Fut>(what: &str, mut check: F) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<bool>>,
+{
+    for _ in 0..150 {
+        if check().await? {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    anyhow::bail!("timed out waiting for {what}")
+}
+
+/// Send notifications to the running workload, drop its listening connection,
+/// and stop it, checking delivery at each step.
+async fn notifications_are_delivered(
+    host: impl HostApi,
+    workload_id: String,
+    admin: &tokio_postgres::Client,
+) -> Result<()> {
+    admin
+        .batch_execute(
+            "NOTIFY orders, 'first'; \
+             SELECT pg_notify('Audit', 'second'); \
+             NOTIFY audit, 'unlistened'",
+        )
+        .await?;
+    eventually("the first notifications", || async {
+        Ok(received(admin).await?.len() >= 2)
+    })
+    .await?;
+    assert_eq!(
+        received(admin).await?,
+        [
+            ("Audit".to_string(), "second".to_string()),
+            ("orders".to_string(), "first".to_string()),
+        ],
+        "only the listened channels are delivered, with their payloads"
+    );
+
+    let terminated: i64 = admin
+        .query_one(
+            "SELECT count(pg_terminate_backend(pid)) FROM pg_stat_activity \
+             WHERE query LIKE 'LISTEN %' AND pid <> pg_backend_pid()",
+            &[],
+        )
+        .await?
+        .get(0);
+    assert_eq!(terminated, 1, "one dedicated listening connection");
+
+    // Notifications sent before the plugin has listened again are lost, so keep
+    // sending until one gets through.
+    eventually("delivery after reconnecting", || async {
+        admin.batch_execute("NOTIFY orders, 'reconnected'").await?;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        Ok(received(admin)
+            .await?
+            .iter()
+            .any(|(_, payload)| payload == "reconnected"))
+    })
+    .await?;
+
+    host.workload_stop(WorkloadStopRequest {
+        workload_id,
+        ..Default::default()
+    })
+    .await?;
+    eventually("the listening connection to close", || async {
+        Ok(listening_backends(admin).await? == 0)
+    })
+    .await
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (postgres); run with `cargo test --include-ignored`"]
+async fn sync_notifications_are_delivered_and_survive_reconnect() -> Result<()> {
+    let (_container, host_addr, admin) = start_received().await?;
+    let request = component_workload_request(
+        "postgres-notifications",
+        "postgres-notifications",
+        POSTGRES_NOTIFICATIONS_WASM,
+        LocalResources::default(),
+        vec![listening_interface(
+            "0.1.1-draft",
+            &["types", "query", "notifications"],
+        )],
+    );
+    let workload_id = request.workload_id.clone();
+    let host = start_workload(&host_addr, request).await?;
+    notifications_are_delivered(host, workload_id, &admin).await
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (postgres); run with `cargo test --include-ignored`"]
+async fn async_notifications_are_delivered_and_survive_reconnect() -> Result<()> {
+    let (_container, host_addr, admin) = start_received().await?;
+    let request = component_workload_request(
+        "postgres-stream-p3",
+        "postgres-stream-p3",
+        STREAM_FIXTURE_WASM,
+        LocalResources::default(),
+        vec![
+            http_incoming_handler_interface("pg-notify-p3", None),
+            listening_interface("0.2.0", &["types", "query", "transaction", "notifications"]),
+        ],
+    );
+    let workload_id = request.workload_id.clone();
+    let host = start_workload(&host_addr, request).await?;
+    notifications_are_delivered(host, workload_id, &admin).await
+}
+
+/// An entry's `listen_url` is where its listening connection goes, in place of
+/// the plugin's bouncer URL, which in transaction pooling mode would lose the
+/// `LISTEN`. The listener is told apart by the application name it connects as.
+#[tokio::test]
+#[ignore = "requires Docker (postgres); run with `cargo test --include-ignored`"]
+async fn listen_url_carries_the_listening_connection() -> Result<()> {
+    let (_container, host_addr, admin) = start_received().await?;
+    let mut interface = listening_interface("0.1.1-draft", &["types", "query", "notifications"]);
+    interface.config.insert(
+        "listen_url".to_string(),
+        format!(
+            "postgres://postgres:postgres@{host_addr}/postgres?application_name=direct-listener"
+        ),
+    );
+    let request = component_workload_request(
+        "postgres-notifications",
+        "postgres-notifications",
+        POSTGRES_NOTIFICATIONS_WASM,
+        LocalResources::default(),
+        vec![interface],
+    );
+    let _host = start_workload(&host_addr, request).await?;
+
+    eventually("the listener to connect", || async {
+        Ok(listening_backends(&admin).await? == 1)
+    })
+    .await?;
+    let application: String = admin
+        .query_one(
+            "SELECT application_name FROM pg_stat_activity \
+             WHERE query LIKE 'LISTEN %' AND pid <> pg_backend_pid()",
+            &[],
+        )
+        .await?
+        .get(0);
+    assert_eq!(application, "direct-listener");
+
+    admin.batch_execute("NOTIFY orders, 'direct'").await?;
+    eventually("delivery through the listen_url", || async {
+        Ok(received(&admin)
+            .await?
+            .iter()
+            .any(|(_, payload)| payload == "direct"))
+    })
+    .await
+}
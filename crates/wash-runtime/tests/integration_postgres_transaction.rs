@@ -0,0 +1,205 @@
+//! `wasmcloud:postgres` `transaction` resource e2e, for both the sync
+//! (`0.1.1-draft`) and async (`0.2.0`) bindings.
+//!
+//! The `postgres-transaction` (p2) fixture and the `/txn/` routes of
+//! `postgres-stream-p3` behave the same: each request inserts an id into
+//! `ledger` inside a serializable transaction and then commits it, rolls it
+//! back, drops it, or fails a statement and tries to commit. Only committed ids
+//! may land in the table.
+//!
+//! The plugin runs with `pool_size=1`, so every transaction reuses the same
+//! connection. A later commit seeing only the earlier committed rows proves a
+//! dropped or aborted transaction was rolled back before its connection went
+//! back to the pool, rather than leaking into the next `begin`.
+//!
+//! Requires Docker; marked `#[ignore]`, so it runs only under
+//! `cargo test --include-ignored` (CI's Linux leg) and not a plain `cargo test`.
+#![cfg(feature = "wasmcloud-postgres")]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::time::timeout;
+
+use wash_runtime::{
+    engine::Engine,
+    host::{
+        HostApi, HostBuilder,
+        http::{DevRouter, Ingress},
+    },
+    plugin::wasmcloud_postgres::WasmcloudPostgres,
+    types::{LocalResources, WorkloadState},
+    wit::WitInterface,
+};
+
+mod common;
+use common::postgres::{admin_client, start_postgres, start_postgres_workload_with_url};
+use common::{component_workload_request, http_incoming_handler_interface};
+
+const POSTGRES_TRANSACTION_WASM: &[u8] = include_bytes!("wasm/postgres_transaction.wasm");
+
+/// Start postgres and create the empty `ledger` table; returns the container
+/// (kept alive by the caller), the plugin URL capped at one pooled connection,
+/// and an admin client for checking what was committed.
+async fn start_ledger() -> Result<(
+    testcontainers::ContainerAsync<testcontainers::GenericImage>,
+    String,
+    tokio_postgres::Client,
+)> {
+    let (container, host_addr) = start_postgres().await?;
+    let admin = admin_client(&format!(
+        "postgres://postgres:postgres@{host_addr}/postgres"
+    ))
+    .await?;
+    admin
+        .batch_execute("CREATE TABLE ledger (id INT PRIMARY KEY)")
+        .await
+        .context("failed to create ledger table")?;
+    let url = format!("postgres://postgres:postgres@{host_addr}/?pool_size=1");
+    Ok((container, url, admin))
+}
+
+/// Start a host running the sync `postgres-transaction` fixture.
+async fn start_sync_workload(url: &str, host_header: &str) -> Result<(SocketAddr, impl HostApi)> {
+    let ingress = Ingress::new(DevRouter::default(), "127.0.0.1:0".parse()?).await?;
+    let addr = ingress.addr();
+    let host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress))
+        .with_plugin(Arc::new(WasmcloudPostgres::new(url)?))?
+        .build()?
+        .start()
+        .await?;
+
+    let postgres = WitInterface {
+        namespace: "wasmcloud".to_string(),
+        package: "postgres".to_string(),
+        interfaces: ["types".to_string(), "transaction".to_string()]
+            .into_iter()
+            .collect(),
+        version: Some(semver::Version::parse("0.1.1-draft").unwrap()),
+        config: HashMap::from([("database".to_string(), "postgres".to_string())]),
+        name: None,
+    };
+    let response = host
+        .workload_start(component_workload_request(
+            "postgres-transaction",
+            "postgres-transaction",
+            POSTGRES_TRANSACTION_WASM,
+            LocalResources::default(),
+            vec![http_incoming_handler_interface(host_header, None), postgres],
+        ))
+        .await?;
+    assert_eq!(
+        response.workload_status.workload_state,
+        WorkloadState::Running,
+        "workload should resolve: {}",
+        response.workload_status.message
+    );
+    Ok((addr, host))
+}
+
+/// GET `path` and return the response body.
+async fn get(addr: SocketAddr, host_header: &str, path: &str) -> Result<String> {
+    let response = timeout(
+        Duration::from_secs(15),
+        reqwest::Client::new()
+            .get(format!("http://{addr}{path}"))
+            .header("HOST", host_header)
+            .send(),
+    )
+    .await
+    .context("request timed out")?
+    .context("request failed")?;
+    let status = response.status();
+    let body = response.text().await?;
+    anyhow::ensure!(status.is_success(), "GET {path} returned {status}: {body}");
+    Ok(body)
+}
+
+/// Drive every way a transaction can end through the fixture routes under
+/// `prefix`, then check that exactly the committed ids reached `ledger`.
+async fn transactions_end_cleanly(
+    addr: SocketAddr,
+    host_header: &str,
+    prefix: &str,
+    admin: &tokio_postgres::Client,
+) -> Result<()> {
+    let get = |path: String| async move { get(addr, host_header, &path).await };
+
+    assert_eq!(
+        get(format!("{prefix}commit/1")).await?,
+        "committed isolation=serializable visible=1"
+    );
+    assert_eq!(get(format!("{prefix}rollback/2")).await?, "rolled-back");
+    assert_eq!(get(format!("{prefix}drop/3")).await?, "dropped");
+    assert_eq!(
+        get(format!("{prefix}aborted/4")).await?,
+        "commit-refused",
+        "a transaction with a failed statement must not report a successful commit"
+    );
+    assert_eq!(
+        get(format!("{prefix}commit/5")).await?,
+        "committed isolation=serializable visible=2",
+        "the next transaction on the pooled connection must not see earlier uncommitted work"
+    );
+
+    let ids: Vec<i32> = admin
+        .query("SELECT id FROM ledger ORDER BY id", &[])
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(ids, [1, 5], "only committed transactions may persist");
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (postgres); run with `cargo test --include-ignored`"]
+async fn sync_transactions_commit_roll_back_and_roll_back_on_drop() -> Result<()> {
+    let (_container, url, admin) = start_ledger().await?;
+    let (addr, _host) = start_sync_workload(&url, "pg-txn").await?;
+    transactions_end_cleanly(addr, "pg-txn", "/", &admin).await
+}
+
+#[tokio::test]
+#[ignore = "requires Docker (postgres); run with `cargo test --include-ignored`"]
+async fn async_transactions_commit_roll_back_and_roll_back_on_drop() -> Result<()> {
+    let (_container, url, admin) = start_ledger().await?;
+    let (addr, _host) = start_postgres_workload_with_url(&url, "pg-txn-p3").await?;
+    transactions_end_cleanly(addr, "pg-txn-p3", "/txn/", &admin).await
+}
+
+/// A component gets at most half the pool (here, its one connection) in open
+/// transactions: a nested `begin` is refused at once rather than waiting for a
+/// connection the component itself is holding, and the slot comes back once
+/// the transaction is gone.
+#[tokio::test]
+#[ignore = "requires Docker (postgres); run with `cargo test --include-ignored`"]
+async fn transactions_per_component_are_capped() -> Result<()> {
+    let (_container, url, admin) = start_ledger().await?;
+    let (addr, _host) = start_sync_workload(&url, "pg-txn-cap").await?;
+
+    let nested = get(addr, "pg-txn-cap", "/nested/1").await?;
+    assert!(
+        nested.starts_with("nested-refused") && nested.contains("too many open transactions"),
+        "unexpected response: {nested}"
+    );
+    assert_eq!(
+        get(addr, "pg-txn-cap", "/commit/2").await?,
+        "committed isolation=serializable visible=1"
+    );
+
+    let ids: Vec<i32> = admin
+        .query("SELECT id FROM ledger ORDER BY id", &[])
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(ids, [2]);
+    Ok(())
+}
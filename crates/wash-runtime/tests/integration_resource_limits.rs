@@ -0,0 +1,191 @@
+//! `LocalResources.memory_limit_mb` and `cpu_limit` enforcement.
+//!
+//! Every store built for a component carries a resource limiter sized from
+//! its memory limit. A component that fits keeps serving; one that grows past
+//! its limit traps, and the workload is failed with a resource-limit reason of
+//! its own rather than left running to trap on every request.
+//!
+//! A `cpu_limit` time-slices the guest: the `http-sleeper` fixture's `/spin`
+//! never returns to the host, yet with a limit set the other tasks on its
+//! (single-threaded) runtime keep being polled while it spins.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use wash_runtime::host::HostApi;
+use wash_runtime::types::{LocalResources, WorkloadState, WorkloadStatusRequest};
+
+mod common;
+use common::{
+    component_workload_request, http_only_host_interfaces, start_host_with_p3_http_handler,
+};
+
+const HTTP_HANDLER_P2_WASM: &[u8] = include_bytes!("wasm/http_handler_p2.wasm");
+const HTTP_SLEEPER_WASM: &[u8] = include_bytes!("wasm/http_sleeper.wasm");
+
+/// Ends the `/spin` calls, which would otherwise never return.
+const SPIN_TIME: Duration = Duration::from_millis(1_000);
+
+const SETTLE_POLLS: usize = 100;
+
+fn limited(memory_limit_mb: i32) -> LocalResources {
+    LocalResources {
+        memory_limit_mb,
+        ..Default::default()
+    }
+}
+
+fn client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .pool_max_idle_per_host(0)
+        .timeout(Duration::from_secs(15))
+        .build()?)
+}
+
+#[tokio::test]
+async fn a_component_within_its_memory_limit_serves() -> Result<()> {
+    let (addr, host) = start_host_with_p3_http_handler("127.0.0.1:0").await?;
+    let request = component_workload_request(
+        "handler",
+        "within-limit",
+        HTTP_HANDLER_P2_WASM,
+        limited(64),
+        http_only_host_interfaces("within-limit"),
+    );
+    host.workload_start(request)
+        .await
+        .context("workload should start")?;
+
+    let resp = client()?
+        .get(format!("http://{addr}/"))
+        .header("HOST", "within-limit")
+        .send()
+        .await?;
+    assert!(resp.status().is_success(), "status {}", resp.status());
+    Ok(())
+}
+
+/// A 1 MiB limit is below the initial linear memory of any Rust component (its
+/// stack alone is 1 MiB), so the first instantiation breaches it.
+#[tokio::test]
+async fn a_memory_limit_breach_fails_the_workload() -> Result<()> {
+    let (addr, host) = start_host_with_p3_http_handler("127.0.0.1:0").await?;
+    let request = component_workload_request(
+        "handler",
+        "over-limit",
+        HTTP_HANDLER_P2_WASM,
+        limited(1),
+        http_only_host_interfaces("over-limit"),
+    );
+    let workload_id = request.workload_id.clone();
+    // Starting may already instantiate (and breach); either way the workload
+    // must end up failed.
+    let _ = host.workload_start(request).await;
+
+    if let Ok(resp) = client()?
+        .get(format!("http://{addr}/"))
+        .header("HOST", "over-limit")
+        .send()
+        .await
+    {
+        assert!(
+            !resp.status().is_success(),
+            "a request that breaches the memory limit must fail"
+        );
+    }
+
+    let mut status = None;
+    for _ in 0..SETTLE_POLLS {
+        let current = host
+            .workload_status(WorkloadStatusRequest {
+                workload_id: workload_id.clone(),
+            })
+            .await?
+            .workload_status;
+        if current.workload_state == WorkloadState::Error {
+            status = Some(current);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    let status = status.expect("the breaching workload must be failed");
+    assert!(
+        status.message.contains("resource limit exceeded"),
+        "the failure must name the resource limit, got {:?}",
+        status.message
+    );
+    Ok(())
+}
+
+/// The longest the test's runtime went without polling a 5ms ticker while a
+/// `/spin` call ran on a component with `cpu_limit`.
+async fn longest_stall_while_spinning(cpu_limit: i32, host_header: &str) -> Result<Duration> {
+    let (addr, host) = start_host_with_p3_http_handler("127.0.0.1:0").await?;
+    let mut request = component_workload_request(
+        "sleeper",
+        host_header,
+        HTTP_SLEEPER_WASM,
+        LocalResources {
+            cpu_limit,
+            ..Default::default()
+        },
+        http_only_host_interfaces(host_header),
+    );
+    for component in &mut request.workload.components {
+        component.max_execution_time = Some(SPIN_TIME);
+    }
+    host.workload_start(request)
+        .await
+        .context("sleeper workload should start")?;
+
+    let spin = client()?
+        .get(format!("http://{addr}/spin"))
+        .header("HOST", host_header)
+        .send();
+    let mut longest = Duration::ZERO;
+    let ticker = async {
+        let mut interval = tokio::time::interval(Duration::from_millis(5));
+        let mut last = tokio::time::Instant::now();
+        loop {
+            interval.tick().await;
+            let now = tokio::time::Instant::now();
+            longest = longest.max(now - last);
+            last = now;
+        }
+    };
+    tokio::select! {
+        _ = spin => {}
+        _ = ticker => {}
+    }
+    Ok(longest)
+}
+
+/// The spinning guest shares a single thread with the ticker. Without a
+/// `cpu_limit` it holds the thread until its deadline traps it; with one it
+/// yields every tick of the engine's epoch. The runtime checks its timers on
+/// every scheduler tick, since by default a task that keeps yielding only lets
+/// them fire every 61st.
+#[test]
+fn a_cpu_limit_time_slices_a_spinning_guest() -> Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .event_interval(1)
+        .build()?;
+    runtime.block_on(async {
+        let unsliced = longest_stall_while_spinning(-1, "spin-unsliced").await?;
+        assert!(
+            unsliced >= SPIN_TIME / 2,
+            "without a cpu_limit the spin should hold the thread, longest stall {unsliced:?}"
+        );
+
+        let sliced = longest_stall_while_spinning(1, "spin-sliced").await?;
+        assert!(
+            sliced < SPIN_TIME / 4,
+            "with a cpu_limit the spin should yield every slice, longest stall {sliced:?}"
+        );
+        Ok(())
+    })
+}
@@ -53,6 +53,7 @@ fn http_handler_p2_request(host_header: &str) -> WorkloadStartRequest {
             volume_mounts: vec![],
             allowed_hosts: Default::default(),
             allowed_ip_name_lookups: Default::default(),
+            allowed_socket_addrs: Default::default(),
         },
         http_only_host_interfaces(host_header),
     )
@@ -113,8 +114,11 @@ async fn test_dev_router_last_workload_wins() -> Result<()> {
 
     // Stop B. DevRouter's only target is cleared so it does not fall back
     // to A (which is still registered). Requests will fail.
-    host.workload_stop(WorkloadStopRequest { workload_id: id_b })
-        .await?;
+    host.workload_stop(WorkloadStopRequest {
+        workload_id: id_b,
+        ..Default::default()
+    })
+    .await?;
     let status = get_status(&client, addr, "anything").await?;
     assert!(
         is_unrouted(status),
@@ -123,8 +127,11 @@ async fn test_dev_router_last_workload_wins() -> Result<()> {
     );
 
     // Clean up A.
-    host.workload_stop(WorkloadStopRequest { workload_id: id_a })
-        .await?;
+    host.workload_stop(WorkloadStopRequest {
+        workload_id: id_a,
+        ..Default::default()
+    })
+    .await?;
 
     Ok(())
 }
@@ -149,8 +156,11 @@ async fn test_dev_router_unbind_clears_only_matching() -> Result<()> {
 
     // Stopping A (non-current one) should not change the DevRouter mapping;
     // requests still route (to B).
-    host.workload_stop(WorkloadStopRequest { workload_id: id_a })
-        .await?;
+    host.workload_stop(WorkloadStopRequest {
+        workload_id: id_a,
+        ..Default::default()
+    })
+    .await?;
     assert!(
         get_status(&client, addr, "whatever").await?.is_success(),
         "stopping non-current workload must not unbind router"
@@ -158,8 +168,11 @@ async fn test_dev_router_unbind_clears_only_matching() -> Result<()> {
 
     // Stopping B clears the binding so subsequent requests are rejected by
     // the router (404 Not Found via RouteError::NoWorkloadForHost).
-    host.workload_stop(WorkloadStopRequest { workload_id: id_b })
-        .await?;
+    host.workload_stop(WorkloadStopRequest {
+        workload_id: id_b,
+        ..Default::default()
+    })
+    .await?;
     let status = get_status(&client, addr, "whatever").await?;
     assert!(
         is_unrouted(status),
@@ -202,8 +215,11 @@ async fn test_dev_router_concurrent_reads_during_swap() -> Result<()> {
 
     // Midway through the staggered window, swap A -> B on the main task.
     tokio::time::sleep(Duration::from_millis(40)).await;
-    host.workload_stop(WorkloadStopRequest { workload_id: id_a })
-        .await?;
+    host.workload_stop(WorkloadStopRequest {
+        workload_id: id_a,
+        ..Default::default()
+    })
+    .await?;
     let req_b = http_counter_request("b");
     host.workload_start(req_b).await?;
 
@@ -209,8 +209,11 @@ async fn test_dynamic_router_routes_race_with_unbind() -> Result<()> {
 
     // Give some requests a chance to get in-flight, then stop the workload.
     tokio::time::sleep(Duration::from_millis(20)).await;
-    host.workload_stop(WorkloadStopRequest { workload_id })
-        .await?;
+    host.workload_stop(WorkloadStopRequest {
+        workload_id,
+        ..Default::default()
+    })
+    .await?;
 
     // Every task must resolve (success or graceful status), none may hang.
     let resolved = join_all(handles)
@@ -316,6 +319,7 @@ async fn test_multiple_workloads_same_host_both_serve() -> Result<()> {
     // Stop one workload — the other must keep the host alive.
     host.workload_stop(wash_runtime::types::WorkloadStopRequest {
         workload_id: workload_id_a,
+        ..Default::default()
     })
     .await?;
 
@@ -385,8 +389,11 @@ async fn test_dynamic_router_aliases_removed_on_unbind() -> Result<()> {
         );
     }
 
-    host.workload_stop(WorkloadStopRequest { workload_id })
-        .await?;
+    host.workload_stop(WorkloadStopRequest {
+        workload_id,
+        ..Default::default()
+    })
+    .await?;
 
     // After unbind, every former route — primary and aliases — must 404.
     for hostname in std::iter::once(primary).chain(aliases) {
@@ -0,0 +1,153 @@
+//! Integration tests for `DynamicRouter` route rules: several workloads
+//! behind one hostname, split by path prefix or by request header.
+//!
+//! Each workload is an `http-sleeper` with a pool of one, whose reply counts
+//! the requests its instance has served. A reply's count therefore tells
+//! which workload answered: the first request a workload sees is served `1`,
+//! whatever its neighbours have already served.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use anyhow::{Context, Result};
+
+use wash_runtime::host::HostApi;
+use wash_runtime::types::{
+    Component, LocalResources, Workload, WorkloadStartRequest, WorkloadState, WorkloadStopRequest,
+};
+
+mod common;
+use common::{http_incoming_handler_interface, start_host_with_dynamic_router};
+
+const HTTP_SLEEPER_WASM: &[u8] = include_bytes!("wasm/http_sleeper.wasm");
+
+/// A sleeper workload on `host_header`, routed by `rule` (extra
+/// incoming-handler config, e.g. `path-prefix`).
+fn routed_workload(host_header: &str, rule: &[(&str, &str)]) -> WorkloadStartRequest {
+    let mut http = http_incoming_handler_interface(host_header, None);
+    http.config.extend(
+        rule.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+    );
+    WorkloadStartRequest {
+        workload_id: uuid::Uuid::new_v4().to_string(),
+        workload: Workload {
+            namespace: "test".to_string(),
+            name: host_header.to_string(),
+            annotations: HashMap::new(),
+            service: None,
+            components: vec![Component {
+                name: "sleeper".to_string(),
+                digest: None,
+                bytes: bytes::Bytes::from_static(HTTP_SLEEPER_WASM),
+                local_resources: LocalResources::default(),
+                pool_size: 1,
+                max_invocations: 0,
+                max_concurrency: 0,
+                ..Default::default()
+            }],
+            host_interfaces: vec![http],
+            volumes: vec![],
+        },
+    }
+}
+
+/// GET `path` on `host` and return how many requests the answering
+/// instance has served, this one included.
+async fn served(
+    client: &reqwest::Client,
+    addr: SocketAddr,
+    host: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+) -> Result<u64> {
+    let mut request = client
+        .get(format!("http://{addr}{path}"))
+        .header("HOST", host);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let response = request.send().await?;
+    let status = response.status();
+    let body = response.text().await?;
+    anyhow::ensure!(status.is_success(), "{path}: {status} {body}");
+    let reply: serde_json::Value = serde_json::from_str(&body)?;
+    reply
+        .get("served")
+        .and_then(serde_json::Value::as_u64)
+        .context("reply has no served count")
+}
+
+#[tokio::test]
+async fn path_prefixes_split_one_hostname() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    let client = reqwest::Client::new();
+
+    host.workload_start(routed_workload("shop.local", &[("path-prefix", "/api")]))
+        .await?;
+    host.workload_start(routed_workload("shop.local", &[]))
+        .await?;
+
+    assert_eq!(
+        served(&client, addr, "shop.local", "/api/users", &[]).await?,
+        1
+    );
+    assert_eq!(served(&client, addr, "shop.local", "/api", &[]).await?, 2);
+    // Neither the root nor a path that merely shares the prefix's letters
+    // falls under `/api`.
+    assert_eq!(served(&client, addr, "shop.local", "/", &[]).await?, 1);
+    assert_eq!(
+        served(&client, addr, "shop.local", "/apiary", &[]).await?,
+        2
+    );
+    assert_eq!(
+        served(&client, addr, "shop.local", "/api/orders", &[]).await?,
+        3
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_header_routes_to_the_canary() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    let client = reqwest::Client::new();
+
+    host.workload_start(routed_workload("svc.local", &[]))
+        .await?;
+    let canary = routed_workload("svc.local", &[("match-headers", "x-canary=1")]);
+    let canary_id = canary.workload_id.clone();
+    host.workload_start(canary).await?;
+
+    let flagged = [("x-canary", "1")];
+    assert_eq!(served(&client, addr, "svc.local", "/", &flagged).await?, 1);
+    assert_eq!(served(&client, addr, "svc.local", "/", &flagged).await?, 2);
+    assert_eq!(served(&client, addr, "svc.local", "/", &[]).await?, 1);
+    assert_eq!(
+        served(&client, addr, "svc.local", "/", &[("x-canary", "0")]).await?,
+        2
+    );
+
+    // With the canary gone its requests fall back to the stable workload.
+    host.workload_stop(WorkloadStopRequest {
+        workload_id: canary_id,
+        ..Default::default()
+    })
+    .await?;
+    assert_eq!(served(&client, addr, "svc.local", "/", &flagged).await?, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_malformed_rule_fails_the_workload() -> Result<()> {
+    let (_addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+
+    let started = host
+        .workload_start(routed_workload("bad.local", &[("path-prefix", "api")]))
+        .await?;
+    let status = started.workload_status;
+    assert_eq!(status.workload_state, WorkloadState::Error);
+    assert!(status.message.contains("route rule"), "{}", status.message);
+    Ok(())
+}
@@ -0,0 +1,554 @@
+//! `wasi:blobstore` over S3, against an in-process stand-in for an
+//! S3-compatible service.
+//!
+//! The stand-in keeps buckets in memory and speaks just enough of the S3 API
+//! for the plugin: bucket and object CRUD, ranged reads, server-side copy,
+//! `ListObjectsV2` paged two keys at a time and multipart uploads. It doesn't
+//! check signatures, only that every request carries one, and records the
+//! access key it was made with.
+
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::indexing_slicing)]
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use bytes::Bytes;
+use http_body_util::{BodyExt as _, Full};
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+
+use wash_runtime::{
+    engine::Engine,
+    host::{
+        HostApi, HostBuilder,
+        http::{DevRouter, Ingress},
+    },
+    plugin::wasi_blobstore::S3Blobstore,
+    types::{LocalResources, WorkloadState},
+    wit::WitInterface,
+};
+
+mod common;
+use common::{component_workload_request, http_incoming_handler_interface};
+
+const HTTP_BLOBSTORE_WASM: &[u8] = include_bytes!("wasm/http_blobstore.wasm");
+
+/// The container the `http-blobstore` fixture writes to.
+const FIXTURE_CONTAINER: &str = "my-container-real";
+
+const ACCESS_KEY: &str = "AKIDSTANDIN";
+
+/// Smallest part size the plugin accepts.
+const PART_SIZE: usize = 5 * 1024 * 1024;
+
+#[derive(Default)]
+struct Upload {
+    bucket: String,
+    key: String,
+    parts: BTreeMap<u16, Vec<u8>>,
+}
+
+#[derive(Default)]
+struct State {
+    buckets: HashMap<String, BTreeMap<String, Vec<u8>>>,
+    uploads: HashMap<String, Upload>,
+    /// Part counts of completed multipart uploads, in order.
+    completed_uploads: Vec<usize>,
+    /// The access key of every request, `None` if it was unsigned.
+    access_keys: Vec<Option<String>>,
+}
+
+#[derive(Clone, Default)]
+struct StandIn(Arc<Mutex<State>>);
+
+impl StandIn {
+    async fn start() -> Result<(SocketAddr, Self)> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let stand_in = Self::default();
+        let server = stand_in.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let server = server.clone();
+                tokio::spawn(async move {
+                    let service = hyper::service::service_fn(move |request| {
+                        let server = server.clone();
+                        async move { Ok::<_, Infallible>(server.handle(request).await) }
+                    });
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+        Ok((addr, stand_in))
+    }
+
+    fn object(&self, bucket: &str, key: &str) -> Option<Vec<u8>> {
+        self.0
+            .lock()
+            .unwrap()
+            .buckets
+            .get(bucket)?
+            .get(key)
+            .cloned()
+    }
+
+    async fn handle(&self, request: Request<hyper::body::Incoming>) -> Response<Full<Bytes>> {
+        let (parts, body) = request.into_parts();
+        let body = body.collect().await.unwrap().to_bytes().to_vec();
+        let query: HashMap<String, String> = parts
+            .uri
+            .query()
+            .map(|q| {
+                url::form_urlencoded::parse(q.as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let path = percent_encoding::percent_decode_str(parts.uri.path())
+            .decode_utf8()
+            .unwrap()
+            .into_owned();
+        let (bucket, key) = match path.trim_start_matches('/').split_once('/') {
+            Some((bucket, "")) => (bucket.to_string(), None),
+            Some((bucket, key)) => (bucket.to_string(), Some(key.to_string())),
+            None => (path.trim_start_matches('/').to_string(), None),
+        };
+        let header = |name: &str| {
+            parts
+                .headers
+                .get(name)
+                .map(|value| value.to_str().unwrap().to_string())
+        };
+
+        let mut state = self.0.lock().unwrap();
+        state.access_keys.push(
+            query
+                .get("X-Amz-Credential")
+                .filter(|_| query.contains_key("X-Amz-Signature"))
+                .map(|credential| credential.split('/').next().unwrap().to_string()),
+        );
+
+        let Some(key) = key else {
+            return match parts.method {
+                Method::PUT if state.buckets.contains_key(&bucket) => {
+                    error(StatusCode::CONFLICT, "BucketAlreadyOwnedByYou")
+                }
+                Method::PUT => {
+                    state.buckets.insert(bucket, BTreeMap::new());
+                    ok(Vec::new())
+                }
+                _ if !state.buckets.contains_key(&bucket) => {
+                    error(StatusCode::NOT_FOUND, "NoSuchBucket")
+                }
+                Method::HEAD => ok(Vec::new()),
+                Method::DELETE if !state.buckets[&bucket].is_empty() => {
+                    error(StatusCode::CONFLICT, "BucketNotEmpty")
+                }
+                Method::DELETE => {
+                    state.buckets.remove(&bucket);
+                    status(StatusCode::NO_CONTENT)
+                }
+                Method::GET => list(&state.buckets[&bucket], query.get("continuation-token")),
+                _ => error(StatusCode::METHOD_NOT_ALLOWED, "MethodNotAllowed"),
+            };
+        };
+
+        if let Some(upload_id) = query.get("uploadId") {
+            return match parts.method {
+                Method::PUT => {
+                    let part_number = query["partNumber"].parse().unwrap();
+                    let Some(upload) = state.uploads.get_mut(upload_id) else {
+                        return error(StatusCode::NOT_FOUND, "NoSuchUpload");
+                    };
+                    upload.parts.insert(part_number, body);
+                    let mut response = ok(Vec::new());
+                    response
+                        .headers_mut()
+                        .insert("etag", format!("\"part-{part_number}\"").parse().unwrap());
+                    response
+                }
+                Method::POST => {
+                    let Some(upload) = state.uploads.remove(upload_id) else {
+                        return error(StatusCode::NOT_FOUND, "NoSuchUpload");
+                    };
+                    let listed = String::from_utf8(body).unwrap().matches("<Part>").count();
+                    if listed != upload.parts.len() {
+                        // S3 reports a bad part list in a 200.
+                        return ok(b"<Error><Code>InvalidPart</Code></Error>".to_vec());
+                    }
+                    state.completed_uploads.push(upload.parts.len());
+                    let object = upload.parts.into_values().flatten().collect();
+                    state
+                        .buckets
+                        .get_mut(&upload.bucket)
+                        .unwrap()
+                        .insert(upload.key, object);
+                    ok(b"<CompleteMultipartUploadResult/>".to_vec())
+                }
+                Method::DELETE => {
+                    state.uploads.remove(upload_id);
+                    status(StatusCode::NO_CONTENT)
+                }
+                _ => error(StatusCode::METHOD_NOT_ALLOWED, "MethodNotAllowed"),
+            };
+        }
+
+        let source = header("x-amz-copy-source").map(|source| {
+            percent_encoding::percent_decode_str(&source)
+                .decode_utf8()
+                .unwrap()
+                .into_owned()
+        });
+        let copied = source.as_ref().map(|source| {
+            let (src_bucket, src_key) = source.trim_start_matches('/').split_once('/').unwrap();
+            state
+                .buckets
+                .get(src_bucket)
+                .and_then(|objects| objects.get(src_key))
+                .cloned()
+        });
+        let Some(objects) = state.buckets.get_mut(&bucket) else {
+            return error(StatusCode::NOT_FOUND, "NoSuchBucket");
+        };
+
+        match parts.method {
+            Method::POST if query.contains_key("uploads") => {
+                let upload_id = uuid::Uuid::new_v4().to_string();
+                state.uploads.insert(
+                    upload_id.clone(),
+                    Upload {
+                        bucket,
+                        key,
+                        ..Default::default()
+                    },
+                );
+                ok(format!(
+                    "<InitiateMultipartUploadResult xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+                     <UploadId>{upload_id}</UploadId></InitiateMultipartUploadResult>"
+                )
+                .into_bytes())
+            }
+            Method::PUT => match copied {
+                Some(None) => error(StatusCode::NOT_FOUND, "NoSuchKey"),
+                Some(Some(object)) => {
+                    objects.insert(key, object);
+                    ok(b"<CopyObjectResult/>".to_vec())
+                }
+                None => {
+                    objects.insert(key, body);
+                    ok(Vec::new())
+                }
+            },
+            Method::DELETE => {
+                objects.remove(&key);
+                status(StatusCode::NO_CONTENT)
+            }
+            Method::HEAD | Method::GET => {
+                let Some(object) = objects.get(&key) else {
+                    return if parts.method == Method::HEAD {
+                        status(StatusCode::NOT_FOUND)
+                    } else {
+                        error(StatusCode::NOT_FOUND, "NoSuchKey")
+                    };
+                };
+                if parts.method == Method::HEAD {
+                    let mut response = ok(Vec::new());
+                    let headers = response.headers_mut();
+                    headers.insert("content-length", object.len().into());
+                    headers.insert(
+                        "last-modified",
+                        "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+                    );
+                    return response;
+                }
+                let Some(range) = header("range") else {
+                    return ok(object.clone());
+                };
+                let (start, end) = range
+                    .strip_prefix("bytes=")
+                    .unwrap()
+                    .split_once('-')
+                    .unwrap();
+                let start: usize = start.parse().unwrap();
+                if start >= object.len() {
+                    return error(StatusCode::RANGE_NOT_SATISFIABLE, "InvalidRange");
+                }
+                let end = end
+                    .parse::<usize>()
+                    .map_or(object.len(), |end| (end + 1).min(object.len()));
+                let mut response = ok(object[start..end].to_vec());
+                *response.status_mut() = StatusCode::PARTIAL_CONTENT;
+                response
+            }
+            _ => error(StatusCode::METHOD_NOT_ALLOWED, "MethodNotAllowed"),
+        }
+    }
+}
+
+/// One page of `ListObjectsV2`, two keys long, continuing after the key named
+/// by `token`.
+fn list(objects: &BTreeMap<String, Vec<u8>>, token: Option<&String>) -> Response<Full<Bytes>> {
+    let mut keys = objects
+        .iter()
+        .filter(|(key, _)| token.is_none_or(|token| *key > token))
+        .peekable();
+    let mut body = String::from(
+        "<ListBucketResult xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\"><MaxKeys>2</MaxKeys>",
+    );
+    let mut last = None;
+    for (key, object) in keys.by_ref().take(2) {
+        body.push_str(&format!(
+            "<Contents><Key>{key}</Key><LastModified>2015-10-21T07:28:00.000Z</LastModified>\
+             <ETag>\"etag\"</ETag><Size>{}</Size></Contents>",
+            object.len()
+        ));
+        last = Some(key);
+    }
+    if keys.peek().is_some() {
+        body.push_str(&format!(
+            "<NextContinuationToken>{}</NextContinuationToken>",
+            last.unwrap()
+        ));
+    }
+    body.push_str("</ListBucketResult>");
+    ok(body.into_bytes())
+}
+
+fn ok(body: Vec<u8>) -> Response<Full<Bytes>> {
+    Response::new(Full::new(Bytes::from(body)))
+}
+
+fn status(status: StatusCode) -> Response<Full<Bytes>> {
+    let mut response = ok(Vec::new());
+    *response.status_mut() = status;
+    response
+}
+
+fn error(status: StatusCode, code: &str) -> Response<Full<Bytes>> {
+    let mut response =
+        ok(format!("<Error><Code>{code}</Code><Message>{code}</Message></Error>").into_bytes());
+    *response.status_mut() = status;
+    response
+}
+
+/// Config pointing a blobstore interface at the stand-in.
+fn s3_config(addr: SocketAddr) -> HashMap<String, String> {
+    HashMap::from([
+        ("endpoint".to_string(), format!("http://{addr}")),
+        ("bucket_prefix".to_string(), "tenant-".to_string()),
+        ("access_key_id".to_string(), ACCESS_KEY.to_string()),
+        (
+            "secret_access_key".to_string(),
+            "stand-in-secret".to_string(),
+        ),
+        ("part_size".to_string(), PART_SIZE.to_string()),
+    ])
+}
+
+async fn start_host(config: HashMap<String, String>) -> Result<(SocketAddr, impl HostApi)> {
+    let ingress = Ingress::new(DevRouter::default(), "127.0.0.1:0".parse()?).await?;
+    let addr = ingress.addr();
+    let host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress))
+        .with_plugin(Arc::new(S3Blobstore::new()))?
+        .build()?
+        .start()
+        .await?;
+
+    let blobstore = WitInterface {
+        namespace: "wasi".to_string(),
+        package: "blobstore".to_string(),
+        interfaces: ["blobstore", "container", "types"]
+            .map(String::from)
+            .into_iter()
+            .collect(),
+        version: Some(semver::Version::parse("0.2.0-draft")?),
+        config,
+        name: None,
+    };
+    let response = host
+        .workload_start(component_workload_request(
+            "http-blobstore-component",
+            "s3-blobstore",
+            HTTP_BLOBSTORE_WASM,
+            LocalResources::default(),
+            vec![http_incoming_handler_interface("foo", None), blobstore],
+        ))
+        .await?;
+    anyhow::ensure!(
+        response.workload_status.workload_state == WorkloadState::Running,
+        "{}",
+        response.workload_status.message
+    );
+    Ok((addr, host))
+}
+
+/// Have the fixture write `body` and stream it back.
+async fn round_trip(addr: SocketAddr, body: Vec<u8>) -> Result<(StatusCode, Bytes)> {
+    let response = reqwest::Client::new()
+        .post(format!("http://{addr}/"))
+        .header("HOST", "foo")
+        .body(body)
+        .send()
+        .await?;
+    let status = StatusCode::from_u16(response.status().as_u16())?;
+    Ok((status, response.bytes().await?))
+}
+
+#[tokio::test]
+async fn objects_round_trip_through_s3() -> Result<()> {
+    let (s3_addr, s3) = StandIn::start().await?;
+    let (addr, _host) = start_host(s3_config(s3_addr)).await?;
+    let bucket = format!("tenant-{FIXTURE_CONTAINER}");
+
+    let small = b"Hello from the S3 blobstore".to_vec();
+    let (status, body) = round_trip(addr, small.clone()).await?;
+    assert_eq!(status, StatusCode::OK, "{body:?}");
+    assert_eq!(body, small);
+    assert_eq!(s3.object(&bucket, "hi.txt"), Some(small));
+    assert!(s3.0.lock().unwrap().completed_uploads.is_empty());
+
+    // Two full parts and a short last one.
+    let large: Vec<u8> = (0..PART_SIZE * 2 + 1234).map(|i| (i % 251) as u8).collect();
+    let (status, body) = round_trip(addr, large.clone()).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body == large, "large object came back different");
+    assert_eq!(
+        s3.object(&bucket, "hi.txt").map(|o| o.len()),
+        Some(large.len())
+    );
+
+    let state = s3.0.lock().unwrap();
+    assert_eq!(state.completed_uploads, vec![3]);
+    assert!(state.uploads.is_empty());
+    assert!(
+        state
+            .access_keys
+            .iter()
+            .all(|key| key.as_deref() == Some(ACCESS_KEY)),
+        "every request is signed with the configured key: {:?}",
+        state.access_keys
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn containers_outside_buckets_are_refused() -> Result<()> {
+    let (s3_addr, s3) = StandIn::start().await?;
+    let mut config = s3_config(s3_addr);
+    config.insert("buckets".to_string(), "somewhere-else".to_string());
+    let (addr, _host) = start_host(config).await?;
+
+    let (status, body) = round_trip(addr, b"nope".to_vec()).await?;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(
+        String::from_utf8_lossy(&body).contains("unauthorized"),
+        "{body:?}"
+    );
+    assert!(s3.0.lock().unwrap().buckets.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_config_fails_the_workload() -> Result<()> {
+    let mut config = s3_config("127.0.0.1:9".parse()?);
+    config.remove("secret_access_key");
+    let Err(e) = start_host(config).await else {
+        panic!("a workload with half its credentials started");
+    };
+    assert!(format!("{e:#}").contains("S3 configuration"), "{e:#}");
+    Ok(())
+}
+
+#[cfg(feature = "wasm_component_model_implements")]
+#[tokio::test]
+async fn multiplexed_backend_covers_the_container_api() -> Result<()> {
+    use wash_runtime::plugin::multiplex::BackendProvider as _;
+    use wash_runtime::plugin::wasi_blobstore::{BlobBackendError, S3BlobProvider};
+
+    let (s3_addr, s3) = StandIn::start().await?;
+    let backend = S3BlobProvider::default()
+        .instantiate(&s3_config(s3_addr))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        backend.get_container("media").await,
+        Err(BlobBackendError::NoSuchContainer(_))
+    ));
+    backend.create_container("media").await.unwrap();
+    assert!(matches!(
+        backend.create_container("media").await,
+        Err(BlobBackendError::ContainerAlreadyExists(_))
+    ));
+    assert!(backend.container_exists("media").await.unwrap());
+
+    for (name, data) in [
+        ("a", "alpha"),
+        ("b", "bravo"),
+        ("c d/e", "charlie"),
+        ("f", ""),
+    ] {
+        backend
+            .write_data("media", name, data.as_bytes().to_vec())
+            .await
+            .unwrap();
+    }
+    // Three pages of the stand-in's two keys.
+    assert_eq!(
+        backend.list_objects("media").await.unwrap(),
+        vec!["a", "b", "c d/e", "f"]
+    );
+    assert_eq!(backend.get_data("media", "b", 1, 3).await.unwrap(), b"rav");
+    assert_eq!(
+        backend.get_data("media", "b", 2, u64::MAX).await.unwrap(),
+        b"avo"
+    );
+    assert_eq!(backend.get_data("media", "b", 10, 20).await.unwrap(), b"");
+    assert_eq!(
+        backend.get_data("media", "f", 0, u64::MAX).await.unwrap(),
+        b""
+    );
+    assert!(matches!(
+        backend.get_data("media", "zulu", 0, u64::MAX).await,
+        Err(BlobBackendError::NoSuchObject(_))
+    ));
+
+    let info = backend.object_info("media", "a").await.unwrap();
+    assert_eq!((info.size, info.created_at), (5, 1_445_412_480));
+    assert!(!backend.has_object("media", "zulu").await.unwrap());
+    assert!(matches!(
+        backend.has_object("nowhere", "a").await,
+        Err(BlobBackendError::NoSuchContainer(_))
+    ));
+
+    backend.create_container("archive").await.unwrap();
+    backend
+        .move_object("media", "c d/e", "archive", "c")
+        .await
+        .unwrap();
+    assert_eq!(
+        backend.get_data("archive", "c", 0, u64::MAX).await.unwrap(),
+        b"charlie"
+    );
+    assert!(!backend.has_object("media", "c d/e").await.unwrap());
+
+    backend
+        .delete_objects("media", &["a".to_string(), "never".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(backend.list_objects("media").await.unwrap(), vec!["b", "f"]);
+
+    // Not empty, so it's emptied first.
+    backend.delete_container("media").await.unwrap();
+    backend.delete_container("media").await.unwrap();
+    assert!(!backend.container_exists("media").await.unwrap());
+    assert!(s3.0.lock().unwrap().buckets.contains_key("tenant-archive"));
+    Ok(())
+}
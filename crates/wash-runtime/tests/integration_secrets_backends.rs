@@ -0,0 +1,300 @@
+//! `wasmcloud:secrets` resolved through an external backend at `get` time.
+//!
+//! The `secrets-caller` fixture's bind-time config maps each key it may read
+//! to a reference into the backend. The Vault tests run against an in-process
+//! stand-in for a KV v2 engine that records the token each request carried;
+//! the NATS test needs Docker and is marked `#[ignore]` (run with
+//! `cargo test --include-ignored`).
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+
+use wash_runtime::{
+    engine::Engine,
+    host::{
+        HostApi, HostBuilder,
+        http::{DynamicRouter, Ingress},
+    },
+    plugin::wasmcloud_secrets::{NatsKvBackend, SecretBackend, VaultBackend, WasmcloudSecrets},
+    types::{LocalResources, WorkloadState},
+};
+
+mod common;
+use common::{component_workload_request, req, secrets_caller_host_interfaces_with_config};
+
+const SECRETS_CALLER_WASM: &[u8] = include_bytes!("wasm/secrets_caller.wasm");
+
+const VAULT_TOKEN: &str = "s.stand-in-token";
+
+#[derive(Default)]
+struct State {
+    /// The data of the latest version of each secret, by path under `kv/`.
+    secrets: HashMap<String, serde_json::Value>,
+    /// The `X-Vault-Token` of every request.
+    tokens: Vec<Option<String>>,
+}
+
+/// Just enough of Vault's KV v2 read API, for an engine mounted at `kv`.
+#[derive(Clone, Default)]
+struct VaultStandIn(Arc<Mutex<State>>);
+
+impl VaultStandIn {
+    async fn start() -> Result<(SocketAddr, Self)> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let stand_in = Self::default();
+        let server = stand_in.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let server = server.clone();
+                tokio::spawn(async move {
+                    let service = hyper::service::service_fn(move |request| {
+                        let server = server.clone();
+                        async move { Ok::<_, Infallible>(server.handle(request)) }
+                    });
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+        Ok((addr, stand_in))
+    }
+
+    fn write(&self, path: &str, data: serde_json::Value) {
+        self.0
+            .lock()
+            .unwrap()
+            .secrets
+            .insert(path.to_string(), data);
+    }
+
+    fn handle(&self, request: Request<hyper::body::Incoming>) -> Response<Full<Bytes>> {
+        let mut state = self.0.lock().unwrap();
+        state.tokens.push(
+            request
+                .headers()
+                .get("x-vault-token")
+                .map(|t| t.to_str().unwrap().to_string()),
+        );
+        let data = request
+            .uri()
+            .path()
+            .strip_prefix("/v1/kv/data/")
+            .and_then(|path| state.secrets.get(path));
+        let (status, body) = match data {
+            Some(data) => (
+                StatusCode::OK,
+                serde_json::json!({
+                    "data": { "data": data, "metadata": { "version": 1 } }
+                }),
+            ),
+            None => (StatusCode::NOT_FOUND, serde_json::json!({ "errors": [] })),
+        };
+        let mut response = Response::new(Full::new(Bytes::from(body.to_string())));
+        *response.status_mut() = status;
+        response
+    }
+}
+
+/// Start a host whose secrets plugin resolves through `backend`, and deploy a
+/// `secrets-caller` workload for each `(host, config)`.
+///
+/// Tests using it are multi-threaded: the by-host router uses `block_in_place`.
+async fn start_host(
+    backend: impl SecretBackend,
+    cache_ttl: Duration,
+    workloads: &[(&str, &[(&str, &str)])],
+) -> Result<(SocketAddr, impl HostApi)> {
+    let ingress = Ingress::new(DynamicRouter::default(), "127.0.0.1:0".parse()?).await?;
+    let addr = ingress.addr();
+    let host = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress))
+        .with_plugin(Arc::new(WasmcloudSecrets::with_backend(backend, cache_ttl)))?
+        .build()?
+        .start()
+        .await?;
+    for (host_header, config) in workloads {
+        let config = config
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let response = host
+            .workload_start(component_workload_request(
+                "secrets-caller",
+                host_header,
+                SECRETS_CALLER_WASM,
+                LocalResources::default(),
+                secrets_caller_host_interfaces_with_config(host_header, config),
+            ))
+            .await?;
+        anyhow::ensure!(
+            response.workload_status.workload_state == WorkloadState::Running,
+            "{}",
+            response.workload_status.message
+        );
+    }
+    Ok((addr, host))
+}
+
+/// A value rotated in Vault reaches the running component once the cached
+/// copy expires, without redeploying it.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn vault_secrets_are_read_at_get_time() -> Result<()> {
+    let (vault_addr, vault) = VaultStandIn::start().await?;
+    vault.write("apps/orders/db", serde_json::json!({ "password": "first" }));
+    vault.write("apps/api", serde_json::json!({ "value": "api-secret" }));
+
+    let backend = VaultBackend::new(&format!("http://{vault_addr}"), VAULT_TOKEN)?.with_mount("kv");
+    let (addr, _host) = start_host(
+        backend,
+        Duration::from_secs(2),
+        &[(
+            "vault-rotate",
+            &[
+                ("db-password", "apps/orders/db#password"),
+                ("api-key", "apps/api"),
+                ("gone", "apps/missing"),
+            ],
+        )],
+    )
+    .await?;
+    let client = reqwest::Client::new();
+
+    let (status, body) = req(&client, &addr, "vault-rotate", "/get?key=db-password").await?;
+    assert_eq!((status.as_u16(), body.as_str()), (200, "first"));
+    let (status, body) = req(&client, &addr, "vault-rotate", "/get?key=api-key").await?;
+    assert_eq!(
+        (status.as_u16(), body.as_str()),
+        (200, "api-secret"),
+        "a reference without a field reads `value`"
+    );
+    let (status, _) = req(&client, &addr, "vault-rotate", "/get?key=gone").await?;
+    assert_eq!(status.as_u16(), 404, "a reference Vault doesn't have");
+
+    vault.write(
+        "apps/orders/db",
+        serde_json::json!({ "password": "second" }),
+    );
+    let (_, body) = req(&client, &addr, "vault-rotate", "/get?key=db-password").await?;
+    assert_eq!(body, "first", "served from the cache until it expires");
+
+    tokio::time::sleep(Duration::from_millis(2100)).await;
+    let (status, body) = req(&client, &addr, "vault-rotate", "/get?key=db-password").await?;
+    assert_eq!((status.as_u16(), body.as_str()), (200, "second"));
+
+    let tokens = vault.0.lock().unwrap().tokens.clone();
+    assert!(!tokens.is_empty());
+    assert!(
+        tokens.iter().all(|t| t.as_deref() == Some(VAULT_TOKEN)),
+        "every request is authenticated: {tokens:?}"
+    );
+    Ok(())
+}
+
+/// A component can only read the keys its own bind-time config maps, even
+/// when the backend holds the secret another workload is allowed to read.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn vault_secrets_stay_scoped_to_the_binding_component() -> Result<()> {
+    let (vault_addr, vault) = VaultStandIn::start().await?;
+    vault.write(
+        "apps/orders/db",
+        serde_json::json!({ "password": "orders" }),
+    );
+
+    let backend = VaultBackend::new(&format!("http://{vault_addr}"), VAULT_TOKEN)?.with_mount("kv");
+    let (addr, _host) = start_host(
+        backend,
+        Duration::ZERO,
+        &[
+            (
+                "vault-orders",
+                &[("db-password", "apps/orders/db#password")],
+            ),
+            ("vault-other", &[("unrelated", "apps/other")]),
+        ],
+    )
+    .await?;
+    let client = reqwest::Client::new();
+
+    let (status, body) = req(&client, &addr, "vault-orders", "/get?key=db-password").await?;
+    assert_eq!((status.as_u16(), body.as_str()), (200, "orders"));
+
+    let requests = vault.0.lock().unwrap().tokens.len();
+    for key in ["db-password", "apps/orders/db#password"] {
+        let (status, _) = req(&client, &addr, "vault-other", &format!("/get?key={key}")).await?;
+        assert_eq!(status.as_u16(), 404, "{key:?} isn't bound for vault-other");
+    }
+    assert_eq!(
+        vault.0.lock().unwrap().tokens.len(),
+        requests,
+        "an unbound key never reaches Vault"
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore = "requires Docker (NATS); run with `cargo test --include-ignored`"]
+async fn nats_secrets_are_decrypted_at_get_time() -> Result<()> {
+    use testcontainers::{
+        GenericImage, ImageExt,
+        core::{IntoContainerPort, WaitFor},
+        runners::AsyncRunner,
+    };
+
+    let nats = GenericImage::new("nats", "2.12.8-alpine")
+        .with_exposed_port(4222.tcp())
+        .with_wait_for(WaitFor::message_on_stderr("Server is ready"))
+        .with_cmd(["-js"])
+        .start()
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to start NATS: {e}"))?;
+    let client = async_nats::connect(format!(
+        "nats://127.0.0.1:{}",
+        nats.get_host_port_ipv4(4222).await?
+    ))
+    .await?;
+
+    let key = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+    let writer = NatsKvBackend::new(&client, "secrets", key).await?;
+    writer.put("orders-db", "first").await?;
+
+    let (addr, _host) = start_host(
+        NatsKvBackend::new(&client, "secrets", key).await?,
+        Duration::ZERO,
+        &[("nats-secrets", &[("db-password", "orders-db")])],
+    )
+    .await?;
+    let http = reqwest::Client::new();
+
+    let (status, body) = req(&http, &addr, "nats-secrets", "/get?key=db-password").await?;
+    assert_eq!((status.as_u16(), body.as_str()), (200, "first"));
+
+    let stored = async_nats::jetstream::new(client)
+        .get_key_value("secrets")
+        .await?
+        .get("orders-db")
+        .await?
+        .unwrap();
+    assert!(
+        !stored.windows(5).any(|w| w == b"first"),
+        "the bucket holds only ciphertext"
+    );
+
+    writer.put("orders-db", "second").await?;
+    let (status, body) = req(&http, &addr, "nats-secrets", "/get?key=db-password").await?;
+    assert_eq!((status.as_u16(), body.as_str()), (200, "second"));
+    Ok(())
+}
@@ -130,6 +130,7 @@ async fn test_secret_survives_other_workload_stop() -> Result<()> {
 
     h.workload_stop(WorkloadStopRequest {
         workload_id: stopped_id,
+        ..Default::default()
     })
     .await?;
 
@@ -20,7 +20,9 @@ use anyhow::{Context, Result};
 use tokio::time::timeout;
 
 use wash_runtime::host::HostApi;
-use wash_runtime::types::{LocalResources, Service, Workload, WorkloadStartRequest};
+use wash_runtime::types::{
+    LocalResources, Service, Workload, WorkloadListRequest, WorkloadStartRequest,
+};
 
 mod common;
 use common::{
@@ -50,6 +52,7 @@ fn svc_counter_request(host: &str, max_restarts: u64) -> WorkloadStartRequest {
                 bytes: bytes::Bytes::from_static(SVC_COUNTER_WASM),
                 local_resources: LocalResources::default(),
                 max_restarts,
+                ..Default::default()
             }),
             components: vec![],
             host_interfaces: http_only_host_interfaces(host),
@@ -213,6 +216,17 @@ async fn test_trigger_service_http_restarts_on_fault() -> Result<()> {
          per-instance http_calls restarts at 1"
     );
 
+    // The workload list counts the supervisor's restart against the service.
+    let listed = host.workload_list(WorkloadListRequest::default()).await?;
+    let [workload] = listed.workloads.as_slice() else {
+        anyhow::bail!("expected one workload, got {:?}", listed.workloads);
+    };
+    let [service] = workload.components.as_slice() else {
+        anyhow::bail!("expected only the service, got {:?}", workload.components);
+    };
+    assert_eq!(service.name, "service");
+    assert_eq!(service.restarts, 1);
+
     Ok(())
 }
 
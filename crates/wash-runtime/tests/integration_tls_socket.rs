@@ -5,13 +5,14 @@
 //! exchange against a local rustls echo server started in-process.
 
 #![cfg(feature = "wasi-tls")]
+#![allow(clippy::expect_used)]
 
 use anyhow::{Context, Result};
 use std::{collections::HashMap, time::Duration};
 use tokio::time::timeout;
 
 use wash_runtime::{
-    host::{HostApi, HostBuilder},
+    host::{HostApi, HostBuilder, allowed_socket_addr::AllowedSocketAddrs},
     types::{LocalResources, Service, Workload, WorkloadStartRequest, WorkloadState},
 };
 
@@ -44,8 +45,18 @@ fn echo_client_workload_request(
                     volume_mounts: vec![],
                     allowed_hosts: Default::default(),
                     allowed_ip_name_lookups: Default::default(),
+                    allowed_socket_addrs: std::sync::Arc::new(AllowedSocketAddrs {
+                        connect: vec![
+                            echo_addr
+                                .to_string()
+                                .parse()
+                                .expect("a socket address is a valid allowlist entry"),
+                        ],
+                        ..Default::default()
+                    }),
                 },
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![],
             host_interfaces: vec![],
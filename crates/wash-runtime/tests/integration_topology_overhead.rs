@@ -77,6 +77,7 @@ fn component_only(host: &str, warm: i32) -> WorkloadStartRequest {
                 pool_size: warm,
                 max_invocations: 0,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: http_only_host_interfaces(host),
             volumes: vec![],
@@ -97,6 +98,7 @@ fn service_only(host: &str) -> WorkloadStartRequest {
                 bytes: bytes::Bytes::from_static(SVC_NO_RUN_WASM),
                 local_resources: resources(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![],
             host_interfaces: http_only_host_interfaces(host),
@@ -119,6 +121,7 @@ fn service_plus_component(host: &str, warm: i32) -> WorkloadStartRequest {
                 bytes: bytes::Bytes::from_static(EPHEMERAL_CALLER_P3_WASM),
                 local_resources: resources(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![Component {
                 name: "callee".to_string(),
@@ -128,6 +131,7 @@ fn service_plus_component(host: &str, warm: i32) -> WorkloadStartRequest {
                 pool_size: warm,
                 max_invocations: 0,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: http_only_host_interfaces(host),
             volumes: vec![],
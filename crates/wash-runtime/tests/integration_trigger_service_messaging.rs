@@ -21,7 +21,7 @@ use std::time::Duration;
 
 use anyhow::{Context, Result};
 use testcontainers::{
-    GenericImage,
+    GenericImage, ImageExt,
     core::{IntoContainerPort, WaitFor},
     runners::AsyncRunner,
 };
@@ -37,7 +37,7 @@ use wash_runtime::plugin::{
     wasi_logging::TracingLogger,
 };
 use wash_runtime::types::{
-    LocalResources, Service, Workload, WorkloadStartRequest, WorkloadStopRequest,
+    LocalResources, Service, Workload, WorkloadStartRequest, WorkloadState, WorkloadStopRequest,
 };
 use wash_runtime::wit::WitInterface;
 
@@ -58,6 +58,7 @@ fn msg_counter_request(workload_id: &str, max_restarts: u64) -> WorkloadStartReq
                 bytes: bytes::Bytes::from_static(MSG_COUNTER_WASM),
                 local_resources: LocalResources::default(),
                 max_restarts,
+                ..Default::default()
             }),
             components: vec![],
             host_interfaces: vec![],
@@ -160,6 +161,7 @@ async fn test_trigger_service_stop_drops_messaging_subscription() -> Result<()>
 
     host.workload_stop(WorkloadStopRequest {
         workload_id: workload_id.clone(),
+        ..Default::default()
     })
     .await
     .context("failed to stop trigger service workload")?;
@@ -273,6 +275,7 @@ fn msg_counter_e2e_request(
                 bytes: bytes::Bytes::from_static(MSG_COUNTER_WASM),
                 local_resources: LocalResources::default(),
                 max_restarts: 0,
+                ..Default::default()
             }),
             components: vec![],
             host_interfaces,
@@ -464,3 +467,255 @@ async fn test_trigger_service_messaging_via_nats_backend() -> Result<()> {
 
     Ok(())
 }
+
+/// A failed delivery to the trigger service is retried from a timer, not inline
+/// in the receive loop, so the message queued behind it is handled while the
+/// first backs off. Once its retries run out a message is dead-lettered.
+///
+/// `msg-counter` fails every message, and the dead-letter subject is one the
+/// service receives too, so every attempt shows up in its count.
+#[tokio::test]
+async fn test_trigger_service_retries_off_the_receive_loop_and_dead_letters() -> Result<()> {
+    const BACKOFF: Duration = Duration::from_millis(1000);
+
+    let engine = Engine::builder().build()?;
+    let ingress = Arc::new(Ingress::new(DevRouter::default(), "127.0.0.1:0".parse()?).await?);
+    let addr = ingress.addr();
+    let messaging = Arc::new(InMemoryMessaging::new());
+    let host = HostBuilder::new()
+        .with_engine(engine)
+        .with_http_handler(ingress.clone())
+        .with_plugin(Arc::new(InMemoryBlobstore::new(None)))?
+        .with_plugin(Arc::new(InMemoryKeyValue::new()))?
+        .with_plugin(Arc::new(TracingLogger::default()))?
+        .with_plugin(Arc::new(DynamicConfig::default()))?
+        .with_plugin(messaging.clone())?
+        .build()?;
+    let host = host.start().await.context("failed to start host")?;
+
+    let workload_id = uuid::Uuid::new_v4().to_string();
+    let host_header = "msg-retry";
+    let backoff_ms = BACKOFF.as_millis().to_string();
+    let request = msg_counter_configured_request(
+        &workload_id,
+        host_header,
+        "work.>,dead",
+        &[
+            ("retry_count", "1"),
+            ("retry_backoff_ms", &backoff_ms),
+            ("dead_letter_subject", "dead"),
+        ],
+    );
+    host.workload_start(request)
+        .await
+        .context("failed to start msg-counter trigger service workload")?;
+
+    let started = std::time::Instant::now();
+    for subject in ["work.a", "work.b"] {
+        messaging
+            .publish(&workload_id, subject, b"hello".to_vec())
+            .await
+            .map_err(|e| anyhow::anyhow!("publish failed: {e}"))?;
+    }
+
+    let client = reqwest::Client::new();
+    assert_eq!(
+        await_count(&client, addr, host_header, 2).await?,
+        2,
+        "both messages got their first attempt"
+    );
+    assert!(
+        started.elapsed() < BACKOFF,
+        "the second message waited out the first one's backoff ({:?})",
+        started.elapsed()
+    );
+
+    // Each message fails its retry too and is dead-lettered; the two dead
+    // letters fail and are retried in turn, but are not dead-lettered again.
+    assert_eq!(await_count(&client, addr, host_header, 8).await?, 8);
+    tokio::time::sleep(BACKOFF * 2).await;
+    assert_eq!(
+        get_count(&client, addr, host_header).await?,
+        8,
+        "no attempts beyond the retry policy"
+    );
+
+    Ok(())
+}
+
+/// Start `msg-counter` as a trigger service subscribed to `subscriptions`, with
+/// `config` as the service's own config. Both backends read a component's own
+/// config ahead of the interface's, and the in-memory backend reads its
+/// subscriptions from there only.
+fn msg_counter_configured_request(
+    workload_id: &str,
+    host: &str,
+    subscriptions: &str,
+    config: &[(&str, &str)],
+) -> WorkloadStartRequest {
+    let mut request = msg_counter_e2e_request(workload_id, host, Some(subscriptions));
+    if let Some(service) = &mut request.workload.service {
+        service.local_resources.config = config
+            .iter()
+            .chain([&("subscriptions", subscriptions)])
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+    }
+    request
+}
+
+/// A durable (JetStream-mode) message the handler fails is nak'd and
+/// redelivered until `jetstream_max_deliver` deliveries, then dropped.
+#[tokio::test]
+async fn test_trigger_service_durable_message_is_redelivered_in_memory() -> Result<()> {
+    let engine = Engine::builder().build()?;
+    let ingress = Arc::new(Ingress::new(DevRouter::default(), "127.0.0.1:0".parse()?).await?);
+    let addr = ingress.addr();
+    let messaging = Arc::new(InMemoryMessaging::new());
+    let host = HostBuilder::new()
+        .with_engine(engine)
+        .with_http_handler(ingress.clone())
+        .with_plugin(Arc::new(InMemoryBlobstore::new(None)))?
+        .with_plugin(Arc::new(InMemoryKeyValue::new()))?
+        .with_plugin(Arc::new(TracingLogger::default()))?
+        .with_plugin(Arc::new(DynamicConfig::default()))?
+        .with_plugin(messaging.clone())?
+        .build()?;
+    let host = host.start().await.context("failed to start host")?;
+
+    let workload_id = uuid::Uuid::new_v4().to_string();
+    let host_header = "msg-durable";
+    let response = host
+        .workload_start(msg_counter_configured_request(
+            &workload_id,
+            host_header,
+            "orders.>",
+            &[
+                ("jetstream_stream", "ORDERS"),
+                ("jetstream_max_deliver", "3"),
+                ("jetstream_backoff_ms", "50"),
+            ],
+        ))
+        .await
+        .context("failed to start msg-counter trigger service workload")?;
+    anyhow::ensure!(
+        response.workload_status.workload_state == WorkloadState::Running,
+        "{}",
+        response.workload_status.message
+    );
+
+    messaging
+        .publish(&workload_id, "orders.created", b"order".to_vec())
+        .await
+        .map_err(|e| anyhow::anyhow!("publish failed: {e}"))?;
+
+    let client = reqwest::Client::new();
+    assert_eq!(
+        await_count(&client, addr, host_header, 3).await?,
+        3,
+        "the failed message was redelivered up to max_deliver"
+    );
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(
+        get_count(&client, addr, host_header).await?,
+        3,
+        "no delivery past max_deliver"
+    );
+    Ok(())
+}
+
+/// The same redelivery as [`test_trigger_service_durable_message_is_redelivered_in_memory`]
+/// against a real JetStream stream: each failure naks the message, and the
+/// server redelivers it until `max_deliver`.
+///
+/// Requires Docker (NATS); marked `#[ignore]`, run with `cargo test --include-ignored`.
+#[tokio::test]
+#[ignore = "requires Docker (NATS); run with `cargo test --include-ignored`"]
+async fn test_trigger_service_jetstream_message_is_redelivered_after_nak() -> Result<()> {
+    let container = GenericImage::new("nats", "2.12.8-alpine")
+        .with_exposed_port(4222.tcp())
+        .with_wait_for(WaitFor::message_on_stderr("Server is ready"))
+        .with_cmd(["-js"])
+        .start()
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to start NATS container: {e}"))?;
+    let port = container
+        .get_host_port_ipv4(4222)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to get NATS host port: {e}"))?;
+    let nats_url = format!("nats://127.0.0.1:{port}");
+
+    let publisher = async_nats::connect(&nats_url)
+        .await
+        .context("failed to connect the test's NATS client")?;
+    let jetstream = async_nats::jetstream::new(publisher);
+    jetstream
+        .create_stream(async_nats::jetstream::stream::Config {
+            name: "ORDERS".to_string(),
+            subjects: vec!["orders.>".to_string()],
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to create stream: {e}"))?;
+
+    let engine = Engine::builder().build()?;
+    let ingress = Arc::new(Ingress::new(DevRouter::default(), "127.0.0.1:0".parse()?).await?);
+    let addr = ingress.addr();
+    let plugin_client = Arc::new(
+        async_nats::connect(&nats_url)
+            .await
+            .context("failed to connect the plugin's NATS client")?,
+    );
+    let host = HostBuilder::new()
+        .with_engine(engine)
+        .with_http_handler(ingress.clone())
+        .with_plugin(Arc::new(InMemoryBlobstore::new(None)))?
+        .with_plugin(Arc::new(InMemoryKeyValue::new()))?
+        .with_plugin(Arc::new(TracingLogger::default()))?
+        .with_plugin(Arc::new(DynamicConfig::default()))?
+        .with_plugin(Arc::new(NatsMessaging::new(plugin_client)))?
+        .build()?;
+    let host = host.start().await.context("failed to start host")?;
+
+    let workload_id = uuid::Uuid::new_v4().to_string();
+    let host_header = "msg-jetstream";
+    let response = host
+        .workload_start(msg_counter_configured_request(
+            &workload_id,
+            host_header,
+            "orders.>",
+            &[
+                ("jetstream_stream", "ORDERS"),
+                ("jetstream_max_deliver", "3"),
+                ("jetstream_backoff_ms", "50"),
+            ],
+        ))
+        .await
+        .context("failed to start msg-counter trigger service workload")?;
+    anyhow::ensure!(
+        response.workload_status.workload_state == WorkloadState::Running,
+        "{}",
+        response.workload_status.message
+    );
+
+    jetstream
+        .publish("orders.created", bytes::Bytes::from_static(b"order"))
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to publish: {e}"))?
+        .await
+        .map_err(|e| anyhow::anyhow!("publish was not acknowledged: {e}"))?;
+
+    let client = reqwest::Client::new();
+    assert_eq!(
+        await_count(&client, addr, host_header, 3).await?,
+        3,
+        "the nak'd message was redelivered up to max_deliver"
+    );
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(
+        get_count(&client, addr, host_header).await?,
+        3,
+        "no delivery past max_deliver"
+    );
+    Ok(())
+}
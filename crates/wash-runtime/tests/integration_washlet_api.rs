@@ -15,6 +15,8 @@
 //! - Host registration: the published heartbeat and the `heartbeat` RPC carry
 //!   the host ID, the `hostgroup` label, and the environment the operator
 //!   records verbatim, plus a workload count that tracks running workloads.
+//! - Event stream: workload transitions are published on
+//!   `runtime.operator.events.{id}` as they happen, stamped with the host ID.
 //!
 //! Requires Docker (NATS); marked `#[ignore]`, run with `cargo test --include-ignored`.
 
@@ -33,7 +35,9 @@ use testcontainers::{
     core::{IntoContainerPort, WaitFor},
     runners::AsyncRunner,
 };
-use wash_runtime::washlet::{ClusterHostBuilder, heartbeat_subject, rpc_subject, types::v2};
+use wash_runtime::washlet::{
+    ClusterHostBuilder, events_subject, heartbeat_subject, rpc_subject, types::v2,
+};
 
 const HOST_GROUP: &str = "e2e";
 const ENVIRONMENT: &str = "e2e-env";
@@ -44,6 +48,8 @@ struct TestHarness {
     /// Subscribed before the host starts, so it observes the heartbeat the
     /// washlet publishes on its immediate first tick.
     heartbeat_sub: async_nats::Subscriber,
+    /// Subscribed before the host starts, like `heartbeat_sub`.
+    events_sub: async_nats::Subscriber,
     shutdown: Pin<Box<dyn Future<Output = Result<()>> + Send>>,
     _container: ContainerAsync<GenericImage>,
 }
@@ -91,6 +97,10 @@ async fn setup() -> Result<TestHarness> {
         .subscribe(heartbeat_subject(&host_id))
         .await
         .context("failed to subscribe to heartbeats")?;
+    let events_sub = api_client
+        .subscribe(events_subject(&host_id))
+        .await
+        .context("failed to subscribe to host events")?;
     api_client
         .flush()
         .await
@@ -105,6 +115,7 @@ async fn setup() -> Result<TestHarness> {
         api_client,
         host_id,
         heartbeat_sub,
+        events_sub,
         shutdown: Box::pin(shutdown),
         _container: container,
     };
@@ -162,6 +173,7 @@ impl TestHarness {
             self.subject("workload.stop"),
             &v2::WorkloadStopRequest {
                 workload_id: workload_id.to_string(),
+                ..Default::default()
             },
         )
         .await?;
@@ -177,6 +189,14 @@ impl TestHarness {
         serde_json::from_slice(&reply.payload).context("failed to deserialize heartbeat")
     }
 
+    async fn next_event(&mut self) -> Result<v2::HostEvent> {
+        let published = tokio::time::timeout(Duration::from_secs(10), self.events_sub.next())
+            .await
+            .context("no host event published within 10s")?
+            .context("host event subscription closed")?;
+        serde_json::from_slice(&published.payload).context("failed to deserialize host event")
+    }
+
     async fn shutdown(self) -> Result<()> {
         self.shutdown.await.context("washlet shutdown failed")
     }
@@ -392,3 +412,67 @@ async fn heartbeat_reports_identity_and_workload_count() -> Result<()> {
 
     harness.shutdown().await
 }
+
+/// The operator learns about workload transitions from the event stream
+/// rather than by polling status: each one is published in order, stamped
+/// with the host that saw it.
+#[tokio::test]
+#[ignore = "requires Docker (NATS); run with `cargo test --include-ignored`"]
+async fn workload_transitions_are_published_as_events() -> Result<()> {
+    let mut harness = setup().await?;
+    let workload_id = "washlet-api-e2e-events";
+
+    harness.start(&empty_start_request(workload_id)).await?;
+    harness.stop(workload_id).await?;
+
+    let id = workload_id.to_string();
+    let expected = [
+        v2::host_event::Event::WorkloadStarting(v2::WorkloadStartingEvent {
+            workload_id: id.clone(),
+        }),
+        v2::host_event::Event::WorkloadRunning(v2::WorkloadRunningEvent {
+            workload_id: id.clone(),
+        }),
+        v2::host_event::Event::WorkloadStopped(v2::WorkloadStoppedEvent { workload_id: id }),
+    ];
+    for expected in expected {
+        let event = harness.next_event().await?;
+        assert_eq!(event.host_id, harness.host_id);
+        assert!(event.timestamp.is_some(), "events carry a timestamp");
+        assert_eq!(event.event, Some(expected));
+    }
+
+    harness.shutdown().await
+}
+
+/// Debugging tools enumerate a host's workloads with `workload.list` rather
+/// than guessing IDs to probe with `workload.status`.
+#[tokio::test]
+#[ignore = "requires Docker (NATS); run with `cargo test --include-ignored`"]
+async fn workload_list_reports_every_workload() -> Result<()> {
+    let harness = setup().await?;
+
+    for workload_id in ["washlet-api-e2e-list-b", "washlet-api-e2e-list-a"] {
+        harness.start(&empty_start_request(workload_id)).await?;
+    }
+
+    let listed: v2::WorkloadListResponse = rpc(
+        &harness.api_client,
+        harness.subject("workload.list"),
+        &v2::WorkloadListRequest {},
+    )
+    .await?;
+    let ids = listed
+        .workloads
+        .iter()
+        .map(|w| w.workload_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["washlet-api-e2e-list-a", "washlet-api-e2e-list-b"]);
+    for workload in &listed.workloads {
+        assert_eq!(workload.workload_state(), v2::WorkloadState::Running);
+        assert_eq!(workload.name, "washlet-api-e2e");
+        assert_eq!(workload.namespace, "default");
+    }
+
+    harness.shutdown().await
+}
@@ -0,0 +1,258 @@
+//! Request-reply between `wasi:messaging@0.2.0-draft` components.
+//!
+//! The `wasi-messaging-echo` fixture answers `echo.*` topics with
+//! `request-reply.reply` and sends requests (`text/plain`, with an
+//! `x-request-id` metadata entry) from its HTTP endpoint. The in-memory tests
+//! route both sides within one workload; the NATS test, which also crosses
+//! into `wasmcloud:messaging` both ways, needs Docker and is marked
+//! `#[ignore]` (run with `cargo test --include-ignored`).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use wash_runtime::{
+    engine::Engine,
+    host::{
+        HostApi, HostBuilder,
+        http::{DevRouter, DynamicRouter, Ingress, Router},
+    },
+    plugin::{HostPlugin, wasi_messaging::WasiMessaging, wasmcloud_messaging::NatsMessaging},
+    types::{LocalResources, WorkloadState},
+    wit::WitInterface,
+};
+
+mod common;
+use common::{component_workload_request, http_incoming_handler_interface, req};
+
+const WASI_MESSAGING_ECHO_WASM: &[u8] = include_bytes!("wasm/wasi_messaging_echo.wasm");
+const MESSAGING_REPLY_WASM: &[u8] = include_bytes!("wasm/messaging_reply.wasm");
+
+const HOST_HEADER: &str = "wasi-messaging-echo";
+
+fn messaging_interface(spec: &str, subscriptions: &str) -> WitInterface {
+    WitInterface {
+        config: HashMap::from([("subscriptions".to_string(), subscriptions.to_string())]),
+        ..WitInterface::from(spec)
+    }
+}
+
+async fn start_host(
+    router: impl Router,
+    plugins: Vec<Arc<dyn HostPlugin>>,
+) -> Result<(std::net::SocketAddr, impl HostApi)> {
+    let ingress = Ingress::new(router, "127.0.0.1:0".parse()?).await?;
+    let addr = ingress.addr();
+    let mut builder = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress));
+    for plugin in plugins {
+        builder = builder.with_plugin(plugin)?;
+    }
+    let host = builder.build()?.start().await?;
+    let response = host
+        .workload_start(component_workload_request(
+            "wasi-messaging-echo",
+            HOST_HEADER,
+            WASI_MESSAGING_ECHO_WASM,
+            LocalResources::default(),
+            vec![
+                http_incoming_handler_interface(HOST_HEADER, None),
+                messaging_interface(
+                    "wasi:messaging/types,request-reply,incoming-handler@0.2.0-draft",
+                    "echo.>, fail",
+                ),
+            ],
+        ))
+        .await?;
+    anyhow::ensure!(
+        response.workload_status.workload_state == WorkloadState::Running,
+        "{}",
+        response.workload_status.message
+    );
+    Ok((addr, host))
+}
+
+#[tokio::test]
+async fn wasi_messaging_request_reply_in_memory() -> Result<()> {
+    let (addr, _host) = start_host(
+        DevRouter::default(),
+        vec![Arc::new(WasiMessaging::in_memory())],
+    )
+    .await?;
+    let client = reqwest::Client::new();
+
+    let (status, body) = req(
+        &client,
+        &addr,
+        HOST_HEADER,
+        "/request?topic=echo.one&body=hi&id=42",
+    )
+    .await?;
+    assert_eq!(
+        (status.as_u16(), body.as_str()),
+        (200, "hi|42 (text/plain)"),
+        "the handler saw the request's metadata and content type"
+    );
+
+    let (status, body) = req(
+        &client,
+        &addr,
+        HOST_HEADER,
+        "/request?topic=echo.twice&body=hi&id=7&replies=2",
+    )
+    .await?;
+    assert_eq!(
+        (status.as_u16(), body.as_str()),
+        (200, "hi|7 (text/plain), hi|7 (text/plain)")
+    );
+
+    let (status, body) = req(
+        &client,
+        &addr,
+        HOST_HEADER,
+        "/request?topic=fail&body=hi&id=1",
+    )
+    .await?;
+    assert_eq!((status.as_u16(), body.as_str()), (504, "Error::Timeout"));
+
+    let (status, body) = req(
+        &client,
+        &addr,
+        HOST_HEADER,
+        "/request?topic=nobody&body=hi&id=1",
+    )
+    .await?;
+    assert_eq!(status.as_u16(), 504);
+    assert!(body.contains("no responders"), "{body}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn wasi_messaging_in_memory_refuses_consumer_groups() -> Result<()> {
+    let (_addr, host) = start_host(
+        DevRouter::default(),
+        vec![Arc::new(WasiMessaging::in_memory())],
+    )
+    .await?;
+    let mut interface = messaging_interface(
+        "wasi:messaging/types,request-reply,incoming-handler@0.2.0-draft",
+        "grouped.>",
+    );
+    interface
+        .config
+        .insert("consumer_group".to_string(), "workers".to_string());
+    let response = host
+        .workload_start(component_workload_request(
+            "wasi-messaging-echo",
+            "grouped-echo",
+            WASI_MESSAGING_ECHO_WASM,
+            LocalResources::default(),
+            vec![
+                http_incoming_handler_interface("grouped-echo", None),
+                interface,
+            ],
+        ))
+        .await?;
+    assert_eq!(
+        response.workload_status.workload_state,
+        WorkloadState::Error,
+        "the in-memory bus can't split messages within a group"
+    );
+    assert!(
+        response.workload_status.message.contains("needs NATS"),
+        "{}",
+        response.workload_status.message
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires Docker (NATS); run with `cargo test --include-ignored`"]
+async fn wasi_messaging_interoperates_with_wasmcloud_messaging_over_nats() -> Result<()> {
+    use testcontainers::{
+        GenericImage,
+        core::{IntoContainerPort, WaitFor},
+        runners::AsyncRunner,
+    };
+
+    let nats = GenericImage::new("nats", "2.12.8-alpine")
+        .with_exposed_port(4222.tcp())
+        .with_wait_for(WaitFor::message_on_stderr("Server is ready"))
+        .start()
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to start NATS: {e}"))?;
+    let url = format!("nats://127.0.0.1:{}", nats.get_host_port_ipv4(4222).await?);
+    let client = Arc::new(async_nats::connect(&url).await?);
+    let (addr, host) = start_host(
+        DynamicRouter::default(),
+        vec![
+            Arc::new(WasiMessaging::nats(client.clone())),
+            Arc::new(NatsMessaging::new(client.clone())),
+        ],
+    )
+    .await?;
+
+    // Metadata and content type arrive as NATS headers.
+    let mut headers = async_nats::HeaderMap::new();
+    headers.insert("x-request-id", "9");
+    headers.insert("content-type", "application/json");
+    let reply = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.request_with_headers("echo.nats", headers, "{}".into()),
+    )
+    .await
+    .context("no reply")??;
+    assert_eq!(reply.payload.as_ref(), b"{}|9");
+    let reply_headers = reply.headers.context("reply has no headers")?;
+    assert_eq!(
+        reply_headers.get("content-type").map(|v| v.as_str()),
+        Some("application/json")
+    );
+    assert_eq!(
+        reply_headers.get("x-request-id").map(|v| v.as_str()),
+        Some("9")
+    );
+
+    // A `wasmcloud:messaging` handler answers a `wasi:messaging` request and
+    // sees its metadata as headers.
+    let response = host
+        .workload_start(component_workload_request(
+            "messaging-reply",
+            "messaging-reply",
+            MESSAGING_REPLY_WASM,
+            LocalResources::default(),
+            vec![
+                http_incoming_handler_interface("messaging-reply", None),
+                messaging_interface("wasmcloud:messaging/consumer,handler@0.3.0", "reply.>"),
+            ],
+        ))
+        .await?;
+    anyhow::ensure!(
+        response.workload_status.workload_state == WorkloadState::Running,
+        "{}",
+        response.workload_status.message
+    );
+    let (status, body) = req(
+        &reqwest::Client::new(),
+        &addr,
+        HOST_HEADER,
+        "/request?topic=reply.echo&body=hi&id=42",
+    )
+    .await?;
+    assert_eq!((status.as_u16(), body.as_str()), (200, "hi|42 ()"));
+
+    // And the other way: a `wasi:messaging` handler answers a
+    // `wasmcloud:messaging` request and sees its headers as metadata.
+    let (status, body) = req(
+        &reqwest::Client::new(),
+        &addr,
+        "messaging-reply",
+        "/request?subject=echo.cross&body=hi&id=5",
+    )
+    .await?;
+    assert_eq!((status.as_u16(), body.as_str()), (200, "hi|5"));
+    Ok(())
+}
@@ -142,10 +142,12 @@ async fn test_p2_http_component_works_with_p3_enabled() -> Result<()> {
                     // http-counter calls example.com — empty-list default would deny.
                     allowed_hosts: vec!["example.com".parse().unwrap()].into(),
                     allowed_ip_name_lookups: Default::default(),
+                    allowed_socket_addrs: Default::default(),
                 },
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: http_counter_host_interfaces("p2-test"),
             volumes: vec![],
@@ -203,6 +205,7 @@ async fn test_p2_concurrent_requests_with_p3_enabled() -> Result<()> {
                 pool_size: 1,
                 max_invocations: 100,
                 max_concurrency: 1,
+                ..Default::default()
             }],
             host_interfaces: http_counter_host_interfaces("concurrent-test"),
             volumes: vec![],
@@ -265,6 +268,7 @@ async fn test_p3_linker_accepts_p2_component() -> Result<()> {
             pool_size: 1,
             max_invocations: 100,
             max_concurrency: 1,
+            ..Default::default()
         }],
         host_interfaces: http_counter_host_interfaces("linker-test"),
         volumes: vec![],
@@ -0,0 +1,137 @@
+//! Stopping a workload drains the calls it is already serving.
+//!
+//! The `http-sleeper` fixture's first call on an instance pays a 100ms setup,
+//! long enough to stop the workload while that call is still in flight. Its
+//! `/wedge` path never answers, so only the stop's drain deadline ends it.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use wash_runtime::host::HostApi;
+use wash_runtime::types::{
+    Component, LocalResources, Workload, WorkloadListRequest, WorkloadStartRequest,
+    WorkloadStopRequest,
+};
+
+mod common;
+use common::{http_only_host_interfaces, req, start_host_with_dynamic_router};
+
+const HTTP_SLEEPER_WASM: &[u8] = include_bytes!("wasm/http_sleeper.wasm");
+
+fn sleeper_workload(host_header: &str) -> Workload {
+    Workload {
+        namespace: "test".to_string(),
+        name: host_header.to_string(),
+        annotations: HashMap::new(),
+        service: None,
+        components: vec![Component {
+            name: "sleeper".to_string(),
+            digest: None,
+            bytes: bytes::Bytes::from_static(HTTP_SLEEPER_WASM),
+            local_resources: LocalResources::default(),
+            pool_size: 1,
+            max_invocations: 0,
+            max_concurrency: 0,
+            ..Default::default()
+        }],
+        host_interfaces: http_only_host_interfaces(host_header),
+        volumes: vec![],
+    }
+}
+
+/// Wait until the workload's one component reports a call in flight.
+async fn until_in_flight(host: &impl HostApi) -> Result<()> {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    loop {
+        let listed = host.workload_list(WorkloadListRequest::default()).await?;
+        let in_flight = listed
+            .workloads
+            .iter()
+            .flat_map(|workload| workload.components.iter())
+            .map(|component| component.in_flight)
+            .sum::<usize>();
+        if in_flight > 0 {
+            return Ok(());
+        }
+        anyhow::ensure!(
+            tokio::time::Instant::now() < deadline,
+            "no call went in flight"
+        );
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+}
+
+#[tokio::test]
+async fn stop_lets_an_in_flight_call_finish() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    let client = reqwest::Client::new();
+    let workload_id = uuid::Uuid::new_v4().to_string();
+
+    host.workload_start(WorkloadStartRequest {
+        workload_id: workload_id.clone(),
+        workload: sleeper_workload("drain-finish"),
+    })
+    .await?;
+
+    let call = tokio::spawn(async move { req(&client, &addr, "drain-finish", "/").await });
+    until_in_flight(&host).await?;
+
+    host.workload_stop(WorkloadStopRequest {
+        workload_id,
+        drain_timeout: Some(Duration::from_secs(10)),
+    })
+    .await?;
+
+    let (status, body) = call.await??;
+    assert!(
+        status.is_success(),
+        "the drained call succeeds: {status} {body}"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn stop_gives_up_on_a_wedged_call_at_the_deadline() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    let client = reqwest::Client::new();
+    let workload_id = uuid::Uuid::new_v4().to_string();
+
+    host.workload_start(WorkloadStartRequest {
+        workload_id: workload_id.clone(),
+        workload: sleeper_workload("drain-wedge"),
+    })
+    .await?;
+
+    let call = tokio::spawn(async move { req(&client, &addr, "drain-wedge", "/wedge").await });
+    until_in_flight(&host).await?;
+
+    let started = tokio::time::Instant::now();
+    tokio::time::timeout(
+        Duration::from_secs(10),
+        host.workload_stop(WorkloadStopRequest {
+            workload_id,
+            drain_timeout: Some(Duration::from_millis(200)),
+        }),
+    )
+    .await
+    .expect("stop returns once the drain deadline passes")?;
+    assert!(
+        started.elapsed() >= Duration::from_millis(200),
+        "stop waited out the drain deadline"
+    );
+
+    // The wedged call does not outlive its workload: the host answers it
+    // with an error once it's cancelled.
+    let (status, body) = tokio::time::timeout(Duration::from_secs(10), call)
+        .await
+        .expect("the wedged call ends with its workload")??;
+    assert!(
+        status.is_server_error(),
+        "a cancelled call fails: {status} {body}"
+    );
+    Ok(())
+}
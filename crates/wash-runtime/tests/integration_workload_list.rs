@@ -0,0 +1,169 @@
+//! `HostApi::workload_list` reports what is running and how its components
+//! are doing.
+//!
+//! The `http-sleeper` fixture with a pool of one makes the per-component
+//! counters predictable: one warm instance once a request has been served,
+//! nothing in flight between requests, and one restart after `/trap` poisons
+//! that instance.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use wash_runtime::host::HostApi;
+use wash_runtime::types::{
+    Component, ComponentStatus, LocalResources, Workload, WorkloadListRequest,
+    WorkloadStartRequest, WorkloadState, WorkloadStopRequest,
+};
+
+mod common;
+use common::{get_status, http_only_host_interfaces, req, start_host_with_dynamic_router};
+
+const HTTP_SLEEPER_WASM: &[u8] = include_bytes!("wasm/http_sleeper.wasm");
+
+fn sleeper_workload(host_header: &str) -> Workload {
+    Workload {
+        namespace: "test".to_string(),
+        name: host_header.to_string(),
+        annotations: HashMap::new(),
+        service: None,
+        components: vec![Component {
+            name: "sleeper".to_string(),
+            digest: Some("sha256:sleeper".to_string()),
+            bytes: bytes::Bytes::from_static(HTTP_SLEEPER_WASM),
+            local_resources: LocalResources::default(),
+            pool_size: 1,
+            max_invocations: 0,
+            max_concurrency: 0,
+            ..Default::default()
+        }],
+        host_interfaces: http_only_host_interfaces(host_header),
+        volumes: vec![],
+    }
+}
+
+/// The single component of the single listed workload.
+async fn sleeper_status(host: &impl HostApi) -> Result<ComponentStatus> {
+    let listed = host.workload_list(WorkloadListRequest::default()).await?;
+    let [workload] = listed.workloads.as_slice() else {
+        anyhow::bail!("expected one workload, got {:?}", listed.workloads);
+    };
+    let [component] = workload.components.as_slice() else {
+        anyhow::bail!("expected one component, got {:?}", workload.components);
+    };
+    Ok(component.clone())
+}
+
+#[tokio::test]
+async fn running_workloads_report_their_components() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    let client = reqwest::Client::new();
+    let workload_id = uuid::Uuid::new_v4().to_string();
+
+    host.workload_start(WorkloadStartRequest {
+        workload_id: workload_id.clone(),
+        workload: sleeper_workload("list-status"),
+    })
+    .await?;
+
+    let listed = host.workload_list(WorkloadListRequest::default()).await?;
+    let [workload] = listed.workloads.as_slice() else {
+        anyhow::bail!("expected one workload, got {:?}", listed.workloads);
+    };
+    assert_eq!(workload.workload_id, workload_id);
+    assert_eq!(workload.name, "list-status");
+    assert_eq!(workload.namespace, "test");
+    assert_eq!(workload.workload_state, WorkloadState::Running);
+
+    // Instances are built on demand, so none is warm before the first call.
+    let status = sleeper_status(&host).await?;
+    assert_eq!(status.name, "sleeper");
+    assert_eq!(status.digest.as_deref(), Some("sha256:sleeper"));
+    assert_eq!(status.pool_size, 1);
+    assert_eq!(status.warm_instances, 0);
+
+    assert!(get_status(&client, addr, "list-status").await?.is_success());
+    let status = sleeper_status(&host).await?;
+    assert_eq!(status.warm_instances, 1);
+    assert_eq!(status.in_flight, 0, "no call is running between requests");
+    assert_eq!(status.restarts, 0);
+
+    host.workload_stop(WorkloadStopRequest {
+        workload_id,
+        ..Default::default()
+    })
+    .await?;
+    let listed = host.workload_list(WorkloadListRequest::default()).await?;
+    assert!(listed.workloads.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_faulted_instance_counts_as_a_restart() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    let client = reqwest::Client::new();
+
+    host.workload_start(WorkloadStartRequest {
+        workload_id: uuid::Uuid::new_v4().to_string(),
+        workload: sleeper_workload("list-trap"),
+    })
+    .await?;
+    assert!(get_status(&client, addr, "list-trap").await?.is_success());
+
+    let (status, _) = req(&client, &addr, "list-trap", "/trap").await?;
+    assert!(status.is_server_error(), "a trap fails the call: {status}");
+
+    // The driver records the fault as it winds down, which can land just
+    // after the failed response does.
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    let status = loop {
+        let status = sleeper_status(&host).await?;
+        if status.restarts > 0 || tokio::time::Instant::now() >= deadline {
+            break status;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    };
+    assert_eq!(status.restarts, 1);
+    assert_eq!(status.warm_instances, 0, "the faulted instance is not live");
+    assert_eq!(status.in_flight, 0);
+
+    assert!(get_status(&client, addr, "list-trap").await?.is_success());
+    let status = sleeper_status(&host).await?;
+    assert_eq!(status.warm_instances, 1, "a fresh instance replaced it");
+    assert_eq!(status.restarts, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn failed_workloads_keep_their_name() -> Result<()> {
+    let (_addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    let mut workload = sleeper_workload("list-broken");
+    if let Some(component) = workload.components.first_mut() {
+        component.bytes = bytes::Bytes::from_static(b"not a component");
+    }
+    let workload_id = uuid::Uuid::new_v4().to_string();
+    let response = host
+        .workload_start(WorkloadStartRequest {
+            workload_id: workload_id.clone(),
+            workload,
+        })
+        .await?;
+    assert_eq!(
+        response.workload_status.workload_state,
+        WorkloadState::Error
+    );
+
+    let listed = host.workload_list(WorkloadListRequest::default()).await?;
+    let [workload] = listed.workloads.as_slice() else {
+        anyhow::bail!("expected one workload, got {:?}", listed.workloads);
+    };
+    assert_eq!(workload.workload_id, workload_id);
+    assert_eq!(workload.workload_state, WorkloadState::Error);
+    assert_eq!(workload.name, "list-broken");
+    assert_eq!(workload.namespace, "test");
+    assert!(workload.components.is_empty());
+    Ok(())
+}
@@ -0,0 +1,491 @@
+//! `HostApi::workload_update` replaces a running workload without a gap.
+//!
+//! The `http-sleeper` fixture reports `served`, its instance's own request
+//! count. With a pool of one, a count that starts over at one after an update
+//! shows the traffic moved to the new revision's instance; a request loop kept
+//! running across the update shows none of it was dropped on the way.
+//!
+//! The messaging tests check the swap from the plugins' side: the replaced
+//! revision's subscriptions are gone once the update returns, the new
+//! revision is bound under the workload's own ID, and a new revision that
+//! fails to bind leaves the current one bound again.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+use wash_runtime::engine::Engine;
+use wash_runtime::host::http::{DynamicRouter, Ingress};
+use wash_runtime::host::{HostApi, HostBuilder};
+use wash_runtime::plugin::HostPlugin;
+use wash_runtime::plugin::wasi_messaging::WasiMessaging;
+use wash_runtime::plugin::wasmcloud_messaging::InMemoryMessaging;
+use wash_runtime::plugin::{
+    wasi_blobstore::InMemoryBlobstore, wasi_config::DynamicConfig, wasi_keyvalue::InMemoryKeyValue,
+    wasi_logging::TracingLogger,
+};
+use wash_runtime::types::{
+    Component, LocalResources, Service, Workload, WorkloadStartRequest, WorkloadState,
+    WorkloadStatusRequest, WorkloadStopRequest, WorkloadUpdateRequest,
+};
+use wash_runtime::wit::WitInterface;
+
+mod common;
+use common::{
+    get_status_and_body, http_incoming_handler_interface, http_only_host_interfaces,
+    json_u64_field, req, start_host_with_dynamic_router,
+};
+
+const HTTP_SLEEPER_WASM: &[u8] = include_bytes!("wasm/http_sleeper.wasm");
+const WASI_MESSAGING_ECHO_WASM: &[u8] = include_bytes!("wasm/wasi_messaging_echo.wasm");
+const MSG_COUNTER_WASM: &[u8] = include_bytes!("wasm/msg_counter.wasm");
+
+fn sleeper_workload(host_header: &str, bytes: &'static [u8]) -> Workload {
+    Workload {
+        namespace: "test".to_string(),
+        name: host_header.to_string(),
+        annotations: HashMap::new(),
+        service: None,
+        components: vec![Component {
+            name: "sleeper".to_string(),
+            digest: None,
+            bytes: bytes::Bytes::from_static(bytes),
+            local_resources: LocalResources::default(),
+            pool_size: 1,
+            max_invocations: 0,
+            max_concurrency: 0,
+            ..Default::default()
+        }],
+        host_interfaces: http_only_host_interfaces(host_header),
+        volumes: vec![],
+    }
+}
+
+/// GET `/` and return the serving instance's `served` count.
+async fn served(
+    client: &reqwest::Client,
+    addr: std::net::SocketAddr,
+    host_header: &str,
+) -> Result<u64> {
+    let (status, body) = get_status_and_body(client, addr, host_header).await?;
+    anyhow::ensure!(status.is_success(), "status {status}: {body}");
+    Ok(json_u64_field(&body, "served"))
+}
+
+async fn start_sleeper(host: &impl HostApi, workload_id: &str, host_header: &str) -> Result<()> {
+    let started = host
+        .workload_start(WorkloadStartRequest {
+            workload_id: workload_id.to_string(),
+            workload: sleeper_workload(host_header, HTTP_SLEEPER_WASM),
+        })
+        .await?;
+    anyhow::ensure!(
+        started.workload_status.workload_state == WorkloadState::Running,
+        "sleeper should start: {}",
+        started.workload_status.message
+    );
+    Ok(())
+}
+
+async fn update_sleeper(host: &impl HostApi, workload_id: &str, host_header: &str) -> Result<()> {
+    let updated = host
+        .workload_update(WorkloadUpdateRequest {
+            workload_id: workload_id.to_string(),
+            workload: sleeper_workload(host_header, HTTP_SLEEPER_WASM),
+        })
+        .await?;
+    anyhow::ensure!(
+        updated.workload_status.workload_state == WorkloadState::Running,
+        "sleeper should update: {}",
+        updated.workload_status.message
+    );
+    anyhow::ensure!(updated.workload_status.workload_id == workload_id);
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_moves_traffic_to_the_new_revision() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    let client = reqwest::Client::new();
+    let workload_id = uuid::Uuid::new_v4().to_string();
+
+    start_sleeper(&host, &workload_id, "update-swap").await?;
+    for expected in 1..=3 {
+        assert_eq!(served(&client, addr, "update-swap").await?, expected);
+    }
+
+    update_sleeper(&host, &workload_id, "update-swap").await?;
+
+    // The old revision is unrouted as soon as the new one is swapped in.
+    assert_eq!(
+        served(&client, addr, "update-swap").await?,
+        1,
+        "requests after the update must reach the new revision's instance"
+    );
+    let status = host
+        .workload_status(WorkloadStatusRequest {
+            workload_id: workload_id.clone(),
+        })
+        .await?;
+    assert_eq!(
+        status.workload_status.workload_state,
+        WorkloadState::Running
+    );
+
+    // The updated workload stops under the ID it was started with.
+    host.workload_stop(WorkloadStopRequest {
+        workload_id: workload_id.clone(),
+        ..Default::default()
+    })
+    .await?;
+    let status = host
+        .workload_status(WorkloadStatusRequest { workload_id })
+        .await?;
+    assert_eq!(
+        status.workload_status.workload_state,
+        WorkloadState::NotFound
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn requests_during_an_update_all_succeed() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    let client = reqwest::Client::new();
+    let workload_id = uuid::Uuid::new_v4().to_string();
+
+    start_sleeper(&host, &workload_id, "update-traffic").await?;
+    served(&client, addr, "update-traffic").await?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let traffic = tokio::spawn({
+        let client = client.clone();
+        let stop = stop.clone();
+        async move {
+            let mut sent = 0u64;
+            while !stop.load(Ordering::Relaxed) {
+                served(&client, addr, "update-traffic")
+                    .await
+                    .with_context(|| format!("request {sent} during the update failed"))?;
+                sent += 1;
+            }
+            anyhow::Ok(sent)
+        }
+    });
+
+    update_sleeper(&host, &workload_id, "update-traffic").await?;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    stop.store(true, Ordering::Relaxed);
+    let sent = traffic.await??;
+    assert!(sent > 0, "no requests were sent during the update");
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_failed_update_keeps_the_current_revision_serving() -> Result<()> {
+    let (addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+    let client = reqwest::Client::new();
+    let workload_id = uuid::Uuid::new_v4().to_string();
+
+    host.workload_start(WorkloadStartRequest {
+        workload_id: workload_id.clone(),
+        workload: sleeper_workload("update-fail", HTTP_SLEEPER_WASM),
+    })
+    .await?;
+    assert_eq!(served(&client, addr, "update-fail").await?, 1);
+
+    let updated = host
+        .workload_update(WorkloadUpdateRequest {
+            workload_id: workload_id.clone(),
+            workload: sleeper_workload("update-fail", b"not a component"),
+        })
+        .await?;
+    assert_eq!(updated.workload_status.workload_state, WorkloadState::Error);
+
+    assert_eq!(
+        served(&client, addr, "update-fail").await?,
+        2,
+        "the current revision must keep serving after a failed update"
+    );
+    let status = host
+        .workload_status(WorkloadStatusRequest { workload_id })
+        .await?;
+    assert_eq!(
+        status.workload_status.workload_state,
+        WorkloadState::Running
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn updating_an_unknown_workload_is_not_found() -> Result<()> {
+    let (_addr, host) = start_host_with_dynamic_router("127.0.0.1:0").await?;
+
+    let updated = host
+        .workload_update(WorkloadUpdateRequest {
+            workload_id: "missing".to_string(),
+            workload: sleeper_workload("update-missing", HTTP_SLEEPER_WASM),
+        })
+        .await?;
+    assert_eq!(
+        updated.workload_status.workload_state,
+        WorkloadState::NotFound
+    );
+    Ok(())
+}
+
+/// Start a host with a dynamic router and `plugins`.
+async fn start_host_with_plugins(
+    plugins: Vec<Arc<dyn HostPlugin>>,
+) -> Result<(std::net::SocketAddr, impl HostApi)> {
+    let ingress = Ingress::new(DynamicRouter::default(), "127.0.0.1:0".parse()?).await?;
+    let addr = ingress.addr();
+    let mut builder = HostBuilder::new()
+        .with_engine(Engine::builder().build()?)
+        .with_http_handler(Arc::new(ingress));
+    for plugin in plugins {
+        builder = builder.with_plugin(plugin)?;
+    }
+    let host = builder.build()?.start().await?;
+    Ok((addr, host))
+}
+
+/// The `wasi-messaging-echo` fixture, answering `echo.*` requests over the
+/// in-memory `wasi:messaging` bus.
+fn echo_workload(host_header: &str) -> Workload {
+    Workload {
+        namespace: "test".to_string(),
+        name: host_header.to_string(),
+        annotations: HashMap::new(),
+        service: None,
+        components: vec![Component {
+            name: "echo".to_string(),
+            bytes: bytes::Bytes::from_static(WASI_MESSAGING_ECHO_WASM),
+            pool_size: 1,
+            ..Default::default()
+        }],
+        host_interfaces: vec![
+            http_incoming_handler_interface(host_header, None),
+            WitInterface {
+                config: HashMap::from([("subscriptions".to_string(), "echo.>".to_string())]),
+                ..WitInterface::from(
+                    "wasi:messaging/types,request-reply,incoming-handler@0.2.0-draft",
+                )
+            },
+        ],
+        volumes: vec![],
+    }
+}
+
+#[tokio::test]
+async fn an_update_never_delivers_a_message_to_both_revisions() -> Result<()> {
+    let (addr, host) = start_host_with_plugins(vec![Arc::new(WasiMessaging::in_memory())]).await?;
+    let client = reqwest::Client::new();
+    let workload_id = uuid::Uuid::new_v4().to_string();
+
+    let started = host
+        .workload_start(WorkloadStartRequest {
+            workload_id: workload_id.clone(),
+            workload: echo_workload("update-echo"),
+        })
+        .await?;
+    assert_eq!(
+        started.workload_status.workload_state,
+        WorkloadState::Running,
+        "{}",
+        started.workload_status.message
+    );
+
+    // Ask for two answers: every subscribed handler answers once, so a second
+    // one would come from a revision still subscribed next to the first.
+    let path = "/request?topic=echo.one&body=hi&id=1&replies=2";
+    let (status, body) = req(&client, &addr, "update-echo", path).await?;
+    assert_eq!((status.as_u16(), body.as_str()), (200, "hi|1 (text/plain)"));
+
+    let updated = host
+        .workload_update(WorkloadUpdateRequest {
+            workload_id: workload_id.clone(),
+            workload: echo_workload("update-echo"),
+        })
+        .await?;
+    assert_eq!(
+        updated.workload_status.workload_state,
+        WorkloadState::Running,
+        "{}",
+        updated.workload_status.message
+    );
+
+    let (status, body) = req(&client, &addr, "update-echo", path).await?;
+    assert_eq!(
+        (status.as_u16(), body.as_str()),
+        (200, "hi|1 (text/plain)"),
+        "the replaced revision must be unsubscribed by the time the update returns"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn an_update_that_fails_to_bind_keeps_the_current_revision_serving() -> Result<()> {
+    let (addr, host) = start_host_with_plugins(vec![Arc::new(WasiMessaging::in_memory())]).await?;
+    let client = reqwest::Client::new();
+    let workload_id = uuid::Uuid::new_v4().to_string();
+
+    let started = host
+        .workload_start(WorkloadStartRequest {
+            workload_id: workload_id.clone(),
+            workload: echo_workload("update-rebind"),
+        })
+        .await?;
+    assert_eq!(
+        started.workload_status.workload_state,
+        WorkloadState::Running,
+        "{}",
+        started.workload_status.message
+    );
+
+    // The new revision compiles, but the in-memory bus refuses its consumer
+    // group once the current revision has already been unbound.
+    let mut grouped = echo_workload("update-rebind");
+    grouped.host_interfaces[1]
+        .config
+        .insert("consumer_group".to_string(), "workers".to_string());
+    let updated = host
+        .workload_update(WorkloadUpdateRequest {
+            workload_id: workload_id.clone(),
+            workload: grouped,
+        })
+        .await?;
+    assert_eq!(updated.workload_status.workload_state, WorkloadState::Error);
+    assert!(
+        updated.workload_status.message.contains("needs NATS"),
+        "{}",
+        updated.workload_status.message
+    );
+
+    let status = host
+        .workload_status(WorkloadStatusRequest {
+            workload_id: workload_id.clone(),
+        })
+        .await?;
+    assert_eq!(
+        status.workload_status.workload_state,
+        WorkloadState::Running,
+        "{}",
+        status.workload_status.message
+    );
+    // Bound again exactly once: still subscribed, and only once.
+    let path = "/request?topic=echo.one&body=hi&id=1&replies=2";
+    let (status, body) = req(&client, &addr, "update-rebind", path).await?;
+    assert_eq!((status.as_u16(), body.as_str()), (200, "hi|1 (text/plain)"));
+
+    // A later update still starts from the restored revision.
+    let updated = host
+        .workload_update(WorkloadUpdateRequest {
+            workload_id,
+            workload: echo_workload("update-rebind"),
+        })
+        .await?;
+    assert_eq!(
+        updated.workload_status.workload_state,
+        WorkloadState::Running,
+        "{}",
+        updated.workload_status.message
+    );
+    Ok(())
+}
+
+/// The `msg-counter` trigger service, which counts the messages it handles
+/// and reports the count at `/count`.
+fn msg_counter_workload(host_header: &str) -> Workload {
+    let mut host_interfaces = http_only_host_interfaces(host_header);
+    host_interfaces.push(WitInterface::from("wasmcloud:messaging/handler@0.2.0"));
+    Workload {
+        namespace: "test".to_string(),
+        name: host_header.to_string(),
+        annotations: HashMap::new(),
+        service: Some(Service {
+            bytes: bytes::Bytes::from_static(MSG_COUNTER_WASM),
+            ..Default::default()
+        }),
+        components: vec![],
+        host_interfaces,
+        volumes: vec![],
+    }
+}
+
+/// Poll the service's `/count` until it reaches `want`, returning the last one.
+async fn await_count(
+    client: &reqwest::Client,
+    addr: std::net::SocketAddr,
+    host_header: &str,
+    want: u64,
+) -> Result<u64> {
+    let mut count = 0;
+    for _ in 0..100 {
+        let (status, body) = req(client, &addr, host_header, "/count").await?;
+        anyhow::ensure!(status.is_success(), "status {status}: {body}");
+        count = json_u64_field(&body, "count");
+        if count >= want {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    Ok(count)
+}
+
+#[tokio::test]
+async fn plugins_reach_an_updated_workload_under_its_own_id() -> Result<()> {
+    let messaging = Arc::new(InMemoryMessaging::new());
+    let (addr, host) = start_host_with_plugins(vec![
+        Arc::new(InMemoryBlobstore::new(None)),
+        Arc::new(InMemoryKeyValue::new()),
+        Arc::new(TracingLogger::default()),
+        Arc::new(DynamicConfig::default()),
+        messaging.clone(),
+    ])
+    .await?;
+    let client = reqwest::Client::new();
+    let workload_id = uuid::Uuid::new_v4().to_string();
+
+    host.workload_start(WorkloadStartRequest {
+        workload_id: workload_id.clone(),
+        workload: msg_counter_workload("update-counter"),
+    })
+    .await?;
+    messaging
+        .publish(&workload_id, "test.subject", b"one".to_vec())
+        .await
+        .map_err(|e| anyhow::anyhow!("publish failed: {e}"))?;
+    assert_eq!(await_count(&client, addr, "update-counter", 1).await?, 1);
+
+    let updated = host
+        .workload_update(WorkloadUpdateRequest {
+            workload_id: workload_id.clone(),
+            workload: msg_counter_workload("update-counter"),
+        })
+        .await?;
+    assert_eq!(
+        updated.workload_status.workload_state,
+        WorkloadState::Running,
+        "{}",
+        updated.workload_status.message
+    );
+    assert_eq!(
+        await_count(&client, addr, "update-counter", 0).await?,
+        0,
+        "requests after the update reach the new revision's service"
+    );
+
+    // Publishing to the workload's own ID reaches the new revision.
+    messaging
+        .publish(&workload_id, "test.subject", b"two".to_vec())
+        .await
+        .map_err(|e| anyhow::anyhow!("publish failed: {e}"))?;
+    assert_eq!(await_count(&client, addr, "update-counter", 1).await?, 1);
+    Ok(())
+}
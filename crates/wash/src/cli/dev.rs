@@ -117,18 +117,24 @@ impl CliCommand for DevCommand {
             None
         };
 
-        // Enable wasmcloud:messaging — NATS when data_nats_url is configured,
-        // otherwise the in-memory backend.
+        // Enable wasmcloud:messaging and wasi:messaging — NATS when
+        // data_nats_url is configured, otherwise the in-memory backends.
         if let Some(client) = &data_nats_client {
-            host_builder = host_builder.with_plugin(Arc::new(
-                plugin::wasmcloud_messaging::NatsMessaging::new(client.clone()),
-            ))?;
-            debug!("wasmcloud:messaging plugin registered with NATS backend (data_nats_url)");
+            host_builder = host_builder
+                .with_plugin(Arc::new(plugin::wasmcloud_messaging::NatsMessaging::new(
+                    client.clone(),
+                )))?
+                .with_plugin(Arc::new(plugin::wasi_messaging::WasiMessaging::nats(
+                    client.clone(),
+                )))?;
+            debug!("messaging plugins registered with NATS backend (data_nats_url)");
         } else {
-            host_builder = host_builder.with_plugin(Arc::new(
-                plugin::wasmcloud_messaging::InMemoryMessaging::default(),
-            ))?;
-            debug!("wasmcloud:messaging plugin registered with in-memory backend");
+            host_builder = host_builder
+                .with_plugin(Arc::new(
+                    plugin::wasmcloud_messaging::InMemoryMessaging::default(),
+                ))?
+                .with_plugin(Arc::new(plugin::wasi_messaging::WasiMessaging::in_memory()))?;
+            debug!("messaging plugins registered with in-memory backend");
         }
 
         // Per-plugin settings override the in-memory default. The order of precedence is:
@@ -403,6 +409,7 @@ impl CliCommand for DevCommand {
         if let Err(e) = host
             .workload_stop(WorkloadStopRequest {
                 workload_id: workload_id.clone(),
+                drain_timeout: None,
             })
             .await
         {
@@ -585,6 +592,7 @@ fn build_workload(
         config: w.config.clone(),
         allowed_hosts: w.allowed_hosts.clone().into(),
         allowed_ip_name_lookups: w.allowed_ip_name_lookups.clone().into(),
+        allowed_socket_addrs: Arc::new(w.allowed_socket_addrs.clone()),
         ..Default::default()
     };
 
@@ -596,6 +604,7 @@ fn build_workload(
             digest: None,
             max_restarts: 0,
             local_resources: local_resources_for(resolved_workload),
+            max_execution_time: None,
         })
     } else {
         components.push(Component {
@@ -606,6 +615,7 @@ fn build_workload(
             pool_size: UNSET_LIMIT,
             max_invocations: UNSET_LIMIT,
             max_concurrency: UNSET_LIMIT,
+            max_execution_time: None,
         });
 
         if let Some(service_bytes) = service_bytes {
@@ -614,6 +624,7 @@ fn build_workload(
                 digest: None,
                 max_restarts: 0,
                 local_resources: local_resources_for(resolved_workload),
+                max_execution_time: None,
             });
         }
     }
@@ -630,6 +641,7 @@ fn build_workload(
             pool_size: sidecar.pool_size.unwrap_or(UNSET_LIMIT),
             max_invocations: sidecar.max_invocations.unwrap_or(UNSET_LIMIT),
             max_concurrency: sidecar.max_concurrency.unwrap_or(UNSET_LIMIT),
+            max_execution_time: None,
         });
     }
 
@@ -730,8 +742,11 @@ async fn reload_component(
     workload_id: Option<String>,
 ) -> anyhow::Result<String> {
     if let Some(workload_id) = workload_id {
-        host.workload_stop(WorkloadStopRequest { workload_id })
-            .await?;
+        host.workload_stop(WorkloadStopRequest {
+            workload_id,
+            drain_timeout: None,
+        })
+        .await?;
     }
 
     let response = host
@@ -835,6 +850,7 @@ mod tests {
             config: HashMap::from([("flag".into(), "on".into())]),
             allowed_hosts: vec!["https://api.example.com".parse().unwrap()],
             allowed_ip_name_lookups: vec!["*".parse().unwrap()],
+            allowed_socket_addrs: Default::default(),
         };
         let dev_cfg = DevConfig {
             components: vec![dev_component_named("sidecar-a")],
@@ -948,6 +964,7 @@ mod tests {
             config: HashMap::from([("flag".into(), "on".into())]),
             allowed_hosts: vec!["https://api.example.com".parse().unwrap()],
             allowed_ip_name_lookups: vec![],
+            allowed_socket_addrs: Default::default(),
         };
         let dev_cfg = DevConfig {
             components: vec![dev_component_named("sidecar-a")],
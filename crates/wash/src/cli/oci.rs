@@ -4,14 +4,19 @@ use std::path::PathBuf;
 use anyhow::Context as _;
 use chrono::Utc;
 use clap::{Args, Parser, Subcommand};
+use humansize::{BINARY, format_size};
 use tracing::instrument;
 use wash_runtime::component_source::ComponentSource;
-use wash_runtime::oci::{OciConfig, OciPullPolicy, push_component};
+use wash_runtime::oci::{
+    OciConfig, OciPullPolicy, fetch_manifest, list_tags, push_component,
+    signature::{SigningKey, sign_component},
+};
 use wasm_metadata::Payload;
 
 pub(crate) const OCI_CACHE_DIR: &str = "oci";
 
 use crate::cli::{CliCommand, CliContext, CommandOutput};
+use crate::inspect::{decode_component, get_component_wit};
 
 /// How to reach a registry, for every command that names an OCI reference.
 #[derive(Args, Debug, Clone, Default)]
@@ -47,7 +52,7 @@ impl RegistryArgs {
     }
 }
 
-/// Push or pull Wasm components to/from an OCI registry
+/// Push, pull or inspect Wasm components in an OCI registry
 #[derive(Parser, Debug, Clone)]
 #[command(subcommand_required = true, arg_required_else_help = true)]
 pub struct OciArgs {
@@ -61,6 +66,10 @@ pub enum OciCommand {
     Pull(PullCommand),
     /// Push a Wasm component to an OCI registry
     Push(PushCommand),
+    /// Show a component image's manifest, digest, size, annotations and WIT world
+    Inspect(InspectCommand),
+    /// List the tags of a repository in an OCI registry
+    Tags(TagsCommand),
 }
 
 impl CliCommand for OciArgs {
@@ -77,6 +86,8 @@ impl CliCommand for OciCommand {
         match self {
             OciCommand::Pull(cmd) => cmd.handle(ctx).await,
             OciCommand::Push(cmd) => cmd.handle(ctx).await,
+            OciCommand::Inspect(cmd) => cmd.handle(ctx).await,
+            OciCommand::Tags(cmd) => cmd.handle(ctx).await,
         }
     }
 }
@@ -138,6 +149,10 @@ pub struct PushCommand {
     pub reference: String,
     /// The path to the component to push
     pub component_path: PathBuf,
+    /// Sign the pushed image with this PEM private key (unencrypted PKCS#8,
+    /// ECDSA P-256), storing a cosign-compatible signature beside it
+    #[arg(long = "sign-key")]
+    pub sign_key: Option<PathBuf>,
     #[command(flatten)]
     pub registry: RegistryArgs,
 }
@@ -157,6 +172,13 @@ impl PushCommand {
             .await
             .context("failed to read component file")?;
 
+        // Read before pushing, so a bad key doesn't leave an unsigned image behind
+        let sign_key = self
+            .sign_key
+            .as_ref()
+            .map(|path| SigningKey::from_file(ctx.original_working_dir().join(path)))
+            .transpose()?;
+
         let payload = Payload::from_binary(&component)?;
         let metadata = payload.metadata();
 
@@ -211,17 +233,145 @@ impl PushCommand {
         let digest = push_component(
             &self.reference,
             &component,
-            oci_config,
+            oci_config.clone(),
             Some(all_annotations),
         )
         .await?;
 
+        let signature = match &sign_key {
+            Some(key) => Some(sign_component(&self.reference, &digest, key, oci_config).await?),
+            None => None,
+        };
+
         Ok(CommandOutput::ok(
             "OCI command executed successfully.".to_string(),
             Some(serde_json::json!({
                 "message": "OCI command executed successfully.",
                 "success": true,
                 "digest": digest,
+                "signature": signature,
+            })),
+        ))
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct InspectCommand {
+    /// The OCI reference to inspect
+    pub reference: String,
+    /// Only read the manifest, skipping the download of the component that
+    /// rendering its WIT world needs
+    #[arg(long = "no-wit", default_value_t = false)]
+    pub no_wit: bool,
+    #[command(flatten)]
+    pub registry: RegistryArgs,
+}
+
+impl InspectCommand {
+    /// Handle the OCI command
+    #[instrument(level = "debug", skip_all, name = "oci")]
+    pub async fn handle(&self, ctx: &CliContext) -> anyhow::Result<CommandOutput> {
+        let oci_config = self.registry.oci_config(ctx);
+        let image = fetch_manifest(&self.reference, oci_config.clone()).await?;
+
+        // Pull by digest, so the WIT shown is that of the manifest shown even if
+        // the tag moves in between. A cached copy is as good as a fresh one.
+        let wit = if self.no_wit {
+            None
+        } else {
+            let loaded = ComponentSource::Oci {
+                image: image.pinned_reference.clone(),
+                pull_policy: OciPullPolicy::IfNotPresent,
+            }
+            .load(oci_config)
+            .await?;
+            let component = decode_component(loaded.bytes.as_ref())
+                .await
+                .context("failed to decode component")?;
+            Some(
+                get_component_wit(component)
+                    .await
+                    .context("failed to print component WIT")?,
+            )
+        };
+
+        let size = image.layers_size();
+        let manifest = &image.manifest;
+        let annotations = manifest.annotations.clone().unwrap_or_default();
+        let config = serde_json::from_str::<serde_json::Value>(&image.config)
+            .unwrap_or(serde_json::Value::String(image.config.clone()));
+
+        let mut text = format!(
+            "Reference: {}\nDigest: {}\nMedia type: {}\nSize: {} ({size} bytes)\n",
+            self.reference,
+            image.digest,
+            manifest
+                .media_type
+                .as_deref()
+                .unwrap_or("application/vnd.oci.image.manifest.v1+json"),
+            format_size(size, BINARY),
+        );
+        if let Some(artifact_type) = &manifest.artifact_type {
+            text.push_str(&format!("Artifact type: {artifact_type}\n"));
+        }
+        text.push_str(&format!("Config: {}\n", manifest.config.media_type));
+        text.push_str("Layers:\n");
+        for layer in &manifest.layers {
+            text.push_str(&format!(
+                "  {} {} ({})\n",
+                layer.media_type,
+                layer.digest,
+                format_size(u64::try_from(layer.size).unwrap_or_default(), BINARY)
+            ));
+        }
+        if !annotations.is_empty() {
+            text.push_str("Annotations:\n");
+            for (key, value) in &annotations {
+                text.push_str(&format!("  {key}: {value}\n"));
+            }
+        }
+        if let Some(wit) = &wit {
+            text.push_str(&format!("World:\n{wit}"));
+        }
+
+        Ok(CommandOutput::ok(
+            text.trim_end().to_string(),
+            Some(serde_json::json!({
+                "message": "Component image inspected successfully.",
+                "success": true,
+                "reference": self.reference,
+                "digest": image.digest,
+                "size": size,
+                "annotations": annotations,
+                "manifest": manifest,
+                "config": config,
+                "wit": wit,
+            })),
+        ))
+    }
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct TagsCommand {
+    /// The repository to list, as an OCI reference; any tag on it is ignored
+    pub reference: String,
+    #[command(flatten)]
+    pub registry: RegistryArgs,
+}
+
+impl TagsCommand {
+    /// Handle the OCI command
+    #[instrument(level = "debug", skip_all, name = "oci")]
+    pub async fn handle(&self, ctx: &CliContext) -> anyhow::Result<CommandOutput> {
+        let tags = list_tags(&self.reference, self.registry.oci_config(ctx)).await?;
+
+        Ok(CommandOutput::ok(
+            tags.join("\n"),
+            Some(serde_json::json!({
+                "message": "Tags listed successfully.",
+                "success": true,
+                "reference": self.reference,
+                "tags": tags,
             })),
         ))
     }
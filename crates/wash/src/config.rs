@@ -17,6 +17,7 @@ use tracing::info;
 use wash_runtime::component_source::ComponentSource;
 use wash_runtime::host::allowed_hosts::AllowedHost;
 use wash_runtime::host::allowed_ip_name::AllowedIpName;
+use wash_runtime::host::allowed_socket_addr::AllowedSocketAddrs;
 use wash_runtime::oci::OciPullPolicy;
 use wash_runtime::wit::WitInterface;
 
@@ -261,6 +262,17 @@ pub struct WorkloadConfig {
     #[serde(default)]
     #[builder(default)]
     pub allowed_ip_name_lookups: Vec<AllowedIpName>,
+    /// Addresses components may `connect` and `bind` through raw
+    /// `wasi:sockets` TCP and UDP, written as `connect:` and `bind:` lists
+    /// of CIDR blocks with optional ports (`"10.0.0.0/8:5432"`, `"*:443"`,
+    /// `"*"`), plus a `loopback` switch for the workload-local loopback
+    /// network.
+    ///
+    /// Omitted lists deny every non-loopback connect and bind; loopback
+    /// stays available unless `loopback: false`.
+    #[serde(default)]
+    #[builder(default)]
+    pub allowed_socket_addrs: AllowedSocketAddrs,
 }
 
 // The `configs:`/`secrets:` source model moved to wash-runtime so every
@@ -364,6 +376,11 @@ pub struct DevComponent {
     /// denies every lookup); when omitted the workload list applies.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub allowed_ip_name_lookups: Option<Vec<AllowedIpName>>,
+    /// Raw socket policy for this component. When set it replaces
+    /// `workload.allowedSocketAddrs` for this component; when omitted the
+    /// workload policy applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_socket_addrs: Option<AllowedSocketAddrs>,
     /// How many instances of this component to keep warm between calls.
     ///
     /// Unset (or `0`) keeps the default: every call runs in a fresh instance
@@ -410,6 +427,7 @@ impl DevComponent {
             config: HashMap::new(),
             allowed_hosts: None,
             allowed_ip_name_lookups: None,
+            allowed_socket_addrs: None,
             pool_size: None,
             max_invocations: None,
             max_concurrency: None,
@@ -28,6 +28,7 @@ use std::path::Path;
 use anyhow::Result;
 use wash_runtime::host::allowed_hosts::AllowedHost;
 use wash_runtime::host::allowed_ip_name::AllowedIpName;
+use wash_runtime::host::allowed_socket_addr::AllowedSocketAddrs;
 
 use wash_runtime::config_source::resolve_environment_layer;
 
@@ -51,6 +52,9 @@ pub struct ResolvedWorkload {
     /// `wasi:sockets/ip-name-lookup`. Empty denies every lookup, which is
     /// what an omitted `allowedIpNameLookups` resolves to.
     pub allowed_ip_name_lookups: Vec<AllowedIpName>,
+    /// Raw `wasi:sockets` policy. Denies every non-loopback connect and
+    /// bind when `allowedSocketAddrs` is omitted.
+    pub allowed_socket_addrs: AllowedSocketAddrs,
 }
 
 /// Resolves the workload section of a [`Config`], pulling in named entries
@@ -95,6 +99,7 @@ pub fn resolve_workload(
         config: workload.config.clone(),
         allowed_hosts: workload.allowed_hosts.clone(),
         allowed_ip_name_lookups: workload.allowed_ip_name_lookups.clone(),
+        allowed_socket_addrs: workload.allowed_socket_addrs.clone(),
     })
 }
 
@@ -105,7 +110,8 @@ pub fn resolve_workload(
 /// key conflicts. `allowedHosts`, when set, replaces the workload list
 /// (an explicit `[]` denies all egress); when omitted the workload list
 /// applies. `allowedIpNameLookups`, when set, likewise replaces the workload
-/// list (an explicit `[]` denies every lookup).
+/// list (an explicit `[]` denies every lookup). `allowedSocketAddrs`, when
+/// set, replaces the workload policy as a whole.
 ///
 /// # Errors
 ///
@@ -144,11 +150,17 @@ pub fn resolve_component_workload(
         .clone()
         .unwrap_or_else(|| base.allowed_ip_name_lookups.clone());
 
+    let allowed_socket_addrs = component
+        .allowed_socket_addrs
+        .clone()
+        .unwrap_or_else(|| base.allowed_socket_addrs.clone());
+
     Ok(ResolvedWorkload {
         environment,
         config: merged_config,
         allowed_hosts,
         allowed_ip_name_lookups,
+        allowed_socket_addrs,
     })
 }
 
@@ -193,6 +205,7 @@ mod tests {
             config: HashMap::from([("WORKLOAD_CFG".into(), "cfg_value".into())]),
             allowed_hosts: vec!["https://api.example.com".parse().unwrap()],
             allowed_ip_name_lookups: vec![],
+            allowed_socket_addrs: Default::default(),
         };
 
         let configs = BTreeMap::from([(
@@ -257,6 +270,7 @@ mod tests {
             ]),
             allowed_hosts: vec![AllowedHost::Any],
             allowed_ip_name_lookups: vec![],
+            allowed_socket_addrs: Default::default(),
         };
         let component = DevComponent {
             environment: Some(
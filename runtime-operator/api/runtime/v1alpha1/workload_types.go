@@ -120,6 +120,36 @@ type LocalResources struct {
 	// +kubebuilder:validation:Optional
 	// +kubebuilder:validation:items:Pattern=`^\*$|^(\*\.)?[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?(\.[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?)*$|^[0-9A-Fa-f:.]+$`
 	AllowedIPNameLookups []string `json:"allowedIpNameLookups,omitempty"`
+	// AllowedSocketAddrs is the address policy for raw wasi:sockets TCP and
+	// UDP: where this component may connect and what it may bind.
+	//
+	// Absent allowedSocketAddrs denies every non-loopback connect and bind.
+	// It is separate from allowedHosts, which governs outgoing HTTP
+	// requests. Final validation runs in the runtime.
+	// +kubebuilder:validation:Optional
+	AllowedSocketAddrs *AllowedSocketAddrs `json:"allowedSocketAddrs,omitempty"`
+}
+
+// AllowedSocketAddrs describes the addresses a workload component may reach
+// through raw wasi:sockets TCP and UDP.
+type AllowedSocketAddrs struct {
+	// Connect lists where the component may connect TCP sockets, connect UDP
+	// sockets and send datagrams.
+	//
+	// Each entry is "*", a CIDR block or an address, with an optional port
+	// or port range, e.g. "10.0.0.0/8", "10.1.2.3:5432", "*:443" or
+	// "[2001:db8::/32]:8000-8999". Empty denies every connect.
+	// +kubebuilder:validation:Optional
+	Connect []string `json:"connect,omitempty"`
+	// Bind lists the non-loopback addresses the component may bind, in the
+	// same forms as connect. Empty denies every bind but a UDP client
+	// socket's unspecified one.
+	// +kubebuilder:validation:Optional
+	Bind []string `json:"bind,omitempty"`
+	// DenyLoopback turns off the workload-local loopback network
+	// (127.0.0.0/8 and ::1), which is otherwise always available.
+	// +kubebuilder:validation:Optional
+	DenyLoopback bool `json:"denyLoopback,omitempty"`
 }
 
 // WorkloadComponent represents a component of a workload.
@@ -25,6 +25,31 @@ import (
 	"k8s.io/apimachinery/pkg/runtime"
 )
 
+// DeepCopyInto is an autogenerated deepcopy function, copying the receiver, writing into out. in must be non-nil.
+func (in *AllowedSocketAddrs) DeepCopyInto(out *AllowedSocketAddrs) {
+	*out = *in
+	if in.Connect != nil {
+		in, out := &in.Connect, &out.Connect
+		*out = make([]string, len(*in))
+		copy(*out, *in)
+	}
+	if in.Bind != nil {
+		in, out := &in.Bind, &out.Bind
+		*out = make([]string, len(*in))
+		copy(*out, *in)
+	}
+}
+
+// DeepCopy is an autogenerated deepcopy function, copying the receiver, creating a new AllowedSocketAddrs.
+func (in *AllowedSocketAddrs) DeepCopy() *AllowedSocketAddrs {
+	if in == nil {
+		return nil
+	}
+	out := new(AllowedSocketAddrs)
+	in.DeepCopyInto(out)
+	return out
+}
+
 // DeepCopyInto is an autogenerated deepcopy function, copying the receiver, writing into out. in must be non-nil.
 func (in *Artifact) DeepCopyInto(out *Artifact) {
 	*out = *in
@@ -343,6 +368,11 @@ func (in *LocalResources) DeepCopyInto(out *LocalResources) {
 		*out = make([]string, len(*in))
 		copy(*out, *in)
 	}
+	if in.AllowedSocketAddrs != nil {
+		in, out := &in.AllowedSocketAddrs, &out.AllowedSocketAddrs
+		*out = new(AllowedSocketAddrs)
+		(*in).DeepCopyInto(*out)
+	}
 }
 
 // DeepCopy is an autogenerated deepcopy function, copying the receiver, creating a new LocalResources.
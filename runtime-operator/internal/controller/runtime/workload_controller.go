@@ -216,6 +216,13 @@ func materializeLocalResources(ctx context.Context, c client.Client, namespace s
 
 	lr.AllowedHosts = spec.AllowedHosts
 	lr.AllowedIpNameLookups = spec.AllowedIPNameLookups
+	if spec.AllowedSocketAddrs != nil {
+		lr.AllowedSocketAddrs = &runtimev2.AllowedSocketAddrs{
+			Connect:      spec.AllowedSocketAddrs.Connect,
+			Bind:         spec.AllowedSocketAddrs.Bind,
+			DenyLoopback: spec.AllowedSocketAddrs.DenyLoopback,
+		}
+	}
 	lr.Config = spec.Config
 
 	if spec.Environment != nil {
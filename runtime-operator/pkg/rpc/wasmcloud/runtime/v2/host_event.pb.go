@@ -0,0 +1,662 @@
+// Code generated by protoc-gen-go. DO NOT EDIT.
+// versions:
+// 	protoc-gen-go v1.36.4
+// 	protoc        (unknown)
+// source: wasmcloud/runtime/v2/host_event.proto
+
+package v2
+
+import (
+	protoreflect "google.golang.org/protobuf/reflect/protoreflect"
+	protoimpl "google.golang.org/protobuf/runtime/protoimpl"
+	timestamppb "google.golang.org/protobuf/types/known/timestamppb"
+	reflect "reflect"
+	sync "sync"
+	unsafe "unsafe"
+)
+
+const (
+	// Verify that this generated code is sufficiently up-to-date.
+	_ = protoimpl.EnforceVersion(20 - protoimpl.MinVersion)
+	// Verify that runtime/protoimpl is sufficiently up-to-date.
+	_ = protoimpl.EnforceVersion(protoimpl.MaxVersion - 20)
+)
+
+// A workload or plugin state change, published by the Wasm Host on
+// `runtime.operator.events.<host_id>` as it happens.
+// The Runtime Operator uses these to react to state changes without waiting
+// for the next status poll or heartbeat.
+type HostEvent struct {
+	state protoimpl.MessageState `protogen:"open.v1"`
+	// unique identifier of the host that published the event
+	HostId string `protobuf:"bytes,1,opt,name=host_id,json=hostId,proto3" json:"host_id,omitempty"`
+	// timestamp when the host published the event
+	Timestamp *timestamppb.Timestamp `protobuf:"bytes,2,opt,name=timestamp,proto3" json:"timestamp,omitempty"`
+	// Types that are valid to be assigned to Event:
+	//
+	//	*HostEvent_WorkloadStarting
+	//	*HostEvent_WorkloadRunning
+	//	*HostEvent_WorkloadFailed
+	//	*HostEvent_WorkloadStopped
+	//	*HostEvent_ComponentRestarted
+	//	*HostEvent_PluginCrashLooped
+	Event         isHostEvent_Event `protobuf_oneof:"event"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *HostEvent) Reset() {
+	*x = HostEvent{}
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[0]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *HostEvent) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*HostEvent) ProtoMessage() {}
+
+func (x *HostEvent) ProtoReflect() protoreflect.Message {
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[0]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use HostEvent.ProtoReflect.Descriptor instead.
+func (*HostEvent) Descriptor() ([]byte, []int) {
+	return file_wasmcloud_runtime_v2_host_event_proto_rawDescGZIP(), []int{0}
+}
+
+func (x *HostEvent) GetHostId() string {
+	if x != nil {
+		return x.HostId
+	}
+	return ""
+}
+
+func (x *HostEvent) GetTimestamp() *timestamppb.Timestamp {
+	if x != nil {
+		return x.Timestamp
+	}
+	return nil
+}
+
+func (x *HostEvent) GetEvent() isHostEvent_Event {
+	if x != nil {
+		return x.Event
+	}
+	return nil
+}
+
+func (x *HostEvent) GetWorkloadStarting() *WorkloadStartingEvent {
+	if x != nil {
+		if x, ok := x.Event.(*HostEvent_WorkloadStarting); ok {
+			return x.WorkloadStarting
+		}
+	}
+	return nil
+}
+
+func (x *HostEvent) GetWorkloadRunning() *WorkloadRunningEvent {
+	if x != nil {
+		if x, ok := x.Event.(*HostEvent_WorkloadRunning); ok {
+			return x.WorkloadRunning
+		}
+	}
+	return nil
+}
+
+func (x *HostEvent) GetWorkloadFailed() *WorkloadFailedEvent {
+	if x != nil {
+		if x, ok := x.Event.(*HostEvent_WorkloadFailed); ok {
+			return x.WorkloadFailed
+		}
+	}
+	return nil
+}
+
+func (x *HostEvent) GetWorkloadStopped() *WorkloadStoppedEvent {
+	if x != nil {
+		if x, ok := x.Event.(*HostEvent_WorkloadStopped); ok {
+			return x.WorkloadStopped
+		}
+	}
+	return nil
+}
+
+func (x *HostEvent) GetComponentRestarted() *ComponentRestartedEvent {
+	if x != nil {
+		if x, ok := x.Event.(*HostEvent_ComponentRestarted); ok {
+			return x.ComponentRestarted
+		}
+	}
+	return nil
+}
+
+func (x *HostEvent) GetPluginCrashLooped() *PluginCrashLoopedEvent {
+	if x != nil {
+		if x, ok := x.Event.(*HostEvent_PluginCrashLooped); ok {
+			return x.PluginCrashLooped
+		}
+	}
+	return nil
+}
+
+type isHostEvent_Event interface {
+	isHostEvent_Event()
+}
+
+type HostEvent_WorkloadStarting struct {
+	WorkloadStarting *WorkloadStartingEvent `protobuf:"bytes,3,opt,name=workload_starting,json=workloadStarting,proto3,oneof"`
+}
+
+type HostEvent_WorkloadRunning struct {
+	WorkloadRunning *WorkloadRunningEvent `protobuf:"bytes,4,opt,name=workload_running,json=workloadRunning,proto3,oneof"`
+}
+
+type HostEvent_WorkloadFailed struct {
+	WorkloadFailed *WorkloadFailedEvent `protobuf:"bytes,5,opt,name=workload_failed,json=workloadFailed,proto3,oneof"`
+}
+
+type HostEvent_WorkloadStopped struct {
+	WorkloadStopped *WorkloadStoppedEvent `protobuf:"bytes,6,opt,name=workload_stopped,json=workloadStopped,proto3,oneof"`
+}
+
+type HostEvent_ComponentRestarted struct {
+	ComponentRestarted *ComponentRestartedEvent `protobuf:"bytes,7,opt,name=component_restarted,json=componentRestarted,proto3,oneof"`
+}
+
+type HostEvent_PluginCrashLooped struct {
+	PluginCrashLooped *PluginCrashLoopedEvent `protobuf:"bytes,8,opt,name=plugin_crash_looped,json=pluginCrashLooped,proto3,oneof"`
+}
+
+func (*HostEvent_WorkloadStarting) isHostEvent_Event() {}
+
+func (*HostEvent_WorkloadRunning) isHostEvent_Event() {}
+
+func (*HostEvent_WorkloadFailed) isHostEvent_Event() {}
+
+func (*HostEvent_WorkloadStopped) isHostEvent_Event() {}
+
+func (*HostEvent_ComponentRestarted) isHostEvent_Event() {}
+
+func (*HostEvent_PluginCrashLooped) isHostEvent_Event() {}
+
+// A workload was accepted and is being started.
+type WorkloadStartingEvent struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	WorkloadId    string                 `protobuf:"bytes,1,opt,name=workload_id,json=workloadId,proto3" json:"workload_id,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *WorkloadStartingEvent) Reset() {
+	*x = WorkloadStartingEvent{}
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[1]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *WorkloadStartingEvent) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*WorkloadStartingEvent) ProtoMessage() {}
+
+func (x *WorkloadStartingEvent) ProtoReflect() protoreflect.Message {
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[1]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use WorkloadStartingEvent.ProtoReflect.Descriptor instead.
+func (*WorkloadStartingEvent) Descriptor() ([]byte, []int) {
+	return file_wasmcloud_runtime_v2_host_event_proto_rawDescGZIP(), []int{1}
+}
+
+func (x *WorkloadStartingEvent) GetWorkloadId() string {
+	if x != nil {
+		return x.WorkloadId
+	}
+	return ""
+}
+
+// A workload started, or an update swapped in its new revision.
+type WorkloadRunningEvent struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	WorkloadId    string                 `protobuf:"bytes,1,opt,name=workload_id,json=workloadId,proto3" json:"workload_id,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *WorkloadRunningEvent) Reset() {
+	*x = WorkloadRunningEvent{}
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[2]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *WorkloadRunningEvent) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*WorkloadRunningEvent) ProtoMessage() {}
+
+func (x *WorkloadRunningEvent) ProtoReflect() protoreflect.Message {
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[2]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use WorkloadRunningEvent.ProtoReflect.Descriptor instead.
+func (*WorkloadRunningEvent) Descriptor() ([]byte, []int) {
+	return file_wasmcloud_runtime_v2_host_event_proto_rawDescGZIP(), []int{2}
+}
+
+func (x *WorkloadRunningEvent) GetWorkloadId() string {
+	if x != nil {
+		return x.WorkloadId
+	}
+	return ""
+}
+
+// A workload failed to start, or failed while it was running.
+type WorkloadFailedEvent struct {
+	state      protoimpl.MessageState `protogen:"open.v1"`
+	WorkloadId string                 `protobuf:"bytes,1,opt,name=workload_id,json=workloadId,proto3" json:"workload_id,omitempty"`
+	// human-readable cause, the same as the workload's status message
+	Reason        string `protobuf:"bytes,2,opt,name=reason,proto3" json:"reason,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *WorkloadFailedEvent) Reset() {
+	*x = WorkloadFailedEvent{}
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[3]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *WorkloadFailedEvent) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*WorkloadFailedEvent) ProtoMessage() {}
+
+func (x *WorkloadFailedEvent) ProtoReflect() protoreflect.Message {
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[3]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use WorkloadFailedEvent.ProtoReflect.Descriptor instead.
+func (*WorkloadFailedEvent) Descriptor() ([]byte, []int) {
+	return file_wasmcloud_runtime_v2_host_event_proto_rawDescGZIP(), []int{3}
+}
+
+func (x *WorkloadFailedEvent) GetWorkloadId() string {
+	if x != nil {
+		return x.WorkloadId
+	}
+	return ""
+}
+
+func (x *WorkloadFailedEvent) GetReason() string {
+	if x != nil {
+		return x.Reason
+	}
+	return ""
+}
+
+// A workload was stopped and removed from the host.
+type WorkloadStoppedEvent struct {
+	state         protoimpl.MessageState `protogen:"open.v1"`
+	WorkloadId    string                 `protobuf:"bytes,1,opt,name=workload_id,json=workloadId,proto3" json:"workload_id,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *WorkloadStoppedEvent) Reset() {
+	*x = WorkloadStoppedEvent{}
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[4]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *WorkloadStoppedEvent) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*WorkloadStoppedEvent) ProtoMessage() {}
+
+func (x *WorkloadStoppedEvent) ProtoReflect() protoreflect.Message {
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[4]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use WorkloadStoppedEvent.ProtoReflect.Descriptor instead.
+func (*WorkloadStoppedEvent) Descriptor() ([]byte, []int) {
+	return file_wasmcloud_runtime_v2_host_event_proto_rawDescGZIP(), []int{4}
+}
+
+func (x *WorkloadStoppedEvent) GetWorkloadId() string {
+	if x != nil {
+		return x.WorkloadId
+	}
+	return ""
+}
+
+// A workload's service faulted and was restarted by its supervisor.
+type ComponentRestartedEvent struct {
+	state       protoimpl.MessageState `protogen:"open.v1"`
+	WorkloadId  string                 `protobuf:"bytes,1,opt,name=workload_id,json=workloadId,proto3" json:"workload_id,omitempty"`
+	ComponentId string                 `protobuf:"bytes,2,opt,name=component_id,json=componentId,proto3" json:"component_id,omitempty"`
+	// why the previous incarnation ended
+	Reason        string `protobuf:"bytes,3,opt,name=reason,proto3" json:"reason,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *ComponentRestartedEvent) Reset() {
+	*x = ComponentRestartedEvent{}
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[5]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *ComponentRestartedEvent) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*ComponentRestartedEvent) ProtoMessage() {}
+
+func (x *ComponentRestartedEvent) ProtoReflect() protoreflect.Message {
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[5]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use ComponentRestartedEvent.ProtoReflect.Descriptor instead.
+func (*ComponentRestartedEvent) Descriptor() ([]byte, []int) {
+	return file_wasmcloud_runtime_v2_host_event_proto_rawDescGZIP(), []int{5}
+}
+
+func (x *ComponentRestartedEvent) GetWorkloadId() string {
+	if x != nil {
+		return x.WorkloadId
+	}
+	return ""
+}
+
+func (x *ComponentRestartedEvent) GetComponentId() string {
+	if x != nil {
+		return x.ComponentId
+	}
+	return ""
+}
+
+func (x *ComponentRestartedEvent) GetReason() string {
+	if x != nil {
+		return x.Reason
+	}
+	return ""
+}
+
+// A host plugin's driver faulted past its restart budget and was not
+// restarted again.
+type PluginCrashLoopedEvent struct {
+	state    protoimpl.MessageState `protogen:"open.v1"`
+	PluginId string                 `protobuf:"bytes,1,opt,name=plugin_id,json=pluginId,proto3" json:"plugin_id,omitempty"`
+	// restarts the plugin went through before giving up
+	Restarts      uint32 `protobuf:"varint,2,opt,name=restarts,proto3" json:"restarts,omitempty"`
+	unknownFields protoimpl.UnknownFields
+	sizeCache     protoimpl.SizeCache
+}
+
+func (x *PluginCrashLoopedEvent) Reset() {
+	*x = PluginCrashLoopedEvent{}
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[6]
+	ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+	ms.StoreMessageInfo(mi)
+}
+
+func (x *PluginCrashLoopedEvent) String() string {
+	return protoimpl.X.MessageStringOf(x)
+}
+
+func (*PluginCrashLoopedEvent) ProtoMessage() {}
+
+func (x *PluginCrashLoopedEvent) ProtoReflect() protoreflect.Message {
+	mi := &file_wasmcloud_runtime_v2_host_event_proto_msgTypes[6]
+	if x != nil {
+		ms := protoimpl.X.MessageStateOf(protoimpl.Pointer(x))
+		if ms.LoadMessageInfo() == nil {
+			ms.StoreMessageInfo(mi)
+		}
+		return ms
+	}
+	return mi.MessageOf(x)
+}
+
+// Deprecated: Use PluginCrashLoopedEvent.ProtoReflect.Descriptor instead.
+func (*PluginCrashLoopedEvent) Descriptor() ([]byte, []int) {
+	return file_wasmcloud_runtime_v2_host_event_proto_rawDescGZIP(), []int{6}
+}
+
+func (x *PluginCrashLoopedEvent) GetPluginId() string {
+	if x != nil {
+		return x.PluginId
+	}
+	return ""
+}
+
+func (x *PluginCrashLoopedEvent) GetRestarts() uint32 {
+	if x != nil {
+		return x.Restarts
+	}
+	return 0
+}
+
+var File_wasmcloud_runtime_v2_host_event_proto protoreflect.FileDescriptor
+
+var file_wasmcloud_runtime_v2_host_event_proto_rawDesc = string([]byte{
+	0x0a, 0x25, 0x77, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x2f, 0x72, 0x75, 0x6e, 0x74,
+	0x69, 0x6d, 0x65, 0x2f, 0x76, 0x32, 0x2f, 0x68, 0x6f, 0x73, 0x74, 0x5f, 0x65, 0x76, 0x65, 0x6e,
+	0x74, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x12, 0x14, 0x77, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f,
+	0x75, 0x64, 0x2e, 0x72, 0x75, 0x6e, 0x74, 0x69, 0x6d, 0x65, 0x2e, 0x76, 0x32, 0x1a, 0x1f, 0x67,
+	0x6f, 0x6f, 0x67, 0x6c, 0x65, 0x2f, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x62, 0x75, 0x66, 0x2f, 0x74,
+	0x69, 0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x22, 0x8d,
+	0x05, 0x0a, 0x09, 0x48, 0x6f, 0x73, 0x74, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x12, 0x17, 0x0a, 0x07,
+	0x68, 0x6f, 0x73, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x06, 0x68,
+	0x6f, 0x73, 0x74, 0x49, 0x64, 0x12, 0x38, 0x0a, 0x09, 0x74, 0x69, 0x6d, 0x65, 0x73, 0x74, 0x61,
+	0x6d, 0x70, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x1a, 0x2e, 0x67, 0x6f, 0x6f, 0x67, 0x6c,
+	0x65, 0x2e, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x62, 0x75, 0x66, 0x2e, 0x54, 0x69, 0x6d, 0x65, 0x73,
+	0x74, 0x61, 0x6d, 0x70, 0x52, 0x09, 0x74, 0x69, 0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x12,
+	0x5a, 0x0a, 0x11, 0x77, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x5f, 0x73, 0x74, 0x61, 0x72,
+	0x74, 0x69, 0x6e, 0x67, 0x18, 0x03, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x2b, 0x2e, 0x77, 0x61, 0x73,
+	0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x2e, 0x72, 0x75, 0x6e, 0x74, 0x69, 0x6d, 0x65, 0x2e, 0x76,
+	0x32, 0x2e, 0x57, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x53, 0x74, 0x61, 0x72, 0x74, 0x69,
+	0x6e, 0x67, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x48, 0x00, 0x52, 0x10, 0x77, 0x6f, 0x72, 0x6b, 0x6c,
+	0x6f, 0x61, 0x64, 0x53, 0x74, 0x61, 0x72, 0x74, 0x69, 0x6e, 0x67, 0x12, 0x57, 0x0a, 0x10, 0x77,
+	0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x5f, 0x72, 0x75, 0x6e, 0x6e, 0x69, 0x6e, 0x67, 0x18,
+	0x04, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x2a, 0x2e, 0x77, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75,
+	0x64, 0x2e, 0x72, 0x75, 0x6e, 0x74, 0x69, 0x6d, 0x65, 0x2e, 0x76, 0x32, 0x2e, 0x57, 0x6f, 0x72,
+	0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x52, 0x75, 0x6e, 0x6e, 0x69, 0x6e, 0x67, 0x45, 0x76, 0x65, 0x6e,
+	0x74, 0x48, 0x00, 0x52, 0x0f, 0x77, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x52, 0x75, 0x6e,
+	0x6e, 0x69, 0x6e, 0x67, 0x12, 0x54, 0x0a, 0x0f, 0x77, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64,
+	0x5f, 0x66, 0x61, 0x69, 0x6c, 0x65, 0x64, 0x18, 0x05, 0x20, 0x01, 0x28, 0x0b, 0x32, 0x29, 0x2e,
+	0x77, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x2e, 0x72, 0x75, 0x6e, 0x74, 0x69, 0x6d,
+	0x65, 0x2e, 0x76, 0x32, 0x2e, 0x57, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x46, 0x61, 0x69,
+	0x6c, 0x65, 0x64, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x48, 0x00, 0x52, 0x0e, 0x77, 0x6f, 0x72, 0x6b,
+	0x6c, 0x6f, 0x61, 0x64, 0x46, 0x61, 0x69, 0x6c, 0x65, 0x64, 0x12, 0x57, 0x0a, 0x10, 0x77, 0x6f,
+	0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x5f, 0x73, 0x74, 0x6f, 0x70, 0x70, 0x65, 0x64, 0x18, 0x06,
+	0x20, 0x01, 0x28, 0x0b, 0x32, 0x2a, 0x2e, 0x77, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64,
+	0x2e, 0x72, 0x75, 0x6e, 0x74, 0x69, 0x6d, 0x65, 0x2e, 0x76, 0x32, 0x2e, 0x57, 0x6f, 0x72, 0x6b,
+	0x6c, 0x6f, 0x61, 0x64, 0x53, 0x74, 0x6f, 0x70, 0x70, 0x65, 0x64, 0x45, 0x76, 0x65, 0x6e, 0x74,
+	0x48, 0x00, 0x52, 0x0f, 0x77, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x53, 0x74, 0x6f, 0x70,
+	0x70, 0x65, 0x64, 0x12, 0x60, 0x0a, 0x13, 0x63, 0x6f, 0x6d, 0x70, 0x6f, 0x6e, 0x65, 0x6e, 0x74,
+	0x5f, 0x72, 0x65, 0x73, 0x74, 0x61, 0x72, 0x74, 0x65, 0x64, 0x18, 0x07, 0x20, 0x01, 0x28, 0x0b,
+	0x32, 0x2d, 0x2e, 0x77, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x2e, 0x72, 0x75, 0x6e,
+	0x74, 0x69, 0x6d, 0x65, 0x2e, 0x76, 0x32, 0x2e, 0x43, 0x6f, 0x6d, 0x70, 0x6f, 0x6e, 0x65, 0x6e,
+	0x74, 0x52, 0x65, 0x73, 0x74, 0x61, 0x72, 0x74, 0x65, 0x64, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x48,
+	0x00, 0x52, 0x12, 0x63, 0x6f, 0x6d, 0x70, 0x6f, 0x6e, 0x65, 0x6e, 0x74, 0x52, 0x65, 0x73, 0x74,
+	0x61, 0x72, 0x74, 0x65, 0x64, 0x12, 0x5e, 0x0a, 0x13, 0x70, 0x6c, 0x75, 0x67, 0x69, 0x6e, 0x5f,
+	0x63, 0x72, 0x61, 0x73, 0x68, 0x5f, 0x6c, 0x6f, 0x6f, 0x70, 0x65, 0x64, 0x18, 0x08, 0x20, 0x01,
+	0x28, 0x0b, 0x32, 0x2c, 0x2e, 0x77, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x2e, 0x72,
+	0x75, 0x6e, 0x74, 0x69, 0x6d, 0x65, 0x2e, 0x76, 0x32, 0x2e, 0x50, 0x6c, 0x75, 0x67, 0x69, 0x6e,
+	0x43, 0x72, 0x61, 0x73, 0x68, 0x4c, 0x6f, 0x6f, 0x70, 0x65, 0x64, 0x45, 0x76, 0x65, 0x6e, 0x74,
+	0x48, 0x00, 0x52, 0x11, 0x70, 0x6c, 0x75, 0x67, 0x69, 0x6e, 0x43, 0x72, 0x61, 0x73, 0x68, 0x4c,
+	0x6f, 0x6f, 0x70, 0x65, 0x64, 0x42, 0x07, 0x0a, 0x05, 0x65, 0x76, 0x65, 0x6e, 0x74, 0x22, 0x38,
+	0x0a, 0x15, 0x57, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x53, 0x74, 0x61, 0x72, 0x74, 0x69,
+	0x6e, 0x67, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x12, 0x1f, 0x0a, 0x0b, 0x77, 0x6f, 0x72, 0x6b, 0x6c,
+	0x6f, 0x61, 0x64, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0a, 0x77, 0x6f,
+	0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x49, 0x64, 0x22, 0x37, 0x0a, 0x14, 0x57, 0x6f, 0x72, 0x6b,
+	0x6c, 0x6f, 0x61, 0x64, 0x52, 0x75, 0x6e, 0x6e, 0x69, 0x6e, 0x67, 0x45, 0x76, 0x65, 0x6e, 0x74,
+	0x12, 0x1f, 0x0a, 0x0b, 0x77, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x5f, 0x69, 0x64, 0x18,
+	0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0a, 0x77, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x49,
+	0x64, 0x22, 0x4e, 0x0a, 0x13, 0x57, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x46, 0x61, 0x69,
+	0x6c, 0x65, 0x64, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x12, 0x1f, 0x0a, 0x0b, 0x77, 0x6f, 0x72, 0x6b,
+	0x6c, 0x6f, 0x61, 0x64, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0a, 0x77,
+	0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x49, 0x64, 0x12, 0x16, 0x0a, 0x06, 0x72, 0x65, 0x61,
+	0x73, 0x6f, 0x6e, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x06, 0x72, 0x65, 0x61, 0x73, 0x6f,
+	0x6e, 0x22, 0x37, 0x0a, 0x14, 0x57, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x53, 0x74, 0x6f,
+	0x70, 0x70, 0x65, 0x64, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x12, 0x1f, 0x0a, 0x0b, 0x77, 0x6f, 0x72,
+	0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0a,
+	0x77, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61, 0x64, 0x49, 0x64, 0x22, 0x75, 0x0a, 0x17, 0x43, 0x6f,
+	0x6d, 0x70, 0x6f, 0x6e, 0x65, 0x6e, 0x74, 0x52, 0x65, 0x73, 0x74, 0x61, 0x72, 0x74, 0x65, 0x64,
+	0x45, 0x76, 0x65, 0x6e, 0x74, 0x12, 0x1f, 0x0a, 0x0b, 0x77, 0x6f, 0x72, 0x6b, 0x6c, 0x6f, 0x61,
+	0x64, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0a, 0x77, 0x6f, 0x72, 0x6b,
+	0x6c, 0x6f, 0x61, 0x64, 0x49, 0x64, 0x12, 0x21, 0x0a, 0x0c, 0x63, 0x6f, 0x6d, 0x70, 0x6f, 0x6e,
+	0x65, 0x6e, 0x74, 0x5f, 0x69, 0x64, 0x18, 0x02, 0x20, 0x01, 0x28, 0x09, 0x52, 0x0b, 0x63, 0x6f,
+	0x6d, 0x70, 0x6f, 0x6e, 0x65, 0x6e, 0x74, 0x49, 0x64, 0x12, 0x16, 0x0a, 0x06, 0x72, 0x65, 0x61,
+	0x73, 0x6f, 0x6e, 0x18, 0x03, 0x20, 0x01, 0x28, 0x09, 0x52, 0x06, 0x72, 0x65, 0x61, 0x73, 0x6f,
+	0x6e, 0x22, 0x51, 0x0a, 0x16, 0x50, 0x6c, 0x75, 0x67, 0x69, 0x6e, 0x43, 0x72, 0x61, 0x73, 0x68,
+	0x4c, 0x6f, 0x6f, 0x70, 0x65, 0x64, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x12, 0x1b, 0x0a, 0x09, 0x70,
+	0x6c, 0x75, 0x67, 0x69, 0x6e, 0x5f, 0x69, 0x64, 0x18, 0x01, 0x20, 0x01, 0x28, 0x09, 0x52, 0x08,
+	0x70, 0x6c, 0x75, 0x67, 0x69, 0x6e, 0x49, 0x64, 0x12, 0x1a, 0x0a, 0x08, 0x72, 0x65, 0x73, 0x74,
+	0x61, 0x72, 0x74, 0x73, 0x18, 0x02, 0x20, 0x01, 0x28, 0x0d, 0x52, 0x08, 0x72, 0x65, 0x73, 0x74,
+	0x61, 0x72, 0x74, 0x73, 0x42, 0xdf, 0x01, 0x0a, 0x18, 0x63, 0x6f, 0x6d, 0x2e, 0x77, 0x61, 0x73,
+	0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x2e, 0x72, 0x75, 0x6e, 0x74, 0x69, 0x6d, 0x65, 0x2e, 0x76,
+	0x32, 0x42, 0x0e, 0x48, 0x6f, 0x73, 0x74, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x50, 0x72, 0x6f, 0x74,
+	0x6f, 0x50, 0x01, 0x5a, 0x41, 0x67, 0x6f, 0x2e, 0x77, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75,
+	0x64, 0x2e, 0x64, 0x65, 0x76, 0x2f, 0x72, 0x75, 0x6e, 0x74, 0x69, 0x6d, 0x65, 0x2d, 0x6f, 0x70,
+	0x65, 0x72, 0x61, 0x74, 0x6f, 0x72, 0x2f, 0x76, 0x32, 0x2f, 0x70, 0x6b, 0x67, 0x2f, 0x72, 0x70,
+	0x63, 0x2f, 0x77, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x2f, 0x72, 0x75, 0x6e, 0x74,
+	0x69, 0x6d, 0x65, 0x2f, 0x76, 0x32, 0xa2, 0x02, 0x03, 0x57, 0x52, 0x58, 0xaa, 0x02, 0x14, 0x57,
+	0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x2e, 0x52, 0x75, 0x6e, 0x74, 0x69, 0x6d, 0x65,
+	0x2e, 0x56, 0x32, 0xca, 0x02, 0x14, 0x57, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x5c,
+	0x52, 0x75, 0x6e, 0x74, 0x69, 0x6d, 0x65, 0x5c, 0x56, 0x32, 0xe2, 0x02, 0x20, 0x57, 0x61, 0x73,
+	0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x5c, 0x52, 0x75, 0x6e, 0x74, 0x69, 0x6d, 0x65, 0x5c, 0x56,
+	0x32, 0x5c, 0x47, 0x50, 0x42, 0x4d, 0x65, 0x74, 0x61, 0x64, 0x61, 0x74, 0x61, 0xea, 0x02, 0x16,
+	0x57, 0x61, 0x73, 0x6d, 0x63, 0x6c, 0x6f, 0x75, 0x64, 0x3a, 0x3a, 0x52, 0x75, 0x6e, 0x74, 0x69,
+	0x6d, 0x65, 0x3a, 0x3a, 0x56, 0x32, 0x62, 0x06, 0x70, 0x72, 0x6f, 0x74, 0x6f, 0x33,
+})
+
+var (
+	file_wasmcloud_runtime_v2_host_event_proto_rawDescOnce sync.Once
+	file_wasmcloud_runtime_v2_host_event_proto_rawDescData []byte
+)
+
+func file_wasmcloud_runtime_v2_host_event_proto_rawDescGZIP() []byte {
+	file_wasmcloud_runtime_v2_host_event_proto_rawDescOnce.Do(func() {
+		file_wasmcloud_runtime_v2_host_event_proto_rawDescData = protoimpl.X.CompressGZIP(unsafe.Slice(unsafe.StringData(file_wasmcloud_runtime_v2_host_event_proto_rawDesc), len(file_wasmcloud_runtime_v2_host_event_proto_rawDesc)))
+	})
+	return file_wasmcloud_runtime_v2_host_event_proto_rawDescData
+}
+
+var file_wasmcloud_runtime_v2_host_event_proto_msgTypes = make([]protoimpl.MessageInfo, 7)
+var file_wasmcloud_runtime_v2_host_event_proto_goTypes = []any{
+	(*HostEvent)(nil),               // 0: wasmcloud.runtime.v2.HostEvent
+	(*WorkloadStartingEvent)(nil),   // 1: wasmcloud.runtime.v2.WorkloadStartingEvent
+	(*WorkloadRunningEvent)(nil),    // 2: wasmcloud.runtime.v2.WorkloadRunningEvent
+	(*WorkloadFailedEvent)(nil),     // 3: wasmcloud.runtime.v2.WorkloadFailedEvent
+	(*WorkloadStoppedEvent)(nil),    // 4: wasmcloud.runtime.v2.WorkloadStoppedEvent
+	(*ComponentRestartedEvent)(nil), // 5: wasmcloud.runtime.v2.ComponentRestartedEvent
+	(*PluginCrashLoopedEvent)(nil),  // 6: wasmcloud.runtime.v2.PluginCrashLoopedEvent
+	(*timestamppb.Timestamp)(nil),   // 7: google.protobuf.Timestamp
+}
+var file_wasmcloud_runtime_v2_host_event_proto_depIdxs = []int32{
+	7, // 0: wasmcloud.runtime.v2.HostEvent.timestamp:type_name -> google.protobuf.Timestamp
+	1, // 1: wasmcloud.runtime.v2.HostEvent.workload_starting:type_name -> wasmcloud.runtime.v2.WorkloadStartingEvent
+	2, // 2: wasmcloud.runtime.v2.HostEvent.workload_running:type_name -> wasmcloud.runtime.v2.WorkloadRunningEvent
+	3, // 3: wasmcloud.runtime.v2.HostEvent.workload_failed:type_name -> wasmcloud.runtime.v2.WorkloadFailedEvent
+	4, // 4: wasmcloud.runtime.v2.HostEvent.workload_stopped:type_name -> wasmcloud.runtime.v2.WorkloadStoppedEvent
+	5, // 5: wasmcloud.runtime.v2.HostEvent.component_restarted:type_name -> wasmcloud.runtime.v2.ComponentRestartedEvent
+	6, // 6: wasmcloud.runtime.v2.HostEvent.plugin_crash_looped:type_name -> wasmcloud.runtime.v2.PluginCrashLoopedEvent
+	7, // [7:7] is the sub-list for method output_type
+	7, // [7:7] is the sub-list for method input_type
+	7, // [7:7] is the sub-list for extension type_name
+	7, // [7:7] is the sub-list for extension extendee
+	0, // [0:7] is the sub-list for field type_name
+}
+
+func init() { file_wasmcloud_runtime_v2_host_event_proto_init() }
+func file_wasmcloud_runtime_v2_host_event_proto_init() {
+	if File_wasmcloud_runtime_v2_host_event_proto != nil {
+		return
+	}
+	file_wasmcloud_runtime_v2_host_event_proto_msgTypes[0].OneofWrappers = []any{
+		(*HostEvent_WorkloadStarting)(nil),
+		(*HostEvent_WorkloadRunning)(nil),
+		(*HostEvent_WorkloadFailed)(nil),
+		(*HostEvent_WorkloadStopped)(nil),
+		(*HostEvent_ComponentRestarted)(nil),
+		(*HostEvent_PluginCrashLooped)(nil),
+	}
+	type x struct{}
+	out := protoimpl.TypeBuilder{
+		File: protoimpl.DescBuilder{
+			GoPackagePath: reflect.TypeOf(x{}).PkgPath(),
+			RawDescriptor: unsafe.Slice(unsafe.StringData(file_wasmcloud_runtime_v2_host_event_proto_rawDesc), len(file_wasmcloud_runtime_v2_host_event_proto_rawDesc)),
+			NumEnums:      0,
+			NumMessages:   7,
+			NumExtensions: 0,
+			NumServices:   0,
+		},
+		GoTypes:           file_wasmcloud_runtime_v2_host_event_proto_goTypes,
+		DependencyIndexes: file_wasmcloud_runtime_v2_host_event_proto_depIdxs,
+		MessageInfos:      file_wasmcloud_runtime_v2_host_event_proto_msgTypes,
+	}.Build()
+	File_wasmcloud_runtime_v2_host_event_proto = out.File
+	file_wasmcloud_runtime_v2_host_event_proto_goTypes = nil
+	file_wasmcloud_runtime_v2_host_event_proto_depIdxs = nil
+}
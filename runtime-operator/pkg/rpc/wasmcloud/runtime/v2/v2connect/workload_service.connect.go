@@ -42,6 +42,12 @@ const (
 	// WorkloadServiceWorkloadStopProcedure is the fully-qualified name of the WorkloadService's
 	// WorkloadStop RPC.
 	WorkloadServiceWorkloadStopProcedure = "/wasmcloud.runtime.v2.WorkloadService/WorkloadStop"
+	// WorkloadServiceWorkloadUpdateProcedure is the fully-qualified name of the WorkloadService's
+	// WorkloadUpdate RPC.
+	WorkloadServiceWorkloadUpdateProcedure = "/wasmcloud.runtime.v2.WorkloadService/WorkloadUpdate"
+	// WorkloadServiceWorkloadListProcedure is the fully-qualified name of the WorkloadService's
+	// WorkloadList RPC.
+	WorkloadServiceWorkloadListProcedure = "/wasmcloud.runtime.v2.WorkloadService/WorkloadList"
 )
 
 // WorkloadServiceClient is a client for the wasmcloud.runtime.v2.WorkloadService service.
@@ -49,6 +55,13 @@ type WorkloadServiceClient interface {
 	WorkloadStart(context.Context, *connect.Request[v2.WorkloadStartRequest]) (*connect.Response[v2.WorkloadStartResponse], error)
 	WorkloadStatus(context.Context, *connect.Request[v2.WorkloadStatusRequest]) (*connect.Response[v2.WorkloadStatusResponse], error)
 	WorkloadStop(context.Context, *connect.Request[v2.WorkloadStopRequest]) (*connect.Response[v2.WorkloadStopResponse], error)
+	// Replaces a running workload with a new revision: the new revision is
+	// compiled alongside the current one and swapped in under the same workload
+	// ID, and the old revision is then drained and stopped.
+	WorkloadUpdate(context.Context, *connect.Request[v2.WorkloadUpdateRequest]) (*connect.Response[v2.WorkloadUpdateResponse], error)
+	// Lists every workload on the host, with live per-component status for
+	// those that are running.
+	WorkloadList(context.Context, *connect.Request[v2.WorkloadListRequest]) (*connect.Response[v2.WorkloadListResponse], error)
 }
 
 // NewWorkloadServiceClient constructs a client for the wasmcloud.runtime.v2.WorkloadService
@@ -80,6 +93,18 @@ func NewWorkloadServiceClient(httpClient connect.HTTPClient, baseURL string, opt
 			connect.WithSchema(workloadServiceMethods.ByName("WorkloadStop")),
 			connect.WithClientOptions(opts...),
 		),
+		workloadUpdate: connect.NewClient[v2.WorkloadUpdateRequest, v2.WorkloadUpdateResponse](
+			httpClient,
+			baseURL+WorkloadServiceWorkloadUpdateProcedure,
+			connect.WithSchema(workloadServiceMethods.ByName("WorkloadUpdate")),
+			connect.WithClientOptions(opts...),
+		),
+		workloadList: connect.NewClient[v2.WorkloadListRequest, v2.WorkloadListResponse](
+			httpClient,
+			baseURL+WorkloadServiceWorkloadListProcedure,
+			connect.WithSchema(workloadServiceMethods.ByName("WorkloadList")),
+			connect.WithClientOptions(opts...),
+		),
 	}
 }
 
@@ -88,6 +113,8 @@ type workloadServiceClient struct {
 	workloadStart  *connect.Client[v2.WorkloadStartRequest, v2.WorkloadStartResponse]
 	workloadStatus *connect.Client[v2.WorkloadStatusRequest, v2.WorkloadStatusResponse]
 	workloadStop   *connect.Client[v2.WorkloadStopRequest, v2.WorkloadStopResponse]
+	workloadUpdate *connect.Client[v2.WorkloadUpdateRequest, v2.WorkloadUpdateResponse]
+	workloadList   *connect.Client[v2.WorkloadListRequest, v2.WorkloadListResponse]
 }
 
 // WorkloadStart calls wasmcloud.runtime.v2.WorkloadService.WorkloadStart.
@@ -105,11 +132,28 @@ func (c *workloadServiceClient) WorkloadStop(ctx context.Context, req *connect.R
 	return c.workloadStop.CallUnary(ctx, req)
 }
 
+// WorkloadUpdate calls wasmcloud.runtime.v2.WorkloadService.WorkloadUpdate.
+func (c *workloadServiceClient) WorkloadUpdate(ctx context.Context, req *connect.Request[v2.WorkloadUpdateRequest]) (*connect.Response[v2.WorkloadUpdateResponse], error) {
+	return c.workloadUpdate.CallUnary(ctx, req)
+}
+
+// WorkloadList calls wasmcloud.runtime.v2.WorkloadService.WorkloadList.
+func (c *workloadServiceClient) WorkloadList(ctx context.Context, req *connect.Request[v2.WorkloadListRequest]) (*connect.Response[v2.WorkloadListResponse], error) {
+	return c.workloadList.CallUnary(ctx, req)
+}
+
 // WorkloadServiceHandler is an implementation of the wasmcloud.runtime.v2.WorkloadService service.
 type WorkloadServiceHandler interface {
 	WorkloadStart(context.Context, *connect.Request[v2.WorkloadStartRequest]) (*connect.Response[v2.WorkloadStartResponse], error)
 	WorkloadStatus(context.Context, *connect.Request[v2.WorkloadStatusRequest]) (*connect.Response[v2.WorkloadStatusResponse], error)
 	WorkloadStop(context.Context, *connect.Request[v2.WorkloadStopRequest]) (*connect.Response[v2.WorkloadStopResponse], error)
+	// Replaces a running workload with a new revision: the new revision is
+	// compiled alongside the current one and swapped in under the same workload
+	// ID, and the old revision is then drained and stopped.
+	WorkloadUpdate(context.Context, *connect.Request[v2.WorkloadUpdateRequest]) (*connect.Response[v2.WorkloadUpdateResponse], error)
+	// Lists every workload on the host, with live per-component status for
+	// those that are running.
+	WorkloadList(context.Context, *connect.Request[v2.WorkloadListRequest]) (*connect.Response[v2.WorkloadListResponse], error)
 }
 
 // NewWorkloadServiceHandler builds an HTTP handler from the service implementation. It returns the
@@ -137,6 +181,18 @@ func NewWorkloadServiceHandler(svc WorkloadServiceHandler, opts ...connect.Handl
 		connect.WithSchema(workloadServiceMethods.ByName("WorkloadStop")),
 		connect.WithHandlerOptions(opts...),
 	)
+	workloadServiceWorkloadUpdateHandler := connect.NewUnaryHandler(
+		WorkloadServiceWorkloadUpdateProcedure,
+		svc.WorkloadUpdate,
+		connect.WithSchema(workloadServiceMethods.ByName("WorkloadUpdate")),
+		connect.WithHandlerOptions(opts...),
+	)
+	workloadServiceWorkloadListHandler := connect.NewUnaryHandler(
+		WorkloadServiceWorkloadListProcedure,
+		svc.WorkloadList,
+		connect.WithSchema(workloadServiceMethods.ByName("WorkloadList")),
+		connect.WithHandlerOptions(opts...),
+	)
 	return "/wasmcloud.runtime.v2.WorkloadService/", http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
 		switch r.URL.Path {
 		case WorkloadServiceWorkloadStartProcedure:
@@ -145,6 +201,10 @@ func NewWorkloadServiceHandler(svc WorkloadServiceHandler, opts ...connect.Handl
 			workloadServiceWorkloadStatusHandler.ServeHTTP(w, r)
 		case WorkloadServiceWorkloadStopProcedure:
 			workloadServiceWorkloadStopHandler.ServeHTTP(w, r)
+		case WorkloadServiceWorkloadUpdateProcedure:
+			workloadServiceWorkloadUpdateHandler.ServeHTTP(w, r)
+		case WorkloadServiceWorkloadListProcedure:
+			workloadServiceWorkloadListHandler.ServeHTTP(w, r)
 		default:
 			http.NotFound(w, r)
 		}
@@ -165,3 +225,11 @@ func (UnimplementedWorkloadServiceHandler) WorkloadStatus(context.Context, *conn
 func (UnimplementedWorkloadServiceHandler) WorkloadStop(context.Context, *connect.Request[v2.WorkloadStopRequest]) (*connect.Response[v2.WorkloadStopResponse], error) {
 	return nil, connect.NewError(connect.CodeUnimplemented, errors.New("wasmcloud.runtime.v2.WorkloadService.WorkloadStop is not implemented"))
 }
+
+func (UnimplementedWorkloadServiceHandler) WorkloadUpdate(context.Context, *connect.Request[v2.WorkloadUpdateRequest]) (*connect.Response[v2.WorkloadUpdateResponse], error) {
+	return nil, connect.NewError(connect.CodeUnimplemented, errors.New("wasmcloud.runtime.v2.WorkloadService.WorkloadUpdate is not implemented"))
+}
+
+func (UnimplementedWorkloadServiceHandler) WorkloadList(context.Context, *connect.Request[v2.WorkloadListRequest]) (*connect.Response[v2.WorkloadListResponse], error) {
+	return nil, connect.NewError(connect.CodeUnimplemented, errors.New("wasmcloud.runtime.v2.WorkloadService.WorkloadList is not implemented"))
+}
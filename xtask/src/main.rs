@@ -93,7 +93,12 @@ const P2_FIXTURES: &[&str] = &[
     "http-ip-name-lookup",
     "keyvalue-counter",
     "keyvalue-implements",
+    "keyvalue-ttl",
     "postgres-implements",
+    "postgres-transaction",
+    "postgres-notifications",
+    "messaging-reply",
+    "wasi-messaging-echo",
 ];
 
 const P3_FIXTURES: &[&str] = &[
@@ -117,6 +122,7 @@ const P3_FIXTURES: &[&str] = &[
     "blobstore-default-p3",
     "keyvalue-implements-p3",
     "keyvalue-default-p3",
+    "keyvalue-watcher-p3",
     "postgres-stream-p3",
     "svc-counter",
     "svc-http-proxy",